@@ -0,0 +1,54 @@
+// Host side of zkputer's `Risc0Prover`: reads one proving request as JSON on stdin, proves it with
+// the guest in ../methods/guest, and writes the receipt location, image id, journal and cycle count
+// as JSON on stdout. Local or Bonsai proving is selected by the usual RISC0_PROVER / BONSAI_*
+// environment variables.
+use anyhow::{bail, Result};
+use risc0_zkvm::{default_prover, Digest, ExecutorEnv};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use zkputer_risc0_methods::{ZKPUTER_RISC0_GUEST_ELF, ZKPUTER_RISC0_GUEST_ID};
+
+#[derive(Deserialize)]
+struct ProveRequest {
+    public_inputs: Value,
+    witness: Value,
+    receipt_path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct ProveResponse {
+    receipt_path: PathBuf,
+    image_id: String,
+    journal: Value,
+    cycles: u64,
+}
+
+fn main() -> Result<()> {
+    let request: ProveRequest = serde_json::from_reader(std::io::stdin())?;
+    let env = ExecutorEnv::builder()
+        .write(&request.public_inputs.to_string())?
+        .write(&request.witness.to_string())?
+        .build()?;
+
+    let prove_info = default_prover().prove(env, ZKPUTER_RISC0_GUEST_ELF)?;
+    let receipt = prove_info.receipt;
+    receipt.verify(ZKPUTER_RISC0_GUEST_ID)?;
+
+    let journal: Value = serde_json::from_slice(&receipt.journal.bytes)?;
+    if journal != request.public_inputs {
+        bail!("guest committed an unexpected journal");
+    }
+    if let Some(dir) = request.receipt_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&request.receipt_path, bincode::serialize(&receipt)?)?;
+    let response = ProveResponse {
+        receipt_path: request.receipt_path,
+        image_id: format!("0x{}", hex::encode(Digest::from(ZKPUTER_RISC0_GUEST_ID).as_bytes())),
+        journal,
+        cycles: prove_info.stats.total_cycles,
+    };
+    println!("{}", serde_json::to_string(&response)?);
+    Ok(())
+}
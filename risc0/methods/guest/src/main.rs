@@ -0,0 +1,34 @@
+// Opens the claim hash and evidence root in a receipt's public inputs, with the same checks as the
+// SP1 guest in sp1/program. The checked public inputs are committed to the journal.
+use risc0_zkvm::guest::env;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+fn main() {
+    let public_inputs: String = env::read();
+    let witness: String = env::read();
+    let public_inputs: Value = serde_json::from_str(&public_inputs).expect("public inputs are JSON");
+    let witness: Value = serde_json::from_str(&witness).expect("witness is JSON");
+
+    assert_eq!(witness["hash_algorithm"], "sha256", "only sha256 commitments are supported");
+    let claim = &witness["claim_preimage"];
+    let evidence = &witness["evidence_preimage"];
+    assert_eq!(claim["claim_type"], public_inputs["claim_type"], "claim type mismatch");
+    assert_eq!(sha256_json(claim), public_inputs["claim_hash"], "claim hash does not open");
+    assert_eq!(sha256_json(evidence), public_inputs["evidence_root"], "evidence root does not open");
+
+    let leaves = evidence["leaves"].as_array().expect("evidence leaves");
+    for pair in leaves.windows(2) {
+        assert!(pair[0].as_str() <= pair[1].as_str(), "evidence leaves are not sorted");
+    }
+    for leaf in leaves {
+        let leaf = leaf.as_str().expect("leaf is a string");
+        assert!(leaf.starts_with("0x") && leaf.len() == 66, "leaf is not a 32-byte hash");
+    }
+
+    env::commit_slice(public_inputs.to_string().as_bytes());
+}
+
+fn sha256_json(value: &Value) -> String {
+    format!("0x{}", hex::encode(Sha256::digest(value.to_string().as_bytes())))
+}
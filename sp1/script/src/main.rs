@@ -0,0 +1,56 @@
+// Host side of zkputer's `Sp1Prover`: reads one proving request as JSON on stdin, proves it with
+// the guest in ../program, and writes the proof location, verifying key hash, committed public
+// values and cycle count as JSON on stdout. The prover network or local CPU prover is selected by
+// the usual SP1_PROVER environment variables.
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp1_sdk::{include_elf, HashableKey, ProverClient, SP1Stdin};
+use std::path::PathBuf;
+
+const ELF: &[u8] = include_elf!("zkputer-sp1-program");
+
+#[derive(Deserialize)]
+struct ProveRequest {
+    public_inputs: Value,
+    witness: Value,
+    proof_path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct ProveResponse {
+    proof_path: PathBuf,
+    vkey_hash: String,
+    public_values: Value,
+    cycles: u64,
+}
+
+fn main() -> Result<()> {
+    let request: ProveRequest = serde_json::from_reader(std::io::stdin())?;
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&request.public_inputs.to_string());
+    stdin.write(&request.witness.to_string());
+
+    let client = ProverClient::from_env();
+    let (_, report) = client.execute(ELF, &stdin).run()?;
+    let (pk, vk) = client.setup(ELF);
+    let proof = client.prove(&pk, &stdin).compressed().run()?;
+    client.verify(&proof, &vk)?;
+
+    let public_values: Value = serde_json::from_slice(proof.public_values.as_slice())?;
+    if public_values != request.public_inputs {
+        bail!("guest committed unexpected public values");
+    }
+    if let Some(dir) = request.proof_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    proof.save(&request.proof_path)?;
+    let response = ProveResponse {
+        proof_path: request.proof_path,
+        vkey_hash: vk.bytes32(),
+        public_values,
+        cycles: report.total_instruction_count(),
+    };
+    println!("{}", serde_json::to_string(&response)?);
+    Ok(())
+}
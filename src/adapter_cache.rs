@@ -0,0 +1,123 @@
+use crate::models::{hash_json, EvidenceBundle, ExecutionAck, ProofRequest, Venue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdapterCacheStats {
+    pub ttl_ms: u64,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+// What the venue said about one order: the acknowledgement and the evidence collected against it.
+#[derive(Debug, Clone)]
+pub struct AdapterResponse {
+    pub ack: ExecutionAck,
+    pub bundle: EvidenceBundle,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    // The rest of the request the responses were fetched for; a different claim, account or payload
+    // against the same order is a miss rather than someone else's evidence.
+    fingerprint: String,
+    response: AdapterResponse,
+    stored_at: Instant,
+}
+
+// Full acknowledge/collect results per (venue, order_ref), so retries and re-proves of the same order
+// within the staleness window don't go back to the venue. Only successful collections are cached;
+// refresh operations fetch fresh and overwrite the entry.
+#[derive(Debug, Clone)]
+pub struct AdapterCache {
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<(Venue, String), CachedResponse>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl AdapterCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn get(&self, request: &ProofRequest) -> Option<AdapterResponse> {
+        let mut entries = self.entries.lock().expect("adapter cache lock poisoned");
+        let key = (request.venue, request.order_ref.clone());
+        let fresh = entries
+            .get(&key)
+            .filter(|cached| {
+                cached.stored_at.elapsed() < self.ttl && cached.fingerprint == fingerprint(request)
+            })
+            .map(|cached| cached.response.clone());
+        match fresh {
+            Some(response) => {
+                self.hits.fetch_add(1, Ordering::SeqCst);
+                Some(response)
+            }
+            None => {
+                if entries
+                    .get(&key)
+                    .is_some_and(|cached| cached.stored_at.elapsed() >= self.ttl)
+                {
+                    entries.remove(&key);
+                }
+                self.misses.fetch_add(1, Ordering::SeqCst);
+                None
+            }
+        }
+    }
+
+    pub fn put(&self, request: &ProofRequest, ack: &ExecutionAck, bundle: &EvidenceBundle) {
+        let mut entries = self.entries.lock().expect("adapter cache lock poisoned");
+        entries.retain(|_, cached| cached.stored_at.elapsed() < self.ttl);
+        entries.insert(
+            (request.venue, request.order_ref.clone()),
+            CachedResponse {
+                fingerprint: fingerprint(request),
+                response: AdapterResponse {
+                    ack: ack.clone(),
+                    bundle: bundle.clone(),
+                },
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    pub fn invalidate(&self, venue: Venue, order_ref: &str) -> bool {
+        let mut entries = self.entries.lock().expect("adapter cache lock poisoned");
+        entries.remove(&(venue, order_ref.to_string())).is_some()
+    }
+
+    pub fn stats(&self) -> AdapterCacheStats {
+        let entries = self.entries.lock().expect("adapter cache lock poisoned");
+        AdapterCacheStats {
+            ttl_ms: self.ttl.as_millis() as u64,
+            entries: entries
+                .values()
+                .filter(|cached| cached.stored_at.elapsed() < self.ttl)
+                .count(),
+            hits: self.hits.load(Ordering::SeqCst),
+            misses: self.misses.load(Ordering::SeqCst),
+        }
+    }
+}
+
+// Verification mode and deadline only change what happens after collection, so they don't split entries.
+fn fingerprint(request: &ProofRequest) -> String {
+    hash_json(&serde_json::json!({
+        "claim_type": request.claim_type,
+        "account_ref": request.account_ref,
+        "execution_ref": request.execution_ref,
+        "payload": request.payload,
+    }))
+}
@@ -1,43 +1,204 @@
-use crate::models::{EvidenceBundle, ExecutionAck, ProofRequest, Venue};
+use crate::clock::ClockOffset;
+use crate::models::{
+    ClaimType, CorrectionKind, EvidenceBundle, ExecutionAck, ProofRequest, TradeCorrection, Venue,
+};
+use crate::references::ReferenceFormats;
 use anyhow::Result;
 use async_trait::async_trait;
-fn venue_slug(venue: Venue) -> &'static str {
-    match venue {
-        Venue::Hyperliquid => "hyperliquid",
-        Venue::Base => "base",
-        Venue::Solana => "solana",
-        Venue::Polymarket => "polymarket",
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum FinalitySemantics {
+    Instant,
+    Confirmations { depth: u32 },
+    VenueAttested,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterCapabilities {
+    pub adapter_id: String,
+    pub adapter_version: String,
+    pub claim_types: Vec<ClaimType>,
+    pub evidence_tags: Vec<String>,
+    pub finality: FinalitySemantics,
+    pub supports_batch: bool,
+    // The adapter can place orders itself (`VenueAdapter::submit_order`), not only observe them.
+    #[serde(default)]
+    pub supports_execution: bool,
+    // The adapter publishes trade corrections (`VenueAdapter::poll_corrections`).
+    #[serde(default)]
+    pub supports_corrections: bool,
+}
+
+impl AdapterCapabilities {
+    pub fn supports_claim(&self, claim_type: ClaimType) -> bool {
+        self.claim_types.contains(&claim_type)
     }
 }
 
 #[async_trait]
 pub trait VenueAdapter: Send + Sync {
     fn venue(&self) -> Venue;
+    fn capabilities(&self) -> AdapterCapabilities;
     async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck>;
-    async fn collect_evidence(&self, request: &ProofRequest, ack: &ExecutionAck) -> Result<EvidenceBundle>;
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle>;
+
+    // Places the order described by an ORDER_PLACED request and returns the venue's answer to that
+    // submission. Only adapters advertising `supports_execution` implement it.
+    async fn submit_order(&self, _request: &ProofRequest) -> Result<ExecutionAck> {
+        Err(anyhow::anyhow!(
+            "adapter for venue {:?} does not submit orders",
+            self.venue()
+        ))
+    }
+
+    // Corrections published and not yet acknowledged, so one an engine failed to act on comes round
+    // again. Only adapters advertising `supports_corrections` implement this and `ack_correction`.
+    async fn poll_corrections(&self) -> Result<Vec<TradeCorrection>> {
+        Err(anyhow::anyhow!(
+            "adapter for venue {:?} does not publish trade corrections",
+            self.venue()
+        ))
+    }
+
+    // Marks a correction as acted on, so later polls leave it out.
+    async fn ack_correction(&self, _correction_ref: &str) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "adapter for venue {:?} does not publish trade corrections",
+            self.venue()
+        ))
+    }
+
+    // Latest NTP offset measurements for the sources whose clocks stamp this adapter's evidence.
+    // Chain-derived timestamps are consensus time and need none.
+    fn clock_offsets(&self) -> Vec<ClockOffset> {
+        Vec::new()
+    }
+
+    // Formats the engine checks order and execution refs against before accepting a request.
+    fn reference_formats(&self) -> Option<ReferenceFormats> {
+        None
+    }
 
     async fn build_statement(
         &self,
         request: &ProofRequest,
         ack: &ExecutionAck,
-        _bundle: &EvidenceBundle,
+        bundle: &EvidenceBundle,
     ) -> Result<String> {
-        let statement = if request.claim_type == crate::models::ClaimType::ORDER_PLACED {
-            format!(
+        let statement = match request.claim_type {
+            crate::models::ClaimType::ORDER_PLACED => format!(
                 "Order {} for account {} was accepted on venue {} at {}.",
                 request.order_ref,
                 request.account_ref,
-                venue_slug(request.venue),
+                request.venue.slug(),
                 ack.accepted_at
-            )
-        } else {
-            format!(
+            ),
+            crate::models::ClaimType::TRADE_EXECUTED => format!(
                 "Order {} for account {} was executed on venue {} with execution ref {}.",
                 request.order_ref,
                 request.account_ref,
-                venue_slug(request.venue),
+                request.venue.slug(),
                 request.execution_ref.clone().unwrap_or_else(|| "UNKNOWN".to_string())
-            )
+            ),
+            crate::models::ClaimType::BEST_EXECUTION => {
+                let price = bundle
+                    .price_observation
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("best execution statement requires a reference price quote"))?;
+                format!(
+                    "Order {} for account {} was executed on venue {} with execution ref {} at price {}, within {} bps of reference price {} from {} observed at {}.",
+                    request.order_ref,
+                    request.account_ref,
+                    request.venue.slug(),
+                    request.execution_ref.clone().unwrap_or_else(|| "UNKNOWN".to_string()),
+                    price.execution_price,
+                    price.tolerance_bps,
+                    price.reference_price,
+                    price.reference_source,
+                    price.reference_observed_at
+                )
+            }
+            crate::models::ClaimType::TRADE_BUSTED | crate::models::ClaimType::TRADE_AMENDED => {
+                let correction = bundle
+                    .correction
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("correction statement requires a venue correction artifact"))?;
+                let outcome = match &correction.kind {
+                    CorrectionKind::Busted => "was busted".to_string(),
+                    CorrectionKind::Amended {
+                        amended_price,
+                        amended_size,
+                    } => format!(
+                        "was amended to price {} and size {}",
+                        amended_price.map_or_else(|| "unchanged".to_string(), |p| p.to_string()),
+                        amended_size.map_or_else(|| "unchanged".to_string(), |s| s.to_string())
+                    ),
+                };
+                format!(
+                    "Execution {} of order {} for account {} on venue {} {} by venue correction {} observed at {}.",
+                    correction.execution_ref,
+                    request.order_ref,
+                    request.account_ref,
+                    request.venue.slug(),
+                    outcome,
+                    correction.correction_ref,
+                    correction.observed_at
+                )
+            }
+            crate::models::ClaimType::ORDER_REJECTED => format!(
+                "Order {} for account {} was rejected on venue {} at {} with reason \"{}\" (order hash {}).",
+                request.order_ref,
+                request.account_ref,
+                request.venue.slug(),
+                ack.accepted_at,
+                crate::templates::rejection_reason(ack)?,
+                request.order_hash()
+            ),
+            crate::models::ClaimType::ORDER_CANCELLED => {
+                let cancellation = bundle
+                    .cancellation
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("cancellation statement requires a venue cancellation artifact"))?;
+                format!(
+                    "Order {} for account {} was cancelled on venue {} at {} by venue cancellation {}.",
+                    request.order_ref,
+                    request.account_ref,
+                    request.venue.slug(),
+                    cancellation.cancelled_at,
+                    cancellation.cancellation_ref
+                )
+            }
+            crate::models::ClaimType::POSITION_CLOSED => {
+                let (closure, commitment) = crate::templates::position_pnl(request, bundle)?;
+                format!(
+                    "Position {} for account {} was closed on venue {} at {} by order {} (venue closure {}), realizing PnL under commitment {}.",
+                    closure.position_ref,
+                    request.account_ref,
+                    request.venue.slug(),
+                    closure.closed_at,
+                    request.order_ref,
+                    closure.closure_ref,
+                    commitment
+                )
+            }
+            crate::models::ClaimType::BALANCE_AT_TIMESTAMP => {
+                let (snapshot, range) = crate::templates::balance_range(request, bundle)?;
+                format!(
+                    "Account {} held at least {} {} on venue {} as of {} (venue balance snapshot {}).",
+                    request.account_ref,
+                    range.min_balance,
+                    range.asset,
+                    request.venue.slug(),
+                    snapshot.as_of,
+                    snapshot.snapshot_ref
+                )
+            }
         };
         Ok(statement)
     }
@@ -0,0 +1,556 @@
+use crate::adapters::base::{AdapterCapabilities, FinalitySemantics, VenueAdapter};
+use crate::adapters::evm_encoding::{
+    field, header_hash, header_rlp, hex_bytes, keccak, ordered_trie_root, quantity,
+    receipt_encoding, to_hex,
+};
+use crate::adapters::json_rpc::{HttpJsonRpc, JsonRpc};
+use crate::finality::ChainHeadSource;
+use crate::hashing::HashAlgorithm;
+use crate::models::{
+    hash_bytes, hash_json, now_iso, ClaimType, CollectionRecord, EvidenceBundle, EvidenceItem,
+    ExecutionAck, ProofRequest, RawArtifact, Venue,
+};
+use crate::references::ReferenceFormats;
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+const BASE_EVM_ADAPTER_VERSION: &str = "0.1.0";
+
+pub struct EvmChainHead {
+    rpc: Arc<dyn JsonRpc>,
+}
+
+impl EvmChainHead {
+    pub fn new(rpc: Arc<dyn JsonRpc>) -> Self {
+        Self { rpc }
+    }
+}
+
+#[async_trait]
+impl ChainHeadSource for EvmChainHead {
+    async fn head(&self) -> Result<u64> {
+        block_number(self.rpc.as_ref()).await
+    }
+}
+
+async fn block_number(rpc: &dyn JsonRpc) -> Result<u64> {
+    let head = rpc.call("eth_blockNumber", json!([])).await?;
+    quantity(&json!({ "head": head }), "head")
+}
+
+// What the adapter fetched for one transaction, before any of it is trusted.
+struct ChainView {
+    receipt: Value,
+    block: Value,
+    block_receipts: Vec<Value>,
+    head: u64,
+}
+
+// Collects Base evidence from an Ethereum JSON-RPC node. The order (or execution) ref is the
+// transaction hash. Every answer is checked locally: the block header must hash to the block hash,
+// the block's receipts must rebuild its `receiptsRoot`, and the transaction's receipt must be the
+// one at its index in that trie.
+pub struct BaseEvmAdapter {
+    rpc: Arc<dyn JsonRpc>,
+    confirmations: u32,
+}
+
+impl BaseEvmAdapter {
+    pub fn new(rpc: Arc<dyn JsonRpc>) -> Self {
+        Self {
+            rpc,
+            confirmations: 12,
+        }
+    }
+
+    pub fn connect(url: &str) -> Result<Self> {
+        Ok(Self::new(Arc::new(HttpJsonRpc::new(url)?)))
+    }
+
+    pub fn with_confirmations(mut self, confirmations: u32) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    pub fn chain_head(&self) -> EvmChainHead {
+        EvmChainHead::new(self.rpc.clone())
+    }
+
+    fn tx_hash(request: &ProofRequest) -> Result<&str> {
+        let tx_hash = match request.claim_type {
+            ClaimType::ORDER_PLACED => request.order_ref.as_str(),
+            _ => request
+                .execution_ref
+                .as_deref()
+                .unwrap_or(&request.order_ref),
+        };
+        if hex_bytes(tx_hash).map_or(true, |b| b.len() != 32) || !tx_hash.starts_with("0x") {
+            bail!("{} is not a 0x-prefixed transaction hash", tx_hash);
+        }
+        Ok(tx_hash)
+    }
+
+    async fn receipt(&self, tx_hash: &str) -> Result<Value> {
+        let receipt = self
+            .rpc
+            .call("eth_getTransactionReceipt", json!([tx_hash]))
+            .await?;
+        if receipt.is_null() {
+            bail!("transaction {} has no receipt yet", tx_hash);
+        }
+        Ok(receipt)
+    }
+
+    async fn block(&self, block_hash: &str) -> Result<Value> {
+        let block = self
+            .rpc
+            .call("eth_getBlockByHash", json!([block_hash, false]))
+            .await?;
+        if block.is_null() {
+            bail!("block {} is unknown to the node", block_hash);
+        }
+        Ok(block)
+    }
+
+    async fn view(&self, tx_hash: &str) -> Result<ChainView> {
+        let receipt = self.receipt(tx_hash).await?;
+        let block = self.block(field(&receipt, "blockHash")?).await?;
+        let block_receipts = self
+            .rpc
+            .call("eth_getBlockReceipts", json!([field(&block, "number")?]))
+            .await?
+            .as_array()
+            .cloned()
+            .ok_or_else(|| anyhow!("eth_getBlockReceipts returned no receipts"))?;
+        let head = block_number(self.rpc.as_ref()).await?;
+        Ok(ChainView {
+            receipt,
+            block,
+            block_receipts,
+            head,
+        })
+    }
+
+    fn collection_record(
+        &self,
+        item: &EvidenceItem,
+        method: &str,
+        params: Value,
+    ) -> CollectionRecord {
+        CollectionRecord {
+            artifact_hash: item.artifact_hash.clone(),
+            endpoint_url: self.rpc.endpoint(),
+            request_hash: hash_json(&json!({ "method": method, "params": params })),
+            adapter_version: BASE_EVM_ADAPTER_VERSION.to_string(),
+            client_ip: None,
+            client_region: None,
+            collected_at: now_iso(),
+            hashed_fields: vec![],
+        }
+    }
+}
+
+fn block_time(block: &Value) -> Result<String> {
+    let seconds = quantity(block, "timestamp")?;
+    chrono::DateTime::from_timestamp(seconds as i64, 0)
+        .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        .ok_or_else(|| anyhow!("block timestamp {} is out of range", seconds))
+}
+
+fn tags(names: &[&str]) -> Vec<String> {
+    names.iter().map(|t| t.to_string()).collect()
+}
+
+#[async_trait]
+impl VenueAdapter for BaseEvmAdapter {
+    fn venue(&self) -> Venue {
+        Venue::Base
+    }
+
+    fn reference_formats(&self) -> Option<ReferenceFormats> {
+        Some(ReferenceFormats::for_venue(Venue::Base))
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities {
+            adapter_id: "base-evm".to_string(),
+            adapter_version: BASE_EVM_ADAPTER_VERSION.to_string(),
+            claim_types: vec![ClaimType::ORDER_PLACED, ClaimType::TRADE_EXECUTED],
+            evidence_tags: tags(&[
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+                "execution_identity",
+                "execution_timestamp",
+                "execution_artifact",
+            ]),
+            finality: FinalitySemantics::Confirmations {
+                depth: self.confirmations,
+            },
+            supports_batch: false,
+            supports_execution: false,
+            supports_corrections: false,
+        }
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        let tx_hash = Self::tx_hash(request)?;
+        let receipt = self.receipt(tx_hash).await?;
+        let block = self.block(field(&receipt, "blockHash")?).await?;
+        let encoded = receipt_encoding(&receipt)?;
+        Ok(ExecutionAck {
+            accepted: quantity(&receipt, "status")? == 1,
+            venue_order_ref: request.order_ref.clone(),
+            acceptance_artifact_ref: format!(
+                "base://tx/{}?from={}",
+                tx_hash,
+                field(&receipt, "from")?
+            ),
+            acceptance_artifact_hash: hash_bytes(&encoded),
+            accepted_at: block_time(&block)?,
+            rejection_reason: None,
+        })
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        let tx_hash = Self::tx_hash(request)?;
+        let view = self.view(tx_hash).await?;
+        let mut conflicts = Vec::new();
+
+        let block_hash = field(&view.block, "hash")?;
+        if header_hash(&view.block)? != block_hash.to_ascii_lowercase() {
+            conflicts.push("block_header_hash_mismatch".to_string());
+        }
+        let encoded: Vec<Vec<u8>> = view
+            .block_receipts
+            .iter()
+            .map(receipt_encoding)
+            .collect::<Result<_>>()?;
+        let receipts_root = field(&view.block, "receiptsRoot")?;
+        if ordered_trie_root(&encoded) != receipts_root.to_ascii_lowercase() {
+            conflicts.push("receipts_root_mismatch".to_string());
+        }
+        let index = quantity(&view.receipt, "transactionIndex")? as usize;
+        let own = receipt_encoding(&view.receipt)?;
+        if encoded.get(index) != Some(&own) || field(&view.receipt, "blockHash")? != block_hash {
+            conflicts.push("receipt_not_in_block".to_string());
+        }
+        let succeeded = quantity(&view.receipt, "status")? == 1;
+        if !succeeded || !ack.accepted {
+            conflicts.push("transaction_reverted".to_string());
+        }
+
+        let number = quantity(&view.block, "number")?;
+        let block_at = block_time(&view.block)?;
+        let header = header_rlp(&view.block)?;
+        let receipt_item = EvidenceItem {
+            source_id: "base-rpc-receipt".to_string(),
+            source_kind: "canonical_chain_state".to_string(),
+            artifact_ref: ack.acceptance_artifact_ref.clone(),
+            artifact_hash: hash_bytes(&own),
+            observed_at: block_at.clone(),
+            tags: tags(&[
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+            ]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: own.len() as u64,
+        };
+        let header_item = EvidenceItem {
+            source_id: "base-rpc-header".to_string(),
+            source_kind: "canonical_chain_state".to_string(),
+            artifact_ref: format!("base://block/{}/header", number),
+            artifact_hash: hash_bytes(&header),
+            observed_at: block_at,
+            tags: tags(&["execution_timestamp"]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: header.len() as u64,
+        };
+        let inclusion = json!({
+            "block_hash": block_hash,
+            "receipts_root": receipts_root,
+            "transaction_index": index,
+            "receipt_count": encoded.len(),
+            "receipt_keccak": to_hex(&keccak(&own)),
+        });
+        let inclusion_item = EvidenceItem {
+            source_id: "base-rpc-inclusion".to_string(),
+            source_kind: "canonical_chain_state".to_string(),
+            artifact_ref: format!("base://block/{}/receipts/{}", number, index),
+            artifact_hash: hash_json(&inclusion),
+            observed_at: now_iso(),
+            tags: tags(&["execution_identity", "execution_artifact"]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: serde_json::to_vec(&inclusion).map_or(0, |b| b.len() as u64),
+        };
+        let raw_artifacts = vec![
+            RawArtifact::of(&receipt_item, own.clone()),
+            RawArtifact::of(&header_item, header.clone()),
+            RawArtifact::of(&inclusion_item, serde_json::to_vec(&inclusion)?),
+        ];
+
+        let mut observed_tags: HashSet<String> = receipt_item.tags.iter().cloned().collect();
+        // A trade needs a log from the venue contract when the request names one.
+        let contract = request.payload.get("contract").and_then(|v| v.as_str());
+        let emitted = contract.is_none_or(|contract| {
+            view.receipt
+                .get("logs")
+                .and_then(|v| v.as_array())
+                .is_some_and(|logs| {
+                    logs.iter().any(|log| {
+                        field(log, "address").is_ok_and(|a| a.eq_ignore_ascii_case(contract))
+                    })
+                })
+        });
+        if request.claim_type == ClaimType::TRADE_EXECUTED && emitted {
+            observed_tags.extend(header_item.tags.iter().cloned());
+            observed_tags.extend(inclusion_item.tags.iter().cloned());
+        }
+
+        let depth = view.head.saturating_sub(number) + 1;
+        let collection = vec![
+            self.collection_record(&receipt_item, "eth_getTransactionReceipt", json!([tx_hash])),
+            self.collection_record(
+                &header_item,
+                "eth_getBlockByHash",
+                json!([block_hash, false]),
+            ),
+            self.collection_record(
+                &inclusion_item,
+                "eth_getBlockReceipts",
+                json!([field(&view.block, "number")?]),
+            ),
+        ];
+        Ok(EvidenceBundle {
+            items: vec![receipt_item, header_item, inclusion_item],
+            observed_tags,
+            conflicts,
+            finality_observed_at: (depth >= u64::from(self.confirmations)).then(now_iso),
+            inclusion_height: Some(number),
+            confirmations: Some(depth),
+            commitment: None,
+            price_observation: None,
+            correction: None,
+            cancellation: None,
+            position_closure: None,
+            balance_snapshot: None,
+            collection,
+            order_book: None,
+            raw_artifacts,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct SyntheticChainState {
+    blocks: Vec<Value>,
+    receipts: Vec<Vec<Value>>,
+    transactions: u64,
+}
+
+// An in-memory chain that answers the JSON-RPC calls `BaseEvmAdapter` makes with self-consistent
+// headers and receipts. Like a Base block, each block opens with an L1-info deposit transaction.
+#[derive(Debug, Default)]
+pub struct SyntheticEvmChain {
+    state: Mutex<SyntheticChainState>,
+}
+
+pub struct SyntheticLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+}
+
+impl SyntheticEvmChain {
+    pub fn new() -> Self {
+        let chain = Self::default();
+        chain.mine(vec![]);
+        chain
+    }
+
+    // Includes one transaction in a new block and returns its hash.
+    pub fn submit_transaction(
+        &self,
+        from: &str,
+        logs: Vec<SyntheticLog>,
+        succeeded: bool,
+    ) -> String {
+        let tx_hash = {
+            let mut state = self.lock();
+            state.transactions += 1;
+            to_hex(&keccak(
+                format!("{}:{}", from, state.transactions).as_bytes(),
+            ))
+        };
+        self.mine(vec![(tx_hash.clone(), from.to_string(), logs, succeeded)]);
+        tx_hash
+    }
+
+    pub fn mine_empty(&self, blocks: u64) {
+        for _ in 0..blocks {
+            self.mine(vec![]);
+        }
+    }
+
+    // Rewrites a field of a stored receipt without touching the block that committed to it.
+    pub fn tamper_receipt(&self, tx_hash: &str, name: &str, value: Value) {
+        let mut state = self.lock();
+        for receipt in state.receipts.iter_mut().flatten() {
+            if receipt.get("transactionHash").and_then(|v| v.as_str()) == Some(tx_hash) {
+                receipt[name] = value.clone();
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SyntheticChainState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn mine(&self, transactions: Vec<(String, String, Vec<SyntheticLog>, bool)>) {
+        let mut state = self.lock();
+        let number = state.blocks.len() as u64;
+        let zero_bloom = format!("0x{}", "00".repeat(256));
+        let deposit_hash = to_hex(&keccak(format!("l1-info:{}", number).as_bytes()));
+        let mut receipts = vec![json!({
+            "type": "0x7e",
+            "transactionHash": deposit_hash,
+            "transactionIndex": "0x0",
+            "from": "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001",
+            "to": "0x4200000000000000000000000000000000000015",
+            "status": "0x1",
+            "cumulativeGasUsed": "0xb71b",
+            "gasUsed": "0xb71b",
+            "logsBloom": zero_bloom,
+            "logs": [],
+            "depositNonce": format!("0x{:x}", number),
+            "depositReceiptVersion": "0x1",
+        })];
+        for (tx_hash, from, logs, succeeded) in transactions {
+            let index = receipts.len();
+            let logs: Vec<Value> = logs
+                .into_iter()
+                .map(
+                    |log| json!({ "address": log.address, "topics": log.topics, "data": log.data }),
+                )
+                .collect();
+            receipts.push(json!({
+                "type": "0x2",
+                "transactionHash": tx_hash,
+                "transactionIndex": format!("0x{:x}", index),
+                "from": from,
+                "status": if succeeded { "0x1" } else { "0x0" },
+                "cumulativeGasUsed": format!("0x{:x}", 0xb71b + 0x5208 * index as u64),
+                "gasUsed": "0x5208",
+                "logsBloom": zero_bloom,
+                "logs": logs,
+            }));
+        }
+        let encoded: Vec<Vec<u8>> = receipts
+            .iter()
+            .filter_map(|r| receipt_encoding(r).ok())
+            .collect();
+        let tx_hashes: Vec<Vec<u8>> = receipts
+            .iter()
+            .filter_map(|r| {
+                r.get("transactionHash")
+                    .and_then(|v| v.as_str())
+                    .and_then(|h| hex_bytes(h).ok())
+            })
+            .collect();
+        let parent_hash = state
+            .blocks
+            .last()
+            .and_then(|b| b.get("hash").and_then(|v| v.as_str()).map(str::to_string))
+            .unwrap_or_else(|| format!("0x{}", "00".repeat(32)));
+        let mut block = json!({
+            "parentHash": parent_hash,
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": "0x4200000000000000000000000000000000000011",
+            "stateRoot": to_hex(&keccak(format!("state:{}", number).as_bytes())),
+            "transactionsRoot": ordered_trie_root(&tx_hashes),
+            "receiptsRoot": ordered_trie_root(&encoded),
+            "logsBloom": zero_bloom,
+            "difficulty": "0x0",
+            "number": format!("0x{:x}", number),
+            "gasLimit": "0x3938700",
+            "gasUsed": format!("0x{:x}", 0xb71b + 0x5208 * (receipts.len() as u64 - 1)),
+            "timestamp": format!("0x{:x}", 1_760_000_000 + 2 * number),
+            "extraData": "0x",
+            "mixHash": to_hex(&keccak(format!("prevrandao:{}", number).as_bytes())),
+            "nonce": "0x0000000000000000",
+            "baseFeePerGas": "0xf4240",
+            "withdrawalsRoot": ordered_trie_root(&[]),
+            "blobGasUsed": "0x0",
+            "excessBlobGas": "0x0",
+            "parentBeaconBlockRoot": to_hex(&keccak(format!("beacon:{}", number).as_bytes())),
+        });
+        let hash = header_hash(&block).unwrap_or_default();
+        block["hash"] = json!(hash);
+        for receipt in &mut receipts {
+            receipt["blockHash"] = json!(hash);
+            receipt["blockNumber"] = json!(format!("0x{:x}", number));
+        }
+        state.blocks.push(block);
+        state.receipts.push(receipts);
+    }
+}
+
+#[async_trait]
+impl JsonRpc for SyntheticEvmChain {
+    fn endpoint(&self) -> String {
+        "synthetic://base".to_string()
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let state = self.lock();
+        let param = |idx: usize| {
+            params
+                .get(idx)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+        };
+        match method {
+            "eth_blockNumber" => Ok(json!(format!(
+                "0x{:x}",
+                state.blocks.len().saturating_sub(1)
+            ))),
+            "eth_getTransactionReceipt" => {
+                let tx_hash = param(0);
+                Ok(state
+                    .receipts
+                    .iter()
+                    .flatten()
+                    .find(|r| {
+                        r.get("transactionHash").and_then(|v| v.as_str()) == Some(tx_hash.as_str())
+                    })
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }
+            "eth_getBlockByHash" => {
+                let block_hash = param(0);
+                Ok(state
+                    .blocks
+                    .iter()
+                    .find(|b| b.get("hash").and_then(|v| v.as_str()) == Some(block_hash.as_str()))
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }
+            "eth_getBlockReceipts" => {
+                let number = quantity(&json!({ "number": param(0) }), "number")? as usize;
+                Ok(state.receipts.get(number).map_or(Value::Null, |r| json!(r)))
+            }
+            _ => bail!("synthetic chain does not implement {}", method),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::hashing::HashAlgorithm;
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+// Consensus encodings needed to check JSON-RPC answers locally: RLP, block header hashes, typed
+// transaction receipts, and the ordered Merkle-Patricia trie behind `receiptsRoot`.
+
+pub(crate) fn keccak(input: &[u8]) -> [u8; 32] {
+    HashAlgorithm::Keccak256.digest_bytes(input)
+}
+
+pub(crate) fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_length_prefix(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+pub(crate) fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = rlp_length_prefix(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+fn rlp_length_prefix(len: usize, offset: u8) -> Vec<u8> {
+    if len <= 55 {
+        return vec![offset + len as u8];
+    }
+    let len_bytes = trim_leading_zeros(&(len as u64).to_be_bytes());
+    let mut out = vec![offset + 55 + len_bytes.len() as u8];
+    out.extend(len_bytes);
+    out
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+pub(crate) fn hex_bytes(raw: &str) -> Result<Vec<u8>> {
+    let digits = raw.strip_prefix("0x").unwrap_or(raw);
+    let padded = if digits.len() % 2 == 1 {
+        format!("0{}", digits)
+    } else {
+        digits.to_string()
+    };
+    hex::decode(padded).map_err(|_| anyhow!("invalid hex value {}", raw))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub(crate) fn field<'a>(value: &'a Value, name: &str) -> Result<&'a str> {
+    value
+        .get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("JSON-RPC object is missing {}", name))
+}
+
+pub(crate) fn quantity(value: &Value, name: &str) -> Result<u64> {
+    let bytes = quantity_bytes(field(value, name)?)?;
+    if bytes.len() > 8 {
+        bail!("{} does not fit in 64 bits", name);
+    }
+    Ok(bytes.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+}
+
+// Quantities are RLP-encoded as minimal big-endian integers, so zero is the empty string.
+fn quantity_bytes(raw: &str) -> Result<Vec<u8>> {
+    Ok(trim_leading_zeros(&hex_bytes(raw)?))
+}
+
+fn rlp_data_field(value: &Value, name: &str) -> Result<Vec<u8>> {
+    Ok(rlp_bytes(&hex_bytes(field(value, name)?)?))
+}
+
+fn rlp_quantity_field(value: &Value, name: &str) -> Result<Vec<u8>> {
+    Ok(rlp_bytes(&quantity_bytes(field(value, name)?)?))
+}
+
+// Fields after `nonce` were added by later forks and are present only on blocks that have them.
+const HEADER_DATA_FIELDS: [&str; 7] = [
+    "parentHash",
+    "sha3Uncles",
+    "miner",
+    "stateRoot",
+    "transactionsRoot",
+    "receiptsRoot",
+    "logsBloom",
+];
+const HEADER_QUANTITY_FIELDS: [&str; 5] =
+    ["difficulty", "number", "gasLimit", "gasUsed", "timestamp"];
+const HEADER_FORK_FIELDS: [(&str, bool); 6] = [
+    ("baseFeePerGas", true),
+    ("withdrawalsRoot", false),
+    ("blobGasUsed", true),
+    ("excessBlobGas", true),
+    ("parentBeaconBlockRoot", false),
+    ("requestsHash", false),
+];
+
+pub(crate) fn header_rlp(block: &Value) -> Result<Vec<u8>> {
+    let mut items = Vec::with_capacity(21);
+    for name in HEADER_DATA_FIELDS {
+        items.push(rlp_data_field(block, name)?);
+    }
+    for name in HEADER_QUANTITY_FIELDS {
+        items.push(rlp_quantity_field(block, name)?);
+    }
+    items.push(rlp_data_field(block, "extraData")?);
+    items.push(rlp_data_field(block, "mixHash")?);
+    items.push(rlp_data_field(block, "nonce")?);
+    for (name, is_quantity) in HEADER_FORK_FIELDS {
+        if block.get(name).is_none_or(Value::is_null) {
+            break;
+        }
+        items.push(if is_quantity {
+            rlp_quantity_field(block, name)?
+        } else {
+            rlp_data_field(block, name)?
+        });
+    }
+    Ok(rlp_list(&items))
+}
+
+pub(crate) fn header_hash(block: &Value) -> Result<String> {
+    Ok(to_hex(&keccak(&header_rlp(block)?)))
+}
+
+// EIP-2718 receipt encoding: legacy receipts are the bare RLP list, typed ones are prefixed with
+// their type byte. OP-stack deposit receipts append the deposit nonce and receipt version.
+pub(crate) fn receipt_encoding(receipt: &Value) -> Result<Vec<u8>> {
+    let tx_type = match receipt.get("type").and_then(|v| v.as_str()) {
+        Some(raw) => quantity_bytes(raw)?.first().copied().unwrap_or(0),
+        None => 0,
+    };
+    let logs = receipt
+        .get("logs")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("receipt is missing logs"))?;
+    let mut encoded_logs = Vec::with_capacity(logs.len());
+    for log in logs {
+        let topics = log
+            .get("topics")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("log is missing topics"))?
+            .iter()
+            .map(|t| {
+                Ok(rlp_bytes(&hex_bytes(
+                    t.as_str().ok_or_else(|| anyhow!("topic is not a string"))?,
+                )?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        encoded_logs.push(rlp_list(&[
+            rlp_data_field(log, "address")?,
+            rlp_list(&topics),
+            rlp_data_field(log, "data")?,
+        ]));
+    }
+    let mut items = vec![
+        rlp_quantity_field(receipt, "status")?,
+        rlp_quantity_field(receipt, "cumulativeGasUsed")?,
+        rlp_data_field(receipt, "logsBloom")?,
+        rlp_list(&encoded_logs),
+    ];
+    for name in ["depositNonce", "depositReceiptVersion"] {
+        if receipt.get(name).is_some_and(|v| !v.is_null()) {
+            items.push(rlp_quantity_field(receipt, name)?);
+        }
+    }
+    let body = rlp_list(&items);
+    Ok(if tx_type == 0 {
+        body
+    } else {
+        [vec![tx_type], body].concat()
+    })
+}
+
+// Root of the trie keyed by `rlp(index)`, as used for a block's transactions and receipts.
+pub(crate) fn ordered_trie_root(values: &[Vec<u8>]) -> String {
+    let mut entries: Vec<(Vec<u8>, &[u8])> = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            (
+                nibbles(&rlp_bytes(&trim_leading_zeros(&(i as u64).to_be_bytes()))),
+                v.as_slice(),
+            )
+        })
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    trie_root(&entries)
+}
+
+pub(crate) fn trie_root(entries: &[(Vec<u8>, &[u8])]) -> String {
+    if entries.is_empty() {
+        return to_hex(&keccak(&rlp_bytes(&[])));
+    }
+    to_hex(&keccak(&trie_node(entries, 0)))
+}
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+fn hex_prefix(path: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 2 } else { 0 } + (path.len() % 2) as u8;
+    let mut out = Vec::with_capacity(path.len() / 2 + 1);
+    let rest = if path.len() % 2 == 1 {
+        out.push((flag << 4) | path[0]);
+        &path[1..]
+    } else {
+        out.push(flag << 4);
+        path
+    };
+    out.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+// `entries` are sorted, distinct, and share their first `depth` nibbles.
+fn trie_node(entries: &[(Vec<u8>, &[u8])], depth: usize) -> Vec<u8> {
+    if let [(key, value)] = entries {
+        return rlp_list(&[
+            rlp_bytes(&hex_prefix(&key[depth..], true)),
+            rlp_bytes(value),
+        ]);
+    }
+    let shared = entries
+        .iter()
+        .map(|(key, _)| &key[depth..])
+        .reduce(|a, b| &a[..a.iter().zip(b).take_while(|(x, y)| x == y).count()])
+        .map_or(0, <[u8]>::len);
+    if shared > 0 {
+        let path = &entries[0].0[depth..depth + shared];
+        let child = trie_node(entries, depth + shared);
+        return rlp_list(&[rlp_bytes(&hex_prefix(path, false)), node_ref(child)]);
+    }
+    let mut slots = vec![rlp_bytes(&[]); 17];
+    let mut rest = entries;
+    if rest[0].0.len() == depth {
+        slots[16] = rlp_bytes(rest[0].1);
+        rest = &rest[1..];
+    }
+    while let Some((key, _)) = rest.first() {
+        let nibble = key[depth];
+        let end = rest
+            .iter()
+            .position(|(k, _)| k[depth] != nibble)
+            .unwrap_or(rest.len());
+        slots[nibble as usize] = node_ref(trie_node(&rest[..end], depth + 1));
+        rest = &rest[end..];
+    }
+    rlp_list(&slots)
+}
+
+// Children shorter than a hash are embedded in their parent instead of referenced by hash.
+fn node_ref(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        rlp_bytes(&keccak(&node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_and_known_tries_match_reference_roots() {
+        assert_eq!(
+            ordered_trie_root(&[]),
+            "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+        let pairs: Vec<(Vec<u8>, &[u8])> = [
+            ("do", "verb"),
+            ("dog", "puppy"),
+            ("doge", "coin"),
+            ("horse", "stallion"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (nibbles(k.as_bytes()), v.as_bytes()))
+        .collect();
+        assert_eq!(
+            trie_root(&pairs),
+            "0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"
+        );
+    }
+
+    #[test]
+    fn mainnet_genesis_header_hashes_to_its_block_hash() {
+        let zero32 = format!("0x{}", "00".repeat(32));
+        let genesis = serde_json::json!({
+            "parentHash": zero32,
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": format!("0x{}", "00".repeat(20)),
+            "stateRoot": "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x400000000",
+            "number": "0x0",
+            "gasLimit": "0x1388",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "extraData": "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            "mixHash": zero32,
+            "nonce": "0x0000000000000042"
+        });
+        assert_eq!(
+            header_hash(&genesis).expect("hash"),
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::journal::{ObservationJournal, VenueExchange};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const MAX_RPC_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+#[async_trait]
+pub trait JsonRpc: Send + Sync {
+    // Where calls go, without any path or credentials, for collection records.
+    fn endpoint(&self) -> String;
+    async fn call(&self, method: &str, params: Value) -> Result<Value>;
+}
+
+// A plain HTTP/1.1 endpoint, one connection per request. TLS endpoints need a local
+// TLS-terminating proxy (or a service on the same host).
+pub(crate) struct PlainHttp {
+    label: &'static str,
+    authority: String,
+    path: String,
+    timeout: Duration,
+    journal: Option<ObservationJournal>,
+}
+
+impl PlainHttp {
+    pub(crate) fn new(label: &'static str, url: &str) -> Result<Self> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            anyhow!(
+                "only http:// {} endpoints are supported, got {}",
+                label,
+                url
+            )
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], rest[idx..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        if authority.is_empty() {
+            bail!("{} url {} has no host", label, url);
+        }
+        let authority = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{}:80", authority)
+        };
+        Ok(Self {
+            label,
+            authority,
+            path: path.to_string(),
+            timeout: Duration::from_secs(10),
+            journal: None,
+        })
+    }
+
+    pub(crate) fn endpoint(&self) -> String {
+        format!("http://{}", self.authority)
+    }
+
+    pub(crate) fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub(crate) fn set_journal(&mut self, journal: ObservationJournal) {
+        self.journal = Some(journal);
+    }
+
+    // `suffix` is appended to the url's path; the body, if any, is sent as JSON. With a journal,
+    // every response is recorded before it is parsed, and one the journal cannot record is not used.
+    pub(crate) async fn send(
+        &self,
+        method: &str,
+        suffix: &str,
+        body: Option<&[u8]>,
+    ) -> Result<Vec<u8>> {
+        let response = tokio::time::timeout(self.timeout, self.exchange(method, suffix, body))
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "{} request timed out after {} ms",
+                    self.label,
+                    self.timeout.as_millis()
+                )
+            })??;
+        let parsed = self.parse(&response);
+        if let Some(journal) = &self.journal {
+            journal.record(VenueExchange {
+                source: self.label,
+                endpoint: &self.endpoint(),
+                method,
+                path: suffix,
+                request: body,
+                status: parsed.as_ref().ok().map(|(status, _, _)| status.as_str()),
+                response: &response,
+            })?;
+        }
+        let (status, chunked, body) = parsed?;
+        if status != "200" {
+            bail!(
+                "{} endpoint {} answered HTTP {}",
+                self.label,
+                self.authority,
+                status
+            );
+        }
+        if chunked {
+            return dechunk(body);
+        }
+        Ok(body.to_vec())
+    }
+
+    async fn exchange(&self, method: &str, suffix: &str, body: Option<&[u8]>) -> Result<Vec<u8>> {
+        let mut stream = TcpStream::connect(&self.authority)
+            .await
+            .with_context(|| format!("failed to connect to {}", self.authority))?;
+        let path = match format!("{}{}", self.path, suffix) {
+            path if path.is_empty() => "/".to_string(),
+            path => path,
+        };
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nAccept: application/json\r\n",
+            method, path, self.authority
+        );
+        if let Some(body) = body {
+            head.push_str(&format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n",
+                body.len()
+            ));
+        }
+        head.push_str("Connection: close\r\n\r\n");
+        stream.write_all(head.as_bytes()).await?;
+        if let Some(body) = body {
+            stream.write_all(body).await?;
+        }
+        let mut response = Vec::new();
+        (&mut stream)
+            .take(MAX_RPC_RESPONSE_BYTES as u64 + 1)
+            .read_to_end(&mut response)
+            .await?;
+        if response.len() > MAX_RPC_RESPONSE_BYTES {
+            bail!(
+                "{} response exceeds {} bytes",
+                self.label,
+                MAX_RPC_RESPONSE_BYTES
+            );
+        }
+        Ok(response)
+    }
+
+    // The status, whether the body is chunked, and the body as sent.
+    fn parse<'a>(&self, response: &'a [u8]) -> Result<(String, bool, &'a [u8])> {
+        let split = response
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| anyhow!("malformed HTTP response from {}", self.authority))?;
+        let head = String::from_utf8_lossy(&response[..split]).to_ascii_lowercase();
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or_default()
+            .to_string();
+        Ok((
+            status,
+            head.contains("transfer-encoding: chunked"),
+            &response[split + 4..],
+        ))
+    }
+}
+
+pub struct HttpJsonRpc {
+    http: PlainHttp,
+    next_id: AtomicU64,
+}
+
+impl HttpJsonRpc {
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            http: PlainHttp::new("JSON-RPC", url)?,
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.http.set_timeout(timeout);
+        self
+    }
+
+    pub fn with_journal(mut self, journal: ObservationJournal) -> Self {
+        self.http.set_journal(journal);
+        self
+    }
+}
+
+fn dechunk(mut body: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(|| anyhow!("truncated chunked HTTP body"))?;
+        let size_text = String::from_utf8_lossy(&body[..line_end]);
+        let size =
+            usize::from_str_radix(size_text.split(';').next().unwrap_or_default().trim(), 16)
+                .map_err(|_| anyhow!("invalid HTTP chunk size {}", size_text))?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Ok(out);
+        }
+        if body.len() < size + 2 {
+            bail!("truncated chunked HTTP body");
+        }
+        out.extend_from_slice(&body[..size]);
+        body = &body[size + 2..];
+    }
+}
+
+#[async_trait]
+impl JsonRpc for HttpJsonRpc {
+    fn endpoint(&self) -> String {
+        self.http.endpoint()
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let raw = self
+            .http
+            .send("POST", "", Some(&serde_json::to_vec(&request)?))
+            .await
+            .with_context(|| format!("{} failed", method))?;
+        let mut response: Value = serde_json::from_slice(&raw)
+            .with_context(|| format!("{} returned invalid JSON", method))?;
+        if let Some(error) = response.get("error") {
+            bail!(
+                "{} failed: {}",
+                method,
+                error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("unknown error")
+            );
+        }
+        Ok(response
+            .get_mut("result")
+            .map(Value::take)
+            .unwrap_or(Value::Null))
+    }
+}
@@ -1,5 +1,22 @@
 mod base;
+mod evm;
+pub(crate) mod evm_encoding;
+mod json_rpc;
+mod polymarket;
+mod simulation;
+mod solana;
 mod synthetic;
 
-pub use base::VenueAdapter;
+pub use base::{AdapterCapabilities, FinalitySemantics, VenueAdapter};
+pub use evm::{BaseEvmAdapter, EvmChainHead, SyntheticEvmChain, SyntheticLog};
+pub use json_rpc::{HttpJsonRpc, JsonRpc};
+pub use polymarket::{
+    order_filled_topic, ClobApi, HttpClobApi, PolymarketAdapter, SyntheticClobApi, CTF_EXCHANGE,
+    NEG_RISK_CTF_EXCHANGE,
+};
+pub use simulation::{
+    load_workload_scenarios, EvidencePhase, ScenarioScript, StageLatency, WorkloadScenario,
+    WORKLOADS_PATH,
+};
+pub use solana::{SolanaAdapter, SolanaFinalizedSlot, SyntheticSolanaCluster};
 pub use synthetic::SyntheticVenueAdapter;
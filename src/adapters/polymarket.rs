@@ -0,0 +1,598 @@
+use crate::adapters::base::{AdapterCapabilities, FinalitySemantics, VenueAdapter};
+use crate::adapters::evm::EvmChainHead;
+use crate::adapters::evm_encoding::{
+    field, header_hash, hex_bytes, keccak, quantity, receipt_encoding, to_hex,
+};
+use crate::adapters::json_rpc::{HttpJsonRpc, JsonRpc, PlainHttp};
+use crate::finality::ChainHeadSource;
+use crate::hashing::HashAlgorithm;
+use crate::journal::ObservationJournal;
+use crate::models::{
+    hash_bytes, hash_json, now_iso, ClaimType, CollectionRecord, EvidenceBundle, EvidenceItem,
+    ExecutionAck, ProofRequest, RawArtifact, Venue,
+};
+use crate::references::ReferenceFormats;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+const POLYMARKET_ADAPTER_VERSION: &str = "0.1.0";
+// Polymarket's CTF Exchange and Neg Risk CTF Exchange on Polygon, which settle CLOB matches.
+pub const CTF_EXCHANGE: &str = "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e";
+pub const NEG_RISK_CTF_EXCHANGE: &str = "0xc5d563a36ae78145c45a50134d48a1215220f80a";
+const ORDER_FILLED_EVENT: &str =
+    "OrderFilled(bytes32,address,address,uint256,uint256,uint256,uint256,uint256)";
+
+pub fn order_filled_topic() -> String {
+    to_hex(&keccak(ORDER_FILLED_EVENT.as_bytes()))
+}
+
+#[async_trait]
+pub trait ClobApi: Send + Sync {
+    // Where requests go, without any path or credentials, for collection records.
+    fn endpoint(&self) -> String;
+    async fn get(&self, path: &str) -> Result<Value>;
+}
+
+// The CLOB REST API over plain HTTP. The order and trade endpoints need L2 API-key headers, so
+// point this at a local proxy that terminates TLS and signs requests.
+pub struct HttpClobApi {
+    http: PlainHttp,
+}
+
+impl HttpClobApi {
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            http: PlainHttp::new("CLOB API", url)?,
+        })
+    }
+
+    pub fn with_journal(mut self, journal: ObservationJournal) -> Self {
+        self.http.set_journal(journal);
+        self
+    }
+}
+
+#[async_trait]
+impl ClobApi for HttpClobApi {
+    fn endpoint(&self) -> String {
+        self.http.endpoint()
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        let body = self.http.send("GET", path, None).await?;
+        serde_json::from_slice(&body)
+            .with_context(|| format!("CLOB API {} returned invalid JSON", path))
+    }
+}
+
+// Collects Polymarket evidence from two independent places: the CLOB API's record of the order and
+// trade (unsigned venue data), and the CTF Exchange settlement transaction on Polygon (chain
+// state). The order ref is the CLOB order hash and the execution ref is the trade id. A trade is
+// only backed by an execution artifact once its settlement transaction carries an `OrderFilled`
+// event for the order.
+pub struct PolymarketAdapter {
+    clob: Arc<dyn ClobApi>,
+    polygon: Arc<dyn JsonRpc>,
+    confirmations: u32,
+    exchanges: Vec<String>,
+}
+
+impl PolymarketAdapter {
+    pub fn new(clob: Arc<dyn ClobApi>, polygon: Arc<dyn JsonRpc>) -> Self {
+        Self {
+            clob,
+            polygon,
+            confirmations: 32,
+            exchanges: vec![CTF_EXCHANGE.to_string(), NEG_RISK_CTF_EXCHANGE.to_string()],
+        }
+    }
+
+    pub fn connect(clob_url: &str, polygon_url: &str) -> Result<Self> {
+        Ok(Self::new(
+            Arc::new(HttpClobApi::new(clob_url)?),
+            Arc::new(HttpJsonRpc::new(polygon_url)?),
+        ))
+    }
+
+    pub fn with_confirmations(mut self, confirmations: u32) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    pub fn chain_head(&self) -> EvmChainHead {
+        EvmChainHead::new(self.polygon.clone())
+    }
+
+    fn order_id(request: &ProofRequest) -> Result<String> {
+        let order_id = request.order_ref.to_ascii_lowercase();
+        if !order_id.starts_with("0x") || hex_bytes(&order_id).map_or(true, |b| b.len() != 32) {
+            bail!("{} is not a 0x-prefixed CLOB order hash", request.order_ref);
+        }
+        Ok(order_id)
+    }
+
+    async fn order(&self, order_id: &str) -> Result<Value> {
+        let order = self.clob.get(&format!("/data/order/{}", order_id)).await?;
+        if order
+            .get("id")
+            .and_then(|v| v.as_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+            != Some(order_id)
+        {
+            bail!("CLOB API has no order {}", order_id);
+        }
+        Ok(order)
+    }
+
+    async fn trade(&self, trade_id: &str) -> Result<Value> {
+        let answer = self
+            .clob
+            .get(&format!("/data/trades?id={}", trade_id))
+            .await?;
+        let trades = answer
+            .as_array()
+            .or_else(|| answer.get("data").and_then(|v| v.as_array()))
+            .ok_or_else(|| anyhow!("CLOB API trades answer is not a list"))?;
+        trades
+            .iter()
+            .find(|t| t.get("id").and_then(|v| v.as_str()) == Some(trade_id))
+            .cloned()
+            .ok_or_else(|| anyhow!("CLOB API has no trade {}", trade_id))
+    }
+
+    fn collection_record(
+        &self,
+        item: &EvidenceItem,
+        endpoint: String,
+        request: Value,
+    ) -> CollectionRecord {
+        CollectionRecord {
+            artifact_hash: item.artifact_hash.clone(),
+            endpoint_url: endpoint,
+            request_hash: hash_json(&request),
+            adapter_version: POLYMARKET_ADAPTER_VERSION.to_string(),
+            client_ip: None,
+            client_region: None,
+            collected_at: now_iso(),
+            hashed_fields: vec![],
+        }
+    }
+}
+
+// Status and fill progress change as the order trades, so only the fields fixed at placement are
+// committed to.
+fn order_artifact(order: &Value) -> Value {
+    let pick = |name: &str| order.get(name).cloned().unwrap_or(Value::Null);
+    json!({
+        "id": pick("id"),
+        "market": pick("market"),
+        "asset_id": pick("asset_id"),
+        "side": pick("side"),
+        "original_size": pick("original_size"),
+        "price": pick("price"),
+        "maker_address": pick("maker_address"),
+        "created_at": pick("created_at"),
+    })
+}
+
+fn trade_artifact(trade: &Value) -> Value {
+    let pick = |name: &str| trade.get(name).cloned().unwrap_or(Value::Null);
+    json!({
+        "id": pick("id"),
+        "taker_order_id": pick("taker_order_id"),
+        "maker_orders": pick("maker_orders"),
+        "market": pick("market"),
+        "asset_id": pick("asset_id"),
+        "side": pick("side"),
+        "size": pick("size"),
+        "price": pick("price"),
+        "match_time": pick("match_time"),
+    })
+}
+
+// The CLOB reports times as unix seconds, as a number or a numeric string.
+fn unix_time(value: &Value, name: &str) -> Result<String> {
+    let raw = value
+        .get(name)
+        .ok_or_else(|| anyhow!("CLOB answer is missing {}", name))?;
+    let seconds = raw
+        .as_i64()
+        .or_else(|| raw.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| anyhow!("CLOB {} is not a unix timestamp", name))?;
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        .ok_or_else(|| anyhow!("CLOB {} {} is out of range", name, seconds))
+}
+
+fn involves_order(trade: &Value, order_id: &str) -> bool {
+    let is_order = |v: Option<&Value>| {
+        v.and_then(|v| v.as_str())
+            .is_some_and(|id| id.eq_ignore_ascii_case(order_id))
+    };
+    is_order(trade.get("taker_order_id"))
+        || trade
+            .get("maker_orders")
+            .and_then(|v| v.as_array())
+            .is_some_and(|makers| makers.iter().any(|m| is_order(m.get("order_id"))))
+}
+
+fn tags(names: &[&str]) -> Vec<String> {
+    names.iter().map(|t| t.to_string()).collect()
+}
+
+#[async_trait]
+impl VenueAdapter for PolymarketAdapter {
+    fn venue(&self) -> Venue {
+        Venue::Polymarket
+    }
+
+    fn reference_formats(&self) -> Option<ReferenceFormats> {
+        Some(ReferenceFormats::for_venue(Venue::Polymarket))
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities {
+            adapter_id: "polymarket-clob".to_string(),
+            adapter_version: POLYMARKET_ADAPTER_VERSION.to_string(),
+            claim_types: vec![ClaimType::ORDER_PLACED, ClaimType::TRADE_EXECUTED],
+            evidence_tags: tags(&[
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+                "execution_identity",
+                "execution_timestamp",
+                "execution_artifact",
+            ]),
+            finality: FinalitySemantics::Confirmations {
+                depth: self.confirmations,
+            },
+            supports_batch: false,
+            supports_execution: false,
+            supports_corrections: false,
+        }
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        let order_id = Self::order_id(request)?;
+        let order = self.order(&order_id).await?;
+        let market = order
+            .get("market")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        Ok(ExecutionAck {
+            accepted: true,
+            venue_order_ref: order_id.clone(),
+            acceptance_artifact_ref: format!(
+                "polymarket://clob/order/{}?market={}",
+                order_id, market
+            ),
+            acceptance_artifact_hash: hash_json(&order_artifact(&order)),
+            accepted_at: unix_time(&order, "created_at")?,
+            rejection_reason: None,
+        })
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        let order_id = Self::order_id(request)?;
+        let order = self.order(&order_id).await?;
+        let mut conflicts = Vec::new();
+        let order_body = order_artifact(&order);
+        if hash_json(&order_body) != ack.acceptance_artifact_hash {
+            conflicts.push("order_changed_since_acknowledgement".to_string());
+        }
+        let order_item = EvidenceItem {
+            source_id: "polymarket-clob-order".to_string(),
+            source_kind: "venue_api_unsigned".to_string(),
+            artifact_ref: ack.acceptance_artifact_ref.clone(),
+            artifact_hash: hash_json(&order_body),
+            observed_at: unix_time(&order, "created_at")?,
+            tags: tags(&[
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+            ]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: serde_json::to_vec(&order_body).map_or(0, |b| b.len() as u64),
+        };
+        let mut collection = vec![self.collection_record(
+            &order_item,
+            self.clob.endpoint(),
+            json!({ "method": "GET", "path": format!("/data/order/{}", order_id) }),
+        )];
+        let mut raw_artifacts = vec![RawArtifact::of(
+            &order_item,
+            serde_json::to_vec(&order_body)?,
+        )];
+        let mut items = vec![order_item];
+        let mut inclusion_height = None;
+        let mut confirmations = None;
+        let mut finality_observed_at = None;
+
+        if request.claim_type == ClaimType::TRADE_EXECUTED {
+            let trade_id = request.execution_ref.as_deref().ok_or_else(|| {
+                anyhow!("a Polymarket trade claim needs the CLOB trade id as execution_ref")
+            })?;
+            let trade = self.trade(trade_id).await?;
+            if !involves_order(&trade, &order_id) {
+                conflicts.push("trade_order_mismatch".to_string());
+            }
+            if trade.get("market") != order.get("market") {
+                conflicts.push("trade_market_mismatch".to_string());
+            }
+            let trade_body = trade_artifact(&trade);
+            let trade_item = EvidenceItem {
+                source_id: "polymarket-clob-trade".to_string(),
+                source_kind: "venue_api_unsigned".to_string(),
+                artifact_ref: format!("polymarket://clob/trade/{}", trade_id),
+                artifact_hash: hash_json(&trade_body),
+                observed_at: unix_time(&trade, "match_time")?,
+                tags: tags(&["execution_identity", "execution_timestamp"]),
+                hash_algorithm: HashAlgorithm::Sha256,
+                size_bytes: serde_json::to_vec(&trade_body).map_or(0, |b| b.len() as u64),
+            };
+            collection.push(self.collection_record(
+                &trade_item,
+                self.clob.endpoint(),
+                json!({ "method": "GET", "path": format!("/data/trades?id={}", trade_id) }),
+            ));
+            raw_artifacts.push(RawArtifact::of(
+                &trade_item,
+                serde_json::to_vec(&trade_body)?,
+            ));
+            items.push(trade_item);
+
+            let status = trade
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            if status == "FAILED" {
+                conflicts.push("settlement_failed".to_string());
+            }
+            let tx_hash = trade
+                .get("transaction_hash")
+                .and_then(|v| v.as_str())
+                .filter(|h| hex_bytes(h).is_ok_and(|b| b.len() == 32));
+            // Until the match is mined there is no settlement to point at, and the claim lacks its
+            // execution artifact.
+            let settlement = match tx_hash {
+                Some(tx_hash) if matches!(status, "MINED" | "CONFIRMED") => {
+                    let receipt = self
+                        .polygon
+                        .call("eth_getTransactionReceipt", json!([tx_hash]))
+                        .await?;
+                    (!receipt.is_null()).then_some((tx_hash, receipt))
+                }
+                _ => None,
+            };
+            if let Some((tx_hash, receipt)) = settlement {
+                let block_hash = field(&receipt, "blockHash")?;
+                let block = self
+                    .polygon
+                    .call("eth_getBlockByHash", json!([block_hash, false]))
+                    .await?;
+                if block.is_null() {
+                    bail!(
+                        "settlement block {} is unknown to the Polygon node",
+                        block_hash
+                    );
+                }
+                if header_hash(&block)? != block_hash.to_ascii_lowercase() {
+                    conflicts.push("block_header_hash_mismatch".to_string());
+                }
+                if quantity(&receipt, "status")? != 1 {
+                    conflicts.push("settlement_reverted".to_string());
+                }
+                let topic = order_filled_topic();
+                let filled = receipt
+                    .get("logs")
+                    .and_then(|v| v.as_array())
+                    .is_some_and(|logs| {
+                        logs.iter().any(|log| {
+                            let from_exchange = field(log, "address").is_ok_and(|a| {
+                                self.exchanges.iter().any(|e| e.eq_ignore_ascii_case(a))
+                            });
+                            let topics = log.get("topics").and_then(|v| v.as_array());
+                            let topic_at = |idx: usize| {
+                                topics.and_then(|t| t.get(idx)).and_then(|v| v.as_str())
+                            };
+                            from_exchange
+                                && topic_at(0).is_some_and(|t| t.eq_ignore_ascii_case(&topic))
+                                && topic_at(1).is_some_and(|t| t.eq_ignore_ascii_case(&order_id))
+                        })
+                    });
+                if !filled {
+                    conflicts.push("settlement_missing_fill".to_string());
+                }
+                let number = quantity(&block, "number")?;
+                let seconds = quantity(&block, "timestamp")?;
+                let block_at = chrono::DateTime::from_timestamp(seconds as i64, 0)
+                    .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+                    .ok_or_else(|| anyhow!("block timestamp {} is out of range", seconds))?;
+                let encoded = receipt_encoding(&receipt)?;
+                let settlement_item = EvidenceItem {
+                    source_id: "polymarket-polygon-settlement".to_string(),
+                    source_kind: "canonical_chain_state".to_string(),
+                    artifact_ref: format!(
+                        "polymarket://polygon/tx/{}",
+                        tx_hash.to_ascii_lowercase()
+                    ),
+                    artifact_hash: hash_bytes(&encoded),
+                    observed_at: block_at,
+                    tags: tags(&["execution_artifact"]),
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    size_bytes: encoded.len() as u64,
+                };
+                collection.push(self.collection_record(
+                    &settlement_item,
+                    self.polygon.endpoint(),
+                    json!({ "method": "eth_getTransactionReceipt", "params": [tx_hash] }),
+                ));
+                raw_artifacts.push(RawArtifact::of(&settlement_item, encoded));
+                items.push(settlement_item);
+                let head = self.chain_head().head().await?;
+                let depth = head.saturating_sub(number) + 1;
+                inclusion_height = Some(number);
+                confirmations = Some(depth);
+                finality_observed_at = (depth >= u64::from(self.confirmations)).then(now_iso);
+            }
+        }
+
+        let observed_tags: HashSet<String> = items
+            .iter()
+            .flat_map(|item| item.tags.iter().cloned())
+            .collect();
+        Ok(EvidenceBundle {
+            items,
+            observed_tags,
+            conflicts,
+            finality_observed_at,
+            inclusion_height,
+            confirmations,
+            commitment: None,
+            price_observation: None,
+            correction: None,
+            cancellation: None,
+            position_closure: None,
+            balance_snapshot: None,
+            collection,
+            order_book: None,
+            raw_artifacts,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct SyntheticClobState {
+    orders: HashMap<String, Value>,
+    trades: HashMap<String, Value>,
+    placed: u64,
+}
+
+// An in-memory CLOB answering the order and trade lookups `PolymarketAdapter` makes.
+#[derive(Debug, Default)]
+pub struct SyntheticClobApi {
+    state: Mutex<SyntheticClobState>,
+}
+
+impl SyntheticClobApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Rests a limit order on the book and returns its order hash.
+    pub fn place_order(
+        &self,
+        maker: &str,
+        market: &str,
+        asset_id: &str,
+        side: &str,
+        price: f64,
+        size: f64,
+    ) -> String {
+        let mut state = self.lock();
+        state.placed += 1;
+        let placed = state.placed;
+        let order_id = to_hex(&keccak(
+            format!("{}:{}:{}", maker, market, placed).as_bytes(),
+        ));
+        state.orders.insert(
+            order_id.clone(),
+            json!({
+                "id": order_id,
+                "status": "LIVE",
+                "market": market,
+                "asset_id": asset_id,
+                "side": side,
+                "original_size": size.to_string(),
+                "size_matched": "0",
+                "price": price.to_string(),
+                "maker_address": maker,
+                "created_at": 1_760_000_000 + placed as i64,
+                "associate_trades": []
+            }),
+        );
+        order_id
+    }
+
+    // Matches the whole order as taker and returns the trade id. `status` follows the CLOB's trade
+    // lifecycle (MATCHED, MINED, CONFIRMED, RETRYING, FAILED).
+    pub fn match_order(
+        &self,
+        order_id: &str,
+        status: &str,
+        transaction_hash: Option<&str>,
+    ) -> String {
+        let mut state = self.lock();
+        let trade_id = uuid::Uuid::new_v4().to_string();
+        let Some(order) = state.orders.get_mut(order_id) else {
+            return trade_id;
+        };
+        order["status"] = json!("MATCHED");
+        order["size_matched"] = order["original_size"].clone();
+        if let Some(trades) = order["associate_trades"].as_array_mut() {
+            trades.push(json!(trade_id));
+        }
+        let trade = json!({
+            "id": trade_id,
+            "taker_order_id": order_id,
+            "maker_orders": [],
+            "market": order["market"],
+            "asset_id": order["asset_id"],
+            "side": order["side"],
+            "size": order["original_size"],
+            "price": order["price"],
+            "status": status,
+            "match_time": (order["created_at"].as_i64().unwrap_or_default() + 1).to_string(),
+            "transaction_hash": transaction_hash
+        });
+        state.trades.insert(trade_id.clone(), trade);
+        trade_id
+    }
+
+    pub fn set_trade_status(&self, trade_id: &str, status: &str, transaction_hash: Option<&str>) {
+        if let Some(trade) = self.lock().trades.get_mut(trade_id) {
+            trade["status"] = json!(status);
+            trade["transaction_hash"] = json!(transaction_hash);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SyntheticClobState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl ClobApi for SyntheticClobApi {
+    fn endpoint(&self) -> String {
+        "synthetic://clob".to_string()
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        let state = self.lock();
+        if let Some(order_id) = path.strip_prefix("/data/order/") {
+            return Ok(state
+                .orders
+                .get(&order_id.to_ascii_lowercase())
+                .cloned()
+                .unwrap_or(Value::Null));
+        }
+        if let Some(trade_id) = path.strip_prefix("/data/trades?id=") {
+            return Ok(json!(state
+                .trades
+                .get(trade_id)
+                .into_iter()
+                .collect::<Vec<_>>()));
+        }
+        bail!("synthetic CLOB does not serve {}", path)
+    }
+}
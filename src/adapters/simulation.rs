@@ -0,0 +1,122 @@
+use crate::models::{ClaimType, Venue};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const WORKLOADS_PATH: &str = "benchmarks/workloads.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadScenario {
+    pub id: String,
+    pub venue: Venue,
+    pub claim_type: ClaimType,
+    pub action_template: String,
+    pub evidence_profile: Vec<String>,
+    pub target_rate_per_sec: f64,
+    pub payload_bytes_target: usize,
+    #[serde(default)]
+    pub latency_ms: StageLatency,
+}
+
+// Delays injected into each pipeline stage for SLO runs. The synthetic adapter applies the
+// acknowledge and collection delays, `DelayedProver` the proving delay, and `TimedChainHead` paces
+// finality.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StageLatency {
+    pub acknowledge: u64,
+    pub collect_evidence: u64,
+    pub prove: u64,
+    pub finality: u64,
+}
+
+impl StageLatency {
+    // A request payload's `simulate_latency_ms` replaces the stages it names. Only the adapter
+    // stages see the payload; proving and finality delays come from the scenario.
+    pub fn with_overrides(self, payload: &serde_json::Value) -> Self {
+        let Some(overrides) = payload.get("simulate_latency_ms") else {
+            return self;
+        };
+        let stage = |name: &str, default: u64| {
+            overrides
+                .get(name)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(default)
+        };
+        Self {
+            acknowledge: stage("acknowledge", self.acknowledge),
+            collect_evidence: stage("collect_evidence", self.collect_evidence),
+            prove: stage("prove", self.prove),
+            finality: stage("finality", self.finality),
+        }
+    }
+}
+
+// A request payload's `script` walks one request through a chosen path of the engine state
+// machine. Failures name 1-based call numbers, counted per order and claim type, so a retry can
+// get past them; the n-th evidence collection uses the n-th phase (the last one repeats). Finality
+// lands after the given number of head polls when the adapter is the venue's chain head source.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScenarioScript {
+    pub ack_delay_ms: Option<u64>,
+    pub fail_acknowledge: Vec<u32>,
+    pub fail_collect_evidence: Vec<u32>,
+    pub evidence_phases: Vec<EvidencePhase>,
+    pub finality_after_polls: Option<u64>,
+}
+
+// `tags`, when set, limits the observed tags to those listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvidencePhase {
+    pub tags: Option<Vec<String>>,
+    pub conflict: bool,
+}
+
+impl ScenarioScript {
+    pub fn from_payload(payload: &serde_json::Value) -> Result<Option<Self>> {
+        payload
+            .get("script")
+            .map(|script| serde_json::from_value(script.clone()))
+            .transpose()
+            .context("invalid synthetic scenario script")
+    }
+
+    pub fn phase(&self, collection: u32) -> Option<&EvidencePhase> {
+        let index =
+            (collection.max(1) as usize - 1).min(self.evidence_phases.len().saturating_sub(1));
+        self.evidence_phases.get(index)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkloadFile {
+    scenarios: Vec<WorkloadScenario>,
+}
+
+pub fn load_workload_scenarios(path: &Path) -> Result<Vec<WorkloadScenario>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let parsed: WorkloadFile = serde_json::from_str(&text)
+        .with_context(|| format!("failed to parse workloads {}", path.display()))?;
+    Ok(parsed.scenarios)
+}
+
+// A deterministic artifact body padded to the scenario's payload size, so hashing and storage
+// costs in dry runs match the benchmark targets.
+pub(crate) fn scenario_artifact_body(
+    scenario: &WorkloadScenario,
+    fields: serde_json::Value,
+) -> Vec<u8> {
+    let mut body = serde_json::json!({
+        "scenario": scenario.id,
+        "action_template": scenario.action_template,
+        "fields": fields,
+        "padding": "",
+    });
+    let unpadded = serde_json::to_vec(&body).unwrap_or_default().len();
+    let padding = scenario.payload_bytes_target.saturating_sub(unpadded);
+    body["padding"] = serde_json::Value::String("0".repeat(padding));
+    serde_json::to_vec(&body).unwrap_or_default()
+}
@@ -0,0 +1,582 @@
+use crate::adapters::base::{AdapterCapabilities, FinalitySemantics, VenueAdapter};
+use crate::adapters::json_rpc::{HttpJsonRpc, JsonRpc};
+use crate::finality::ChainHeadSource;
+use crate::hashing::HashAlgorithm;
+use crate::models::{
+    hash_json, now_iso, ChainCommitment, ClaimType, CollectionRecord, EvidenceBundle, EvidenceItem,
+    ExecutionAck, ProofRequest, RawArtifact, Venue,
+};
+use crate::references::ReferenceFormats;
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+const SOLANA_ADAPTER_VERSION: &str = "0.1.0";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const MAX_TRACKED_FINALIZED_SLOTS: usize = 4096;
+
+// Reports the cluster's finalized root slot, so a `FinalityObserver` releases a receipt at the
+// moment its slot reaches `finalized` commitment.
+pub struct SolanaFinalizedSlot {
+    rpc: Arc<dyn JsonRpc>,
+}
+
+impl SolanaFinalizedSlot {
+    pub fn new(rpc: Arc<dyn JsonRpc>) -> Self {
+        Self { rpc }
+    }
+}
+
+#[async_trait]
+impl ChainHeadSource for SolanaFinalizedSlot {
+    async fn head(&self) -> Result<u64> {
+        slot_at(self.rpc.as_ref(), "finalized").await
+    }
+}
+
+async fn slot_at(rpc: &dyn JsonRpc, commitment: &str) -> Result<u64> {
+    rpc.call("getSlot", json!([{ "commitment": commitment }]))
+        .await?
+        .as_u64()
+        .ok_or_else(|| anyhow!("getSlot returned a non-integer slot"))
+}
+
+// Collects Solana evidence from a cluster RPC node. The order (or execution) ref is the base58
+// transaction signature. The transaction is read at `confirmed` commitment and cross-checked
+// against its signature status and the block at its slot; the commitment level at collection time
+// is recorded on the bundle.
+pub struct SolanaAdapter {
+    rpc: Arc<dyn JsonRpc>,
+    finalized_at: Mutex<BTreeMap<u64, String>>,
+}
+
+impl SolanaAdapter {
+    pub fn new(rpc: Arc<dyn JsonRpc>) -> Self {
+        Self {
+            rpc,
+            finalized_at: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn connect(url: &str) -> Result<Self> {
+        Ok(Self::new(Arc::new(HttpJsonRpc::new(url)?)))
+    }
+
+    pub fn finalized_slot_head(&self) -> SolanaFinalizedSlot {
+        SolanaFinalizedSlot::new(self.rpc.clone())
+    }
+
+    fn signature(request: &ProofRequest) -> Result<&str> {
+        let signature = match request.claim_type {
+            ClaimType::ORDER_PLACED => request.order_ref.as_str(),
+            _ => request
+                .execution_ref
+                .as_deref()
+                .unwrap_or(&request.order_ref),
+        };
+        if !(64..=88).contains(&signature.len())
+            || !signature.bytes().all(|b| BASE58_ALPHABET.contains(&b))
+        {
+            bail!("{} is not a base58 transaction signature", signature);
+        }
+        Ok(signature)
+    }
+
+    async fn transaction(&self, signature: &str) -> Result<Value> {
+        let tx = self
+            .rpc
+            .call(
+                "getTransaction",
+                json!([signature, { "encoding": "json", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }]),
+            )
+            .await?;
+        if tx.is_null() {
+            bail!("transaction {} is not confirmed yet", signature);
+        }
+        Ok(tx)
+    }
+
+    async fn status(&self, signature: &str) -> Result<Value> {
+        let statuses = self
+            .rpc
+            .call(
+                "getSignatureStatuses",
+                json!([[signature], { "searchTransactionHistory": true }]),
+            )
+            .await?;
+        match statuses.pointer("/value/0") {
+            Some(status) if !status.is_null() => Ok(status.clone()),
+            _ => bail!("signature {} has no status", signature),
+        }
+    }
+
+    async fn block(&self, slot: u64) -> Result<Value> {
+        let block = self
+            .rpc
+            .call(
+                "getBlock",
+                json!([slot, {
+                    "encoding": "json",
+                    "transactionDetails": "signatures",
+                    "rewards": false,
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0
+                }]),
+            )
+            .await?;
+        if block.is_null() {
+            bail!("slot {} has no confirmed block", slot);
+        }
+        Ok(block)
+    }
+
+    // The first time this adapter saw the slot finalized, so repeated collections agree.
+    fn finalized_at(&self, slot: u64) -> String {
+        let mut seen = self
+            .finalized_at
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let at = seen.entry(slot).or_insert_with(now_iso).clone();
+        while seen.len() > MAX_TRACKED_FINALIZED_SLOTS {
+            seen.pop_first();
+        }
+        at
+    }
+
+    fn collection_record(
+        &self,
+        item: &EvidenceItem,
+        method: &str,
+        params: Value,
+    ) -> CollectionRecord {
+        CollectionRecord {
+            artifact_hash: item.artifact_hash.clone(),
+            endpoint_url: self.rpc.endpoint(),
+            request_hash: hash_json(&json!({ "method": method, "params": params })),
+            adapter_version: SOLANA_ADAPTER_VERSION.to_string(),
+            client_ip: None,
+            client_region: None,
+            collected_at: now_iso(),
+            hashed_fields: vec![],
+        }
+    }
+}
+
+fn slot_of(value: &Value) -> Result<u64> {
+    value
+        .get("slot")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| anyhow!("RPC answer is missing slot"))
+}
+
+fn block_time(value: &Value) -> Result<String> {
+    let seconds = value
+        .get("blockTime")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| anyhow!("RPC answer has no block time"))?;
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        .ok_or_else(|| anyhow!("block time {} is out of range", seconds))
+}
+
+fn commitment_level(status: &Value) -> Option<ChainCommitment> {
+    match status.get("confirmationStatus").and_then(|v| v.as_str())? {
+        "processed" => Some(ChainCommitment::Processed),
+        "confirmed" => Some(ChainCommitment::Confirmed),
+        "finalized" => Some(ChainCommitment::Finalized),
+        _ => None,
+    }
+}
+
+// `json` encoding lists account keys as strings; `jsonParsed` wraps them as `{ "pubkey": .. }`.
+fn account_keys(tx: &Value) -> Vec<&str> {
+    tx.pointer("/transaction/message/accountKeys")
+        .and_then(|v| v.as_array())
+        .map(|keys| {
+            keys.iter()
+                .filter_map(|k| {
+                    k.as_str()
+                        .or_else(|| k.get("pubkey").and_then(|p| p.as_str()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn tags(names: &[&str]) -> Vec<String> {
+    names.iter().map(|t| t.to_string()).collect()
+}
+
+#[async_trait]
+impl VenueAdapter for SolanaAdapter {
+    fn venue(&self) -> Venue {
+        Venue::Solana
+    }
+
+    fn reference_formats(&self) -> Option<ReferenceFormats> {
+        Some(ReferenceFormats::for_venue(Venue::Solana))
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities {
+            adapter_id: "solana-rpc".to_string(),
+            adapter_version: SOLANA_ADAPTER_VERSION.to_string(),
+            claim_types: vec![ClaimType::ORDER_PLACED, ClaimType::TRADE_EXECUTED],
+            evidence_tags: tags(&[
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+                "execution_identity",
+                "execution_timestamp",
+                "execution_artifact",
+            ]),
+            // Heights are slots and `SolanaFinalizedSlot` reports the finalized root, so no further
+            // depth is needed once the root reaches the transaction's slot.
+            finality: FinalitySemantics::Confirmations { depth: 0 },
+            supports_batch: false,
+            supports_execution: false,
+            supports_corrections: false,
+        }
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        let signature = Self::signature(request)?;
+        let tx = self.transaction(signature).await?;
+        let fee_payer = account_keys(&tx)
+            .first()
+            .map(|k| k.to_string())
+            .ok_or_else(|| anyhow!("transaction {} lists no accounts", signature))?;
+        Ok(ExecutionAck {
+            accepted: tx.pointer("/meta/err").is_some_and(Value::is_null),
+            venue_order_ref: request.order_ref.clone(),
+            acceptance_artifact_ref: format!("solana://tx/{}?signer={}", signature, fee_payer),
+            acceptance_artifact_hash: hash_json(&tx),
+            accepted_at: block_time(&tx)?,
+            rejection_reason: None,
+        })
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        let signature = Self::signature(request)?;
+        let tx = self.transaction(signature).await?;
+        let slot = slot_of(&tx)?;
+        let status = self.status(signature).await?;
+        let block = self.block(slot).await?;
+        let finalized_root = slot_at(self.rpc.as_ref(), "finalized").await?;
+        let mut conflicts = Vec::new();
+
+        if tx
+            .pointer("/transaction/signatures/0")
+            .and_then(|v| v.as_str())
+            != Some(signature)
+        {
+            conflicts.push("signature_mismatch".to_string());
+        }
+        if slot_of(&status)? != slot {
+            conflicts.push("slot_mismatch".to_string());
+        }
+        let in_block = block
+            .get("signatures")
+            .and_then(|v| v.as_array())
+            .is_some_and(|sigs| sigs.iter().any(|s| s.as_str() == Some(signature)));
+        if !in_block {
+            conflicts.push("transaction_not_in_block".to_string());
+        }
+        if block.get("blockTime") != tx.get("blockTime") {
+            conflicts.push("block_time_mismatch".to_string());
+        }
+        let meta = tx
+            .get("meta")
+            .cloned()
+            .ok_or_else(|| anyhow!("transaction {} has no meta", signature))?;
+        if !meta.get("err").is_some_and(Value::is_null) || !ack.accepted {
+            conflicts.push("transaction_failed".to_string());
+        }
+
+        let commitment = if slot <= finalized_root {
+            Some(ChainCommitment::Finalized)
+        } else {
+            commitment_level(&status)
+        };
+        let finality_observed_at =
+            (commitment == Some(ChainCommitment::Finalized)).then(|| self.finalized_at(slot));
+
+        let block_at = block_time(&tx)?;
+        let blockhash = block
+            .get("blockhash")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("slot {} block has no blockhash", slot))?;
+        let tx_bytes = serde_json::to_vec(&tx)?;
+        let block_bytes = serde_json::to_vec(&block)?;
+        let meta_bytes = serde_json::to_vec(&meta)?;
+        let tx_item = EvidenceItem {
+            source_id: "solana-rpc-transaction".to_string(),
+            source_kind: "canonical_chain_state".to_string(),
+            artifact_ref: ack.acceptance_artifact_ref.clone(),
+            artifact_hash: hash_json(&tx),
+            observed_at: block_at.clone(),
+            tags: tags(&[
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+            ]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: tx_bytes.len() as u64,
+        };
+        let block_item = EvidenceItem {
+            source_id: "solana-rpc-block".to_string(),
+            source_kind: "canonical_chain_state".to_string(),
+            artifact_ref: format!("solana://slot/{}/block/{}", slot, blockhash),
+            artifact_hash: hash_json(&block),
+            observed_at: block_at,
+            tags: tags(&["execution_timestamp"]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: block_bytes.len() as u64,
+        };
+        let meta_item = EvidenceItem {
+            source_id: "solana-rpc-meta".to_string(),
+            source_kind: "canonical_chain_state".to_string(),
+            artifact_ref: format!("solana://tx/{}/meta", signature),
+            artifact_hash: hash_json(&meta),
+            observed_at: now_iso(),
+            tags: tags(&["execution_identity", "execution_artifact"]),
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: meta_bytes.len() as u64,
+        };
+
+        let mut observed_tags: HashSet<String> = tx_item.tags.iter().cloned().collect();
+        // A trade needs the venue program among the transaction's accounts when the request names one.
+        let program = request.payload.get("program").and_then(|v| v.as_str());
+        let invoked = program.is_none_or(|program| account_keys(&tx).contains(&program));
+        if request.claim_type == ClaimType::TRADE_EXECUTED && invoked {
+            observed_tags.extend(block_item.tags.iter().cloned());
+            observed_tags.extend(meta_item.tags.iter().cloned());
+        }
+
+        let raw_artifacts = vec![
+            RawArtifact::of(&tx_item, tx_bytes),
+            RawArtifact::of(&block_item, block_bytes),
+            RawArtifact::of(&meta_item, meta_bytes),
+        ];
+        let collection = vec![
+            self.collection_record(&tx_item, "getTransaction", json!([signature])),
+            self.collection_record(&block_item, "getBlock", json!([slot])),
+            self.collection_record(&meta_item, "getSignatureStatuses", json!([[signature]])),
+        ];
+        Ok(EvidenceBundle {
+            items: vec![tx_item, block_item, meta_item],
+            observed_tags,
+            conflicts,
+            finality_observed_at,
+            inclusion_height: Some(slot),
+            confirmations: None,
+            commitment,
+            price_observation: None,
+            correction: None,
+            cancellation: None,
+            position_closure: None,
+            balance_snapshot: None,
+            collection,
+            order_book: None,
+            raw_artifacts,
+        })
+    }
+}
+
+fn base58(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for byte in bytes {
+        let mut carry = u32::from(*byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+    std::iter::repeat_n(b'1', zeros)
+        .chain(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+// Solana roots a slot once 31 more have been confirmed on top of it.
+const SYNTHETIC_FINALIZATION_LAG: u64 = 32;
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+#[derive(Debug, Default)]
+struct SyntheticClusterState {
+    slot: u64,
+    transactions: HashMap<String, Value>,
+    blocks: BTreeMap<u64, Value>,
+    submitted: u64,
+}
+
+// An in-memory cluster answering the RPC calls `SolanaAdapter` makes. Every submitted transaction
+// lands alone in a new slot, and the finalized root trails the confirmed tip by 32 slots.
+#[derive(Debug, Default)]
+pub struct SyntheticSolanaCluster {
+    state: Mutex<SyntheticClusterState>,
+}
+
+impl SyntheticSolanaCluster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Lands one transaction in a new slot and returns its signature.
+    pub fn submit_transaction(
+        &self,
+        fee_payer: &str,
+        program: Option<&str>,
+        succeeded: bool,
+    ) -> String {
+        let mut state = self.lock();
+        state.submitted += 1;
+        state.slot += 1;
+        let slot = state.slot;
+        let signature = base58(&Sha512::digest(format!(
+            "{}:{}",
+            fee_payer, state.submitted
+        )));
+        let program = program.unwrap_or(SYSTEM_PROGRAM);
+        let block_time = 1_760_000_000 + slot as i64 * 2 / 5;
+        let outcome = if succeeded {
+            "success"
+        } else {
+            "failed: custom program error: 0x1"
+        };
+        state.transactions.insert(
+            signature.clone(),
+            json!({
+                "slot": slot,
+                "blockTime": block_time,
+                "version": 0,
+                "meta": {
+                    "err": if succeeded { Value::Null } else { json!({ "InstructionError": [0, { "Custom": 1 }] }) },
+                    "fee": 5000,
+                    "logMessages": [format!("Program {} invoke [1]", program), format!("Program {} {}", program, outcome)]
+                },
+                "transaction": {
+                    "signatures": [signature],
+                    "message": {
+                        "accountKeys": [fee_payer, program],
+                        "recentBlockhash": base58(&Sha256::digest(format!("block:{}", slot - 1))),
+                        "instructions": [{ "programIdIndex": 1, "accounts": [0], "data": "" }]
+                    }
+                }
+            }),
+        );
+        let block = json!({
+            "blockhash": base58(&Sha256::digest(format!("block:{}", slot))),
+            "previousBlockhash": base58(&Sha256::digest(format!("block:{}", slot - 1))),
+            "parentSlot": slot - 1,
+            "blockHeight": slot,
+            "blockTime": block_time,
+            "signatures": [signature]
+        });
+        state.blocks.insert(slot, block);
+        signature
+    }
+
+    // Confirms `slots` further slots without transactions, moving the finalized root with them.
+    pub fn advance_slots(&self, slots: u64) {
+        self.lock().slot += slots;
+    }
+
+    // Removes a signature from the block that included it, as a lying node would.
+    pub fn drop_from_block(&self, signature: &str) {
+        let mut state = self.lock();
+        for block in state.blocks.values_mut() {
+            if let Some(sigs) = block.get_mut("signatures").and_then(|v| v.as_array_mut()) {
+                sigs.retain(|s| s.as_str() != Some(signature));
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SyntheticClusterState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl JsonRpc for SyntheticSolanaCluster {
+    fn endpoint(&self) -> String {
+        "synthetic://solana".to_string()
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let state = self.lock();
+        let finalized_root = state.slot.saturating_sub(SYNTHETIC_FINALIZATION_LAG);
+        match method {
+            "getSlot" => Ok(
+                match params.pointer("/0/commitment").and_then(|v| v.as_str()) {
+                    Some("finalized") => json!(finalized_root),
+                    _ => json!(state.slot),
+                },
+            ),
+            "getTransaction" => {
+                let signature = params.get(0).and_then(|v| v.as_str()).unwrap_or_default();
+                Ok(state
+                    .transactions
+                    .get(signature)
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }
+            "getSignatureStatuses" => {
+                let signatures = params
+                    .get(0)
+                    .and_then(|v| v.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                let value: Vec<Value> = signatures
+                    .iter()
+                    .map(|s| {
+                        let Some(tx) = s.as_str().and_then(|s| state.transactions.get(s)) else {
+                            return Value::Null;
+                        };
+                        let slot = tx["slot"].as_u64().unwrap_or_default();
+                        let finalized = slot <= finalized_root;
+                        json!({
+                            "slot": slot,
+                            "confirmations": if finalized { Value::Null } else { json!(state.slot - slot) },
+                            "err": tx["meta"]["err"],
+                            "confirmationStatus": if finalized { "finalized" } else { "confirmed" }
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "context": { "slot": state.slot }, "value": value }))
+            }
+            "getBlock" => {
+                let slot = params.get(0).and_then(|v| v.as_u64()).unwrap_or_default();
+                Ok(state.blocks.get(&slot).cloned().unwrap_or(Value::Null))
+            }
+            _ => bail!("synthetic cluster does not implement {}", method),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base58_matches_reference_encodings() {
+        assert_eq!(base58(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+        assert_eq!(base58(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), "11233QC4");
+        assert_eq!(base58(&[0u8; 32]), SYSTEM_PROGRAM);
+    }
+}
@@ -1,28 +1,407 @@
-use crate::adapters::base::VenueAdapter;
+use crate::adapters::base::{AdapterCapabilities, FinalitySemantics, VenueAdapter};
+use crate::adapters::simulation::{
+    scenario_artifact_body, ScenarioScript, StageLatency, WorkloadScenario,
+};
+use crate::clock::ClockOffset;
+use crate::credentials::CredentialStore;
+use crate::finality::ChainHeadSource;
+use crate::hashing::HashAlgorithm;
 use crate::models::{
-    now_iso, hash_json, ClaimType, EvidenceBundle, EvidenceItem, ExecutionAck, ProofRequest, Venue,
+    hash_bytes, hash_json, now_iso, BalanceRange, BalanceSnapshot, ChainCommitment, ClaimType,
+    CollectionRecord, EvidenceBundle, EvidenceItem, ExecutionAck, OrderCancellation,
+    PositionClosure, PriceObservation, ProofRequest, RawArtifact, TradeCorrection, Venue,
+};
+use crate::orderbook::{
+    BookLevel, OrderBookSnapshot, ORDER_BOOK_MID_SOURCE, ORDER_BOOK_SNAPSHOT_TAG,
 };
+use crate::references::ReferenceFormats;
 use anyhow::Result;
 use async_trait::async_trait;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+const SYNTHETIC_ADAPTER_VERSION: &str = "0.1.0";
+const SYNTHETIC_GENESIS_HEIGHT: u64 = 1_000;
+
+#[derive(Debug, Default)]
+struct CorrectionFeed {
+    published: Vec<TradeCorrection>,
+    acknowledged: HashSet<String>,
+}
 
 #[derive(Debug)]
 pub struct SyntheticVenueAdapter {
     venue: Venue,
+    claim_types: Vec<ClaimType>,
+    corrections: Mutex<CorrectionFeed>,
+    cancellations: Mutex<Vec<OrderCancellation>>,
+    closures: Mutex<Vec<PositionClosure>>,
+    balances: Mutex<Vec<BalanceSnapshot>>,
+    credentials: Option<(CredentialStore, String)>,
+    scenarios: Vec<WorkloadScenario>,
+    clock_skews: Vec<(String, i64)>,
+    clock_offsets: Vec<ClockOffset>,
+    reference_formats: Option<ReferenceFormats>,
+    script_calls: Mutex<HashMap<(String, ClaimType, &'static str), u32>>,
+    chain_head: AtomicU64,
+    executes_orders: bool,
+    submitted_orders: Mutex<Vec<String>>,
 }
 
-fn venue_slug(venue: Venue) -> &'static str {
-    match venue {
-        Venue::Hyperliquid => "hyperliquid",
-        Venue::Base => "base",
-        Venue::Solana => "solana",
-        Venue::Polymarket => "polymarket",
+impl SyntheticVenueAdapter {
+    pub fn new(venue: Venue) -> Self {
+        Self::with_claim_types(
+            venue,
+            vec![
+                ClaimType::ORDER_PLACED,
+                ClaimType::TRADE_EXECUTED,
+                ClaimType::BEST_EXECUTION,
+                ClaimType::TRADE_BUSTED,
+                ClaimType::TRADE_AMENDED,
+                ClaimType::ORDER_REJECTED,
+                ClaimType::ORDER_CANCELLED,
+                ClaimType::POSITION_CLOSED,
+                ClaimType::BALANCE_AT_TIMESTAMP,
+            ],
+        )
+    }
+
+    pub fn with_claim_types(venue: Venue, claim_types: Vec<ClaimType>) -> Self {
+        Self {
+            venue,
+            claim_types,
+            corrections: Mutex::new(CorrectionFeed::default()),
+            cancellations: Mutex::new(Vec::new()),
+            closures: Mutex::new(Vec::new()),
+            balances: Mutex::new(Vec::new()),
+            credentials: None,
+            scenarios: vec![],
+            clock_skews: vec![],
+            clock_offsets: vec![],
+            reference_formats: None,
+            script_calls: Mutex::new(HashMap::new()),
+            chain_head: AtomicU64::new(SYNTHETIC_GENESIS_HEIGHT),
+            executes_orders: false,
+            submitted_orders: Mutex::new(Vec::new()),
+        }
+    }
+
+    // The API key is looked up on every call rather than captured, so a rotated key is used by
+    // the next request without rebuilding the adapter.
+    pub fn with_credentials(
+        mut self,
+        credentials: CredentialStore,
+        name: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((credentials, name.into()));
+        self
+    }
+
+    // Scenarios for other venues are ignored. A matching scenario shapes the artifacts for its
+    // claim type: refs follow the action template, artifact bodies are padded to the payload size
+    // target, and the observed tags are exactly the scenario's evidence profile.
+    pub fn with_workload_scenarios(mut self, scenarios: Vec<WorkloadScenario>) -> Self {
+        self.scenarios = scenarios
+            .into_iter()
+            .filter(|s| s.venue == self.venue)
+            .collect();
+        self
+    }
+
+    // Makes the named source's clock run `skew_ms` ahead, shifting the timestamps it stamps.
+    pub fn with_clock_skew(mut self, source_id: impl Into<String>, skew_ms: i64) -> Self {
+        self.clock_skews.push((source_id.into(), skew_ms));
+        self
+    }
+
+    // Synthetic refs are free-form unless the adapter is asked to hold them to the venue's formats.
+    pub fn with_reference_formats(mut self) -> Self {
+        self.reference_formats = Some(ReferenceFormats::for_venue(self.venue));
+        self
+    }
+
+    // Lets the adapter place orders itself. A submitted order is acknowledged, and its acceptance
+    // artifact served, as the venue's response to that submission.
+    pub fn with_order_execution(mut self) -> Self {
+        self.executes_orders = true;
+        self
+    }
+
+    // Order refs this adapter has placed, in submission order.
+    pub fn submitted_orders(&self) -> Vec<String> {
+        self.submitted_orders
+            .lock()
+            .map(|orders| orders.clone())
+            .unwrap_or_default()
+    }
+
+    fn was_submitted(&self, order_ref: &str) -> bool {
+        self.submitted_orders
+            .lock()
+            .is_ok_and(|orders| orders.iter().any(|o| o == order_ref))
+    }
+
+    pub fn with_clock_offset(mut self, offset: ClockOffset) -> Self {
+        self.clock_offsets.push(offset);
+        self
+    }
+
+    fn scenario_for(&self, claim_type: ClaimType) -> Option<&WorkloadScenario> {
+        self.scenarios.iter().find(|s| s.claim_type == claim_type)
+    }
+
+    fn injected_latency(&self, request: &ProofRequest) -> StageLatency {
+        self.scenario_for(request.claim_type)
+            .map(|s| s.latency_ms)
+            .unwrap_or_default()
+            .with_overrides(&request.payload)
+    }
+
+    // Numbers the calls a scripted request makes to each stage, starting at 1.
+    fn scripted_call(&self, request: &ProofRequest, stage: &'static str) -> u32 {
+        let mut calls = self
+            .script_calls
+            .lock()
+            .expect("synthetic script lock poisoned");
+        let count = calls
+            .entry((request.order_ref.clone(), request.claim_type, stage))
+            .or_default();
+        *count += 1;
+        *count
+    }
+
+    // Returns the artifact's ref, hash, and body bytes.
+    fn artifact(
+        &self,
+        claim_type: ClaimType,
+        kind: &str,
+        key: &str,
+        fields: serde_json::Value,
+    ) -> (String, String, Vec<u8>) {
+        match self.scenario_for(claim_type) {
+            Some(scenario) => {
+                let body = scenario_artifact_body(scenario, fields);
+                (
+                    format!(
+                        "{}://{}/{}/{}",
+                        self.venue.slug(),
+                        scenario.action_template,
+                        kind,
+                        key
+                    ),
+                    hash_bytes(&body),
+                    body,
+                )
+            }
+            None => (
+                format!("{}://{}/{}", self.venue.slug(), kind, key),
+                hash_json(&fields),
+                serde_json::to_vec(&fields).unwrap_or_default(),
+            ),
+        }
+    }
+
+    fn ack_artifact(&self, request: &ProofRequest, accepted_at: &str) -> (String, String, Vec<u8>) {
+        let kind = if self.was_submitted(&request.order_ref) {
+            "submission"
+        } else {
+            "acknowledgement"
+        };
+        let mut fields = serde_json::json!({
+            "venue": self.venue.slug(),
+            "order_ref": request.order_ref,
+            "accepted_at": accepted_at,
+            "kind": kind
+        });
+        if let Some(reason) = reject_reason(request) {
+            fields["reject_reason"] = serde_json::Value::String(reason);
+        }
+        self.artifact(request.claim_type, "ack", &request.order_ref, fields)
+    }
+
+    fn ack(&self, request: &ProofRequest, accepted_at: String) -> ExecutionAck {
+        let (artifact_ref, artifact_hash, _) = self.ack_artifact(request, &accepted_at);
+        let rejection_reason = reject_reason(request);
+        ExecutionAck {
+            accepted: rejection_reason.is_none(),
+            venue_order_ref: request.order_ref.clone(),
+            acceptance_artifact_ref: artifact_ref,
+            acceptance_artifact_hash: artifact_hash,
+            accepted_at,
+            rejection_reason,
+        }
+    }
+
+    async fn authorize(&self) -> Result<()> {
+        let Some((credentials, name)) = &self.credentials else {
+            return Ok(());
+        };
+        let api_key = credentials.get(name).await?;
+        if api_key.expose().trim().is_empty() {
+            return Err(anyhow::anyhow!("credential {} is empty", name));
+        }
+        Ok(())
+    }
+
+    fn collection_record(&self, item: &EvidenceItem) -> CollectionRecord {
+        let (scheme, path) = item
+            .artifact_ref
+            .split_once("://")
+            .unwrap_or(("synthetic", &item.artifact_ref));
+        let endpoint_url = format!(
+            "https://api.{}.synthetic.example/v1/{}?source={}",
+            scheme, path, item.source_id
+        );
+        CollectionRecord {
+            artifact_hash: item.artifact_hash.clone(),
+            request_hash: hash_json(&serde_json::json!({ "method": "GET", "url": endpoint_url })),
+            endpoint_url,
+            adapter_version: SYNTHETIC_ADAPTER_VERSION.to_string(),
+            client_ip: Some("198.51.100.24".to_string()),
+            client_region: Some("us-east-1".to_string()),
+            collected_at: item.observed_at.clone(),
+            hashed_fields: vec![],
+        }
+    }
+
+    // A synthetic book centred on `center` with a one basis point spread, so its mid is the
+    // declared price.
+    fn order_book(
+        &self,
+        request: &ProofRequest,
+        center: f64,
+        depth: usize,
+    ) -> Result<OrderBookSnapshot> {
+        let market = request
+            .payload
+            .get("market")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}-default", self.venue.slug()));
+        let step = center * 0.0001;
+        let level = |i: usize, side: f64| {
+            BookLevel::new(
+                center + side * step * (i as f64 + 0.5),
+                1.0 + i as f64 * 0.5,
+            )
+        };
+        OrderBookSnapshot::capture(
+            self.venue,
+            market,
+            now_iso(),
+            depth,
+            (0..depth).map(|i| level(i, -1.0)).collect(),
+            (0..depth).map(|i| level(i, 1.0)).collect(),
+        )
+    }
+
+    pub fn publish_correction(&self, correction: TradeCorrection) {
+        if let Ok(mut feed) = self.corrections.lock() {
+            feed.published.push(correction);
+        }
+    }
+
+    fn find_correction(&self, order_ref: &str, execution_ref: &str) -> Option<TradeCorrection> {
+        let feed = self.corrections.lock().ok()?;
+        feed.published
+            .iter()
+            .rev()
+            .find(|c| c.order_ref == order_ref && c.execution_ref == execution_ref)
+            .cloned()
+    }
+
+    // Scripts the venue cancelling an order; ORDER_CANCELLED requests for it observe the cancellation.
+    pub fn publish_cancellation(&self, cancellation: OrderCancellation) {
+        if let Ok(mut cancellations) = self.cancellations.lock() {
+            cancellations.push(cancellation);
+        }
+    }
+
+    fn find_cancellation(&self, order_ref: &str) -> Option<OrderCancellation> {
+        let cancellations = self.cancellations.lock().ok()?;
+        cancellations
+            .iter()
+            .rev()
+            .find(|c| c.order_ref == order_ref)
+            .cloned()
+    }
+
+    // Scripts the venue closing a position with the fill of `closure.order_ref`; POSITION_CLOSED
+    // requests for that order observe the closure and its realized PnL.
+    pub fn publish_position_closure(&self, closure: PositionClosure) {
+        if let Ok(mut closures) = self.closures.lock() {
+            closures.push(closure);
+        }
+    }
+
+    fn find_position_closure(&self, order_ref: &str) -> Option<PositionClosure> {
+        let closures = self.closures.lock().ok()?;
+        closures
+            .iter()
+            .rev()
+            .find(|c| c.order_ref == order_ref)
+            .cloned()
+    }
+
+    // Scripts the venue's record of an account's balance at a point; BALANCE_AT_TIMESTAMP requests
+    // for that account, asset and point observe it.
+    pub fn publish_balance_snapshot(&self, snapshot: BalanceSnapshot) {
+        if let Ok(mut balances) = self.balances.lock() {
+            balances.push(snapshot);
+        }
+    }
+
+    fn find_balance_snapshot(
+        &self,
+        account_ref: &str,
+        range: &BalanceRange,
+    ) -> Option<BalanceSnapshot> {
+        let balances = self.balances.lock().ok()?;
+        balances
+            .iter()
+            .rev()
+            .find(|b| {
+                b.account_ref == account_ref && b.asset == range.asset && range.is_taken_at(b)
+            })
+            .cloned()
     }
 }
 
-impl SyntheticVenueAdapter {
-    pub fn new(venue: Venue) -> Self {
-        Self { venue }
+// Each head query mines one block, so the adapter can stand in as its venue's chain head source
+// and scripted finality counts observer polls.
+#[async_trait]
+impl ChainHeadSource for SyntheticVenueAdapter {
+    async fn head(&self) -> Result<u64> {
+        Ok(self.chain_head.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+}
+
+async fn sleep_ms(ms: u64) {
+    if ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+    }
+}
+
+fn json_size(value: &impl serde::Serialize) -> u64 {
+    serde_json::to_vec(value).map_or(0, |body| body.len() as u64)
+}
+
+// Scripts the venue refusing the order, with the reason it gives.
+fn reject_reason(request: &ProofRequest) -> Option<String> {
+    request
+        .payload
+        .get("reject_reason")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn finality_semantics(venue: Venue) -> FinalitySemantics {
+    match venue {
+        Venue::Hyperliquid => FinalitySemantics::Instant,
+        Venue::Base => FinalitySemantics::Confirmations { depth: 12 },
+        Venue::Solana => FinalitySemantics::Confirmations { depth: 32 },
+        Venue::Polymarket => FinalitySemantics::VenueAttested,
     }
 }
 
@@ -39,38 +418,167 @@ impl VenueAdapter for SyntheticVenueAdapter {
         self.venue
     }
 
+    fn reference_formats(&self) -> Option<ReferenceFormats> {
+        self.reference_formats
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities {
+            adapter_id: format!("synthetic-{}", self.venue.slug()),
+            adapter_version: SYNTHETIC_ADAPTER_VERSION.to_string(),
+            claim_types: self.claim_types.clone(),
+            evidence_tags: [
+                "order_identity",
+                "submission_timestamp",
+                "venue_acceptance_artifact",
+                "venue_rejection_artifact",
+                "execution_identity",
+                "execution_timestamp",
+                "execution_artifact",
+                "reference_price_quote",
+                "correction_identity",
+                "correction_artifact",
+                "cancellation_identity",
+                "venue_cancellation_artifact",
+                "position_identity",
+                "position_closure_artifact",
+                "realized_pnl_artifact",
+                "account_identity",
+                "balance_snapshot_artifact",
+                ORDER_BOOK_SNAPSHOT_TAG,
+            ]
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+            finality: finality_semantics(self.venue),
+            supports_batch: false,
+            supports_execution: self.executes_orders,
+            supports_corrections: true,
+        }
+    }
+
+    async fn poll_corrections(&self) -> Result<Vec<TradeCorrection>> {
+        let feed = self
+            .corrections
+            .lock()
+            .map_err(|_| anyhow::anyhow!("synthetic correction feed lock poisoned"))?;
+        Ok(feed
+            .published
+            .iter()
+            .filter(|c| !feed.acknowledged.contains(&c.correction_ref))
+            .cloned()
+            .collect())
+    }
+
+    async fn ack_correction(&self, correction_ref: &str) -> Result<()> {
+        let mut feed = self
+            .corrections
+            .lock()
+            .map_err(|_| anyhow::anyhow!("synthetic correction feed lock poisoned"))?;
+        feed.acknowledged.insert(correction_ref.to_string());
+        Ok(())
+    }
+
+    fn clock_offsets(&self) -> Vec<ClockOffset> {
+        self.clock_offsets.clone()
+    }
+
     async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
-        let accepted_at = now_iso();
-        let artifact_ref = format!("{}://ack/{}", venue_slug(self.venue), request.order_ref);
-        let artifact_hash = hash_json(&serde_json::json!({
-            "venue": venue_slug(self.venue),
-            "order_ref": request.order_ref,
-            "accepted_at": accepted_at,
-            "kind": "acknowledgement"
-        }));
-        Ok(ExecutionAck {
-            accepted: true,
-            venue_order_ref: request.order_ref.clone(),
-            acceptance_artifact_ref: artifact_ref,
-            acceptance_artifact_hash: artifact_hash,
-            accepted_at,
-        })
+        self.authorize().await?;
+        let script = ScenarioScript::from_payload(&request.payload)?;
+        let delay = script.as_ref().and_then(|s| s.ack_delay_ms);
+        sleep_ms(delay.unwrap_or(self.injected_latency(request).acknowledge)).await;
+        if let Some(script) = &script {
+            let call = self.scripted_call(request, "acknowledge");
+            if script.fail_acknowledge.contains(&call) {
+                return Err(anyhow::anyhow!(
+                    "scripted acknowledge failure on call {}",
+                    call
+                ));
+            }
+        }
+        Ok(self.ack(request, now_iso()))
     }
 
-    async fn collect_evidence(&self, request: &ProofRequest, ack: &ExecutionAck) -> Result<EvidenceBundle> {
+    async fn submit_order(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        if !self.executes_orders {
+            return Err(anyhow::anyhow!(
+                "adapter for venue {:?} does not submit orders",
+                self.venue
+            ));
+        }
+        self.authorize().await?;
+        sleep_ms(self.injected_latency(request).acknowledge).await;
+        if let Some(script) = ScenarioScript::from_payload(&request.payload)? {
+            let call = self.scripted_call(request, "acknowledge");
+            if script.fail_acknowledge.contains(&call) {
+                return Err(anyhow::anyhow!(
+                    "scripted order submission failure on call {}",
+                    call
+                ));
+            }
+        }
+        self.submitted_orders
+            .lock()
+            .map_err(|_| anyhow::anyhow!("synthetic submission log lock poisoned"))?
+            .push(request.order_ref.clone());
+        Ok(self.ack(request, now_iso()))
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        self.authorize().await?;
+        let (_, _, ack_body) = self.ack_artifact(request, &ack.accepted_at);
+        sleep_ms(self.injected_latency(request).collect_evidence).await;
+        let script = ScenarioScript::from_payload(&request.payload)?;
+        let mut phase = None;
+        if let Some(script) = &script {
+            let call = self.scripted_call(request, "collect_evidence");
+            if script.fail_collect_evidence.contains(&call) {
+                return Err(anyhow::anyhow!(
+                    "scripted collect_evidence failure on call {}",
+                    call
+                ));
+            }
+            phase = script.phase(call);
+        }
+        let ack_tag = if ack.accepted {
+            "venue_acceptance_artifact"
+        } else {
+            "venue_rejection_artifact"
+        };
         let mut observed_tags = HashSet::from([
             "order_identity".to_string(),
             "submission_timestamp".to_string(),
-            "venue_acceptance_artifact".to_string(),
+            ack_tag.to_string(),
         ]);
         let mut conflicts = Vec::new();
         let payload = &request.payload;
-        if payload.get("simulate_conflict").and_then(|v| v.as_bool()).unwrap_or(false) {
+        if payload
+            .get("simulate_conflict")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
             conflicts.push("source_value_mismatch".to_string());
         }
+        match request.claim_type {
+            ClaimType::ORDER_PLACED if !ack.accepted => {
+                conflicts.push("order_rejected".to_string())
+            }
+            ClaimType::ORDER_REJECTED if ack.accepted => {
+                conflicts.push("order_accepted".to_string())
+            }
+            ClaimType::ORDER_CANCELLED | ClaimType::POSITION_CLOSED if !ack.accepted => {
+                conflicts.push("order_rejected".to_string())
+            }
+            _ => {}
+        }
 
         let primary = EvidenceItem {
-            source_id: format!("{}-primary", venue_slug(self.venue)),
+            source_id: format!("{}-primary", self.venue.slug()),
             source_kind: acceptance_source_kind(self.venue).to_string(),
             artifact_ref: ack.acceptance_artifact_ref.clone(),
             artifact_hash: ack.acceptance_artifact_hash.clone(),
@@ -78,61 +586,367 @@ impl VenueAdapter for SyntheticVenueAdapter {
             tags: vec![
                 "order_identity".to_string(),
                 "submission_timestamp".to_string(),
-                "venue_acceptance_artifact".to_string(),
+                ack_tag.to_string(),
             ],
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: ack_body.len() as u64,
         };
 
+        let shadow_body = serde_json::json!({
+            "venue": self.venue.slug(),
+            "api_order_ref": request.order_ref
+        });
         let shadow = EvidenceItem {
-            source_id: format!("{}-api", venue_slug(self.venue)),
+            source_id: format!("{}-api", self.venue.slug()),
             source_kind: "venue_api_unsigned".to_string(),
-            artifact_ref: format!("{}://api/order/{}", venue_slug(self.venue), request.order_ref),
-            artifact_hash: hash_json(&serde_json::json!({
-                "venue": venue_slug(self.venue),
-                "api_order_ref": request.order_ref
-            })),
+            artifact_ref: format!("{}://api/order/{}", self.venue.slug(), request.order_ref),
+            artifact_hash: hash_json(&shadow_body),
             observed_at: now_iso(),
-            tags: vec!["order_identity".to_string(), "submission_timestamp".to_string()],
+            tags: vec![
+                "order_identity".to_string(),
+                "submission_timestamp".to_string(),
+            ],
+            hash_algorithm: HashAlgorithm::Sha256,
+            size_bytes: json_size(&shadow_body),
         };
 
+        let mut raw_artifacts = vec![
+            RawArtifact::of(&primary, ack_body),
+            RawArtifact::of(&shadow, serde_json::to_vec(&shadow_body)?),
+        ];
         let mut items = vec![primary, shadow];
         let mut finality_observed_at = None;
 
-        if request.claim_type == ClaimType::TRADE_EXECUTED {
+        if matches!(
+            request.claim_type,
+            ClaimType::TRADE_EXECUTED
+                | ClaimType::BEST_EXECUTION
+                | ClaimType::TRADE_BUSTED
+                | ClaimType::TRADE_AMENDED
+        ) {
             if let Some(execution_ref) = &request.execution_ref {
                 observed_tags.insert("execution_identity".to_string());
                 observed_tags.insert("execution_timestamp".to_string());
                 observed_tags.insert("execution_artifact".to_string());
-                items.push(EvidenceItem {
-                    source_id: format!("{}-execution", venue_slug(self.venue)),
-                    source_kind: acceptance_source_kind(self.venue).to_string(),
-                    artifact_ref: format!("{}://execution/{}", venue_slug(self.venue), execution_ref),
-                    artifact_hash: hash_json(&serde_json::json!({
-                        "venue": venue_slug(self.venue),
+                let (artifact_ref, artifact_hash, body) = self.artifact(
+                    request.claim_type,
+                    "execution",
+                    execution_ref,
+                    serde_json::json!({
+                        "venue": self.venue.slug(),
                         "order_ref": request.order_ref,
                         "execution_ref": execution_ref
-                    })),
+                    }),
+                );
+                let execution = EvidenceItem {
+                    source_id: format!("{}-execution", self.venue.slug()),
+                    source_kind: acceptance_source_kind(self.venue).to_string(),
+                    artifact_ref,
+                    artifact_hash,
+                    size_bytes: body.len() as u64,
                     observed_at: now_iso(),
                     tags: vec![
                         "execution_identity".to_string(),
                         "execution_timestamp".to_string(),
                         "execution_artifact".to_string(),
                     ],
-                });
+                    hash_algorithm: HashAlgorithm::Sha256,
+                };
+                raw_artifacts.push(RawArtifact::of(&execution, body));
+                items.push(execution);
                 finality_observed_at = Some(now_iso());
             }
         }
 
+        // Captured only when the request asks for a depth, around the price it declares.
+        let mut order_book = None;
+        let book_center = match request.claim_type {
+            ClaimType::BEST_EXECUTION => payload.get("reference_price"),
+            ClaimType::TRADE_EXECUTED => payload.get("expected_price"),
+            _ => None,
+        }
+        .and_then(|v| v.as_f64());
+        let book_depth = payload.get("order_book_depth").and_then(|v| v.as_u64());
+        if let (Some(center), Some(depth)) = (book_center, book_depth) {
+            let book = self.order_book(request, center, depth as usize)?;
+            observed_tags.insert(ORDER_BOOK_SNAPSHOT_TAG.to_string());
+            items.push(EvidenceItem {
+                source_id: format!("{}-book", self.venue.slug()),
+                source_kind: "venue_api_unsigned".to_string(),
+                artifact_ref: format!(
+                    "{}://book/{}/{}?depth={}",
+                    self.venue.slug(),
+                    book.market,
+                    request.order_ref,
+                    book.depth
+                ),
+                artifact_hash: book.snapshot_hash(),
+                observed_at: book.captured_at.clone(),
+                tags: vec![ORDER_BOOK_SNAPSHOT_TAG.to_string()],
+                hash_algorithm: HashAlgorithm::Sha256,
+                size_bytes: book.compress().len() as u64,
+            });
+            order_book = Some(book);
+        }
+
+        let mut price_observation = None;
+        if request.claim_type == ClaimType::BEST_EXECUTION {
+            let execution_price = payload.get("execution_price").and_then(|v| v.as_f64());
+            let reference_source = payload.get("reference_source").and_then(|v| v.as_str());
+            let reference_price = match (reference_source, &order_book) {
+                (Some(ORDER_BOOK_MID_SOURCE), Some(book)) => book.mid_price(),
+                _ => payload.get("reference_price").and_then(|v| v.as_f64()),
+            };
+            let tolerance_bps = payload.get("max_slippage_bps").and_then(|v| v.as_f64());
+            if let (Some(execution_price), Some(reference_price), Some(tolerance_bps)) =
+                (execution_price, reference_price, tolerance_bps)
+            {
+                let reference_source = payload
+                    .get("reference_source")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("venue_mid")
+                    .to_string();
+                let reference_observed_at = now_iso();
+                observed_tags.insert("reference_price_quote".to_string());
+                let quote_body = serde_json::json!({
+                    "venue": self.venue.slug(),
+                    "order_ref": request.order_ref,
+                    "reference_source": reference_source,
+                    "reference_price": reference_price,
+                    "observed_at": reference_observed_at
+                });
+                let quote = EvidenceItem {
+                    source_id: format!("{}-reference-{}", self.venue.slug(), reference_source),
+                    source_kind: "third_party_indexer".to_string(),
+                    artifact_ref: format!(
+                        "{}://quote/{}/{}",
+                        self.venue.slug(),
+                        reference_source,
+                        request.order_ref
+                    ),
+                    artifact_hash: hash_json(&quote_body),
+                    observed_at: reference_observed_at.clone(),
+                    tags: vec!["reference_price_quote".to_string()],
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    size_bytes: json_size(&quote_body),
+                };
+                raw_artifacts.push(RawArtifact::of(&quote, serde_json::to_vec(&quote_body)?));
+                items.push(quote);
+                price_observation = Some(PriceObservation {
+                    execution_price,
+                    reference_price,
+                    reference_source,
+                    reference_observed_at,
+                    tolerance_bps,
+                });
+            }
+        }
+
+        let mut correction = None;
+        if matches!(
+            request.claim_type,
+            ClaimType::TRADE_BUSTED | ClaimType::TRADE_AMENDED
+        ) {
+            if let Some(found) = request
+                .execution_ref
+                .as_deref()
+                .and_then(|execution_ref| self.find_correction(&request.order_ref, execution_ref))
+            {
+                observed_tags.insert("correction_identity".to_string());
+                observed_tags.insert("correction_artifact".to_string());
+                items.push(EvidenceItem {
+                    source_id: format!("{}-corrections", self.venue.slug()),
+                    source_kind: acceptance_source_kind(self.venue).to_string(),
+                    artifact_ref: format!(
+                        "{}://correction/{}",
+                        self.venue.slug(),
+                        found.correction_ref
+                    ),
+                    artifact_hash: hash_json(&found),
+                    observed_at: found.observed_at.clone(),
+                    tags: vec![
+                        "correction_identity".to_string(),
+                        "correction_artifact".to_string(),
+                    ],
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    size_bytes: json_size(&found),
+                });
+                correction = Some(found);
+            }
+        }
+
+        let mut cancellation = None;
+        if request.claim_type == ClaimType::ORDER_CANCELLED {
+            if let Some(found) = self.find_cancellation(&request.order_ref) {
+                observed_tags.insert("cancellation_identity".to_string());
+                observed_tags.insert("venue_cancellation_artifact".to_string());
+                let (artifact_ref, artifact_hash, body) = self.artifact(
+                    request.claim_type,
+                    "cancellation",
+                    &found.cancellation_ref,
+                    serde_json::to_value(&found)?,
+                );
+                let item = EvidenceItem {
+                    source_id: format!("{}-cancellations", self.venue.slug()),
+                    source_kind: acceptance_source_kind(self.venue).to_string(),
+                    artifact_ref,
+                    artifact_hash,
+                    observed_at: found.cancelled_at.clone(),
+                    tags: vec![
+                        "cancellation_identity".to_string(),
+                        "venue_cancellation_artifact".to_string(),
+                    ],
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    size_bytes: body.len() as u64,
+                };
+                raw_artifacts.push(RawArtifact::of(&item, body));
+                items.push(item);
+                cancellation = Some(found);
+            }
+        }
+
+        let mut position_closure = None;
+        if request.claim_type == ClaimType::POSITION_CLOSED {
+            if let Some(found) = self.find_position_closure(&request.order_ref) {
+                let tags = [
+                    "position_identity",
+                    "position_closure_artifact",
+                    "realized_pnl_artifact",
+                ];
+                observed_tags.extend(tags.iter().map(|tag| tag.to_string()));
+                let (artifact_ref, artifact_hash, body) = self.artifact(
+                    request.claim_type,
+                    "position-closure",
+                    &found.closure_ref,
+                    serde_json::to_value(&found)?,
+                );
+                let item = EvidenceItem {
+                    source_id: format!("{}-positions", self.venue.slug()),
+                    source_kind: acceptance_source_kind(self.venue).to_string(),
+                    artifact_ref,
+                    artifact_hash,
+                    observed_at: found.closed_at.clone(),
+                    tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    size_bytes: body.len() as u64,
+                };
+                raw_artifacts.push(RawArtifact::of(&item, body));
+                items.push(item);
+                position_closure = Some(found);
+            }
+        }
+
+        let mut balance_snapshot = None;
+        if request.claim_type == ClaimType::BALANCE_AT_TIMESTAMP {
+            let found = BalanceRange::from_payload(payload)
+                .ok()
+                .and_then(|range| self.find_balance_snapshot(&request.account_ref, &range));
+            if let Some(found) = found {
+                let tags = ["account_identity", "balance_snapshot_artifact"];
+                observed_tags.extend(tags.iter().map(|tag| tag.to_string()));
+                let (artifact_ref, artifact_hash, body) = self.artifact(
+                    request.claim_type,
+                    "balance-snapshot",
+                    &found.snapshot_ref,
+                    serde_json::to_value(&found)?,
+                );
+                let item = EvidenceItem {
+                    source_id: format!("{}-balances", self.venue.slug()),
+                    source_kind: acceptance_source_kind(self.venue).to_string(),
+                    artifact_ref,
+                    artifact_hash,
+                    observed_at: found.as_of.clone(),
+                    tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    size_bytes: body.len() as u64,
+                };
+                raw_artifacts.push(RawArtifact::of(&item, body));
+                items.push(item);
+                balance_snapshot = Some(found);
+            }
+        }
+
+        if let Some(scenario) = self.scenario_for(request.claim_type) {
+            observed_tags.retain(|tag| scenario.evidence_profile.contains(tag));
+        }
+
         if let Some(missing_tags) = payload.get("missing_tags").and_then(|v| v.as_array()) {
             for tag in missing_tags.iter().filter_map(|v| v.as_str()) {
                 observed_tags.remove(tag);
             }
         }
 
+        if let Some(phase) = phase {
+            if let Some(tags) = &phase.tags {
+                observed_tags.retain(|tag| tags.contains(tag));
+            }
+            if phase.conflict && !conflicts.iter().any(|c| c == "source_value_mismatch") {
+                conflicts.push("source_value_mismatch".to_string());
+            }
+        }
+
+        // The observer releases a receipt once the head reaches inclusion plus depth, so including
+        // it `depth - polls` blocks back from the current head leaves exactly `polls` to go.
+        let inclusion_height = script
+            .as_ref()
+            .and_then(|s| s.finality_after_polls)
+            .map(|polls| {
+                let depth = match finality_semantics(self.venue) {
+                    FinalitySemantics::Confirmations { depth } => u64::from(depth),
+                    _ => 0,
+                };
+                (self.chain_head.load(Ordering::SeqCst) + polls).saturating_sub(depth)
+            });
+
+        for item in &mut items {
+            if let Some((_, skew_ms)) = self
+                .clock_skews
+                .iter()
+                .find(|(source, _)| *source == item.source_id)
+            {
+                if let Ok(at) = chrono::DateTime::parse_from_rfc3339(&item.observed_at) {
+                    item.observed_at = (at.with_timezone(&chrono::Utc)
+                        + chrono::Duration::milliseconds(*skew_ms))
+                    .to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+                }
+            }
+        }
+
+        // Unscripted evidence describes a settled chain: exactly the venue's depth, and finalized
+        // commitment on Solana. A scripted inclusion is as deep as the chain head has got.
+        let depth = match finality_semantics(self.venue) {
+            FinalitySemantics::Confirmations { depth } => Some(u64::from(depth)),
+            _ => None,
+        };
+        let confirmations = depth.map(|depth| match inclusion_height {
+            Some(height) => (self.chain_head.load(Ordering::SeqCst) + 1).saturating_sub(height),
+            None => depth,
+        });
+        let commitment = (self.venue == Venue::Solana).then(|| match inclusion_height {
+            Some(_) => ChainCommitment::Confirmed,
+            None => ChainCommitment::Finalized,
+        });
+
+        let collection = items
+            .iter()
+            .map(|item| self.collection_record(item))
+            .collect();
         Ok(EvidenceBundle {
             items,
             observed_tags,
             conflicts,
             finality_observed_at,
+            inclusion_height,
+            confirmations,
+            commitment,
+            price_observation,
+            correction,
+            cancellation,
+            position_closure,
+            balance_snapshot,
+            collection,
+            order_book,
+            raw_artifacts,
         })
     }
 }
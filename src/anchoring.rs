@@ -0,0 +1,546 @@
+use crate::adapters::evm_encoding::{field, quantity};
+use crate::adapters::{HttpJsonRpc, JsonRpc};
+use crate::commitments::{evm_hash_words, hash_word};
+use crate::hashing::HashAlgorithm;
+use crate::models::{now_iso, AnchorInclusion};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorPublication {
+    pub tx_ref: String,
+    pub gas_used: u64,
+    pub fee_wei: u128,
+    #[serde(default)]
+    pub user_op_hash: Option<String>,
+    #[serde(default)]
+    pub sponsored: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    pub base_fee_wei: u128,
+    pub priority_fee_wei: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBid {
+    pub max_fee_per_gas_wei: u128,
+    pub max_priority_fee_per_gas_wei: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PublishAttempt {
+    Included(AnchorPublication),
+    NotIncluded(String),
+}
+
+#[async_trait]
+pub trait AnchorPublisher: Send + Sync {
+    async fn estimate_fees(&self) -> Result<FeeEstimate>;
+    async fn publish_root(
+        &self,
+        root: &str,
+        leaf_count: usize,
+        bid: &FeeBid,
+    ) -> Result<PublishAttempt>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyntheticAnchorPublisher;
+
+const SYNTHETIC_ANCHOR_GAS: u64 = 46_000;
+const SYNTHETIC_BASE_FEE_WEI: u128 = 1_000_000_000;
+const SYNTHETIC_PRIORITY_FEE_WEI: u128 = 100_000_000;
+
+#[async_trait]
+impl AnchorPublisher for SyntheticAnchorPublisher {
+    async fn estimate_fees(&self) -> Result<FeeEstimate> {
+        Ok(FeeEstimate {
+            base_fee_wei: SYNTHETIC_BASE_FEE_WEI,
+            priority_fee_wei: SYNTHETIC_PRIORITY_FEE_WEI,
+        })
+    }
+
+    async fn publish_root(
+        &self,
+        root: &str,
+        leaf_count: usize,
+        bid: &FeeBid,
+    ) -> Result<PublishAttempt> {
+        let gas_price = effective_gas_price(SYNTHETIC_BASE_FEE_WEI, bid);
+        Ok(PublishAttempt::Included(AnchorPublication {
+            tx_ref: format!("synthetic://anchor/{}?leaves={}", root, leaf_count),
+            gas_used: SYNTHETIC_ANCHOR_GAS,
+            fee_wei: u128::from(SYNTHETIC_ANCHOR_GAS) * gas_price,
+            ..AnchorPublication::default()
+        }))
+    }
+}
+
+pub fn effective_gas_price(base_fee_wei: u128, bid: &FeeBid) -> u128 {
+    bid.max_fee_per_gas_wei
+        .min(base_fee_wei + bid.max_priority_fee_per_gas_wei)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorFeePolicy {
+    pub max_fee_per_gas_wei: u128,
+    pub max_priority_fee_per_gas_wei: u128,
+    // Headroom over the estimated base fee so the bid survives a few blocks of base fee growth.
+    pub base_fee_multiplier_pct: u32,
+    pub bump_pct: u32,
+    pub max_attempts: u32,
+}
+
+impl Default for AnchorFeePolicy {
+    fn default() -> Self {
+        Self {
+            max_fee_per_gas_wei: 50_000_000_000,
+            max_priority_fee_per_gas_wei: 2_000_000_000,
+            base_fee_multiplier_pct: 200,
+            bump_pct: 125,
+            max_attempts: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum AnchorStatus {
+    ANCHORED,
+    DEFERRED,
+    FAILED,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum FeeAction {
+    SUBMITTED,
+    BUMPED,
+    DEFERRED,
+    INCLUDED,
+    NOT_INCLUDED,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeDecision {
+    pub attempt: u32,
+    pub action: FeeAction,
+    pub estimate: FeeEstimate,
+    pub bid: Option<FeeBid>,
+    pub capped: bool,
+    pub detail: String,
+    pub decided_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorBatch {
+    pub status: AnchorStatus,
+    pub root: String,
+    pub anchor_tx_ref: Option<String>,
+    pub gas_used: u64,
+    pub fee_wei: u128,
+    pub user_op_hash: Option<String>,
+    pub sponsored: bool,
+    pub fee_decisions: Vec<FeeDecision>,
+    pub inclusions: Vec<AnchorInclusion>,
+}
+
+pub struct FeeManagedPublish {
+    pub status: AnchorStatus,
+    pub publication: Option<AnchorPublication>,
+    pub decisions: Vec<FeeDecision>,
+}
+
+// Bids start at the estimate (with base fee headroom) clamped to the policy caps. A batch that was
+// not included is resubmitted with fees bumped by bump_pct; if the chain's current fees already
+// exceed the caps the batch is deferred instead of overpaying.
+pub async fn publish_with_fee_policy(
+    publisher: &dyn AnchorPublisher,
+    root: &str,
+    leaf_count: usize,
+    policy: &AnchorFeePolicy,
+) -> Result<FeeManagedPublish> {
+    let mut decisions = Vec::new();
+    let mut previous: Option<FeeBid> = None;
+    for attempt in 1..=policy.max_attempts.max(1) {
+        let estimate = publisher.estimate_fees().await?;
+        let required = estimate.base_fee_wei + estimate.priority_fee_wei;
+        if required > policy.max_fee_per_gas_wei
+            || estimate.priority_fee_wei > policy.max_priority_fee_per_gas_wei
+        {
+            decisions.push(FeeDecision {
+                attempt,
+                action: FeeAction::DEFERRED,
+                estimate,
+                bid: None,
+                capped: true,
+                detail: format!(
+                    "estimated fees (base {} + priority {} wei) exceed caps (max fee {}, max priority {} wei)",
+                    estimate.base_fee_wei,
+                    estimate.priority_fee_wei,
+                    policy.max_fee_per_gas_wei,
+                    policy.max_priority_fee_per_gas_wei
+                ),
+                decided_at: now_iso(),
+            });
+            return Ok(FeeManagedPublish {
+                status: AnchorStatus::DEFERRED,
+                publication: None,
+                decisions,
+            });
+        }
+
+        let uncapped = match previous {
+            None => FeeBid {
+                max_fee_per_gas_wei: estimate.base_fee_wei
+                    * u128::from(policy.base_fee_multiplier_pct)
+                    / 100
+                    + estimate.priority_fee_wei,
+                max_priority_fee_per_gas_wei: estimate.priority_fee_wei,
+            },
+            Some(prev) => FeeBid {
+                max_fee_per_gas_wei: bump(prev.max_fee_per_gas_wei, policy.bump_pct).max(required),
+                max_priority_fee_per_gas_wei: bump(
+                    prev.max_priority_fee_per_gas_wei,
+                    policy.bump_pct,
+                )
+                .max(estimate.priority_fee_wei),
+            },
+        };
+        let bid = FeeBid {
+            max_fee_per_gas_wei: uncapped.max_fee_per_gas_wei.min(policy.max_fee_per_gas_wei),
+            max_priority_fee_per_gas_wei: uncapped
+                .max_priority_fee_per_gas_wei
+                .min(policy.max_priority_fee_per_gas_wei),
+        };
+        // A replacement must raise both fees; once either is pinned at its cap, retrying is pointless.
+        if previous.is_some_and(|p| {
+            bid.max_fee_per_gas_wei <= p.max_fee_per_gas_wei
+                || bid.max_priority_fee_per_gas_wei <= p.max_priority_fee_per_gas_wei
+        }) {
+            decisions.push(FeeDecision {
+                attempt,
+                action: FeeAction::DEFERRED,
+                estimate,
+                bid: Some(bid),
+                capped: true,
+                detail: "fee bid cannot be raised further within the policy caps".to_string(),
+                decided_at: now_iso(),
+            });
+            return Ok(FeeManagedPublish {
+                status: AnchorStatus::DEFERRED,
+                publication: None,
+                decisions,
+            });
+        }
+        decisions.push(FeeDecision {
+            attempt,
+            action: if previous.is_some() {
+                FeeAction::BUMPED
+            } else {
+                FeeAction::SUBMITTED
+            },
+            estimate,
+            bid: Some(bid),
+            capped: bid != uncapped,
+            detail: format!(
+                "max fee {} wei, max priority fee {} wei",
+                bid.max_fee_per_gas_wei, bid.max_priority_fee_per_gas_wei
+            ),
+            decided_at: now_iso(),
+        });
+
+        match publisher.publish_root(root, leaf_count, &bid).await? {
+            PublishAttempt::Included(publication) => {
+                decisions.push(FeeDecision {
+                    attempt,
+                    action: FeeAction::INCLUDED,
+                    estimate,
+                    bid: Some(bid),
+                    capped: bid != uncapped,
+                    detail: format!(
+                        "included as {} for {} wei",
+                        publication.tx_ref, publication.fee_wei
+                    ),
+                    decided_at: now_iso(),
+                });
+                return Ok(FeeManagedPublish {
+                    status: AnchorStatus::ANCHORED,
+                    publication: Some(publication),
+                    decisions,
+                });
+            }
+            PublishAttempt::NotIncluded(reason) => decisions.push(FeeDecision {
+                attempt,
+                action: FeeAction::NOT_INCLUDED,
+                estimate,
+                bid: Some(bid),
+                capped: bid != uncapped,
+                detail: reason,
+                decided_at: now_iso(),
+            }),
+        }
+        previous = Some(bid);
+    }
+    Ok(FeeManagedPublish {
+        status: AnchorStatus::FAILED,
+        publication: None,
+        decisions,
+    })
+}
+
+fn bump(value: u128, bump_pct: u32) -> u128 {
+    // EIP-1559 replacement transactions must raise both fees by at least 10%.
+    let bumped = value * u128::from(bump_pct.max(110)) / 100;
+    bumped.max(value + 1)
+}
+
+// Leaves are keccak256(abi.encode(receipt_hash)) and parents hash the sorted pair of children, so
+// membership can be checked on-chain with a standard sorted-pair Merkle proof verifier.
+pub fn receipt_leaf(receipt_hash: &str) -> String {
+    evm_hash_words(&[hash_word(receipt_hash)])
+}
+
+fn hash_pair(a: &str, b: &str) -> String {
+    let (left, right) = if hash_word(a) <= hash_word(b) {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    evm_hash_words(&[hash_word(left), hash_word(right)])
+}
+
+pub fn merkle_tree(receipt_hashes: &[String]) -> Result<(String, Vec<Vec<String>>)> {
+    if receipt_hashes.is_empty() {
+        bail!("cannot anchor an empty batch");
+    }
+    let leaves: Vec<String> = receipt_hashes.iter().map(|h| receipt_leaf(h)).collect();
+    let mut paths = vec![Vec::new(); leaves.len()];
+    let mut positions: Vec<usize> = (0..leaves.len()).collect();
+    let mut level = leaves;
+    while level.len() > 1 {
+        for (leaf_idx, pos) in positions.iter_mut().enumerate() {
+            let sibling = *pos ^ 1;
+            if sibling < level.len() {
+                paths[leaf_idx].push(level[sibling].clone());
+            }
+            *pos /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(a, b),
+                [a] => a.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    Ok((level.remove(0), paths))
+}
+
+pub fn verify_inclusion(receipt_hash: &str, inclusion: &AnchorInclusion) -> bool {
+    let leaf = receipt_leaf(receipt_hash);
+    if leaf != inclusion.leaf {
+        return false;
+    }
+    let computed = inclusion
+        .merkle_path
+        .iter()
+        .fold(leaf, |acc, sibling| hash_pair(&acc, sibling));
+    computed == inclusion.anchored_root
+}
+
+// Emitted by the anchor contract's anchorRoot(bytes32,uint256), with the root as the indexed topic.
+pub const ROOT_ANCHORED_EVENT: &str = "RootAnchored(bytes32,uint256)";
+pub const DEFAULT_ANCHOR_CONFIRMATIONS: u64 = 12;
+
+pub fn root_anchored_topic() -> String {
+    format!(
+        "0x{}",
+        hex::encode(HashAlgorithm::Keccak256.digest_bytes(ROOT_ANCHORED_EVENT.as_bytes()))
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum AnchorTxVerdict {
+    CONFIRMED,
+    NOT_FOUND,
+    REVERTED,
+    ROOT_MISSING,
+    UNCONFIRMED,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorTxCheck {
+    pub verdict: AnchorTxVerdict,
+    pub anchor_tx_ref: String,
+    pub anchored_root: String,
+    pub block_number: Option<u64>,
+    pub confirmations: u64,
+}
+
+// Checks an inclusion's anchor transaction against the chain itself: the transaction exists and
+// succeeded, it emitted RootAnchored for the inclusion's root (from the anchor contract, when one is
+// given), and it is buried at least `min_confirmations` deep. The Merkle path is checked separately
+// by `verify_inclusion`; this covers the half an offline verifier cannot see.
+pub struct AnchorTxChecker {
+    rpc: Arc<dyn JsonRpc>,
+    min_confirmations: u64,
+    anchor_contract: Option<String>,
+    // Receipts in one batch share a transaction. Only confirmed results are kept; confirmations
+    // never decrease, but anything short of CONFIRMED may change on the next look.
+    confirmed: Mutex<HashMap<(String, String), AnchorTxCheck>>,
+}
+
+impl std::fmt::Debug for AnchorTxChecker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnchorTxChecker")
+            .field("endpoint", &self.rpc.endpoint())
+            .field("min_confirmations", &self.min_confirmations)
+            .field("anchor_contract", &self.anchor_contract)
+            .finish()
+    }
+}
+
+impl AnchorTxChecker {
+    pub fn new(rpc: Arc<dyn JsonRpc>) -> Self {
+        Self {
+            rpc,
+            min_confirmations: DEFAULT_ANCHOR_CONFIRMATIONS,
+            anchor_contract: None,
+            confirmed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn connect(url: &str) -> Result<Self> {
+        Ok(Self::new(Arc::new(HttpJsonRpc::new(url)?)))
+    }
+
+    pub fn with_min_confirmations(mut self, min_confirmations: u64) -> Self {
+        self.min_confirmations = min_confirmations.max(1);
+        self
+    }
+
+    pub fn with_anchor_contract(mut self, address: impl Into<String>) -> Self {
+        self.anchor_contract = Some(address.into().to_ascii_lowercase());
+        self
+    }
+
+    pub fn endpoint(&self) -> String {
+        self.rpc.endpoint()
+    }
+
+    // Errors only when the node cannot be asked; anything the node answers is a verdict.
+    pub async fn check(&self, inclusion: &AnchorInclusion) -> Result<AnchorTxCheck> {
+        let key = (
+            inclusion.anchor_tx_ref.to_ascii_lowercase(),
+            inclusion.anchored_root.to_ascii_lowercase(),
+        );
+        if let Some(check) = self
+            .confirmed
+            .lock()
+            .expect("anchor check cache poisoned")
+            .get(&key)
+        {
+            return Ok(check.clone());
+        }
+        let mut check = AnchorTxCheck {
+            verdict: AnchorTxVerdict::NOT_FOUND,
+            anchor_tx_ref: inclusion.anchor_tx_ref.clone(),
+            anchored_root: inclusion.anchored_root.clone(),
+            block_number: None,
+            confirmations: 0,
+        };
+        let receipt = self
+            .rpc
+            .call("eth_getTransactionReceipt", json!([key.0]))
+            .await?;
+        if receipt.is_null() {
+            return Ok(check);
+        }
+        let block_number = quantity(&receipt, "blockNumber")?;
+        let head = quantity(
+            &json!({ "head": self.rpc.call("eth_blockNumber", json!([])).await? }),
+            "head",
+        )?;
+        check.block_number = Some(block_number);
+        check.confirmations = if head >= block_number {
+            head - block_number + 1
+        } else {
+            0
+        };
+        check.verdict = if field(&receipt, "status")? != "0x1" {
+            AnchorTxVerdict::REVERTED
+        } else if !self.emits_root(&receipt, &key.1) {
+            AnchorTxVerdict::ROOT_MISSING
+        } else if check.confirmations < self.min_confirmations {
+            AnchorTxVerdict::UNCONFIRMED
+        } else {
+            AnchorTxVerdict::CONFIRMED
+        };
+        if check.verdict == AnchorTxVerdict::CONFIRMED {
+            self.confirmed
+                .lock()
+                .expect("anchor check cache poisoned")
+                .insert(key, check.clone());
+        }
+        Ok(check)
+    }
+
+    fn emits_root(&self, receipt: &Value, root: &str) -> bool {
+        let topic = root_anchored_topic();
+        let text = |value: &Value| value.as_str().map(str::to_ascii_lowercase);
+        receipt["logs"].as_array().into_iter().flatten().any(|log| {
+            let topics: Vec<Option<String>> = log["topics"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(text)
+                .collect();
+            let from_contract = self
+                .anchor_contract
+                .as_ref()
+                .is_none_or(|contract| text(&log["address"]).as_ref() == Some(contract));
+            from_contract
+                && topics.first() == Some(&Some(topic.clone()))
+                && topics.get(1) == Some(&Some(root.to_string()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::HashAlgorithm;
+
+    #[test]
+    fn every_leaf_verifies_against_the_root() {
+        for size in 1..=7 {
+            let hashes: Vec<String> = (0..size)
+                .map(|i| HashAlgorithm::Sha256.hash_str(&i.to_string()))
+                .collect();
+            let (root, paths) = merkle_tree(&hashes).expect("tree");
+            for (idx, hash) in hashes.iter().enumerate() {
+                let inclusion = AnchorInclusion {
+                    anchored_root: root.clone(),
+                    anchor_tx_ref: "tx".to_string(),
+                    leaf: receipt_leaf(hash),
+                    leaf_index: idx,
+                    merkle_path: paths[idx].clone(),
+                };
+                assert!(verify_inclusion(hash, &inclusion));
+                assert!(!verify_inclusion(
+                    &HashAlgorithm::Sha256.hash_str("other"),
+                    &inclusion
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use crate::anchoring::{verify_inclusion, AnchorTxChecker, AnchorTxVerdict};
+use crate::models::{now_iso, ReceiptStatus, ZKReceipt};
+use crate::schema::validate_receipt_json;
+use crate::signing::KeyRing;
+use crate::verifier::OffchainVerifier;
+use crate::versioning::ReceiptVersionSupport;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+pub const DEFAULT_AUDIT_CONCURRENCY: usize = 8;
+const SLOWEST_CHECKS: usize = 10;
+
+// One receipt as found on disk. `location` is the file, plus the line or index for receipts read
+// from a JSON Lines or JSON array archive.
+#[derive(Debug, Clone)]
+pub struct ReceiptDocument {
+    pub location: String,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckTiming {
+    pub location: String,
+    pub receipt_id: Option<String>,
+    pub check: String,
+    pub elapsed_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptAudit {
+    pub location: String,
+    pub receipt_id: Option<String>,
+    pub status: Option<ReceiptStatus>,
+    pub failures: Vec<String>,
+    pub checks: Vec<CheckTiming>,
+}
+
+impl ReceiptAudit {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditFailure {
+    pub location: String,
+    pub receipt_id: Option<String>,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub source: String,
+    pub checked_at: String,
+    pub signer_keys: String,
+    // The node anchor transactions were checked against, if any.
+    #[serde(default)]
+    pub anchor_rpc: Option<String>,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub by_status: BTreeMap<String, usize>,
+    pub failure_reasons: BTreeMap<String, usize>,
+    pub failures: Vec<AuditFailure>,
+    pub slowest_checks: Vec<CheckTiming>,
+    pub elapsed_ms: f64,
+}
+
+// Reads every receipt under `path`: a directory is walked for .json and .jsonl files; a single
+// file is read as one receipt, a JSON array of receipts, or JSON Lines.
+pub fn load_receipt_documents(path: &Path) -> Result<Vec<ReceiptDocument>> {
+    let mut documents = Vec::new();
+    if path.is_dir() {
+        let mut files = Vec::new();
+        walk(path, &mut files)?;
+        files.sort();
+        for file in files {
+            read_archive(&file, &mut documents)?;
+        }
+    } else {
+        read_archive(path, &mut documents)?;
+    }
+    Ok(documents)
+}
+
+fn walk(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk(&path, files)?;
+        } else if matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("json" | "jsonl")
+        ) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn read_archive(file: &Path, documents: &mut Vec<ReceiptDocument>) -> Result<()> {
+    let contents = std::fs::read_to_string(file)
+        .with_context(|| format!("failed to read {}", file.display()))?;
+    let name = file.display().to_string();
+    if file.extension().and_then(|e| e.to_str()) == Some("jsonl") {
+        for (n, line) in contents
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+        {
+            documents.push(ReceiptDocument {
+                location: format!("{}:{}", name, n + 1),
+                contents: line.to_string(),
+            });
+        }
+        return Ok(());
+    }
+    if let Ok(Value::Array(items)) = serde_json::from_str::<Value>(&contents) {
+        for (n, item) in items.iter().enumerate() {
+            documents.push(ReceiptDocument {
+                location: format!("{}[{}]", name, n),
+                contents: item.to_string(),
+            });
+        }
+        return Ok(());
+    }
+    documents.push(ReceiptDocument {
+        location: name,
+        contents,
+    });
+    Ok(())
+}
+
+// Checks receipts the way a counterparty would: the format is readable, both hashes recompute,
+// the signature is by a published key (or, without a key manifest, by the key the receipt names),
+// proved receipts carry a proof that verifies, and anchored ones an inclusion path to their root.
+// With an anchor checker it also asks a chain node whether that root was really anchored.
+#[derive(Debug, Clone)]
+pub struct ReceiptAuditor {
+    keys: Option<KeyRing>,
+    anchors: Option<Arc<AnchorTxChecker>>,
+    versions: ReceiptVersionSupport,
+    verifier: OffchainVerifier,
+    concurrency: usize,
+}
+
+impl Default for ReceiptAuditor {
+    fn default() -> Self {
+        Self {
+            keys: None,
+            anchors: None,
+            versions: ReceiptVersionSupport::default(),
+            verifier: OffchainVerifier::default(),
+            concurrency: DEFAULT_AUDIT_CONCURRENCY,
+        }
+    }
+}
+
+impl ReceiptAuditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_keys(mut self, keys: KeyRing) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    pub fn with_anchor_checker(mut self, checker: AnchorTxChecker) -> Self {
+        self.anchors = Some(Arc::new(checker));
+        self
+    }
+
+    // Carries the zkVM host keys this deployment pinned.
+    pub fn with_verifier(mut self, verifier: OffchainVerifier) -> Self {
+        self.verifier = verifier;
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub async fn audit(&self, source: &str, documents: Vec<ReceiptDocument>) -> AuditReport {
+        let started = Instant::now();
+        let permits = Arc::new(Semaphore::new(self.concurrency));
+        let auditor = Arc::new(self.clone());
+        let mut tasks = JoinSet::new();
+        for (index, document) in documents.into_iter().enumerate() {
+            let (auditor, permits) = (auditor.clone(), permits.clone());
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                (index, auditor.audit_document(document).await)
+            });
+        }
+        let mut audits = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(audit) = joined {
+                audits.push(audit);
+            }
+        }
+        audits.sort_by_key(|(index, _)| *index);
+        let audits: Vec<ReceiptAudit> = audits.into_iter().map(|(_, audit)| audit).collect();
+        self.report(source, audits, started.elapsed().as_secs_f64() * 1000.0)
+    }
+
+    pub async fn audit_document(&self, document: ReceiptDocument) -> ReceiptAudit {
+        let mut audit = ReceiptAudit {
+            location: document.location,
+            receipt_id: None,
+            status: None,
+            failures: vec![],
+            checks: vec![],
+        };
+        let parsed = timed(&mut audit, "parse", || {
+            serde_json::from_str::<Value>(&document.contents)
+        });
+        let Ok(value) = parsed else {
+            audit.failures.push("unreadable".to_string());
+            return audit;
+        };
+        let schema_issues = timed(&mut audit, "schema", || validate_receipt_json(&value));
+        let receipt = match serde_json::from_value::<ZKReceipt>(value) {
+            Ok(receipt) => receipt,
+            Err(_) => {
+                audit.failures.push("unreadable".to_string());
+                return audit;
+            }
+        };
+        if !schema_issues.is_empty() {
+            audit.failures.push("schema_invalid".to_string());
+        }
+        audit.receipt_id = Some(receipt.receipt_id.clone());
+        audit.status = Some(receipt.status);
+        for check in &mut audit.checks {
+            check.receipt_id = audit.receipt_id.clone();
+        }
+
+        if timed(&mut audit, "version", || {
+            self.versions.check_readable(&receipt)
+        })
+        .is_err()
+        {
+            audit.failures.push("unsupported_version".to_string());
+            return audit;
+        }
+        let integrity = timed(&mut audit, "integrity", || {
+            let mut integrity = receipt.verify_integrity(None);
+            if let Some(keys) = &self.keys {
+                integrity.signature_valid = keys.verify_receipt_signature(&receipt);
+            }
+            integrity
+        });
+        if !integrity.schema_hash_valid {
+            audit.failures.push("schema_hash_mismatch".to_string());
+        }
+        if !integrity.receipt_hash_valid {
+            audit.failures.push("receipt_hash_mismatch".to_string());
+        }
+        if !integrity.signature_valid {
+            audit.failures.push("signature_invalid".to_string());
+        }
+        if receipt.status == ReceiptStatus::PROVED {
+            let started = Instant::now();
+            let proof_valid = self.verifier.verify(&receipt).await;
+            record(&mut audit, "proof", started);
+            if !proof_valid {
+                audit.failures.push("proof_invalid".to_string());
+            }
+        }
+        // Anchored modes only issue once the inclusion path is attached, so a proved one without it
+        // was never anchored.
+        if receipt.status == ReceiptStatus::PROVED
+            && receipt.proof.verification_mode.requires_anchoring()
+            && receipt.proof.anchor_inclusion.is_none()
+        {
+            audit.failures.push("anchor_missing".to_string());
+        }
+        if let Some(inclusion) = &receipt.proof.anchor_inclusion {
+            if !timed(&mut audit, "anchor_inclusion", || {
+                verify_inclusion(&receipt.integrity.receipt_hash, inclusion)
+            }) {
+                audit.failures.push("anchor_inclusion_invalid".to_string());
+            } else if let Some(anchors) = &self.anchors {
+                let started = Instant::now();
+                let checked = anchors.check(inclusion).await;
+                record(&mut audit, "anchor_tx", started);
+                let failure = match checked.map(|check| check.verdict) {
+                    Ok(AnchorTxVerdict::CONFIRMED) => None,
+                    Ok(AnchorTxVerdict::NOT_FOUND) => Some("anchor_tx_not_found"),
+                    Ok(AnchorTxVerdict::REVERTED) => Some("anchor_tx_reverted"),
+                    Ok(AnchorTxVerdict::ROOT_MISSING) => Some("anchor_root_not_in_tx"),
+                    Ok(AnchorTxVerdict::UNCONFIRMED) => Some("anchor_unconfirmed"),
+                    Err(_) => Some("anchor_rpc_unavailable"),
+                };
+                audit.failures.extend(failure.map(str::to_string));
+            }
+        }
+        audit
+    }
+
+    fn report(&self, source: &str, audits: Vec<ReceiptAudit>, elapsed_ms: f64) -> AuditReport {
+        let mut by_status = BTreeMap::new();
+        let mut failure_reasons = BTreeMap::new();
+        let mut failures = Vec::new();
+        let mut slowest_checks = Vec::new();
+        for audit in &audits {
+            let status = audit
+                .status
+                .map_or("UNREADABLE".to_string(), |s| format!("{:?}", s));
+            *by_status.entry(status).or_insert(0) += 1;
+            for reason in &audit.failures {
+                *failure_reasons.entry(reason.clone()).or_insert(0) += 1;
+            }
+            if !audit.passed() {
+                failures.push(AuditFailure {
+                    location: audit.location.clone(),
+                    receipt_id: audit.receipt_id.clone(),
+                    reasons: audit.failures.clone(),
+                });
+            }
+            slowest_checks.extend(audit.checks.iter().cloned());
+        }
+        slowest_checks.sort_by(|a, b| b.elapsed_ms.total_cmp(&a.elapsed_ms));
+        slowest_checks.truncate(SLOWEST_CHECKS);
+        let passed = audits.iter().filter(|a| a.passed()).count();
+        AuditReport {
+            source: source.to_string(),
+            checked_at: now_iso(),
+            signer_keys: if self.keys.is_some() {
+                "manifest"
+            } else {
+                "embedded"
+            }
+            .to_string(),
+            anchor_rpc: self.anchors.as_ref().map(|anchors| anchors.endpoint()),
+            total: audits.len(),
+            passed,
+            failed: audits.len() - passed,
+            by_status,
+            failure_reasons,
+            failures,
+            slowest_checks,
+            elapsed_ms,
+        }
+    }
+}
+
+fn timed<T>(audit: &mut ReceiptAudit, check: &str, run: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = run();
+    record(audit, check, started);
+    result
+}
+
+fn record(audit: &mut ReceiptAudit, check: &str, started: Instant) {
+    audit.checks.push(CheckTiming {
+        location: audit.location.clone(),
+        receipt_id: audit.receipt_id.clone(),
+        check: check.to_string(),
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+    });
+}
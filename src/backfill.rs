@@ -0,0 +1,309 @@
+use crate::engine::ReceiptEngine;
+use crate::models::{ProofRequest, ReceiptStatus};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackfillFormat {
+    Jsonl,
+    Csv,
+}
+
+impl BackfillFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => BackfillFormat::Csv,
+            _ => BackfillFormat::Jsonl,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackfillRecord {
+    pub line: usize,
+    pub request: Result<ProofRequest, String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillError {
+    pub line: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillCheckpoint {
+    pub next_line: usize,
+    pub submitted: usize,
+    pub proved: usize,
+    pub non_provable: usize,
+    pub unfinished: usize,
+    pub errors: Vec<BackfillError>,
+    // Receipts submitted but not yet counted; a resumed run waits for these first.
+    #[serde(default)]
+    pub awaiting: Vec<String>,
+}
+
+impl BackfillCheckpoint {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse checkpoint {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("failed to move checkpoint to {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackfillOptions {
+    pub requests_per_second: Option<f64>,
+    pub batch_size: usize,
+    pub wait_timeout: Duration,
+}
+
+impl Default for BackfillOptions {
+    fn default() -> Self {
+        Self {
+            requests_per_second: None,
+            batch_size: 100,
+            wait_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+pub fn parse_backfill_input(text: &str, format: BackfillFormat) -> Result<Vec<BackfillRecord>> {
+    match format {
+        BackfillFormat::Jsonl => Ok(text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| BackfillRecord {
+                line: idx + 1,
+                request: serde_json::from_str::<Value>(line)
+                    .map_err(|e| e.to_string())
+                    .and_then(|row| request_from_row(row.as_object().cloned().unwrap_or_default())),
+            })
+            .collect()),
+        BackfillFormat::Csv => {
+            let mut lines = text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty());
+            let (_, header) = lines
+                .next()
+                .ok_or_else(|| anyhow!("backfill CSV is missing a header row"))?;
+            let columns = split_csv_line(header)?;
+            Ok(lines
+                .map(|(idx, line)| BackfillRecord {
+                    line: idx + 1,
+                    request: split_csv_line(line)
+                        .map_err(|e| e.to_string())
+                        .and_then(|cells| csv_row(&columns, cells))
+                        .and_then(request_from_row),
+                })
+                .collect())
+        }
+    }
+}
+
+// Known columns map onto ProofRequest fields; everything else lands in the payload.
+fn request_from_row(mut row: Map<String, Value>) -> Result<ProofRequest, String> {
+    let mut take = |field: &str| row.remove(field).filter(|v| !v.is_null() && v != "");
+    let venue = take("venue").ok_or("missing venue")?;
+    let claim_type = take("claim_type").ok_or("missing claim_type")?;
+    let account_ref = take("account_ref").ok_or("missing account_ref")?;
+    let order_ref = take("order_ref").ok_or("missing order_ref")?;
+    let execution_ref = take("execution_ref");
+    let explicit_payload = take("payload");
+    let mut payload = match explicit_payload {
+        Some(Value::Object(map)) => map,
+        Some(_) => return Err("payload must be a JSON object".to_string()),
+        None => Map::new(),
+    };
+    payload.extend(row);
+    Ok(ProofRequest {
+        execution_ref: execution_ref.map(as_string),
+        payload: Value::Object(payload),
+        ..ProofRequest::new(
+            serde_json::from_value(venue).map_err(|e| format!("invalid venue: {}", e))?,
+            serde_json::from_value(claim_type).map_err(|e| format!("invalid claim_type: {}", e))?,
+            as_string(account_ref),
+            as_string(order_ref),
+        )
+    })
+}
+
+fn as_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+fn csv_row(columns: &[String], cells: Vec<String>) -> Result<Map<String, Value>, String> {
+    if cells.len() != columns.len() {
+        return Err(format!(
+            "expected {} columns, found {}",
+            columns.len(),
+            cells.len()
+        ));
+    }
+    Ok(columns
+        .iter()
+        .cloned()
+        .zip(cells)
+        .map(|(column, cell)| {
+            let value = match (column.as_str(), cell.parse::<f64>()) {
+                ("account_ref" | "order_ref" | "execution_ref", _) => Value::String(cell),
+                (_, Ok(number)) => serde_json::json!(number),
+                (_, Err(_)) => Value::String(cell),
+            };
+            (column, value)
+        })
+        .collect())
+}
+
+fn split_csv_line(line: &str) -> Result<Vec<String>> {
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => cells.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("unterminated quoted field");
+    }
+    cells.push(current.trim().to_string());
+    Ok(cells)
+}
+
+pub async fn run_backfill(
+    engine: &ReceiptEngine,
+    records: &[BackfillRecord],
+    checkpoint_path: Option<&Path>,
+    options: &BackfillOptions,
+) -> Result<BackfillCheckpoint> {
+    let mut checkpoint = match checkpoint_path {
+        Some(path) => BackfillCheckpoint::load(path)?,
+        None => BackfillCheckpoint::default(),
+    };
+    let pending: Vec<&BackfillRecord> = records
+        .iter()
+        .filter(|r| r.line >= checkpoint.next_line)
+        .collect();
+    let interval = options
+        .requests_per_second
+        .filter(|rps| *rps > 0.0)
+        .map(|rps| Duration::from_secs_f64(1.0 / rps));
+
+    let save = |checkpoint: &BackfillCheckpoint| match checkpoint_path {
+        Some(path) => checkpoint.save(path),
+        None => Ok(()),
+    };
+
+    if !checkpoint.awaiting.is_empty() {
+        settle(engine, &mut checkpoint, options).await;
+        save(&checkpoint)?;
+    }
+    // The checkpoint moves past each row as it is submitted, so a rerun after a crash neither skips
+    // a row nor submits one twice. A row the engine refuses is recorded and skipped like one that
+    // does not parse.
+    for batch in pending.chunks(options.batch_size.max(1)) {
+        for record in batch {
+            let submitted = match &record.request {
+                Ok(request) => {
+                    let submitted = engine
+                        .submit(request.clone())
+                        .await
+                        .map_err(|err| format!("{:#}", err));
+                    if let Some(interval) = interval {
+                        tokio::time::sleep(interval).await;
+                    }
+                    submitted
+                }
+                Err(error) => Err(error.clone()),
+            };
+            match submitted {
+                Ok(receipt_id) => {
+                    checkpoint.submitted += 1;
+                    checkpoint.awaiting.push(receipt_id);
+                }
+                Err(error) => checkpoint.errors.push(BackfillError {
+                    line: record.line,
+                    error,
+                }),
+            }
+            checkpoint.next_line = record.line + 1;
+            save(&checkpoint)?;
+        }
+        settle(engine, &mut checkpoint, options).await;
+        save(&checkpoint)?;
+    }
+    Ok(checkpoint)
+}
+
+async fn settle(
+    engine: &ReceiptEngine,
+    checkpoint: &mut BackfillCheckpoint,
+    options: &BackfillOptions,
+) {
+    for receipt_id in std::mem::take(&mut checkpoint.awaiting) {
+        match engine
+            .wait_for_receipt(&receipt_id, options.wait_timeout)
+            .await
+        {
+            Ok(receipt) if receipt.status == ReceiptStatus::PROVED => checkpoint.proved += 1,
+            Ok(_) => checkpoint.non_provable += 1,
+            Err(_) => checkpoint.unfinished += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ClaimType, Venue};
+
+    #[test]
+    fn csv_rows_map_known_columns_and_payload_extras() {
+        let records = parse_backfill_input(
+            "venue,claim_type,account_ref,order_ref,execution_ref,notes\n\
+             base,TRADE_EXECUTED,acct-1,42,exec-1,\"fill, partial\"\n\
+             nowhere,ORDER_PLACED,acct-2,o-2,,\n",
+            BackfillFormat::Csv,
+        )
+        .expect("parse");
+        let request = records[0].request.as_ref().expect("valid row");
+        assert_eq!(request.venue, Venue::Base);
+        assert_eq!(request.claim_type, ClaimType::TRADE_EXECUTED);
+        assert_eq!(request.order_ref, "42");
+        assert_eq!(request.payload["notes"], "fill, partial");
+        assert_eq!(records[1].line, 3);
+        assert!(records[1]
+            .request
+            .as_ref()
+            .unwrap_err()
+            .contains("invalid venue"));
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Context, Result};
+use std::path::PathBuf;
+use zkputer::backfill::{parse_backfill_input, run_backfill, BackfillFormat, BackfillOptions};
+use zkputer::ReceiptEngineBuilder;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let input = positional_args(&args)
+        .first()
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("usage: backfill <orders.jsonl|orders.csv> [--checkpoint PATH] [--rate N] [--batch N]"))?;
+    let checkpoint = flag_value(&args, "--checkpoint").map(PathBuf::from);
+    let mut options = BackfillOptions::default();
+    if let Some(rate) = flag_value(&args, "--rate") {
+        options.requests_per_second = Some(rate.parse().context("--rate must be a number")?);
+    }
+    if let Some(batch) = flag_value(&args, "--batch") {
+        options.batch_size = batch.parse().context("--batch must be an integer")?;
+    }
+
+    let text = std::fs::read_to_string(&input)
+        .with_context(|| format!("failed to read {}", input.display()))?;
+    let records = parse_backfill_input(&text, BackfillFormat::from_path(&input))?;
+
+    let engine = ReceiptEngineBuilder::from_env()?.build().await?;
+    let summary = run_backfill(&engine, &records, checkpoint.as_deref(), &options).await?;
+    println!("{}", serde_json::to_string_pretty(&summary)?);
+    Ok(())
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|idx| args.get(idx + 1))
+        .map(|s| s.as_str())
+}
+
+fn positional_args(args: &[String]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("--") {
+            iter.next();
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
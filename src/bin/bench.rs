@@ -0,0 +1,193 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zkputer::adapters::{
+    load_workload_scenarios, FinalitySemantics, SyntheticVenueAdapter, VenueAdapter,
+    WorkloadScenario, WORKLOADS_PATH,
+};
+use zkputer::finality::{FinalityObserver, TimedChainHead};
+use zkputer::models::{ClaimType, ProofRequest};
+use zkputer::prover::{build_mvp_prover, DelayedProver, ProverStrategy};
+use zkputer::slo::{
+    attribute_stages, evaluate_slos, load_latency_profiles, load_slo_document,
+    BackpressureObservation, LatencyProfile, ReceiptSample, LATENCY_PROFILES_PATH, SLOS_PATH,
+};
+use zkputer::{Config, ReceiptEngineBuilder};
+
+const FINALITY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const HEALTH_PROBE_INTERVAL: Duration = Duration::from_millis(5);
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let slos = load_slo_document(&root.join(SLOS_PATH))?;
+    let scenarios = load_workload_scenarios(&root.join(WORKLOADS_PATH))?;
+    let profiles_path = std::env::var("ZKPUTER_LATENCY_PROFILES")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| root.join(LATENCY_PROFILES_PATH));
+    let profiles = load_latency_profiles(&profiles_path)?;
+    let receipts_per_scenario = std::env::var("ZKPUTER_BENCH_RECEIPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10);
+
+    let mut failures = 0;
+    for profile in &profiles {
+        let (samples, backpressure) =
+            run_profile(profile, &scenarios, receipts_per_scenario).await?;
+        println!(
+            "Profile {} ({} receipts, peak finality waiters {}):",
+            profile.id,
+            samples.len(),
+            backpressure.peak_finality_waiters
+        );
+        for check in evaluate_slos(&slos, profile, &samples, &backpressure) {
+            println!(
+                "  - {} {}: measured {:.3}, limit {:.3}",
+                if check.passed { "PASS" } else { "FAIL" },
+                check.name,
+                check.measured,
+                check.limit
+            );
+            if !check.passed {
+                failures += 1;
+            }
+        }
+        for stage in attribute_stages(&samples) {
+            println!(
+                "  - stage {}: p95 {:.1} ms over {} receipts",
+                stage.stage, stage.p95_ms, stage.samples
+            );
+        }
+    }
+    if failures > 0 {
+        bail!("{} SLO check(s) failed", failures);
+    }
+    println!("All SLO checks passed.");
+    Ok(())
+}
+
+async fn run_profile(
+    profile: &LatencyProfile,
+    scenarios: &[WorkloadScenario],
+    receipts_per_scenario: usize,
+) -> Result<(Vec<ReceiptSample>, BackpressureObservation)> {
+    let scenarios: Vec<WorkloadScenario> = scenarios
+        .iter()
+        .cloned()
+        .map(|mut s| {
+            s.latency_ms = profile.latency_ms;
+            s
+        })
+        .collect();
+    let mut venues: Vec<_> = scenarios.iter().map(|s| s.venue).collect();
+    venues.sort_by_key(|v| format!("{:?}", v));
+    venues.dedup();
+
+    // Chain heads advance so that a receipt reaches its venue's confirmation depth after the
+    // profile's finality latency.
+    let mut observer = FinalityObserver::new(FINALITY_POLL_INTERVAL);
+    let mut adapters: Vec<Arc<dyn VenueAdapter>> = Vec::new();
+    for venue in venues {
+        let adapter = SyntheticVenueAdapter::new(venue).with_workload_scenarios(scenarios.clone());
+        if let FinalitySemantics::Confirmations { depth } = adapter.capabilities().finality {
+            let block_time = Duration::from_millis(profile.latency_ms.finality) / depth.max(1);
+            observer = observer.with_head_source(venue, Arc::new(TimedChainHead::new(block_time)));
+        }
+        adapters.push(Arc::new(adapter));
+    }
+    let prover = DelayedProver::new(
+        build_mvp_prover(ProverStrategy::Sp1Only),
+        Duration::from_millis(profile.latency_ms.prove),
+    );
+    let config = Config {
+        venues: adapters.iter().map(|a| a.venue()).collect(),
+        ..Config::default()
+    };
+    let builder = adapters.into_iter().fold(
+        ReceiptEngineBuilder::new(config),
+        ReceiptEngineBuilder::with_adapter,
+    );
+    let engine = Arc::new(
+        builder
+            .with_prover(Arc::new(prover))
+            .build()
+            .await?
+            .with_finality_observer(
+                observer.clone(),
+                Duration::from_millis(profile.finality_timeout_ms),
+            ),
+    );
+
+    let done = Arc::new(AtomicBool::new(false));
+    let peak_waiters = Arc::new(AtomicUsize::new(0));
+    let monitor = {
+        let (engine, done, peak_waiters) = (engine.clone(), done.clone(), peak_waiters.clone());
+        tokio::spawn(async move {
+            while !done.load(Ordering::Relaxed) {
+                let waiters = engine.health().await.queues.finality_waiters;
+                peak_waiters.fetch_max(waiters, Ordering::Relaxed);
+                tokio::time::sleep(HEALTH_PROBE_INTERVAL).await;
+            }
+        })
+    };
+
+    let latency = profile.latency_ms;
+    let wait_limit = Duration::from_millis(
+        latency.acknowledge
+            + latency.collect_evidence
+            + latency.prove
+            + latency.finality.min(profile.finality_timeout_ms),
+    ) + Duration::from_secs(10);
+    let mut waits = Vec::new();
+    for scenario in &scenarios {
+        let awaits_finality = observer.tracks(scenario.venue);
+        for n in 0..receipts_per_scenario {
+            let request = ProofRequest {
+                execution_ref: (scenario.claim_type == ClaimType::TRADE_EXECUTED)
+                    .then(|| format!("exec-bench-{}-{}", scenario.id, n)),
+                ..ProofRequest::new(
+                    scenario.venue,
+                    scenario.claim_type,
+                    format!("acct-bench-{}", scenario.id),
+                    format!("order-bench-{}-{}", scenario.id, n),
+                )
+            };
+            let started = Instant::now();
+            let receipt_id = engine.submit(request).await?;
+            let submit_ms = started.elapsed().as_secs_f64() * 1000.0;
+            let engine = engine.clone();
+            let claim_type = scenario.claim_type;
+            waits.push(tokio::spawn(async move {
+                let receipt = engine.wait_for_receipt(&receipt_id, wait_limit).await?;
+                Ok::<_, anyhow::Error>(ReceiptSample {
+                    claim_type,
+                    awaits_finality,
+                    status: receipt.status,
+                    reason: receipt.non_provable.map(|n| n.reason_code),
+                    submit_ms,
+                    completion_ms: started.elapsed().as_secs_f64() * 1000.0,
+                    breakdown: receipt.timing.breakdown,
+                })
+            }));
+        }
+    }
+    let mut samples = Vec::with_capacity(waits.len());
+    for wait in waits {
+        samples.push(wait.await??);
+    }
+    done.store(true, Ordering::Relaxed);
+    monitor.await?;
+    // Abandoned waiters are pruned on the poller's next tick.
+    tokio::time::sleep(FINALITY_POLL_INTERVAL * 3).await;
+    let residual_finality_waiters = engine.health().await.queues.finality_waiters;
+    Ok((
+        samples,
+        BackpressureObservation {
+            peak_finality_waiters: peak_waiters.load(Ordering::Relaxed),
+            residual_finality_waiters,
+        },
+    ))
+}
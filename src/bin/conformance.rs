@@ -4,6 +4,17 @@ use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use zkputer::adapters::{SyntheticVenueAdapter, VenueAdapter};
+use zkputer::config::{Config, ReceiptEngineBuilder};
+use zkputer::conformance::{
+    certify_adapter, load_adapter_fixtures, FixtureVerdict, ADAPTER_FIXTURES_PATH,
+};
+use zkputer::models::{ReceiptLifecycle, Venue};
+use zkputer::policy::PolicyEngine;
+use zkputer::registry::{claim_names, venue_slugs};
+use zkputer::schema::{receipt_json_schema, validate_receipt_json, GENERATED_RECEIPT_SCHEMA_PATH};
 
 fn main() -> Result<()> {
     let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -21,6 +32,7 @@ fn main() -> Result<()> {
     if pipeline_lifecycle.get("states").is_none() {
         bail!("pipeline-lifecycle: missing states");
     }
+    ReceiptLifecycle::from_spec(&pipeline_lifecycle)?;
     if slos.get("objectives").is_none() {
         bail!("phase0_slos: missing objectives");
     }
@@ -32,13 +44,108 @@ fn main() -> Result<()> {
     for (id, digest) in digests {
         println!("  - {}: {}", id, digest);
     }
+
+    certify_registered_adapters(&root)?;
+    check_receipt_schema(&root)
+}
+
+fn registered_adapters() -> Vec<Arc<dyn VenueAdapter>> {
+    vec![
+        Arc::new(SyntheticVenueAdapter::new(Venue::Hyperliquid)),
+        Arc::new(SyntheticVenueAdapter::new(Venue::Base)),
+        Arc::new(SyntheticVenueAdapter::new(Venue::Solana)),
+        Arc::new(SyntheticVenueAdapter::new(Venue::Polymarket)),
+    ]
+}
+
+fn certify_registered_adapters(root: &Path) -> Result<()> {
+    let fixtures = load_adapter_fixtures(&root.join(ADAPTER_FIXTURES_PATH))?;
+    let policy = PolicyEngine::new(Some(root))?;
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    let mut failures = 0;
+    println!("Adapter certification against {} fixtures:", fixtures.len());
+    for adapter in registered_adapters() {
+        let outcomes = runtime.block_on(certify_adapter(adapter.as_ref(), &policy, &fixtures));
+        for outcome in outcomes {
+            match outcome.verdict {
+                FixtureVerdict::Passed => {
+                    println!("  - {:?} {}: pass", outcome.venue, outcome.fixture_id)
+                }
+                FixtureVerdict::Skipped(detail) => {
+                    println!(
+                        "  - {:?} {}: skipped ({})",
+                        outcome.venue, outcome.fixture_id, detail
+                    )
+                }
+                FixtureVerdict::Failed(detail) => {
+                    failures += 1;
+                    println!(
+                        "  - {:?} {}: FAIL ({})",
+                        outcome.venue, outcome.fixture_id, detail
+                    )
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        bail!(
+            "adapter certification failed for {} fixture run(s)",
+            failures
+        );
+    }
+    println!("Adapter certification passed.");
+    Ok(())
+}
+
+// The committed schema must be the one generated from the receipt types, and receipts the engine
+// issues for the fixtures must validate against it.
+fn check_receipt_schema(root: &Path) -> Result<()> {
+    if read_json(&root.join(GENERATED_RECEIPT_SCHEMA_PATH))? != receipt_json_schema() {
+        bail!(
+            "{} is stale; run `cargo run --bin schema -- --write`",
+            GENERATED_RECEIPT_SCHEMA_PATH
+        );
+    }
+    let fixtures = load_adapter_fixtures(&root.join(ADAPTER_FIXTURES_PATH))?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let mut failures = 0;
+    println!(
+        "Receipt schema checks against {}:",
+        GENERATED_RECEIPT_SCHEMA_PATH
+    );
+    runtime.block_on(async {
+        let engine = ReceiptEngineBuilder::new(Config::default()).build().await?;
+        for fixture in &fixtures {
+            let receipt_id = engine.submit(fixture.request_for(Venue::Base)).await?;
+            let receipt = engine
+                .wait_for_receipt(&receipt_id, Duration::from_secs(10))
+                .await?;
+            let issues = validate_receipt_json(&serde_json::to_value(&receipt)?);
+            if issues.is_empty() {
+                println!("  - {}: pass", fixture.id);
+            } else {
+                failures += 1;
+                println!("  - {}: FAIL ({})", fixture.id, issues.join("; "));
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    })?;
+    if failures > 0 {
+        bail!(
+            "{} issued receipt(s) do not match the receipt schema",
+            failures
+        );
+    }
+    println!("Receipt schema checks passed.");
     Ok(())
 }
 
 fn read_json(path: &Path) -> Result<Value> {
-    let content = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
-    let parsed: Value =
-        serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let parsed: Value = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
     Ok(parsed)
 }
 
@@ -71,6 +178,7 @@ fn validate_claim_taxonomy(data: &Value) -> Result<()> {
         "SCHEMA_INVALID",
         "UNSUPPORTED_VENUE_CLAIM",
         "PROOF_FAILURE",
+        "COMPLIANCE_BLOCKED",
     ]
     .iter()
     .map(|s| (*s).to_string())
@@ -88,7 +196,7 @@ fn validate_source_precedence(data: &Value) -> Result<()> {
         .and_then(|v| v.as_object())
         .ok_or_else(|| anyhow::anyhow!("source-precedence: missing venues"))?;
 
-    for venue in ["hyperliquid", "base", "solana", "polymarket"] {
+    for venue in venue_slugs() {
         let cfg = venues
             .get(venue)
             .and_then(|v| v.as_object())
@@ -96,13 +204,19 @@ fn validate_source_precedence(data: &Value) -> Result<()> {
         if !cfg.contains_key("order_placed_sources_preferred")
             || !cfg.contains_key("trade_executed_sources_preferred")
         {
-            bail!("source-precedence: missing source preference lists for {}", venue);
+            bail!(
+                "source-precedence: missing source preference lists for {}",
+                venue
+            );
         }
     }
     Ok(())
 }
 
-fn validate_workloads_and_digests(workloads: &Value, taxonomy: &Value) -> Result<Vec<(String, String)>> {
+fn validate_workloads_and_digests(
+    workloads: &Value,
+    taxonomy: &Value,
+) -> Result<Vec<(String, String)>> {
     let scenarios = workloads
         .get("scenarios")
         .and_then(|v| v.as_array())
@@ -128,8 +242,8 @@ fn validate_workloads_and_digests(workloads: &Value, taxonomy: &Value) -> Result
         })
         .collect();
 
-    let allowed_venues: HashSet<&str> = ["hyperliquid", "base", "solana", "polymarket"].into_iter().collect();
-    let allowed_claims: HashSet<&str> = ["ORDER_PLACED", "TRADE_EXECUTED"].into_iter().collect();
+    let allowed_venues: HashSet<&str> = venue_slugs().into_iter().collect();
+    let allowed_claims: HashSet<&str> = claim_names().into_iter().collect();
 
     let mut seen_ids = HashSet::new();
     let mut digests = Vec::with_capacity(scenarios.len());
@@ -0,0 +1,17 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use zkputer::grpc::serve;
+use zkputer::ReceiptEngineBuilder;
+
+// Serves the receipt archive (ExportReceipts) over gRPC from the configured store.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let addr = std::env::var("ZKPUTER_GRPC_ADDR").unwrap_or_else(|_| "127.0.0.1:50051".to_string());
+    let engine = ReceiptEngineBuilder::from_env()?.build().await?;
+    let listener = TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("failed to bind {}", addr))?;
+    eprintln!("zkputer grpc listening on {}", listener.local_addr()?);
+    serve(Arc::new(engine), listener, std::future::pending()).await
+}
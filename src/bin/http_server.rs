@@ -0,0 +1,30 @@
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use zkputer::credentials::credential_store_from_env;
+use zkputer::http::{serve, HttpApi};
+use zkputer::ReceiptEngineBuilder;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let addr = std::env::var("ZKPUTER_HTTP_ADDR").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
+    let engine = ReceiptEngineBuilder::from_env()?
+        .with_credentials(credential_store_from_env()?)
+        .build()
+        .await?;
+    let listener = TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("failed to bind {}", addr))?;
+    eprintln!("zkputer http listening on {}", listener.local_addr()?);
+    let engine = Arc::new(engine);
+    let _election = Arc::clone(&engine).run_leader_election();
+    let api = HttpApi::new(engine);
+    let api = match std::env::var("ZKPUTER_HTTP_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty())
+    {
+        Some(token) => api.with_api_token(token),
+        None => api,
+    };
+    serve(listener, Arc::new(api)).await
+}
@@ -1,338 +1,132 @@
 use anyhow::{anyhow, Context, Result};
-use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde::Serialize;
+use serde_json::Value;
 use std::io::{self, BufRead, BufReader, Write};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::net::TcpListener;
 use tokio::runtime::Runtime;
-use zkputer::adapters::{SyntheticVenueAdapter, VenueAdapter};
-use zkputer::models::{ClaimType, ProofRequest, Venue};
-use zkputer::policy::PolicyEngine;
-use zkputer::prover::{build_mvp_prover, ProverStrategy};
-use zkputer::templates::{build_request_from_template, list_verification_templates, template_ids};
-use zkputer::verifier::OffchainVerifier;
-use zkputer::ReceiptEngine;
-
-#[derive(Debug, Deserialize)]
-struct JsonRpcRequest {
-    #[allow(dead_code)]
-    jsonrpc: Option<String>,
-    id: Option<Value>,
-    method: String,
-    params: Option<Value>,
-}
-
-#[derive(Debug, Serialize)]
-struct JsonRpcResponse {
-    jsonrpc: &'static str,
-    id: Value,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    result: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<Value>,
-}
+use zkputer::credentials::credential_store_from_env;
+use zkputer::mcp::{McpFeatures, McpServer};
+use zkputer::mcp_http::{serve_mcp_http, McpHttpTransport, MCP_ENDPOINT};
+use zkputer::redaction::RedactionConfig;
+use zkputer::{ReceiptEngine, ReceiptEngineBuilder};
 
 fn main() -> Result<()> {
     let runtime = Runtime::new().context("failed to create tokio runtime")?;
-    let engine = runtime.block_on(build_engine())?;
+    let engine = Arc::new(runtime.block_on(build_engine())?);
+    let _election = runtime.block_on(async { Arc::clone(&engine).run_leader_election() });
+    let redaction = RedactionConfig::from_env(
+        std::env::var("ZKPUTER_MCP_REDACT").ok().as_deref(),
+        std::env::var("ZKPUTER_MCP_REDACT_STRUCTURED")
+            .ok()
+            .as_deref(),
+    );
+    let configured = McpFeatures::from_env(std::env::var("ZKPUTER_MCP_FEATURES").ok().as_deref());
+    let annotations = tool_annotation_overrides()?;
+    // ZKPUTER_MCP_HTTP_ADDR serves streamable HTTP (and the older SSE transport) instead of stdio.
+    if let Some(addr) = std::env::var("ZKPUTER_MCP_HTTP_ADDR")
+        .ok()
+        .filter(|a| !a.is_empty())
+    {
+        let server = McpServer::new(engine, configured)
+            .with_redaction(redaction)
+            .with_tool_annotation_overrides(&annotations)?;
+        return runtime.block_on(serve_http(server, &addr));
+    }
+    // The stdio server has no streaming transport, so that stays off whatever is configured.
+    let features = McpFeatures {
+        streaming: false,
+        ..configured
+    };
+    let server = McpServer::new(engine, features)
+        .with_redaction(redaction)
+        .with_tool_annotation_overrides(&annotations)?;
+    let mut session = server.session();
+    // Resource updates arrive between requests, so a task writes them as they come.
+    let (outbound, mut updates) = tokio::sync::mpsc::unbounded_channel::<Value>();
+    session.attach_outbound(outbound);
+    runtime.spawn(async move {
+        while let Some(update) = updates.recv().await {
+            if write_message(&mut io::stdout().lock(), &update).is_err() {
+                break;
+            }
+        }
+    });
 
     let stdin = io::stdin();
-    let stdout = io::stdout();
     let mut reader = BufReader::new(stdin.lock());
-    let mut writer = stdout.lock();
 
     while let Some(message) = read_message(&mut reader)? {
-        let request: JsonRpcRequest = match serde_json::from_slice(&message) {
-            Ok(v) => v,
-            Err(err) => {
-                let response = JsonRpcResponse {
-                    jsonrpc: "2.0",
-                    id: Value::Null,
-                    result: None,
-                    error: Some(json!({"code": -32700, "message": format!("Parse error: {}", err)})),
-                };
-                write_message(&mut writer, &response)?;
-                continue;
-            }
-        };
-
-        if let Some(id) = request.id.clone() {
-            let response = handle_request(&runtime, &engine, request, id);
-            write_message(&mut writer, &response)?;
-        } else if request.method == "notifications/initialized" {
-            continue;
+        // Stdout is locked per message so progress notifications go out while the call is running.
+        let mut notify =
+            |notification: Value| write_message(&mut io::stdout().lock(), &notification);
+        if let Some(response) = runtime.block_on(server.handle(&mut session, &message, &mut notify))
+        {
+            write_message(&mut io::stdout().lock(), &response)?;
         }
     }
 
+    session.close();
     Ok(())
 }
 
-async fn build_engine() -> Result<ReceiptEngine> {
-    let prover_strategy_env = std::env::var("ZKPUTER_PROVER_STRATEGY").ok();
-    let prover_strategy = ProverStrategy::from_env(prover_strategy_env.as_deref());
-    let adapters: Vec<Arc<dyn VenueAdapter>> = vec![
-        Arc::new(SyntheticVenueAdapter::new(Venue::Hyperliquid)),
-        Arc::new(SyntheticVenueAdapter::new(Venue::Base)),
-        Arc::new(SyntheticVenueAdapter::new(Venue::Solana)),
-        Arc::new(SyntheticVenueAdapter::new(Venue::Polymarket)),
-    ];
-    let engine = ReceiptEngine::new(
-        adapters,
-        PolicyEngine::new(None)?,
-        build_mvp_prover(prover_strategy),
-        OffchainVerifier::default(),
-    );
-    Ok(engine)
-}
-
-fn handle_request(runtime: &Runtime, engine: &ReceiptEngine, request: JsonRpcRequest, id: Value) -> JsonRpcResponse {
-    let method = request.method.as_str();
-    let params = request.params.unwrap_or_else(|| json!({}));
-    let result = match method {
-        "initialize" => Ok(json!({
-            "protocolVersion": "2024-11-05",
-            "capabilities": { "tools": {} },
-            "serverInfo": {
-                "name": "zkputer-mcp",
-                "version": "0.1.0"
-            }
-        })),
-        "ping" => Ok(json!({})),
-        "tools/list" => Ok(json!({
-            "tools": [
-                {
-                    "name": "zkputer_list_templates",
-                    "description": "List hardened zkputer verification templates for agent usage.",
-                    "inputSchema": {
-                        "type": "object",
-                        "additionalProperties": false,
-                        "properties": {}
-                    }
-                },
-                {
-                    "name": "zkputer_verify_template",
-                    "description": "Submit verification via a hardened zkputer template and optionally wait for receipt completion.",
-                    "inputSchema": {
-                        "type": "object",
-                        "additionalProperties": false,
-                        "properties": {
-                            "template_id": { "type": "string", "enum": template_ids() },
-                            "template_args": { "type": "object" },
-                            "wait_for_result": { "type": "boolean", "default": true },
-                            "wait_timeout_ms": { "type": "integer", "default": 3000 }
-                        },
-                        "required": ["template_id", "template_args"]
-                    }
-                },
-                {
-                    "name": "zkputer_verify_claim",
-                    "description": "Submit a verification request and optionally wait for a receipt.",
-                    "inputSchema": {
-                        "type": "object",
-                        "additionalProperties": false,
-                        "properties": {
-                            "venue": { "type": "string", "enum": ["hyperliquid","base","solana","polymarket"] },
-                            "claim_type": { "type": "string", "enum": ["ORDER_PLACED","TRADE_EXECUTED"] },
-                            "account_ref": { "type": "string" },
-                            "order_ref": { "type": "string" },
-                            "execution_ref": { "type": "string" },
-                            "wait_for_result": { "type": "boolean", "default": true },
-                            "wait_timeout_ms": { "type": "integer", "default": 3000 }
-                        },
-                        "required": ["venue","claim_type","account_ref","order_ref"]
-                    }
-                },
-                {
-                    "name": "zkputer_get_receipt",
-                    "description": "Fetch a previously created receipt by id.",
-                    "inputSchema": {
-                        "type": "object",
-                        "additionalProperties": false,
-                        "properties": {
-                            "receipt_id": { "type": "string" }
-                        },
-                        "required": ["receipt_id"]
-                    }
-                }
-            ]
-        })),
-        "tools/call" => handle_tool_call(runtime, engine, &params),
-        _ => Err(anyhow!("Method not found: {}", method)),
-    };
-
-    match result {
-        Ok(result) => JsonRpcResponse {
-            jsonrpc: "2.0",
-            id,
-            result: Some(result),
-            error: None,
-        },
-        Err(err) => {
-            let code = if method == "tools/call" { -32000 } else { -32601 };
-            JsonRpcResponse {
-                jsonrpc: "2.0",
-                id,
-                result: None,
-                error: Some(json!({
-                    "code": code,
-                    "message": err.to_string()
-                })),
-            }
-        }
-    }
-}
-
-fn parse_wait_options(arguments: &Value) -> (bool, u64) {
-    let wait_for_result = arguments
-        .get("wait_for_result")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(true);
-    let wait_timeout_ms = arguments
-        .get("wait_timeout_ms")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(3000);
-    (wait_for_result, wait_timeout_ms)
-}
-
-fn submit_and_render_receipt(
-    runtime: &Runtime,
-    engine: &ReceiptEngine,
-    request: ProofRequest,
-    wait_for_result: bool,
-    wait_timeout_ms: u64,
-) -> Result<Value> {
-    let receipt_id = runtime.block_on(engine.submit(request))?;
-    let receipt = if wait_for_result {
-        runtime.block_on(engine.wait_for_receipt(&receipt_id, Duration::from_millis(wait_timeout_ms)))?
-    } else {
-        runtime
-            .block_on(engine.get_receipt(&receipt_id))
-            .ok_or_else(|| anyhow!("receipt not found after submit"))?
+// ZKPUTER_MCP_TOOL_ANNOTATIONS names a JSON file of per-tool annotation overrides.
+fn tool_annotation_overrides() -> Result<Value> {
+    let Some(path) = std::env::var("ZKPUTER_MCP_TOOL_ANNOTATIONS")
+        .ok()
+        .filter(|p| !p.is_empty())
+    else {
+        return Ok(Value::Object(Default::default()));
     };
-    let payload = serde_json::to_value(&receipt)?;
-    Ok(json!({
-        "content": [{
-            "type": "text",
-            "text": serde_json::to_string_pretty(&payload)?
-        }],
-        "structuredContent": payload
-    }))
+    let text =
+        std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path))?;
+    serde_json::from_str(&text).with_context(|| format!("{} is not valid JSON", path))
 }
 
-fn handle_tool_call(runtime: &Runtime, engine: &ReceiptEngine, params: &Value) -> Result<Value> {
-    let name = params
-        .get("name")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow!("tools/call missing name"))?;
-    let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
-
-    match name {
-        "zkputer_list_templates" => {
-            let templates_payload = serde_json::to_value(list_verification_templates())?;
-            Ok(json!({
-                "content": [{
-                    "type": "text",
-                    "text": serde_json::to_string_pretty(&templates_payload)?
-                }],
-                "structuredContent": templates_payload
-            }))
-        }
-        "zkputer_verify_template" => {
-            let template_id = arguments
-                .get("template_id")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("template_id is required"))?;
-            let template_args = arguments
-                .get("template_args")
-                .cloned()
-                .ok_or_else(|| anyhow!("template_args is required"))?;
-            let (wait_for_result, wait_timeout_ms) = parse_wait_options(&arguments);
-            let request = build_request_from_template(template_id, &template_args)?;
-            submit_and_render_receipt(runtime, engine, request, wait_for_result, wait_timeout_ms)
-        }
-        "zkputer_verify_claim" => {
-            let venue = parse_venue(arguments.get("venue").and_then(|v| v.as_str()))
-                .ok_or_else(|| anyhow!("invalid venue"))?;
-            let claim_type = parse_claim_type(arguments.get("claim_type").and_then(|v| v.as_str()))
-                .ok_or_else(|| anyhow!("invalid claim_type"))?;
-            let account_ref = arguments
-                .get("account_ref")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("account_ref is required"))?
-                .to_string();
-            let order_ref = arguments
-                .get("order_ref")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("order_ref is required"))?
-                .to_string();
-            let execution_ref = arguments
-                .get("execution_ref")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-            let (wait_for_result, wait_timeout_ms) = parse_wait_options(&arguments);
-
-            let request = ProofRequest {
-                venue,
-                claim_type,
-                account_ref,
-                order_ref,
-                execution_ref,
-                payload: json!({}),
-            };
-            submit_and_render_receipt(runtime, engine, request, wait_for_result, wait_timeout_ms)
-        }
-        "zkputer_get_receipt" => {
-            let receipt_id = arguments
-                .get("receipt_id")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("receipt_id is required"))?;
-            let maybe_receipt = runtime.block_on(engine.get_receipt(receipt_id));
-            match maybe_receipt {
-                Some(receipt) => {
-                    let payload = serde_json::to_value(&receipt)?;
-                    Ok(json!({
-                        "content": [{
-                            "type": "text",
-                            "text": serde_json::to_string_pretty(&payload)?
-                        }],
-                        "structuredContent": payload
-                    }))
-                }
-                None => Ok(json!({
-                    "isError": true,
-                    "content": [{
-                        "type": "text",
-                        "text": format!("receipt not found: {}", receipt_id)
-                    }]
-                })),
-            }
-        }
-        _ => Ok(json!({
-            "isError": true,
-            "content": [{
-                "type": "text",
-                "text": format!("unknown tool: {}", name)
-            }]
-        })),
+async fn serve_http(server: McpServer, addr: &str) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("failed to bind {}", addr))?;
+    eprintln!(
+        "zkputer mcp listening on http://{}{}",
+        listener.local_addr()?,
+        MCP_ENDPOINT
+    );
+    let mut transport = McpHttpTransport::new(Arc::new(server));
+    if let Some(token) = std::env::var("ZKPUTER_MCP_HTTP_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty())
+    {
+        transport = transport.with_api_token(token);
     }
-}
-
-fn parse_venue(value: Option<&str>) -> Option<Venue> {
-    match value? {
-        "hyperliquid" => Some(Venue::Hyperliquid),
-        "base" => Some(Venue::Base),
-        "solana" => Some(Venue::Solana),
-        "polymarket" => Some(Venue::Polymarket),
-        _ => None,
+    if let Ok(origins) = std::env::var("ZKPUTER_MCP_ALLOWED_ORIGINS") {
+        let origins = origins
+            .split(',')
+            .map(str::trim)
+            .filter(|o| !o.is_empty())
+            .map(str::to_string)
+            .collect();
+        transport = transport.with_allowed_origins(origins);
+    }
+    if let Some(secs) = std::env::var("ZKPUTER_MCP_SESSION_IDLE_SECS")
+        .ok()
+        .filter(|s| !s.is_empty())
+    {
+        let secs: u64 = secs
+            .parse()
+            .context("ZKPUTER_MCP_SESSION_IDLE_SECS must be a number of seconds")?;
+        transport = transport.with_session_idle_timeout(Duration::from_secs(secs));
     }
+    serve_mcp_http(listener, Arc::new(transport)).await
 }
 
-fn parse_claim_type(value: Option<&str>) -> Option<ClaimType> {
-    match value? {
-        "ORDER_PLACED" => Some(ClaimType::ORDER_PLACED),
-        "TRADE_EXECUTED" => Some(ClaimType::TRADE_EXECUTED),
-        _ => None,
-    }
+async fn build_engine() -> Result<ReceiptEngine> {
+    let engine = ReceiptEngineBuilder::from_env()?
+        .with_credentials(credential_store_from_env()?)
+        .build()
+        .await?;
+    Ok(engine.with_search_index())
 }
 
 fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Vec<u8>>> {
@@ -348,7 +142,11 @@ fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Vec<u8>>> {
             break;
         }
         if let Some(v) = trimmed.strip_prefix("Content-Length:") {
-            content_length = Some(v.trim().parse::<usize>().context("invalid Content-Length")?);
+            content_length = Some(
+                v.trim()
+                    .parse::<usize>()
+                    .context("invalid Content-Length")?,
+            );
         }
     }
 
@@ -0,0 +1,64 @@
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use zkputer::http::receipt_query;
+use zkputer::ReceiptEngineBuilder;
+
+const USAGE: &str = "usage: receipts <list | search <text>> [--venue <slug>] [--claim-type <type>] [--status <status>] \
+                     [--limit <n>] [--json]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut params = HashMap::new();
+    match args.next().as_deref() {
+        Some("list") => {}
+        Some("search") => {
+            let text = args
+                .next()
+                .filter(|t| !t.starts_with("--"))
+                .context(USAGE)?;
+            params.insert("text".to_string(), text);
+        }
+        _ => bail!("{}", USAGE),
+    }
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        // Flags take the same values as the REST list filters.
+        let name = match arg.as_str() {
+            "--venue" => "venue",
+            "--claim-type" => "claim_type",
+            "--status" => "status",
+            "--limit" => "limit",
+            "--json" => {
+                json = true;
+                continue;
+            }
+            _ => bail!("{}", USAGE),
+        };
+        params.insert(name.to_string(), args.next().context(USAGE)?);
+    }
+    let query = receipt_query(&params)?;
+
+    let engine = ReceiptEngineBuilder::from_env()?
+        .build()
+        .await?
+        .with_search_index();
+    let receipts = engine.list_receipts(&query).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&receipts)?);
+        return Ok(());
+    }
+    for receipt in &receipts {
+        println!(
+            "{}  {:?}  {}  {:?}  {}  {}",
+            receipt.receipt_id,
+            receipt.status,
+            receipt.subject.venue.slug(),
+            receipt.claim.r#type,
+            receipt.timing.created_at,
+            receipt.claim.statement
+        );
+    }
+    println!("{} receipt(s)", receipts.len());
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use zkputer::schema::{
+    proof_request_json_schema, receipt_json_schema, GENERATED_RECEIPT_SCHEMA_PATH,
+};
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let schema = if args.iter().any(|a| a == "--proof-request") {
+        proof_request_json_schema()
+    } else {
+        receipt_json_schema()
+    };
+    let rendered = format!("{}\n", serde_json::to_string_pretty(&schema)?);
+
+    if args.iter().any(|a| a == "--write") {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(GENERATED_RECEIPT_SCHEMA_PATH);
+        std::fs::write(&path, rendered)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        println!("wrote {}", path.display());
+    } else {
+        print!("{}", rendered);
+    }
+    Ok(())
+}
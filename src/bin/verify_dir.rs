@@ -0,0 +1,125 @@
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+use zkputer::anchoring::{AnchorTxChecker, DEFAULT_ANCHOR_CONFIRMATIONS};
+use zkputer::audit::{load_receipt_documents, ReceiptAuditor, DEFAULT_AUDIT_CONCURRENCY};
+use zkputer::models::ProofBackend;
+use zkputer::signing::{KeyManifest, KeyRing};
+use zkputer::verifier::OffchainVerifier;
+
+const USAGE: &str = "usage: verify-dir <dir-or-archive> [--keys <key-manifest.json>] [--concurrency <n>] [--rpc-url <url> \
+                     [--min-confirmations <n>] [--anchor-contract <address>]] [--sp1-vkey-hash <hash>] \
+                     [--risc0-image-id <id>] [--json]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut source = None;
+    let mut keys = None;
+    let mut concurrency = DEFAULT_AUDIT_CONCURRENCY;
+    let mut json = false;
+    let mut rpc_url = None;
+    let mut min_confirmations = DEFAULT_ANCHOR_CONFIRMATIONS;
+    let mut anchor_contract = None;
+    let mut verifier = OffchainVerifier::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--keys" => keys = Some(PathBuf::from(args.next().context(USAGE)?)),
+            "--concurrency" => {
+                concurrency = args
+                    .next()
+                    .context(USAGE)?
+                    .parse()
+                    .context("--concurrency must be a number")?
+            }
+            "--rpc-url" => rpc_url = Some(args.next().context(USAGE)?),
+            "--min-confirmations" => {
+                min_confirmations = args
+                    .next()
+                    .context(USAGE)?
+                    .parse()
+                    .context("--min-confirmations must be a number")?
+            }
+            "--anchor-contract" => anchor_contract = Some(args.next().context(USAGE)?),
+            "--sp1-vkey-hash" => {
+                verifier = verifier.with_pinned_key(ProofBackend::SP1, &args.next().context(USAGE)?)
+            }
+            "--risc0-image-id" => {
+                verifier =
+                    verifier.with_pinned_key(ProofBackend::RISC0, &args.next().context(USAGE)?)
+            }
+            "--json" => json = true,
+            _ if source.is_none() && !arg.starts_with("--") => source = Some(PathBuf::from(arg)),
+            _ => bail!("{}", USAGE),
+        }
+    }
+    let source = source.context(USAGE)?;
+
+    let mut auditor = ReceiptAuditor::new()
+        .with_concurrency(concurrency)
+        .with_verifier(verifier);
+    if let Some(path) = keys {
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let manifest: KeyManifest = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        auditor = auditor.with_keys(KeyRing::new(manifest.keys)?);
+    }
+    if let Some(url) = rpc_url {
+        let mut checker = AnchorTxChecker::connect(&url)?.with_min_confirmations(min_confirmations);
+        if let Some(address) = anchor_contract {
+            checker = checker.with_anchor_contract(address);
+        }
+        auditor = auditor.with_anchor_checker(checker);
+    } else if anchor_contract.is_some() {
+        bail!("--anchor-contract needs --rpc-url");
+    }
+    let documents = load_receipt_documents(&source)?;
+    let report = auditor
+        .audit(&source.display().to_string(), documents)
+        .await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "Verified {} receipt(s) from {} in {:.1} ms: {} passed, {} failed (signatures checked against {} keys).",
+            report.total, report.source, report.elapsed_ms, report.passed, report.failed, report.signer_keys
+        );
+        if let Some(endpoint) = &report.anchor_rpc {
+            println!("Anchor transactions checked against {}.", endpoint);
+        }
+        for (status, count) in &report.by_status {
+            println!("  - {}: {}", status, count);
+        }
+        if !report.failure_reasons.is_empty() {
+            println!("Failure reasons:");
+            for (reason, count) in &report.failure_reasons {
+                println!("  - {}: {}", reason, count);
+            }
+            println!("Failed receipts:");
+            for failure in &report.failures {
+                println!(
+                    "  - {} ({}): {}",
+                    failure.location,
+                    failure.receipt_id.as_deref().unwrap_or("unreadable"),
+                    failure.reasons.join(", ")
+                );
+            }
+        }
+        println!("Slowest checks:");
+        for check in &report.slowest_checks {
+            println!(
+                "  - {:.3} ms {} {}",
+                check.elapsed_ms, check.check, check.location
+            );
+        }
+    }
+    if report.failed > 0 {
+        bail!(
+            "{} of {} receipt(s) failed verification",
+            report.failed,
+            report.total
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::models::{hash_json, ClaimType, ProofBackend, ProofMetadata, VerificationMode};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const MVP_CIRCUIT_VERSION: &str = "v0.2.0";
+
+// The guest program a circuit runs. Inclusion guests open one order or fill against the venue's
+// evidence; aggregate guests fold several observations (quotes, fills) into one statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuestProgram {
+    Inclusion,
+    Aggregate,
+}
+
+impl GuestProgram {
+    pub fn for_claim(claim_type: ClaimType) -> Self {
+        match claim_type {
+            ClaimType::BEST_EXECUTION => GuestProgram::Aggregate,
+            _ => GuestProgram::Inclusion,
+        }
+    }
+
+    pub fn slug(self) -> &'static str {
+        match self {
+            GuestProgram::Inclusion => "inclusion",
+            GuestProgram::Aggregate => "aggregate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitSpec {
+    pub backend: ProofBackend,
+    pub claim_type: ClaimType,
+    pub guest: GuestProgram,
+    pub circuit_id: String,
+    pub circuit_version: String,
+    pub verifier_key_id: String,
+    pub verifier_key_hash: String,
+}
+
+impl CircuitSpec {
+    // The MVP provers' circuit for a claim, e.g. `inclusion-order-placed-sp1`. Its verifying key is
+    // derived from the circuit id and version, named the way the backend names its keys.
+    pub fn mvp(backend: ProofBackend, claim_type: ClaimType, version: &str) -> Self {
+        let guest = GuestProgram::for_claim(claim_type);
+        let backend_slug = format!("{:?}", backend).to_ascii_lowercase();
+        let circuit_id = format!(
+            "{}-{}-{}",
+            guest.slug(),
+            claim_type.name().to_ascii_lowercase().replace('_', "-"),
+            backend_slug
+        );
+        let verifier_key_hash = hash_json(&serde_json::json!({
+            "backend": backend,
+            "circuit_id": circuit_id,
+            "circuit_version": version
+        }));
+        let key_prefix = match backend {
+            ProofBackend::RISC0 => "risc0-image".to_string(),
+            _ => format!("{}-vk", backend_slug),
+        };
+        let verifier_key_id = format!(
+            "{}-{}",
+            key_prefix,
+            &verifier_key_hash.trim_start_matches("0x")[..16]
+        );
+        Self {
+            backend,
+            claim_type,
+            guest,
+            circuit_id,
+            circuit_version: version.to_string(),
+            verifier_key_id,
+            verifier_key_hash,
+        }
+    }
+
+    pub fn proof_metadata(
+        &self,
+        public_inputs: &Value,
+        proof_artifact_ref: String,
+    ) -> ProofMetadata {
+        ProofMetadata {
+            backend: self.backend,
+            circuit_id: self.circuit_id.clone(),
+            circuit_version: self.circuit_version.clone(),
+            verifier_key_id: self.verifier_key_id.clone(),
+            verifier_key_hash: self.verifier_key_hash.clone(),
+            public_inputs_hash: hash_json(public_inputs),
+            verification_mode: VerificationMode::OFFCHAIN,
+            proof_artifact_ref: Some(proof_artifact_ref),
+            anchored_root_ref: None,
+            anchor_inclusion: None,
+            fallback_from: vec![],
+        }
+    }
+
+    // A proof from this circuit names it, its version and its key, and proves the same claim.
+    pub fn matches(&self, proof: &ProofMetadata, claim_type: ClaimType) -> bool {
+        self.backend == proof.backend
+            && self.claim_type == claim_type
+            && self.verifier_key_id == proof.verifier_key_id
+            && self.verifier_key_hash == proof.verifier_key_hash
+    }
+}
+
+// Circuits by backend, claim type and version. A backend proves each claim with the version
+// registered for it last; the others stay so receipts proved with them still verify.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitRegistry {
+    circuits: Vec<CircuitSpec>,
+}
+
+impl CircuitRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Every claim type on each MVP backend, at MVP_CIRCUIT_VERSION.
+    pub fn builtin() -> &'static CircuitRegistry {
+        static BUILTIN: std::sync::OnceLock<CircuitRegistry> = std::sync::OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut registry = CircuitRegistry::new();
+            for backend in [ProofBackend::SP1, ProofBackend::PICO, ProofBackend::RISC0] {
+                for claim_type in ClaimType::all() {
+                    registry.register(CircuitSpec::mvp(backend, claim_type, MVP_CIRCUIT_VERSION));
+                }
+            }
+            registry
+        })
+    }
+
+    // A circuit registered again under the same id and version replaces the earlier entry.
+    pub fn register(&mut self, spec: CircuitSpec) {
+        self.circuits.retain(|c| {
+            !(c.circuit_id == spec.circuit_id && c.circuit_version == spec.circuit_version)
+        });
+        self.circuits.push(spec);
+    }
+
+    pub fn with_circuit(mut self, spec: CircuitSpec) -> Self {
+        self.register(spec);
+        self
+    }
+
+    // The most recently registered circuit for the claim on this backend.
+    pub fn select(&self, backend: ProofBackend, claim_type: ClaimType) -> Option<&CircuitSpec> {
+        self.circuits
+            .iter()
+            .rev()
+            .find(|c| c.backend == backend && c.claim_type == claim_type)
+    }
+
+    // Selects by the claim type the public inputs name.
+    pub fn select_for(&self, backend: ProofBackend, public_inputs: &Value) -> Result<&CircuitSpec> {
+        let claim_type = public_inputs
+            .get("claim_type")
+            .and_then(|v| v.as_str())
+            .and_then(ClaimType::from_name)
+            .ok_or_else(|| anyhow!("public inputs name no known claim type"))?;
+        self.select(backend, claim_type).ok_or_else(|| {
+            anyhow!(
+                "no {:?} circuit is registered for {}",
+                backend,
+                claim_type.name()
+            )
+        })
+    }
+
+    pub fn get(&self, circuit_id: &str, circuit_version: &str) -> Option<&CircuitSpec> {
+        self.circuits
+            .iter()
+            .find(|c| c.circuit_id == circuit_id && c.circuit_version == circuit_version)
+    }
+
+    pub fn circuits(&self) -> &[CircuitSpec] {
+        &self.circuits
+    }
+}
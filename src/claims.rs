@@ -0,0 +1,327 @@
+use crate::adapters::VenueAdapter;
+use crate::models::{
+    BalanceRange, ClaimType, EvidenceBundle, ExecutionAck, NonProvableReason, ProofRequest,
+    Remediation, TruthClaim, Venue,
+};
+use crate::policy::{PolicyDecision, PolicyEngine};
+use crate::templates::{balance_range, position_pnl};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// The layout every built-in claim commits to. Only fields the receipt itself carries go in, so a
+// verifier can recompute the hash from the receipt alone.
+pub fn standard_public_inputs(
+    claim_hash: &str,
+    evidence_root: &str,
+    venue: Venue,
+    claim_type: ClaimType,
+) -> Value {
+    serde_json::json!({
+        "claim_hash": claim_hash,
+        "evidence_root": evidence_root,
+        "venue": venue.slug(),
+        "claim_type": claim_type.name()
+    })
+}
+
+// POSITION_CLOSED also exposes its realized PnL commitment, so a PnL opened to a counterparty can
+// be checked against the proof itself rather than parsed out of the statement.
+pub fn position_closed_public_inputs(
+    claim_hash: &str,
+    evidence_root: &str,
+    venue: Venue,
+    pnl_commitment: &str,
+) -> Value {
+    let mut inputs =
+        standard_public_inputs(claim_hash, evidence_root, venue, ClaimType::POSITION_CLOSED);
+    inputs["pnl_commitment"] = Value::String(pnl_commitment.to_string());
+    inputs
+}
+
+// BALANCE_AT_TIMESTAMP proves the balance lies in a range without revealing it, so the range itself
+// is public: the asset, the lower bound, and the time (and block) it held at.
+pub fn balance_range_public_inputs(
+    claim_hash: &str,
+    evidence_root: &str,
+    venue: Venue,
+    range: &BalanceRange,
+) -> Value {
+    let mut inputs = standard_public_inputs(
+        claim_hash,
+        evidence_root,
+        venue,
+        ClaimType::BALANCE_AT_TIMESTAMP,
+    );
+    inputs["balance_range"] = serde_json::json!({
+        "asset": range.asset,
+        "lower_bound": range.min_balance,
+        "as_of": range.as_of,
+        "block": range.block
+    });
+    inputs
+}
+
+// Claim-specific behaviour the engine defers to: how the statement reads, which evidence must be
+// present, and what the proof commits to. Every method defaults to what the engine does for claims
+// without a handler, so a handler only overrides what its claim does differently.
+#[async_trait]
+pub trait ClaimHandler: Send + Sync {
+    fn claim_type(&self) -> ClaimType;
+
+    // Required on top of the taxonomy's required_evidence_tags_all for this claim.
+    fn required_evidence_tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    // A rejection of evidence the policy passed, for checks the taxonomy cannot express.
+    fn check_evidence(
+        &self,
+        _request: &ProofRequest,
+        _bundle: &EvidenceBundle,
+    ) -> Option<PolicyDecision> {
+        None
+    }
+
+    async fn build_statement(
+        &self,
+        adapter: &dyn VenueAdapter,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+        bundle: &EvidenceBundle,
+    ) -> Result<String> {
+        adapter.build_statement(request, ack, bundle).await
+    }
+
+    // Claim data the receipt carries in `claim.public_values` for the public inputs to commit to.
+    fn public_values(
+        &self,
+        _request: &ProofRequest,
+        _bundle: &EvidenceBundle,
+    ) -> Result<Map<String, Value>> {
+        Ok(Map::new())
+    }
+
+    // Must stay a function of receipt fields; verifiers recompute it with the same handler.
+    fn public_inputs(&self, claim: &TruthClaim, evidence_root: &str, venue: Venue) -> Value {
+        standard_public_inputs(&claim.claim_hash, evidence_root, venue, self.claim_type())
+    }
+}
+
+// POSITION_CLOSED carries its realized PnL commitment, so a PnL opened to a counterparty can be
+// checked against the proof itself rather than parsed out of the statement.
+pub struct PositionClosedHandler;
+
+const PNL_COMMITMENT_VALUE: &str = "pnl_commitment";
+
+#[async_trait]
+impl ClaimHandler for PositionClosedHandler {
+    fn claim_type(&self) -> ClaimType {
+        ClaimType::POSITION_CLOSED
+    }
+
+    fn public_values(
+        &self,
+        request: &ProofRequest,
+        bundle: &EvidenceBundle,
+    ) -> Result<Map<String, Value>> {
+        let (_, commitment) = position_pnl(request, bundle)?;
+        let mut values = Map::new();
+        values.insert(PNL_COMMITMENT_VALUE.to_string(), Value::String(commitment));
+        Ok(values)
+    }
+
+    fn public_inputs(&self, claim: &TruthClaim, evidence_root: &str, venue: Venue) -> Value {
+        match claim
+            .public_values
+            .get(PNL_COMMITMENT_VALUE)
+            .and_then(Value::as_str)
+        {
+            Some(commitment) => {
+                position_closed_public_inputs(&claim.claim_hash, evidence_root, venue, commitment)
+            }
+            None => {
+                standard_public_inputs(&claim.claim_hash, evidence_root, venue, self.claim_type())
+            }
+        }
+    }
+}
+
+// BALANCE_AT_TIMESTAMP carries the range its statement asserts, and is refused unless the venue's
+// snapshot bears that range out.
+pub struct BalanceAtTimestampHandler;
+
+const BALANCE_RANGE_VALUE: &str = "balance_range";
+
+#[async_trait]
+impl ClaimHandler for BalanceAtTimestampHandler {
+    fn claim_type(&self) -> ClaimType {
+        ClaimType::BALANCE_AT_TIMESTAMP
+    }
+
+    fn check_evidence(
+        &self,
+        request: &ProofRequest,
+        bundle: &EvidenceBundle,
+    ) -> Option<PolicyDecision> {
+        if bundle.balance_snapshot.is_none() {
+            return Some(PolicyDecision {
+                ok: false,
+                reason: Some(NonProvableReason::EVIDENCE_MISSING),
+                details: "Balance claim requires a venue balance snapshot at the requested point."
+                    .to_string(),
+                remediation: Some(Remediation {
+                    missing_tags: vec!["balance_snapshot_artifact".to_string()],
+                    ..Remediation::for_reason(NonProvableReason::EVIDENCE_MISSING)
+                }),
+            });
+        }
+        let err = balance_range(request, bundle).err()?;
+        Some(PolicyDecision {
+            ok: false,
+            reason: Some(NonProvableReason::EVIDENCE_CONFLICT),
+            details: format!("{}.", err),
+            remediation: None,
+        })
+    }
+
+    fn public_values(
+        &self,
+        request: &ProofRequest,
+        bundle: &EvidenceBundle,
+    ) -> Result<Map<String, Value>> {
+        let (_, range) = balance_range(request, bundle)?;
+        let mut values = Map::new();
+        values.insert(
+            BALANCE_RANGE_VALUE.to_string(),
+            serde_json::to_value(range)?,
+        );
+        Ok(values)
+    }
+
+    fn public_inputs(&self, claim: &TruthClaim, evidence_root: &str, venue: Venue) -> Value {
+        let range = claim
+            .public_values
+            .get(BALANCE_RANGE_VALUE)
+            .and_then(|range| serde_json::from_value::<BalanceRange>(range.clone()).ok());
+        match range {
+            Some(range) => {
+                balance_range_public_inputs(&claim.claim_hash, evidence_root, venue, &range)
+            }
+            None => {
+                standard_public_inputs(&claim.claim_hash, evidence_root, venue, self.claim_type())
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ClaimHandlers {
+    handlers: HashMap<ClaimType, Arc<dyn ClaimHandler>>,
+}
+
+// The built-in handlers are registered like any other, so a plugin for the same claim replaces them.
+impl Default for ClaimHandlers {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+        .with_handler(Arc::new(PositionClosedHandler))
+        .with_handler(Arc::new(BalanceAtTimestampHandler))
+    }
+}
+
+impl ClaimHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A later registration for the same claim type replaces the earlier one.
+    pub fn register(&mut self, handler: Arc<dyn ClaimHandler>) {
+        self.handlers.insert(handler.claim_type(), handler);
+    }
+
+    pub fn with_handler(mut self, handler: Arc<dyn ClaimHandler>) -> Self {
+        self.register(handler);
+        self
+    }
+
+    pub fn get(&self, claim_type: ClaimType) -> Option<&Arc<dyn ClaimHandler>> {
+        self.handlers.get(&claim_type)
+    }
+
+    pub fn claim_types(&self) -> Vec<ClaimType> {
+        self.handlers.keys().copied().collect()
+    }
+
+    pub async fn build_statement(
+        &self,
+        adapter: &dyn VenueAdapter,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+        bundle: &EvidenceBundle,
+    ) -> Result<String> {
+        match self.get(request.claim_type) {
+            Some(handler) => handler.build_statement(adapter, request, ack, bundle).await,
+            None => adapter.build_statement(request, ack, bundle).await,
+        }
+    }
+
+    pub fn public_values(
+        &self,
+        request: &ProofRequest,
+        bundle: &EvidenceBundle,
+    ) -> Result<Map<String, Value>> {
+        match self.get(request.claim_type) {
+            Some(handler) => handler.public_values(request, bundle),
+            None => Ok(Map::new()),
+        }
+    }
+
+    pub fn public_inputs(&self, claim: &TruthClaim, evidence_root: &str, venue: Venue) -> Value {
+        match self.get(claim.r#type) {
+            Some(handler) => handler.public_inputs(claim, evidence_root, venue),
+            None => standard_public_inputs(&claim.claim_hash, evidence_root, venue, claim.r#type),
+        }
+    }
+
+    // The policy decision, tightened by whatever the claim's handler also checks and requires.
+    pub fn evaluate(
+        &self,
+        policy: &PolicyEngine,
+        request: &ProofRequest,
+        bundle: &EvidenceBundle,
+    ) -> PolicyDecision {
+        let claim_type = request.claim_type;
+        let decision = policy.evaluate(request.venue, claim_type, bundle);
+        let Some(handler) = self.get(claim_type).filter(|_| decision.ok) else {
+            return decision;
+        };
+        if let Some(rejection) = handler.check_evidence(request, bundle) {
+            return rejection;
+        }
+        let missing_tags: Vec<String> = handler
+            .required_evidence_tags()
+            .into_iter()
+            .filter(|tag| !bundle.observed_tags.contains(tag))
+            .collect();
+        if missing_tags.is_empty() {
+            return decision;
+        }
+        PolicyDecision {
+            ok: false,
+            reason: Some(NonProvableReason::EVIDENCE_MISSING),
+            details: format!(
+                "Missing evidence tags required by the {} claim handler: {}",
+                claim_type.name(),
+                missing_tags.join(", ")
+            ),
+            remediation: Some(Remediation {
+                missing_tags,
+                ..Remediation::for_reason(NonProvableReason::EVIDENCE_MISSING)
+            }),
+        }
+    }
+}
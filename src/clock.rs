@@ -0,0 +1,107 @@
+use crate::models::EvidenceBundle;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const TIMESTAMP_TAGS: [&str; 2] = ["submission_timestamp", "execution_timestamp"];
+
+// An adapter's measurement of one evidence source's clock against NTP: `offset_ms` is how far the
+// source clock runs ahead of true time, and `uncertainty_ms` bounds the measurement error (half
+// the round-trip delay plus the server's root dispersion).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClockOffset {
+    pub source_id: String,
+    pub offset_ms: f64,
+    pub uncertainty_ms: f64,
+    pub measured_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimestampTolerance {
+    pub max_skew_ms: f64,
+}
+
+impl Default for TimestampTolerance {
+    fn default() -> Self {
+        Self {
+            max_skew_ms: 1000.0,
+        }
+    }
+}
+
+// A timestamp as true UTC: any RFC 3339 offset or precision, corrected by the source's measured
+// clock offset.
+pub fn normalize_timestamp(raw: &str, offset: Option<&ClockOffset>) -> Option<DateTime<Utc>> {
+    let at = DateTime::parse_from_rfc3339(raw).ok()?.with_timezone(&Utc);
+    let shift_us = offset.map_or(0.0, |o| o.offset_ms * 1000.0);
+    Some(at - chrono::Duration::microseconds(shift_us.round() as i64))
+}
+
+struct Stamp<'a> {
+    source_id: &'a str,
+    at: DateTime<Utc>,
+    uncertainty_ms: f64,
+}
+
+// Sources may disagree on an event's time by the tolerance plus both measurement uncertainties;
+// beyond that, the timestamps conflict. Execution may not precede submission by more than the
+// same allowance.
+pub fn timestamp_conflicts(
+    bundle: &EvidenceBundle,
+    offsets: &[ClockOffset],
+    tolerance: &TimestampTolerance,
+) -> Vec<String> {
+    let by_source: HashMap<&str, &ClockOffset> =
+        offsets.iter().map(|o| (o.source_id.as_str(), o)).collect();
+    let stamps = |tag: &str| -> Vec<Stamp<'_>> {
+        bundle
+            .items
+            .iter()
+            .filter(|item| item.tags.iter().any(|t| t == tag))
+            .filter_map(|item| {
+                let offset = by_source.get(item.source_id.as_str()).copied();
+                Some(Stamp {
+                    source_id: &item.source_id,
+                    at: normalize_timestamp(&item.observed_at, offset)?,
+                    uncertainty_ms: offset.map_or(0.0, |o| o.uncertainty_ms.abs()),
+                })
+            })
+            .collect()
+    };
+    let allowance =
+        |a: &Stamp, b: &Stamp| tolerance.max_skew_ms + a.uncertainty_ms + b.uncertainty_ms;
+    let gap_ms = |a: &Stamp, b: &Stamp| {
+        (a.at - b.at)
+            .num_microseconds()
+            .map_or(f64::INFINITY, |us| us as f64 / 1000.0)
+    };
+
+    let mut conflicts = Vec::new();
+    for tag in TIMESTAMP_TAGS {
+        let stamps = stamps(tag);
+        for (idx, a) in stamps.iter().enumerate() {
+            for b in &stamps[idx + 1..] {
+                let gap = gap_ms(a, b).abs();
+                if gap > allowance(a, b) {
+                    conflicts.push(format!(
+                        "{}_skew between {} and {} ({:.0} ms)",
+                        tag, a.source_id, b.source_id, gap
+                    ));
+                }
+            }
+        }
+    }
+    let (submissions, executions) = (stamps(TIMESTAMP_TAGS[0]), stamps(TIMESTAMP_TAGS[1]));
+    for submission in &submissions {
+        for execution in &executions {
+            let lead = gap_ms(submission, execution);
+            if lead > allowance(submission, execution) {
+                conflicts.push(format!(
+                    "execution_before_submission: {} is {:.0} ms before {}",
+                    execution.source_id, lead, submission.source_id
+                ));
+            }
+        }
+    }
+    conflicts
+}
@@ -0,0 +1,315 @@
+use crate::hashing::HashAlgorithm;
+use crate::models::{
+    EncryptedFieldCommitment, EvidenceBundle, ProofRequest, ReceiptStatus, CLAIM_HASH_DOMAIN,
+    EVIDENCE_HASH_DOMAIN, HASH_DOMAIN_VERSION, INTEGRITY_HASH_DOMAIN, RECEIPT_HASH_DOMAIN,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[allow(non_camel_case_types)]
+pub enum CommitmentMode {
+    #[default]
+    JSON,
+    EVM_ABI,
+}
+
+// In EVM_ABI mode every commitment is keccak256 over a sequence of 32-byte words, so Solidity can
+// recompute it as keccak256(abi.encode(word0, word1, ...)). String fields enter as keccak256(bytes(s))
+// and existing 0x-prefixed 32-byte hashes enter as-is. When domain-separated, JSON commitments
+// carry a "domain" field and EVM_ABI commitments start with the tag's string word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitmentScheme {
+    pub mode: CommitmentMode,
+    pub hash_algorithm: HashAlgorithm,
+    pub domain_version: u32,
+}
+
+impl Default for CommitmentScheme {
+    fn default() -> Self {
+        Self::new(CommitmentMode::default(), HashAlgorithm::default())
+    }
+}
+
+impl CommitmentScheme {
+    pub fn new(mode: CommitmentMode, hash_algorithm: HashAlgorithm) -> Self {
+        Self {
+            mode,
+            hash_algorithm,
+            domain_version: HASH_DOMAIN_VERSION,
+        }
+    }
+
+    pub fn with_domain_version(mut self, domain_version: u32) -> Self {
+        self.domain_version = domain_version;
+        self
+    }
+
+    fn json(&self, domain: &str, value: serde_json::Value) -> String {
+        self.hash_algorithm
+            .hash_json(&self.json_preimage(domain, value))
+    }
+
+    fn json_preimage(&self, domain: &str, mut value: serde_json::Value) -> serde_json::Value {
+        if self.domain_version > 0 {
+            if let Some(fields) = value.as_object_mut() {
+                fields.insert("domain".to_string(), domain.into());
+            }
+        }
+        value
+    }
+
+    fn words(&self, domain: &str, words: &[[u8; 32]]) -> String {
+        if self.domain_version > 0 {
+            let mut tagged = vec![string_word(domain)];
+            tagged.extend_from_slice(words);
+            return evm_hash_words(&tagged);
+        }
+        evm_hash_words(words)
+    }
+
+    pub fn effective_hash_algorithm(&self) -> HashAlgorithm {
+        match self.mode {
+            CommitmentMode::JSON => self.hash_algorithm,
+            CommitmentMode::EVM_ABI => HashAlgorithm::Keccak256,
+        }
+    }
+
+    pub fn request_claim_hash(&self, request: &ProofRequest) -> String {
+        match self.mode {
+            CommitmentMode::JSON => self.json(
+                CLAIM_HASH_DOMAIN,
+                serde_json::json!({
+                    "venue": request.venue,
+                    "claim_type": request.claim_type,
+                    "account_ref": request.account_ref,
+                    "order_ref": request.order_ref,
+                    "execution_ref": request.execution_ref
+                }),
+            ),
+            CommitmentMode::EVM_ABI => self.words(
+                CLAIM_HASH_DOMAIN,
+                &[
+                    string_word(&json_str(&request.venue)),
+                    string_word(&json_str(&request.claim_type)),
+                    string_word(&request.account_ref),
+                    string_word(&request.order_ref),
+                    string_word(request.execution_ref.as_deref().unwrap_or("")),
+                ],
+            ),
+        }
+    }
+
+    // Encrypted field commitments extend the claim only when there are any, so claims without
+    // sealed fields hash as they always have.
+    pub fn claim_hash(
+        &self,
+        request: &ProofRequest,
+        statement: &str,
+        encrypted: &[EncryptedFieldCommitment],
+    ) -> String {
+        match self.mode {
+            CommitmentMode::JSON => self.json(
+                CLAIM_HASH_DOMAIN,
+                claim_fields(request, statement, encrypted),
+            ),
+            CommitmentMode::EVM_ABI => {
+                let mut words = vec![
+                    string_word(&json_str(&request.claim_type)),
+                    string_word(statement),
+                    string_word(&request.order_ref),
+                    string_word(request.execution_ref.as_deref().unwrap_or("")),
+                ];
+                for field in encrypted {
+                    words.push(string_word(&field.field));
+                    words.push(hash_word(&field.plaintext_hash));
+                }
+                self.words(CLAIM_HASH_DOMAIN, &words)
+            }
+        }
+    }
+
+    // The JSON documents `claim_hash` and `evidence_root` hash, for provers that open the
+    // commitments instead of taking them as given. EVM_ABI commitments have no JSON preimage.
+    pub fn claim_preimage(
+        &self,
+        request: &ProofRequest,
+        statement: &str,
+        encrypted: &[EncryptedFieldCommitment],
+    ) -> Option<serde_json::Value> {
+        (self.mode == CommitmentMode::JSON).then(|| {
+            self.json_preimage(
+                CLAIM_HASH_DOMAIN,
+                claim_fields(request, statement, encrypted),
+            )
+        })
+    }
+
+    pub fn evidence_preimage(&self, bundle: &EvidenceBundle) -> Option<serde_json::Value> {
+        (self.mode == CommitmentMode::JSON)
+            .then(|| self.json_preimage(EVIDENCE_HASH_DOMAIN, evidence_fields(bundle)))
+    }
+
+    pub fn empty_evidence_root(&self) -> String {
+        match self.mode {
+            CommitmentMode::JSON => {
+                self.json(EVIDENCE_HASH_DOMAIN, serde_json::json!({"empty": true}))
+            }
+            CommitmentMode::EVM_ABI => self.words(EVIDENCE_HASH_DOMAIN, &[]),
+        }
+    }
+
+    pub fn evidence_root(&self, bundle: &EvidenceBundle) -> String {
+        match self.mode {
+            CommitmentMode::JSON => self.json(EVIDENCE_HASH_DOMAIN, evidence_fields(bundle)),
+            CommitmentMode::EVM_ABI => {
+                let mut leaves: Vec<[u8; 32]> = bundle
+                    .items
+                    .iter()
+                    .map(|i| hash_word(&i.artifact_hash))
+                    .collect();
+                leaves.sort();
+                self.words(EVIDENCE_HASH_DOMAIN, &leaves)
+            }
+        }
+    }
+
+    pub fn schema_hash(&self, receipt_version: &str) -> String {
+        match self.mode {
+            CommitmentMode::JSON => self.json(
+                INTEGRITY_HASH_DOMAIN,
+                serde_json::json!({
+                    "schema": "zkreceipt.schema.json",
+                    "version": receipt_version
+                }),
+            ),
+            CommitmentMode::EVM_ABI => self.words(
+                INTEGRITY_HASH_DOMAIN,
+                &[
+                    string_word("zkreceipt.schema.json"),
+                    string_word(receipt_version),
+                ],
+            ),
+        }
+    }
+
+    pub fn receipt_hash(
+        &self,
+        status: ReceiptStatus,
+        claim_hash: &str,
+        evidence_root: &str,
+        proof_hash: &str,
+    ) -> String {
+        match self.mode {
+            CommitmentMode::JSON => self.json(
+                RECEIPT_HASH_DOMAIN,
+                serde_json::json!({
+                    "status": status,
+                    "claim_hash": claim_hash,
+                    "evidence_root": evidence_root,
+                    "proof_hash": proof_hash
+                }),
+            ),
+            CommitmentMode::EVM_ABI => self.words(
+                RECEIPT_HASH_DOMAIN,
+                &[
+                    string_word(&json_str(&status)),
+                    hash_word(claim_hash),
+                    hash_word(evidence_root),
+                    hash_word(proof_hash),
+                ],
+            ),
+        }
+    }
+}
+
+pub fn string_word(value: &str) -> [u8; 32] {
+    HashAlgorithm::Keccak256.digest_bytes(value.as_bytes())
+}
+
+pub fn hash_word(hash: &str) -> [u8; 32] {
+    let decoded = hex::decode(hash.trim_start_matches("0x")).ok();
+    match decoded.and_then(|bytes| <[u8; 32]>::try_from(bytes).ok()) {
+        Some(word) => word,
+        None => string_word(hash),
+    }
+}
+
+pub fn evm_hash_words(words: &[[u8; 32]]) -> String {
+    HashAlgorithm::Keccak256.hash_bytes(&words.concat())
+}
+
+fn claim_fields(
+    request: &ProofRequest,
+    statement: &str,
+    encrypted: &[EncryptedFieldCommitment],
+) -> serde_json::Value {
+    let mut fields = serde_json::json!({
+        "claim_type": request.claim_type,
+        "statement": statement,
+        "order_ref": request.order_ref,
+        "execution_ref": request.execution_ref
+    });
+    if !encrypted.is_empty() {
+        fields["encrypted_fields"] = serde_json::json!(encrypted);
+    }
+    fields
+}
+
+fn evidence_fields(bundle: &EvidenceBundle) -> serde_json::Value {
+    let mut leaves: Vec<&String> = bundle.items.iter().map(|i| &i.artifact_hash).collect();
+    leaves.sort();
+    serde_json::json!({ "leaves": leaves })
+}
+
+fn json_str(value: &impl Serialize) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evm_words_match_abi_encode_of_bytes32() {
+        let a = string_word("ORDER_PLACED");
+        let b = hash_word("0x00000000000000000000000000000000000000000000000000000000000000ff");
+        assert_eq!(b[31], 0xff);
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&a);
+        packed.extend_from_slice(&b);
+        assert_eq!(
+            evm_hash_words(&[a, b]),
+            HashAlgorithm::Keccak256.hash_bytes(&packed)
+        );
+    }
+
+    #[test]
+    fn domain_tags_separate_contexts_and_legacy_stays_untagged() {
+        let tagged = CommitmentScheme::default();
+        let legacy = CommitmentScheme::default().with_domain_version(0);
+        assert_eq!(
+            legacy.empty_evidence_root(),
+            HashAlgorithm::Sha256.hash_json(&serde_json::json!({"empty": true}))
+        );
+        assert_ne!(tagged.empty_evidence_root(), legacy.empty_evidence_root());
+        assert_ne!(
+            tagged.json(CLAIM_HASH_DOMAIN, serde_json::json!({"a": 1})),
+            tagged.json(RECEIPT_HASH_DOMAIN, serde_json::json!({"a": 1}))
+        );
+        let evm = CommitmentScheme::new(CommitmentMode::EVM_ABI, HashAlgorithm::Sha256);
+        assert_eq!(
+            evm.empty_evidence_root(),
+            evm_hash_words(&[string_word(EVIDENCE_HASH_DOMAIN)])
+        );
+    }
+
+    #[test]
+    fn evm_mode_always_reports_keccak() {
+        let scheme = CommitmentScheme::new(CommitmentMode::EVM_ABI, HashAlgorithm::Blake3);
+        assert_eq!(scheme.effective_hash_algorithm(), HashAlgorithm::Keccak256);
+    }
+}
@@ -0,0 +1,626 @@
+use crate::adapters::{SyntheticVenueAdapter, VenueAdapter, WorkloadScenario};
+use crate::credentials::{adapter_credential_name, CredentialStore, Secret};
+use crate::engine::ReceiptEngine;
+use crate::leader::{LeaderElection, DEFAULT_LEASE_NAME, DEFAULT_LEASE_TTL};
+use crate::models::{hash_str, ProofBackend, SignerIdentity, StatementLocale, Venue};
+use crate::payload_encryption::PayloadDecryptionKey;
+use crate::policy::{
+    default_spec_document, read_json, PolicyEngine, PolicyWatch, CLAIM_TAXONOMY_FILE,
+    FINALITY_RULES_FILE, SOURCE_PRECEDENCE_FILE,
+};
+use crate::proof_queue::ProofQueue;
+use crate::prover::{
+    build_mvp_prover, FallbackProver, PicoMvpProver, ProverBackend, ProverStrategy, Sp1MvpProver,
+};
+use crate::signing::Ed25519ReceiptSigner;
+use crate::store::ReceiptStore;
+use crate::verifier::OffchainVerifier;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub const CONFIG_ENV: &str = "ZKPUTER_CONFIG";
+const DEFAULT_SIGNER_NAME: &str = "zkputer";
+
+// How a deployment assembles its engine. Read from the TOML file ZKPUTER_CONFIG names, then
+// overridden by the ZKPUTER_* variables the binaries have always read; unset fields keep the
+// defaults an engine built by hand would have.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub venues: Vec<Venue>,
+    pub receipt_version: Option<String>,
+    pub statement_locales: Vec<StatementLocale>,
+    pub max_concurrent_proofs: Option<usize>,
+    pub order_execution: bool,
+    pub signer: SignerConfig,
+    pub prover: ProverConfig,
+    pub policy: PolicyConfig,
+    pub store: StoreConfig,
+    pub payload_encryption: PayloadEncryptionConfig,
+    pub leader_election: LeaderElectionConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            venues: Venue::all().collect(),
+            receipt_version: None,
+            statement_locales: vec![],
+            max_concurrent_proofs: None,
+            order_execution: false,
+            signer: SignerConfig::default(),
+            prover: ProverConfig::default(),
+            policy: PolicyConfig::default(),
+            store: StoreConfig::default(),
+            payload_encryption: PayloadEncryptionConfig::default(),
+            leader_election: LeaderElectionConfig::default(),
+        }
+    }
+}
+
+// With neither a key path nor a seed, receipts are bound with the sha256 signer, under `name`
+// when one is given and the dev signer otherwise. The seed only ever comes from
+// ZKPUTER_SIGNING_KEY, never from the file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignerConfig {
+    pub name: Option<String>,
+    pub key_path: Option<PathBuf>,
+    #[serde(skip)]
+    pub key_seed: Option<Secret>,
+}
+
+// A host binary replaces the strategy's MVP stand-in for that backend with real proving, which
+// needs the crate's matching feature. `sp1_vkey_hash` and `risc0_image_id` pin the keys of the
+// guests built for the hosts; receipts proved by a host verify only under a pinned key.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProverConfig {
+    pub strategy: String,
+    pub sp1_host: Option<PathBuf>,
+    pub sp1_proof_dir: Option<PathBuf>,
+    pub sp1_vkey_hash: Option<String>,
+    pub risc0_host: Option<PathBuf>,
+    pub risc0_receipt_dir: Option<PathBuf>,
+    pub risc0_image_id: Option<String>,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            strategy: "sp1".to_string(),
+            sp1_host: None,
+            sp1_proof_dir: None,
+            sp1_vkey_hash: None,
+            risc0_host: None,
+            risc0_receipt_dir: None,
+            risc0_image_id: None,
+        }
+    }
+}
+
+impl ProverConfig {
+    pub fn prover(&self) -> Result<Arc<dyn ProverBackend>> {
+        let strategy = ProverStrategy::from_env(Some(&self.strategy));
+        let sp1 = || -> Result<Arc<dyn ProverBackend>> {
+            match &self.sp1_host {
+                Some(host) => self.sp1_prover(host),
+                None => Ok(Arc::new(Sp1MvpProver)),
+            }
+        };
+        Ok(match strategy {
+            ProverStrategy::Sp1Only => sp1()?,
+            ProverStrategy::Sp1WithPicoFallback => {
+                Arc::new(FallbackProver::new(sp1()?, Arc::new(PicoMvpProver)))
+            }
+            ProverStrategy::Risc0Only => match &self.risc0_host {
+                Some(host) => self.risc0_prover(host)?,
+                None => build_mvp_prover(strategy),
+            },
+            ProverStrategy::PicoOnly => build_mvp_prover(strategy),
+        })
+    }
+
+    #[cfg(feature = "sp1")]
+    fn sp1_prover(&self, host: &Path) -> Result<Arc<dyn ProverBackend>> {
+        if self.sp1_vkey_hash.is_none() {
+            bail!("prover.sp1_host needs prover.sp1_vkey_hash so its proofs can be verified");
+        }
+        let proof_dir = self
+            .sp1_proof_dir
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("zkputer-sp1"));
+        Ok(Arc::new(crate::sp1::Sp1Prover::new(host, proof_dir)))
+    }
+
+    #[cfg(not(feature = "sp1"))]
+    fn sp1_prover(&self, _host: &Path) -> Result<Arc<dyn ProverBackend>> {
+        bail!("prover.sp1_host needs zkputer built with the sp1 feature")
+    }
+
+    #[cfg(feature = "risc0")]
+    fn risc0_prover(&self, host: &Path) -> Result<Arc<dyn ProverBackend>> {
+        if self.risc0_image_id.is_none() {
+            bail!("prover.risc0_host needs prover.risc0_image_id so its receipts can be verified");
+        }
+        let receipt_dir = self
+            .risc0_receipt_dir
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("zkputer-risc0"));
+        Ok(Arc::new(crate::risc0::Risc0Prover::new(host, receipt_dir)))
+    }
+
+    #[cfg(not(feature = "risc0"))]
+    fn risc0_prover(&self, _host: &Path) -> Result<Arc<dyn ProverBackend>> {
+        bail!("prover.risc0_host needs zkputer built with the risc0 feature")
+    }
+
+    pub fn verifier(&self) -> OffchainVerifier {
+        let mut verifier = OffchainVerifier::new();
+        if let Some(key_hash) = &self.sp1_vkey_hash {
+            verifier = verifier.with_pinned_key(ProofBackend::SP1, key_hash);
+        }
+        if let Some(image_id) = &self.risc0_image_id {
+            verifier = verifier.with_pinned_key(ProofBackend::RISC0, image_id);
+        }
+        verifier
+    }
+}
+
+// Any document left unset is read from the crate's own spec directory.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyConfig {
+    pub claim_taxonomy: Option<PathBuf>,
+    pub source_precedence: Option<PathBuf>,
+    pub finality_rules: Option<PathBuf>,
+    // Reload the claim taxonomy and source precedence when they change on disk; needs both paths.
+    // Finality rules are read once.
+    pub watch: bool,
+    // Tenant id to the overlay document patching the base policy for that tenant's requests.
+    pub tenant_overlays: BTreeMap<String, PathBuf>,
+}
+
+// Without a key, requests with encrypted payload fields are rejected. Like the signing seed, the
+// hex secret only ever comes from ZKPUTER_PAYLOAD_KEY, never from the file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PayloadEncryptionConfig {
+    pub key_path: Option<PathBuf>,
+    #[serde(skip)]
+    pub key: Option<Secret>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreBackend {
+    #[default]
+    Memory,
+    Sqlite,
+    EventSourced,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StoreConfig {
+    pub backend: StoreBackend,
+    pub path: Option<PathBuf>,
+}
+
+// Instances sharing one persistent store campaign for its lease; only the holder proves. Each
+// instance needs a distinct `instance_id`; without one a random id is picked at startup.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaderElectionConfig {
+    pub enabled: bool,
+    pub instance_id: Option<String>,
+    pub lease_name: String,
+    pub lease_ttl_ms: u64,
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            instance_id: None,
+            lease_name: DEFAULT_LEASE_NAME.to_string(),
+            lease_ttl_ms: DEFAULT_LEASE_TTL.as_millis() as u64,
+        }
+    }
+}
+
+impl LeaderElectionConfig {
+    pub fn election(&self) -> Result<Option<LeaderElection>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        if self.lease_ttl_ms == 0 {
+            bail!("leader_election.lease_ttl_ms must be positive");
+        }
+        let instance_id = self
+            .instance_id
+            .clone()
+            .unwrap_or_else(|| format!("zkputer-{}", uuid::Uuid::new_v4()));
+        Ok(Some(
+            LeaderElection::new(
+                instance_id,
+                std::time::Duration::from_millis(self.lease_ttl_ms),
+            )
+            .with_lease_name(self.lease_name.clone()),
+        ))
+    }
+}
+
+impl Config {
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        toml::from_str(text).context("invalid zkputer config")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_toml_str(&text).with_context(|| format!("in {}", path.display()))
+    }
+
+    // The file ZKPUTER_CONFIG names (or the defaults), with the process environment applied on top.
+    pub fn from_env() -> Result<Self> {
+        let config = match std::env::var(CONFIG_ENV)
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+        {
+            Some(path) => Self::load(Path::new(path.trim()))?,
+            None => Self::default(),
+        };
+        config.with_env_overrides(|name| std::env::var(name).ok())
+    }
+
+    // Each variable that is set and non-empty replaces the matching field.
+    pub fn with_env_overrides(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| set_var(&var, name);
+        if let Some(venues) = var("ZKPUTER_VENUES") {
+            self.venues = venues
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    Venue::from_slug(v)
+                        .ok_or_else(|| anyhow!("unknown venue {} in ZKPUTER_VENUES", v))
+                })
+                .collect::<Result<_>>()?;
+        }
+        if let Some(version) = var("ZKPUTER_RECEIPT_VERSION") {
+            self.receipt_version = Some(version);
+        }
+        if let Some(locales) = var("ZKPUTER_STATEMENT_LOCALES") {
+            self.statement_locales = StatementLocale::list_from_env(Some(&locales));
+        }
+        if let Some(max) = var("ZKPUTER_MAX_CONCURRENT_PROOFS") {
+            self.max_concurrent_proofs = Some(
+                max.parse()
+                    .context("ZKPUTER_MAX_CONCURRENT_PROOFS must be an integer")?,
+            );
+        }
+        self.signer = self.signer.with_env_overrides(var);
+        if let Some(strategy) = var("ZKPUTER_PROVER_STRATEGY") {
+            self.prover.strategy = strategy;
+        }
+        if let Some(host) = var("ZKPUTER_SP1_HOST") {
+            self.prover.sp1_host = Some(PathBuf::from(host));
+        }
+        if let Some(dir) = var("ZKPUTER_SP1_PROOF_DIR") {
+            self.prover.sp1_proof_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(host) = var("ZKPUTER_RISC0_HOST") {
+            self.prover.risc0_host = Some(PathBuf::from(host));
+        }
+        if let Some(dir) = var("ZKPUTER_RISC0_RECEIPT_DIR") {
+            self.prover.risc0_receipt_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(key_hash) = var("ZKPUTER_SP1_VKEY_HASH") {
+            self.prover.sp1_vkey_hash = Some(key_hash);
+        }
+        if let Some(image_id) = var("ZKPUTER_RISC0_IMAGE_ID") {
+            self.prover.risc0_image_id = Some(image_id);
+        }
+        if let Some(path) = var("ZKPUTER_CLAIM_TAXONOMY") {
+            self.policy.claim_taxonomy = Some(PathBuf::from(path));
+        }
+        if let Some(path) = var("ZKPUTER_SOURCE_PRECEDENCE") {
+            self.policy.source_precedence = Some(PathBuf::from(path));
+        }
+        if let Some(path) = var("ZKPUTER_FINALITY_RULES") {
+            self.policy.finality_rules = Some(PathBuf::from(path));
+        }
+        if let Some(watch) = var("ZKPUTER_POLICY_WATCH") {
+            self.policy.watch = matches!(watch.as_str(), "1" | "true");
+        }
+        self.store = self.store.with_env_overrides(var)?;
+        if let Some(path) = var("ZKPUTER_PAYLOAD_KEY_FILE") {
+            self.payload_encryption.key_path = Some(PathBuf::from(path));
+        }
+        if let Some(key) = var("ZKPUTER_PAYLOAD_KEY") {
+            self.payload_encryption.key = Some(Secret::new(key));
+        }
+        if let Some(enabled) = var("ZKPUTER_LEADER_ELECTION") {
+            self.leader_election.enabled = matches!(enabled.as_str(), "1" | "true");
+        }
+        if let Some(instance_id) = var("ZKPUTER_INSTANCE_ID") {
+            self.leader_election.instance_id = Some(instance_id);
+        }
+        Ok(self)
+    }
+}
+
+impl StoreConfig {
+    pub fn with_env_overrides(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| set_var(&var, name);
+        // A store path alone selects SQLite, as it always has.
+        if let Some(path) = var("ZKPUTER_STORE_PATH") {
+            self.path = Some(PathBuf::from(path));
+            if self.backend == StoreBackend::Memory {
+                self.backend = StoreBackend::Sqlite;
+            }
+        }
+        if let Some(mode) = var("ZKPUTER_STORE_MODE") {
+            self.backend = match mode.as_str() {
+                "current" if self.path.is_some() => StoreBackend::Sqlite,
+                "current" => StoreBackend::Memory,
+                "event_sourced" => StoreBackend::EventSourced,
+                other => bail!(
+                    "unknown ZKPUTER_STORE_MODE {}; expected current or event_sourced",
+                    other
+                ),
+            };
+        }
+        Ok(self)
+    }
+
+    pub fn open(&self) -> Result<ReceiptStore> {
+        match (self.backend, &self.path) {
+            (StoreBackend::Memory, _) => Ok(ReceiptStore::new()),
+            (StoreBackend::Sqlite, Some(path)) => ReceiptStore::open_sqlite(path),
+            (StoreBackend::EventSourced, Some(path)) => ReceiptStore::open_event_sourced(path),
+            (backend, None) => bail!("the {:?} store backend needs a path", backend),
+        }
+    }
+}
+
+impl PolicyConfig {
+    // Documents without a configured path fall back to the defaults `PolicyEngine::new(None)` uses.
+    pub fn load(&self) -> Result<PolicyEngine> {
+        let mut policy = PolicyEngine::from_values(
+            document(&self.claim_taxonomy, CLAIM_TAXONOMY_FILE)?,
+            document(&self.source_precedence, SOURCE_PRECEDENCE_FILE)?,
+        )?
+        .with_finality_rules(document(&self.finality_rules, FINALITY_RULES_FILE)?)?;
+        for (tenant, path) in &self.tenant_overlays {
+            policy = policy.with_tenant_overlay(tenant, read_json(path)?)?;
+        }
+        Ok(policy)
+    }
+
+    pub fn watch(&self) -> Result<PolicyWatch> {
+        let mut watch = match (&self.claim_taxonomy, &self.source_precedence) {
+            (Some(claim_taxonomy), Some(source_precedence)) => {
+                PolicyEngine::watch(claim_taxonomy, source_precedence)?
+                    .with_finality_rules(document(&self.finality_rules, FINALITY_RULES_FILE)?)?
+            }
+            _ => bail!("watching the policy needs both claim_taxonomy and source_precedence paths"),
+        };
+        for (tenant, path) in &self.tenant_overlays {
+            watch = watch.with_tenant_overlay(tenant, read_json(path)?)?;
+        }
+        Ok(watch)
+    }
+}
+
+// A configured policy document, or the default one of that name.
+fn document(path: &Option<PathBuf>, file: &str) -> Result<serde_json::Value> {
+    match path {
+        Some(path) => read_json(path),
+        None => default_spec_document(file),
+    }
+}
+
+impl SignerConfig {
+    pub fn with_env_overrides(mut self, var: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name: &str| set_var(&var, name);
+        if let Some(name) = var("ZKPUTER_SIGNER_NAME") {
+            self.name = Some(name);
+        }
+        if let Some(path) = var("ZKPUTER_SIGNING_KEY_FILE") {
+            self.key_path = Some(PathBuf::from(path));
+        }
+        if let Some(seed) = var("ZKPUTER_SIGNING_KEY") {
+            self.key_seed = Some(Secret::new(seed));
+        }
+        self
+    }
+
+    pub fn ed25519_signer(&self) -> Result<Option<Ed25519ReceiptSigner>> {
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SIGNER_NAME.to_string());
+        match (&self.key_seed, &self.key_path) {
+            (Some(seed), _) => Ed25519ReceiptSigner::from_hex_seed(name, seed.expose()).map(Some),
+            (None, Some(path)) => Ed25519ReceiptSigner::from_file(name, path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+impl PayloadEncryptionConfig {
+    pub fn decryption_key(&self) -> Result<Option<PayloadDecryptionKey>> {
+        match (&self.key, &self.key_path) {
+            (Some(key), _) => PayloadDecryptionKey::from_hex(key.expose()).map(Some),
+            (None, Some(path)) => PayloadDecryptionKey::from_file(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+// Assembles a ReceiptEngine from a Config. Anything set explicitly (an adapter for a venue, a
+// prover, a policy, a store) replaces what the config would have built; enabled venues without an
+// explicit adapter get a synthetic one, holding the venue's API key when the credential store has it.
+pub struct ReceiptEngineBuilder {
+    config: Config,
+    adapters: HashMap<Venue, Arc<dyn VenueAdapter>>,
+    prover: Option<Arc<dyn ProverBackend>>,
+    policy_engine: Option<PolicyEngine>,
+    store: Option<ReceiptStore>,
+    credentials: Option<CredentialStore>,
+    workload_scenarios: Vec<WorkloadScenario>,
+}
+
+impl ReceiptEngineBuilder {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            adapters: HashMap::new(),
+            prover: None,
+            policy_engine: None,
+            store: None,
+            credentials: None,
+            workload_scenarios: vec![],
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Config::from_env().map(Self::new)
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    // Used for its venue when that venue is enabled.
+    pub fn with_adapter(mut self, adapter: Arc<dyn VenueAdapter>) -> Self {
+        self.adapters.insert(adapter.venue(), adapter);
+        self
+    }
+
+    pub fn with_prover(mut self, prover: Arc<dyn ProverBackend>) -> Self {
+        self.prover = Some(prover);
+        self
+    }
+
+    pub fn with_policy_engine(mut self, policy_engine: PolicyEngine) -> Self {
+        self.policy_engine = Some(policy_engine);
+        self
+    }
+
+    pub fn with_store(mut self, store: ReceiptStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn with_credentials(mut self, credentials: CredentialStore) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    // Shapes the synthetic adapters the builder creates; explicit adapters are left as they are.
+    pub fn with_workload_scenarios(mut self, scenarios: Vec<WorkloadScenario>) -> Self {
+        self.workload_scenarios = scenarios;
+        self
+    }
+
+    pub async fn build(self) -> Result<ReceiptEngine> {
+        let Self {
+            config,
+            mut adapters,
+            prover,
+            policy_engine,
+            store,
+            credentials,
+            workload_scenarios,
+        } = self;
+        if config.venues.is_empty() {
+            bail!("no venues are enabled");
+        }
+        let mut enabled: Vec<Arc<dyn VenueAdapter>> = Vec::new();
+        for venue in &config.venues {
+            if let Some(adapter) = adapters.remove(venue) {
+                enabled.push(adapter);
+                continue;
+            }
+            let mut adapter = SyntheticVenueAdapter::new(*venue)
+                .with_workload_scenarios(workload_scenarios.clone());
+            let name = adapter_credential_name(*venue);
+            if let Some(credentials) = &credentials {
+                if credentials.contains(&name).await {
+                    adapter = adapter.with_credentials(credentials.clone(), name);
+                }
+            }
+            enabled.push(Arc::new(adapter));
+        }
+        let (policy_engine, policy_watch) = match policy_engine {
+            Some(policy_engine) => (policy_engine, None),
+            None if config.policy.watch => {
+                let watch = config.policy.watch()?;
+                (watch.current(), Some(watch))
+            }
+            None => (config.policy.load()?, None),
+        };
+        let prover = match prover {
+            Some(prover) => prover,
+            None => config.prover.prover()?,
+        };
+        let store = match store {
+            Some(store) => store,
+            None => config.store.open()?,
+        };
+        let election = config.leader_election.election()?;
+        // Only a shared store gives the instances one lease to contend for.
+        if election.is_some() && !store.is_persistent() {
+            bail!("leader election needs a persistent store shared by every instance");
+        }
+        let proof_queue = config
+            .max_concurrent_proofs
+            .map(ProofQueue::new)
+            .unwrap_or_default();
+
+        let mut engine =
+            ReceiptEngine::new(enabled, policy_engine, prover, config.prover.verifier())
+                .with_store(store)
+                .with_proof_queue(proof_queue)
+                .with_statement_locales(config.statement_locales.clone())
+                .with_order_execution(config.order_execution);
+        if let Some(version) = &config.receipt_version {
+            engine = engine.with_receipt_version(version)?;
+        }
+        if let Some(credentials) = credentials {
+            engine = engine.with_credentials(credentials);
+        }
+        if let Some(watch) = &policy_watch {
+            engine = engine.with_policy_watch(watch);
+        }
+        if let Some(key) = config.payload_encryption.decryption_key()? {
+            engine = engine.with_payload_decryption_key(key);
+        }
+        if let Some(election) = election {
+            engine = engine.with_leader_election(election);
+        }
+        engine = match (config.signer.ed25519_signer()?, &config.signer.name) {
+            (Some(signer), _) => engine.with_receipt_signer(Arc::new(signer)),
+            (None, Some(name)) => engine.with_signer(SignerIdentity::new(
+                name.clone(),
+                format!("{}-key-0", name),
+                hash_str(name),
+                "sha256-binding",
+            )),
+            (None, None) => engine,
+        };
+        Ok(engine)
+    }
+}
+
+fn set_var(var: impl Fn(&str) -> Option<String>, name: &str) -> Option<String> {
+    var(name)
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
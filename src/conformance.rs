@@ -0,0 +1,163 @@
+use crate::adapters::VenueAdapter;
+use crate::models::{ClaimType, NonProvableReason, ProofRequest, Venue};
+use crate::policy::PolicyEngine;
+use crate::validation::{validate_evidence, validate_payload, validate_request};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+pub const ADAPTER_FIXTURES_PATH: &str = "fixtures/adapter-conformance.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterFixture {
+    pub id: String,
+    pub claim_type: ClaimType,
+    pub account_ref: String,
+    pub order_ref: String,
+    #[serde(default)]
+    pub execution_ref: Option<String>,
+    #[serde(default)]
+    pub payload: Value,
+    #[serde(default)]
+    pub expected_reason: Option<NonProvableReason>,
+}
+
+impl AdapterFixture {
+    pub fn request_for(&self, venue: Venue) -> ProofRequest {
+        ProofRequest {
+            execution_ref: self.execution_ref.clone(),
+            payload: self.payload.clone(),
+            ..ProofRequest::new(
+                venue,
+                self.claim_type,
+                self.account_ref.clone(),
+                self.order_ref.clone(),
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "verdict", content = "detail")]
+pub enum FixtureVerdict {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureOutcome {
+    pub fixture_id: String,
+    pub venue: Venue,
+    pub verdict: FixtureVerdict,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureFile {
+    fixtures: Vec<AdapterFixture>,
+}
+
+pub fn load_adapter_fixtures(path: &Path) -> Result<Vec<AdapterFixture>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let parsed: FixtureFile = serde_json::from_str(&text)
+        .with_context(|| format!("failed to parse fixtures {}", path.display()))?;
+    Ok(parsed.fixtures)
+}
+
+pub async fn certify_adapter(
+    adapter: &dyn VenueAdapter,
+    policy: &PolicyEngine,
+    fixtures: &[AdapterFixture],
+) -> Vec<FixtureOutcome> {
+    let venue = adapter.venue();
+    let capabilities = adapter.capabilities();
+    let mut outcomes = Vec::with_capacity(fixtures.len());
+    for fixture in fixtures {
+        let verdict = if !capabilities.supports_claim(fixture.claim_type) {
+            FixtureVerdict::Skipped(format!("adapter does not declare {:?}", fixture.claim_type))
+        } else {
+            match run_fixture(
+                adapter,
+                policy,
+                &fixture.request_for(venue),
+                &capabilities.evidence_tags,
+            )
+            .await
+            {
+                Err(detail) => FixtureVerdict::Failed(detail),
+                Ok(reason) if reason == fixture.expected_reason => FixtureVerdict::Passed,
+                Ok(reason) => FixtureVerdict::Failed(format!(
+                    "expected {} but adapter evidence produced {}",
+                    describe(fixture.expected_reason),
+                    describe(reason)
+                )),
+            }
+        };
+        outcomes.push(FixtureOutcome {
+            fixture_id: fixture.id.clone(),
+            venue,
+            verdict,
+        });
+    }
+    outcomes
+}
+
+// Mirrors the engine pipeline up to policy evaluation. Ok(None) means the evidence would be provable.
+async fn run_fixture(
+    adapter: &dyn VenueAdapter,
+    policy: &PolicyEngine,
+    request: &ProofRequest,
+    declared_tags: &[String],
+) -> std::result::Result<Option<NonProvableReason>, String> {
+    let mut issues = validate_request(request);
+    if let Some(schema) = policy.payload_schema(request.claim_type) {
+        issues.extend(validate_payload(&request.payload, &schema));
+    }
+    if !issues.is_empty() {
+        return Err(format!("fixture request is invalid: {}", issues.join("; ")));
+    }
+    let Ok(ack) = adapter.acknowledge(request).await else {
+        return Ok(Some(NonProvableReason::SOURCE_UNAVAILABLE));
+    };
+    let Ok(bundle) = adapter.collect_evidence(request, &ack).await else {
+        return Ok(Some(NonProvableReason::SOURCE_UNAVAILABLE));
+    };
+    let evidence_issues = validate_evidence(
+        &bundle,
+        &policy.evidence_tag_vocabulary(),
+        &policy.known_source_kinds(),
+    );
+    if !evidence_issues.is_empty() {
+        return Err(format!(
+            "malformed evidence: {}",
+            evidence_issues.join("; ")
+        ));
+    }
+    let mut undeclared: Vec<&String> = bundle
+        .observed_tags
+        .iter()
+        .filter(|tag| !declared_tags.contains(tag))
+        .collect();
+    if !undeclared.is_empty() {
+        undeclared.sort();
+        return Err(format!(
+            "evidence carries tags not declared in adapter capabilities: {}",
+            undeclared
+                .iter()
+                .map(|t| t.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    let decision = policy.evaluate(request.venue, request.claim_type, &bundle);
+    Ok(if decision.ok { None } else { decision.reason })
+}
+
+fn describe(reason: Option<NonProvableReason>) -> String {
+    match reason {
+        Some(reason) => format!("NON_PROVABLE {:?}", reason),
+        None => "provable evidence".to_string(),
+    }
+}
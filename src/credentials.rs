@@ -0,0 +1,433 @@
+use crate::models::{hash_str, now_iso, Venue};
+use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::aead::{AeadCore, AeadInPlace, KeyInit, OsRng};
+use chacha20poly1305::{Tag, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+pub const DEFAULT_ENV_PREFIX: &str = "ZKPUTER_CRED_";
+const SEALED_FORMAT_VERSION: u32 = 2;
+// Bound into the tag, so a file cannot be passed off as another format's.
+const SEALED_ASSOCIATED_DATA: &[u8] = b"zkputer sealed credentials v2";
+const REDACTED: &str = "[REDACTED]";
+// Values this short are not scanned for in text; they would match unrelated content.
+const MIN_SCANNED_SECRET_LEN: usize = 6;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn fingerprint(&self) -> String {
+        hash_str(&self.0)[..18].to_string()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
+pub trait CredentialSource: Send + Sync {
+    fn describe(&self) -> String;
+    fn load(&self) -> Result<HashMap<String, Secret>>;
+}
+
+// Reads `<prefix><NAME>` variables; `ZKPUTER_CRED_HYPERLIQUID_API_KEY` becomes
+// `hyperliquid_api_key`. Re-read on every refresh so values rotated in the environment of a
+// long-running process (e.g. by a secrets sidecar) are picked up.
+#[derive(Debug, Clone)]
+pub struct EnvCredentialSource {
+    prefix: String,
+}
+
+impl EnvCredentialSource {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl Default for EnvCredentialSource {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENV_PREFIX)
+    }
+}
+
+impl CredentialSource for EnvCredentialSource {
+    fn describe(&self) -> String {
+        format!("env:{}*", self.prefix)
+    }
+
+    fn load(&self) -> Result<HashMap<String, Secret>> {
+        Ok(std::env::vars()
+            .filter_map(|(key, value)| {
+                let name = key.strip_prefix(&self.prefix)?;
+                (!name.is_empty() && !value.is_empty())
+                    .then(|| (name.to_ascii_lowercase(), Secret::new(value)))
+            })
+            .collect())
+    }
+}
+
+pub fn adapter_credential_name(venue: Venue) -> String {
+    format!("{}_api_key", venue.slug())
+}
+
+// ZKPUTER_CREDENTIALS_FILE plus ZKPUTER_CREDENTIALS_KEY select a sealed file; otherwise
+// ZKPUTER_CRED_* variables are used. Either is reloaded every ZKPUTER_CREDENTIALS_REFRESH_SECS
+// (default 60).
+pub fn credential_store_from_env() -> Result<CredentialStore> {
+    let source: Arc<dyn CredentialSource> = match std::env::var("ZKPUTER_CREDENTIALS_FILE") {
+        Ok(path) => {
+            let key = std::env::var("ZKPUTER_CREDENTIALS_KEY").map_err(|_| {
+                anyhow!("ZKPUTER_CREDENTIALS_KEY is required with ZKPUTER_CREDENTIALS_FILE")
+            })?;
+            Arc::new(SealedFileCredentialSource::from_hex_key(path, &key)?)
+        }
+        Err(_) => Arc::new(EnvCredentialSource::default()),
+    };
+    let refresh_secs = std::env::var("ZKPUTER_CREDENTIALS_REFRESH_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(60);
+    Ok(CredentialStore::new(source)?.with_refresh_interval(Duration::from_secs(refresh_secs)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SealedFile {
+    version: u32,
+    nonce: String,
+    ciphertext: String,
+    tag: String,
+}
+
+// A JSON map of credential names to values, sealed under a 32-byte key with XChaCha20-Poly1305
+// and a random 24-byte nonce. The file can be replaced in place to rotate; the next refresh picks
+// it up.
+pub struct SealedFileCredentialSource {
+    path: PathBuf,
+    key: [u8; 32],
+}
+
+impl SealedFileCredentialSource {
+    pub fn new(path: impl Into<PathBuf>, key: [u8; 32]) -> Self {
+        Self {
+            path: path.into(),
+            key,
+        }
+    }
+
+    pub fn from_hex_key(path: impl Into<PathBuf>, key_hex: &str) -> Result<Self> {
+        Ok(Self::new(path, parse_key(key_hex)?))
+    }
+}
+
+impl CredentialSource for SealedFileCredentialSource {
+    fn describe(&self) -> String {
+        format!("sealed_file:{}", self.path.display())
+    }
+
+    fn load(&self) -> Result<HashMap<String, Secret>> {
+        let raw = std::fs::read_to_string(&self.path).with_context(|| {
+            format!("failed to read sealed credentials {}", self.path.display())
+        })?;
+        unseal_credentials(&self.key, &raw)
+    }
+}
+
+pub fn parse_key(key_hex: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(key_hex.trim().trim_start_matches("0x"))
+        .context("credentials key must be hex")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("credentials key must be 32 bytes"))
+}
+
+pub fn seal_credentials(key: &[u8; 32], credentials: &HashMap<String, String>) -> Result<String> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let mut data = serde_json::to_vec(credentials)?;
+    let tag = XChaCha20Poly1305::new(key.into())
+        .encrypt_in_place_detached(&nonce, SEALED_ASSOCIATED_DATA, &mut data)
+        .map_err(|_| anyhow!("failed to seal credentials"))?;
+    Ok(serde_json::to_string_pretty(&SealedFile {
+        version: SEALED_FORMAT_VERSION,
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(&data),
+        tag: hex::encode(tag),
+    })?)
+}
+
+pub fn unseal_credentials(key: &[u8; 32], sealed: &str) -> Result<HashMap<String, Secret>> {
+    let file: SealedFile =
+        serde_json::from_str(sealed).context("sealed credentials are not valid JSON")?;
+    if file.version != SEALED_FORMAT_VERSION {
+        return Err(anyhow!(
+            "unsupported sealed credentials version {}; re-seal the file with seal_credentials",
+            file.version
+        ));
+    }
+    let nonce = hex::decode(&file.nonce).context("sealed credentials nonce must be hex")?;
+    let mut data =
+        hex::decode(&file.ciphertext).context("sealed credentials ciphertext must be hex")?;
+    let tag = hex::decode(&file.tag).context("sealed credentials tag must be hex")?;
+    if nonce.len() != 24 || tag.len() != 16 {
+        return Err(anyhow!(
+            "sealed credentials nonce or tag has the wrong length"
+        ));
+    }
+    XChaCha20Poly1305::new(key.into())
+        .decrypt_in_place_detached(
+            XNonce::from_slice(&nonce),
+            SEALED_ASSOCIATED_DATA,
+            &mut data,
+            Tag::from_slice(&tag),
+        )
+        .map_err(|_| {
+            anyhow!("sealed credentials failed authentication (wrong key or tampered file)")
+        })?;
+    let map: HashMap<String, String> =
+        serde_json::from_slice(&data).context("sealed credentials payload is malformed")?;
+    Ok(map
+        .into_iter()
+        .map(|(name, value)| (name, Secret::new(value)))
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialInfo {
+    pub name: String,
+    pub version: u32,
+    pub fingerprint: String,
+    pub rotated_at: String,
+}
+
+#[derive(Debug, Clone)]
+struct StoredCredential {
+    secret: Secret,
+    version: u32,
+    rotated_at: String,
+}
+
+struct CredentialState {
+    credentials: HashMap<String, StoredCredential>,
+    loaded_at: Instant,
+}
+
+#[derive(Clone)]
+pub struct CredentialStore {
+    source: Arc<dyn CredentialSource>,
+    refresh_interval: Option<Duration>,
+    state: Arc<RwLock<CredentialState>>,
+}
+
+impl fmt::Debug for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialStore")
+            .field("source", &self.source.describe())
+            .field("refresh_interval", &self.refresh_interval)
+            .finish()
+    }
+}
+
+impl CredentialStore {
+    pub fn new(source: Arc<dyn CredentialSource>) -> Result<Self> {
+        let now = now_iso();
+        let credentials = source
+            .load()?
+            .into_iter()
+            .map(|(name, secret)| {
+                let stored = StoredCredential {
+                    secret,
+                    version: 1,
+                    rotated_at: now.clone(),
+                };
+                (name, stored)
+            })
+            .collect();
+        Ok(Self {
+            source,
+            refresh_interval: None,
+            state: Arc::new(RwLock::new(CredentialState {
+                credentials,
+                loaded_at: Instant::now(),
+            })),
+        })
+    }
+
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = Some(interval);
+        self
+    }
+
+    pub async fn contains(&self, name: &str) -> bool {
+        self.state.read().await.credentials.contains_key(name)
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Secret> {
+        if let Some(interval) = self.refresh_interval {
+            let stale = self.state.read().await.loaded_at.elapsed() >= interval;
+            // A failed background reload keeps serving the last good credentials.
+            if stale && self.refresh().await.is_err() {
+                self.state.write().await.loaded_at = Instant::now();
+            }
+        }
+        self.state
+            .read()
+            .await
+            .credentials
+            .get(name)
+            .map(|c| c.secret.clone())
+            .ok_or_else(|| {
+                anyhow!(
+                    "credential {} is not configured in {}",
+                    name,
+                    self.source.describe()
+                )
+            })
+    }
+
+    // Reloads the source and returns the names whose value changed, was added, or was removed.
+    pub async fn refresh(&self) -> Result<Vec<String>> {
+        let loaded = self.source.load()?;
+        let now = now_iso();
+        let mut state = self.state.write().await;
+        let mut changed: Vec<String> = state
+            .credentials
+            .keys()
+            .filter(|name| !loaded.contains_key(*name))
+            .cloned()
+            .collect();
+        state
+            .credentials
+            .retain(|name, _| loaded.contains_key(name));
+        for (name, secret) in loaded {
+            match state.credentials.get_mut(&name) {
+                Some(existing) if existing.secret == secret => {}
+                Some(existing) => {
+                    existing.secret = secret;
+                    existing.version += 1;
+                    existing.rotated_at = now.clone();
+                    changed.push(name);
+                }
+                None => {
+                    state.credentials.insert(
+                        name.clone(),
+                        StoredCredential {
+                            secret,
+                            version: 1,
+                            rotated_at: now.clone(),
+                        },
+                    );
+                    changed.push(name);
+                }
+            }
+        }
+        state.loaded_at = Instant::now();
+        changed.sort();
+        Ok(changed)
+    }
+
+    pub async fn describe(&self) -> Vec<CredentialInfo> {
+        let state = self.state.read().await;
+        let mut infos: Vec<CredentialInfo> = state
+            .credentials
+            .iter()
+            .map(|(name, c)| CredentialInfo {
+                name: name.clone(),
+                version: c.version,
+                fingerprint: c.secret.fingerprint(),
+                rotated_at: c.rotated_at.clone(),
+            })
+            .collect();
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+        infos
+    }
+
+    pub async fn redact(&self, text: &str) -> String {
+        let state = self.state.read().await;
+        let mut secrets: Vec<&str> = state
+            .credentials
+            .values()
+            .map(|c| c.secret.expose())
+            .filter(|s| s.len() >= MIN_SCANNED_SECRET_LEN)
+            .collect();
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        secrets.into_iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret, REDACTED)
+        })
+    }
+
+    // Name of the first credential whose value appears anywhere in the serialized value.
+    pub async fn find_leak<T: Serialize>(&self, value: &T) -> Option<String> {
+        let text = serde_json::to_string(value).ok()?;
+        let state = self.state.read().await;
+        let mut names: Vec<&String> = state
+            .credentials
+            .iter()
+            .filter(|(_, c)| {
+                c.secret.expose().len() >= MIN_SCANNED_SECRET_LEN
+                    && text.contains(c.secret.expose())
+            })
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        names.first().map(|name| name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_credentials_round_trip_and_reject_tampering() {
+        let key = [7u8; 32];
+        let credentials = HashMap::from([(
+            "hyperliquid_api_key".to_string(),
+            "hl-secret-123".to_string(),
+        )]);
+        let sealed = seal_credentials(&key, &credentials).expect("seal");
+        assert!(!sealed.contains("hl-secret-123"));
+        let unsealed = unseal_credentials(&key, &sealed).expect("unseal");
+        assert_eq!(unsealed["hyperliquid_api_key"].expose(), "hl-secret-123");
+        assert_eq!(
+            format!("{:?}", unsealed["hyperliquid_api_key"]),
+            "Secret(<redacted>)"
+        );
+
+        assert!(unseal_credentials(&[8u8; 32], &sealed).is_err());
+        let mut file: SealedFile = serde_json::from_str(&sealed).unwrap();
+        file.ciphertext.replace_range(
+            0..2,
+            if file.ciphertext.starts_with("00") {
+                "01"
+            } else {
+                "00"
+            },
+        );
+        assert!(unseal_credentials(&key, &serde_json::to_string(&file).unwrap()).is_err());
+
+        // Files in the retired BLAKE3 format are refused rather than read.
+        let mut file: SealedFile = serde_json::from_str(&sealed).unwrap();
+        assert_eq!(hex::decode(&file.nonce).unwrap().len(), 24);
+        file.version = 1;
+        let err = unseal_credentials(&key, &serde_json::to_string(&file).unwrap())
+            .expect_err("v1 is retired");
+        assert!(err.to_string().contains("re-seal"), "{}", err);
+    }
+}
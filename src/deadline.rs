@@ -0,0 +1,52 @@
+use crate::models::{DeadlineMiss, StageSpend};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+// Tracks one request's deadline across pipeline stages. Each stage runs with whatever budget the
+// earlier ones left; without a deadline, stages run unbounded and nothing is recorded.
+#[derive(Debug, Clone)]
+pub struct DeadlineBudget {
+    started: Instant,
+    budget: Option<Duration>,
+    spent: Vec<StageSpend>,
+}
+
+impl DeadlineBudget {
+    pub fn new(deadline_ms: Option<u64>) -> Self {
+        Self {
+            started: Instant::now(),
+            budget: deadline_ms.map(Duration::from_millis),
+            spent: vec![],
+        }
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        self.budget
+            .map(|budget| budget.saturating_sub(self.started.elapsed()))
+    }
+
+    pub async fn run<F: Future>(
+        &mut self,
+        stage: &str,
+        work: F,
+    ) -> Result<F::Output, DeadlineMiss> {
+        let Some(remaining) = self.remaining() else {
+            return Ok(work.await);
+        };
+        let started = Instant::now();
+        let outcome = if remaining.is_zero() {
+            None
+        } else {
+            tokio::time::timeout(remaining, work).await.ok()
+        };
+        self.spent.push(StageSpend {
+            stage: stage.to_string(),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        });
+        outcome.ok_or_else(|| DeadlineMiss {
+            budget_ms: self.budget.map_or(0, |b| b.as_millis() as u64),
+            stage: stage.to_string(),
+            spent: self.spent.clone(),
+        })
+    }
+}
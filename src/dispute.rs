@@ -0,0 +1,156 @@
+use crate::models::{hash_bytes, hash_json, now_iso, ProofRequest, ZKReceipt};
+use crate::screening::ScreeningDecision;
+use crate::signing::KeyManifest;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const DISPUTE_BUNDLE_VERSION: &str = "1";
+pub const DEFAULT_DISPUTE_INLINE_LIMIT: usize = 256 * 1024;
+const DISPUTE_MEDIA_TYPE: &str = "application/vnd.zkputer.dispute-bundle+json";
+
+// Everything a counterparty needs to check a receipt without access to this deployment: the
+// receipt with its evidence, the request it answered, screening records, the receipts it
+// supersedes or is superseded by, and the signer keys. Operator cost records are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisputeBundle {
+    pub version: String,
+    pub generated_at: String,
+    pub receipt: ZKReceipt,
+    pub request: Option<ProofRequest>,
+    pub lineage: Vec<ZKReceipt>,
+    pub screening: Vec<ScreeningDecision>,
+    pub key_manifest: KeyManifest,
+    pub integrity_valid: bool,
+    pub proof_valid: bool,
+    pub bundle_hash: String,
+}
+
+impl DisputeBundle {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        receipt: ZKReceipt,
+        request: Option<ProofRequest>,
+        lineage: Vec<ZKReceipt>,
+        screening: Vec<ScreeningDecision>,
+        key_manifest: KeyManifest,
+        integrity_valid: bool,
+        proof_valid: bool,
+    ) -> Self {
+        let mut bundle = Self {
+            version: DISPUTE_BUNDLE_VERSION.to_string(),
+            generated_at: now_iso(),
+            receipt,
+            request,
+            lineage,
+            screening,
+            key_manifest,
+            integrity_valid,
+            proof_valid,
+            bundle_hash: String::new(),
+        };
+        bundle.bundle_hash = bundle.compute_hash();
+        bundle
+    }
+
+    pub fn compute_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "version": self.version,
+            "generated_at": self.generated_at,
+            "receipt": self.receipt,
+            "request": self.request,
+            "lineage": self.lineage,
+            "screening": self.screening,
+            "key_manifest": self.key_manifest,
+            "integrity_valid": self.integrity_valid,
+            "proof_valid": self.proof_valid,
+        }))
+    }
+}
+
+// Small bundles travel inline as base64; larger ones are written under `dir` and referenced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum DisputeExport {
+    Inline {
+        media_type: String,
+        size_bytes: usize,
+        sha256: String,
+        base64: String,
+    },
+    Reference {
+        media_type: String,
+        size_bytes: usize,
+        sha256: String,
+        uri: String,
+    },
+}
+
+pub fn export_dispute_bundle(
+    bundle: &DisputeBundle,
+    max_inline_bytes: usize,
+    dir: &Path,
+) -> Result<DisputeExport> {
+    let bytes = serde_json::to_vec_pretty(bundle)?;
+    let sha256 = hash_bytes(&bytes);
+    if bytes.len() <= max_inline_bytes {
+        return Ok(DisputeExport::Inline {
+            media_type: DISPUTE_MEDIA_TYPE.to_string(),
+            size_bytes: bytes.len(),
+            sha256,
+            base64: base64_encode(&bytes),
+        });
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path: PathBuf = dir.join(format!(
+        "dispute-{}-{}.json",
+        bundle.receipt.receipt_id,
+        &sha256[..16]
+    ));
+    std::fs::write(&path, &bytes).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(DisputeExport::Reference {
+        media_type: DISPUTE_MEDIA_TYPE.to_string(),
+        size_bytes: bytes.len(),
+        sha256,
+        uri: format!("file://{}", path.display()),
+    })
+}
+
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_encode(input.as_bytes()), expected);
+        }
+    }
+}
@@ -1,28 +1,115 @@
-use crate::adapters::VenueAdapter;
+use crate::adapter_cache::{AdapterCache, AdapterCacheStats};
+use crate::adapters::{AdapterCapabilities, FinalitySemantics, VenueAdapter};
+use crate::anchoring::{
+    merkle_tree, publish_with_fee_policy, receipt_leaf, verify_inclusion, AnchorBatch,
+    AnchorFeePolicy, AnchorPublisher,
+};
+use crate::claims::{ClaimHandler, ClaimHandlers};
+use crate::clock::timestamp_conflicts;
+use crate::commitments::{CommitmentMode, CommitmentScheme};
+use crate::credentials::CredentialStore;
+use crate::deadline::DeadlineBudget;
+use crate::dispute::DisputeBundle;
+use crate::escrow::{escrow_for, published, release};
+use crate::events::ReceiptEvent;
+use crate::export::{export_receipts, ExportItem, ReceiptExport};
+use crate::federation::{ForeignVerification, InstanceDescriptor, TrustStore};
+use crate::finality::{FinalityObserver, FinalityRule};
+use crate::hashing::HashAlgorithm;
+use crate::health::{
+    AdapterHealth, CircuitBreakers, CircuitState, HealthReport, HealthStatus, ProverHealth,
+    QueueDepths, StoreHealth,
+};
+use crate::issuance::{IssuanceEntry, IssuanceLedger, Watermark};
+use crate::leader::{now_ms, EngineRole, LeaderElection, WriteFenced};
 use crate::models::{
-    hash_json, new_receipt_id, now_iso, ClaimType, Integrity, NonProvable, NonProvableReason, PolicyContext,
-    ProofMetadata, ProofRequest, Provenance, ReceiptStatus, Subject, Timing, TruthClaim, Venue, ZKReceipt,
+    new_receipt_id, now_iso, AnchorInclusion, ChainCommitment, ClaimType, CorrectionOutcome,
+    CostRecord, DeadlineMiss, EvidenceBundle, EvidenceItem, EvidenceRefresh, ExecutionAck,
+    Integrity, Invalidation, LocalizedStatement, NonProvable, NonProvableReason, PolicyContext,
+    PriorAttempt, ProofMetadata, ProofRequest, Provenance, PublicVerification,
+    PublicVerificationQuery, RawArtifact, ReceiptStatus, RefreshAction, SignerIdentity,
+    StageBreakdown, StatementLocale, Subject, Timing, TradeCorrection, TruthClaim, Venue,
+    VerificationMode, ZKReceipt, HASH_DOMAIN_VERSION,
+};
+use crate::oracles::{
+    check_quote, cross_check, quote_evidence, venue_reported_price, OracleTolerance, PriceOracle,
+    ORACLE_PRICE_TAG, ORACLE_SOURCE_KIND,
+};
+use crate::payload_encryption::{open_payload, PayloadDecryptionKey, PayloadEncryptionKey};
+use crate::policy::{PolicyEngine, PolicyWatch};
+use crate::presentation::{present_receipt, PresentationChallenge, ReceiptPresentation};
+use crate::proof_queue::{ProofQueue, ProofQueueStats};
+use crate::prover::{
+    attestation_metadata, no_proof_metadata, ProofWitness, ProverBackend, ProverChain, ProverUsage,
+};
+use crate::redaction::CollectionRedaction;
+use crate::report::{build_period_report, PeriodReport};
+use crate::retention::{EvidenceVault, RetentionEvent};
+use crate::screening::{
+    evidence_addresses, screen_subjects, ScreeningDecision, ScreeningOutcome, ScreeningProvider,
+    ScreeningStage,
+};
+use crate::signing::{
+    receipt_signing_message, HashBindingSigner, KeyManifest, KeyRing, ReceiptSigner,
+    ED25519_ALGORITHM,
 };
-use crate::policy::PolicyEngine;
-use crate::prover::{no_proof_metadata, ProverBackend};
-use crate::verifier::OffchainVerifier;
+use crate::stats::{build_stats, ReceiptStats};
+use crate::store::{ExportCursor, ReceiptQuery, ReceiptStore, ReceiptWatch};
+use crate::templates::render_localized_statements;
+use crate::validation::{
+    check_evidence_size, check_payload_size, validate_evidence, validate_payload, validate_request,
+    EvidenceLimits,
+};
+use crate::verifier::{OffchainVerifier, ReceiptVerification, SignerKeySource};
+use crate::versioning::ReceiptVersionSupport;
+use crate::webhooks::{sign_webhook_delivery, SignedWebhookDelivery, WebhookEvent};
 use anyhow::{anyhow, Result};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
 pub struct ReceiptEngine {
     adapters: HashMap<Venue, Arc<dyn VenueAdapter>>,
-    policy_engine: PolicyEngine,
+    policy: watch::Receiver<PolicyEngine>,
+    policy_history: Arc<std::sync::Mutex<Vec<PolicyContext>>>,
     prover: Arc<dyn ProverBackend>,
     verifier: OffchainVerifier,
-    signer: String,
-    receipt_version: String,
-    store: Arc<Mutex<HashMap<String, ZKReceipt>>>,
-    tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    keyring: KeyRing,
+    receipt_signers: Vec<Arc<dyn ReceiptSigner>>,
+    versions: ReceiptVersionSupport,
+    commitments: CommitmentScheme,
+    store: ReceiptStore,
+    finality: Option<FinalityWait>,
+    finality_recheck: Option<Duration>,
+    circuits: CircuitBreakers,
+    anchor_fees: AnchorFeePolicy,
+    anchor_publisher: Option<Arc<dyn AnchorPublisher>>,
+    statement_locales: Vec<StatementLocale>,
+    credentials: Option<CredentialStore>,
+    screening: Option<Arc<dyn ScreeningProvider>>,
+    issuance: IssuanceLedger,
+    collection_redaction: CollectionRedaction,
+    evidence_limits: EvidenceLimits,
+    trust_store: TrustStore,
+    oracles: Vec<Arc<dyn PriceOracle>>,
+    proof_queue: ProofQueue,
+    adapter_cache: Option<AdapterCache>,
+    evidence_vault: Option<EvidenceVault>,
+    claim_handlers: ClaimHandlers,
+    order_execution: bool,
+    payload_key: Option<PayloadDecryptionKey>,
+    leadership: Option<LeaderElection>,
+    in_flight: Arc<std::sync::Mutex<HashMap<String, JoinHandle<()>>>>,
+    corrections_error: Arc<std::sync::Mutex<Option<String>>>,
 }
 
+const STORE_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+const SUBMIT_ORDER_STAGE: &str = "submit_order";
+// A settling write that keeps conflicting with writes to a receipt still in flight is given up on.
+const COMMIT_ATTEMPTS: usize = 3;
+
 impl ReceiptEngine {
     pub fn new(
         adapters: Vec<Arc<dyn VenueAdapter>>,
@@ -33,95 +120,1932 @@ impl ReceiptEngine {
         let map = adapters.into_iter().map(|a| (a.venue(), a)).collect();
         Self {
             adapters: map,
-            policy_engine,
+            policy: watch::channel(policy_engine).1,
+            policy_history: Arc::default(),
             prover,
             verifier,
-            signer: "zkputer-dev-signer".to_string(),
-            receipt_version: "v0.1.0".to_string(),
-            store: Arc::new(Mutex::new(HashMap::new())),
-            tasks: Arc::new(Mutex::new(HashMap::new())),
+            keyring: KeyRing::default(),
+            receipt_signers: vec![],
+            versions: ReceiptVersionSupport::default(),
+            commitments: CommitmentScheme::default(),
+            store: ReceiptStore::new(),
+            finality: None,
+            finality_recheck: None,
+            circuits: CircuitBreakers::default(),
+            anchor_fees: AnchorFeePolicy::default(),
+            anchor_publisher: None,
+            statement_locales: vec![],
+            credentials: None,
+            screening: None,
+            issuance: IssuanceLedger::new(),
+            collection_redaction: CollectionRedaction::default(),
+            evidence_limits: EvidenceLimits::default(),
+            trust_store: TrustStore::new(),
+            oracles: vec![],
+            proof_queue: ProofQueue::default(),
+            adapter_cache: None,
+            evidence_vault: None,
+            claim_handlers: ClaimHandlers::default(),
+            order_execution: false,
+            payload_key: None,
+            leadership: None,
+            in_flight: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            corrections_error: Arc::default(),
+        }
+    }
+
+    // Proves with each backend in order until one succeeds, before a receipt is marked PROOF_FAILURE.
+    pub fn with_provers(
+        mut self,
+        provers: Vec<Arc<dyn ProverBackend>>,
+        attempt_timeout: Option<Duration>,
+    ) -> Result<Self> {
+        let mut chain = ProverChain::new(provers)?;
+        if let Some(timeout) = attempt_timeout {
+            chain = chain.with_attempt_timeout(timeout);
+        }
+        self.prover = Arc::new(chain);
+        Ok(self)
+    }
+
+    // At most `max_concurrent` proofs run at once; receipts past that wait for a slot.
+    pub fn with_max_concurrent_proofs(mut self, max_concurrent: usize) -> Self {
+        self.proof_queue = ProofQueue::new(max_concurrent);
+        self
+    }
+
+    pub fn with_proof_queue(mut self, queue: ProofQueue) -> Self {
+        self.proof_queue = queue;
+        self
+    }
+
+    pub fn proof_queue_stats(&self) -> ProofQueueStats {
+        self.proof_queue.stats()
+    }
+
+    // Reuses acknowledge/collect results for the same order within `ttl`; refresh_evidence always
+    // goes to the venue and replaces what was cached.
+    pub fn with_adapter_cache(mut self, ttl: Duration) -> Self {
+        self.adapter_cache = Some(AdapterCache::new(ttl));
+        self
+    }
+
+    pub fn adapter_cache_stats(&self) -> Option<AdapterCacheStats> {
+        self.adapter_cache.as_ref().map(AdapterCache::stats)
+    }
+
+    pub fn invalidate_adapter_cache(&self, venue: Venue, order_ref: &str) -> bool {
+        self.adapter_cache
+            .as_ref()
+            .is_some_and(|cache| cache.invalidate(venue, order_ref))
+    }
+
+    // Keeps raw evidence bodies for `ttl`, usually far shorter than receipts are kept. Receipts
+    // commit only to artifact hashes, so they verify the same after the bodies are deleted.
+    pub fn with_evidence_retention(mut self, ttl: Duration) -> Self {
+        self.evidence_vault = Some(EvidenceVault::new(ttl));
+        self
+    }
+
+    pub fn evidence_artifact(&self, receipt_id: &str, artifact_hash: &str) -> Option<Vec<u8>> {
+        self.evidence_vault
+            .as_ref()
+            .and_then(|vault| vault.get(receipt_id, artifact_hash))
+    }
+
+    // Deletes every artifact past its retention period; returns how many were deleted.
+    pub fn sweep_evidence(&self) -> usize {
+        self.evidence_vault.as_ref().map_or(0, EvidenceVault::sweep)
+    }
+
+    pub fn erase_evidence(&self, receipt_id: &str, reason: &str) -> Result<usize> {
+        if reason.trim().is_empty() {
+            return Err(anyhow!("an erasure needs a reason"));
+        }
+        Ok(self
+            .evidence_vault
+            .as_ref()
+            .map_or(0, |vault| vault.erase(receipt_id, reason)))
+    }
+
+    pub fn evidence_retention_log(&self) -> Vec<RetentionEvent> {
+        self.evidence_vault
+            .as_ref()
+            .map(EvidenceVault::log)
+            .unwrap_or_default()
+    }
+
+    pub fn with_claim_handler(mut self, handler: Arc<dyn ClaimHandler>) -> Self {
+        self.claim_handlers.register(handler);
+        self
+    }
+
+    pub fn claim_handlers(&self) -> &ClaimHandlers {
+        &self.claim_handlers
+    }
+
+    // Allows `execute_and_prove`. Off by default: an engine that can place orders is an execution
+    // gateway, not only an observer.
+    pub fn with_order_execution(mut self, enabled: bool) -> Self {
+        self.order_execution = enabled;
+        self
+    }
+
+    // Opens payload fields clients sealed to this key's public half (see `payload_encryption`). The
+    // plaintext exists only inside the pipeline: stored requests keep the sealed fields, and the
+    // claim commits to each field's plaintext hash.
+    pub fn with_payload_decryption_key(mut self, key: PayloadDecryptionKey) -> Self {
+        self.payload_key = Some(key);
+        self
+    }
+
+    pub fn payload_encryption_key(&self) -> Option<PayloadEncryptionKey> {
+        self.payload_key
+            .as_ref()
+            .map(PayloadDecryptionKey::public_key)
+    }
+
+    // Follows a watched policy: each request is checked and proved under the revision in force when
+    // its pipeline starts, and the receipt records that revision's policy id.
+    pub fn with_policy_watch(mut self, watch: &PolicyWatch) -> Self {
+        self.policy = watch.subscribe();
+        self
+    }
+
+    pub fn with_price_oracles(mut self, oracles: Vec<Arc<dyn PriceOracle>>) -> Self {
+        self.oracles = oracles;
+        self
+    }
+
+    pub fn with_trust_store(mut self, trust_store: TrustStore) -> Self {
+        self.trust_store = trust_store;
+        self
+    }
+
+    pub fn with_evidence_limits(mut self, limits: EvidenceLimits) -> Self {
+        self.evidence_limits = limits;
+        self
+    }
+
+    pub fn with_collection_redaction(mut self, redaction: CollectionRedaction) -> Self {
+        self.collection_redaction = redaction;
+        self
+    }
+
+    pub fn with_screening_provider(mut self, provider: Arc<dyn ScreeningProvider>) -> Self {
+        self.screening = Some(provider);
+        self
+    }
+
+    // Adapter errors are scrubbed of these values before they reach a receipt, and evidence that
+    // contains one is rejected rather than persisted.
+    pub fn with_credentials(mut self, credentials: CredentialStore) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    pub fn with_statement_locales(mut self, locales: Vec<StatementLocale>) -> Self {
+        let mut unique = Vec::new();
+        for locale in locales {
+            if !unique.contains(&locale) {
+                unique.push(locale);
+            }
+        }
+        self.statement_locales = unique;
+        self
+    }
+
+    pub fn with_signer(mut self, signer: SignerIdentity) -> Self {
+        self.keyring = KeyRing::single(signer);
+        self
+    }
+
+    pub fn with_keyring(mut self, keyring: KeyRing) -> Self {
+        self.keyring = keyring;
+        self
+    }
+
+    // Supplies the private key for a key ring entry; a signer the key ring does not list becomes
+    // its only key.
+    pub fn with_receipt_signer(mut self, signer: Arc<dyn ReceiptSigner>) -> Self {
+        if !self
+            .keyring
+            .keys()
+            .iter()
+            .any(|k| k.identity == *signer.identity())
+        {
+            self.keyring = KeyRing::single(signer.identity().clone());
+        }
+        self.receipt_signers
+            .retain(|s| s.identity().key_id != signer.identity().key_id);
+        self.receipt_signers.push(signer);
+        self
+    }
+
+    pub fn with_receipt_version(self, version: &str) -> Result<Self> {
+        self.versions.check_produces(version)?;
+        Ok(self)
+    }
+
+    pub fn receipt_versions(&self) -> &ReceiptVersionSupport {
+        &self.versions
+    }
+
+    pub fn with_hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.commitments.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    pub fn with_commitment_mode(mut self, mode: CommitmentMode) -> Self {
+        self.commitments.mode = mode;
+        self
+    }
+
+    // Disabling domain separation reproduces the untagged encoding of receipts issued before
+    // the tags were introduced.
+    pub fn with_domain_separation(mut self, enabled: bool) -> Self {
+        self.commitments.domain_version = if enabled { HASH_DOMAIN_VERSION } else { 0 };
+        self
+    }
+
+    pub fn with_finality_observer(mut self, observer: FinalityObserver, timeout: Duration) -> Self {
+        self.finality = Some(FinalityWait { observer, timeout });
+        self
+    }
+
+    // A claim the venue's finality rule gates, collected before the venue reports it final, has
+    // the adapter re-checked every `interval` until the finality deadline: the tighter of the
+    // rule's timeout and the request's `finality_timeout_ms`. Without this it fails at once.
+    pub fn with_finality_rechecks(mut self, interval: Duration) -> Self {
+        self.finality_recheck = Some(interval);
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.circuits = CircuitBreakers::new(failure_threshold, cooldown);
+        self
+    }
+
+    // Requests asking for an anchored verification mode are anchored through this publisher, one
+    // receipt per batch, before they are issued.
+    pub fn with_anchor_publisher(mut self, publisher: Arc<dyn AnchorPublisher>) -> Self {
+        self.anchor_publisher = Some(publisher);
+        self
+    }
+
+    pub fn with_anchor_fee_policy(mut self, policy: AnchorFeePolicy) -> Self {
+        self.anchor_fees = policy;
+        self
+    }
+
+    // Issued sequences continue from the receipts the store already holds.
+    pub fn with_store(mut self, store: ReceiptStore) -> Self {
+        self.restore_issuance(store.snapshot_now());
+        self.store = match &self.leadership {
+            Some(election) => store.with_fence(election.clone()),
+            None => store,
+        };
+        self
+    }
+
+    fn restore_issuance(&self, receipts: Vec<ZKReceipt>) {
+        for receipt in receipts {
+            let integrity = &receipt.integrity;
+            if integrity.sequence == 0 {
+                continue;
+            }
+            self.issuance.restore(
+                &integrity.signer,
+                IssuanceEntry {
+                    key_id: integrity.signer.key_id.clone(),
+                    sequence: integrity.sequence,
+                    receipt_id: receipt.receipt_id.clone(),
+                    receipt_hash: integrity.receipt_hash.clone(),
+                    status: receipt.status,
+                    issued_at: integrity
+                        .issued_at
+                        .clone()
+                        .unwrap_or_else(|| receipt.timing.created_at.clone()),
+                },
+            );
+        }
+    }
+
+    // Engines sharing a persistent store elect one leader through a lease held in it. The leader
+    // accepts submissions and runs receipt pipelines; standbys serve reads, refreshed from the store,
+    // and turn away writes until they win the lease. Until `campaign` first wins it, this engine is a
+    // standby. Receipt writes through the engine's store are fenced on the tenure it won.
+    pub fn with_leader_election(mut self, election: LeaderElection) -> Self {
+        self.store = self.store.with_fence(election.clone());
+        self.leadership = Some(election);
+        self
+    }
+
+    pub fn leader_election(&self) -> Option<&LeaderElection> {
+        self.leadership.as_ref()
+    }
+
+    // Engines without leader election always lead.
+    pub fn role(&self) -> EngineRole {
+        self.leadership
+            .as_ref()
+            .map_or(EngineRole::Leader, LeaderElection::role)
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.role() == EngineRole::Leader
+    }
+
+    // The instance holding the lease when this engine last looked, if it has not lapsed since.
+    pub fn current_leader(&self) -> Option<String> {
+        let lease = self.leadership.as_ref()?.lease()?;
+        (lease.expires_at_ms > now_ms()).then_some(lease.holder)
+    }
+
+    fn ensure_leader(&self) -> Result<()> {
+        let Some(election) = self
+            .leadership
+            .as_ref()
+            .filter(|election| !election.is_leader())
+        else {
+            return Ok(());
+        };
+        Err(anyhow!(
+            "instance {} is a standby; {}",
+            election.holder(),
+            match self.current_leader() {
+                Some(leader) => format!("send writes to the leader, {}", leader),
+                None => "no leader holds the lease yet".to_string(),
+            }
+        ))
+    }
+
+    // Takes or renews the lease and acts on the outcome. An engine that just became leader
+    // refreshes the store and resumes the PENDING and AWAITING_FINALITY receipts no task of its own
+    // is running, i.e. those a lapsed leader left behind; one that lost the lease stops its tasks,
+    // which also stop themselves before proving. Standbys refresh the store on every call. A
+    // campaign that fails steps down, since this engine can no longer tell whether it still holds
+    // the lease, and the error stays on the election for health to report until one succeeds.
+    pub async fn campaign(&self) -> Result<EngineRole> {
+        let Some(election) = &self.leadership else {
+            return Ok(EngineRole::Leader);
+        };
+        let outcome = self.run_campaign(election).await;
+        if outcome.is_err() {
+            self.abort_in_flight();
+            election.observe(None);
         }
+        election.record_outcome(outcome.as_ref().err());
+        outcome
+    }
+
+    async fn run_campaign(&self, election: &LeaderElection) -> Result<EngineRole> {
+        let was_leader = election.is_leader();
+        let lease =
+            self.store
+                .acquire_lease(election.lease_name(), election.holder(), election.ttl())?;
+        election.observe(Some(lease));
+        match election.role() {
+            EngineRole::Leader if !was_leader => self.take_over().await?,
+            EngineRole::Leader => {}
+            EngineRole::Standby => {
+                if was_leader {
+                    self.abort_in_flight();
+                }
+                self.store.refresh().await?;
+            }
+        }
+        Ok(election.role())
+    }
+
+    // Campaigns every `renew_interval` until the task is aborted. None without leader election.
+    pub fn run_leader_election(self: Arc<Self>) -> Option<JoinHandle<()>> {
+        let interval = self.leadership.as_ref()?.renew_interval();
+        Some(tokio::spawn(async move {
+            loop {
+                // A failed campaign has already stepped down and is reported by health.
+                self.campaign().await.ok();
+                tokio::time::sleep(interval).await;
+            }
+        }))
+    }
+
+    // Gives up the lease at once, e.g. on shutdown, so a standby need not wait for it to lapse.
+    pub async fn step_down(&self) -> Result<()> {
+        let Some(election) = &self.leadership else {
+            return Ok(());
+        };
+        self.abort_in_flight();
+        election.observe(None);
+        self.store
+            .release_lease(election.lease_name(), election.holder())
+    }
+
+    async fn take_over(&self) -> Result<()> {
+        self.store.refresh().await?;
+        let receipts = self.store.list(&ReceiptQuery::default()).await?;
+        self.restore_issuance(receipts.clone());
+        for receipt in receipts {
+            if !matches!(
+                receipt.status,
+                ReceiptStatus::PENDING | ReceiptStatus::AWAITING_FINALITY
+            ) || self
+                .in_flight
+                .lock()
+                .expect("in-flight task lock poisoned")
+                .contains_key(&receipt.receipt_id)
+            {
+                continue;
+            }
+            let Some(request) = self.store.request(&receipt.receipt_id).await else {
+                continue;
+            };
+            let Some(adapter) = self.adapters.get(&request.venue).cloned() else {
+                continue;
+            };
+            // The old leader may have placed the order already; resuming only observes it.
+            let integrity = self.integrity_context(&now_iso())?;
+            self.spawn_receipt_task(adapter, integrity, receipt.receipt_id, request, false);
+        }
+        Ok(())
+    }
+
+    fn abort_in_flight(&self) {
+        for (_, task) in self
+            .in_flight
+            .lock()
+            .expect("in-flight task lock poisoned")
+            .drain()
+        {
+            task.abort();
+        }
+    }
+
+    pub fn with_search_index(mut self) -> Self {
+        self.store = self.store.with_search_index();
+        self
+    }
+
+    pub fn adapter_capabilities(&self) -> HashMap<Venue, AdapterCapabilities> {
+        self.adapters
+            .iter()
+            .map(|(venue, adapter)| (*venue, adapter.capabilities()))
+            .collect()
+    }
+
+    pub fn payload_schemas(&self) -> serde_json::Value {
+        self.policy_engine().payload_schemas()
+    }
+
+    pub fn keyring(&self) -> &KeyRing {
+        &self.keyring
+    }
+
+    pub fn key_manifest(&self) -> KeyManifest {
+        self.keyring.manifest()
+    }
+
+    // Published for other deployments to add to their trust stores. The instance is named after
+    // its current signer.
+    pub fn instance_descriptor(&self) -> InstanceDescriptor {
+        let keys = self.keyring.keys().to_vec();
+        let instance_id = self
+            .keyring
+            .current_signer()
+            .unwrap_or(&keys[0].identity)
+            .name
+            .clone();
+        // Lists the policies in force, tenant overlays included, even before any receipt was issued
+        // under them.
+        for tenant in self.policy_engine().tenants() {
+            self.tenant_policy(Some(&tenant));
+        }
+        let policies = self
+            .policy_history
+            .lock()
+            .expect("policy history lock poisoned")
+            .clone();
+        InstanceDescriptor {
+            instance_id,
+            published_at: now_iso(),
+            keys,
+            policies,
+            payload_encryption_key: self.payload_encryption_key().map(|key| key.to_hex()),
+        }
+    }
+
+    pub async fn verify_foreign_receipt(&self, receipt: &ZKReceipt) -> Result<ForeignVerification> {
+        self.versions.check_readable(receipt)?;
+        self.trust_store
+            .verify_foreign_receipt(&self.verifier, receipt)
+            .await
+    }
+
+    // Checks any receipt document without needing it in the store. The signature is checked
+    // against `public_key` when given, else the signer's key in this deployment's key ring or a
+    // trusted instance's descriptor (which also bound the key's validity window), else the key
+    // the receipt itself carries.
+    pub async fn verify_receipt(
+        &self,
+        receipt: &ZKReceipt,
+        public_key: Option<&str>,
+    ) -> Result<ReceiptVerification> {
+        self.versions.check_readable(receipt)?;
+        let signer = &receipt.integrity.signer;
+        let integrity = receipt.verify_integrity(public_key);
+        let known = |keys: &KeyRing| keys.keys().iter().any(|k| k.identity == *signer);
+        let (key_source, signature_valid) = match public_key {
+            Some(_) => (SignerKeySource::Supplied, integrity.signature_valid),
+            None if known(&self.keyring) => (
+                SignerKeySource::LocalKeyRing,
+                self.keyring.verify_receipt_signature(receipt),
+            ),
+            None => match self.trust_store.issuer_of(signer) {
+                Some(issuer) => (
+                    SignerKeySource::TrustedInstance {
+                        instance_id: issuer.instance_id.clone(),
+                    },
+                    KeyRing::new(issuer.keys.clone())?.verify_receipt_signature(receipt),
+                ),
+                None => (SignerKeySource::Embedded, integrity.signature_valid),
+            },
+        };
+        let proof_valid = match receipt.status {
+            ReceiptStatus::PROVED => Some(
+                self.verifier
+                    .verify_with_handlers(receipt, &self.claim_handlers)
+                    .await,
+            ),
+            _ => None,
+        };
+        Ok(ReceiptVerification {
+            receipt_id: receipt.receipt_id.clone(),
+            status: receipt.status,
+            schema_hash_valid: integrity.schema_hash_valid,
+            receipt_hash_valid: integrity.receipt_hash_valid,
+            signature_valid,
+            proof_valid,
+            signer_key_id: signer.key_id.clone(),
+            signer_trusted: key_source.is_trusted(),
+            key_source,
+            valid: integrity.schema_hash_valid
+                && integrity.receipt_hash_valid
+                && signature_valid
+                && proof_valid != Some(false),
+            checked_at: now_iso(),
+        })
+    }
+
+    // The policy in force now. Every revision handed out is remembered, so the instance descriptor
+    // keeps vouching for receipts issued under earlier ones.
+    fn policy_engine(&self) -> PolicyEngine {
+        self.remember_policy(self.policy.borrow().clone())
+    }
+
+    // The policy in force now for a tenant's requests, remembered the same way.
+    fn tenant_policy(&self, tenant: Option<&str>) -> PolicyEngine {
+        self.remember_policy(self.policy_engine().for_tenant(tenant))
+    }
+
+    fn remember_policy(&self, policy: PolicyEngine) -> PolicyEngine {
+        let context = policy.context();
+        let mut history = self
+            .policy_history
+            .lock()
+            .expect("policy history lock poisoned");
+        if !history.contains(&context) {
+            history.push(context);
+        }
+        policy
+    }
+
+    pub fn sign_webhook_delivery(
+        &self,
+        event: &str,
+        receipt: ZKReceipt,
+    ) -> Result<SignedWebhookDelivery> {
+        sign_webhook_delivery(
+            self.current_signer(&now_iso())?.as_ref(),
+            &WebhookEvent {
+                event: event.to_string(),
+                receipt: published(receipt),
+            },
+        )
     }
 
     pub async fn submit(&self, request: ProofRequest) -> Result<String> {
-        let receipt = self.new_pending_receipt(&request);
+        self.submit_with_lineage(request, None, false).await
+    }
+
+    // Places the order through the venue's adapter and proves ORDER_PLACED from the venue's answer
+    // to that submission, or ORDER_REJECTED with the venue's reason when it refuses the order. Needs `with_order_execution(true)` and an adapter that advertises
+    // `supports_execution`; otherwise nothing is sent and no receipt is created. A request that
+    // fails validation, screening or policy is rejected before the order is sent.
+    pub async fn execute_and_prove(&self, request: ProofRequest) -> Result<String> {
+        if !self.order_execution {
+            return Err(anyhow!("order execution is not enabled on this engine"));
+        }
+        if request.claim_type != ClaimType::ORDER_PLACED {
+            return Err(anyhow!(
+                "only ORDER_PLACED requests can be executed, not {}",
+                request.claim_type.name()
+            ));
+        }
+        if !self
+            .adapters
+            .get(&request.venue)
+            .is_some_and(|a| a.capabilities().supports_execution)
+        {
+            return Err(anyhow!(
+                "adapter for venue {:?} does not submit orders",
+                request.venue
+            ));
+        }
+        self.submit_with_lineage(request, None, true).await
+    }
+
+    async fn submit_with_lineage(
+        &self,
+        request: ProofRequest,
+        supersedes: Option<String>,
+        submit_order: bool,
+    ) -> Result<String> {
+        self.ensure_leader()?;
+        let now = now_iso();
+        let integrity = self.integrity_context(&now)?;
+        let mut receipt = self.new_pending_receipt(&request, &integrity, now, supersedes);
+        receipt.provenance.submitted_by_engine = submit_order;
         let receipt_id = receipt.receipt_id.clone();
-        self.store.lock().await.insert(receipt_id.clone(), receipt);
-
-        let store = Arc::clone(&self.store);
-        let tasks = Arc::clone(&self.tasks);
-        let adapter = self.adapters.get(&request.venue).cloned();
-        let policy_engine = self.policy_engine.clone();
-        let prover = Arc::clone(&self.prover);
-        let verifier = self.verifier.clone();
-        let signer = self.signer.clone();
-        let receipt_version = self.receipt_version.clone();
-        let receipt_id_for_task = receipt_id.clone();
-        let receipt_id_for_cleanup = receipt_id.clone();
 
-        let handle = tokio::spawn(async move {
-            process_receipt_task(
-                store,
-                adapter,
-                policy_engine,
-                prover,
-                verifier,
-                signer,
-                receipt_version,
-                receipt_id_for_task,
-                request,
+        // Checks run on the opened payload; only the sealed request is stored and handed on.
+        let sealed = request;
+        let request = match open_payload(&sealed, self.payload_key.as_ref()) {
+            Ok((request, _)) => request,
+            Err(err) => {
+                let rejected = NonProvable::new(
+                    NonProvableReason::SCHEMA_INVALID,
+                    format!("Request failed validation: {:#}", err),
+                );
+                persist_new(
+                    &self.store,
+                    mark_non_provable(receipt, rejected, &integrity),
+                )
+                .await?;
+                return Ok(receipt_id);
+            }
+        };
+        let policy = self.tenant_policy(request.tenant_id.as_deref());
+        let mut issues = validate_request(&request);
+        issues.extend(check_payload_size(&request.payload, &self.evidence_limits));
+        if let Some(schema) = policy.payload_schema(request.claim_type) {
+            issues.extend(validate_payload(&request.payload, &schema));
+        }
+        if !issues.is_empty() {
+            let rejected = NonProvable::new(
+                NonProvableReason::SCHEMA_INVALID,
+                format!("Request failed validation: {}", issues.join("; ")),
+            );
+            persist_new(
+                &self.store,
+                mark_non_provable(receipt, rejected, &integrity),
+            )
+            .await?;
+            return Ok(receipt_id);
+        }
+        // Skipping the proof is the policy's call, not the requester's.
+        if request.verification_mode == VerificationMode::ATTESTATION_ONLY
+            && !policy.is_attestation_only(request.venue, request.claim_type)
+        {
+            let rejected = NonProvable::new(
+                NonProvableReason::POLICY_VIOLATION,
+                format!(
+                    "Policy does not designate {:?} {:?} claims as attestation-only",
+                    request.venue, request.claim_type
+                ),
+            );
+            persist_new(
+                &self.store,
+                mark_non_provable(receipt, rejected, &integrity),
+            )
+            .await?;
+            return Ok(receipt_id);
+        }
+
+        if let Some(screening) = &self.screening {
+            let subjects = vec![request.account_ref.clone()];
+            let decision = screen_subjects(
+                screening.as_ref(),
+                &receipt_id,
+                ScreeningStage::PreSubmission,
+                subjects,
             )
             .await;
-            tasks.lock().await.remove(&receipt_id_for_cleanup);
-        });
-        self.tasks.lock().await.insert(receipt_id.clone(), handle);
+            let rejection = screening_rejection(&decision);
+            self.store.record_screening(decision).await;
+            if let Some(rejected) = rejection {
+                persist_new(
+                    &self.store,
+                    mark_non_provable(receipt, rejected, &integrity),
+                )
+                .await?;
+                return Ok(receipt_id);
+            }
+        }
+
+        let adapter = match self.adapters.get(&request.venue) {
+            Some(adapter) if adapter.capabilities().supports_claim(request.claim_type) => {
+                Arc::clone(adapter)
+            }
+            Some(_) => {
+                let rejected = NonProvable::new(
+                    NonProvableReason::UNSUPPORTED_VENUE_CLAIM,
+                    format!(
+                        "Adapter for venue {:?} does not support claim type {:?}",
+                        request.venue, request.claim_type
+                    ),
+                );
+                persist_new(
+                    &self.store,
+                    mark_non_provable(receipt, rejected, &integrity),
+                )
+                .await?;
+                return Ok(receipt_id);
+            }
+            None => {
+                let rejected = NonProvable::new(
+                    NonProvableReason::UNSUPPORTED_VENUE_CLAIM,
+                    format!("No adapter registered for venue {:?}", request.venue),
+                );
+                persist_new(
+                    &self.store,
+                    mark_non_provable(receipt, rejected, &integrity),
+                )
+                .await?;
+                return Ok(receipt_id);
+            }
+        };
+        // Malformed refs can never resolve at the venue; reject them before the adapter round trip.
+        let ref_issues = adapter
+            .reference_formats()
+            .map(|f| f.validate(&request))
+            .unwrap_or_default();
+        if !ref_issues.is_empty() {
+            let rejected = NonProvable::new(
+                NonProvableReason::SCHEMA_INVALID,
+                format!("Request failed validation: {}", ref_issues.join("; ")),
+            );
+            persist_new(
+                &self.store,
+                mark_non_provable(receipt, rejected, &integrity),
+            )
+            .await?;
+            return Ok(receipt_id);
+        }
+        if request.verification_mode.requires_anchoring() && self.anchor_publisher.is_none() {
+            let rejected = NonProvable::new(
+                NonProvableReason::UNSUPPORTED_VENUE_CLAIM,
+                format!(
+                    "Verification mode {:?} requires onchain anchoring, but no anchor publisher is configured",
+                    request.verification_mode
+                ),
+            );
+            persist_new(
+                &self.store,
+                mark_non_provable(receipt, rejected, &integrity),
+            )
+            .await?;
+            return Ok(receipt_id);
+        }
+        if !self.circuits.admit(request.venue).await {
+            let mut rejected = NonProvable::new(
+                NonProvableReason::SOURCE_UNAVAILABLE,
+                format!(
+                    "Circuit breaker for venue {:?} is open after repeated adapter failures, or already trying one request",
+                    request.venue
+                ),
+            )
+            .with_failed_source(format!("{:?} circuit breaker", request.venue));
+            if let Some(retry_after) = self.circuits.retry_after(request.venue).await {
+                rejected.remediation.retry_after_ms = Some(retry_after.as_millis() as u64);
+            }
+            persist_new(
+                &self.store,
+                mark_non_provable(receipt, rejected, &integrity),
+            )
+            .await?;
+            return Ok(receipt_id);
+        }
+        persist_new(&self.store, receipt.finalize(&integrity)).await?;
+        self.store.record_request(&receipt_id, sealed.clone()).await;
+        self.spawn_receipt_task(adapter, integrity, receipt_id.clone(), sealed, submit_order);
         Ok(receipt_id)
     }
 
-    pub async fn get_receipt(&self, receipt_id: &str) -> Option<ZKReceipt> {
-        self.store.lock().await.get(receipt_id).cloned()
+    fn spawn_receipt_task(
+        &self,
+        adapter: Arc<dyn VenueAdapter>,
+        integrity: IntegrityContext,
+        receipt_id: String,
+        request: ProofRequest,
+        submit_order: bool,
+    ) {
+        let task = ReceiptTask {
+            store: self.store.clone(),
+            adapter,
+            policy_engine: self.tenant_policy(request.tenant_id.as_deref()),
+            prover: Arc::clone(&self.prover),
+            verifier: self.verifier.clone(),
+            finality: self.finality.clone(),
+            finality_recheck: self.finality_recheck,
+            circuits: self.circuits.clone(),
+            statement_locales: self.statement_locales.clone(),
+            credentials: self.credentials.clone(),
+            screening: self.screening.clone(),
+            collection_redaction: self.collection_redaction.clone(),
+            evidence_limits: self.evidence_limits,
+            oracles: self.oracles.clone(),
+            proof_queue: self.proof_queue.clone(),
+            adapter_cache: self.adapter_cache.clone(),
+            evidence_vault: self.evidence_vault.clone(),
+            claim_handlers: self.claim_handlers.clone(),
+            anchoring: self
+                .anchor_publisher
+                .clone()
+                .map(|publisher| AnchorPipeline {
+                    publisher,
+                    fees: self.anchor_fees,
+                }),
+            integrity,
+            submit_order,
+            payload_key: self.payload_key.clone(),
+            leadership: self.leadership.clone(),
+        };
+        // Held across the spawn so the task cannot finish and deregister before it is registered.
+        let mut in_flight = self.in_flight.lock().expect("in-flight task lock poisoned");
+        let registry = Arc::clone(&self.in_flight);
+        let id = receipt_id.clone();
+        let handle = tokio::spawn(async move {
+            process_receipt_task(task, id.clone(), request).await;
+            let mut in_flight = registry.lock().expect("in-flight task lock poisoned");
+            // A re-prove may already have registered a newer task for the same receipt.
+            if in_flight
+                .get(&id)
+                .is_some_and(|handle| handle.id() == tokio::task::id())
+            {
+                in_flight.remove(&id);
+            }
+        });
+        in_flight.insert(receipt_id, handle);
+    }
+
+    // Stops the receipt's pipeline task wherever it is (awaiting the venue, finality, a proof slot or the
+    // prover) and settles the receipt as CANCELLED. Receipts that already settled are left as they are.
+    pub async fn cancel(&self, receipt_id: &str) -> Result<ZKReceipt> {
+        self.ensure_leader()?;
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        if receipt.status.is_terminal() {
+            return Err(anyhow!(
+                "receipt {} is {:?}; only in-flight receipts can be cancelled",
+                receipt_id,
+                receipt.status
+            ));
+        }
+        let task = self
+            .in_flight
+            .lock()
+            .expect("in-flight task lock poisoned")
+            .remove(receipt_id);
+        if let Some(task) = task {
+            task.abort();
+            // Once the task is joined it can no longer write, so the status read next is final.
+            let _ = task.await;
+        }
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        if receipt.status.is_terminal() {
+            return Err(anyhow!(
+                "receipt {} settled as {:?} before it could be cancelled",
+                receipt_id,
+                receipt.status
+            ));
+        }
+        let integrity = self.integrity_context(&now_iso())?;
+        persist(&self.store, cancel_receipt(receipt, &integrity)).await
+    }
+
+    // Runs evidence collection and proving again for a receipt that failed on something transient: a
+    // venue or source outage, a prover failure, or finality not reached in time. The failed attempt moves into
+    // `provenance.prior_attempts` and the receipt goes back to PENDING under the same id.
+    pub async fn retry(&self, receipt_id: &str) -> Result<ZKReceipt> {
+        self.ensure_leader()?;
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        let failure = match (&receipt.status, &receipt.non_provable) {
+            (ReceiptStatus::NON_PROVABLE, Some(non_provable))
+                if matches!(
+                    non_provable.reason_code,
+                    NonProvableReason::SOURCE_UNAVAILABLE
+                        | NonProvableReason::PROOF_FAILURE
+                        | NonProvableReason::FINALITY_TIMEOUT
+                ) =>
+            {
+                non_provable.clone()
+            }
+            _ => {
+                return Err(anyhow!(
+                    "receipt {} is {:?}{}; only SOURCE_UNAVAILABLE, PROOF_FAILURE and FINALITY_TIMEOUT receipts can be retried",
+                    receipt_id,
+                    receipt.status,
+                    receipt
+                        .non_provable
+                        .as_ref()
+                        .map(|n| format!(" ({:?})", n.reason_code))
+                        .unwrap_or_default()
+                ))
+            }
+        };
+        // The venue may have taken an order whose submission errored; placing it again could fill twice.
+        if receipt.provenance.submitted_by_engine
+            && failure
+                .remediation
+                .failed_source
+                .as_deref()
+                .is_some_and(|s| s.ends_with(SUBMIT_ORDER_STAGE))
+        {
+            return Err(anyhow!(
+                "order submission for receipt {} failed and may still have reached the venue; check the venue and submit an observation request",
+                receipt_id
+            ));
+        }
+        // Receipts rejected at submission (an open circuit breaker) never kept their request.
+        let request = self.store.request(receipt_id).await.ok_or_else(|| {
+            anyhow!(
+                "original request for receipt {} is not available; submit it again",
+                receipt_id
+            )
+        })?;
+        let adapter = self
+            .adapters
+            .get(&request.venue)
+            .cloned()
+            .ok_or_else(|| anyhow!("No adapter registered for venue {:?}", request.venue))?;
+
+        let integrity = self.integrity_context(&now_iso())?;
+        let mut retrying = receipt;
+        let attempt = PriorAttempt {
+            attempt: retrying.provenance.prior_attempts.len() as u32 + 1,
+            failed_at: retrying.timing.updated_at.clone(),
+            reason_code: failure.reason_code,
+            details: failure.details,
+            failed_source: failure.remediation.failed_source,
+            evidence_root: retrying.provenance.evidence_root.clone(),
+            artifact_hashes: retrying
+                .provenance
+                .evidence_items
+                .iter()
+                .map(|item| item.artifact_hash.clone())
+                .collect(),
+            adapter_id: retrying.provenance.adapter_id.clone(),
+            adapter_version: retrying.provenance.adapter_version.clone(),
+        };
+        retrying.provenance.prior_attempts.push(attempt);
+        retrying.status = ReceiptStatus::PENDING;
+        retrying.non_provable = None;
+        retrying.proof = no_proof_metadata();
+        retrying.timing.updated_at = now_iso();
+        let retrying = persist(&self.store, retrying.finalize(&integrity)).await?;
+        self.spawn_receipt_task(adapter, integrity, receipt_id.to_string(), request, false);
+        Ok(retrying)
+    }
+
+    // Withdraws a PROVED receipt, e.g. after an out-of-band dispute or a revoked policy. The receipt
+    // is re-signed as INVALIDATED and `invalidation` records who did it and why.
+    pub async fn invalidate(
+        &self,
+        receipt_id: &str,
+        reason: &str,
+        actor: &str,
+    ) -> Result<ZKReceipt> {
+        self.ensure_leader()?;
+        if reason.trim().is_empty() || actor.trim().is_empty() {
+            return Err(anyhow!(
+                "invalidating a receipt needs a reason and an actor"
+            ));
+        }
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        if receipt.status != ReceiptStatus::PROVED {
+            return Err(anyhow!(
+                "receipt {} is {:?}; only PROVED receipts can be invalidated",
+                receipt_id,
+                receipt.status
+            ));
+        }
+        let integrity = self.integrity_context(&now_iso())?;
+        let invalidation = Invalidation {
+            reason: reason.to_string(),
+            actor: actor.to_string(),
+            invalidated_at: now_iso(),
+        };
+        persist(
+            &self.store,
+            revoke_receipt(receipt, invalidation, &integrity),
+        )
+        .await
+    }
+
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight
+            .lock()
+            .expect("in-flight task lock poisoned")
+            .len()
+    }
+
+    pub async fn refresh_evidence(&self, receipt_id: &str) -> Result<EvidenceRefresh> {
+        self.ensure_leader()?;
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        if receipt.status != ReceiptStatus::PROVED {
+            return Err(anyhow!(
+                "receipt {} is {:?}; only PROVED receipts can be refreshed",
+                receipt_id,
+                receipt.status
+            ));
+        }
+        let sealed = self.store.request(receipt_id).await.ok_or_else(|| {
+            anyhow!(
+                "original request for receipt {} is not available",
+                receipt_id
+            )
+        })?;
+        let (request, _) = open_payload(&sealed, self.payload_key.as_ref())?;
+        let adapter = self
+            .adapters
+            .get(&request.venue)
+            .cloned()
+            .ok_or_else(|| anyhow!("No adapter registered for venue {:?}", request.venue))?;
+
+        let adapter_calls = CostRecord {
+            adapter_calls: 2,
+            ..CostRecord::default()
+        };
+        self.store.record_cost(receipt_id, &adapter_calls).await;
+        let ack = match adapter.acknowledge(&request).await {
+            Ok(ack) => ack,
+            Err(err) => return Err(anyhow!(scrub_error(&self.credentials, &err).await)),
+        };
+        let mut bundle = match adapter.collect_evidence(&request, &ack).await {
+            Ok(bundle) => bundle,
+            Err(err) => return Err(anyhow!(scrub_error(&self.credentials, &err).await)),
+        };
+        // Refreshes bypass the cache; later retries of this order should see what was just fetched.
+        if let Some(cache) = &self.adapter_cache {
+            cache.put(&request, &ack, &bundle);
+        }
+        // Oracle quotes are published history for the original claim time, so they are carried over
+        // rather than fetched again for the new acknowledgement time.
+        for item in &receipt.provenance.evidence_items {
+            if item.source_kind == ORACLE_SOURCE_KIND {
+                bundle.observed_tags.extend(item.tags.iter().cloned());
+                bundle.items.push(item.clone());
+            }
+        }
+        if let Some(name) = credential_leak(&self.credentials, &bundle).await {
+            return Err(anyhow!(
+                "refreshed evidence contains credential material ({})",
+                name
+            ));
+        }
+        let divergences = diff_evidence(
+            &receipt.provenance.evidence_items,
+            &bundle.items,
+            &bundle.raw_artifacts,
+        );
+        if divergences.is_empty() {
+            return Ok(EvidenceRefresh {
+                receipt_id: receipt_id.to_string(),
+                action: RefreshAction::RECONFIRMED,
+                divergences,
+            });
+        }
+
+        let integrity = self.integrity_context(&now_iso())?;
+        let policy = self.tenant_policy(request.tenant_id.as_deref());
+        let evidence_issues = validate_evidence(
+            &bundle,
+            &policy.evidence_tag_vocabulary(),
+            &policy.known_source_kinds(),
+        );
+        let tolerance = policy.timestamp_tolerance(request.venue);
+        bundle.conflicts.extend(timestamp_conflicts(
+            &bundle,
+            &adapter.clock_offsets(),
+            &tolerance,
+        ));
+        let decision = self.claim_handlers.evaluate(&policy, &request, &bundle);
+        let rejection = if !evidence_issues.is_empty() {
+            Some(NonProvable::new(
+                NonProvableReason::SCHEMA_INVALID,
+                evidence_issues.join("; "),
+            ))
+        } else if !decision.ok {
+            Some(decision.into_non_provable())
+        } else {
+            None
+        };
+
+        let action = match rejection {
+            Some(mut non_provable) => {
+                non_provable.details = format!(
+                    "Evidence diverged after proving ({}): {}",
+                    divergences.join("; "),
+                    non_provable.details
+                );
+                let invalidated = invalidate_receipt(receipt, non_provable);
+                persist(&self.store, invalidated.finalize(&integrity)).await?;
+                RefreshAction::INVALIDATED
+            }
+            None => {
+                let mut reproving = receipt;
+                reproving.status = ReceiptStatus::PENDING;
+                reproving.proof = no_proof_metadata();
+                reproving.timing.updated_at = now_iso();
+                persist(&self.store, reproving.finalize(&integrity)).await?;
+                self.spawn_receipt_task(adapter, integrity, receipt_id.to_string(), sealed, false);
+                RefreshAction::REPROVING
+            }
+        };
+        Ok(EvidenceRefresh {
+            receipt_id: receipt_id.to_string(),
+            action,
+            divergences,
+        })
+    }
+
+    pub async fn get_receipt(&self, receipt_id: &str) -> Option<ZKReceipt> {
+        self.store.get(receipt_id).await
+    }
+
+    pub async fn receipt_history(&self, receipt_id: &str) -> Result<Vec<ReceiptEvent>> {
+        self.store.history(receipt_id).await
+    }
+
+    pub async fn receipt_at(&self, receipt_id: &str, at: &str) -> Result<Option<ZKReceipt>> {
+        self.store.receipt_at(receipt_id, at).await
+    }
+
+    // For clients pinned to an older receipt format.
+    pub async fn get_receipt_as(
+        &self,
+        receipt_id: &str,
+        version: &str,
+    ) -> Result<serde_json::Value> {
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        self.versions.convert(&receipt, version)
+    }
+
+    // The receipt as HTTP, MCP and webhook consumers see it: the commitment alone while escrow
+    // withholds it, in full otherwise. `get_receipt` stays the operator's view.
+    pub async fn published_receipt(&self, receipt_id: &str) -> Option<ZKReceipt> {
+        self.store.get(receipt_id).await.map(published)
+    }
+
+    pub async fn published_receipt_as(
+        &self,
+        receipt_id: &str,
+        version: &str,
+    ) -> Result<serde_json::Value> {
+        let receipt = self
+            .published_receipt(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        self.versions.convert(&receipt, version)
+    }
+
+    // Publishes a receipt submitted with `escrow_hold_ms` in full once its holding period is over.
+    // Only settled receipts can be released, so the pipeline never overwrites the release.
+    pub async fn release_escrow(&self, receipt_id: &str, actor: &str) -> Result<ZKReceipt> {
+        self.ensure_leader()?;
+        if actor.trim().is_empty() {
+            return Err(anyhow!("releasing a receipt from escrow needs an actor"));
+        }
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        if !receipt.status.is_terminal() {
+            return Err(anyhow!(
+                "receipt {} is {:?}; escrow is released once the receipt settles",
+                receipt_id,
+                receipt.status
+            ));
+        }
+        let integrity = self.integrity_context(&now_iso())?;
+        persist(&self.store, release(receipt, actor)?.finalize(&integrity)).await
+    }
+
+    pub async fn present_receipt(
+        &self,
+        receipt_id: &str,
+        challenge: &PresentationChallenge,
+    ) -> Result<ReceiptPresentation> {
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        let now = now_iso();
+        let holder = self
+            .keyring
+            .signer_at(&now)
+            .ok_or_else(|| anyhow!("no signer key is valid at {}", now))?;
+        let signer = self
+            .receipt_signers
+            .iter()
+            .find(|s| s.identity() == holder)
+            .ok_or_else(|| anyhow!("no private key loaded for signer {}", holder.key_id))?;
+        present_receipt(&self.keyring, signer.as_ref(), &receipt, challenge)
+    }
+
+    pub async fn list_receipts(&self, query: &ReceiptQuery) -> Result<Vec<ZKReceipt>> {
+        self.store.list(query).await
+    }
+
+    // One page of matching receipts in export order, oldest first, strictly after `after`.
+    pub async fn page_receipts(
+        &self,
+        query: &ReceiptQuery,
+        after: Option<&ExportCursor>,
+        limit: usize,
+    ) -> Result<Vec<ZKReceipt>> {
+        self.store.page(query, after, limit).await
+    }
+
+    pub fn export_receipts(
+        &self,
+        export: ReceiptExport,
+    ) -> tokio::sync::mpsc::Receiver<Result<ExportItem>> {
+        export_receipts(self.store.clone(), export)
+    }
+
+    pub async fn watch(&self, receipt_id: &str) -> Result<ReceiptWatch> {
+        self.store
+            .watch(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))
+    }
+
+    // Every write to the receipt, including those after it settles (anchoring, invalidation). Unlike
+    // `subscribe` the channel stays open while the receipt exists.
+    pub async fn receipt_updates(&self, receipt_id: &str) -> Result<watch::Receiver<ZKReceipt>> {
+        self.store
+            .subscribe(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))
+    }
+
+    // Status-only view of `watch` for callers that just react to transitions. The sender is dropped,
+    // closing the channel, once the receipt reaches a terminal status or every receiver is gone.
+    pub async fn subscribe(&self, receipt_id: &str) -> Result<watch::Receiver<ReceiptStatus>> {
+        let mut updates = self
+            .store
+            .subscribe(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        let initial = updates.borrow_and_update().status;
+        let (statuses, receiver) = watch::channel(initial);
+        if initial.is_terminal() {
+            return Ok(receiver);
+        }
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = updates.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                        let status = updates.borrow_and_update().status;
+                        statuses.send_if_modified(|current| std::mem::replace(current, status) != status);
+                        if status.is_terminal() {
+                            return;
+                        }
+                    }
+                    _ = statuses.closed() => return,
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    // Works through the pending corrections of every adapter that publishes them. Each PROVED trade
+    // receipt a correction names gets a superseding receipt, and is invalidated only once that
+    // receipt is PROVED; until then the original stands. A correction is acknowledged to its
+    // adapter only when none of its superseding receipts is still in flight, so the next call picks
+    // up one left halfway and reuses the superseding receipts already issued. A failing feed does
+    // not hold up the others; the failures are returned together and kept for health.
+    pub async fn process_corrections(&self) -> Result<Vec<CorrectionOutcome>> {
+        self.ensure_leader()?;
+        let mut outcomes = Vec::new();
+        let mut failures = Vec::new();
+        for adapter in self
+            .adapters
+            .values()
+            .filter(|a| a.capabilities().supports_corrections)
+        {
+            if let Err(err) = self
+                .process_adapter_corrections(adapter.as_ref(), &mut outcomes)
+                .await
+            {
+                failures.push(format!("{:?}: {:#}", adapter.venue(), err));
+            }
+        }
+        let failure = (!failures.is_empty()).then(|| failures.join("; "));
+        *self
+            .corrections_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = failure.clone();
+        match failure {
+            Some(failure) => Err(anyhow!("venue corrections failed: {}", failure)),
+            None => Ok(outcomes),
+        }
+    }
+
+    async fn process_adapter_corrections(
+        &self,
+        adapter: &dyn VenueAdapter,
+        outcomes: &mut Vec<CorrectionOutcome>,
+    ) -> Result<()> {
+        for correction in adapter.poll_corrections().await? {
+            let affected = self
+                .store
+                .list(&ReceiptQuery {
+                    venue: Some(correction.venue),
+                    status: Some(ReceiptStatus::PROVED),
+                    ..ReceiptQuery::default()
+                })
+                .await?;
+            let mut settled = true;
+            for receipt in affected.into_iter().filter(|r| {
+                matches!(
+                    r.claim.r#type,
+                    ClaimType::TRADE_EXECUTED | ClaimType::BEST_EXECUTION
+                ) && r.subject.order_ref == correction.order_ref
+                    && r.subject.execution_ref.as_deref() == Some(correction.execution_ref.as_str())
+            }) {
+                let outcome = self.supersede_receipt(receipt, &correction).await?;
+                settled &= outcome.superseding_status.is_terminal();
+                outcomes.push(outcome);
+            }
+            if settled {
+                adapter.ack_correction(&correction.correction_ref).await?;
+            }
+        }
+        Ok(())
+    }
+
+    // Processes corrections every `interval` while this engine leads. Failures are reported by
+    // health until a pass succeeds.
+    pub fn watch_corrections(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                if self.is_leader() {
+                    self.process_corrections().await.ok();
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    // The superseding receipt already issued for `correction` against `receipt_id`, if any.
+    async fn superseding_receipt(
+        &self,
+        receipt_id: &str,
+        correction: &TradeCorrection,
+    ) -> Result<Option<ZKReceipt>> {
+        let candidates = self
+            .store
+            .list(&ReceiptQuery {
+                venue: Some(correction.venue),
+                claim_type: Some(correction.claim_type()),
+                ..ReceiptQuery::default()
+            })
+            .await?;
+        for candidate in candidates
+            .into_iter()
+            .filter(|r| r.supersedes.as_deref() == Some(receipt_id))
+        {
+            let request = self.store.request(&candidate.receipt_id).await;
+            if request
+                .is_some_and(|r| r.payload["correction_ref"] == correction.correction_ref.as_str())
+            {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn supersede_receipt(
+        &self,
+        receipt: ZKReceipt,
+        correction: &TradeCorrection,
+    ) -> Result<CorrectionOutcome> {
+        let superseding = match self
+            .superseding_receipt(&receipt.receipt_id, correction)
+            .await?
+        {
+            Some(superseding) => superseding,
+            None => {
+                let superseding_id = self.submit_superseding(&receipt, correction).await?;
+                self.store
+                    .get(&superseding_id)
+                    .await
+                    .ok_or_else(|| anyhow!("unknown receipt id: {}", superseding_id))?
+            }
+        };
+        let outcome = CorrectionOutcome {
+            correction_ref: correction.correction_ref.clone(),
+            original_receipt_id: receipt.receipt_id.clone(),
+            superseding_receipt_id: superseding.receipt_id.clone(),
+            superseding_status: superseding.status,
+        };
+        // A superseding receipt that could not be proved leaves the original standing.
+        if superseding.status != ReceiptStatus::PROVED {
+            return Ok(outcome);
+        }
+        let integrity = self.integrity_context(&now_iso())?;
+        let mut invalidated = invalidate_receipt(
+            receipt,
+            NonProvable::new(
+                NonProvableReason::EVIDENCE_CONFLICT,
+                format!(
+                    "Venue correction {} reports the execution as {:?}; superseded by receipt {}.",
+                    correction.correction_ref,
+                    correction.claim_type(),
+                    superseding.receipt_id
+                ),
+            ),
+        );
+        invalidated.superseded_by = Some(superseding.receipt_id);
+        persist(&self.store, invalidated.finalize(&integrity)).await?;
+        Ok(outcome)
+    }
+
+    async fn submit_superseding(
+        &self,
+        receipt: &ZKReceipt,
+        correction: &TradeCorrection,
+    ) -> Result<String> {
+        // The superseding receipt is held to the same tenant's policy as the one it replaces.
+        let original = self.store.request(&receipt.receipt_id).await;
+        let verification_mode = original
+            .as_ref()
+            .map(|r| r.verification_mode)
+            .unwrap_or_default();
+        let tenant_id = original.and_then(|r| r.tenant_id);
+        self.submit_with_lineage(
+            ProofRequest {
+                execution_ref: Some(correction.execution_ref.clone()),
+                payload: serde_json::json!({
+                    "correction_ref": correction.correction_ref,
+                    "supersedes": receipt.receipt_id
+                }),
+                verification_mode,
+                tenant_id,
+                ..ProofRequest::new(
+                    correction.venue,
+                    correction.claim_type(),
+                    receipt.subject.account_ref.clone(),
+                    correction.order_ref.clone(),
+                )
+            },
+            Some(receipt.receipt_id.clone()),
+            false,
+        )
+        .await
+    }
+
+    pub async fn anchor_receipts(
+        &self,
+        receipt_ids: &[String],
+        publisher: &dyn AnchorPublisher,
+    ) -> Result<AnchorBatch> {
+        self.ensure_leader()?;
+        let mut receipts = Vec::with_capacity(receipt_ids.len());
+        for receipt_id in receipt_ids {
+            let receipt = self
+                .store
+                .get(receipt_id)
+                .await
+                .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+            if receipt.status != ReceiptStatus::PROVED {
+                return Err(anyhow!(
+                    "receipt {} is {:?}; only PROVED receipts can be anchored",
+                    receipt_id,
+                    receipt.status
+                ));
+            }
+            receipts.push(receipt);
+        }
+        let (batch, anchored) =
+            anchor_batch(&self.store, publisher, &self.anchor_fees, receipts).await?;
+        // A receipt written while its root was being published keeps the newer state; its
+        // inclusion is still reported in the batch.
+        let integrity = self.integrity_context(&now_iso())?;
+        for receipt in anchored {
+            let _ = persist(&self.store, receipt.finalize(&integrity)).await;
+        }
+        Ok(batch)
+    }
+
+    pub fn watermarks(&self) -> Vec<Watermark> {
+        self.issuance
+            .watermarks(|identity| self.signer_for(identity))
+    }
+
+    pub fn issuance_log(&self, key_id: &str) -> Vec<IssuanceEntry> {
+        self.issuance.entries(key_id)
+    }
+
+    pub async fn screening_decisions(&self, receipt_id: &str) -> Vec<ScreeningDecision> {
+        self.store.screenings(receipt_id).await
+    }
+
+    pub async fn receipt_cost(&self, receipt_id: &str) -> Option<CostRecord> {
+        self.store.cost(receipt_id).await
+    }
+
+    pub async fn stats(&self, query: &ReceiptQuery) -> Result<ReceiptStats> {
+        let receipts = self.store.list(query).await?;
+        let mut stats = build_stats(&receipts, &self.store.costs().await);
+        stats.proof_queue = self.proof_queue.stats();
+        Ok(stats)
+    }
+
+    // Follows `supersedes` and `superseded_by` in both directions for the lineage.
+    pub async fn dispute_bundle(&self, receipt_id: &str) -> Result<DisputeBundle> {
+        let receipt = self
+            .store
+            .get(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        let mut lineage = Vec::new();
+        for link in [
+            |r: &ZKReceipt| r.supersedes.clone(),
+            |r: &ZKReceipt| r.superseded_by.clone(),
+        ] {
+            let mut next = link(&receipt);
+            while let Some(id) = next {
+                let Some(linked) = self.store.get(&id).await else {
+                    break;
+                };
+                if linked.receipt_id == receipt.receipt_id
+                    || lineage.iter().any(|r: &ZKReceipt| r.receipt_id == id)
+                {
+                    break;
+                }
+                next = link(&linked);
+                lineage.push(linked);
+            }
+        }
+        let integrity_valid = recompute_receipt_hash(&receipt) == receipt.integrity.receipt_hash
+            && self.keyring.verify_receipt_signature(&receipt);
+        let proof_valid = receipt.status == ReceiptStatus::PROVED
+            && self
+                .verifier
+                .verify_with_handlers(&receipt, &self.claim_handlers)
+                .await;
+        Ok(DisputeBundle::new(
+            receipt,
+            self.store.request(receipt_id).await,
+            lineage,
+            self.store.screenings(receipt_id).await,
+            self.keyring.manifest(),
+            integrity_valid,
+            proof_valid,
+        ))
+    }
+
+    pub async fn build_period_report(
+        &self,
+        account_ref: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<PeriodReport> {
+        let query = ReceiptQuery {
+            status: Some(ReceiptStatus::PROVED),
+            ..ReceiptQuery::default()
+        };
+        let mut receipts = Vec::new();
+        for receipt in self.store.list(&query).await? {
+            if receipt.subject.account_ref == account_ref {
+                let request = self.store.request(&receipt.receipt_id).await;
+                receipts.push((receipt, request));
+            }
+        }
+        build_period_report(
+            account_ref,
+            from,
+            to,
+            &receipts,
+            self.current_signer(&now_iso())?.as_ref(),
+        )
+    }
+
+    // Unauthenticated lookups only disclose status and anchoring, never the subject refs. A
+    // presented receipt is checked on its own, its signature against this deployment's key ring
+    // or a trusted instance's descriptor, so copies issued by federated deployments still verify.
+    pub async fn verify_public(&self, query: &PublicVerificationQuery) -> PublicVerification {
+        let (receipt_hash, presented) = match query {
+            PublicVerificationQuery::ReceiptHash(hash) => (hash.clone(), None),
+            PublicVerificationQuery::Receipt(receipt) => (
+                receipt.integrity.receipt_hash.clone(),
+                Some(receipt.as_ref()),
+            ),
+        };
+        let stored = self.store.find_by_receipt_hash(&receipt_hash).await;
+        let subject = stored.as_ref().or(presented);
+        let proof_valid = match presented.or(stored.as_ref()) {
+            Some(receipt) => Some(
+                self.verifier
+                    .verify_with_handlers(receipt, &self.claim_handlers)
+                    .await,
+            ),
+            None => None,
+        };
+        let inclusion = subject.and_then(|r| r.proof.anchor_inclusion.as_ref());
+        PublicVerification {
+            known: stored.is_some(),
+            status: stored.as_ref().map(|r| r.status),
+            integrity_valid: presented.map(|r| {
+                recompute_receipt_hash(r) == r.integrity.receipt_hash
+                    && self.trusted_signature_valid(r)
+            }),
+            proof_valid,
+            verification_mode: subject.map(|r| r.proof.verification_mode),
+            anchored_root_ref: subject.and_then(|r| r.proof.anchored_root_ref.clone()),
+            anchor_tx_ref: inclusion.map(|i| i.anchor_tx_ref.clone()),
+            anchor_inclusion_valid: inclusion.map(|i| verify_inclusion(&receipt_hash, i)),
+            receipt_hash,
+            checked_at: now_iso(),
+        }
+    }
+
+    // Signers unknown to both this deployment and the trust store never verify.
+    fn trusted_signature_valid(&self, receipt: &ZKReceipt) -> bool {
+        let signer = &receipt.integrity.signer;
+        if self.keyring.keys().iter().any(|k| k.identity == *signer) {
+            return self.keyring.verify_receipt_signature(receipt);
+        }
+        self.trust_store
+            .issuer_of(signer)
+            .and_then(|issuer| KeyRing::new(issuer.keys.clone()).ok())
+            .is_some_and(|keys| keys.verify_receipt_signature(receipt))
+    }
+
+    pub async fn wait_for_receipt(
+        &self,
+        receipt_id: &str,
+        timeout: std::time::Duration,
+    ) -> Result<ZKReceipt> {
+        let mut updates = self
+            .store
+            .subscribe(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))?;
+        let receipt = tokio::time::timeout(timeout, updates.wait_for(|r| r.status.is_terminal()))
+            .await
+            .map_err(|_| anyhow!("timeout waiting for receipt {}", receipt_id))?
+            .map_err(|_| anyhow!("receipt {} was dropped before completion", receipt_id))?
+            .clone();
+        Ok(receipt)
+    }
+
+    pub async fn health(&self) -> HealthReport {
+        let checked_at = now_iso();
+        let mut issues = Vec::new();
+
+        let mut venues: Vec<Venue> = self.adapters.keys().copied().collect();
+        venues.sort_by_key(|v| format!("{:?}", v));
+        let mut adapters = Vec::with_capacity(venues.len());
+        for venue in venues {
+            let circuit = self.circuits.state(venue).await;
+            if circuit != CircuitState::Closed {
+                issues.push(format!(
+                    "{:?} adapter circuit breaker is {:?}",
+                    venue, circuit
+                ));
+            }
+            adapters.push(AdapterHealth {
+                venue,
+                circuit,
+                consecutive_failures: self.circuits.consecutive_failures(venue).await,
+            });
+        }
+        if adapters.is_empty() {
+            issues.push("no venue adapters are registered".to_string());
+        }
+
+        let counts = tokio::time::timeout(STORE_PROBE_TIMEOUT, self.store.status_counts()).await;
+        let (store, mut queues) = match counts {
+            Ok(counts) => (
+                StoreHealth {
+                    reachable: true,
+                    receipts: counts.values().sum(),
+                    persistent: self.store.is_persistent(),
+                },
+                QueueDepths {
+                    pending: counts.get(&ReceiptStatus::PENDING).copied().unwrap_or(0),
+                    awaiting_finality: counts
+                        .get(&ReceiptStatus::AWAITING_FINALITY)
+                        .copied()
+                        .unwrap_or(0),
+                    finality_waiters: 0,
+                    ..QueueDepths::default()
+                },
+            ),
+            Err(_) => {
+                issues.push(format!(
+                    "receipt store did not respond within {} ms",
+                    STORE_PROBE_TIMEOUT.as_millis()
+                ));
+                (
+                    StoreHealth {
+                        reachable: false,
+                        receipts: 0,
+                        persistent: self.store.is_persistent(),
+                    },
+                    QueueDepths::default(),
+                )
+            }
+        };
+        if let Some(err) = self.store.persistence_error() {
+            issues.push(format!("receipt store write-through failed: {}", err));
+        }
+        if let Some(err) = self
+            .corrections_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+        {
+            issues.push(format!("venue corrections failed: {}", err));
+        }
+        if let Some(dropped) = self.store.dropped_write() {
+            issues.push(format!(
+                "receipt write dropped after a conflict: {}",
+                dropped
+            ));
+        }
+        if let Some(err) = self
+            .leadership
+            .as_ref()
+            .and_then(LeaderElection::last_error)
+        {
+            issues.push(format!("leader election failed: {}", err));
+        }
+        if let Some(wait) = &self.finality {
+            for venue in self.adapters.keys() {
+                queues.finality_waiters += wait.observer.pending(*venue).await;
+            }
+        }
+        let proofs = self.proof_queue.stats();
+        queues.proofs_running = proofs.running;
+        queues.proofs_queued = proofs.queued;
+
+        let prover = ProverHealth {
+            backend: self.prover.backend_name(),
+            available: self.prover.available().await,
+        };
+        if !prover.available {
+            issues.push(format!(
+                "prover backend {:?} is unavailable",
+                prover.backend
+            ));
+        }
+        let signer_valid = self.keyring.signer_at(&checked_at).is_some();
+        if !signer_valid {
+            issues.push(format!("no signer key is valid at {}", checked_at));
+        }
+
+        let live = store.reachable;
+        let ready = live
+            && prover.available
+            && signer_valid
+            && adapters.iter().any(|a| a.circuit != CircuitState::Open);
+        let status = if !ready {
+            HealthStatus::UNHEALTHY
+        } else if issues.is_empty() {
+            HealthStatus::HEALTHY
+        } else {
+            HealthStatus::DEGRADED
+        };
+        HealthReport {
+            status,
+            live,
+            ready,
+            checked_at,
+            adapters,
+            store,
+            prover,
+            queues,
+            issues,
+            role: self.leadership.as_ref().map(LeaderElection::role),
+        }
+    }
+
+    // The loaded private key for `identity`, else a hash binding for identities that have no key.
+    fn signer_for(&self, identity: &SignerIdentity) -> Option<Arc<dyn ReceiptSigner>> {
+        if let Some(signer) = self
+            .receipt_signers
+            .iter()
+            .find(|s| s.identity() == identity)
+        {
+            return Some(Arc::clone(signer));
+        }
+        (identity.algorithm != ED25519_ALGORITHM)
+            .then(|| Arc::new(HashBindingSigner::new(identity.clone())) as Arc<dyn ReceiptSigner>)
     }
 
-    pub async fn wait_for_receipt(&self, receipt_id: &str, timeout: std::time::Duration) -> Result<ZKReceipt> {
-        let handle_opt = { self.tasks.lock().await.remove(receipt_id) };
-        if let Some(handle) = handle_opt {
-            tokio::time::timeout(timeout, handle).await.map_err(|_| anyhow!("timeout waiting for receipt task"))??;
-        }
-        self.get_receipt(receipt_id)
-            .await
-            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))
+    // The signer for the key ring's key at `now`.
+    fn current_signer(&self, now: &str) -> Result<Arc<dyn ReceiptSigner>> {
+        let identity = self
+            .keyring
+            .signer_at(now)
+            .ok_or_else(|| anyhow!("no signer key is valid at {}", now))?;
+        self.signer_for(identity).ok_or_else(|| {
+            anyhow!(
+                "no private key loaded for ed25519 signer {}",
+                identity.key_id
+            )
+        })
     }
 
-    fn new_pending_receipt(&self, request: &ProofRequest) -> ZKReceipt {
-        let now = now_iso();
-        let claim_hash = hash_json(&serde_json::json!({
-            "venue": request.venue,
-            "claim_type": request.claim_type,
-            "account_ref": request.account_ref,
-            "order_ref": request.order_ref,
-            "execution_ref": request.execution_ref
-        }));
+    fn integrity_context(&self, now: &str) -> Result<IntegrityContext> {
+        let receipt_signer = self.current_signer(now)?;
+        Ok(IntegrityContext {
+            signer: receipt_signer.identity().clone(),
+            receipt_signer,
+            receipt_version: self.versions.produces.clone(),
+            commitments: self.commitments,
+            ledger: self.issuance.clone(),
+        })
+    }
+
+    fn new_pending_receipt(
+        &self,
+        request: &ProofRequest,
+        integrity: &IntegrityContext,
+        now: String,
+        supersedes: Option<String>,
+    ) -> ZKReceipt {
+        let claim_hash = integrity.commitments.request_claim_hash(request);
         let claim = TruthClaim {
             r#type: request.claim_type,
-            statement: "PENDING: statement unavailable until evidence collection completes".to_string(),
-            claim_hash: claim_hash.clone(),
+            statement: "PENDING: statement unavailable until evidence collection completes"
+                .to_string(),
+            claim_hash,
+            statement_locale: StatementLocale::En,
+            localized_statements: vec![],
+            encrypted_fields: vec![],
+            public_values: serde_json::Map::new(),
         };
         let provenance = Provenance {
-            evidence_root: hash_json(&serde_json::json!({"empty": true})),
+            evidence_root: integrity.commitments.empty_evidence_root(),
             evidence_items: vec![],
+            collection: vec![],
+            adapter_id: String::new(),
+            adapter_version: String::new(),
+            prior_attempts: vec![],
+            submitted_by_engine: false,
         };
         let proof = no_proof_metadata();
-        let integrity = build_integrity(
-            &self.signer,
-            &self.receipt_version,
-            ReceiptStatus::PENDING,
-            &claim_hash,
-            &provenance.evidence_root,
-            &proof.public_inputs_hash,
-        );
+        let integrity = unsigned_integrity(integrity);
+        let escrow = request
+            .escrow_hold_ms
+            .map(|hold_ms| escrow_for(hold_ms, &now));
         ZKReceipt {
             receipt_id: new_receipt_id(),
-            version: self.receipt_version.clone(),
+            version: self.versions.produces.clone(),
             status: ReceiptStatus::PENDING,
             claim,
             subject: Subject {
@@ -130,245 +2054,1260 @@ impl ReceiptEngine {
                 order_ref: request.order_ref.clone(),
                 execution_ref: request.execution_ref.clone(),
             },
-            policy: PolicyContext {
-                policy_id: self.policy_engine.policy_id(),
-                finality_rule_id: self.policy_engine.finality_rule_id(),
-                source_precedence_version: self.policy_engine.source_precedence_version(),
-            },
+            policy: self.tenant_policy(request.tenant_id.as_deref()).context(),
             provenance,
             timing: Timing {
                 created_at: now.clone(),
                 updated_at: now,
                 execution_observed_at: None,
                 finality_observed_at: None,
+                deadline_miss: None,
+                breakdown: StageBreakdown::default(),
+                finality_deadline: None,
             },
             proof,
             integrity,
             non_provable: None,
+            supersedes,
+            superseded_by: None,
+            invalidation: None,
+            revision: 0,
+            escrow,
         }
     }
 }
 
-async fn process_receipt_task(
-    store: Arc<Mutex<HashMap<String, ZKReceipt>>>,
-    adapter: Option<Arc<dyn VenueAdapter>>,
+#[derive(Clone)]
+struct FinalityWait {
+    observer: FinalityObserver,
+    timeout: Duration,
+}
+
+struct AnchorPipeline {
+    publisher: Arc<dyn AnchorPublisher>,
+    fees: AnchorFeePolicy,
+}
+
+#[derive(Clone)]
+struct IntegrityContext {
+    signer: SignerIdentity,
+    receipt_signer: Arc<dyn ReceiptSigner>,
+    receipt_version: String,
+    commitments: CommitmentScheme,
+    ledger: IssuanceLedger,
+}
+
+struct ReceiptTask {
+    store: ReceiptStore,
+    adapter: Arc<dyn VenueAdapter>,
     policy_engine: PolicyEngine,
     prover: Arc<dyn ProverBackend>,
     verifier: OffchainVerifier,
-    signer: String,
-    receipt_version: String,
-    receipt_id: String,
-    request: ProofRequest,
-) {
-    let current = { store.lock().await.get(&receipt_id).cloned() };
-    let Some(receipt) = current else { return; };
+    finality: Option<FinalityWait>,
+    finality_recheck: Option<Duration>,
+    circuits: CircuitBreakers,
+    statement_locales: Vec<StatementLocale>,
+    credentials: Option<CredentialStore>,
+    screening: Option<Arc<dyn ScreeningProvider>>,
+    collection_redaction: CollectionRedaction,
+    evidence_limits: EvidenceLimits,
+    oracles: Vec<Arc<dyn PriceOracle>>,
+    proof_queue: ProofQueue,
+    adapter_cache: Option<AdapterCache>,
+    evidence_vault: Option<EvidenceVault>,
+    claim_handlers: ClaimHandlers,
+    anchoring: Option<AnchorPipeline>,
+    integrity: IntegrityContext,
+    // Acknowledge by placing the order rather than looking it up.
+    submit_order: bool,
+    payload_key: Option<PayloadDecryptionKey>,
+    leadership: Option<LeaderElection>,
+}
 
-    let Some(adapter) = adapter else {
-        let updated = mark_non_provable(
-            receipt,
-            NonProvableReason::UNSUPPORTED_VENUE_CLAIM,
-            format!("No adapter registered for venue {:?}", request.venue),
-            &signer,
-            &receipt_version,
-        );
-        store.lock().await.insert(receipt_id, updated);
+// `sealed` is the request as stored; the pipeline works on a copy with its encrypted fields opened.
+async fn process_receipt_task(task: ReceiptTask, receipt_id: String, sealed: ProofRequest) {
+    let ReceiptTask {
+        store,
+        adapter,
+        policy_engine,
+        prover,
+        verifier,
+        finality,
+        finality_recheck,
+        circuits,
+        statement_locales,
+        credentials,
+        screening,
+        collection_redaction,
+        evidence_limits,
+        oracles,
+        proof_queue,
+        adapter_cache,
+        evidence_vault,
+        claim_handlers,
+        anchoring,
+        integrity,
+        submit_order,
+        payload_key,
+        leadership,
+    } = task;
+    let current = store.get(&receipt_id).await;
+    let Some(mut receipt) = current else {
         return;
     };
+    // A retry or re-prove may run under a newer policy revision than the one first recorded.
+    receipt.policy = policy_engine.context();
+    receipt.timing.breakdown = StageBreakdown::default();
+    receipt.timing.finality_deadline = None;
+    let mut request = match open_payload(&sealed, payload_key.as_ref()) {
+        Ok((request, encrypted_fields)) => {
+            receipt.claim.encrypted_fields = encrypted_fields;
+            request
+        }
+        Err(err) => {
+            let rejected = NonProvable::new(
+                NonProvableReason::SCHEMA_INVALID,
+                format!("Request failed validation: {:#}", err),
+            );
+            commit(&store, mark_non_provable(receipt, rejected, &integrity)).await;
+            return;
+        }
+    };
+    // Pinned before any adapter call so every later state, including NON_PROVABLE, records which
+    // adapter build produced (or failed to produce) the evidence.
+    let capabilities = adapter.capabilities();
+    receipt.provenance.adapter_id = capabilities.adapter_id;
+    receipt.provenance.adapter_version = capabilities.adapter_version;
 
-    let ack = match adapter.acknowledge(&request).await {
+    let adapter_call = CostRecord {
+        adapter_calls: 1,
+        ..CostRecord::default()
+    };
+    let mut deadline = DeadlineBudget::new(request.deadline_ms);
+    // A cache hit answers both adapter calls, so neither is billed to the receipt. An order being
+    // placed is never answered from the cache.
+    let cached = match submit_order {
+        true => None,
+        false => adapter_cache.as_ref().and_then(|cache| cache.get(&request)),
+    };
+    if cached.is_none() {
+        store.record_cost(&receipt_id, &adapter_call).await;
+    }
+    let acknowledge = async {
+        match &cached {
+            Some(hit) => Ok(hit.ack.clone()),
+            None if submit_order => adapter.submit_order(&request).await,
+            None => adapter.acknowledge(&request).await,
+        }
+    };
+    let started = Instant::now();
+    let acknowledged = deadline.run("acknowledge", acknowledge).await;
+    receipt.timing.breakdown.ack_ms = Some(elapsed_ms(started));
+    let acknowledged = match acknowledged {
+        Ok(acknowledged) => acknowledged,
+        Err(miss) => {
+            commit(&store, deadline_exceeded(receipt, miss, &integrity)).await;
+            return;
+        }
+    };
+    let ack = match acknowledged {
         Ok(v) => v,
         Err(err) => {
+            circuits.record_failure(request.venue).await;
             let updated = mark_non_provable(
                 receipt,
-                NonProvableReason::SOURCE_UNAVAILABLE,
-                err.to_string(),
-                &signer,
-                &receipt_version,
+                NonProvable::new(
+                    NonProvableReason::SOURCE_UNAVAILABLE,
+                    scrub_error(&credentials, &err).await,
+                )
+                .with_failed_source(format!(
+                    "{:?} adapter {}",
+                    request.venue,
+                    if submit_order {
+                        SUBMIT_ORDER_STAGE
+                    } else {
+                        "acknowledge"
+                    }
+                )),
+                &integrity,
             );
-            store.lock().await.insert(receipt_id, updated);
+            commit(&store, updated).await;
             return;
         }
     };
+    // A venue that refuses an order the engine placed is still answering it, so the receipt
+    // proves the refusal instead of the placement.
+    if submit_order && !ack.accepted {
+        request.claim_type = ClaimType::ORDER_REJECTED;
+        receipt.claim.r#type = ClaimType::ORDER_REJECTED;
+        receipt.claim.claim_hash = integrity.commitments.request_claim_hash(&request);
+        let rejected = ProofRequest {
+            claim_type: ClaimType::ORDER_REJECTED,
+            ..sealed
+        };
+        store.record_request(&receipt_id, rejected).await;
+    }
 
-    let bundle = match adapter.collect_evidence(&request, &ack).await {
+    if cached.is_none() {
+        store.record_cost(&receipt_id, &adapter_call).await;
+    }
+    let collect = async {
+        match &cached {
+            Some(hit) => Ok(hit.bundle.clone()),
+            None => adapter.collect_evidence(&request, &ack).await,
+        }
+    };
+    let started = Instant::now();
+    let collected = deadline.run("collect_evidence", collect).await;
+    receipt.timing.breakdown.collect_ms = Some(elapsed_ms(started));
+    let collected = match collected {
+        Ok(collected) => collected,
+        Err(miss) => {
+            commit(&store, deadline_exceeded(receipt, miss, &integrity)).await;
+            return;
+        }
+    };
+    let mut bundle = match collected {
         Ok(v) => v,
         Err(err) => {
+            circuits.record_failure(request.venue).await;
             let updated = mark_non_provable(
                 receipt,
-                NonProvableReason::SOURCE_UNAVAILABLE,
-                err.to_string(),
-                &signer,
-                &receipt_version,
+                NonProvable::new(
+                    NonProvableReason::SOURCE_UNAVAILABLE,
+                    scrub_error(&credentials, &err).await,
+                )
+                .with_failed_source(format!("{:?} adapter collect_evidence", request.venue)),
+                &integrity,
+            );
+            commit(&store, updated).await;
+            return;
+        }
+    };
+
+    if cached.is_none() {
+        circuits.record_success(request.venue).await;
+        if let Some(cache) = &adapter_cache {
+            cache.put(&request, &ack, &bundle);
+        }
+    }
+    let policy_started = Instant::now();
+    let tolerance = policy_engine.oracle_tolerance();
+    if let Err(rejected) = attach_oracle_evidence(
+        &oracles,
+        &tolerance,
+        &request,
+        &ack.accepted_at,
+        &mut bundle,
+    )
+    .await
+    {
+        commit(
+            &store,
+            mark_non_provable(time_policy(receipt, policy_started), rejected, &integrity),
+        )
+        .await;
+        return;
+    }
+
+    let size_issues = check_evidence_size(&bundle, &evidence_limits);
+    if !size_issues.is_empty() {
+        let updated = mark_non_provable(
+            time_policy(receipt, policy_started),
+            NonProvable::new(
+                NonProvableReason::POLICY_VIOLATION,
+                format!(
+                    "Adapter evidence exceeds size limits: {}",
+                    size_issues.join("; ")
+                ),
+            )
+            .with_failed_source(format!("{:?} adapter collect_evidence", request.venue)),
+            &integrity,
+        );
+        commit(&store, updated).await;
+        return;
+    }
+
+    // Records for artifacts the adapter replaced or dropped describe fetches no longer in evidence.
+    let items = &bundle.items;
+    bundle.collection.retain(|record| {
+        items
+            .iter()
+            .any(|item| item.artifact_hash == record.artifact_hash)
+    });
+    bundle
+        .collection
+        .iter_mut()
+        .for_each(|record| collection_redaction.apply(record));
+
+    if let Some(name) = credential_leak(&credentials, &bundle).await {
+        let updated = mark_non_provable(
+            time_policy(receipt, policy_started),
+            NonProvable::new(
+                NonProvableReason::SCHEMA_INVALID,
+                format!(
+                    "Adapter evidence contains credential material ({}); evidence was discarded",
+                    name
+                ),
+            )
+            .with_failed_source(format!("{:?} adapter collect_evidence", request.venue)),
+            &integrity,
+        );
+        commit(&store, updated).await;
+        return;
+    }
+
+    let evidence_issues = validate_evidence(
+        &bundle,
+        &policy_engine.evidence_tag_vocabulary(),
+        &policy_engine.known_source_kinds(),
+    );
+    if !evidence_issues.is_empty() {
+        let updated = mark_non_provable(
+            time_policy(receipt, policy_started),
+            NonProvable::new(
+                NonProvableReason::SCHEMA_INVALID,
+                format!(
+                    "Adapter evidence failed validation: {}",
+                    evidence_issues.join("; ")
+                ),
+            )
+            .with_failed_source(format!("{:?} adapter collect_evidence", request.venue)),
+            &integrity,
+        );
+        commit(&store, updated).await;
+        return;
+    }
+    let tolerance = policy_engine.timestamp_tolerance(request.venue);
+    bundle.conflicts.extend(timestamp_conflicts(
+        &bundle,
+        &adapter.clock_offsets(),
+        &tolerance,
+    ));
+
+    if let Some(screening) = &screening {
+        let addresses = evidence_addresses(&bundle);
+        if !addresses.is_empty() {
+            let decision = screen_subjects(
+                screening.as_ref(),
+                &receipt_id,
+                ScreeningStage::Evidence,
+                addresses,
+            )
+            .await;
+            let rejection = screening_rejection(&decision);
+            store.record_screening(decision).await;
+            if let Some(rejected) = rejection {
+                commit(
+                    &store,
+                    mark_non_provable(time_policy(receipt, policy_started), rejected, &integrity),
+                )
+                .await;
+                return;
+            }
+        }
+    }
+
+    // Only bodies of artifacts the receipt commits to are kept.
+    if let Some(vault) = &evidence_vault {
+        let items = &bundle.items;
+        bundle.raw_artifacts.retain(|raw| {
+            items
+                .iter()
+                .any(|item| item.artifact_hash == raw.artifact_hash)
+        });
+        if let Err(err) = vault.put(&receipt_id, &bundle.raw_artifacts) {
+            let updated = mark_non_provable(
+                time_policy(receipt, policy_started),
+                NonProvable::new(
+                    NonProvableReason::SCHEMA_INVALID,
+                    format!("Adapter evidence failed validation: {}", err),
+                )
+                .with_failed_source(format!("{:?} adapter collect_evidence", request.venue)),
+                &integrity,
             );
-            store.lock().await.insert(receipt_id, updated);
+            commit(&store, updated).await;
             return;
         }
+    }
+
+    let mut receipt = time_policy(receipt, policy_started);
+    receipt.status = ReceiptStatus::AWAITING_FINALITY;
+    receipt.provenance.evidence_root = integrity.commitments.evidence_root(&bundle);
+    receipt.provenance.evidence_items = bundle.items.clone();
+    receipt.provenance.collection = bundle.collection.clone();
+    receipt.timing.updated_at = now_iso();
+    let Some(mut receipt) = commit(&store, receipt.finalize(&integrity)).await else {
+        return;
+    };
+
+    // The venue's finality rule sets the depth, and its timeout (or the request's) applies when
+    // tighter than the engine's.
+    let rule = policy_engine.finality_rule(request.venue);
+    let depth = match (rule, adapter.capabilities().finality) {
+        (Some(FinalityRule::Confirmations { depth, .. }), _)
+        | (_, FinalitySemantics::Confirmations { depth }) => Some(depth),
+        _ => None,
+    };
+    if let (Some(wait), Some(depth)) = (&finality, depth) {
+        if wait.observer.tracks(request.venue) {
+            let timeout =
+                finality_timeout(rule, &request).map_or(wait.timeout, |t| t.min(wait.timeout));
+            let started = Instant::now();
+            let waited = deadline
+                .run(
+                    "finality",
+                    tokio::time::timeout(
+                        timeout,
+                        wait.observer
+                            .await_finality(request.venue, bundle.inclusion_height, depth),
+                    ),
+                )
+                .await;
+            receipt.timing.breakdown.finality_ms = Some(elapsed_ms(started));
+            let observed = match waited {
+                Ok(observed) => observed,
+                Err(miss) => {
+                    commit(&store, deadline_exceeded(receipt, miss, &integrity)).await;
+                    return;
+                }
+            };
+            let failure = match observed {
+                Ok(Ok(event)) => {
+                    bundle.confirmations = Some(match bundle.inclusion_height {
+                        Some(height) => event.final_height.saturating_sub(height) + 1,
+                        None => u64::from(depth),
+                    });
+                    // On venues with commitment levels the observer follows the finalized head.
+                    if bundle.commitment.is_some() {
+                        bundle.commitment = Some(ChainCommitment::Finalized);
+                    }
+                    bundle.finality_observed_at = Some(event.observed_at);
+                    None
+                }
+                Ok(Err(err)) => Some(
+                    NonProvable::new(NonProvableReason::SOURCE_UNAVAILABLE, err.to_string())
+                        .with_failed_source(format!("{:?} finality observer", request.venue)),
+                ),
+                Err(_) => Some(NonProvable::new(
+                    NonProvableReason::FINALITY_TIMEOUT,
+                    format!(
+                        "Finality depth {} not reached on {:?} within {} ms.",
+                        depth,
+                        request.venue,
+                        timeout.as_millis()
+                    ),
+                )),
+            };
+            if let Some(non_provable) = failure {
+                commit(&store, mark_non_provable(receipt, non_provable, &integrity)).await;
+                return;
+            }
+        }
+    }
+
+    // With re-checks on, a gated claim the evidence does not yet show final waits for the venue to
+    // report it, until the finality deadline, rather than failing evaluation now.
+    let recheck = match (
+        finality_recheck,
+        policy_engine.finality_gate(request.venue, request.claim_type),
+    ) {
+        (Some(interval), Some(rule)) if rule.shortfall(&bundle).is_some() => {
+            finality_timeout(Some(rule), &request).map(|timeout| (interval, rule, timeout))
+        }
+        _ => None,
     };
+    if let Some((interval, rule, timeout)) = recheck {
+        let finality_deadline =
+            (chrono::Utc::now() + timeout).to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        receipt.timing.finality_deadline = Some(finality_deadline.clone());
+        receipt.timing.updated_at = now_iso();
+        receipt = match commit(&store, receipt.finalize(&integrity)).await {
+            Some(receipt) => receipt,
+            None => return,
+        };
+        let started = Instant::now();
+        let rechecks = recheck_finality(
+            &store,
+            &receipt_id,
+            adapter.as_ref(),
+            &request,
+            &ack,
+            rule,
+            &mut bundle,
+            interval,
+        );
+        let rechecked = deadline
+            .run("finality", tokio::time::timeout(timeout, rechecks))
+            .await;
+        let finality_ms = receipt.timing.breakdown.finality_ms.unwrap_or(0) + elapsed_ms(started);
+        receipt.timing.breakdown.finality_ms = Some(finality_ms);
+        match rechecked {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => {
+                let non_provable = NonProvable::new(
+                    NonProvableReason::FINALITY_TIMEOUT,
+                    format!(
+                        "{} on {:?} was not final by its finality deadline {}: {}.",
+                        request.claim_type.name(),
+                        request.venue,
+                        finality_deadline,
+                        rule.shortfall(&bundle).unwrap_or_default()
+                    ),
+                );
+                commit(&store, mark_non_provable(receipt, non_provable, &integrity)).await;
+                return;
+            }
+            Err(miss) => {
+                commit(&store, deadline_exceeded(receipt, miss, &integrity)).await;
+                return;
+            }
+        }
+    }
 
-    let decision = policy_engine.evaluate(request.venue, request.claim_type, &bundle);
+    let policy_started = Instant::now();
+    let decision = claim_handlers.evaluate(&policy_engine, &request, &bundle);
     if !decision.ok {
         let updated = mark_non_provable(
-            receipt,
-            decision.reason.unwrap_or(NonProvableReason::POLICY_VIOLATION),
-            decision.details,
-            &signer,
-            &receipt_version,
+            time_policy(receipt, policy_started),
+            decision.into_non_provable(),
+            &integrity,
         );
-        store.lock().await.insert(receipt_id, updated);
+        commit(&store, updated).await;
         return;
     }
 
-    let statement = match adapter.build_statement(&request, &ack, &bundle).await {
+    let statement = match claim_handlers
+        .build_statement(adapter.as_ref(), &request, &ack, &bundle)
+        .await
+    {
         Ok(v) => v,
         Err(err) => {
             let updated = mark_non_provable(
-                receipt,
-                NonProvableReason::POLICY_VIOLATION,
-                err.to_string(),
-                &signer,
-                &receipt_version,
+                time_policy(receipt, policy_started),
+                NonProvable::new(NonProvableReason::POLICY_VIOLATION, err.to_string()),
+                &integrity,
             );
-            store.lock().await.insert(receipt_id, updated);
+            commit(&store, updated).await;
             return;
         }
     };
 
-    let claim_hash = hash_json(&serde_json::json!({
-        "claim_type": request.claim_type,
-        "statement": statement,
-        "order_ref": request.order_ref,
-        "execution_ref": request.execution_ref
-    }));
-    let venue_str = match request.venue {
-        Venue::Hyperliquid => "hyperliquid",
-        Venue::Base => "base",
-        Venue::Solana => "solana",
-        Venue::Polymarket => "polymarket",
-    };
-    let claim_type_str = match request.claim_type {
-        ClaimType::ORDER_PLACED => "ORDER_PLACED",
-        ClaimType::TRADE_EXECUTED => "TRADE_EXECUTED",
+    let claim_hash =
+        integrity
+            .commitments
+            .claim_hash(&request, &statement, &receipt.claim.encrypted_fields);
+    let localized =
+        match render_localized_statements(&statement_locales, &claim_hash, &request, &ack, &bundle)
+        {
+            Ok(v) => v,
+            Err(err) => {
+                let updated = mark_non_provable(
+                    time_policy(receipt, policy_started),
+                    NonProvable::new(NonProvableReason::POLICY_VIOLATION, err.to_string()),
+                    &integrity,
+                );
+                commit(&store, updated).await;
+                return;
+            }
+        };
+    receipt.claim.public_values = match claim_handlers.public_values(&request, &bundle) {
+        Ok(values) => values,
+        Err(err) => {
+            let updated = mark_non_provable(
+                time_policy(receipt, policy_started),
+                NonProvable::new(NonProvableReason::POLICY_VIOLATION, err.to_string()),
+                &integrity,
+            );
+            commit(&store, updated).await;
+            return;
+        }
     };
-    let public_inputs = serde_json::json!({
-        "claim_hash": claim_hash,
-        "evidence_root": bundle.evidence_root(),
-        "venue": venue_str,
-        "claim_type": claim_type_str
-    });
+    let public_inputs = claim_handlers.public_inputs(
+        &TruthClaim {
+            claim_hash: claim_hash.clone(),
+            ..receipt.claim.clone()
+        },
+        &integrity.commitments.evidence_root(&bundle),
+        request.venue,
+    );
 
-    let proof = match prover.prove(&public_inputs).await {
-        Ok(v) => v,
+    let witness = ProofWitness {
+        hash_algorithm: integrity.commitments.hash_algorithm,
+        claim_preimage: integrity.commitments.claim_preimage(
+            &request,
+            &statement,
+            &receipt.claim.encrypted_fields,
+        ),
+        evidence_preimage: integrity.commitments.evidence_preimage(&bundle),
+    };
+    // A leader that lost its lease leaves the receipt as it is; the new leader resumes it.
+    if leadership
+        .as_ref()
+        .is_some_and(|election| !election.is_leader())
+    {
+        return;
+    }
+    let mut receipt = time_policy(receipt, policy_started);
+    // Anchoring requests still get a proof; attestation-only is for claims that stay offchain.
+    let attested = policy_engine.is_attestation_only(request.venue, request.claim_type)
+        && !request.verification_mode.requires_anchoring();
+    let proved = if attested {
+        Ok((
+            attestation_metadata(&public_inputs, &integrity.signer),
+            ProverUsage::default(),
+        ))
+    } else {
+        let started = Instant::now();
+        let proved = deadline
+            .run(
+                "prove",
+                proof_queue.run(prover.prove_with_witness(&public_inputs, &witness)),
+            )
+            .await;
+        receipt.timing.breakdown.prove_ms = Some(elapsed_ms(started));
+        match proved {
+            Ok(proved) => proved,
+            Err(miss) => {
+                commit(&store, deadline_exceeded(receipt, miss, &integrity)).await;
+                return;
+            }
+        }
+    };
+    let proof = match proved {
+        Ok((proof, usage)) => {
+            let proving = CostRecord {
+                prover_cycles: usage.cycles,
+                prover_fee_usd: usage.fee_usd,
+                ..CostRecord::default()
+            };
+            store.record_cost(&receipt_id, &proving).await;
+            proof
+        }
         Err(err) => {
             let updated = mark_non_provable(
                 receipt,
-                NonProvableReason::PROOF_FAILURE,
-                err.to_string(),
-                &signer,
-                &receipt_version,
+                NonProvable::new(NonProvableReason::PROOF_FAILURE, err.to_string()),
+                &integrity,
             );
-            store.lock().await.insert(receipt_id, updated);
+            commit(&store, updated).await;
             return;
         }
     };
 
-    let proved = build_proved_receipt(receipt, claim_hash, statement, bundle, proof, &signer, &receipt_version);
-    let verified = verifier.verify(&proved).await;
-    let final_receipt = if verified {
-        proved
-    } else {
-        mark_non_provable(
+    let mut proved = build_proved_receipt(
+        receipt, claim_hash, statement, localized, bundle, proof, &integrity,
+    );
+    let started = Instant::now();
+    let verified = verifier
+        .verify_with_handlers(&proved, &claim_handlers)
+        .await;
+    proved.timing.breakdown.verify_ms = Some(elapsed_ms(started));
+    if !verified {
+        let updated = mark_non_provable(
             proved,
-            NonProvableReason::PROOF_FAILURE,
-            "Offchain verification failed for produced proof metadata.".to_string(),
-            &signer,
-            &receipt_version,
-        )
+            NonProvable::new(
+                NonProvableReason::PROOF_FAILURE,
+                "Offchain verification failed for produced proof metadata.",
+            ),
+            &integrity,
+        );
+        commit(&store, updated).await;
+        return;
+    }
+
+    let mode = request.verification_mode;
+    let final_receipt = match (&anchoring, mode.requires_anchoring()) {
+        (Some(anchoring), true) => {
+            let started = Instant::now();
+            let anchored = deadline
+                .run(
+                    "anchor",
+                    anchor_proved_receipt(&store, anchoring, proved.clone(), mode),
+                )
+                .await;
+            let anchor_ms = Some(elapsed_ms(started));
+            proved.timing.breakdown.anchor_ms = anchor_ms;
+            match anchored {
+                Ok(Ok(mut anchored)) => {
+                    anchored.timing.breakdown.anchor_ms = anchor_ms;
+                    anchored.finalize(&integrity)
+                }
+                Ok(Err(rejected)) => mark_non_provable(proved, rejected, &integrity),
+                Err(miss) => deadline_exceeded(proved, miss, &integrity),
+            }
+        }
+        _ => proved.finalize(&integrity),
+    };
+    commit(&store, final_receipt).await;
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+// The tightest of the venue rule's timeout and the request's own.
+fn finality_timeout(rule: Option<FinalityRule>, request: &ProofRequest) -> Option<Duration> {
+    let requested = request.finality_timeout_ms.map(Duration::from_millis);
+    match (rule.and_then(|rule| rule.timeout()), requested) {
+        (Some(rule), Some(requested)) => Some(rule.min(requested)),
+        (rule, requested) => rule.or(requested),
+    }
+}
+
+// Collects from the adapter every `interval` until its evidence meets `rule`, carrying each fresh
+// collection's finality signals into `bundle`; the evidence items stay as first collected. A
+// failed collection is retried like a shortfall. Runs until the caller's timeout drops it.
+#[allow(clippy::too_many_arguments)]
+async fn recheck_finality(
+    store: &ReceiptStore,
+    receipt_id: &str,
+    adapter: &dyn VenueAdapter,
+    request: &ProofRequest,
+    ack: &ExecutionAck,
+    rule: FinalityRule,
+    bundle: &mut EvidenceBundle,
+    interval: Duration,
+) {
+    while rule.shortfall(bundle).is_some() {
+        tokio::time::sleep(interval).await;
+        let adapter_call = CostRecord {
+            adapter_calls: 1,
+            ..CostRecord::default()
+        };
+        store.record_cost(receipt_id, &adapter_call).await;
+        if let Ok(fresh) = adapter.collect_evidence(request, ack).await {
+            bundle.confirmations = fresh.confirmations;
+            bundle.commitment = fresh.commitment;
+            bundle.finality_observed_at = fresh.finality_observed_at;
+        }
+    }
+}
+
+// The policy stage runs in two parts around the finality wait, so each part adds to it.
+fn time_policy(mut receipt: ZKReceipt, started: Instant) -> ZKReceipt {
+    let spent = receipt.timing.breakdown.policy_ms.unwrap_or(0) + elapsed_ms(started);
+    receipt.timing.breakdown.policy_ms = Some(spent);
+    receipt
+}
+
+fn deadline_exceeded(
+    mut receipt: ZKReceipt,
+    miss: DeadlineMiss,
+    integrity: &IntegrityContext,
+) -> FinalizedReceipt {
+    let spent = miss
+        .spent
+        .iter()
+        .map(|s| format!("{} {} ms", s.stage, s.elapsed_ms))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let details = format!(
+        "Deadline of {} ms ran out during {} ({}).",
+        miss.budget_ms, miss.stage, spent
+    );
+    receipt.timing.deadline_miss = Some(miss);
+    mark_non_provable(
+        receipt,
+        NonProvable::new(NonProvableReason::DEADLINE_EXCEEDED, details),
+        integrity,
+    )
+}
+
+// Task writes are conditional on the revision the task last read. On a conflict the task re-reads
+// the receipt, other engines' writes included: a settled copy, e.g. a cancellation, stands and the
+// task's result is dropped, but a settling write is retried on top of a copy still in flight, since
+// the task would otherwise leave the receipt unsettled. Dropped writes are kept for health.
+async fn commit(store: &ReceiptStore, mut receipt: FinalizedReceipt) -> Option<ZKReceipt> {
+    let mut attempts = 0;
+    loop {
+        let err = match persist(store, receipt.clone()).await {
+            Ok(stored) => return Some(stored),
+            Err(err) => err,
+        };
+        attempts += 1;
+        let fenced = err.downcast_ref::<WriteFenced>().is_some();
+        if !fenced {
+            store.refresh().await.ok();
+        }
+        let stored = store.get(&receipt.0.receipt_id).await;
+        match stored {
+            Some(stored)
+                if !fenced
+                    && attempts < COMMIT_ATTEMPTS
+                    && receipt.0.status.is_terminal()
+                    && !stored.status.is_terminal()
+                    && stored.revision != receipt.0.revision =>
+            {
+                // The revision is outside the receipt hash, so the receipt stays finalized.
+                receipt.0.revision = stored.revision;
+            }
+            _ => {
+                store.record_dropped_write(&receipt.0.receipt_id, &err);
+                return None;
+            }
+        }
+    }
+}
+
+// The only writes engine code makes to the store, so nothing reaches it without being finalized.
+async fn persist_new(store: &ReceiptStore, receipt: FinalizedReceipt) -> Result<()> {
+    store.create(receipt.0).await
+}
+
+async fn persist(store: &ReceiptStore, receipt: FinalizedReceipt) -> Result<ZKReceipt> {
+    store.compare_and_swap(receipt.0).await
+}
+
+// The anchored leaf is the PROVED receipt hash, which issuance leaves unchanged, so the inclusion
+// path stays valid once the receipt is sequenced and signed.
+async fn anchor_proved_receipt(
+    store: &ReceiptStore,
+    anchoring: &AnchorPipeline,
+    proved: ZKReceipt,
+    mode: VerificationMode,
+) -> std::result::Result<ZKReceipt, NonProvable> {
+    let unavailable = |detail: String| {
+        NonProvable::new(NonProvableReason::SOURCE_UNAVAILABLE, detail)
+            .with_failed_source("anchor publisher")
+    };
+    let (batch, anchored) = anchor_batch(
+        store,
+        anchoring.publisher.as_ref(),
+        &anchoring.fees,
+        vec![proved],
+    )
+    .await
+    .map_err(|err| unavailable(format!("Anchoring failed: {}", err)))?;
+    let Some(mut anchored) = anchored.into_iter().next() else {
+        return Err(unavailable(format!(
+            "Anchor batch was {:?}; the receipt could not be anchored within the fee policy",
+            batch.status
+        )));
+    };
+    anchored.proof.verification_mode = mode;
+    Ok(anchored)
+}
+
+// Quotes every configured oracle for the claim's `oracle_feed` at `at`, records each as evidence,
+// and rejects the claim when the venue-reported price falls outside the oracle tolerance.
+async fn attach_oracle_evidence(
+    oracles: &[Arc<dyn PriceOracle>],
+    tolerance: &OracleTolerance,
+    request: &ProofRequest,
+    at: &str,
+    bundle: &mut crate::models::EvidenceBundle,
+) -> std::result::Result<(), NonProvable> {
+    let Some(feed) = request.payload.get("oracle_feed").and_then(|v| v.as_str()) else {
+        return Ok(());
+    };
+    if oracles.is_empty() {
+        return Ok(());
+    }
+    let Some(venue_price) = venue_reported_price(request, bundle) else {
+        return Err(NonProvable::new(
+            NonProvableReason::EVIDENCE_MISSING,
+            format!(
+                "Claim names oracle feed {} but carries no venue-reported price to cross-check.",
+                feed
+            ),
+        ));
+    };
+    let mut divergence = None;
+    for oracle in oracles {
+        let quote = match oracle.quote_at(feed, at).await {
+            Ok(quote) => check_quote(&quote, oracle.as_ref(), feed, at, tolerance).map(|_| quote),
+            Err(err) => Err(err),
+        };
+        let quote = quote.map_err(|err| {
+            NonProvable::new(
+                NonProvableReason::SOURCE_UNAVAILABLE,
+                format!("Price oracle unavailable: {}", err),
+            )
+            .with_failed_source(format!("{} price oracle", oracle.kind().slug()))
+        })?;
+        bundle.items.push(quote_evidence(&quote));
+        bundle.observed_tags.insert(ORACLE_PRICE_TAG.to_string());
+        divergence = divergence.or_else(|| cross_check(venue_price, &quote, tolerance));
+    }
+    match divergence {
+        Some(details) => Err(NonProvable::new(
+            NonProvableReason::POLICY_VIOLATION,
+            details,
+        )),
+        None => Ok(()),
+    }
+}
+
+// Body fields that record when an artifact was fetched rather than what it says.
+const OBSERVATION_TIME_FIELDS: &[&str] = &["accepted_at", "observed_at"];
+
+fn diff_evidence(
+    original: &[EvidenceItem],
+    fresh: &[EvidenceItem],
+    fresh_bodies: &[RawArtifact],
+) -> Vec<String> {
+    let mut divergences = Vec::new();
+    for item in original {
+        match fresh.iter().find(|f| f.artifact_ref == item.artifact_ref) {
+            None => divergences.push(format!(
+                "artifact {} is no longer reported",
+                item.artifact_ref
+            )),
+            Some(f)
+                if f.artifact_hash != item.artifact_hash
+                    && !restamped_matches(item, f, fresh_bodies) =>
+            {
+                divergences.push(format!(
+                    "artifact {} changed hash from {} to {}",
+                    item.artifact_ref, item.artifact_hash, f.artifact_hash
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    for item in fresh {
+        if !original.iter().any(|o| o.artifact_ref == item.artifact_ref) {
+            divergences.push(format!("artifact {} is newly reported", item.artifact_ref));
+        }
+    }
+    divergences
+}
+
+// A re-fetched body differs from the original by its observation times alone if putting the
+// original time back reproduces the original hash.
+fn restamped_matches(
+    original: &EvidenceItem,
+    fresh: &EvidenceItem,
+    fresh_bodies: &[RawArtifact],
+) -> bool {
+    let Some(raw) = fresh_bodies
+        .iter()
+        .find(|raw| raw.artifact_hash == fresh.artifact_hash)
+    else {
+        return false;
     };
-    store.lock().await.insert(receipt_id, final_receipt);
+    let Ok(mut body) = serde_json::from_slice::<serde_json::Value>(&raw.bytes) else {
+        return false;
+    };
+    restamp(&mut body, &original.observed_at);
+    let Ok(bytes) = serde_json::to_vec(&body) else {
+        return false;
+    };
+    original.hash_algorithm.hash_bytes(&bytes) == original.artifact_hash
+}
+
+fn restamp(value: &mut serde_json::Value, observed_at: &str) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if OBSERVATION_TIME_FIELDS.contains(&key.as_str()) && field.is_string() {
+                    *field = serde_json::Value::String(observed_at.to_string());
+                } else {
+                    restamp(field, observed_at);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(|item| restamp(item, observed_at))
+        }
+        _ => {}
+    }
 }
 
 fn build_proved_receipt(
     mut receipt: ZKReceipt,
     claim_hash: String,
     statement: String,
+    localized: Vec<LocalizedStatement>,
     bundle: crate::models::EvidenceBundle,
     proof: ProofMetadata,
-    signer: &str,
-    receipt_version: &str,
+    integrity: &IntegrityContext,
 ) -> ZKReceipt {
     receipt.status = ReceiptStatus::PROVED;
     receipt.claim.statement = statement;
-    receipt.claim.claim_hash = claim_hash.clone();
-    receipt.provenance = Provenance {
-        evidence_root: bundle.evidence_root(),
-        evidence_items: bundle.items,
-    };
+    receipt.claim.localized_statements = localized;
+    receipt.claim.claim_hash = claim_hash;
+    receipt.provenance.evidence_root = integrity.commitments.evidence_root(&bundle);
+    receipt.provenance.evidence_items = bundle.items;
+    receipt.provenance.collection = bundle.collection;
     let now = now_iso();
     receipt.timing.updated_at = now.clone();
     receipt.timing.execution_observed_at = Some(now);
     receipt.timing.finality_observed_at = bundle.finality_observed_at;
-    receipt.proof = proof.clone();
-    receipt.integrity = build_integrity(
-        signer,
-        receipt_version,
-        ReceiptStatus::PROVED,
-        &claim_hash,
-        &receipt.provenance.evidence_root,
-        &proof.public_inputs_hash,
-    );
+    receipt.proof = proof;
     receipt.non_provable = None;
+    // Anchoring commits to the receipt hash before the receipt is finalized.
+    receipt.refresh_hashes(integrity);
     receipt
 }
 
 fn mark_non_provable(
-    mut receipt: ZKReceipt,
-    reason: NonProvableReason,
-    details: String,
-    signer: &str,
-    receipt_version: &str,
-) -> ZKReceipt {
-    let proof = no_proof_metadata();
+    receipt: ZKReceipt,
+    non_provable: NonProvable,
+    integrity: &IntegrityContext,
+) -> FinalizedReceipt {
+    unissued_non_provable(receipt, non_provable).finalize(integrity)
+}
+
+fn unissued_non_provable(mut receipt: ZKReceipt, non_provable: NonProvable) -> ZKReceipt {
     receipt.status = ReceiptStatus::NON_PROVABLE;
-    receipt.non_provable = Some(NonProvable { reason_code: reason, details });
+    receipt.non_provable = Some(non_provable);
+    receipt.timing.updated_at = now_iso();
+    receipt.proof = no_proof_metadata();
+    receipt
+}
+
+fn invalidate_receipt(receipt: ZKReceipt, non_provable: NonProvable) -> ZKReceipt {
+    let mut invalidated = unissued_non_provable(receipt, non_provable);
+    invalidated.status = ReceiptStatus::INVALIDATED;
+    invalidated
+}
+
+fn revoke_receipt(
+    mut receipt: ZKReceipt,
+    invalidation: Invalidation,
+    integrity: &IntegrityContext,
+) -> FinalizedReceipt {
+    receipt.status = ReceiptStatus::INVALIDATED;
+    receipt.non_provable = None;
+    receipt.timing.updated_at = invalidation.invalidated_at.clone();
+    receipt.invalidation = Some(invalidation);
+    receipt.proof = no_proof_metadata();
+    receipt.finalize(integrity)
+}
+
+fn cancel_receipt(mut receipt: ZKReceipt, integrity: &IntegrityContext) -> FinalizedReceipt {
+    receipt.status = ReceiptStatus::CANCELLED;
+    receipt.non_provable = None;
     receipt.timing.updated_at = now_iso();
-    receipt.proof = proof.clone();
-    receipt.integrity = build_integrity(
-        signer,
-        receipt_version,
-        ReceiptStatus::NON_PROVABLE,
+    receipt.proof = no_proof_metadata();
+    receipt.finalize(integrity)
+}
+
+// A receipt whose integrity block was derived from its current contents. Only
+// `ZKReceipt::finalize` makes one, and `persist` and `persist_new` take nothing else.
+#[derive(Clone)]
+struct FinalizedReceipt(ZKReceipt);
+
+impl ZKReceipt {
+    // Recomputes every derived integrity field from the receipt's own status, claim hash, evidence
+    // root and proof, then signs, so no mutation can be persisted with a stale hash or signature.
+    // Terminal receipts take the signer's next issuance sequence and the signature covers it; one
+    // already issued keeps its sequence while neither its receipt hash nor its signer changes.
+    fn finalize(mut self, context: &IntegrityContext) -> FinalizedReceipt {
+        let previous = std::mem::replace(&mut self.integrity, unsigned_integrity(context));
+        self.refresh_hashes(context);
+        if self.status.is_terminal() {
+            let unchanged = previous.signer == self.integrity.signer
+                && previous.receipt_hash == self.integrity.receipt_hash;
+            match previous.issued_at {
+                Some(issued_at) if unchanged => {
+                    self.integrity.sequence = previous.sequence;
+                    self.integrity.issued_at = Some(issued_at);
+                }
+                _ => {
+                    let entry = context.ledger.issue(
+                        &self.integrity.signer,
+                        &self.receipt_id,
+                        &self.integrity.receipt_hash,
+                        self.status,
+                    );
+                    self.integrity.sequence = entry.sequence;
+                    self.integrity.issued_at = Some(entry.issued_at);
+                }
+            }
+        }
+        self.integrity.signature = sign_integrity(context, &self.integrity);
+        FinalizedReceipt(self)
+    }
+
+    // A changed hash voids any earlier issuance, so `finalize` issues the receipt again.
+    fn refresh_hashes(&mut self, context: &IntegrityContext) {
+        let receipt_hash = context.commitments.receipt_hash(
+            self.status,
+            &self.claim.claim_hash,
+            &self.provenance.evidence_root,
+            &self.proof.public_inputs_hash,
+        );
+        if receipt_hash != self.integrity.receipt_hash {
+            self.integrity.sequence = 0;
+            self.integrity.issued_at = None;
+        }
+        self.integrity.schema_hash = context.commitments.schema_hash(&context.receipt_version);
+        self.integrity.receipt_hash = receipt_hash;
+    }
+}
+
+// Issued (terminal) receipts are signed over their sequence number too; interim ones are not.
+fn sign_integrity(context: &IntegrityContext, integrity: &Integrity) -> String {
+    context
+        .receipt_signer
+        .sign(&receipt_signing_message(integrity))
+}
+
+// Batch gas and fees are split evenly across leaves; the first leaves absorb any remainder.
+fn batch_share(total: u128, batch_size: usize, leaf_index: usize) -> u128 {
+    let batch_size = batch_size as u128;
+    total / batch_size + u128::from((leaf_index as u128) < total % batch_size)
+}
+
+fn screening_rejection(decision: &ScreeningDecision) -> Option<NonProvable> {
+    match decision.outcome {
+        ScreeningOutcome::CLEAR => None,
+        ScreeningOutcome::BLOCKED => Some(NonProvable::new(
+            NonProvableReason::COMPLIANCE_BLOCKED,
+            format!(
+                "{} of {} screened subject(s) matched {} list {}; screening decision {}",
+                decision.hits.len(),
+                decision.subjects.len(),
+                decision.provider,
+                decision.list_version.as_deref().unwrap_or("unknown"),
+                decision.decision_hash
+            ),
+        )),
+        // Screening fails closed: no receipt is issued for a subject that could not be checked.
+        ScreeningOutcome::UNAVAILABLE => Some(
+            NonProvable::new(
+                NonProvableReason::SOURCE_UNAVAILABLE,
+                format!(
+                    "Screening provider {} unavailable: {}; screening decision {}",
+                    decision.provider,
+                    decision.detail.as_deref().unwrap_or("no detail"),
+                    decision.decision_hash
+                ),
+            )
+            .with_failed_source(format!("{} screening provider", decision.provider)),
+        ),
+    }
+}
+
+async fn scrub_error(credentials: &Option<CredentialStore>, err: &anyhow::Error) -> String {
+    match credentials {
+        Some(credentials) => credentials.redact(&err.to_string()).await,
+        None => err.to_string(),
+    }
+}
+
+async fn credential_leak(
+    credentials: &Option<CredentialStore>,
+    bundle: &crate::models::EvidenceBundle,
+) -> Option<String> {
+    let persisted = (
+        &bundle.items,
+        &bundle.conflicts,
+        &bundle.price_observation,
+        &bundle.correction,
+        &bundle.cancellation,
+        &bundle.position_closure,
+        &bundle.balance_snapshot,
+        &bundle.collection,
+    );
+    credentials.as_ref()?.find_leak(&persisted).await
+}
+
+pub(crate) fn recompute_schema_hash(receipt: &ZKReceipt) -> String {
+    CommitmentScheme::new(
+        receipt.integrity.commitment_mode,
+        receipt.integrity.hash_algorithm,
+    )
+    .with_domain_version(receipt.integrity.hash_domain_version)
+    .schema_hash(&receipt.version)
+}
+
+pub(crate) fn recompute_receipt_hash(receipt: &ZKReceipt) -> String {
+    CommitmentScheme::new(
+        receipt.integrity.commitment_mode,
+        receipt.integrity.hash_algorithm,
+    )
+    .with_domain_version(receipt.integrity.hash_domain_version)
+    .receipt_hash(
+        receipt.status,
         &receipt.claim.claim_hash,
         &receipt.provenance.evidence_root,
-        &proof.public_inputs_hash,
-    );
-    receipt
+        &receipt.proof.public_inputs_hash,
+    )
+}
+
+// Anchored receipts are returned rather than stored so the task pipeline can anchor a receipt
+// before issuing it.
+async fn anchor_batch(
+    store: &ReceiptStore,
+    publisher: &dyn AnchorPublisher,
+    fees: &AnchorFeePolicy,
+    receipts: Vec<ZKReceipt>,
+) -> Result<(AnchorBatch, Vec<ZKReceipt>)> {
+    let receipt_hashes: Vec<String> = receipts
+        .iter()
+        .map(|r| r.integrity.receipt_hash.clone())
+        .collect();
+    let (root, paths) = merkle_tree(&receipt_hashes)?;
+    let published = publish_with_fee_policy(publisher, &root, receipts.len(), fees).await?;
+    let Some(publication) = published.publication else {
+        let batch = AnchorBatch {
+            status: published.status,
+            root,
+            anchor_tx_ref: None,
+            gas_used: 0,
+            fee_wei: 0,
+            user_op_hash: None,
+            sponsored: false,
+            fee_decisions: published.decisions,
+            inclusions: vec![],
+        };
+        return Ok((batch, vec![]));
+    };
+    let batch_size = receipts.len();
+
+    let mut inclusions = Vec::with_capacity(receipts.len());
+    let mut anchored = Vec::with_capacity(receipts.len());
+    for (leaf_index, (mut receipt, merkle_path)) in receipts.into_iter().zip(paths).enumerate() {
+        let share = CostRecord {
+            anchoring_gas: batch_share(u128::from(publication.gas_used), batch_size, leaf_index)
+                as u64,
+            anchoring_fee_wei: batch_share(publication.fee_wei, batch_size, leaf_index),
+            ..CostRecord::default()
+        };
+        store.record_cost(&receipt.receipt_id, &share).await;
+        let inclusion = AnchorInclusion {
+            anchored_root: root.clone(),
+            anchor_tx_ref: publication.tx_ref.clone(),
+            leaf: receipt_leaf(&receipt.integrity.receipt_hash),
+            leaf_index,
+            merkle_path,
+        };
+        receipt.proof.verification_mode = VerificationMode::OFFCHAIN_AND_ANCHORED;
+        receipt.proof.anchored_root_ref = Some(root.clone());
+        receipt.proof.anchor_inclusion = Some(inclusion.clone());
+        receipt.timing.updated_at = now_iso();
+        anchored.push(receipt);
+        inclusions.push(inclusion);
+    }
+    let batch = AnchorBatch {
+        status: published.status,
+        root,
+        anchor_tx_ref: Some(publication.tx_ref),
+        gas_used: publication.gas_used,
+        fee_wei: publication.fee_wei,
+        user_op_hash: publication.user_op_hash,
+        sponsored: publication.sponsored,
+        fee_decisions: published.decisions,
+        inclusions,
+    };
+    Ok((batch, anchored))
 }
 
-fn build_integrity(
-    signer: &str,
-    receipt_version: &str,
-    status: ReceiptStatus,
-    claim_hash: &str,
-    evidence_root: &str,
-    proof_hash: &str,
-) -> Integrity {
-    let schema_hash = hash_json(&serde_json::json!({
-        "schema": "zkreceipt.schema.json",
-        "version": receipt_version
-    }));
-    let receipt_hash = hash_json(&serde_json::json!({
-        "status": status,
-        "claim_hash": claim_hash,
-        "evidence_root": evidence_root,
-        "proof_hash": proof_hash
-    }));
-    let signature = hash_json(&serde_json::json!({
-        "signer": signer,
-        "receipt_hash": receipt_hash
-    }));
+// The context's scheme and signer with nothing derived yet; `finalize` fills in the rest.
+fn unsigned_integrity(context: &IntegrityContext) -> Integrity {
     Integrity {
-        schema_hash,
-        receipt_hash,
-        signer: signer.to_string(),
-        signature,
+        hash_algorithm: context.commitments.effective_hash_algorithm(),
+        commitment_mode: context.commitments.mode,
+        hash_domain_version: context.commitments.domain_version,
+        schema_hash: String::new(),
+        receipt_hash: String::new(),
+        signer: context.signer.clone(),
+        signature: String::new(),
+        sequence: 0,
+        issued_at: None,
     }
 }
@@ -0,0 +1,87 @@
+use crate::models::{now_iso, ReceiptEscrow, ZKReceipt};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+
+// Stands in for the refs a withheld receipt leaves out.
+pub const WITHHELD: &str = "WITHHELD";
+
+// Escrow for a receipt created at `now` and held for `hold_ms`.
+pub fn escrow_for(hold_ms: u64, now: &str) -> ReceiptEscrow {
+    let since =
+        DateTime::parse_from_rfc3339(now).map_or_else(|_| Utc::now(), |at| at.with_timezone(&Utc));
+    let release_after =
+        chrono::TimeDelta::try_milliseconds(i64::try_from(hold_ms).unwrap_or(i64::MAX))
+            .and_then(|hold| since.checked_add_signed(hold))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    ReceiptEscrow {
+        release_after: release_after.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        released_at: None,
+        released_by: None,
+    }
+}
+
+pub fn is_withheld(receipt: &ZKReceipt) -> bool {
+    receipt
+        .escrow
+        .as_ref()
+        .is_some_and(|escrow| escrow.released_at.is_none())
+}
+
+// The receipt as everyone but the operator sees it. While escrow holds it, that is the commitment
+// alone: status, claim and evidence hashes, proof and anchor, and the integrity block, which still
+// verifies because none of the withheld fields are in the receipt hash.
+pub fn published(mut receipt: ZKReceipt) -> ZKReceipt {
+    let Some(escrow) = receipt
+        .escrow
+        .as_ref()
+        .filter(|escrow| escrow.released_at.is_none())
+    else {
+        return receipt;
+    };
+    receipt.claim.statement = format!(
+        "{}: held in escrow until {}",
+        WITHHELD, escrow.release_after
+    );
+    receipt.claim.localized_statements.clear();
+    receipt.claim.encrypted_fields.clear();
+    receipt.subject.account_ref = WITHHELD.to_string();
+    receipt.subject.order_ref = WITHHELD.to_string();
+    receipt.subject.execution_ref = None;
+    receipt.provenance.evidence_items.clear();
+    receipt.provenance.collection.clear();
+    receipt.provenance.prior_attempts.clear();
+    receipt.timing.execution_observed_at = None;
+    receipt.timing.finality_observed_at = None;
+    if let Some(non_provable) = receipt.non_provable.as_mut() {
+        non_provable.details = String::new();
+        non_provable.remediation.failed_source = None;
+    }
+    receipt
+}
+
+// Marks a withheld receipt released by `actor`. Fails before the holding period ends and for
+// receipts that were never escrowed or are already out.
+pub fn release(mut receipt: ZKReceipt, actor: &str) -> Result<ZKReceipt> {
+    let Some(escrow) = receipt.escrow.as_mut() else {
+        bail!("receipt {} is not held in escrow", receipt.receipt_id);
+    };
+    if escrow.released_at.is_some() {
+        bail!(
+            "receipt {} was already released from escrow",
+            receipt.receipt_id
+        );
+    }
+    let now = now_iso();
+    let due = DateTime::parse_from_rfc3339(&escrow.release_after)?;
+    if DateTime::parse_from_rfc3339(&now)? < due {
+        bail!(
+            "receipt {} is held in escrow until {}",
+            receipt.receipt_id,
+            escrow.release_after
+        );
+    }
+    escrow.released_at = Some(now.clone());
+    escrow.released_by = Some(actor.to_string());
+    receipt.timing.updated_at = now;
+    Ok(receipt)
+}
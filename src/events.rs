@@ -0,0 +1,243 @@
+use crate::models::{hash_json, now_iso, ReceiptStatus, ZKReceipt};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const GENESIS_EVENT_HASH: &str = "genesis";
+
+// One field set or removed, addressed by JSON pointer. Objects are diffed key by key; arrays and
+// scalars are replaced whole.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub path: String,
+    pub value: Option<Value>,
+}
+
+// An immutable record of one receipt write. The first event of a receipt carries the whole body
+// at path ""; later ones carry only what changed. Each event commits to its predecessor, so
+// editing, dropping or reordering history breaks the chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptEvent {
+    pub receipt_id: String,
+    pub revision: u64,
+    pub status: ReceiptStatus,
+    pub recorded_at: String,
+    pub changes: Vec<FieldChange>,
+    pub previous_hash: String,
+    pub event_hash: String,
+}
+
+impl ReceiptEvent {
+    // The event that takes `previous` (the fold of the events so far) to `receipt`.
+    pub fn next(
+        previous: Option<(&ReceiptEvent, &ZKReceipt)>,
+        receipt: &ZKReceipt,
+    ) -> Result<Self> {
+        let after = serde_json::to_value(receipt)?;
+        let (changes, previous_hash) = match previous {
+            None => (
+                vec![FieldChange {
+                    path: String::new(),
+                    value: Some(after),
+                }],
+                GENESIS_EVENT_HASH.to_string(),
+            ),
+            Some((event, before)) => {
+                let mut changes = Vec::new();
+                diff(
+                    &serde_json::to_value(before)?,
+                    &after,
+                    &mut String::new(),
+                    &mut changes,
+                );
+                (changes, event.event_hash.clone())
+            }
+        };
+        let mut event = Self {
+            receipt_id: receipt.receipt_id.clone(),
+            revision: receipt.revision,
+            status: receipt.status,
+            recorded_at: now_iso(),
+            changes,
+            previous_hash,
+            event_hash: String::new(),
+        };
+        event.event_hash = event.compute_hash();
+        Ok(event)
+    }
+
+    pub fn compute_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "receipt_id": self.receipt_id,
+            "revision": self.revision,
+            "status": self.status,
+            "recorded_at": self.recorded_at,
+            "changes": self.changes,
+            "previous_hash": self.previous_hash,
+        }))
+    }
+}
+
+// Replays a receipt's events in order, checking the hash chain as it goes.
+pub fn fold_events(events: &[ReceiptEvent]) -> Result<ZKReceipt> {
+    let first = events.first().ok_or_else(|| anyhow!("no events to fold"))?;
+    let mut state = Value::Null;
+    let mut previous_hash = GENESIS_EVENT_HASH.to_string();
+    for event in events {
+        if event.receipt_id != first.receipt_id {
+            bail!(
+                "event for receipt {} found in history of {}",
+                event.receipt_id,
+                first.receipt_id
+            );
+        }
+        if event.previous_hash != previous_hash || event.event_hash != event.compute_hash() {
+            bail!(
+                "history of receipt {} is broken at revision {}",
+                event.receipt_id,
+                event.revision
+            );
+        }
+        for change in &event.changes {
+            apply(&mut state, change)?;
+        }
+        previous_hash = event.event_hash.clone();
+    }
+    let receipt: ZKReceipt = serde_json::from_value(state).with_context(|| {
+        format!(
+            "history of receipt {} does not fold to a receipt",
+            first.receipt_id
+        )
+    })?;
+    let last = events.last().map_or(0, |e| e.revision);
+    if receipt.revision != last {
+        bail!(
+            "history of receipt {} folds to revision {}, expected {}",
+            receipt.receipt_id,
+            receipt.revision,
+            last
+        );
+    }
+    Ok(receipt)
+}
+
+// The receipt as it stood at `at`, or None if it did not exist yet.
+pub fn fold_events_at(events: &[ReceiptEvent], at: &str) -> Result<Option<ZKReceipt>> {
+    let at = parse_time(at)?;
+    let mut recorded = Vec::new();
+    for event in events {
+        if parse_time(&event.recorded_at)? > at {
+            break;
+        }
+        recorded.push(event.clone());
+    }
+    if recorded.is_empty() {
+        return Ok(None);
+    }
+    fold_events(&recorded).map(Some)
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("invalid timestamp {}", value))?
+        .with_timezone(&Utc))
+}
+
+fn diff(before: &Value, after: &Value, path: &mut String, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, value) in new {
+                let len = path.len();
+                push_segment(path, key);
+                match old.get(key) {
+                    Some(previous) => diff(previous, value, path, changes),
+                    None => changes.push(FieldChange {
+                        path: path.clone(),
+                        value: Some(value.clone()),
+                    }),
+                }
+                path.truncate(len);
+            }
+            for key in old.keys().filter(|k| !new.contains_key(*k)) {
+                let len = path.len();
+                push_segment(path, key);
+                changes.push(FieldChange {
+                    path: path.clone(),
+                    value: None,
+                });
+                path.truncate(len);
+            }
+        }
+        _ if before == after => {}
+        _ => changes.push(FieldChange {
+            path: path.clone(),
+            value: Some(after.clone()),
+        }),
+    }
+}
+
+fn push_segment(path: &mut String, key: &str) {
+    path.push('/');
+    path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+}
+
+fn apply(state: &mut Value, change: &FieldChange) -> Result<()> {
+    if change.path.is_empty() {
+        *state = change.value.clone().unwrap_or(Value::Null);
+        return Ok(());
+    }
+    let segments: Vec<String> = change
+        .path
+        .strip_prefix('/')
+        .ok_or_else(|| anyhow!("invalid change path {}", change.path))?
+        .split('/')
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let (last, parents) = segments
+        .split_last()
+        .ok_or_else(|| anyhow!("empty change path"))?;
+    let mut target = state;
+    for segment in parents {
+        target = target
+            .as_object_mut()
+            .and_then(|object| object.get_mut(segment))
+            .ok_or_else(|| anyhow!("change path {} does not exist", change.path))?;
+    }
+    let object: &mut Map<String, Value> = target.as_object_mut().ok_or_else(|| {
+        anyhow!(
+            "change path {} does not address an object field",
+            change.path
+        )
+    })?;
+    match &change.value {
+        Some(value) => {
+            object.insert(last.clone(), value.clone());
+        }
+        None => {
+            object.remove(last);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_reapply_to_the_same_document() {
+        let before =
+            serde_json::json!({ "a": 1, "b": { "c/d": [1, 2], "e~": null }, "gone": true });
+        let after =
+            serde_json::json!({ "a": 1, "b": { "c/d": [1, 2, 3], "e~": "set", "f": null } });
+        let mut changes = Vec::new();
+        diff(&before, &after, &mut String::new(), &mut changes);
+        assert_eq!(changes.len(), 4);
+        let mut state = before.clone();
+        for change in &changes {
+            apply(&mut state, change).expect("apply");
+        }
+        assert_eq!(state, after);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::models::ZKReceipt;
+use crate::store::{ExportCursor, ReceiptQuery, ReceiptStore};
+use anyhow::{anyhow, Context, Result};
+use std::io::Read;
+use std::path::Path;
+use tokio::sync::mpsc;
+
+pub const DEFAULT_EXPORT_PAGE_SIZE: usize = 100;
+pub const ARTIFACT_CHUNK_BYTES: usize = 64 * 1024;
+// Items buffered ahead of a slow reader; past this the exporter waits instead of reading further.
+const EXPORT_BUFFER: usize = 16;
+
+// What to export. `query.limit` caps the total; `after` resumes from the cursor of the last receipt
+// a previous export delivered.
+#[derive(Debug, Clone, Default)]
+pub struct ReceiptExport {
+    pub query: ReceiptQuery,
+    pub after: Option<ExportCursor>,
+    pub page_size: usize,
+    pub include_artifacts: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExportItem {
+    Receipt {
+        receipt: Box<ZKReceipt>,
+        cursor: ExportCursor,
+    },
+    // Proof artifact bytes, in order, following the receipt they belong to. Only artifacts this
+    // instance holds locally (file:// refs) are streamed; other refs stay on the receipt.
+    ArtifactChunk(ArtifactChunk),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactChunk {
+    pub receipt_id: String,
+    pub artifact_ref: String,
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub last: bool,
+}
+
+// Streams an export page by page. The channel is bounded, so a reader that stops pulling stops the
+// exporter too, and neither side ever holds more than a page plus the buffer. An error ends the
+// stream; the cursor of the last receipt received resumes it.
+pub fn export_receipts(
+    store: ReceiptStore,
+    export: ReceiptExport,
+) -> mpsc::Receiver<Result<ExportItem>> {
+    let (items, receiver) = mpsc::channel(EXPORT_BUFFER);
+    let runtime = tokio::runtime::Handle::current();
+    tokio::task::spawn_blocking(move || {
+        if let Err(err) = run_export(&runtime, &store, export, &items) {
+            let _ = items.blocking_send(Err(err));
+        }
+    });
+    receiver
+}
+
+fn run_export(
+    runtime: &tokio::runtime::Handle,
+    store: &ReceiptStore,
+    export: ReceiptExport,
+    items: &mpsc::Sender<Result<ExportItem>>,
+) -> Result<()> {
+    let page_size = if export.page_size == 0 {
+        DEFAULT_EXPORT_PAGE_SIZE
+    } else {
+        export.page_size
+    };
+    let mut remaining = export.query.limit.unwrap_or(usize::MAX);
+    let mut after = export.after;
+    while remaining > 0 {
+        let page = runtime.block_on(store.page(
+            &export.query,
+            after.as_ref(),
+            page_size.min(remaining),
+        ))?;
+        let Some(last) = page.last() else {
+            return Ok(());
+        };
+        after = Some(ExportCursor::of(last));
+        remaining -= page.len();
+        let full_page = page.len() == page_size;
+        for receipt in page {
+            let cursor = ExportCursor::of(&receipt);
+            let artifact = export
+                .include_artifacts
+                .then(|| receipt.proof.proof_artifact_ref.clone())
+                .flatten()
+                .filter(|r| r.starts_with("file://"));
+            let item = ExportItem::Receipt {
+                receipt: Box::new(receipt),
+                cursor: cursor.clone(),
+            };
+            if items.blocking_send(Ok(item)).is_err() {
+                return Ok(());
+            }
+            if let Some(artifact_ref) = artifact {
+                if !send_artifact(&cursor.receipt_id, &artifact_ref, items)? {
+                    return Ok(());
+                }
+            }
+        }
+        if !full_page {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+// Returns false once the reader has gone away.
+fn send_artifact(
+    receipt_id: &str,
+    artifact_ref: &str,
+    items: &mpsc::Sender<Result<ExportItem>>,
+) -> Result<bool> {
+    let path = Path::new(artifact_ref.trim_start_matches("file://"));
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("failed to open proof artifact {}", artifact_ref))?;
+    let size = file.metadata()?.len();
+    let mut offset = 0u64;
+    loop {
+        let mut data = vec![0u8; ARTIFACT_CHUNK_BYTES];
+        let read = file.read(&mut data)?;
+        data.truncate(read);
+        let last = read == 0 || offset + read as u64 >= size;
+        if read == 0 && offset < size {
+            return Err(anyhow!(
+                "proof artifact {} ended after {} of {} bytes",
+                artifact_ref,
+                offset,
+                size
+            ));
+        }
+        let chunk = ArtifactChunk {
+            receipt_id: receipt_id.to_string(),
+            artifact_ref: artifact_ref.to_string(),
+            offset,
+            data,
+            last,
+        };
+        if items
+            .blocking_send(Ok(ExportItem::ArtifactChunk(chunk)))
+            .is_err()
+        {
+            return Ok(false);
+        }
+        offset += read as u64;
+        if last {
+            return Ok(true);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::engine::recompute_receipt_hash;
+use crate::models::{now_iso, PolicyContext, ReceiptStatus, SignerIdentity, ZKReceipt};
+use crate::signing::{KeyRing, SignerKey};
+use crate::verifier::OffchainVerifier;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// What a deployment publishes so others can verify its receipts: every signer key it has used and
+// the policy versions its receipts may carry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceDescriptor {
+    pub instance_id: String,
+    pub published_at: String,
+    pub keys: Vec<SignerKey>,
+    pub policies: Vec<PolicyContext>,
+    // X25519 key clients seal sensitive payload fields to, when the deployment accepts them.
+    #[serde(default)]
+    pub payload_encryption_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForeignVerification {
+    pub instance_id: String,
+    pub receipt_id: String,
+    pub receipt_hash: String,
+    pub status: ReceiptStatus,
+    pub signer: SignerIdentity,
+    pub policy: PolicyContext,
+    pub proof_verified: bool,
+    pub checked_at: String,
+}
+
+// Descriptors of other deployments this one has chosen to trust, keyed by instance id. Trust is
+// configured by the operator; nothing is fetched or accepted implicitly.
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    instances: BTreeMap<String, InstanceDescriptor>,
+}
+
+impl TrustStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trust(&mut self, descriptor: InstanceDescriptor) -> Result<()> {
+        if descriptor.instance_id.trim().is_empty() {
+            bail!("trusted instance requires an instance id");
+        }
+        KeyRing::new(descriptor.keys.clone())?;
+        if descriptor.policies.is_empty() {
+            bail!(
+                "trusted instance {} publishes no policy versions",
+                descriptor.instance_id
+            );
+        }
+        self.instances
+            .insert(descriptor.instance_id.clone(), descriptor);
+        Ok(())
+    }
+
+    pub fn with_instance(mut self, descriptor: InstanceDescriptor) -> Result<Self> {
+        self.trust(descriptor)?;
+        Ok(self)
+    }
+
+    pub fn revoke(&mut self, instance_id: &str) -> bool {
+        self.instances.remove(instance_id).is_some()
+    }
+
+    pub fn instances(&self) -> impl Iterator<Item = &InstanceDescriptor> {
+        self.instances.values()
+    }
+
+    pub fn issuer_of(&self, signer: &SignerIdentity) -> Option<&InstanceDescriptor> {
+        self.instances
+            .values()
+            .find(|d| d.keys.iter().any(|k| k.identity == *signer))
+    }
+
+    // The issuer is found by the receipt's signer key, so a receipt cannot claim a different
+    // instance than the one whose key signed it.
+    pub async fn verify_foreign_receipt(
+        &self,
+        verifier: &OffchainVerifier,
+        receipt: &ZKReceipt,
+    ) -> Result<ForeignVerification> {
+        let signer = &receipt.integrity.signer;
+        let issuer = self.issuer_of(signer).ok_or_else(|| {
+            anyhow!(
+                "signer key {} belongs to no trusted instance",
+                signer.key_id
+            )
+        })?;
+        if recompute_receipt_hash(receipt) != receipt.integrity.receipt_hash {
+            bail!(
+                "receipt {} hash does not match its contents",
+                receipt.receipt_id
+            );
+        }
+        if !KeyRing::new(issuer.keys.clone())?.verify_receipt_signature(receipt) {
+            bail!(
+                "receipt {} signature does not verify against instance {}",
+                receipt.receipt_id,
+                issuer.instance_id
+            );
+        }
+        if !issuer.policies.contains(&receipt.policy) {
+            bail!(
+                "policy {} ({}, {}) is not published by instance {}",
+                receipt.policy.policy_id,
+                receipt.policy.finality_rule_id,
+                receipt.policy.source_precedence_version,
+                issuer.instance_id
+            );
+        }
+        let proof_verified =
+            receipt.status == ReceiptStatus::PROVED && verifier.verify(receipt).await;
+        if receipt.status == ReceiptStatus::PROVED && !proof_verified {
+            bail!("receipt {} proof does not verify", receipt.receipt_id);
+        }
+        Ok(ForeignVerification {
+            instance_id: issuer.instance_id.clone(),
+            receipt_id: receipt.receipt_id.clone(),
+            receipt_hash: receipt.integrity.receipt_hash.clone(),
+            status: receipt.status,
+            signer: signer.clone(),
+            policy: receipt.policy.clone(),
+            proof_verified,
+            checked_at: now_iso(),
+        })
+    }
+}
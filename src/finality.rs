@@ -0,0 +1,248 @@
+use crate::models::{now_iso, ChainCommitment, EvidenceBundle, Venue};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, Mutex};
+
+// A venue's entry in the finality rules document: what a gated claim's evidence must show before
+// it is proved, and how long the finality observer may wait for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum FinalityRule {
+    Instant,
+    Confirmations {
+        depth: u32,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    },
+    Commitment {
+        level: ChainCommitment,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    },
+    // The adapter reports finality itself (`finality_observed_at`), e.g. from a venue's settled status.
+    VenueAttested {
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    },
+}
+
+impl FinalityRule {
+    pub fn timeout(&self) -> Option<Duration> {
+        match self {
+            FinalityRule::Instant => None,
+            FinalityRule::Confirmations { timeout_ms, .. }
+            | FinalityRule::Commitment { timeout_ms, .. }
+            | FinalityRule::VenueAttested { timeout_ms } => timeout_ms.map(Duration::from_millis),
+        }
+    }
+
+    // What the evidence falls short of, or None once the rule is met.
+    pub fn shortfall(&self, bundle: &EvidenceBundle) -> Option<String> {
+        match *self {
+            FinalityRule::Instant => None,
+            FinalityRule::Confirmations { depth, .. } => match bundle.confirmations {
+                Some(seen) if seen >= u64::from(depth) => None,
+                Some(seen) => Some(format!("{} of {} confirmations", seen, depth)),
+                None => Some(format!("no confirmation count, {} required", depth)),
+            },
+            FinalityRule::Commitment { level, .. } => match bundle.commitment {
+                Some(seen) if seen >= level => None,
+                Some(seen) => Some(format!("{:?} commitment, {:?} required", seen, level)),
+                None => Some(format!("no commitment level, {:?} required", level)),
+            },
+            FinalityRule::VenueAttested { .. } => match bundle.finality_observed_at {
+                Some(_) => None,
+                None => Some("the venue has not reported the execution final".to_string()),
+            },
+        }
+    }
+}
+
+#[async_trait]
+pub trait ChainHeadSource: Send + Sync {
+    async fn head(&self) -> Result<u64>;
+}
+
+#[derive(Debug)]
+pub struct SyntheticChainHead {
+    head: AtomicU64,
+    step: u64,
+}
+
+impl SyntheticChainHead {
+    pub fn new(start: u64, step: u64) -> Self {
+        Self {
+            head: AtomicU64::new(start),
+            step,
+        }
+    }
+}
+
+#[async_trait]
+impl ChainHeadSource for SyntheticChainHead {
+    async fn head(&self) -> Result<u64> {
+        Ok(self.head.fetch_add(self.step, Ordering::SeqCst) + self.step)
+    }
+}
+
+// A head that grows by one block every `block_time` of wall-clock time from creation, so a
+// receipt `depth` blocks deep waits about `depth * block_time`.
+#[derive(Debug)]
+pub struct TimedChainHead {
+    started: Instant,
+    block_time: Duration,
+}
+
+impl TimedChainHead {
+    pub fn new(block_time: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            block_time,
+        }
+    }
+}
+
+#[async_trait]
+impl ChainHeadSource for TimedChainHead {
+    async fn head(&self) -> Result<u64> {
+        let block_nanos = self.block_time.as_nanos().max(1);
+        Ok((self.started.elapsed().as_nanos() / block_nanos) as u64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalityEvent {
+    pub venue: Venue,
+    pub final_height: u64,
+    pub observed_at: String,
+}
+
+struct Waiter {
+    target_height: u64,
+    notify: oneshot::Sender<FinalityEvent>,
+}
+
+#[derive(Default)]
+struct VenueState {
+    head: Option<u64>,
+    waiters: Vec<Waiter>,
+    polling: bool,
+}
+
+struct VenueWatch {
+    source: Arc<dyn ChainHeadSource>,
+    state: Mutex<VenueState>,
+}
+
+// One poller per venue tracks the chain head and releases every pending receipt whose target
+// height has been reached, so the number of head queries does not grow with pending receipts.
+#[derive(Clone)]
+pub struct FinalityObserver {
+    venues: Arc<HashMap<Venue, Arc<VenueWatch>>>,
+    poll_interval: Duration,
+}
+
+impl FinalityObserver {
+    pub fn new(poll_interval: Duration) -> Self {
+        Self {
+            venues: Arc::new(HashMap::new()),
+            poll_interval,
+        }
+    }
+
+    pub fn with_head_source(mut self, venue: Venue, source: Arc<dyn ChainHeadSource>) -> Self {
+        let mut venues = (*self.venues).clone();
+        venues.insert(
+            venue,
+            Arc::new(VenueWatch {
+                source,
+                state: Mutex::new(VenueState::default()),
+            }),
+        );
+        self.venues = Arc::new(venues);
+        self
+    }
+
+    pub fn tracks(&self, venue: Venue) -> bool {
+        self.venues.contains_key(&venue)
+    }
+
+    pub async fn pending(&self, venue: Venue) -> usize {
+        match self.venues.get(&venue) {
+            Some(watch) => watch.state.lock().await.waiters.len(),
+            None => 0,
+        }
+    }
+
+    pub async fn await_finality(
+        &self,
+        venue: Venue,
+        inclusion_height: Option<u64>,
+        depth: u32,
+    ) -> Result<FinalityEvent> {
+        let watch = self
+            .venues
+            .get(&venue)
+            .cloned()
+            .ok_or_else(|| anyhow!("no chain head source registered for venue {:?}", venue))?;
+        let cached_head = watch.state.lock().await.head;
+        let base_height = match (inclusion_height, cached_head) {
+            (Some(height), _) => height,
+            (None, Some(head)) => head,
+            (None, None) => watch.source.head().await?,
+        };
+        let target_height = base_height + u64::from(depth);
+
+        let (notify, receiver) = oneshot::channel();
+        let start_poller = {
+            let mut state = watch.state.lock().await;
+            state.waiters.push(Waiter {
+                target_height,
+                notify,
+            });
+            !std::mem::replace(&mut state.polling, true)
+        };
+        if start_poller {
+            tokio::spawn(poll_venue(venue, watch, self.poll_interval));
+        }
+        receiver
+            .await
+            .map_err(|_| anyhow!("finality observer for venue {:?} stopped", venue))
+    }
+}
+
+async fn poll_venue(venue: Venue, watch: Arc<VenueWatch>, poll_interval: Duration) {
+    loop {
+        let head = watch.source.head().await.ok();
+        {
+            let mut state = watch.state.lock().await;
+            if let Some(head) = head {
+                state.head = Some(head);
+                let observed_at = now_iso();
+                let (ready, waiting): (Vec<Waiter>, Vec<Waiter>) = state
+                    .waiters
+                    .drain(..)
+                    .partition(|w| w.target_height <= head);
+                state.waiters = waiting;
+                for waiter in ready {
+                    let _ = waiter.notify.send(FinalityEvent {
+                        venue,
+                        final_height: head,
+                        observed_at: observed_at.clone(),
+                    });
+                }
+            }
+            state.waiters.retain(|w| !w.notify.is_closed());
+            if state.waiters.is_empty() {
+                state.polling = false;
+                return;
+            }
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
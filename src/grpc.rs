@@ -0,0 +1,249 @@
+use crate::export::{ExportItem, ReceiptExport};
+use crate::models::{ClaimType, ReceiptStatus, Venue};
+use crate::store::{ExportCursor, ReceiptQuery};
+use crate::ReceiptEngine;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tonic::codegen::tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::codegen::tokio_stream::StreamExt;
+use tonic::codegen::{http, Arc, Body, BoxFuture, BoxStream, Context, Poll, Service, StdError};
+use tonic::server::{NamedService, ServerStreamingService};
+use tonic::{Request, Response, Status};
+use tonic_prost::ProstCodec;
+
+// Messages and paths mirror proto/zkputer.proto; they are written out by hand so the build needs
+// no protoc.
+pub const SERVICE_NAME: &str = "zkputer.v1.ReceiptArchive";
+pub const EXPORT_RECEIPTS_PATH: &str = "/zkputer.v1.ReceiptArchive/ExportReceipts";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExportReceiptsRequest {
+    #[prost(string, optional, tag = "1")]
+    pub venue: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub claim_type: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub status: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub after: Option<String>,
+    #[prost(uint32, tag = "5")]
+    pub page_size: u32,
+    #[prost(uint64, optional, tag = "6")]
+    pub limit: Option<u64>,
+    #[prost(bool, tag = "7")]
+    pub include_artifacts: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExportReceiptsResponse {
+    #[prost(oneof = "export_receipts_response::Item", tags = "1, 2")]
+    pub item: Option<export_receipts_response::Item>,
+}
+
+pub mod export_receipts_response {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Item {
+        #[prost(message, tag = "1")]
+        Receipt(super::ExportedReceipt),
+        #[prost(message, tag = "2")]
+        ArtifactChunk(super::ArtifactChunk),
+    }
+}
+
+// The receipt travels as its canonical JSON, so hashes and signatures check exactly as they would
+// on a receipt fetched any other way.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExportedReceipt {
+    #[prost(string, tag = "1")]
+    pub receipt_id: String,
+    #[prost(string, tag = "2")]
+    pub cursor: String,
+    #[prost(string, tag = "3")]
+    pub receipt_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ArtifactChunk {
+    #[prost(string, tag = "1")]
+    pub receipt_id: String,
+    #[prost(string, tag = "2")]
+    pub artifact_ref: String,
+    #[prost(uint64, tag = "3")]
+    pub offset: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub data: Vec<u8>,
+    #[prost(bool, tag = "5")]
+    pub last: bool,
+}
+
+impl ExportReceiptsRequest {
+    fn into_export(self) -> Result<ReceiptExport, Status> {
+        let venue = self
+            .venue
+            .map(|v| {
+                Venue::from_slug(&v)
+                    .ok_or_else(|| Status::invalid_argument(format!("unknown venue: {}", v)))
+            })
+            .transpose()?;
+        let claim_type = self
+            .claim_type
+            .map(|c| {
+                ClaimType::from_name(&c)
+                    .ok_or_else(|| Status::invalid_argument(format!("unknown claim type: {}", c)))
+            })
+            .transpose()?;
+        let status = self
+            .status
+            .map(|s| {
+                serde_json::from_value::<ReceiptStatus>(serde_json::Value::String(s.clone()))
+                    .map_err(|_| Status::invalid_argument(format!("unknown receipt status: {}", s)))
+            })
+            .transpose()?;
+        let after = self
+            .after
+            .map(|a| {
+                ExportCursor::decode(&a).map_err(|err| Status::invalid_argument(err.to_string()))
+            })
+            .transpose()?;
+        Ok(ReceiptExport {
+            query: ReceiptQuery {
+                venue,
+                claim_type,
+                status,
+                limit: self.limit.map(|l| l as usize),
+                ..ReceiptQuery::default()
+            },
+            after,
+            page_size: self.page_size as usize,
+            include_artifacts: self.include_artifacts,
+        })
+    }
+}
+
+fn to_message(item: anyhow::Result<ExportItem>) -> Result<ExportReceiptsResponse, Status> {
+    let item = match item.map_err(|err| Status::internal(err.to_string()))? {
+        ExportItem::Receipt { receipt, cursor } => {
+            export_receipts_response::Item::Receipt(ExportedReceipt {
+                receipt_id: receipt.receipt_id.clone(),
+                cursor: cursor.encode(),
+                receipt_json: serde_json::to_string(&receipt)
+                    .map_err(|err| Status::internal(err.to_string()))?,
+            })
+        }
+        ExportItem::ArtifactChunk(chunk) => {
+            export_receipts_response::Item::ArtifactChunk(ArtifactChunk {
+                receipt_id: chunk.receipt_id,
+                artifact_ref: chunk.artifact_ref,
+                offset: chunk.offset,
+                data: chunk.data,
+                last: chunk.last,
+            })
+        }
+    };
+    Ok(ExportReceiptsResponse { item: Some(item) })
+}
+
+#[derive(Clone)]
+pub struct ReceiptArchiveService {
+    engine: Arc<ReceiptEngine>,
+}
+
+impl ReceiptArchiveService {
+    pub fn new(engine: Arc<ReceiptEngine>) -> Self {
+        Self { engine }
+    }
+}
+
+impl NamedService for ReceiptArchiveService {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+struct ExportReceipts(Arc<ReceiptEngine>);
+
+impl ServerStreamingService<ExportReceiptsRequest> for ExportReceipts {
+    type Response = ExportReceiptsResponse;
+    type ResponseStream = BoxStream<ExportReceiptsResponse>;
+    type Future = BoxFuture<Response<Self::ResponseStream>, Status>;
+
+    fn call(&mut self, request: Request<ExportReceiptsRequest>) -> Self::Future {
+        let engine = Arc::clone(&self.0);
+        Box::pin(async move {
+            let export = request.into_inner().into_export()?;
+            let items = ReceiverStream::new(engine.export_receipts(export)).map(to_message);
+            Ok(Response::new(Box::pin(items) as Self::ResponseStream))
+        })
+    }
+}
+
+impl<B> Service<http::Request<B>> for ReceiptArchiveService
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let engine = Arc::clone(&self.engine);
+        match request.uri().path() {
+            EXPORT_RECEIPTS_PATH => Box::pin(async move {
+                let mut grpc = tonic::server::Grpc::new(ProstCodec::default());
+                Ok(grpc.server_streaming(ExportReceipts(engine), request).await)
+            }),
+            _ => Box::pin(async move { Ok(Status::unimplemented("unknown method").into_http()) }),
+        }
+    }
+}
+
+// Serves the archive service on an already bound listener until `shutdown` resolves.
+pub async fn serve(
+    engine: Arc<ReceiptEngine>,
+    listener: TcpListener,
+    shutdown: impl std::future::Future<Output = ()>,
+) -> anyhow::Result<()> {
+    tonic::transport::Server::builder()
+        .serve_with_incoming_shutdown(
+            ReceiptArchiveService::new(engine),
+            TcpListenerStream::new(listener),
+            shutdown,
+        )
+        .await?;
+    Ok(())
+}
+
+pub struct ReceiptArchiveClient {
+    grpc: tonic::client::Grpc<tonic::transport::Channel>,
+}
+
+impl ReceiptArchiveClient {
+    pub async fn connect(addr: SocketAddr) -> anyhow::Result<Self> {
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://{}", addr))?
+            .connect()
+            .await?;
+        Ok(Self {
+            grpc: tonic::client::Grpc::new(channel),
+        })
+    }
+
+    pub async fn export_receipts(
+        &mut self,
+        request: ExportReceiptsRequest,
+    ) -> Result<tonic::Streaming<ExportReceiptsResponse>, Status> {
+        self.grpc
+            .ready()
+            .await
+            .map_err(|err| Status::unavailable(format!("receipt archive unavailable: {}", err)))?;
+        let path = http::uri::PathAndQuery::from_static(EXPORT_RECEIPTS_PATH);
+        let response = self
+            .grpc
+            .server_streaming(Request::new(request), path, ProstCodec::default())
+            .await?;
+        Ok(response.into_inner())
+    }
+}
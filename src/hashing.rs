@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Keccak256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub fn all() -> &'static [HashAlgorithm] {
+        &[
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Keccak256,
+            HashAlgorithm::Blake3,
+        ]
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Keccak256 => "keccak256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    pub fn from_id(raw: &str) -> Result<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|alg| alg.id() == raw.trim().to_ascii_lowercase())
+            .ok_or_else(|| anyhow!("unsupported hash algorithm: {}", raw))
+    }
+
+    pub fn digest_bytes(self, input: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlgorithm::Sha256 => Sha256::digest(input).into(),
+            HashAlgorithm::Keccak256 => Keccak256::digest(input).into(),
+            HashAlgorithm::Blake3 => *blake3::hash(input).as_bytes(),
+        }
+    }
+
+    pub fn hash_bytes(self, input: &[u8]) -> String {
+        format!("0x{}", hex::encode(self.digest_bytes(input)))
+    }
+
+    pub fn hash_str(self, input: &str) -> String {
+        self.hash_bytes(input.as_bytes())
+    }
+
+    pub fn hash_json(self, value: &impl Serialize) -> String {
+        let serialized = serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string());
+        self.hash_str(&serialized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_empty_digests() {
+        assert_eq!(
+            HashAlgorithm::Sha256.hash_str(""),
+            "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            HashAlgorithm::Keccak256.hash_str(""),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            HashAlgorithm::Blake3.hash_str(""),
+            "0xaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+
+    #[test]
+    fn registry_round_trips_ids() {
+        for alg in HashAlgorithm::all() {
+            assert_eq!(HashAlgorithm::from_id(alg.id()).expect("known id"), *alg);
+        }
+        assert!(HashAlgorithm::from_id("md5").is_err());
+    }
+}
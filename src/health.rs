@@ -0,0 +1,171 @@
+use crate::leader::EngineRole;
+use crate::models::{ProofBackend, Venue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    // When the half-open breaker let its trial request through.
+    probe_started: Option<Instant>,
+}
+
+// Consecutive adapter failures open a venue's breaker; once the cooldown elapses a single trial
+// request is let through (half-open) and its outcome either closes or re-opens the breaker. Other
+// requests are turned away while the trial runs; a trial that never reports back, e.g. one that
+// ran out of deadline, is replaced by another after a further cooldown.
+#[derive(Clone)]
+pub struct CircuitBreakers {
+    failure_threshold: u32,
+    cooldown: Duration,
+    venues: Arc<Mutex<HashMap<Venue, BreakerState>>>,
+}
+
+impl CircuitBreakers {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            venues: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn state(&self, venue: Venue) -> CircuitState {
+        let venues = self.venues.lock().await;
+        self.classify(venues.get(&venue).copied().unwrap_or_default())
+    }
+
+    pub async fn consecutive_failures(&self, venue: Venue) -> u32 {
+        self.venues
+            .lock()
+            .await
+            .get(&venue)
+            .map(|s| s.consecutive_failures)
+            .unwrap_or(0)
+    }
+
+    pub async fn retry_after(&self, venue: Venue) -> Option<Duration> {
+        let venues = self.venues.lock().await;
+        let opened_at = venues.get(&venue)?.opened_at?;
+        self.cooldown.checked_sub(opened_at.elapsed())
+    }
+
+    // Whether a request to `venue` may go ahead: always while closed, never while open, and only
+    // as the one trial request while half-open.
+    pub async fn admit(&self, venue: Venue) -> bool {
+        let mut venues = self.venues.lock().await;
+        let Some(state) = venues.get_mut(&venue) else {
+            return true;
+        };
+        match self.classify(*state) {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => {
+                if state
+                    .probe_started
+                    .is_some_and(|started| started.elapsed() < self.cooldown)
+                {
+                    return false;
+                }
+                state.probe_started = Some(Instant::now());
+                true
+            }
+        }
+    }
+
+    pub async fn record_success(&self, venue: Venue) {
+        self.venues.lock().await.remove(&venue);
+    }
+
+    pub async fn record_failure(&self, venue: Venue) {
+        let mut venues = self.venues.lock().await;
+        let state = venues.entry(venue).or_default();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold {
+            state.opened_at = Some(Instant::now());
+            state.probe_started = None;
+        }
+    }
+
+    fn classify(&self, state: BreakerState) -> CircuitState {
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+impl Default for CircuitBreakers {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum HealthStatus {
+    HEALTHY,
+    DEGRADED,
+    UNHEALTHY,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdapterHealth {
+    pub venue: Venue,
+    pub circuit: CircuitState,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreHealth {
+    pub reachable: bool,
+    pub receipts: usize,
+    #[serde(default)]
+    pub persistent: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverHealth {
+    pub backend: ProofBackend,
+    pub available: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueueDepths {
+    pub pending: usize,
+    pub awaiting_finality: usize,
+    pub finality_waiters: usize,
+    #[serde(default)]
+    pub proofs_running: usize,
+    #[serde(default)]
+    pub proofs_queued: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub live: bool,
+    pub ready: bool,
+    pub checked_at: String,
+    pub adapters: Vec<AdapterHealth>,
+    pub store: StoreHealth,
+    pub prover: ProverHealth,
+    pub queues: QueueDepths,
+    pub issues: Vec<String>,
+    // Leader or standby, for engines taking part in leader election.
+    #[serde(default)]
+    pub role: Option<EngineRole>,
+}
@@ -0,0 +1,657 @@
+use crate::engine::ReceiptEngine;
+use crate::escrow::published;
+use crate::models::{ClaimType, ProofRequest, PublicVerificationQuery, ReceiptStatus, Venue};
+use crate::store::ReceiptQuery;
+use crate::validation::is_hash_hex;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+const MAX_BODY_BYTES: usize = 1 << 20;
+// Bounds on the request head, so a client cannot hold a connection's memory or its task open.
+const MAX_HEAD_LINE_BYTES: usize = 8 << 10;
+const MAX_HEADERS: usize = 100;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+pub const DEFAULT_LIST_LIMIT: usize = 100;
+pub const MAX_LIST_LIMIT: usize = 1_000;
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    // Raw query string, without the '?'.
+    pub query: Option<String>,
+    pub authorization: Option<String>,
+    pub peer: Option<IpAddr>,
+    pub body: Vec<u8>,
+    // Every header as received, names as sent.
+    pub headers: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Decoded query parameters; a repeated name keeps its last value.
+    pub fn query_params(&self) -> Result<HashMap<String, String>> {
+        let mut params = HashMap::new();
+        for pair in self
+            .query
+            .as_deref()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|p| !p.is_empty())
+        {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            params.insert(percent_decode(name)?, percent_decode(value)?);
+        }
+        Ok(params)
+    }
+}
+
+fn percent_decode(raw: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut input = raw.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let digits = [input.next(), input.next()];
+                let hex: String = digits.iter().flatten().map(|b| *b as char).collect();
+                let decoded = u8::from_str_radix(&hex, 16).ok().filter(|_| hex.len() == 2);
+                bytes.push(decoded.ok_or_else(|| anyhow!("invalid percent-encoding in {}", raw))?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| anyhow!("query parameter {} is not UTF-8", raw))
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Value,
+}
+
+impl HttpResponse {
+    pub fn json(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body,
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub(crate) fn reason(&self) -> &'static str {
+        reason_phrase(self.status)
+    }
+}
+
+pub(crate) fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub per_minute: u32,
+    pub burst: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn full(limit: RateLimit) -> Self {
+        Self {
+            tokens: f64::from(limit.burst.max(1)),
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, limit: RateLimit) {
+        let rate = f64::from(limit.per_minute) / 60.0;
+        self.tokens = (self.tokens + self.updated.elapsed().as_secs_f64() * rate)
+            .min(f64::from(limit.burst.max(1)));
+        self.updated = Instant::now();
+    }
+
+    fn retry_after(&self, limit: RateLimit) -> Duration {
+        let rate = f64::from(limit.per_minute.max(1)) / 60.0;
+        Duration::from_secs_f64(((1.0 - self.tokens) / rate).max(0.0))
+    }
+}
+
+// Token buckets per client address plus one shared bucket, so a flood from many addresses is
+// still capped.
+pub struct RateLimiter {
+    per_client: RateLimit,
+    global: RateLimit,
+    state: Mutex<(Bucket, HashMap<IpAddr, Bucket>)>,
+}
+
+impl RateLimiter {
+    pub fn new(per_client: RateLimit, global: RateLimit) -> Self {
+        Self {
+            per_client,
+            global,
+            state: Mutex::new((Bucket::full(global), HashMap::new())),
+        }
+    }
+
+    pub async fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        let mut state = self.state.lock().await;
+        let (global, clients) = &mut *state;
+        if clients.len() >= MAX_TRACKED_CLIENTS {
+            let per_client = self.per_client;
+            clients.retain(|_, bucket| {
+                bucket.refill(per_client);
+                bucket.tokens < f64::from(per_client.burst.max(1))
+            });
+        }
+        let bucket = clients
+            .entry(client)
+            .or_insert_with(|| Bucket::full(self.per_client));
+        bucket.refill(self.per_client);
+        global.refill(self.global);
+        if bucket.tokens < 1.0 {
+            return Err(bucket.retry_after(self.per_client));
+        }
+        if global.tokens < 1.0 {
+            return Err(global.retry_after(self.global));
+        }
+        bucket.tokens -= 1.0;
+        global.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+pub struct HttpApi {
+    engine: Arc<ReceiptEngine>,
+    public_verify: RateLimiter,
+    api_token: Option<String>,
+}
+
+impl HttpApi {
+    pub fn new(engine: Arc<ReceiptEngine>) -> Self {
+        Self {
+            engine,
+            public_verify: RateLimiter::new(
+                RateLimit {
+                    per_minute: 10,
+                    burst: 5,
+                },
+                RateLimit {
+                    per_minute: 600,
+                    burst: 60,
+                },
+            ),
+            api_token: None,
+        }
+    }
+
+    // Requires `Authorization: Bearer <token>` on the /v1/receipts routes. Probes and the public
+    // verification endpoint stay open.
+    pub fn with_api_token(mut self, token: impl Into<String>) -> Self {
+        self.api_token = Some(token.into());
+        self
+    }
+
+    pub fn with_public_verify_limit(mut self, per_client: RateLimit, global: RateLimit) -> Self {
+        self.public_verify = RateLimiter::new(per_client, global);
+        self
+    }
+
+    pub async fn route(&self, request: &HttpRequest) -> HttpResponse {
+        let path = request.path.as_str();
+        match (request.method.as_str(), path) {
+            ("GET", "/healthz") => {
+                let report = self.engine.health().await;
+                let status = if report.live { 200 } else { 503 };
+                HttpResponse::json(
+                    status,
+                    json!({ "live": report.live, "checked_at": report.checked_at }),
+                )
+            }
+            ("GET", "/readyz") => {
+                let report = self.engine.health().await;
+                let status = if report.ready { 200 } else { 503 };
+                HttpResponse::json(status, serde_json::to_value(&report).unwrap_or(Value::Null))
+            }
+            ("GET", "/health") => {
+                let report = self.engine.health().await;
+                let status = if report.live { 200 } else { 503 };
+                HttpResponse::json(status, serde_json::to_value(&report).unwrap_or(Value::Null))
+            }
+            (_, "/health" | "/healthz" | "/readyz") => {
+                HttpResponse::json(405, json!({ "error": "method not allowed" }))
+            }
+            _ if path == "/v1/receipts" || path.starts_with("/v1/receipts/") => {
+                if let Some(denied) = self.authorize(request) {
+                    return denied;
+                }
+                if request.method == "POST" {
+                    if let Some(standby) = self.standby() {
+                        return standby;
+                    }
+                }
+                match (request.method.as_str(), path.strip_prefix("/v1/receipts/")) {
+                    ("POST", None) => self.submit_receipt(request).await,
+                    ("GET", None) => self.list_receipts(request).await,
+                    ("GET", Some(receipt_id))
+                        if !receipt_id.is_empty() && !receipt_id.contains('/') =>
+                    {
+                        self.get_receipt(request, receipt_id).await
+                    }
+                    ("POST", Some(rest)) if rest.ends_with("/escrow/release") => {
+                        let receipt_id = rest.trim_end_matches("/escrow/release");
+                        if receipt_id.is_empty() || receipt_id.contains('/') {
+                            return HttpResponse::json(
+                                404,
+                                json!({ "error": format!("no route for {}", path) }),
+                            );
+                        }
+                        self.release_escrow(request, receipt_id).await
+                    }
+                    ("GET", Some(_)) => HttpResponse::json(
+                        404,
+                        json!({ "error": format!("no route for {}", path) }),
+                    ),
+                    _ => HttpResponse::json(405, json!({ "error": "method not allowed" })),
+                }
+            }
+            ("POST", "/v1/verify") => {
+                self.public_verification(request, || {
+                    serde_json::from_slice(&request.body).map_err(|e| e.to_string())
+                })
+                .await
+            }
+            ("GET", _) if path.starts_with("/v1/verify/") => {
+                let hash = path.trim_start_matches("/v1/verify/").to_string();
+                self.public_verification(request, || Ok(PublicVerificationQuery::ReceiptHash(hash)))
+                    .await
+            }
+            ("GET", "/v1/instance") => HttpResponse::json(
+                200,
+                serde_json::to_value(self.engine.instance_descriptor()).unwrap_or(Value::Null),
+            ),
+            (_, "/v1/instance") => {
+                HttpResponse::json(405, json!({ "error": "method not allowed" }))
+            }
+            ("GET", "/v1/watermark") => {
+                HttpResponse::json(200, json!({ "watermarks": self.engine.watermarks() }))
+            }
+            (_, "/v1/watermark") => {
+                HttpResponse::json(405, json!({ "error": "method not allowed" }))
+            }
+            (_, "/v1/verify") => HttpResponse::json(405, json!({ "error": "method not allowed" })),
+            _ => HttpResponse::json(404, json!({ "error": format!("no route for {}", path) })),
+        }
+    }
+
+    fn authorize(&self, request: &HttpRequest) -> Option<HttpResponse> {
+        let token = self.api_token.as_deref()?;
+        let presented = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if presented
+            .is_some_and(|presented| constant_time_eq(presented.as_bytes(), token.as_bytes()))
+        {
+            return None;
+        }
+        Some(
+            HttpResponse::json(401, json!({ "error": "missing or invalid bearer token" }))
+                .with_header("WWW-Authenticate", "Bearer"),
+        )
+    }
+
+    // Writes go to the leader; a standby answers 503 and names the leader when it knows it.
+    fn standby(&self) -> Option<HttpResponse> {
+        if self.engine.is_leader() {
+            return None;
+        }
+        Some(
+            HttpResponse::json(
+                503,
+                json!({ "error": "this instance is a standby", "leader": self.engine.current_leader() }),
+            )
+            .with_header("Retry-After", "1"),
+        )
+    }
+
+    // Accepts a ProofRequest and answers as soon as the receipt exists; the pipeline runs on, and
+    // clients poll the Location until the status settles.
+    async fn submit_receipt(&self, request: &HttpRequest) -> HttpResponse {
+        let proof_request: ProofRequest = match serde_json::from_slice(&request.body) {
+            Ok(proof_request) => proof_request,
+            Err(err) => {
+                return HttpResponse::json(
+                    400,
+                    json!({ "error": format!("invalid proof request: {}", err) }),
+                )
+            }
+        };
+        let receipt_id = match self.engine.submit(proof_request).await {
+            Ok(receipt_id) => receipt_id,
+            Err(err) => return HttpResponse::json(400, json!({ "error": err.to_string() })),
+        };
+        match self.engine.published_receipt(&receipt_id).await {
+            Some(receipt) => {
+                HttpResponse::json(202, serde_json::to_value(&receipt).unwrap_or(Value::Null))
+                    .with_header("Location", format!("/v1/receipts/{}", receipt_id))
+            }
+            None => HttpResponse::json(500, json!({ "error": "receipt not found after submit" })),
+        }
+    }
+
+    async fn get_receipt(&self, request: &HttpRequest, receipt_id: &str) -> HttpResponse {
+        let version = match request.query_params() {
+            Ok(params) => params.get("version").cloned(),
+            Err(err) => return HttpResponse::json(400, json!({ "error": err.to_string() })),
+        };
+        let Some(receipt) = self.engine.published_receipt(receipt_id).await else {
+            return HttpResponse::json(
+                404,
+                json!({ "error": format!("receipt not found: {}", receipt_id) }),
+            );
+        };
+        match version {
+            Some(version) => match self.engine.published_receipt_as(receipt_id, &version).await {
+                Ok(receipt) => HttpResponse::json(200, receipt),
+                Err(err) => HttpResponse::json(400, json!({ "error": err.to_string() })),
+            },
+            None => HttpResponse::json(200, serde_json::to_value(&receipt).unwrap_or(Value::Null)),
+        }
+    }
+
+    // Body: `{"actor": "..."}`. Answers 409 while the holding period runs or the receipt is still
+    // in the pipeline.
+    async fn release_escrow(&self, request: &HttpRequest, receipt_id: &str) -> HttpResponse {
+        let body: Value = match serde_json::from_slice(&request.body) {
+            Ok(body) => body,
+            Err(err) => {
+                return HttpResponse::json(
+                    400,
+                    json!({ "error": format!("invalid release request: {}", err) }),
+                )
+            }
+        };
+        let actor = body
+            .get("actor")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if self.engine.get_receipt(receipt_id).await.is_none() {
+            return HttpResponse::json(
+                404,
+                json!({ "error": format!("receipt not found: {}", receipt_id) }),
+            );
+        }
+        match self.engine.release_escrow(receipt_id, actor).await {
+            Ok(receipt) => {
+                HttpResponse::json(200, serde_json::to_value(&receipt).unwrap_or(Value::Null))
+            }
+            Err(err) if actor.trim().is_empty() => {
+                HttpResponse::json(400, json!({ "error": err.to_string() }))
+            }
+            Err(err) => HttpResponse::json(409, json!({ "error": err.to_string() })),
+        }
+    }
+
+    async fn list_receipts(&self, request: &HttpRequest) -> HttpResponse {
+        let query = match request
+            .query_params()
+            .and_then(|params| receipt_query(&params))
+        {
+            Ok(query) => query,
+            Err(err) => return HttpResponse::json(400, json!({ "error": err.to_string() })),
+        };
+        match self.engine.list_receipts(&query).await {
+            Ok(receipts) => {
+                let receipts: Vec<_> = receipts.into_iter().map(published).collect();
+                HttpResponse::json(
+                    200,
+                    json!({ "count": receipts.len(), "receipts": receipts }),
+                )
+            }
+            Err(err) => HttpResponse::json(503, json!({ "error": err.to_string() })),
+        }
+    }
+
+    async fn public_verification(
+        &self,
+        request: &HttpRequest,
+        parse: impl FnOnce() -> Result<PublicVerificationQuery, String>,
+    ) -> HttpResponse {
+        let client = request.peer.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        if let Err(retry_after) = self.public_verify.check(client).await {
+            let seconds = retry_after.as_secs_f64().ceil() as u64;
+            return HttpResponse::json(
+                429,
+                json!({ "error": "rate limit exceeded", "retry_after_secs": seconds }),
+            )
+            .with_header("Retry-After", seconds.to_string());
+        }
+        let query = match parse() {
+            Ok(query) => query,
+            Err(err) => {
+                return HttpResponse::json(
+                    400,
+                    json!({ "error": format!("expected {{\"receipt_hash\": ...}} or {{\"receipt\": ...}}: {}", err) }),
+                )
+            }
+        };
+        if let PublicVerificationQuery::ReceiptHash(hash) = &query {
+            if !is_hash_hex(hash) {
+                return HttpResponse::json(
+                    400,
+                    json!({ "error": "receipt_hash must be a 0x-prefixed 32-byte hex hash" }),
+                );
+            }
+        }
+        let verification = self.engine.verify_public(&query).await;
+        HttpResponse::json(
+            200,
+            serde_json::to_value(&verification).unwrap_or(Value::Null),
+        )
+    }
+}
+
+// Filters use the wire spellings: ?venue=base&claim_type=ORDER_PLACED&status=PROVED&text=...&limit=50.
+pub fn receipt_query(params: &HashMap<String, String>) -> Result<ReceiptQuery> {
+    if let Some(name) = params.keys().find(|name| {
+        !matches!(
+            name.as_str(),
+            "venue" | "claim_type" | "status" | "text" | "limit"
+        )
+    }) {
+        return Err(anyhow!("unknown filter: {}", name));
+    }
+    let venue = params
+        .get("venue")
+        .map(|v| Venue::from_slug(v).ok_or_else(|| anyhow!("unknown venue: {}", v)))
+        .transpose()?;
+    let claim_type = params
+        .get("claim_type")
+        .map(|c| ClaimType::from_name(c).ok_or_else(|| anyhow!("unknown claim type: {}", c)))
+        .transpose()?;
+    let status = params
+        .get("status")
+        .map(|s| {
+            serde_json::from_value::<ReceiptStatus>(Value::String(s.clone()))
+                .map_err(|_| anyhow!("unknown receipt status: {}", s))
+        })
+        .transpose()?;
+    let limit = match params.get("limit") {
+        Some(limit) => limit
+            .parse::<usize>()
+            .map_err(|_| anyhow!("limit must be a number"))?,
+        None => DEFAULT_LIST_LIMIT,
+    };
+    Ok(ReceiptQuery {
+        venue,
+        claim_type,
+        status,
+        text: params.get("text").cloned().filter(|t| !t.is_empty()),
+        limit: Some(limit.clamp(1, MAX_LIST_LIMIT)),
+    })
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub async fn serve(listener: TcpListener, api: Arc<HttpApi>) -> Result<()> {
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .context("failed to accept HTTP connection")?;
+        let api = Arc::clone(&api);
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, &api).await;
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, api: &HttpApi) -> Result<()> {
+    let response = match read_request(&mut stream).await {
+        Ok(request) => api.route(&request).await,
+        Err(err) => HttpResponse::json(400, json!({ "error": err.to_string() })),
+    };
+    write_response(&mut stream, &response).await
+}
+
+pub(crate) async fn write_response(stream: &mut TcpStream, response: &HttpResponse) -> Result<()> {
+    let body = serde_json::to_vec(&response.body)?;
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.reason(),
+        body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+// The whole request has to arrive within `REQUEST_READ_TIMEOUT`, body included.
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest> {
+    tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request_head_and_body(stream))
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "request was not received within {} s",
+                REQUEST_READ_TIMEOUT.as_secs()
+            )
+        })?
+}
+
+async fn read_request_head_and_body(stream: &mut TcpStream) -> Result<HttpRequest> {
+    let peer = stream.peer_addr().ok().map(|addr| addr.ip());
+    let mut reader = BufReader::new(stream);
+    let request_line = read_head_line(&mut reader).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts
+        .next()
+        .ok_or_else(|| anyhow!("empty request line"))?
+        .to_string();
+    let target = parts
+        .next()
+        .ok_or_else(|| anyhow!("request line is missing a path"))?;
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+
+    let mut content_length = 0usize;
+    let mut authorization = None;
+    let mut headers = Vec::new();
+    loop {
+        let line = read_head_line(&mut reader).await?;
+        if line.trim().is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(anyhow!("request has more than {} headers", MAX_HEADERS));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .context("invalid Content-Length header")?;
+            } else if name.trim().eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_string());
+            }
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(anyhow!("request body exceeds {} bytes", MAX_BODY_BYTES));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(HttpRequest {
+        method,
+        path,
+        query,
+        authorization,
+        peer,
+        body,
+        headers,
+    })
+}
+
+// One line of the request head, at most `MAX_HEAD_LINE_BYTES`; empty at end of stream.
+async fn read_head_line(reader: &mut BufReader<&mut TcpStream>) -> Result<String> {
+    let mut line = Vec::new();
+    (&mut *reader)
+        .take(MAX_HEAD_LINE_BYTES as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .await?;
+    if line.len() > MAX_HEAD_LINE_BYTES {
+        return Err(anyhow!(
+            "request line or header exceeds {} bytes",
+            MAX_HEAD_LINE_BYTES
+        ));
+    }
+    String::from_utf8(line).context("request head is not valid UTF-8")
+}
@@ -0,0 +1,155 @@
+use crate::models::{now_iso, ReceiptStatus, SignerIdentity};
+use crate::signing::{verify_signature, ReceiptSigner};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const WATERMARK_SIGNING_DOMAIN: &str = "zkputer-watermark-v1";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuanceEntry {
+    pub key_id: String,
+    pub sequence: u64,
+    pub receipt_id: String,
+    pub receipt_hash: String,
+    pub status: ReceiptStatus,
+    pub issued_at: String,
+}
+
+// The latest sequence a signer has issued, signed by that signer. A verifier holding receipts
+// below the watermark can spot numbers it was never shown, and a receipt whose `issued_at` is
+// earlier than one with a lower sequence was back-dated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watermark {
+    pub signer: SignerIdentity,
+    pub sequence: u64,
+    pub receipt_hash: String,
+    pub issued_at: String,
+    pub published_at: String,
+    pub signature: String,
+}
+
+impl Watermark {
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!(
+            "{}:{}:{}:{}:{}",
+            WATERMARK_SIGNING_DOMAIN,
+            self.signer.key_id,
+            self.sequence,
+            self.receipt_hash,
+            self.issued_at
+        )
+        .into_bytes()
+    }
+
+    pub fn verify(&self) -> bool {
+        verify_signature(&self.signer, &self.signing_message(), &self.signature)
+    }
+}
+
+#[derive(Debug, Default)]
+struct LedgerState {
+    signers: HashMap<String, SignerIdentity>,
+    entries: HashMap<String, Vec<IssuanceEntry>>,
+}
+
+// Sequence numbers are consumed only when a receipt reaches a terminal status; PENDING and
+// AWAITING_FINALITY blocks are interim and stay unsequenced.
+#[derive(Debug, Clone, Default)]
+pub struct IssuanceLedger {
+    state: Arc<Mutex<LedgerState>>,
+}
+
+impl IssuanceLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn issue(
+        &self,
+        signer: &SignerIdentity,
+        receipt_id: &str,
+        receipt_hash: &str,
+        status: ReceiptStatus,
+    ) -> IssuanceEntry {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.signers.insert(signer.key_id.clone(), signer.clone());
+        let entries = state.entries.entry(signer.key_id.clone()).or_default();
+        let entry = IssuanceEntry {
+            key_id: signer.key_id.clone(),
+            sequence: entries.last().map_or(1, |e| e.sequence + 1),
+            receipt_id: receipt_id.to_string(),
+            receipt_hash: receipt_hash.to_string(),
+            status,
+            issued_at: now_iso(),
+        };
+        entries.push(entry.clone());
+        entry
+    }
+
+    // Re-registers an entry issued before a restart, or by another instance, so new sequences
+    // continue after it. Sequences already held are left alone.
+    pub fn restore(&self, signer: &SignerIdentity, entry: IssuanceEntry) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.signers.insert(signer.key_id.clone(), signer.clone());
+        let entries = state.entries.entry(signer.key_id.clone()).or_default();
+        if entries.iter().any(|e| e.sequence == entry.sequence) {
+            return;
+        }
+        entries.push(entry);
+        entries.sort_by_key(|e| e.sequence);
+    }
+
+    pub fn entries(&self, key_id: &str) -> Vec<IssuanceEntry> {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.entries.get(key_id).cloned().unwrap_or_default()
+    }
+
+    // `signer_for` supplies the signer for each key; a key it has no signer for gets no watermark.
+    pub fn watermarks(
+        &self,
+        signer_for: impl Fn(&SignerIdentity) -> Option<Arc<dyn ReceiptSigner>>,
+    ) -> Vec<Watermark> {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let published_at = now_iso();
+        let mut watermarks: Vec<Watermark> = state
+            .entries
+            .iter()
+            .filter_map(|(key_id, entries)| {
+                let latest = entries.last()?;
+                let identity = state.signers.get(key_id)?;
+                let signer = signer_for(identity)?;
+                let mut watermark = Watermark {
+                    signer: identity.clone(),
+                    sequence: latest.sequence,
+                    receipt_hash: latest.receipt_hash.clone(),
+                    issued_at: latest.issued_at.clone(),
+                    published_at: published_at.clone(),
+                    signature: String::new(),
+                };
+                watermark.signature = signer.sign(&watermark.signing_message());
+                Some(watermark)
+            })
+            .collect();
+        watermarks.sort_by(|a, b| a.signer.key_id.cmp(&b.signer.key_id));
+        watermarks
+    }
+}
+
+// Sequence numbers at or below `watermark` that are absent from `sequences`.
+pub fn missing_sequences(sequences: &[u64], watermark: u64) -> Vec<u64> {
+    let seen: std::collections::HashSet<u64> = sequences.iter().copied().collect();
+    (1..=watermark).filter(|s| !seen.contains(s)).collect()
+}
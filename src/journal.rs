@@ -0,0 +1,321 @@
+use crate::models::{hash_bytes, hash_json, now_iso};
+use crate::signing::{verify_ed25519, ReceiptSigner};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+pub const GENESIS_OBSERVATION_HASH: &str = "genesis";
+const JOURNAL_SIGNING_DOMAIN: &str = "zkputer-journal-v1";
+const JOURNAL_FILE_PREFIX: &str = "observations-";
+const JOURNAL_FILE_SUFFIX: &str = ".jsonl";
+
+// One raw venue response as an adapter received it. Only hashes are kept: the response bytes are
+// hashed as they came off the wire, status line and headers included, and the request body when
+// there was one. Entries chain across files, so a dropped, reordered or edited entry (or a missing
+// file between two others) breaks the journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Observation {
+    pub sequence: u64,
+    pub observed_at: String,
+    pub source: String,
+    pub endpoint: String,
+    pub method: String,
+    // The request path past the configured url, which may carry an API key and is left out.
+    pub path: String,
+    pub request_hash: Option<String>,
+    // None when the response was not HTTP at all.
+    pub status: Option<String>,
+    pub response_hash: String,
+    pub response_bytes: u64,
+    pub previous_hash: String,
+    pub entry_hash: String,
+    pub key_id: Option<String>,
+    pub signature: Option<String>,
+}
+
+impl Observation {
+    pub fn compute_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "sequence": self.sequence,
+            "observed_at": self.observed_at,
+            "source": self.source,
+            "endpoint": self.endpoint,
+            "method": self.method,
+            "path": self.path,
+            "request_hash": self.request_hash,
+            "status": self.status,
+            "response_hash": self.response_hash,
+            "response_bytes": self.response_bytes,
+            "previous_hash": self.previous_hash,
+        }))
+    }
+
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!("{}:{}", JOURNAL_SIGNING_DOMAIN, self.entry_hash).into_bytes()
+    }
+
+    // Whether `response` is the raw response this entry recorded.
+    pub fn records(&self, response: &[u8]) -> bool {
+        self.response_bytes == response.len() as u64 && self.response_hash == hash_bytes(response)
+    }
+}
+
+// What a transport hands the journal for one request.
+#[derive(Debug, Clone, Copy)]
+pub struct VenueExchange<'a> {
+    pub source: &'a str,
+    pub endpoint: &'a str,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub request: Option<&'a [u8]>,
+    pub status: Option<&'a str>,
+    pub response: &'a [u8],
+}
+
+struct Segment {
+    day: String,
+    part: u32,
+    file: File,
+    bytes: u64,
+}
+
+struct JournalState {
+    segment: Option<Segment>,
+    next_sequence: u64,
+    previous_hash: String,
+}
+
+// An append-only record of every response the adapters sharing it receive, one JSON line per
+// response in `observations-<UTC day>-<part>.jsonl` under one directory. A new file starts each UTC
+// day, and within a day once a file reaches `max_file_bytes`. With a signer every entry is signed
+// as well, so the operator can show what a venue returned even when it was never selected as
+// receipt evidence. Clones share the open file.
+#[derive(Clone)]
+pub struct ObservationJournal {
+    dir: PathBuf,
+    max_file_bytes: Option<u64>,
+    signer: Option<Arc<dyn ReceiptSigner>>,
+    state: Arc<Mutex<JournalState>>,
+}
+
+impl ObservationJournal {
+    // Picks up the chain where the newest file in `dir` left it, appending to that file while it
+    // is still the current day's.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create journal directory {}", dir.display()))?;
+        let mut state = JournalState {
+            segment: None,
+            next_sequence: 0,
+            previous_hash: GENESIS_OBSERVATION_HASH.to_string(),
+        };
+        if let Some((path, day, part)) = journal_files(&dir)?.pop() {
+            if let Some(last) = read_journal_file(&path)?.pop() {
+                state.next_sequence = last.sequence + 1;
+                state.previous_hash = last.entry_hash;
+            }
+            if day == today() {
+                let file = OpenOptions::new().append(true).open(&path)?;
+                let bytes = file.metadata()?.len();
+                state.segment = Some(Segment {
+                    day,
+                    part,
+                    file,
+                    bytes,
+                });
+            }
+        }
+        Ok(Self {
+            dir,
+            max_file_bytes: None,
+            signer: None,
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    pub fn with_max_file_bytes(mut self, max_file_bytes: u64) -> Self {
+        self.max_file_bytes = Some(max_file_bytes.max(1));
+        self
+    }
+
+    pub fn with_signer(mut self, signer: Arc<dyn ReceiptSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn record(&self, exchange: VenueExchange<'_>) -> Result<Observation> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut entry = Observation {
+            sequence: state.next_sequence,
+            observed_at: now_iso(),
+            source: exchange.source.to_string(),
+            endpoint: exchange.endpoint.to_string(),
+            method: exchange.method.to_string(),
+            path: exchange.path.to_string(),
+            request_hash: exchange.request.map(hash_bytes),
+            status: exchange.status.map(str::to_string),
+            response_hash: hash_bytes(exchange.response),
+            response_bytes: exchange.response.len() as u64,
+            previous_hash: state.previous_hash.clone(),
+            entry_hash: String::new(),
+            key_id: None,
+            signature: None,
+        };
+        entry.entry_hash = entry.compute_hash();
+        if let Some(signer) = &self.signer {
+            entry.key_id = Some(signer.identity().key_id.clone());
+            entry.signature = Some(signer.sign(&entry.signing_message()));
+        }
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        let day = today();
+        let part = match &state.segment {
+            Some(segment)
+                if segment.day == day
+                    && self.max_file_bytes.is_some_and(|max| segment.bytes >= max) =>
+            {
+                Some(segment.part + 1)
+            }
+            Some(segment) if segment.day == day => None,
+            _ => Some(0),
+        };
+        if let Some(part) = part {
+            let path = self.dir.join(journal_file_name(&day, part));
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("failed to open journal file {}", path.display()))?;
+            let bytes = file.metadata()?.len();
+            state.segment = Some(Segment {
+                day,
+                part,
+                file,
+                bytes,
+            });
+        }
+        let segment = state
+            .segment
+            .as_mut()
+            .expect("journal segment was just opened");
+        segment
+            .file
+            .write_all(&line)
+            .context("failed to append to the observation journal")?;
+        segment.bytes += line.len() as u64;
+        state.next_sequence += 1;
+        state.previous_hash = entry.entry_hash.clone();
+        Ok(entry)
+    }
+}
+
+// Every entry in `dir`, oldest first.
+pub fn read_journal(dir: impl AsRef<Path>) -> Result<Vec<Observation>> {
+    let mut entries = Vec::new();
+    for (path, _, _) in journal_files(dir.as_ref())? {
+        entries.extend(read_journal_file(&path)?);
+    }
+    Ok(entries)
+}
+
+// Checks the chain through every file in `dir` and, given the signer's public key, that each
+// entry is signed by it. Older files may have been archived away, so the chain is checked from the
+// first entry present rather than from genesis. Returns the number of entries.
+pub fn verify_journal(dir: impl AsRef<Path>, public_key: Option<&str>) -> Result<usize> {
+    let entries = read_journal(dir)?;
+    let mut expected: Option<(u64, String)> = None;
+    for entry in &entries {
+        if let Some((sequence, previous_hash)) = &expected {
+            if entry.sequence != *sequence || entry.previous_hash != *previous_hash {
+                bail!("observation journal is broken at entry {}", entry.sequence);
+            }
+        }
+        if entry.entry_hash != entry.compute_hash() {
+            bail!("observation journal entry {} was altered", entry.sequence);
+        }
+        if let Some(public_key) = public_key {
+            let signed = entry.signature.as_deref().is_some_and(|signature| {
+                verify_ed25519(public_key, &entry.signing_message(), signature)
+            });
+            if !signed {
+                bail!(
+                    "observation journal entry {} is not signed by {}",
+                    entry.sequence,
+                    public_key
+                );
+            }
+        }
+        expected = Some((entry.sequence + 1, entry.entry_hash.clone()));
+    }
+    Ok(entries.len())
+}
+
+fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
+fn journal_file_name(day: &str, part: u32) -> String {
+    format!(
+        "{}{}-{:03}{}",
+        JOURNAL_FILE_PREFIX, day, part, JOURNAL_FILE_SUFFIX
+    )
+}
+
+// Journal files in `dir` with their day and part, in the order they were written.
+fn journal_files(dir: &Path) -> Result<Vec<(PathBuf, String, u32)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read journal directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(stem) = name
+            .strip_prefix(JOURNAL_FILE_PREFIX)
+            .and_then(|rest| rest.strip_suffix(JOURNAL_FILE_SUFFIX))
+        else {
+            continue;
+        };
+        let Some((day, part)) = stem.rsplit_once('-') else {
+            continue;
+        };
+        if let Ok(part) = part.parse::<u32>() {
+            files.push((path.clone(), day.to_string(), part));
+        }
+    }
+    files.sort_by(|a, b| (&a.1, a.2).cmp(&(&b.1, b.2)));
+    Ok(files)
+}
+
+fn read_journal_file(path: &Path) -> Result<Vec<Observation>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read journal file {}", path.display()))?;
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                anyhow!(
+                    "{} line {} is not a journal entry: {}",
+                    path.display(),
+                    index + 1,
+                    err
+                )
+            })
+        })
+        .collect()
+}
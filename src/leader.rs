@@ -0,0 +1,179 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The lease engines sharing a store compete for unless told otherwise.
+pub const DEFAULT_LEASE_NAME: &str = "receipt-engine";
+pub const DEFAULT_LEASE_TTL: Duration = Duration::from_secs(15);
+
+// Who holds a named lease and until when, in unix milliseconds. `epoch` goes up each time the
+// lease changes hands, so it tells tenures apart even when one holder comes back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lease {
+    pub name: String,
+    pub holder: String,
+    pub epoch: u64,
+    pub expires_at_ms: u64,
+}
+
+impl Lease {
+    // The lease after `holder` asks for it at `now_ms`: renewed if it already holds it, taken over
+    // if it is free or expired, and otherwise left with its holder.
+    pub fn claim(
+        current: Option<&Lease>,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+        now_ms: u64,
+    ) -> Lease {
+        let expires_at_ms = now_ms.saturating_add(ttl.as_millis() as u64);
+        match current {
+            Some(lease) if lease.holder == holder => Lease {
+                expires_at_ms,
+                ..lease.clone()
+            },
+            Some(lease) if lease.expires_at_ms > now_ms => lease.clone(),
+            current => Lease {
+                name: name.to_string(),
+                holder: holder.to_string(),
+                epoch: current.map_or(0, |lease| lease.epoch) + 1,
+                expires_at_ms,
+            },
+        }
+    }
+
+    pub fn is_held_by(&self, holder: &str, now_ms: u64) -> bool {
+        self.holder == holder && self.expires_at_ms > now_ms
+    }
+
+    // Errors unless `current`, the lease as the store holds it now, is still this tenure. A write
+    // fenced by an older tenure comes from a leader that has since been replaced.
+    pub fn check_fence(&self, current: Option<&Lease>) -> Result<()> {
+        let reason = match current {
+            Some(lease) if lease.holder == self.holder && lease.epoch == self.epoch => {
+                return Ok(())
+            }
+            Some(lease) => format!(
+                "the {} lease passed from {} (epoch {}) to {} (epoch {})",
+                self.name, self.holder, self.epoch, lease.holder, lease.epoch
+            ),
+            None => format!("nobody holds the {} lease", self.name),
+        };
+        Err(WriteFenced(reason).into())
+    }
+}
+
+// A receipt write refused because the writer no longer holds the tenure it was fenced on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteFenced(pub String);
+
+impl std::fmt::Display for WriteFenced {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "write fenced: {}", self.0)
+    }
+}
+
+impl std::error::Error for WriteFenced {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineRole {
+    Leader,
+    Standby,
+}
+
+// An engine's candidacy for its store's lease. Clones share what was last observed, so the
+// pipeline tasks an engine spawns see it step down.
+#[derive(Debug, Clone)]
+pub struct LeaderElection {
+    holder: String,
+    lease_name: String,
+    ttl: Duration,
+    observed: Arc<Mutex<Option<Lease>>>,
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl LeaderElection {
+    pub fn new(holder: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            holder: holder.into(),
+            lease_name: DEFAULT_LEASE_NAME.to_string(),
+            ttl,
+            observed: Arc::default(),
+            last_error: Arc::default(),
+        }
+    }
+
+    // Engines on separate lease names lead independently, e.g. one per venue set.
+    pub fn with_lease_name(mut self, lease_name: impl Into<String>) -> Self {
+        self.lease_name = lease_name.into();
+        self
+    }
+
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    pub fn lease_name(&self) -> &str {
+        &self.lease_name
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    // Renewing three times per lease leaves two attempts to spare before it lapses.
+    pub fn renew_interval(&self) -> Duration {
+        self.ttl / 3
+    }
+
+    // The lease as this candidate last saw it.
+    pub fn lease(&self) -> Option<Lease> {
+        self.observed().clone()
+    }
+
+    // Leader only while the lease it last renewed is unexpired by the local clock, so a leader cut
+    // off from the store stops acting as one before a standby can take over.
+    pub fn role(&self) -> EngineRole {
+        match self.observed().as_ref() {
+            Some(lease) if lease.is_held_by(&self.holder, now_ms()) => EngineRole::Leader,
+            _ => EngineRole::Standby,
+        }
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.role() == EngineRole::Leader
+    }
+
+    // Why the last campaign failed; None once one succeeds.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn observe(&self, lease: Option<Lease>) {
+        *self.observed() = lease;
+    }
+
+    pub(crate) fn record_outcome(&self, err: Option<&anyhow::Error>) {
+        *self
+            .last_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = err.map(|err| format!("{:#}", err));
+    }
+
+    fn observed(&self) -> std::sync::MutexGuard<'_, Option<Lease>> {
+        self.observed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64)
+}
@@ -1,19 +1,85 @@
+pub mod adapter_cache;
 pub mod adapters;
+pub mod anchoring;
+pub mod audit;
+pub mod backfill;
+pub mod circuits;
+pub mod claims;
+pub mod clock;
+pub mod commitments;
+pub mod config;
+pub mod conformance;
+pub mod credentials;
+pub mod deadline;
+pub mod dispute;
 pub mod engine;
+pub mod escrow;
+pub mod events;
+pub mod export;
+pub mod federation;
+pub mod finality;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod hashing;
+pub mod health;
+pub mod http;
+pub mod issuance;
+pub mod journal;
+pub mod leader;
+pub mod mcp;
+pub mod mcp_http;
 pub mod models;
+pub mod oracles;
+pub mod orderbook;
+pub mod payload_encryption;
+pub mod persistence;
 pub mod policy;
+pub mod presentation;
+pub mod proof_queue;
 pub mod prover;
+pub mod redaction;
+pub mod references;
+pub mod registry;
+pub mod report;
+pub mod retention;
+#[cfg(feature = "risc0")]
+pub mod risc0;
+pub mod schema;
+pub mod screening;
+pub mod search;
+pub mod signing;
+pub mod slo;
+pub mod smart_account;
+#[cfg(feature = "sp1")]
+pub mod sp1;
+pub mod stats;
+pub mod store;
 pub mod templates;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+pub mod validation;
 pub mod verifier;
+pub mod versioning;
+pub mod webhooks;
+#[cfg(any(feature = "sp1", feature = "risc0"))]
+mod zkvm_host;
 
+pub use commitments::CommitmentMode;
+pub use config::{Config, ReceiptEngineBuilder};
 pub use engine::ReceiptEngine;
+pub use hashing::HashAlgorithm;
 pub use models::{
-    ClaimType, NonProvableReason, ProofRequest, ReceiptStatus, Venue, ZKReceipt,
+    ClaimType, NonProvableReason, ProofRequest, ReceiptStatus, SignerIdentity, Venue, ZKReceipt,
 };
 pub use prover::{
     build_mvp_prover, FallbackProver, PicoMvpProver, ProverBackend, ProverStrategy, Sp1MvpProver,
 };
+pub use signing::{
+    Ed25519ReceiptSigner, IntegrityVerification, KeyManifest, KeyRing, ReceiptSigner, SignerKey,
+};
+pub use store::{ExportCursor, ReceiptQuery, ReceiptStore, ReceiptWatch};
 pub use templates::{
     build_request_from_template, list_verification_templates, template_ids,
-    TEMPLATE_ORDER_PLACEMENT_VERIFICATION, TEMPLATE_TRADE_EXECUTION_VERIFICATION,
+    TEMPLATE_BEST_EXECUTION_VERIFICATION, TEMPLATE_ORDER_PLACEMENT_VERIFICATION,
+    TEMPLATE_TRADE_EXECUTION_VERIFICATION,
 };
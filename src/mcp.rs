@@ -0,0 +1,1193 @@
+use crate::dispute::{export_dispute_bundle, DEFAULT_DISPUTE_INLINE_LIMIT};
+use crate::escrow::{is_withheld, published};
+use crate::models::{ClaimType, NonProvableReason, ProofRequest, ReceiptStatus, Venue, ZKReceipt};
+use crate::redaction::RedactionConfig;
+use crate::registry::{claim_names, venue_slugs};
+use crate::schema::validate_receipt_json;
+use crate::store::{ExportCursor, ReceiptQuery};
+use crate::templates::{build_request_from_template, list_verification_templates, template_ids};
+use crate::ReceiptEngine;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+
+// Newest first. MCP versions are dates, so they order as strings.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+pub const LATEST_PROTOCOL_VERSION: &str = SUPPORTED_PROTOCOL_VERSIONS[0];
+pub const SERVER_NAME: &str = "zkputer-mcp";
+pub const DEFAULT_LIST_PAGE_SIZE: usize = 20;
+pub const MAX_LIST_PAGE_SIZE: usize = 100;
+pub const RECEIPT_URI_SCHEME: &str = "zkreceipt://";
+pub const RECEIPT_MIME_TYPE: &str = "application/json";
+
+// JSON-RPC codes for errors that are not the method's usual one.
+const INVALID_PARAMS: i64 = -32602;
+const METHOD_NOT_FOUND: i64 = -32601;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+// Tool results carry `structuredContent` from this version on.
+const STRUCTURED_CONTENT_SINCE: &str = "2025-06-18";
+// Progress notifications carry a human-readable `message` from this version on.
+const PROGRESS_MESSAGE_SINCE: &str = "2025-03-26";
+// Tool definitions carry `annotations` from this version on.
+const TOOL_ANNOTATIONS_SINCE: &str = "2025-03-26";
+
+// The optional parts of the server an operator has switched on. Only what is on is advertised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct McpFeatures {
+    pub resources: bool,
+    pub notifications: bool,
+    pub streaming: bool,
+}
+
+impl McpFeatures {
+    // Comma-separated feature names, e.g. ZKPUTER_MCP_FEATURES=notifications,streaming.
+    pub fn from_env(value: Option<&str>) -> Self {
+        let mut features = Self::default();
+        for name in value
+            .unwrap_or("")
+            .split(',')
+            .map(|f| f.trim().to_ascii_lowercase())
+        {
+            match name.as_str() {
+                "resources" => features.resources = true,
+                "notifications" => features.notifications = true,
+                "streaming" => features.streaming = true,
+                "all" => {
+                    features = Self {
+                        resources: true,
+                        notifications: true,
+                        streaming: true,
+                    }
+                }
+                _ => {}
+            }
+        }
+        features
+    }
+}
+
+// MCP's behaviour hints for a tool, which clients use to decide what needs the user's confirmation.
+// They are hints: a client should not rely on them for servers it does not trust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ToolAnnotations {
+    pub read_only_hint: bool,
+    pub destructive_hint: bool,
+    pub idempotent_hint: bool,
+    pub open_world_hint: bool,
+}
+
+impl ToolAnnotations {
+    // Lookups touch nothing and ask nothing of a venue.
+    pub const READ_ONLY: Self = Self {
+        read_only_hint: true,
+        destructive_hint: false,
+        idempotent_hint: true,
+        open_world_hint: false,
+    };
+
+    // What this server ships for each of its tools. Submitting creates a new receipt per call and
+    // queries venues, but never changes or removes what is already there; exporting a dispute
+    // bundle may write a file, the same one each time.
+    pub fn for_tool(name: &str) -> Option<Self> {
+        match name {
+            "zkputer_verify_template" | "zkputer_verify_claim" => Some(Self {
+                read_only_hint: false,
+                destructive_hint: false,
+                idempotent_hint: false,
+                open_world_hint: true,
+            }),
+            "zkputer_export_dispute_bundle" => Some(Self {
+                read_only_hint: false,
+                ..Self::READ_ONLY
+            }),
+            "zkputer_list_templates"
+            | "zkputer_get_payload_schemas"
+            | "zkputer_get_receipt"
+            | "zkputer_verify_receipt"
+            | "zkputer_list_receipts"
+            | "zkputer_get_stats" => Some(Self::READ_ONLY),
+            _ => None,
+        }
+    }
+}
+
+// The client's requested version when this server speaks it, otherwise the newest one it does; the
+// client then decides whether it can work with that.
+pub fn negotiate_protocol_version(requested: Option<&str>) -> &'static str {
+    requested
+        .and_then(|requested| {
+            SUPPORTED_PROTOCOL_VERSIONS
+                .iter()
+                .find(|v| **v == requested)
+        })
+        .copied()
+        .unwrap_or(LATEST_PROTOCOL_VERSION)
+}
+
+// Per-connection protocol state. Until `initialize` arrives the session behaves as the newest
+// version, which is what the server did before negotiation existed.
+// Clones share the outbound channel and resource subscriptions, so a transport may hand each
+// request its own copy.
+#[derive(Debug, Clone)]
+pub struct McpSession {
+    features: McpFeatures,
+    protocol_version: &'static str,
+    client_info: Option<Value>,
+    outbound: Arc<Mutex<Option<mpsc::UnboundedSender<Value>>>>,
+    subscriptions: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+impl McpSession {
+    pub fn new(features: McpFeatures) -> Self {
+        Self {
+            features,
+            protocol_version: LATEST_PROTOCOL_VERSION,
+            client_info: None,
+            outbound: Arc::new(Mutex::new(None)),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // Where messages that answer no request (resource updates) go. A transport attaches one when
+    // the client opens a server-to-client stream; updates raised while none is attached are dropped.
+    pub fn attach_outbound(&self, sender: mpsc::UnboundedSender<Value>) {
+        *self.outbound.lock().expect("mcp outbound lock poisoned") = Some(sender);
+    }
+
+    pub fn detach_outbound(&self) {
+        self.outbound
+            .lock()
+            .expect("mcp outbound lock poisoned")
+            .take();
+    }
+
+    pub fn subscriptions(&self) -> Vec<String> {
+        let mut uris: Vec<String> = self
+            .subscriptions
+            .lock()
+            .expect("mcp subscription lock poisoned")
+            .keys()
+            .cloned()
+            .collect();
+        uris.sort();
+        uris
+    }
+
+    // Ends every resource subscription; transports call it when the session goes away.
+    pub fn close(&self) {
+        for (_, task) in self
+            .subscriptions
+            .lock()
+            .expect("mcp subscription lock poisoned")
+            .drain()
+        {
+            task.abort();
+        }
+        self.detach_outbound();
+    }
+
+    // Sends `notifications/resources/updated` for each write to the receipt until unsubscribed.
+    fn watch_resource(&self, uri: String, mut updates: watch::Receiver<ZKReceipt>) {
+        updates.borrow_and_update();
+        let outbound = Arc::clone(&self.outbound);
+        let notified = uri.clone();
+        let task = tokio::spawn(async move {
+            while updates.changed().await.is_ok() {
+                let notification = json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": { "uri": notified }
+                });
+                if let Some(sender) = outbound
+                    .lock()
+                    .expect("mcp outbound lock poisoned")
+                    .as_ref()
+                {
+                    let _ = sender.send(notification);
+                }
+            }
+        });
+        if let Some(previous) = self
+            .subscriptions
+            .lock()
+            .expect("mcp subscription lock poisoned")
+            .insert(uri, task)
+        {
+            previous.abort();
+        }
+    }
+
+    fn unwatch_resource(&self, uri: &str) {
+        if let Some(task) = self
+            .subscriptions
+            .lock()
+            .expect("mcp subscription lock poisoned")
+            .remove(uri)
+        {
+            task.abort();
+        }
+    }
+
+    pub fn features(&self) -> McpFeatures {
+        self.features
+    }
+
+    pub fn protocol_version(&self) -> &'static str {
+        self.protocol_version
+    }
+
+    pub fn client_info(&self) -> Option<&Value> {
+        self.client_info.as_ref()
+    }
+
+    pub fn supports(&self, since: &str) -> bool {
+        self.protocol_version >= since
+    }
+
+    pub fn initialize(&mut self, params: &Value, server_version: &str) -> Value {
+        self.protocol_version =
+            negotiate_protocol_version(params.get("protocolVersion").and_then(|v| v.as_str()));
+        self.client_info = params.get("clientInfo").cloned();
+        json!({
+            "protocolVersion": self.protocol_version,
+            "capabilities": self.capabilities(),
+            "serverInfo": {
+                "name": SERVER_NAME,
+                "version": server_version
+            }
+        })
+    }
+
+    pub fn capabilities(&self) -> Value {
+        let mut capabilities = Map::new();
+        capabilities.insert("tools".to_string(), json!({ "listChanged": false }));
+        if self.features.resources {
+            capabilities.insert(
+                "resources".to_string(),
+                json!({ "subscribe": self.features.notifications, "listChanged": false }),
+            );
+        }
+        // What MCP has no standard flag for is advertised under our own experimental key.
+        capabilities.insert(
+            "experimental".to_string(),
+            json!({
+                "zkputer": {
+                    "progressNotifications": self.features.notifications,
+                    "streaming": self.features.streaming,
+                    "structuredContent": self.supports(STRUCTURED_CONTENT_SINCE)
+                }
+            }),
+        );
+        Value::Object(capabilities)
+    }
+
+    // Text content always carries the whole payload, so clients too old for structured content
+    // lose nothing when it is left out.
+    pub fn tool_result(&self, text: String, structured: Value) -> Value {
+        let mut result = json!({
+            "content": [{
+                "type": "text",
+                "text": text
+            }]
+        });
+        if self.supports(STRUCTURED_CONTENT_SINCE) {
+            result["structuredContent"] = structured;
+        }
+        result
+    }
+
+    // None when notifications are off; the caller sends nothing.
+    pub fn progress_notification(
+        &self,
+        token: &Value,
+        progress: u64,
+        message: &str,
+    ) -> Option<Value> {
+        if !self.features.notifications {
+            return None;
+        }
+        let mut params = json!({ "progressToken": token, "progress": progress });
+        if self.supports(PROGRESS_MESSAGE_SINCE) {
+            params["message"] = json!(message);
+        }
+        Some(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": params
+        }))
+    }
+}
+
+// What an agent needs to pick a receipt out of a listing; the full receipt is one
+// zkputer_get_receipt call away.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReceiptSummary {
+    pub receipt_id: String,
+    pub status: ReceiptStatus,
+    pub venue: Venue,
+    pub claim_type: ClaimType,
+    pub account_ref: String,
+    pub order_ref: String,
+    pub execution_ref: Option<String>,
+    pub statement: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub non_provable_reason: Option<NonProvableReason>,
+}
+
+impl ReceiptSummary {
+    pub fn of(receipt: &ZKReceipt) -> Self {
+        Self {
+            receipt_id: receipt.receipt_id.clone(),
+            status: receipt.status,
+            venue: receipt.subject.venue,
+            claim_type: receipt.claim.r#type,
+            account_ref: receipt.subject.account_ref.clone(),
+            order_ref: receipt.subject.order_ref.clone(),
+            execution_ref: receipt.subject.execution_ref.clone(),
+            statement: receipt.claim.statement.clone(),
+            created_at: receipt.timing.created_at.clone(),
+            updated_at: receipt.timing.updated_at.clone(),
+            non_provable_reason: receipt.non_provable.as_ref().map(|n| n.reason_code),
+        }
+    }
+}
+
+// Writes a notification to the client ahead of the response it belongs to.
+pub type Notify<'a> = &'a mut (dyn FnMut(Value) -> Result<()> + Send);
+
+// Transport-independent MCP request handling: the stdio loop and the HTTP transports both hand
+// each JSON-RPC message to `handle` with the connection's session.
+pub struct McpServer {
+    engine: Arc<ReceiptEngine>,
+    redaction: RedactionConfig,
+    features: McpFeatures,
+    annotations: HashMap<String, ToolAnnotations>,
+}
+
+impl McpServer {
+    pub fn new(engine: Arc<ReceiptEngine>, features: McpFeatures) -> Self {
+        Self {
+            engine,
+            redaction: RedactionConfig::default(),
+            features,
+            annotations: tool_names()
+                .into_iter()
+                .filter_map(|name| Some((name.clone(), ToolAnnotations::for_tool(&name)?)))
+                .collect(),
+        }
+    }
+
+    pub fn with_redaction(mut self, redaction: RedactionConfig) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    // Replaces what the server advertises for one tool, e.g. to have clients confirm every
+    // submission on a deployment where verification is billed.
+    pub fn with_tool_annotations(
+        mut self,
+        tool: &str,
+        annotations: ToolAnnotations,
+    ) -> Result<Self> {
+        if !tool_names().iter().any(|name| name == tool) {
+            return Err(anyhow!("unknown tool {}", tool));
+        }
+        self.annotations.insert(tool.to_string(), annotations);
+        Ok(self)
+    }
+
+    // Overrides from a JSON object keyed by tool name, each naming only the hints it changes:
+    // `{"zkputer_verify_claim": {"destructiveHint": true}}`. ZKPUTER_MCP_TOOL_ANNOTATIONS points
+    // the binary at such a file.
+    pub fn with_tool_annotation_overrides(mut self, overrides: &Value) -> Result<Self> {
+        let overrides = overrides.as_object().ok_or_else(|| {
+            anyhow!("tool annotation overrides must be a JSON object keyed by tool name")
+        })?;
+        for (tool, hints) in overrides {
+            let hints = hints
+                .as_object()
+                .ok_or_else(|| anyhow!("annotation overrides for {} must be an object", tool))?;
+            let mut merged = serde_json::to_value(
+                self.annotations
+                    .get(tool)
+                    .copied()
+                    .unwrap_or(ToolAnnotations::READ_ONLY),
+            )?;
+            for (hint, value) in hints {
+                merged[hint] = value.clone();
+            }
+            let annotations = serde_json::from_value(merged)
+                .map_err(|err| anyhow!("annotation overrides for {}: {}", tool, err))?;
+            self = self.with_tool_annotations(tool, annotations)?;
+        }
+        Ok(self)
+    }
+
+    pub fn tool_annotations(&self, tool: &str) -> Option<ToolAnnotations> {
+        self.annotations.get(tool).copied()
+    }
+
+    // The tool list as this session sees it: annotated for clients that know annotations.
+    pub fn tool_definitions(&self, session: &McpSession) -> Value {
+        let mut definitions = tool_list();
+        if !session.supports(TOOL_ANNOTATIONS_SINCE) {
+            return definitions;
+        }
+        for tool in definitions["tools"].as_array_mut().into_iter().flatten() {
+            if let Some(annotations) = tool["name"]
+                .as_str()
+                .and_then(|name| self.tool_annotations(name))
+            {
+                tool["annotations"] = json!(annotations);
+            }
+        }
+        definitions
+    }
+
+    pub fn features(&self) -> McpFeatures {
+        self.features
+    }
+
+    pub fn engine(&self) -> &Arc<ReceiptEngine> {
+        &self.engine
+    }
+
+    pub fn session(&self) -> McpSession {
+        McpSession::new(self.features)
+    }
+
+    // The response to one message, or None when the message was a notification.
+    pub async fn handle(
+        &self,
+        session: &mut McpSession,
+        message: &[u8],
+        notify: Notify<'_>,
+    ) -> Option<Value> {
+        let request: JsonRpcRequest = match serde_json::from_slice(message) {
+            Ok(request) => request,
+            Err(err) => {
+                return Some(error_response(
+                    Value::Null,
+                    -32700,
+                    format!("Parse error: {}", err),
+                ))
+            }
+        };
+        let id = request.id?;
+        let method = request.method.as_str();
+        let params = request.params.unwrap_or_else(|| json!({}));
+        let result = match method {
+            "initialize" => Ok(session.initialize(&params, env!("CARGO_PKG_VERSION"))),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(self.tool_definitions(session)),
+            "tools/call" => self.call_tool(session, &params, notify).await,
+            "resources/list" if self.features.resources => self.list_resources(&params).await,
+            "resources/templates/list" if self.features.resources => Ok(resource_templates()),
+            "resources/read" if self.features.resources => self.read_resource(&params).await,
+            "resources/subscribe" if self.features.resources && self.features.notifications => {
+                self.subscribe_resource(session, &params).await
+            }
+            "resources/unsubscribe" if self.features.resources && self.features.notifications => {
+                resource_uri(&params).map(|uri| {
+                    session.unwatch_resource(uri);
+                    json!({})
+                })
+            }
+            _ => Err(anyhow!("Method not found: {}", method)),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(err) => {
+                let code = match err.downcast_ref::<RpcError>() {
+                    Some(rpc) => rpc.code,
+                    None if method == "tools/call" => -32000,
+                    None => METHOD_NOT_FOUND,
+                };
+                error_response(id, code, err.to_string())
+            }
+        })
+    }
+
+    // Every receipt, oldest first, one page per call.
+    async fn list_resources(&self, params: &Value) -> Result<Value> {
+        let after = match params.get("cursor").and_then(|v| v.as_str()) {
+            Some(cursor) => Some(
+                ExportCursor::decode(cursor)
+                    .map_err(|err| RpcError::new(INVALID_PARAMS, err.to_string()))?,
+            ),
+            None => None,
+        };
+        let mut page = self
+            .engine
+            .page_receipts(
+                &ReceiptQuery::default(),
+                after.as_ref(),
+                MAX_LIST_PAGE_SIZE + 1,
+            )
+            .await?;
+        let has_more = page.len() > MAX_LIST_PAGE_SIZE;
+        page.truncate(MAX_LIST_PAGE_SIZE);
+        // Descriptions leave out refs and statements, which redaction may need to mask; reading
+        // the resource applies it.
+        let resources: Vec<Value> = page
+            .iter()
+            .map(|receipt| {
+                json!({
+                    "uri": receipt_uri(&receipt.receipt_id),
+                    "name": receipt.receipt_id,
+                    "description": format!(
+                        "{} receipt on {}, {:?}",
+                        receipt.claim.r#type.name(),
+                        receipt.subject.venue.slug(),
+                        receipt.status
+                    ),
+                    "mimeType": RECEIPT_MIME_TYPE
+                })
+            })
+            .collect();
+        let mut result = json!({ "resources": resources });
+        if let Some(last) = page.last().filter(|_| has_more) {
+            result["nextCursor"] = json!(ExportCursor::of(last).encode());
+        }
+        Ok(result)
+    }
+
+    async fn read_resource(&self, params: &Value) -> Result<Value> {
+        let uri = resource_uri(params)?;
+        let receipt = self.resolve_resource(uri).await?;
+        let payload = serde_json::to_value(&receipt)?;
+        Ok(json!({
+            "contents": [{
+                "uri": uri,
+                "mimeType": RECEIPT_MIME_TYPE,
+                "text": self.redaction.render_text(&payload)
+            }]
+        }))
+    }
+
+    async fn subscribe_resource(&self, session: &McpSession, params: &Value) -> Result<Value> {
+        let uri = resource_uri(params)?;
+        let receipt = self.resolve_resource(uri).await?;
+        let updates = self.engine.receipt_updates(&receipt.receipt_id).await?;
+        session.watch_resource(uri.to_string(), updates);
+        Ok(json!({}))
+    }
+
+    async fn resolve_resource(&self, uri: &str) -> Result<ZKReceipt> {
+        let not_found =
+            || RpcError::new(RESOURCE_NOT_FOUND, format!("Resource not found: {}", uri));
+        let receipt_id = receipt_id_from_uri(uri).ok_or_else(not_found)?;
+        Ok(self
+            .engine
+            .published_receipt(receipt_id)
+            .await
+            .ok_or_else(not_found)?)
+    }
+
+    fn tool_result(&self, session: &McpSession, payload: &Value) -> Value {
+        session.tool_result(
+            self.redaction.render_text(payload),
+            self.redaction.structured(payload),
+        )
+    }
+
+    // Sends one progress notification per status the receipt passes through while the call waits.
+    async fn wait_with_progress(
+        &self,
+        session: &McpSession,
+        receipt_id: &str,
+        token: &Value,
+        timeout: Duration,
+        notify: Notify<'_>,
+    ) -> Result<ZKReceipt> {
+        let mut statuses = self.engine.subscribe(receipt_id).await?;
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut progress = 0;
+        loop {
+            let status = *statuses.borrow_and_update();
+            progress += 1;
+            if let Some(notification) = session.progress_notification(
+                token,
+                progress,
+                &format!("receipt {} is {:?}", receipt_id, status),
+            ) {
+                notify(notification)?;
+            }
+            if status.is_terminal() {
+                break;
+            }
+            match tokio::time::timeout_at(deadline, statuses.changed()).await {
+                Ok(Ok(())) => {}
+                // The channel closes once the receipt is terminal; the final fetch below reports it.
+                Ok(Err(_)) => break,
+                Err(_) => return Err(anyhow!("timed out waiting for receipt {}", receipt_id)),
+            }
+        }
+        self.engine
+            .published_receipt(receipt_id)
+            .await
+            .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt_id))
+    }
+
+    async fn submit_and_render_receipt(
+        &self,
+        session: &McpSession,
+        request: ProofRequest,
+        arguments: &Value,
+        progress_token: Option<&Value>,
+        notify: Notify<'_>,
+    ) -> Result<Value> {
+        let (wait_for_result, wait_timeout_ms) = parse_wait_options(arguments);
+        let receipt_id = self.engine.submit(request).await?;
+        let timeout = Duration::from_millis(wait_timeout_ms);
+        let receipt = if wait_for_result {
+            match progress_token.filter(|_| session.features().notifications) {
+                Some(token) => {
+                    self.wait_with_progress(session, &receipt_id, token, timeout, notify)
+                        .await?
+                }
+                None => published(self.engine.wait_for_receipt(&receipt_id, timeout).await?),
+            }
+        } else {
+            self.engine
+                .published_receipt(&receipt_id)
+                .await
+                .ok_or_else(|| anyhow!("receipt not found after submit"))?
+        };
+        let payload = serde_json::to_value(&receipt)?;
+        Ok(self.tool_result(session, &payload))
+    }
+
+    async fn call_tool(
+        &self,
+        session: &McpSession,
+        params: &Value,
+        notify: Notify<'_>,
+    ) -> Result<Value> {
+        let engine = &self.engine;
+        let name = params
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("tools/call missing name"))?;
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        let progress_token = params.pointer("/_meta/progressToken");
+
+        match name {
+            "zkputer_list_templates" => {
+                let templates_payload = serde_json::to_value(list_verification_templates())?;
+                Ok(self.tool_result(session, &templates_payload))
+            }
+            "zkputer_verify_template" => {
+                let template_id = arguments
+                    .get("template_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("template_id is required"))?;
+                let template_args = arguments
+                    .get("template_args")
+                    .cloned()
+                    .ok_or_else(|| anyhow!("template_args is required"))?;
+                let request = build_request_from_template(template_id, &template_args)?;
+                self.submit_and_render_receipt(session, request, &arguments, progress_token, notify)
+                    .await
+            }
+            "zkputer_verify_claim" => {
+                let venue = parse_venue(arguments.get("venue").and_then(|v| v.as_str()))
+                    .ok_or_else(|| anyhow!("invalid venue"))?;
+                let claim_type =
+                    parse_claim_type(arguments.get("claim_type").and_then(|v| v.as_str()))
+                        .ok_or_else(|| anyhow!("invalid claim_type"))?;
+                let account_ref = arguments
+                    .get("account_ref")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("account_ref is required"))?
+                    .to_string();
+                let order_ref = arguments
+                    .get("order_ref")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("order_ref is required"))?
+                    .to_string();
+                let execution_ref = arguments
+                    .get("execution_ref")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                let payload = arguments
+                    .get("payload")
+                    .cloned()
+                    .unwrap_or_else(|| json!({}));
+
+                let request = ProofRequest {
+                    execution_ref,
+                    payload,
+                    deadline_ms: arguments.get("deadline_ms").and_then(|v| v.as_u64()),
+                    escrow_hold_ms: arguments.get("escrow_hold_ms").and_then(|v| v.as_u64()),
+                    finality_timeout_ms: arguments
+                        .get("finality_timeout_ms")
+                        .and_then(|v| v.as_u64()),
+                    tenant_id: arguments
+                        .get("tenant_id")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                    ..ProofRequest::new(venue, claim_type, account_ref, order_ref)
+                };
+                self.submit_and_render_receipt(session, request, &arguments, progress_token, notify)
+                    .await
+            }
+            "zkputer_get_payload_schemas" => {
+                let schemas = engine.payload_schemas();
+                let payload = match arguments.get("claim_type").and_then(|v| v.as_str()) {
+                    Some(claim_type) => {
+                        parse_claim_type(Some(claim_type))
+                            .ok_or_else(|| anyhow!("invalid claim_type"))?;
+                        schemas
+                            .get(claim_type)
+                            .cloned()
+                            .unwrap_or_else(|| json!({}))
+                    }
+                    None => schemas,
+                };
+                Ok(self.tool_result(session, &payload))
+            }
+            "zkputer_get_receipt" => {
+                let receipt_id = arguments
+                    .get("receipt_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("receipt_id is required"))?;
+                if let Some(version) = arguments.get("receipt_version").and_then(|v| v.as_str()) {
+                    let payload = engine.published_receipt_as(receipt_id, version).await?;
+                    return Ok(self.tool_result(session, &payload));
+                }
+                match engine.published_receipt(receipt_id).await {
+                    Some(receipt) => {
+                        let payload = serde_json::to_value(&receipt)?;
+                        Ok(self.tool_result(session, &payload))
+                    }
+                    None => Ok(tool_error(format!("receipt not found: {}", receipt_id))),
+                }
+            }
+            "zkputer_verify_receipt" => {
+                let document = arguments
+                    .get("receipt")
+                    .ok_or_else(|| anyhow!("receipt is required"))?;
+                // Agents often pass the document through as a JSON string.
+                let document = match document {
+                    Value::String(text) => match serde_json::from_str::<Value>(text) {
+                        Ok(document) => document,
+                        Err(err) => {
+                            return Ok(tool_error(format!(
+                                "receipt is not a zkreceipt document: {}",
+                                err
+                            )))
+                        }
+                    },
+                    other => other.clone(),
+                };
+                let issues = validate_receipt_json(&document);
+                if !issues.is_empty() {
+                    return Ok(tool_error(format!(
+                        "receipt does not match the zkreceipt schema: {}",
+                        issues.join("; ")
+                    )));
+                }
+                let parsed = serde_json::from_value::<ZKReceipt>(document);
+                let receipt = match parsed {
+                    Ok(receipt) => receipt,
+                    Err(err) => {
+                        return Ok(tool_error(format!(
+                            "receipt is not a zkreceipt document: {}",
+                            err
+                        )))
+                    }
+                };
+                let public_key = arguments.get("public_key").and_then(|v| v.as_str());
+                match engine.verify_receipt(&receipt, public_key).await {
+                    Ok(verification) => {
+                        Ok(self.tool_result(session, &serde_json::to_value(&verification)?))
+                    }
+                    Err(err) => Ok(tool_error(err.to_string())),
+                }
+            }
+            "zkputer_list_receipts" => {
+                let page_size = arguments
+                    .get("page_size")
+                    .and_then(|v| v.as_u64())
+                    .map_or(DEFAULT_LIST_PAGE_SIZE, |n| n as usize)
+                    .clamp(1, MAX_LIST_PAGE_SIZE);
+                let after = match arguments.get("cursor").and_then(|v| v.as_str()) {
+                    Some(cursor) => Some(ExportCursor::decode(cursor)?),
+                    None => None,
+                };
+                let query = ReceiptQuery {
+                    status: match arguments.get("status").and_then(|v| v.as_str()) {
+                        Some(status) => {
+                            Some(parse_status(status).ok_or_else(|| anyhow!("invalid status"))?)
+                        }
+                        None => None,
+                    },
+                    ..receipt_query(&arguments)?
+                };
+                // One extra receipt tells whether another page follows.
+                let mut page = engine
+                    .page_receipts(&query, after.as_ref(), page_size + 1)
+                    .await?;
+                let has_more = page.len() > page_size;
+                page.truncate(page_size);
+                let next_cursor = page
+                    .last()
+                    .filter(|_| has_more)
+                    .map(|last| ExportCursor::of(last).encode());
+                let receipts: Vec<ReceiptSummary> = page
+                    .into_iter()
+                    .map(|receipt| ReceiptSummary::of(&published(receipt)))
+                    .collect();
+                let payload = json!({
+                    "count": receipts.len(),
+                    "receipts": receipts,
+                    "next_cursor": next_cursor
+                });
+                Ok(self.tool_result(session, &payload))
+            }
+            "zkputer_export_dispute_bundle" => {
+                let receipt_id = arguments
+                    .get("receipt_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("receipt_id is required"))?;
+                let max_inline_bytes = arguments
+                    .get("max_inline_bytes")
+                    .and_then(|v| v.as_u64())
+                    .map(|n| n as usize)
+                    .unwrap_or_else(dispute_inline_limit);
+                // The bundle carries the original request, so it waits for the escrow release too.
+                if engine
+                    .get_receipt(receipt_id)
+                    .await
+                    .as_ref()
+                    .is_some_and(is_withheld)
+                {
+                    return Ok(tool_error(format!(
+                        "receipt {} is held in escrow",
+                        receipt_id
+                    )));
+                }
+                let bundle = engine.dispute_bundle(receipt_id).await?;
+                let export = export_dispute_bundle(&bundle, max_inline_bytes, &dispute_dir())?;
+                let payload = json!({
+                    "receipt_id": receipt_id,
+                    "bundle_hash": bundle.bundle_hash,
+                    "export": export
+                });
+                Ok(self.tool_result(session, &payload))
+            }
+            "zkputer_get_stats" => {
+                if let Some(receipt_id) = arguments.get("receipt_id").and_then(|v| v.as_str()) {
+                    let cost = engine
+                        .receipt_cost(receipt_id)
+                        .await
+                        .ok_or_else(|| anyhow!("no cost record for receipt: {}", receipt_id))?;
+                    return Ok(self
+                        .tool_result(session, &json!({ "receipt_id": receipt_id, "cost": cost })));
+                }
+                let stats = engine.stats(&receipt_query(&arguments)?).await?;
+                Ok(self.tool_result(session, &serde_json::to_value(&stats)?))
+            }
+            _ => Ok(tool_error(format!("unknown tool: {}", name))),
+        }
+    }
+}
+
+pub fn receipt_uri(receipt_id: &str) -> String {
+    format!("{}{}", RECEIPT_URI_SCHEME, receipt_id)
+}
+
+pub fn receipt_id_from_uri(uri: &str) -> Option<&str> {
+    uri.strip_prefix(RECEIPT_URI_SCHEME)
+        .filter(|id| !id.is_empty() && !id.contains('/'))
+}
+
+fn resource_uri(params: &Value) -> Result<&str> {
+    Ok(params
+        .get("uri")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "uri is required".to_string()))?)
+}
+
+fn resource_templates() -> Value {
+    json!({
+        "resourceTemplates": [{
+            "uriTemplate": format!("{}{{receipt_id}}", RECEIPT_URI_SCHEME),
+            "name": "receipt",
+            "description": "A zkputer receipt by id, as its full JSON document.",
+            "mimeType": RECEIPT_MIME_TYPE
+        }]
+    })
+}
+
+// Carries its own JSON-RPC code through anyhow; other errors get the method's default code.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    id: Option<Value>,
+    method: String,
+    params: Option<Value>,
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+fn tool_error(text: String) -> Value {
+    json!({
+        "isError": true,
+        "content": [{
+            "type": "text",
+            "text": text
+        }]
+    })
+}
+
+fn parse_wait_options(arguments: &Value) -> (bool, u64) {
+    let wait_for_result = arguments
+        .get("wait_for_result")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    let wait_timeout_ms = arguments
+        .get("wait_timeout_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(3000);
+    (wait_for_result, wait_timeout_ms)
+}
+
+// ZKPUTER_DISPUTE_INLINE_LIMIT (bytes) caps inline bundles; larger ones go to ZKPUTER_DISPUTE_DIR.
+fn dispute_inline_limit() -> usize {
+    std::env::var("ZKPUTER_DISPUTE_INLINE_LIMIT")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_DISPUTE_INLINE_LIMIT)
+}
+
+fn dispute_dir() -> PathBuf {
+    std::env::var("ZKPUTER_DISPUTE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("zkputer-disputes"))
+}
+
+// The venue and claim_type filters shared by the listing and stats tools.
+fn receipt_query(arguments: &Value) -> Result<ReceiptQuery> {
+    Ok(ReceiptQuery {
+        venue: match arguments.get("venue").and_then(|v| v.as_str()) {
+            Some(venue) => Some(parse_venue(Some(venue)).ok_or_else(|| anyhow!("invalid venue"))?),
+            None => None,
+        },
+        claim_type: match arguments.get("claim_type").and_then(|v| v.as_str()) {
+            Some(claim) => {
+                Some(parse_claim_type(Some(claim)).ok_or_else(|| anyhow!("invalid claim_type"))?)
+            }
+            None => None,
+        },
+        ..ReceiptQuery::default()
+    })
+}
+
+fn parse_status(value: &str) -> Option<ReceiptStatus> {
+    serde_json::from_value(Value::String(value.to_string())).ok()
+}
+
+fn status_names() -> Value {
+    serde_json::to_value(schemars::schema_for!(ReceiptStatus))
+        .map(|schema| schema["enum"].clone())
+        .unwrap_or(Value::Null)
+}
+
+fn parse_venue(value: Option<&str>) -> Option<Venue> {
+    Venue::from_slug(value?)
+}
+
+fn parse_claim_type(value: Option<&str>) -> Option<ClaimType> {
+    ClaimType::from_name(value?)
+}
+
+// Every tool with the annotations this server ships.
+pub fn tool_definitions() -> Value {
+    let mut definitions = tool_list();
+    for tool in definitions["tools"].as_array_mut().into_iter().flatten() {
+        if let Some(annotations) = tool["name"].as_str().and_then(ToolAnnotations::for_tool) {
+            tool["annotations"] = json!(annotations);
+        }
+    }
+    definitions
+}
+
+fn tool_names() -> Vec<String> {
+    tool_list()["tools"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| tool["name"].as_str().map(str::to_string))
+        .collect()
+}
+
+fn tool_list() -> Value {
+    json!({
+        "tools": [
+            {
+                "name": "zkputer_list_templates",
+                "description": "List hardened zkputer verification templates for agent usage.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {}
+                }
+            },
+            {
+                "name": "zkputer_verify_template",
+                "description": "Submit verification via a hardened zkputer template and optionally wait for receipt completion.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "template_id": { "type": "string", "enum": template_ids() },
+                        "template_args": { "type": "object" },
+                        "wait_for_result": { "type": "boolean", "default": true },
+                        "wait_timeout_ms": { "type": "integer", "default": 3000 }
+                    },
+                    "required": ["template_id", "template_args"]
+                }
+            },
+            {
+                "name": "zkputer_verify_claim",
+                "description": "Submit a verification request and optionally wait for a receipt.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "venue": { "type": "string", "enum": venue_slugs() },
+                        "claim_type": { "type": "string", "enum": claim_names() },
+                        "account_ref": { "type": "string" },
+                        "order_ref": { "type": "string" },
+                        "execution_ref": { "type": "string" },
+                        "payload": { "type": "object" },
+                        "deadline_ms": { "type": "integer", "minimum": 1 },
+                        "escrow_hold_ms": { "type": "integer", "minimum": 1 },
+                        "finality_timeout_ms": { "type": "integer", "minimum": 1 },
+                        "tenant_id": { "type": "string" },
+                        "wait_for_result": { "type": "boolean", "default": true },
+                        "wait_timeout_ms": { "type": "integer", "default": 3000 }
+                    },
+                    "required": ["venue","claim_type","account_ref","order_ref"]
+                }
+            },
+            {
+                "name": "zkputer_get_payload_schemas",
+                "description": "Return the JSON Schema each claim type's payload is validated against on submit.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "claim_type": { "type": "string", "enum": claim_names() }
+                    }
+                }
+            },
+            {
+                "name": "zkputer_get_receipt",
+                "description": "Fetch a previously created receipt by id, optionally down-converted to an older receipt format.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "receipt_id": { "type": "string" },
+                        "receipt_version": { "type": "string", "enum": ["v0.1.0", "v0.2.0"] }
+                    },
+                    "required": ["receipt_id"]
+                }
+            },
+            {
+                "name": "zkputer_verify_receipt",
+                "description": "Check a full receipt JSON document, possibly issued by another deployment: recomputes its schema and receipt hashes, checks the signature, and for PROVED receipts verifies the proof and its public inputs. The signature is checked against public_key if given, else a key this server knows; signer_trusted is false when only the receipt's own embedded key was available.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "receipt": { "type": ["object", "string"] },
+                        "public_key": { "type": "string" }
+                    },
+                    "required": ["receipt"]
+                }
+            },
+            {
+                "name": "zkputer_list_receipts",
+                "description": "List receipts matching venue, status and claim type filters as summaries (id, status, refs, statement, timestamps), oldest first. Pass next_cursor back as cursor for the following page.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "venue": { "type": "string", "enum": venue_slugs() },
+                        "status": { "type": "string", "enum": status_names() },
+                        "claim_type": { "type": "string", "enum": claim_names() },
+                        "page_size": { "type": "integer", "minimum": 1, "maximum": MAX_LIST_PAGE_SIZE, "default": DEFAULT_LIST_PAGE_SIZE },
+                        "cursor": { "type": "string" }
+                    }
+                }
+            },
+            {
+                "name": "zkputer_export_dispute_bundle",
+                "description": "Package a receipt with its evidence, original request, screening records, lineage and signer keys for a counterparty. Returns the bundle base64-encoded when it fits under max_inline_bytes, otherwise a file reference.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "receipt_id": { "type": "string" },
+                        "max_inline_bytes": { "type": "integer" }
+                    },
+                    "required": ["receipt_id"]
+                }
+            },
+            {
+                "name": "zkputer_get_stats",
+                "description": "Receipt counts and resource costs (adapter calls, prover cycles/fees, anchoring gas) grouped by venue and claim type, or the cost record of one receipt.",
+                "inputSchema": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "venue": { "type": "string", "enum": venue_slugs() },
+                        "claim_type": { "type": "string", "enum": claim_names() },
+                        "receipt_id": { "type": "string" }
+                    }
+                }
+            }
+        ]
+    })
+}
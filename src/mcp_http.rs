@@ -0,0 +1,574 @@
+use crate::http::{constant_time_eq, read_request, reason_phrase, HttpRequest, HttpResponse};
+use crate::mcp::{McpServer, McpSession, SUPPORTED_PROTOCOL_VERSIONS};
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+// Streamable HTTP (MCP 2025-03-26 and later) is served on MCP_ENDPOINT. Clients still on the
+// 2024-11-05 HTTP+SSE transport open SSE_ENDPOINT and post to the endpoint it announces.
+pub const MCP_ENDPOINT: &str = "/mcp";
+pub const SSE_ENDPOINT: &str = "/sse";
+pub const SSE_MESSAGES_ENDPOINT: &str = "/messages";
+pub const SESSION_HEADER: &str = "Mcp-Session-Id";
+pub const PROTOCOL_VERSION_HEADER: &str = "MCP-Protocol-Version";
+const MAX_SESSIONS: usize = 10_000;
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+const SSE_BUFFER: usize = 64;
+
+// A streamable HTTP session outlives its connections, so it is dropped once idle for too long.
+struct HttpSession {
+    session: McpSession,
+    last_seen: Instant,
+}
+
+struct SseSession {
+    session: McpSession,
+    events: mpsc::Sender<Value>,
+}
+
+pub struct McpHttpTransport {
+    server: Arc<McpServer>,
+    sessions: Mutex<HashMap<String, HttpSession>>,
+    sse_sessions: Mutex<HashMap<String, SseSession>>,
+    api_token: Option<String>,
+    allowed_origins: Vec<String>,
+    session_idle_timeout: Duration,
+}
+
+impl McpHttpTransport {
+    pub fn new(server: Arc<McpServer>) -> Self {
+        Self {
+            server,
+            sessions: Mutex::new(HashMap::new()),
+            sse_sessions: Mutex::new(HashMap::new()),
+            api_token: None,
+            allowed_origins: vec![],
+            session_idle_timeout: SESSION_IDLE_TIMEOUT,
+        }
+    }
+
+    // Every request then needs `Authorization: Bearer <token>`.
+    pub fn with_api_token(mut self, token: impl Into<String>) -> Self {
+        self.api_token = Some(token.into());
+        self
+    }
+
+    // Browser origins allowed besides loopback ones, e.g. "https://agent.example.com". Requests
+    // from any other Origin are refused so a web page cannot reach a local server (DNS rebinding).
+    pub fn with_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.allowed_origins = origins;
+        self
+    }
+
+    // How long a streamable HTTP session may go without a request before it is closed. An open
+    // update stream keeps its session alive.
+    pub fn with_session_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.session_idle_timeout = timeout;
+        self
+    }
+
+    pub fn session_count(&self) -> usize {
+        self.sessions
+            .lock()
+            .expect("mcp session lock poisoned")
+            .len()
+            + self
+                .sse_sessions
+                .lock()
+                .expect("mcp session lock poisoned")
+                .len()
+    }
+
+    fn expire_idle_sessions(&self) {
+        let mut sessions = self.sessions.lock().expect("mcp session lock poisoned");
+        sessions.retain(|_, entry| {
+            let live = entry.last_seen.elapsed() < self.session_idle_timeout;
+            if !live {
+                entry.session.close();
+            }
+            live
+        });
+    }
+
+    // The live session under `id`, marked as just used.
+    fn touch_session(&self, id: &str) -> Option<McpSession> {
+        let mut sessions = self.sessions.lock().expect("mcp session lock poisoned");
+        let entry = sessions.get_mut(id)?;
+        if entry.last_seen.elapsed() >= self.session_idle_timeout {
+            let expired = sessions.remove(id)?;
+            expired.session.close();
+            return None;
+        }
+        entry.last_seen = Instant::now();
+        Some(entry.session.clone())
+    }
+
+    fn origin_allowed(&self, origin: &str) -> bool {
+        if self.allowed_origins.iter().any(|allowed| allowed == origin) {
+            return true;
+        }
+        let host = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+        let host = match host.strip_prefix('[') {
+            Some(v6) => v6.split(']').next().unwrap_or_default(),
+            None => host.split([':', '/']).next().unwrap_or_default(),
+        };
+        matches!(host, "localhost" | "127.0.0.1" | "::1")
+    }
+
+    fn refuse(&self, request: &HttpRequest) -> Option<HttpResponse> {
+        if let Some(origin) = request.header("Origin").filter(|o| !self.origin_allowed(o)) {
+            return Some(HttpResponse::json(
+                403,
+                json!({ "error": format!("origin {} is not allowed", origin) }),
+            ));
+        }
+        let token = self.api_token.as_deref()?;
+        let presented = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        if constant_time_eq(presented.trim().as_bytes(), token.as_bytes()) {
+            return None;
+        }
+        Some(
+            HttpResponse::json(401, json!({ "error": "missing or invalid bearer token" }))
+                .with_header("WWW-Authenticate", "Bearer"),
+        )
+    }
+
+    async fn handle_connection(self: Arc<Self>, mut stream: TcpStream) -> Result<()> {
+        let request = match read_request(&mut stream).await {
+            Ok(request) => request,
+            Err(err) => {
+                return write_json(
+                    &mut stream,
+                    400,
+                    &[],
+                    Some(&json!({ "error": err.to_string() })),
+                )
+                .await
+            }
+        };
+        if let Some(refused) = self.refuse(&request) {
+            return write_json(
+                &mut stream,
+                refused.status,
+                &refused.headers,
+                Some(&refused.body),
+            )
+            .await;
+        }
+        match (request.method.as_str(), request.path.as_str()) {
+            ("POST", MCP_ENDPOINT) => self.post_message(&mut stream, &request).await,
+            ("DELETE", MCP_ENDPOINT) => {
+                let Some(id) = request.header(SESSION_HEADER) else {
+                    return write_json(
+                        &mut stream,
+                        400,
+                        &[],
+                        Some(&json!({ "error": "missing Mcp-Session-Id" })),
+                    )
+                    .await;
+                };
+                let removed = self
+                    .sessions
+                    .lock()
+                    .expect("mcp session lock poisoned")
+                    .remove(id);
+                if let Some(entry) = &removed {
+                    entry.session.close();
+                }
+                write_json(
+                    &mut stream,
+                    if removed.is_some() { 200 } else { 404 },
+                    &[],
+                    None,
+                )
+                .await
+            }
+            ("GET", MCP_ENDPOINT) => self.open_update_stream(stream, &request).await,
+            (_, MCP_ENDPOINT) => {
+                write_json(
+                    &mut stream,
+                    405,
+                    &[("Allow".to_string(), "GET, POST, DELETE".to_string())],
+                    None,
+                )
+                .await
+            }
+            ("GET", SSE_ENDPOINT) => self.open_sse_stream(stream).await,
+            ("POST", SSE_MESSAGES_ENDPOINT) => self.post_sse_message(&mut stream, &request).await,
+            (_, SSE_ENDPOINT | SSE_MESSAGES_ENDPOINT) => {
+                write_json(&mut stream, 405, &[], None).await
+            }
+            _ => {
+                write_json(
+                    &mut stream,
+                    404,
+                    &[],
+                    Some(&json!({ "error": format!("no route for {}", request.path) })),
+                )
+                .await
+            }
+        }
+    }
+
+    async fn post_message(&self, stream: &mut TcpStream, request: &HttpRequest) -> Result<()> {
+        let message: Value = match serde_json::from_slice(&request.body) {
+            Ok(message) => message,
+            Err(err) => {
+                let error = json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32700, "message": format!("Parse error: {}", err) } });
+                return write_json(stream, 400, &[], Some(&error)).await;
+            }
+        };
+        if message.is_array() {
+            let error = json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32600, "message": "batched messages are not supported" } });
+            return write_json(stream, 400, &[], Some(&error)).await;
+        }
+
+        if message.get("method").and_then(|m| m.as_str()) == Some("initialize") {
+            self.expire_idle_sessions();
+            if self.session_count() >= MAX_SESSIONS {
+                return write_json(
+                    stream,
+                    503,
+                    &[],
+                    Some(&json!({ "error": "too many MCP sessions" })),
+                )
+                .await;
+            }
+            let mut session = self.server.session();
+            let response = self
+                .server
+                .handle(&mut session, &request.body, &mut |_| Ok(()))
+                .await;
+            let id = Uuid::new_v4().simple().to_string();
+            let entry = HttpSession {
+                session,
+                last_seen: Instant::now(),
+            };
+            self.sessions
+                .lock()
+                .expect("mcp session lock poisoned")
+                .insert(id.clone(), entry);
+            return write_json(
+                stream,
+                200,
+                &[(SESSION_HEADER.to_string(), id)],
+                response.as_ref(),
+            )
+            .await;
+        }
+
+        let Some(id) = request.header(SESSION_HEADER) else {
+            return write_json(
+                stream,
+                400,
+                &[],
+                Some(&json!({ "error": "missing Mcp-Session-Id" })),
+            )
+            .await;
+        };
+        let Some(mut session) = self.touch_session(id) else {
+            return write_json(
+                stream,
+                404,
+                &[],
+                Some(&json!({ "error": "unknown or expired MCP session" })),
+            )
+            .await;
+        };
+        if let Some(version) = request.header(PROTOCOL_VERSION_HEADER) {
+            if !SUPPORTED_PROTOCOL_VERSIONS.contains(&version) {
+                return write_json(stream, 400, &[], Some(&json!({ "error": format!("unsupported MCP protocol version {}", version) }))).await;
+            }
+        }
+
+        let accepts_sse = request
+            .header("Accept")
+            .is_some_and(|accept| accept.contains("text/event-stream"));
+        if !(accepts_sse && session.features().streaming) {
+            let response = self
+                .server
+                .handle(&mut session, &request.body, &mut |_| Ok(()))
+                .await;
+            return match response {
+                Some(response) => write_json(stream, 200, &[], Some(&response)).await,
+                // Notifications and client responses are only acknowledged.
+                None => write_json(stream, 202, &[], None).await,
+            };
+        }
+
+        // Notifications raised while the request runs go out as events ahead of its response.
+        write_sse_head(stream).await?;
+        let (events, mut pending) = mpsc::unbounded_channel();
+        let mut notify = move |event: Value| {
+            events
+                .send(event)
+                .map_err(|_| anyhow!("MCP client went away"))
+        };
+        let handled = self.server.handle(&mut session, &request.body, &mut notify);
+        tokio::pin!(handled);
+        let response = loop {
+            tokio::select! {
+                response = &mut handled => break response,
+                Some(event) = pending.recv() => write_sse_event(stream, "message", &event).await?,
+            }
+        };
+        while let Ok(event) = pending.try_recv() {
+            write_sse_event(stream, "message", &event).await?;
+        }
+        if let Some(response) = response {
+            write_sse_event(stream, "message", &response).await?;
+        }
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    // The server-to-client stream of a streamable HTTP session, which carries resource updates. A
+    // second GET takes over from the first, and deleting the session ends it.
+    async fn open_update_stream(&self, mut stream: TcpStream, request: &HttpRequest) -> Result<()> {
+        if !request
+            .header("Accept")
+            .is_some_and(|accept| accept.contains("text/event-stream"))
+        {
+            return write_json(
+                &mut stream,
+                406,
+                &[],
+                Some(&json!({ "error": "GET /mcp needs Accept: text/event-stream" })),
+            )
+            .await;
+        }
+        let Some(id) = request.header(SESSION_HEADER) else {
+            return write_json(
+                &mut stream,
+                400,
+                &[],
+                Some(&json!({ "error": "missing Mcp-Session-Id" })),
+            )
+            .await;
+        };
+        let Some(session) = self.touch_session(id) else {
+            return write_json(
+                &mut stream,
+                404,
+                &[],
+                Some(&json!({ "error": "unknown or expired MCP session" })),
+            )
+            .await;
+        };
+        let (outbound, mut updates) = mpsc::unbounded_channel();
+        session.attach_outbound(outbound);
+        write_sse_head(&mut stream).await?;
+        let mut keepalive = tokio::time::interval(SSE_KEEPALIVE);
+        keepalive.tick().await;
+        loop {
+            tokio::select! {
+                update = updates.recv() => match update {
+                    Some(update) => write_sse_event(&mut stream, "message", &update).await?,
+                    None => break,
+                },
+                _ = keepalive.tick() => {
+                    stream.write_all(b": keepalive\n\n").await?;
+                    stream.flush().await?;
+                    if self.touch_session(id).is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    async fn open_sse_stream(self: Arc<Self>, mut stream: TcpStream) -> Result<()> {
+        self.expire_idle_sessions();
+        if self.session_count() >= MAX_SESSIONS {
+            return write_json(
+                &mut stream,
+                503,
+                &[],
+                Some(&json!({ "error": "too many MCP sessions" })),
+            )
+            .await;
+        }
+        let id = Uuid::new_v4().simple().to_string();
+        let (events, mut outbox) = mpsc::channel(SSE_BUFFER);
+        let session = self.server.session();
+        let (outbound, mut updates) = mpsc::unbounded_channel();
+        session.attach_outbound(outbound);
+        self.sse_sessions
+            .lock()
+            .expect("mcp session lock poisoned")
+            .insert(
+                id.clone(),
+                SseSession {
+                    session: session.clone(),
+                    events,
+                },
+            );
+        let streamed = async {
+            write_sse_head(&mut stream).await?;
+            let endpoint = format!("{}?session_id={}", SSE_MESSAGES_ENDPOINT, id);
+            write_sse_data(&mut stream, "endpoint", &endpoint).await?;
+            let mut keepalive = tokio::time::interval(SSE_KEEPALIVE);
+            keepalive.tick().await;
+            loop {
+                tokio::select! {
+                    event = outbox.recv() => match event {
+                        Some(event) => write_sse_event(&mut stream, "message", &event).await?,
+                        None => return Ok::<(), anyhow::Error>(()),
+                    },
+                    Some(update) = updates.recv() => write_sse_event(&mut stream, "message", &update).await?,
+                    // A failed write is how a vanished client is noticed.
+                    _ = keepalive.tick() => {
+                        stream.write_all(b": keepalive\n\n").await?;
+                        stream.flush().await?;
+                    }
+                }
+            }
+        };
+        let result = streamed.await;
+        self.sse_sessions
+            .lock()
+            .expect("mcp session lock poisoned")
+            .remove(&id);
+        session.close();
+        result
+    }
+
+    async fn post_sse_message(
+        self: Arc<Self>,
+        stream: &mut TcpStream,
+        request: &HttpRequest,
+    ) -> Result<()> {
+        let params = match request.query_params() {
+            Ok(params) => params,
+            Err(err) => {
+                return write_json(stream, 400, &[], Some(&json!({ "error": err.to_string() })))
+                    .await
+            }
+        };
+        let Some(id) = params.get("session_id").cloned() else {
+            return write_json(
+                stream,
+                400,
+                &[],
+                Some(&json!({ "error": "missing session_id" })),
+            )
+            .await;
+        };
+        let found = self
+            .sse_sessions
+            .lock()
+            .expect("mcp session lock poisoned")
+            .get(&id)
+            .map(|sse| (sse.session.clone(), sse.events.clone()));
+        let Some((mut session, events)) = found else {
+            return write_json(
+                stream,
+                404,
+                &[],
+                Some(&json!({ "error": "unknown or closed SSE session" })),
+            )
+            .await;
+        };
+        write_json(stream, 202, &[], None).await?;
+
+        // The response travels on the session's event stream, so this connection is already done.
+        let body = request.body.clone();
+        tokio::spawn(async move {
+            let notifications = events.clone();
+            let mut notify = move |event: Value| {
+                notifications
+                    .try_send(event)
+                    .map_err(|_| anyhow!("SSE stream is closed or not keeping up"))
+            };
+            let response = self.server.handle(&mut session, &body, &mut notify).await;
+            // Only initialize changes session state.
+            if let Some(sse) = self
+                .sse_sessions
+                .lock()
+                .expect("mcp session lock poisoned")
+                .get_mut(&id)
+            {
+                sse.session = session;
+            }
+            if let Some(response) = response {
+                let _ = events.send(response).await;
+            }
+        });
+        Ok(())
+    }
+}
+
+pub async fn serve_mcp_http(listener: TcpListener, transport: Arc<McpHttpTransport>) -> Result<()> {
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .context("failed to accept MCP connection")?;
+        let transport = Arc::clone(&transport);
+        tokio::spawn(async move {
+            let _ = transport.handle_connection(stream).await;
+        });
+    }
+}
+
+async fn write_json(
+    stream: &mut TcpStream,
+    status: u16,
+    headers: &[(String, String)],
+    body: Option<&Value>,
+) -> Result<()> {
+    let body = body
+        .map(serde_json::to_vec)
+        .transpose()?
+        .unwrap_or_default();
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        reason_phrase(status),
+        body.len()
+    );
+    if !body.is_empty() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+// The stream has no length; closing the connection ends it.
+async fn write_sse_head(stream: &mut TcpStream) -> Result<()> {
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n";
+    stream.write_all(head.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+async fn write_sse_event(stream: &mut TcpStream, event: &str, message: &Value) -> Result<()> {
+    write_sse_data(stream, event, &serde_json::to_string(message)?).await
+}
+
+async fn write_sse_data(stream: &mut TcpStream, event: &str, data: &str) -> Result<()> {
+    stream
+        .write_all(format!("event: {}\ndata: {}\n\n", event, data).as_bytes())
+        .await?;
+    stream.flush().await?;
+    Ok(())
+}
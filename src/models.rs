@@ -1,11 +1,27 @@
-use chrono::Utc;
+use crate::commitments::CommitmentMode;
+use crate::hashing::HashAlgorithm;
+use crate::orderbook::OrderBookSnapshot;
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+// Domain-separation tags bound into every commitment, so a digest computed in one context can
+// never be passed off as another. Receipts record the tag version they were hashed under in
+// `integrity.hash_domain_version`; version 0 means the untagged legacy encoding.
+pub const HASH_DOMAIN_VERSION: u32 = 1;
+pub const CLAIM_HASH_DOMAIN: &str = "zkputer.claim.v1";
+pub const EVIDENCE_HASH_DOMAIN: &str = "zkputer.evidence.v1";
+pub const RECEIPT_HASH_DOMAIN: &str = "zkputer.receipt.v1";
+pub const INTEGRITY_HASH_DOMAIN: &str = "zkputer.integrity.v1";
+pub const PNL_COMMITMENT_DOMAIN: &str = "zkputer.pnl.v1";
+// The POSITION_CLOSED payload field holding the requester's blinding for the PnL commitment.
+pub const PNL_BLINDING_FIELD: &str = "pnl_blinding";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Venue {
     Hyperliquid,
@@ -14,23 +30,109 @@ pub enum Venue {
     Polymarket,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[allow(non_camel_case_types)]
 pub enum ClaimType {
     ORDER_PLACED,
     TRADE_EXECUTED,
+    BEST_EXECUTION,
+    TRADE_BUSTED,
+    TRADE_AMENDED,
+    ORDER_REJECTED,
+    ORDER_CANCELLED,
+    POSITION_CLOSED,
+    BALANCE_AT_TIMESTAMP,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[allow(non_camel_case_types)]
 pub enum ReceiptStatus {
     PENDING,
+    AWAITING_FINALITY,
     PROVED,
     NON_PROVABLE,
     INVALIDATED,
+    // Withdrawn by the caller while in flight; carries no proof and no non-provable reason.
+    CANCELLED,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl ReceiptStatus {
+    pub fn is_terminal(self) -> bool {
+        !matches!(
+            self,
+            ReceiptStatus::PENDING | ReceiptStatus::AWAITING_FINALITY
+        )
+    }
+}
+
+// The receipt status changes a stored receipt may go through, from `receipt_status_transitions`
+// in spec/pipeline-lifecycle.json. A write whose status change is not listed is rejected.
+#[derive(Debug, Clone)]
+pub struct ReceiptLifecycle {
+    initial: HashSet<ReceiptStatus>,
+    transitions: HashSet<(ReceiptStatus, ReceiptStatus)>,
+}
+
+impl ReceiptLifecycle {
+    pub fn from_spec(spec: &Value) -> Result<Self> {
+        let section = spec
+            .get("receipt_status_transitions")
+            .ok_or_else(|| anyhow!("pipeline-lifecycle: missing receipt_status_transitions"))?;
+        let status = |value: &Value| -> Result<ReceiptStatus> {
+            serde_json::from_value(value.clone())
+                .map_err(|_| anyhow!("pipeline-lifecycle: unknown receipt status {}", value))
+        };
+        let initial = section
+            .get("initial")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("pipeline-lifecycle: missing initial receipt statuses"))?
+            .iter()
+            .map(status)
+            .collect::<Result<_>>()?;
+        let transitions = section
+            .get("transitions")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("pipeline-lifecycle: missing receipt status transitions"))?
+            .iter()
+            .map(|t| Ok((status(&t["from"])?, status(&t["to"])?)))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            initial,
+            transitions,
+        })
+    }
+
+    // The lifecycle compiled in from the spec.
+    pub fn standard() -> &'static Self {
+        static LIFECYCLE: std::sync::OnceLock<ReceiptLifecycle> = std::sync::OnceLock::new();
+        LIFECYCLE.get_or_init(|| {
+            let spec: Value = serde_json::from_str(include_str!("../spec/pipeline-lifecycle.json"))
+                .expect("spec/pipeline-lifecycle.json is valid JSON");
+            Self::from_spec(&spec)
+                .expect("spec/pipeline-lifecycle.json defines receipt status transitions")
+        })
+    }
+
+    pub fn allows(&self, from: ReceiptStatus, to: ReceiptStatus) -> bool {
+        self.transitions.contains(&(from, to))
+    }
+
+    pub fn check_initial(&self, status: ReceiptStatus) -> Result<()> {
+        if !self.initial.contains(&status) {
+            bail!("a receipt cannot be created as {:?}", status);
+        }
+        Ok(())
+    }
+
+    pub fn check(&self, from: ReceiptStatus, to: ReceiptStatus) -> Result<()> {
+        if !self.allows(from, to) {
+            bail!("illegal receipt transition {:?} -> {:?}", from, to);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[allow(non_camel_case_types)]
 pub enum NonProvableReason {
     EVIDENCE_MISSING,
@@ -41,24 +143,39 @@ pub enum NonProvableReason {
     SCHEMA_INVALID,
     UNSUPPORTED_VENUE_CLAIM,
     PROOF_FAILURE,
+    COMPLIANCE_BLOCKED,
+    DEADLINE_EXCEEDED,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[allow(non_camel_case_types)]
 pub enum VerificationMode {
+    #[default]
     OFFCHAIN,
     ONCHAIN_ANCHORED,
     OFFCHAIN_AND_ANCHORED,
+    // No zk proof: the issuer's signature over the claim and evidence commitments is the attestation.
+    ATTESTATION_ONLY,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl VerificationMode {
+    pub fn requires_anchoring(self) -> bool {
+        matches!(
+            self,
+            VerificationMode::ONCHAIN_ANCHORED | VerificationMode::OFFCHAIN_AND_ANCHORED
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum ProofBackend {
     SP1,
     PICO,
+    RISC0,
     NONE,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EvidenceItem {
     pub source_id: String,
     pub source_kind: String,
@@ -67,6 +184,35 @@ pub struct EvidenceItem {
     pub observed_at: String,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PriceObservation {
+    pub execution_price: f64,
+    pub reference_price: f64,
+    pub reference_source: String,
+    pub reference_observed_at: String,
+    pub tolerance_bps: f64,
+}
+
+impl PriceObservation {
+    pub fn slippage_bps(&self) -> f64 {
+        ((self.execution_price - self.reference_price).abs() / self.reference_price) * 10_000.0
+    }
+}
+
+// How settled the chain reported the inclusion to be when evidence was collected, on chains that
+// expose commitment levels (Solana's processed, confirmed, and finalized).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainCommitment {
+    Processed,
+    Confirmed,
+    Finalized,
 }
 
 #[derive(Debug, Clone)]
@@ -75,17 +221,263 @@ pub struct EvidenceBundle {
     pub observed_tags: HashSet<String>,
     pub conflicts: Vec<String>,
     pub finality_observed_at: Option<String>,
+    pub inclusion_height: Option<u64>,
+    // Blocks from the inclusion to the head, counting the inclusion block, as last measured by the
+    // adapter or the finality observer.
+    pub confirmations: Option<u64>,
+    pub commitment: Option<ChainCommitment>,
+    pub price_observation: Option<PriceObservation>,
+    pub correction: Option<TradeCorrection>,
+    pub cancellation: Option<OrderCancellation>,
+    pub position_closure: Option<PositionClosure>,
+    pub balance_snapshot: Option<BalanceSnapshot>,
+    pub collection: Vec<CollectionRecord>,
+    pub order_book: Option<OrderBookSnapshot>,
+    // What the adapter fetched, byte for byte, for items whose hash was taken over those bytes.
+    // Receipts only ever carry the hashes; the bytes go to the evidence vault, if one is configured.
+    pub raw_artifacts: Vec<RawArtifact>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawArtifact {
+    pub artifact_hash: String,
+    pub bytes: Vec<u8>,
+}
+
+impl RawArtifact {
+    pub fn of(item: &EvidenceItem, bytes: Vec<u8>) -> Self {
+        Self {
+            artifact_hash: item.artifact_hash.clone(),
+            bytes,
+        }
+    }
 }
 
 impl EvidenceBundle {
     pub fn evidence_root(&self) -> String {
+        self.evidence_root_with(HashAlgorithm::Sha256)
+    }
+
+    pub fn evidence_root_with(&self, algorithm: HashAlgorithm) -> String {
         let mut leaves: Vec<&String> = self.items.iter().map(|i| &i.artifact_hash).collect();
         leaves.sort();
-        hash_json(&serde_json::json!({ "leaves": leaves }))
+        algorithm.hash_json(&serde_json::json!({ "leaves": leaves }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum CorrectionKind {
+    Busted,
+    Amended {
+        amended_price: Option<f64>,
+        amended_size: Option<f64>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TradeCorrection {
+    pub venue: Venue,
+    pub order_ref: String,
+    pub execution_ref: String,
+    pub correction_ref: String,
+    pub kind: CorrectionKind,
+    pub observed_at: String,
+}
+
+impl TradeCorrection {
+    pub fn claim_type(&self) -> ClaimType {
+        match self.kind {
+            CorrectionKind::Busted => ClaimType::TRADE_BUSTED,
+            CorrectionKind::Amended { .. } => ClaimType::TRADE_AMENDED,
+        }
+    }
+}
+
+// A venue's record that an open order was cancelled before it filled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OrderCancellation {
+    pub venue: Venue,
+    pub order_ref: String,
+    pub cancellation_ref: String,
+    pub cancelled_at: String,
+}
+
+// A venue's record that a position was closed out, with the PnL it realized. The PnL is a decimal
+// string in `currency` so its commitment does not depend on float formatting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PositionClosure {
+    pub venue: Venue,
+    pub position_ref: String,
+    // The order whose fill closed the position.
+    pub order_ref: String,
+    pub closure_ref: String,
+    pub closed_at: String,
+    pub realized_pnl: String,
+    pub currency: String,
+}
+
+impl PositionClosure {
+    // Commits to the realized PnL without revealing it. The requester's blinding keeps a PnL that
+    // is easy to guess from being found by hashing candidates; whoever holds it can open the
+    // commitment to a chosen counterparty.
+    pub fn pnl_commitment(&self, blinding: &str) -> String {
+        pnl_commitment(
+            &self.position_ref,
+            &self.realized_pnl,
+            &self.currency,
+            blinding,
+        )
+    }
+}
+
+pub fn pnl_commitment(
+    position_ref: &str,
+    realized_pnl: &str,
+    currency: &str,
+    blinding: &str,
+) -> String {
+    hash_json(&serde_json::json!({
+        "domain": PNL_COMMITMENT_DOMAIN,
+        "position_ref": position_ref,
+        "realized_pnl": realized_pnl,
+        "currency": currency,
+        "blinding": blinding
+    }))
+}
+
+// A venue's record of what an account held of one asset at a point in time (and block, on chains).
+// The balance is a decimal string, compared digit by digit, so large on-chain amounts keep their
+// precision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceSnapshot {
+    pub venue: Venue,
+    pub account_ref: String,
+    pub asset: String,
+    pub balance: String,
+    pub as_of: String,
+    pub block: Option<u64>,
+    pub snapshot_ref: String,
+}
+
+// The range a BALANCE_AT_TIMESTAMP claim asserts the balance lies in: at least `min_balance` of
+// `asset` at `as_of`, or at `block` when one is given. Read from the request's payload and carried
+// in the claim's public values, since it is what the proof's public inputs commit to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceRange {
+    pub asset: String,
+    pub min_balance: String,
+    pub as_of: String,
+    #[serde(default)]
+    pub block: Option<u64>,
+}
+
+impl BalanceRange {
+    pub fn from_payload(payload: &Value) -> Result<Self> {
+        let field = |name: &str| {
+            payload
+                .get(name)
+                .and_then(Value::as_str)
+                .filter(|value| !value.trim().is_empty())
+                .ok_or_else(|| anyhow!("balance claim requires the payload's {}", name))
+        };
+        let range = Self {
+            asset: field("asset")?.to_string(),
+            min_balance: field("min_balance")?.to_string(),
+            as_of: field("as_of")?.to_string(),
+            block: payload.get("block").and_then(Value::as_u64),
+        };
+        if parse_decimal(&range.min_balance).is_none() {
+            bail!(
+                "min_balance {} is not a non-negative decimal",
+                range.min_balance
+            );
+        }
+        if DateTime::parse_from_rfc3339(&range.as_of).is_err() {
+            bail!("as_of {} is not an RFC 3339 timestamp", range.as_of);
+        }
+        Ok(range)
+    }
+
+    // Whether `snapshot` was taken at this range's point: the same block when one is asked for,
+    // otherwise the same instant.
+    pub fn is_taken_at(&self, snapshot: &BalanceSnapshot) -> bool {
+        match self.block {
+            Some(block) => snapshot.block == Some(block),
+            None => match (
+                DateTime::parse_from_rfc3339(&self.as_of),
+                DateTime::parse_from_rfc3339(&snapshot.as_of),
+            ) {
+                (Ok(asked), Ok(taken)) => asked == taken,
+                _ => false,
+            },
+        }
+    }
+
+    // None when the balance is not a non-negative decimal.
+    pub fn contains(&self, balance: &str) -> Option<bool> {
+        decimal_at_least(balance, &self.min_balance)
+    }
+}
+
+// Whether the non-negative decimal `value` is at least `floor`, or None if either is malformed.
+pub fn decimal_at_least(value: &str, floor: &str) -> Option<bool> {
+    let (value_int, value_frac) = parse_decimal(value)?;
+    let (floor_int, floor_frac) = parse_decimal(floor)?;
+    let width = value_frac.len().max(floor_frac.len());
+    let ordering = value_int
+        .len()
+        .cmp(&floor_int.len())
+        .then_with(|| value_int.cmp(floor_int))
+        .then_with(|| format!("{:0<width$}", value_frac).cmp(&format!("{:0<width$}", floor_frac)));
+    Some(ordering.is_ge())
+}
+
+// Integer digits without leading zeros and fraction digits without trailing ones.
+fn parse_decimal(text: &str) -> Option<(&str, &str)> {
+    let (int, frac) = text.split_once('.').unwrap_or((text, "0"));
+    if int.is_empty()
+        || frac.is_empty()
+        || !int.bytes().all(|b| b.is_ascii_digit())
+        || !frac.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    Some((int.trim_start_matches('0'), frac.trim_end_matches('0')))
+}
+
+// Resources consumed on behalf of a receipt. Kept beside the receipt rather than inside it, since
+// it is operator billing data and not part of the signed claim.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostRecord {
+    pub adapter_calls: u32,
+    pub prover_cycles: u64,
+    pub prover_fee_usd: f64,
+    pub anchoring_gas: u64,
+    pub anchoring_fee_wei: u128,
+}
+
+impl CostRecord {
+    pub fn add(&mut self, other: &CostRecord) {
+        self.adapter_calls += other.adapter_calls;
+        self.prover_cycles += other.prover_cycles;
+        self.prover_fee_usd += other.prover_fee_usd;
+        self.anchoring_gas += other.anchoring_gas;
+        self.anchoring_fee_wei += other.anchoring_fee_wei;
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+// Where one corrected receipt stands: invalidated once `superseding_status` is PROVED, and
+// still standing while the superseding receipt is in flight or could not be proved.
+pub struct CorrectionOutcome {
+    pub correction_ref: String,
+    pub original_receipt_id: String,
+    pub superseding_receipt_id: String,
+    pub superseding_status: ReceiptStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProofRequest {
     pub venue: Venue,
     pub claim_type: ClaimType,
@@ -94,6 +486,57 @@ pub struct ProofRequest {
     pub execution_ref: Option<String>,
     #[serde(default)]
     pub payload: Value,
+    #[serde(default)]
+    pub verification_mode: VerificationMode,
+    // Overall budget from submission, shared by every pipeline stage.
+    #[serde(default)]
+    pub deadline_ms: Option<u64>,
+    // Withholds the full receipt for this long after submission; until it is released, only the
+    // commitment (receipt hash, proof and anchor) is published.
+    #[serde(default)]
+    pub escrow_hold_ms: Option<u64>,
+    // How long the receipt may wait for finality evidence. Only tightens the venue's finality rule.
+    #[serde(default)]
+    pub finality_timeout_ms: Option<u64>,
+    // Selects the tenant's policy overlay, if it has one; otherwise the base policy applies.
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+}
+
+impl ProofRequest {
+    // An off-chain request with an empty payload and no deadlines, escrow or tenant overlay; set the
+    // other fields with struct update syntax.
+    pub fn new(
+        venue: Venue,
+        claim_type: ClaimType,
+        account_ref: impl Into<String>,
+        order_ref: impl Into<String>,
+    ) -> Self {
+        Self {
+            venue,
+            claim_type,
+            account_ref: account_ref.into(),
+            order_ref: order_ref.into(),
+            execution_ref: None,
+            payload: serde_json::json!({}),
+            verification_mode: VerificationMode::OFFCHAIN,
+            deadline_ms: None,
+            escrow_hold_ms: None,
+            finality_timeout_ms: None,
+            tenant_id: None,
+        }
+    }
+
+    // Commits to the order as submitted, leaving out the claim type so an ORDER_PLACED request
+    // and the ORDER_REJECTED receipt it turns into share one hash.
+    pub fn order_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "venue": self.venue,
+            "account_ref": self.account_ref,
+            "order_ref": self.order_ref,
+            "payload": self.payload
+        }))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -103,16 +546,68 @@ pub struct ExecutionAck {
     pub acceptance_artifact_ref: String,
     pub acceptance_artifact_hash: String,
     pub accepted_at: String,
+    // The venue's stated reason when it refused the order.
+    pub rejection_reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TruthClaim {
     pub r#type: ClaimType,
     pub statement: String,
     pub claim_hash: String,
+    #[serde(default)]
+    pub statement_locale: StatementLocale,
+    #[serde(default)]
+    pub localized_statements: Vec<LocalizedStatement>,
+    // Hashes of the payload fields the request sealed to the engine's key, bound into `claim_hash`.
+    #[serde(default)]
+    pub encrypted_fields: Vec<EncryptedFieldCommitment>,
+    // Claim data set by the claim's handler for the public inputs to commit to, such as a
+    // POSITION_CLOSED PnL commitment or a BALANCE_AT_TIMESTAMP range.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub public_values: serde_json::Map<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EncryptedFieldCommitment {
+    pub field: String,
+    // Hash of the sealed plaintext (field name, value and salt); the value itself is never stored.
+    pub plaintext_hash: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementLocale {
+    #[default]
+    En,
+    Ja,
+    Zh,
+}
+
+impl StatementLocale {
+    pub fn list_from_env(raw: Option<&str>) -> Vec<Self> {
+        raw.unwrap_or("")
+            .split(',')
+            .filter_map(|locale| match locale.trim().to_ascii_lowercase().as_str() {
+                "en" => Some(Self::En),
+                "ja" => Some(Self::Ja),
+                "zh" => Some(Self::Zh),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// Prose rendered from the claim for readers in other locales. Only `statement` is hashed into
+// `claim_hash`; these carry the hash they were derived from and are never canonical.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LocalizedStatement {
+    pub locale: StatementLocale,
+    pub text: String,
+    pub derived_from: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Subject {
     pub venue: Venue,
     pub account_ref: String,
@@ -120,28 +615,130 @@ pub struct Subject {
     pub execution_ref: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct PolicyContext {
     pub policy_id: String,
     pub finality_rule_id: String,
     pub source_precedence_version: String,
+    // Hash of the effective policy documents, tenant overlay included. Absent on receipts issued
+    // before it was recorded.
+    #[serde(default)]
+    pub policy_hash: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Provenance {
     pub evidence_root: String,
     pub evidence_items: Vec<EvidenceItem>,
+    #[serde(default)]
+    pub collection: Vec<CollectionRecord>,
+    #[serde(default)]
+    pub adapter_id: String,
+    #[serde(default)]
+    pub adapter_version: String,
+    #[serde(default)]
+    pub prior_attempts: Vec<PriorAttempt>,
+    // The engine placed the order itself (`ReceiptEngine::execute_and_prove`), so the acceptance
+    // artifact is the venue's answer to that submission rather than an observation of it.
+    #[serde(default)]
+    pub submitted_by_engine: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// A failed run of the pipeline that `retry` superseded, oldest first. Kept for audit only; nothing
+// in it is hashed or signed into the current receipt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PriorAttempt {
+    pub attempt: u32,
+    pub failed_at: String,
+    pub reason_code: NonProvableReason,
+    pub details: String,
+    pub failed_source: Option<String>,
+    pub evidence_root: String,
+    #[serde(default)]
+    pub artifact_hashes: Vec<String>,
+    #[serde(default)]
+    pub adapter_id: String,
+    #[serde(default)]
+    pub adapter_version: String,
+}
+
+// How one evidence item was fetched, so an auditor can repeat the request. Not part of the
+// evidence root; fields named in `hashed_fields` hold a salted hash instead of the raw value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CollectionRecord {
+    pub artifact_hash: String,
+    pub endpoint_url: String,
+    pub request_hash: String,
+    pub adapter_version: String,
+    pub client_ip: Option<String>,
+    pub client_region: Option<String>,
+    pub collected_at: String,
+    #[serde(default)]
+    pub hashed_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Timing {
     pub created_at: String,
     pub updated_at: String,
     pub execution_observed_at: Option<String>,
     pub finality_observed_at: Option<String>,
+    #[serde(default)]
+    pub deadline_miss: Option<DeadlineMiss>,
+    #[serde(default)]
+    pub breakdown: StageBreakdown,
+    // When a receipt re-checking the venue for finality evidence gives up with FINALITY_TIMEOUT.
+    #[serde(default)]
+    pub finality_deadline: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Milliseconds the receipt's last pipeline run spent in each stage. A stage is timed whether it
+// passed or failed; stages the run never reached stay unset. `policy` covers the evidence checks
+// and claim evaluation on both sides of the finality wait, which is timed on its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StageBreakdown {
+    pub ack_ms: Option<u64>,
+    pub collect_ms: Option<u64>,
+    pub policy_ms: Option<u64>,
+    pub finality_ms: Option<u64>,
+    pub prove_ms: Option<u64>,
+    pub verify_ms: Option<u64>,
+    pub anchor_ms: Option<u64>,
+}
+
+impl StageBreakdown {
+    // The timed stages in pipeline order.
+    pub fn stages(&self) -> Vec<(&'static str, u64)> {
+        [
+            ("ack", self.ack_ms),
+            ("collect", self.collect_ms),
+            ("policy", self.policy_ms),
+            ("finality", self.finality_ms),
+            ("prove", self.prove_ms),
+            ("verify", self.verify_ms),
+            ("anchor", self.anchor_ms),
+        ]
+        .into_iter()
+        .filter_map(|(stage, ms)| ms.map(|ms| (stage, ms)))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StageSpend {
+    pub stage: String,
+    pub elapsed_ms: u64,
+}
+
+// Where a request's deadline went: the stages that ran, in order, ending with the one it ran out in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DeadlineMiss {
+    pub budget_ms: u64,
+    pub stage: String,
+    pub spent: Vec<StageSpend>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProofMetadata {
     pub backend: ProofBackend,
     pub circuit_id: String,
@@ -152,23 +749,134 @@ pub struct ProofMetadata {
     pub verification_mode: VerificationMode,
     pub proof_artifact_ref: Option<String>,
     pub anchored_root_ref: Option<String>,
+    #[serde(default)]
+    pub anchor_inclusion: Option<AnchorInclusion>,
+    // Backends that failed, in order, before `backend` produced this proof.
+    #[serde(default)]
+    pub fallback_from: Vec<ProofBackend>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AnchorInclusion {
+    pub anchored_root: String,
+    pub anchor_tx_ref: String,
+    pub leaf: String,
+    pub leaf_index: usize,
+    pub merkle_path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Remediation {
+    #[serde(default)]
+    pub missing_tags: Vec<String>,
+    pub failed_source: Option<String>,
+    pub retry_after_ms: Option<u64>,
+    pub reprove_may_help: bool,
+}
+
+impl Remediation {
+    pub fn for_reason(reason: NonProvableReason) -> Self {
+        let retry_after_ms = match reason {
+            NonProvableReason::SOURCE_UNAVAILABLE => Some(5_000),
+            NonProvableReason::FINALITY_TIMEOUT => Some(30_000),
+            NonProvableReason::PROOF_FAILURE => Some(1_000),
+            _ => None,
+        };
+        Self {
+            missing_tags: vec![],
+            failed_source: None,
+            retry_after_ms,
+            reprove_may_help: retry_after_ms.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NonProvable {
     pub reason_code: NonProvableReason,
     pub details: String,
+    #[serde(default)]
+    pub remediation: Remediation,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl NonProvable {
+    pub fn new(reason_code: NonProvableReason, details: impl Into<String>) -> Self {
+        Self {
+            reason_code,
+            details: details.into(),
+            remediation: Remediation::for_reason(reason_code),
+        }
+    }
+
+    pub fn with_failed_source(mut self, source: impl Into<String>) -> Self {
+        self.remediation.failed_source = Some(source.into());
+        self
+    }
+
+    pub fn with_remediation(mut self, remediation: Remediation) -> Self {
+        self.remediation = remediation;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SignerIdentity {
+    pub name: String,
+    pub key_id: String,
+    pub public_key: String,
+    pub algorithm: String,
+}
+
+impl SignerIdentity {
+    pub fn new(
+        name: impl Into<String>,
+        key_id: impl Into<String>,
+        public_key: impl Into<String>,
+        algorithm: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            key_id: key_id.into(),
+            public_key: public_key.into(),
+            algorithm: algorithm.into(),
+        }
+    }
+
+    pub fn dev() -> Self {
+        Self::new(
+            "zkputer-dev-signer",
+            "zkputer-dev-key-0",
+            hash_str("zkputer-dev-signer"),
+            "sha256-binding",
+        )
+    }
+}
+
+impl Default for SignerIdentity {
+    fn default() -> Self {
+        Self::dev()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Integrity {
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    #[serde(default)]
+    pub commitment_mode: CommitmentMode,
+    #[serde(default)]
+    pub hash_domain_version: u32,
     pub schema_hash: String,
     pub receipt_hash: String,
-    pub signer: String,
+    pub signer: SignerIdentity,
     pub signature: String,
+    #[serde(default)]
+    pub sequence: u64,
+    #[serde(default)]
+    pub issued_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ZKReceipt {
     pub receipt_id: String,
     pub version: String,
@@ -181,6 +889,70 @@ pub struct ZKReceipt {
     pub proof: ProofMetadata,
     pub integrity: Integrity,
     pub non_provable: Option<NonProvable>,
+    #[serde(default)]
+    pub supersedes: Option<String>,
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+    #[serde(default)]
+    pub invalidation: Option<Invalidation>,
+    #[serde(default)]
+    pub revision: u64,
+    #[serde(default)]
+    pub escrow: Option<ReceiptEscrow>,
+}
+
+// Delayed disclosure for a receipt submitted with `escrow_hold_ms`. Outside the receipt hash, so
+// releasing it changes nothing the signature covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ReceiptEscrow {
+    pub release_after: String,
+    pub released_at: Option<String>,
+    pub released_by: Option<String>,
+}
+
+// Who withdrew a proved receipt, why and when. Only `ReceiptEngine::invalidate` sets it; pipeline
+// invalidations (diverged evidence, venue corrections) explain themselves in `non_provable`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Invalidation {
+    pub reason: String,
+    pub actor: String,
+    pub invalidated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum RefreshAction {
+    RECONFIRMED,
+    REPROVING,
+    INVALIDATED,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceRefresh {
+    pub receipt_id: String,
+    pub action: RefreshAction,
+    pub divergences: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicVerificationQuery {
+    ReceiptHash(String),
+    Receipt(Box<ZKReceipt>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicVerification {
+    pub receipt_hash: String,
+    pub known: bool,
+    pub status: Option<ReceiptStatus>,
+    pub integrity_valid: Option<bool>,
+    pub proof_valid: Option<bool>,
+    pub verification_mode: Option<VerificationMode>,
+    pub anchored_root_ref: Option<String>,
+    pub anchor_tx_ref: Option<String>,
+    pub anchor_inclusion_valid: Option<bool>,
+    pub checked_at: String,
 }
 
 pub fn now_iso() -> String {
@@ -192,12 +964,13 @@ pub fn new_receipt_id() -> String {
 }
 
 pub fn hash_str(input: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(input.as_bytes());
-    format!("0x{}", hex::encode(hasher.finalize()))
+    hash_bytes(input.as_bytes())
+}
+
+pub fn hash_bytes(input: &[u8]) -> String {
+    HashAlgorithm::Sha256.hash_bytes(input)
 }
 
 pub fn hash_json(value: &impl Serialize) -> String {
-    let serialized = serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string());
-    hash_str(&serialized)
+    HashAlgorithm::Sha256.hash_json(value)
 }
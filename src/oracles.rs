@@ -0,0 +1,279 @@
+use crate::hashing::HashAlgorithm;
+use crate::models::{hash_json, ClaimType, EvidenceBundle, EvidenceItem, ProofRequest};
+use crate::signing::{verify_ed25519, Ed25519ReceiptSigner, ReceiptSigner};
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const ORACLE_SOURCE_KIND: &str = "oracle_signed_price";
+pub const ORACLE_PRICE_TAG: &str = "oracle_price_quote";
+const ORACLE_QUOTE_SIGNING_DOMAIN: &str = "zkputer-oracle-quote-v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OracleKind {
+    Chainlink,
+    Pyth,
+}
+
+impl OracleKind {
+    pub fn slug(self) -> &'static str {
+        match self {
+            OracleKind::Chainlink => "chainlink",
+            OracleKind::Pyth => "pyth",
+        }
+    }
+}
+
+// One signed price in the shape of a Chainlink round or a Pyth price update. `round` is the round
+// id or publish slot, and `confidence` is Pyth's confidence interval. `signer` is the hex Ed25519
+// public key the quote is signed with. Only the synthetic oracle below produces quotes: there is no
+// adapter reading Chainlink aggregators or verifying Pyth's Wormhole-signed updates yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OracleQuote {
+    pub oracle: OracleKind,
+    pub feed: String,
+    pub price: f64,
+    pub confidence: Option<f64>,
+    pub round: u64,
+    pub published_at: String,
+    pub signer: String,
+    pub signature: String,
+}
+
+impl OracleQuote {
+    pub fn signing_message(&self) -> Vec<u8> {
+        let quote_hash = hash_json(&serde_json::json!({
+            "signer": self.signer,
+            "oracle": self.oracle,
+            "feed": self.feed,
+            "price": self.price,
+            "confidence": self.confidence,
+            "round": self.round,
+            "published_at": self.published_at
+        }));
+        format!("{}:{}", ORACLE_QUOTE_SIGNING_DOMAIN, quote_hash).into_bytes()
+    }
+
+    pub fn verify(&self, public_key: &str) -> bool {
+        self.signer == public_key
+            && verify_ed25519(public_key, &self.signing_message(), &self.signature)
+    }
+}
+
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    fn kind(&self) -> OracleKind;
+    // The hex Ed25519 public key the oracle's quotes are signed with, as the oracle publishes it.
+    fn signer(&self) -> String;
+    // The latest price for `feed` published at or before `at`.
+    async fn quote_at(&self, feed: &str, at: &str) -> Result<OracleQuote>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OracleTolerance {
+    pub max_deviation_bps: f64,
+    pub max_staleness: Duration,
+}
+
+impl Default for OracleTolerance {
+    fn default() -> Self {
+        Self {
+            max_deviation_bps: 50.0,
+            max_staleness: Duration::from_secs(60),
+        }
+    }
+}
+
+// The price the venue reported for the claim: the fill price for best execution, otherwise the
+// declared expected price of a trade.
+pub fn venue_reported_price(request: &ProofRequest, bundle: &EvidenceBundle) -> Option<f64> {
+    match request.claim_type {
+        ClaimType::BEST_EXECUTION => bundle.price_observation.as_ref().map(|p| p.execution_price),
+        ClaimType::TRADE_EXECUTED => request
+            .payload
+            .get("expected_price")
+            .and_then(|v| v.as_f64()),
+        _ => None,
+    }
+}
+
+// Rejects quotes that are unsigned, for another feed, or too far from the claim time.
+pub fn check_quote(
+    quote: &OracleQuote,
+    oracle: &dyn PriceOracle,
+    feed: &str,
+    at: &str,
+    tolerance: &OracleTolerance,
+) -> Result<()> {
+    if quote.oracle != oracle.kind() || quote.feed != feed {
+        bail!(
+            "{} answered for {} {} instead of {}",
+            oracle.kind().slug(),
+            quote.oracle.slug(),
+            quote.feed,
+            feed
+        );
+    }
+    if !quote.verify(&oracle.signer()) {
+        bail!(
+            "{} quote for {} is not signed by {}",
+            quote.oracle.slug(),
+            feed,
+            oracle.signer()
+        );
+    }
+    if !(quote.price.is_finite() && quote.price > 0.0) {
+        bail!(
+            "{} quote for {} has non-positive price {}",
+            quote.oracle.slug(),
+            feed,
+            quote.price
+        );
+    }
+    let age = parse_ts(at)?.signed_duration_since(parse_ts(&quote.published_at)?);
+    let max_age =
+        chrono::Duration::from_std(tolerance.max_staleness).unwrap_or(chrono::Duration::MAX);
+    if age < chrono::Duration::zero() || age > max_age {
+        bail!(
+            "{} quote for {} published at {} is not within {} s before {}",
+            quote.oracle.slug(),
+            feed,
+            quote.published_at,
+            tolerance.max_staleness.as_secs(),
+            at
+        );
+    }
+    Ok(())
+}
+
+// A Pyth quote's confidence interval widens the band, since the oracle itself only vouches for
+// the price to within it.
+pub fn cross_check(
+    venue_price: f64,
+    quote: &OracleQuote,
+    tolerance: &OracleTolerance,
+) -> Option<String> {
+    let deviation = (venue_price - quote.price).abs();
+    let allowed =
+        quote.price * tolerance.max_deviation_bps / 10_000.0 + quote.confidence.unwrap_or(0.0);
+    (deviation > allowed).then(|| {
+        format!(
+            "Venue price {} deviates {:.2} bps from {} {} price {}, over the {} bps tolerance.",
+            venue_price,
+            deviation / quote.price * 10_000.0,
+            quote.oracle.slug(),
+            quote.feed,
+            quote.price,
+            tolerance.max_deviation_bps
+        )
+    })
+}
+
+pub fn quote_evidence(quote: &OracleQuote) -> EvidenceItem {
+    EvidenceItem {
+        source_id: format!("{}-{}", quote.oracle.slug(), quote.feed),
+        source_kind: ORACLE_SOURCE_KIND.to_string(),
+        artifact_ref: format!(
+            "{}://{}/round/{}",
+            quote.oracle.slug(),
+            quote.feed,
+            quote.round
+        ),
+        artifact_hash: hash_json(quote),
+        observed_at: quote.published_at.clone(),
+        tags: vec![ORACLE_PRICE_TAG.to_string()],
+        hash_algorithm: HashAlgorithm::Sha256,
+        size_bytes: serde_json::to_vec(quote).map_or(0, |body| body.len() as u64),
+    }
+}
+
+// Answers from a fixed price table, signing with its own key. Chainlink-style feeds publish on a
+// heartbeat, so the quote is the round that closed at the last heartbeat before the requested time;
+// Pyth-style feeds publish every slot and carry a confidence interval.
+#[derive(Clone)]
+pub struct SyntheticPriceOracle {
+    kind: OracleKind,
+    signer: Arc<Ed25519ReceiptSigner>,
+    prices: HashMap<String, f64>,
+    heartbeat: Duration,
+    confidence_bps: f64,
+}
+
+impl SyntheticPriceOracle {
+    pub fn chainlink(signer: Ed25519ReceiptSigner) -> Self {
+        Self {
+            kind: OracleKind::Chainlink,
+            signer: Arc::new(signer),
+            prices: HashMap::new(),
+            heartbeat: Duration::from_secs(10),
+            confidence_bps: 0.0,
+        }
+    }
+
+    pub fn pyth(signer: Ed25519ReceiptSigner) -> Self {
+        Self {
+            kind: OracleKind::Pyth,
+            signer: Arc::new(signer),
+            prices: HashMap::new(),
+            heartbeat: Duration::from_millis(400),
+            confidence_bps: 2.0,
+        }
+    }
+
+    pub fn with_price(mut self, feed: impl Into<String>, price: f64) -> Self {
+        self.prices.insert(feed.into(), price);
+        self
+    }
+
+    pub fn with_heartbeat(mut self, heartbeat: Duration) -> Self {
+        self.heartbeat = heartbeat;
+        self
+    }
+}
+
+#[async_trait]
+impl PriceOracle for SyntheticPriceOracle {
+    fn kind(&self) -> OracleKind {
+        self.kind
+    }
+
+    fn signer(&self) -> String {
+        self.signer.identity().public_key.clone()
+    }
+
+    async fn quote_at(&self, feed: &str, at: &str) -> Result<OracleQuote> {
+        let price = *self
+            .prices
+            .get(feed)
+            .ok_or_else(|| anyhow!("{} has no feed {}", self.kind.slug(), feed))?;
+        let at_ms = parse_ts(at)?.timestamp_millis();
+        let heartbeat_ms = (self.heartbeat.as_millis() as i64).max(1);
+        let round = at_ms.div_euclid(heartbeat_ms);
+        let published_at = DateTime::<Utc>::from_timestamp_millis(round * heartbeat_ms)
+            .ok_or_else(|| anyhow!("timestamp {} is out of range", at))?
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let mut quote = OracleQuote {
+            oracle: self.kind,
+            feed: feed.to_string(),
+            price,
+            confidence: (self.confidence_bps > 0.0).then(|| price * self.confidence_bps / 10_000.0),
+            round: round as u64,
+            published_at,
+            signer: self.signer(),
+            signature: String::new(),
+        };
+        quote.signature = self.signer.sign(&quote.signing_message());
+        Ok(quote)
+    }
+}
+
+fn parse_ts(raw: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| anyhow!("invalid timestamp {}", raw))
+}
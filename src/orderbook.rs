@@ -0,0 +1,375 @@
+use crate::models::{hash_bytes, Venue};
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+pub const ORDER_BOOK_ENCODING: &str = "zkputer.orderbook.v1";
+pub const MAX_BOOK_DEPTH: usize = 50;
+pub const ORDER_BOOK_SNAPSHOT_TAG: &str = "order_book_snapshot";
+// A best-execution `reference_source` that takes the reference price from the captured book.
+pub const ORDER_BOOK_MID_SOURCE: &str = "order_book_mid";
+
+// Prices and sizes are fixed to 8 decimals on capture, so both encodings below are exact.
+const FIXED_POINT_SCALE: f64 = 100_000_000.0;
+const COMPRESSED_MAGIC: &[u8; 4] = b"ZKOB";
+const COMPRESSED_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: f64,
+    pub size: f64,
+}
+
+impl BookLevel {
+    pub fn new(price: f64, size: f64) -> Self {
+        Self { price, size }
+    }
+}
+
+// The top `depth` levels of each side around submission time, best level first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderBookSnapshot {
+    pub venue: Venue,
+    pub market: String,
+    pub captured_at: String,
+    pub depth: usize,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+impl OrderBookSnapshot {
+    // Levels at the same price are merged, and anything beyond the best `depth` per side is
+    // dropped before the snapshot is hashed.
+    pub fn capture(
+        venue: Venue,
+        market: impl Into<String>,
+        captured_at: impl Into<String>,
+        depth: usize,
+        bids: Vec<BookLevel>,
+        asks: Vec<BookLevel>,
+    ) -> Result<Self> {
+        let (market, captured_at) = (market.into(), captured_at.into());
+        if depth == 0 || depth > MAX_BOOK_DEPTH {
+            bail!(
+                "order book depth must be between 1 and {}, got {}",
+                MAX_BOOK_DEPTH,
+                depth
+            );
+        }
+        check_field("market", &market)?;
+        check_field("captured_at", &captured_at)?;
+        let bids = top_levels(bids, depth, true)?;
+        let asks = top_levels(asks, depth, false)?;
+        if let (Some(bid), Some(ask)) = (bids.first(), asks.first()) {
+            if bid.price >= ask.price {
+                bail!(
+                    "order book is crossed: best bid {} is not below best ask {}",
+                    bid.price,
+                    ask.price
+                );
+            }
+        }
+        Ok(Self {
+            venue,
+            market,
+            captured_at,
+            depth,
+            bids,
+            asks,
+        })
+    }
+
+    pub fn mid_price(&self) -> Option<f64> {
+        let (bid, ask) = (self.bids.first()?, self.asks.first()?);
+        Some(from_fixed((to_fixed(bid.price) + to_fixed(ask.price)) / 2))
+    }
+
+    // One `key=value` header line per field, then one `bid`/`ask` line per level with price and
+    // size as 8-decimal fixed-point integers. Every line ends in `\n`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut out = format!(
+            "{}\nvenue={}\nmarket={}\ncaptured_at={}\ndepth={}\n",
+            ORDER_BOOK_ENCODING,
+            venue_label(self.venue),
+            self.market,
+            self.captured_at,
+            self.depth
+        );
+        for (side, levels) in [("bid", &self.bids), ("ask", &self.asks)] {
+            for level in levels {
+                out.push_str(&format!(
+                    "{} {} {}\n",
+                    side,
+                    to_fixed(level.price),
+                    to_fixed(level.size)
+                ));
+            }
+        }
+        out.into_bytes()
+    }
+
+    pub fn snapshot_hash(&self) -> String {
+        hash_bytes(&self.canonical_bytes())
+    }
+
+    // Compact form for storage: varint header fields, then per side the first price followed by
+    // price deltas between adjacent levels, all zigzag varints. The hash is always taken over the
+    // canonical encoding, never over this.
+    pub fn compress(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(64 + 8 * (self.bids.len() + self.asks.len()));
+        out.extend_from_slice(COMPRESSED_MAGIC);
+        out.push(COMPRESSED_VERSION);
+        for field in [
+            venue_label(self.venue).as_str(),
+            &self.market,
+            &self.captured_at,
+        ] {
+            write_varint(&mut out, field.len() as u64);
+            out.extend_from_slice(field.as_bytes());
+        }
+        write_varint(&mut out, self.depth as u64);
+        for levels in [&self.bids, &self.asks] {
+            write_varint(&mut out, levels.len() as u64);
+            let mut previous = 0;
+            for level in levels {
+                let price = to_fixed(level.price);
+                write_varint(&mut out, zigzag(price - previous));
+                write_varint(&mut out, zigzag(to_fixed(level.size)));
+                previous = price;
+            }
+        }
+        out
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(COMPRESSED_MAGIC.len())? != COMPRESSED_MAGIC {
+            bail!("not a compressed order book snapshot");
+        }
+        let version = reader.take(1)?[0];
+        if version != COMPRESSED_VERSION {
+            bail!("unsupported compressed order book version {}", version);
+        }
+        let venue: Venue = serde_json::from_value(serde_json::Value::String(reader.string()?))
+            .map_err(|_| anyhow!("compressed order book names an unknown venue"))?;
+        let market = reader.string()?;
+        let captured_at = reader.string()?;
+        let depth = reader.varint()? as usize;
+        let mut sides = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let count = reader.varint()? as usize;
+            if count > MAX_BOOK_DEPTH {
+                bail!(
+                    "compressed order book side has {} levels, over the {} limit",
+                    count,
+                    MAX_BOOK_DEPTH
+                );
+            }
+            let mut levels = Vec::with_capacity(count);
+            let mut price = 0i64;
+            for _ in 0..count {
+                price = price
+                    .checked_add(unzigzag(reader.varint()?))
+                    .ok_or_else(|| anyhow!("compressed order book price overflows"))?;
+                let size = unzigzag(reader.varint()?);
+                levels.push(BookLevel::new(from_fixed(price), from_fixed(size)));
+            }
+            sides.push(levels);
+        }
+        if reader.pos != bytes.len() {
+            bail!(
+                "compressed order book has {} trailing bytes",
+                bytes.len() - reader.pos
+            );
+        }
+        let asks = sides.pop().unwrap_or_default();
+        let bids = sides.pop().unwrap_or_default();
+        let snapshot = Self::capture(
+            venue,
+            market,
+            captured_at,
+            depth,
+            bids.clone(),
+            asks.clone(),
+        )?;
+        if snapshot.bids != bids || snapshot.asks != asks {
+            bail!("compressed order book levels are not in canonical order");
+        }
+        Ok(snapshot)
+    }
+}
+
+fn check_field(name: &str, value: &str) -> Result<()> {
+    if value.is_empty() || value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        bail!("order book {} must be non-empty without whitespace", name);
+    }
+    Ok(())
+}
+
+fn top_levels(levels: Vec<BookLevel>, depth: usize, descending: bool) -> Result<Vec<BookLevel>> {
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(levels.len());
+    for level in levels {
+        if !(level.price.is_finite()
+            && level.price > 0.0
+            && level.size.is_finite()
+            && level.size > 0.0)
+        {
+            bail!(
+                "order book level {}@{} must have a positive price and size",
+                level.size,
+                level.price
+            );
+        }
+        let (price, size) = (to_fixed(level.price), to_fixed(level.size));
+        if price == 0 || size == 0 {
+            bail!(
+                "order book level {}@{} is below the fixed-point resolution",
+                level.size,
+                level.price
+            );
+        }
+        match merged.iter_mut().find(|(p, _)| *p == price) {
+            Some((_, total)) => *total = total.saturating_add(size),
+            None => merged.push((price, size)),
+        }
+    }
+    merged.sort_by_key(|(price, _)| if descending { -price } else { *price });
+    merged.truncate(depth);
+    Ok(merged
+        .into_iter()
+        .map(|(price, size)| BookLevel::new(from_fixed(price), from_fixed(size)))
+        .collect())
+}
+
+fn venue_label(venue: Venue) -> String {
+    match serde_json::to_value(venue) {
+        Ok(serde_json::Value::String(label)) => label,
+        _ => format!("{:?}", venue).to_ascii_lowercase(),
+    }
+}
+
+fn to_fixed(value: f64) -> i64 {
+    (value * FIXED_POINT_SCALE).round() as i64
+}
+
+fn from_fixed(value: i64) -> f64 {
+    value as f64 / FIXED_POINT_SCALE
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("compressed order book is truncated"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        bail!("compressed order book varint is too long")
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.varint()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| anyhow!("compressed order book field is not UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> OrderBookSnapshot {
+        OrderBookSnapshot::capture(
+            Venue::Hyperliquid,
+            "BTC-PERP",
+            "2026-03-02T14:00:00.000Z",
+            2,
+            vec![
+                BookLevel::new(64_999.5, 1.25),
+                BookLevel::new(65_000.0, 0.5),
+                BookLevel::new(64_990.0, 3.0),
+            ],
+            vec![
+                BookLevel::new(65_001.0, 0.75),
+                BookLevel::new(65_001.0, 0.25),
+                BookLevel::new(65_010.0, 2.0),
+            ],
+        )
+        .expect("snapshot")
+    }
+
+    #[test]
+    fn capture_keeps_best_levels_and_merges_duplicates() {
+        let book = snapshot();
+        assert_eq!(
+            book.bids,
+            vec![
+                BookLevel::new(65_000.0, 0.5),
+                BookLevel::new(64_999.5, 1.25)
+            ]
+        );
+        assert_eq!(
+            book.asks,
+            vec![BookLevel::new(65_001.0, 1.0), BookLevel::new(65_010.0, 2.0)]
+        );
+        assert_eq!(book.mid_price(), Some(65_000.5));
+    }
+
+    #[test]
+    fn compression_round_trips_and_preserves_the_hash() {
+        let book = snapshot();
+        let compressed = book.compress();
+        assert!(compressed.len() < book.canonical_bytes().len());
+        let restored = OrderBookSnapshot::decompress(&compressed).expect("decompress");
+        assert_eq!(restored, book);
+        assert_eq!(restored.snapshot_hash(), book.snapshot_hash());
+        assert!(OrderBookSnapshot::decompress(&compressed[..compressed.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn crossed_books_are_rejected() {
+        let crossed = OrderBookSnapshot::capture(
+            Venue::Base,
+            "ETH-USDC",
+            "2026-03-02T14:00:00.000Z",
+            5,
+            vec![BookLevel::new(3_001.0, 1.0)],
+            vec![BookLevel::new(3_000.0, 1.0)],
+        );
+        assert!(crossed.is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::models::{hash_json, EncryptedFieldCommitment, ProofRequest};
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{AeadInPlace, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce, Tag};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+// Payload key holding the sealed fields: `{"encrypted_fields": {"<field>": <EncryptedField>}}`.
+pub const ENCRYPTED_FIELDS_KEY: &str = "encrypted_fields";
+const ENCRYPTED_FIELD_VERSION: u32 = 2;
+const FIELD_KEY_INFO: &[u8] = b"zkputer encrypted payload field v2";
+
+// X25519 secret the engine opens sealed payload fields with. Clients seal to its public half,
+// which the engine publishes in its instance descriptor.
+#[derive(Clone)]
+pub struct PayloadDecryptionKey {
+    secret: [u8; 32],
+}
+
+impl fmt::Debug for PayloadDecryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PayloadDecryptionKey({})", self.public_key().to_hex())
+    }
+}
+
+impl PayloadDecryptionKey {
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self { secret }
+    }
+
+    pub fn generate() -> Self {
+        Self::new(random_bytes())
+    }
+
+    pub fn from_hex(secret: &str) -> Result<Self> {
+        let bytes =
+            hex::decode(secret.trim()).context("payload decryption key is not valid hex")?;
+        let secret: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            anyhow!(
+                "payload decryption key must be 32 bytes, got {}",
+                bytes.len()
+            )
+        })?;
+        Ok(Self::new(secret))
+    }
+
+    // The file holds the 32-byte secret as hex.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let secret = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read payload decryption key {}", path.display()))?;
+        Self::from_hex(&secret)
+    }
+
+    pub fn public_key(&self) -> PayloadEncryptionKey {
+        PayloadEncryptionKey(PublicKey::from(&StaticSecret::from(self.secret)).to_bytes())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadEncryptionKey([u8; 32]);
+
+impl PayloadEncryptionKey {
+    pub fn from_hex(public_key: &str) -> Result<Self> {
+        let bytes =
+            hex::decode(public_key.trim()).context("payload encryption key is not valid hex")?;
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| anyhow!("payload encryption key must be 32 bytes"))
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+// One field sealed to the engine's key: X25519 with a fresh ephemeral key, HKDF-SHA256 over the
+// shared secret and both public keys, then ChaCha20-Poly1305. The plaintext carries the field name
+// and a random salt next to the value, so a ciphertext cannot be moved to another field and the
+// committed hash cannot be confirmed by guessing low-entropy values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedField {
+    pub version: u32,
+    pub ephemeral_public_key: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    pub tag: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FieldPlaintext {
+    field: String,
+    value: Value,
+    salt: String,
+}
+
+impl FieldPlaintext {
+    fn commitment(&self) -> EncryptedFieldCommitment {
+        EncryptedFieldCommitment {
+            field: self.field.clone(),
+            plaintext_hash: hash_json(self),
+        }
+    }
+}
+
+// Seals `value` for `field`, returning the envelope to put under the payload's `encrypted_fields`
+// and the plaintext hash the receipt's claim will commit to.
+pub fn seal_payload_field(
+    key: &PayloadEncryptionKey,
+    field: &str,
+    value: &Value,
+) -> Result<(EncryptedField, EncryptedFieldCommitment)> {
+    let ephemeral = EphemeralSecret::random_from_rng(OsRng);
+    let ephemeral_public = PublicKey::from(&ephemeral).to_bytes();
+    let shared = ephemeral.diffie_hellman(&PublicKey::from(key.0));
+    let cipher = field_cipher(
+        shared.was_contributory(),
+        shared.as_bytes(),
+        &ephemeral_public,
+        &key.0,
+    )?;
+    let plaintext = FieldPlaintext {
+        field: field.to_string(),
+        value: value.clone(),
+        salt: hex::encode(random_bytes::<16>()),
+    };
+    let nonce: [u8; 12] = random_bytes();
+    let mut data = serde_json::to_vec(&plaintext)?;
+    let tag = cipher
+        .encrypt_in_place_detached(Nonce::from_slice(&nonce), FIELD_KEY_INFO, &mut data)
+        .map_err(|_| anyhow!("failed to seal payload field {}", field))?;
+    let envelope = EncryptedField {
+        version: ENCRYPTED_FIELD_VERSION,
+        ephemeral_public_key: hex::encode(ephemeral_public),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(&data),
+        tag: hex::encode(tag),
+    };
+    Ok((envelope, plaintext.commitment()))
+}
+
+// The request with its sealed fields opened into the payload, and a commitment per field. A
+// request without sealed fields comes back unchanged. The plaintext only ever lives in the
+// returned copy; callers keep storing the sealed request.
+pub fn open_payload(
+    request: &ProofRequest,
+    key: Option<&PayloadDecryptionKey>,
+) -> Result<(ProofRequest, Vec<EncryptedFieldCommitment>)> {
+    let Some(sealed) = request.payload.get(ENCRYPTED_FIELDS_KEY) else {
+        return Ok((request.clone(), vec![]));
+    };
+    let key = key.ok_or_else(|| {
+        anyhow!("payload has encrypted fields but this engine has no payload decryption key")
+    })?;
+    let fields: BTreeMap<String, EncryptedField> = serde_json::from_value(sealed.clone())
+        .context("encrypted_fields must map field names to encrypted fields")?;
+    let mut payload: Map<String, Value> = request.payload.as_object().cloned().unwrap_or_default();
+    payload.remove(ENCRYPTED_FIELDS_KEY);
+    let mut commitments = Vec::with_capacity(fields.len());
+    for (field, envelope) in fields {
+        if payload.contains_key(&field) {
+            bail!(
+                "payload field {} is given both encrypted and in plaintext",
+                field
+            );
+        }
+        let plaintext =
+            open_field(key, &envelope).with_context(|| format!("encrypted field {}", field))?;
+        if plaintext.field != field {
+            bail!(
+                "encrypted field {} was sealed for field {}",
+                field,
+                plaintext.field
+            );
+        }
+        commitments.push(plaintext.commitment());
+        payload.insert(field, plaintext.value);
+    }
+    let mut opened = request.clone();
+    opened.payload = Value::Object(payload);
+    Ok((opened, commitments))
+}
+
+fn open_field(key: &PayloadDecryptionKey, envelope: &EncryptedField) -> Result<FieldPlaintext> {
+    if envelope.version != ENCRYPTED_FIELD_VERSION {
+        bail!("unsupported encrypted field version {}", envelope.version);
+    }
+    let ephemeral_public: [u8; 32] = hex::decode(&envelope.ephemeral_public_key)
+        .context("ephemeral public key must be hex")?
+        .try_into()
+        .map_err(|_| anyhow!("ephemeral public key must be 32 bytes"))?;
+    let nonce = hex::decode(&envelope.nonce).context("nonce must be hex")?;
+    let mut data = hex::decode(&envelope.ciphertext).context("ciphertext must be hex")?;
+    let tag = hex::decode(&envelope.tag).context("tag must be hex")?;
+    if nonce.len() != 12 || tag.len() != 16 {
+        bail!("nonce or tag has the wrong length");
+    }
+    let shared = StaticSecret::from(key.secret).diffie_hellman(&PublicKey::from(ephemeral_public));
+    let cipher = field_cipher(
+        shared.was_contributory(),
+        shared.as_bytes(),
+        &ephemeral_public,
+        &key.public_key().0,
+    )?;
+    cipher
+        .decrypt_in_place_detached(
+            Nonce::from_slice(&nonce),
+            FIELD_KEY_INFO,
+            &mut data,
+            Tag::from_slice(&tag),
+        )
+        .map_err(|_| anyhow!("failed authentication (sealed to another key, or tampered)"))?;
+    serde_json::from_slice(&data).context("plaintext is malformed")
+}
+
+fn field_cipher(
+    contributory: bool,
+    shared: &[u8; 32],
+    ephemeral_public: &[u8; 32],
+    recipient: &[u8; 32],
+) -> Result<ChaCha20Poly1305> {
+    // A low-order public key yields the all-zero secret, which would key every field the same way.
+    if !contributory {
+        bail!("payload encryption key is not a valid X25519 public key");
+    }
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral_public);
+    salt[32..].copy_from_slice(recipient);
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(FIELD_KEY_INFO, &mut key)
+        .map_err(|_| anyhow!("failed to derive the payload field key"))?;
+    Ok(ChaCha20Poly1305::new(&key.into()))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
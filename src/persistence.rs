@@ -0,0 +1,598 @@
+use crate::events::{fold_events, ReceiptEvent};
+use crate::leader::Lease;
+use crate::models::{CostRecord, ProofRequest, ZKReceipt};
+use crate::screening::ScreeningDecision;
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Transaction, TransactionBehavior};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+pub struct StoredRecords {
+    pub receipts: Vec<ZKReceipt>,
+    pub requests: Vec<(String, ProofRequest)>,
+    pub costs: Vec<(String, CostRecord)>,
+    pub screenings: Vec<ScreeningDecision>,
+}
+
+const CHANGE_RECEIPT: &str = "receipt";
+const CHANGE_REQUEST: &str = "request";
+const CHANGE_COST: &str = "cost";
+const CHANGE_SCREENING: &str = "screening";
+
+// How far a reader has got through a backend's writes; the default reads from the start.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChangeCursor(pub u64);
+
+// Durable storage behind `ReceiptStore`. The store keeps serving reads from memory and writes
+// through to the backend, so a backend only has to persist and reload records.
+pub trait ReceiptBackend: Send + Sync {
+    fn load(&self) -> Result<StoredRecords>;
+
+    // What was written after `cursor`, and the cursor to read from next time. Backends that
+    // cannot tell return everything each time, which `ReceiptStore::refresh` tolerates.
+    fn load_changes(&self, cursor: ChangeCursor) -> Result<(StoredRecords, ChangeCursor)> {
+        Ok((self.load()?, cursor))
+    }
+
+    // Writes `receipt` only if the stored copy is still at `expected_revision` (0: no copy yet),
+    // so engines sharing the backend cannot overwrite each other's writes. With a `fence`, only
+    // while that lease is still in the same tenure, so a replaced leader cannot write either. A
+    // refused write is an error and leaves the stored copy alone.
+    fn compare_and_swap_receipt(
+        &self,
+        receipt: &ZKReceipt,
+        expected_revision: u64,
+        fence: Option<&Lease>,
+    ) -> Result<()>;
+    fn put_request(&self, receipt_id: &str, request: &ProofRequest) -> Result<()>;
+    fn put_cost(&self, receipt_id: &str, cost: &CostRecord) -> Result<()>;
+    fn put_screening(&self, decision: &ScreeningDecision) -> Result<()>;
+
+    // Every write of a receipt, oldest first. Only event-sourced backends keep them.
+    fn history(&self, receipt_id: &str) -> Result<Vec<ReceiptEvent>> {
+        bail!(
+            "receipt backend keeps only current receipts, not the history of {}",
+            receipt_id
+        )
+    }
+
+    // Claims the named lease for `holder` per `Lease::claim` and returns whoever holds it after.
+    // Backends shared between engines must do this atomically.
+    fn acquire_lease(&self, name: &str, holder: &str, ttl: Duration, now_ms: u64) -> Result<Lease> {
+        let _ = (holder, ttl, now_ms);
+        bail!("receipt backend cannot hold the {} lease", name)
+    }
+
+    // Gives the lease up early if `holder` still has it.
+    fn release_lease(&self, name: &str, holder: &str) -> Result<()> {
+        let _ = holder;
+        bail!("receipt backend cannot hold the {} lease", name)
+    }
+}
+
+// Receipts are stored as their JSON body beside the columns an operator is likely to filter on,
+// so the database can be queried directly after the fact. Every write also appends to `changes`,
+// so engines refreshing from the database read only what changed since they last looked.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS receipts (
+    receipt_id TEXT PRIMARY KEY,
+    revision INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    venue TEXT NOT NULL,
+    claim_type TEXT NOT NULL,
+    account_ref TEXT NOT NULL,
+    receipt_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    body TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS receipts_status ON receipts (status);
+CREATE INDEX IF NOT EXISTS receipts_receipt_hash ON receipts (receipt_hash);
+CREATE TABLE IF NOT EXISTS requests (
+    receipt_id TEXT PRIMARY KEY,
+    body TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS costs (
+    receipt_id TEXT PRIMARY KEY,
+    body TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS screenings (
+    decision_hash TEXT PRIMARY KEY,
+    receipt_id TEXT NOT NULL,
+    decided_at TEXT NOT NULL,
+    body TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS receipt_events (
+    receipt_id TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    recorded_at TEXT NOT NULL,
+    event_hash TEXT NOT NULL,
+    body TEXT NOT NULL,
+    PRIMARY KEY (receipt_id, revision)
+);
+CREATE TRIGGER IF NOT EXISTS receipt_events_no_update BEFORE UPDATE ON receipt_events
+BEGIN SELECT RAISE(ABORT, 'receipt events are immutable'); END;
+CREATE TRIGGER IF NOT EXISTS receipt_events_no_delete BEFORE DELETE ON receipt_events
+BEGIN SELECT RAISE(ABORT, 'receipt events are immutable'); END;
+CREATE TABLE IF NOT EXISTS leases (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    epoch INTEGER NOT NULL,
+    expires_at_ms INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS changes (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    record_key TEXT NOT NULL
+);
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReceiptLayout {
+    // One row per receipt, replaced on every write.
+    Current,
+    // One immutable row per write in `receipt_events`; receipts are folded from them on load.
+    EventSourced,
+}
+
+pub struct SqliteReceiptBackend {
+    conn: Mutex<Connection>,
+    layout: ReceiptLayout,
+    // Latest event and folded receipt per receipt id, so a write only has to diff against it.
+    heads: Mutex<HashMap<String, (ReceiptEvent, ZKReceipt)>>,
+}
+
+impl SqliteReceiptBackend {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(open_file(path.as_ref())?, ReceiptLayout::Current)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?, ReceiptLayout::Current)
+    }
+
+    pub fn open_event_sourced(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(open_file(path.as_ref())?, ReceiptLayout::EventSourced)
+    }
+
+    pub fn open_event_sourced_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?, ReceiptLayout::EventSourced)
+    }
+
+    fn with_connection(conn: Connection, layout: ReceiptLayout) -> Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("failed to create receipt database schema")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            layout,
+            heads: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn is_event_sourced(&self) -> bool {
+        self.layout == ReceiptLayout::EventSourced
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn heads(&self) -> std::sync::MutexGuard<'_, HashMap<String, (ReceiptEvent, ZKReceipt)>> {
+        self.heads
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn events(conn: &Connection, receipt_id: &str) -> Result<Vec<ReceiptEvent>> {
+        let mut stmt = conn
+            .prepare("SELECT body FROM receipt_events WHERE receipt_id = ?1 ORDER BY revision")?;
+        let rows = stmt.query_map([receipt_id], |row| row.get::<_, String>(0))?;
+        rows.map(|body| Ok(serde_json::from_str(&body?)?)).collect()
+    }
+
+    // Immediate, so the checks a write makes still hold when it commits.
+    fn write(&self, write: impl FnOnce(&Transaction) -> Result<()>) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        write(&tx)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn load_all(&self, conn: &Connection) -> Result<StoredRecords> {
+        let receipts = match self.layout {
+            ReceiptLayout::Current => bodies(
+                conn,
+                "SELECT body FROM receipts ORDER BY created_at, receipt_id",
+            )?,
+            ReceiptLayout::EventSourced => self.load_folded(conn)?,
+        };
+        let requests = keyed_bodies(conn, "SELECT receipt_id, body FROM requests")?;
+        let costs = keyed_bodies(conn, "SELECT receipt_id, body FROM costs")?;
+        let screenings = bodies(conn, "SELECT body FROM screenings ORDER BY rowid")?;
+        Ok(StoredRecords {
+            receipts,
+            requests,
+            costs,
+            screenings,
+        })
+    }
+
+    // The current copy of one receipt, folding its events for the event-sourced layout.
+    fn load_receipt(&self, conn: &Connection, receipt_id: &str) -> Result<Option<ZKReceipt>> {
+        if self.layout == ReceiptLayout::Current {
+            return body_by_key(
+                conn,
+                "SELECT body FROM receipts WHERE receipt_id = ?1",
+                receipt_id,
+            );
+        }
+        let history = Self::events(conn, receipt_id)?;
+        let Some(last) = history.last() else {
+            return Ok(None);
+        };
+        let receipt = fold_events(&history)?;
+        self.heads()
+            .insert(receipt_id.to_string(), (last.clone(), receipt.clone()));
+        Ok(Some(receipt))
+    }
+
+    fn load_folded(&self, conn: &Connection) -> Result<Vec<ZKReceipt>> {
+        let events: Vec<ReceiptEvent> = bodies(
+            conn,
+            "SELECT body FROM receipt_events ORDER BY receipt_id, revision",
+        )?;
+        let mut histories: BTreeMap<String, Vec<ReceiptEvent>> = BTreeMap::new();
+        for event in events {
+            histories
+                .entry(event.receipt_id.clone())
+                .or_default()
+                .push(event);
+        }
+        let mut heads = self.heads();
+        let mut receipts = Vec::with_capacity(histories.len());
+        for (receipt_id, history) in histories {
+            let receipt = fold_events(&history)?;
+            if let Some(last) = history.into_iter().last() {
+                heads.insert(receipt_id, (last, receipt.clone()));
+            }
+            receipts.push(receipt);
+        }
+        receipts.sort_by(|a, b| {
+            (&a.timing.created_at, &a.receipt_id).cmp(&(&b.timing.created_at, &b.receipt_id))
+        });
+        Ok(receipts)
+    }
+
+    // The cached head may be behind what another engine appended, so a mismatch re-reads it, and
+    // the (receipt_id, revision) key stops two writers appending the same revision.
+    fn append_event(
+        &self,
+        conn: &Connection,
+        receipt: &ZKReceipt,
+        expected_revision: u64,
+    ) -> Result<()> {
+        let mut heads = self.heads();
+        let head_revision = |heads: &HashMap<String, (ReceiptEvent, ZKReceipt)>| {
+            heads
+                .get(&receipt.receipt_id)
+                .map_or(0, |(event, _)| event.revision)
+        };
+        if head_revision(&heads) != expected_revision {
+            let history = Self::events(conn, &receipt.receipt_id)?;
+            if let Some(last) = history.last() {
+                heads.insert(
+                    receipt.receipt_id.clone(),
+                    (last.clone(), fold_events(&history)?),
+                );
+            }
+        }
+        if head_revision(&heads) != expected_revision || receipt.revision != expected_revision + 1 {
+            bail!(conflict(&receipt.receipt_id, expected_revision));
+        }
+        let head = heads.get(&receipt.receipt_id);
+        let event = ReceiptEvent::next(head.map(|(event, folded)| (event, folded)), receipt)?;
+        let appended = conn.execute(
+            "INSERT OR IGNORE INTO receipt_events (receipt_id, revision, status, recorded_at, event_hash, body)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                event.receipt_id,
+                event.revision as i64,
+                label(&event.status),
+                event.recorded_at,
+                event.event_hash,
+                serde_json::to_string(&event)?,
+            ],
+        )?;
+        if appended != 1 {
+            heads.remove(&receipt.receipt_id);
+            bail!(conflict(&receipt.receipt_id, expected_revision));
+        }
+        heads.insert(receipt.receipt_id.clone(), (event, receipt.clone()));
+        Ok(())
+    }
+
+    // A new receipt must not exist yet; an update must find the row still at the expected revision.
+    fn replace_receipt(
+        conn: &Connection,
+        receipt: &ZKReceipt,
+        expected_revision: u64,
+    ) -> Result<()> {
+        let (revision, expected) = (receipt.revision as i64, expected_revision as i64);
+        let (status, venue, claim_type) = (
+            label(&receipt.status),
+            label(&receipt.subject.venue),
+            label(&receipt.claim.r#type),
+        );
+        let body = serde_json::to_string(receipt)?;
+        let mut values = params![
+            receipt.receipt_id,
+            revision,
+            status,
+            venue,
+            claim_type,
+            receipt.subject.account_ref,
+            receipt.integrity.receipt_hash,
+            receipt.timing.created_at,
+            body,
+        ]
+        .to_vec();
+        let written = if expected_revision == 0 {
+            conn.execute(
+                "INSERT OR IGNORE INTO receipts
+                    (receipt_id, revision, status, venue, claim_type, account_ref, receipt_hash, created_at, body)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                values.as_slice(),
+            )?
+        } else {
+            values.push(&expected);
+            conn.execute(
+                "UPDATE receipts SET revision = ?2, status = ?3, venue = ?4, claim_type = ?5, account_ref = ?6,
+                    receipt_hash = ?7, created_at = ?8, body = ?9
+                 WHERE receipt_id = ?1 AND revision = ?10",
+                values.as_slice(),
+            )?
+        };
+        if written != 1 {
+            bail!(conflict(&receipt.receipt_id, expected_revision));
+        }
+        Ok(())
+    }
+}
+
+fn open_file(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)
+        .with_context(|| format!("failed to open receipt database {}", path.display()))?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    // Engines sharing the file contend for the lease row, so wait out their write locks.
+    conn.busy_timeout(Duration::from_secs(5))?;
+    Ok(conn)
+}
+
+impl ReceiptBackend for SqliteReceiptBackend {
+    fn load(&self) -> Result<StoredRecords> {
+        self.load_all(&self.conn())
+    }
+
+    fn load_changes(&self, cursor: ChangeCursor) -> Result<(StoredRecords, ChangeCursor)> {
+        let mut conn = self.conn();
+        // One read transaction, so the records and the cursor come from the same snapshot.
+        let tx = conn.transaction()?;
+        let latest = ChangeCursor(tx.query_row(
+            "SELECT COALESCE(MAX(seq), 0) FROM changes",
+            [],
+            |row| row.get::<_, i64>(0),
+        )? as u64);
+        if cursor == ChangeCursor::default() {
+            return Ok((self.load_all(&tx)?, latest));
+        }
+        let mut changed: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        {
+            let mut stmt =
+                tx.prepare("SELECT kind, record_key FROM changes WHERE seq > ?1 AND seq <= ?2")?;
+            let rows = stmt.query_map(params![cursor.0 as i64, latest.0 as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                let (kind, key) = row?;
+                changed.entry(kind).or_default().insert(key);
+            }
+        }
+        let keys = |kind: &str| changed.get(kind).into_iter().flatten();
+        let mut records = StoredRecords::default();
+        for receipt_id in keys(CHANGE_RECEIPT) {
+            records.receipts.extend(self.load_receipt(&tx, receipt_id)?);
+        }
+        for receipt_id in keys(CHANGE_REQUEST) {
+            if let Some(request) = body_by_key(
+                &tx,
+                "SELECT body FROM requests WHERE receipt_id = ?1",
+                receipt_id,
+            )? {
+                records.requests.push((receipt_id.clone(), request));
+            }
+        }
+        for receipt_id in keys(CHANGE_COST) {
+            if let Some(cost) = body_by_key(
+                &tx,
+                "SELECT body FROM costs WHERE receipt_id = ?1",
+                receipt_id,
+            )? {
+                records.costs.push((receipt_id.clone(), cost));
+            }
+        }
+        for decision_hash in keys(CHANGE_SCREENING) {
+            records.screenings.extend(body_by_key(
+                &tx,
+                "SELECT body FROM screenings WHERE decision_hash = ?1",
+                decision_hash,
+            )?);
+        }
+        Ok((records, latest))
+    }
+
+    fn compare_and_swap_receipt(
+        &self,
+        receipt: &ZKReceipt,
+        expected_revision: u64,
+        fence: Option<&Lease>,
+    ) -> Result<()> {
+        self.write(|tx| {
+            if let Some(fence) = fence {
+                fence.check_fence(read_lease(tx, &fence.name)?.as_ref())?;
+            }
+            match self.layout {
+                ReceiptLayout::Current => Self::replace_receipt(tx, receipt, expected_revision)?,
+                ReceiptLayout::EventSourced => self.append_event(tx, receipt, expected_revision)?,
+            }
+            record_change(tx, CHANGE_RECEIPT, &receipt.receipt_id)
+        })
+    }
+
+    fn put_request(&self, receipt_id: &str, request: &ProofRequest) -> Result<()> {
+        self.write(|tx| {
+            tx.execute(
+                "INSERT OR REPLACE INTO requests (receipt_id, body) VALUES (?1, ?2)",
+                params![receipt_id, serde_json::to_string(request)?],
+            )?;
+            record_change(tx, CHANGE_REQUEST, receipt_id)
+        })
+    }
+
+    fn put_cost(&self, receipt_id: &str, cost: &CostRecord) -> Result<()> {
+        self.write(|tx| {
+            tx.execute(
+                "INSERT OR REPLACE INTO costs (receipt_id, body) VALUES (?1, ?2)",
+                params![receipt_id, serde_json::to_string(cost)?],
+            )?;
+            record_change(tx, CHANGE_COST, receipt_id)
+        })
+    }
+
+    fn put_screening(&self, decision: &ScreeningDecision) -> Result<()> {
+        self.write(|tx| {
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO screenings (decision_hash, receipt_id, decided_at, body) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    decision.decision_hash,
+                    decision.receipt_id,
+                    decision.decided_at,
+                    serde_json::to_string(decision)?,
+                ],
+            )?;
+            if inserted == 0 {
+                return Ok(());
+            }
+            record_change(tx, CHANGE_SCREENING, &decision.decision_hash)
+        })
+    }
+
+    fn history(&self, receipt_id: &str) -> Result<Vec<ReceiptEvent>> {
+        if self.layout != ReceiptLayout::EventSourced {
+            bail!(
+                "receipt database keeps only current receipts, not the history of {}",
+                receipt_id
+            );
+        }
+        Self::events(&self.conn(), receipt_id)
+    }
+
+    fn acquire_lease(&self, name: &str, holder: &str, ttl: Duration, now_ms: u64) -> Result<Lease> {
+        let mut conn = self.conn();
+        // Immediate, so two engines cannot both read the lease free and both take it.
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let current = read_lease(&tx, name)?;
+        let lease = Lease::claim(current.as_ref(), name, holder, ttl, now_ms);
+        if current.as_ref() != Some(&lease) {
+            tx.execute(
+                "INSERT OR REPLACE INTO leases (name, holder, epoch, expires_at_ms) VALUES (?1, ?2, ?3, ?4)",
+                params![lease.name, lease.holder, lease.epoch as i64, lease.expires_at_ms as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(lease)
+    }
+
+    fn release_lease(&self, name: &str, holder: &str) -> Result<()> {
+        // Expiring the row rather than deleting it keeps the epoch counting up.
+        self.conn().execute(
+            "UPDATE leases SET expires_at_ms = 0 WHERE name = ?1 AND holder = ?2",
+            params![name, holder],
+        )?;
+        Ok(())
+    }
+}
+
+fn read_lease(conn: &Connection, name: &str) -> Result<Option<Lease>> {
+    let lease = conn
+        .query_row(
+            "SELECT holder, epoch, expires_at_ms FROM leases WHERE name = ?1",
+            [name],
+            |row| {
+                Ok(Lease {
+                    name: name.to_string(),
+                    holder: row.get(0)?,
+                    epoch: row.get::<_, i64>(1)? as u64,
+                    expires_at_ms: row.get::<_, i64>(2)? as u64,
+                })
+            },
+        )
+        .optional()?;
+    Ok(lease)
+}
+
+fn record_change(conn: &Connection, kind: &str, key: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO changes (kind, record_key) VALUES (?1, ?2)",
+        params![kind, key],
+    )?;
+    Ok(())
+}
+
+fn conflict(receipt_id: &str, expected_revision: u64) -> String {
+    format!(
+        "receipt {} was modified concurrently: the stored copy is no longer at revision {}",
+        receipt_id, expected_revision
+    )
+}
+
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn bodies<T: DeserializeOwned>(conn: &Connection, sql: &str) -> Result<Vec<T>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    rows.map(|body| Ok(serde_json::from_str(&body?)?)).collect()
+}
+
+fn body_by_key<T: DeserializeOwned>(conn: &Connection, sql: &str, key: &str) -> Result<Option<T>> {
+    let body = conn
+        .query_row(sql, [key], |row| row.get::<_, String>(0))
+        .optional()?;
+    body.map(|body| Ok(serde_json::from_str(&body)?))
+        .transpose()
+}
+
+fn keyed_bodies<T: DeserializeOwned>(conn: &Connection, sql: &str) -> Result<Vec<(String, T)>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    rows.map(|row| {
+        let (key, body) = row?;
+        Ok((key, serde_json::from_str(&body)?))
+    })
+    .collect()
+}
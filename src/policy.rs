@@ -1,35 +1,216 @@
-use crate::models::{ClaimType, EvidenceBundle, NonProvableReason, Venue};
-use anyhow::{Context, Result};
+use crate::clock::TimestampTolerance;
+use crate::finality::FinalityRule;
+use crate::models::{
+    hash_json, ClaimType, EvidenceBundle, NonProvable, NonProvableReason, PolicyContext,
+    Remediation, Venue,
+};
+use crate::oracles::OracleTolerance;
+use crate::orderbook::{ORDER_BOOK_MID_SOURCE, ORDER_BOOK_SNAPSHOT_TAG};
+use anyhow::{bail, Context, Result};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+pub const CLAIM_TAXONOMY_FILE: &str = "claim-taxonomy.json";
+pub const SOURCE_PRECEDENCE_FILE: &str = "source-precedence.json";
+pub const FINALITY_RULES_FILE: &str = "finality-rules.json";
+pub const DEFAULT_POLICY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+// The documents a tenant overlay may patch.
+const OVERLAY_DOCUMENTS: [&str; 3] = ["claim_taxonomy", "source_precedence", "finality_rules"];
+
+// With `embedded-spec` the default policy documents are compiled into the crate, so a binary or
+// dependent crate works without the repo's spec/ directory next to it.
+#[cfg(feature = "embedded-spec")]
+const EMBEDDED_CLAIM_TAXONOMY: &str = include_str!("../spec/claim-taxonomy.json");
+#[cfg(feature = "embedded-spec")]
+const EMBEDDED_SOURCE_PRECEDENCE: &str = include_str!("../spec/source-precedence.json");
+#[cfg(feature = "embedded-spec")]
+const EMBEDDED_FINALITY_RULES: &str = include_str!("../spec/finality-rules.json");
 
 #[derive(Debug, Clone)]
 pub struct PolicyDecision {
     pub ok: bool,
     pub reason: Option<NonProvableReason>,
     pub details: String,
+    pub remediation: Option<Remediation>,
+}
+
+impl PolicyDecision {
+    pub fn into_non_provable(self) -> NonProvable {
+        let non_provable = NonProvable::new(
+            self.reason.unwrap_or(NonProvableReason::POLICY_VIOLATION),
+            self.details,
+        );
+        match self.remediation {
+            Some(remediation) => non_provable.with_remediation(remediation),
+            None => non_provable,
+        }
+    }
 }
 
+// The documents are shared, so a clone is cheap and a pinned snapshot of the policy in force.
 #[derive(Debug, Clone)]
 pub struct PolicyEngine {
-    claim_taxonomy: Value,
-    source_precedence: Value,
+    claim_taxonomy: Arc<Value>,
+    source_precedence: Arc<Value>,
+    // Null until `with_finality_rules`; without rules no claim waits on finality evidence.
+    finality_rules: Arc<Value>,
+    revision: u64,
+    // Set on the effective policy of a tenant that has an overlay.
+    tenant: Option<String>,
+    tenant_overlays: Arc<BTreeMap<String, Value>>,
 }
 
 impl PolicyEngine {
+    // Without a root, the default documents: embedded ones under `embedded-spec`, otherwise the
+    // crate checkout's spec/ directory. Either way the finality rules come with them.
     pub fn new(repo_root: Option<&Path>) -> Result<Self> {
-        let root = repo_root
-            .map(PathBuf::from)
-            .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")));
-        let spec_dir = root.join("spec");
-        let claim_taxonomy = read_json(&spec_dir.join("claim-taxonomy.json"))?;
-        let source_precedence = read_json(&spec_dir.join("source-precedence.json"))?;
+        match repo_root {
+            Some(root) => {
+                let spec_dir = root.join("spec");
+                Self::from_files(
+                    &spec_dir.join(CLAIM_TAXONOMY_FILE),
+                    &spec_dir.join(SOURCE_PRECEDENCE_FILE),
+                )?
+                .with_finality_rules(read_json(&spec_dir.join(FINALITY_RULES_FILE))?)
+            }
+            None => Self::from_values(
+                default_spec_document(CLAIM_TAXONOMY_FILE)?,
+                default_spec_document(SOURCE_PRECEDENCE_FILE)?,
+            )?
+            .with_finality_rules(default_spec_document(FINALITY_RULES_FILE)?),
+        }
+    }
+
+    // Loads the two policy documents from wherever a deployment keeps them.
+    pub fn from_files(claim_taxonomy: &Path, source_precedence: &Path) -> Result<Self> {
+        Self::from_values(read_json(claim_taxonomy)?, read_json(source_precedence)?)
+    }
+
+    // For documents that never touch the filesystem: fetched from a config service, or built in code.
+    pub fn from_values(claim_taxonomy: Value, source_precedence: Value) -> Result<Self> {
+        check_documents(&claim_taxonomy, &source_precedence)?;
         Ok(Self {
-            claim_taxonomy,
-            source_precedence,
+            claim_taxonomy: Arc::new(claim_taxonomy),
+            source_precedence: Arc::new(source_precedence),
+            finality_rules: Arc::new(Value::Null),
+            revision: 0,
+            tenant: None,
+            tenant_overlays: Arc::default(),
         })
     }
 
+    // Gates the document's `gated_claims` on each venue's rule. Every rule must parse, so a typo
+    // cannot quietly leave a venue ungated.
+    pub fn with_finality_rules(mut self, finality_rules: Value) -> Result<Self> {
+        check_finality_rules(&finality_rules)?;
+        self.finality_rules = Arc::new(finality_rules);
+        self.check_tenant_overlays()?;
+        Ok(self)
+    }
+
+    // Patches the base documents for one tenant's requests. The overlay is an object keyed by
+    // `claim_taxonomy`, `source_precedence` and `finality_rules`, each a JSON merge patch (RFC 7396)
+    // on that document: objects merge, null removes a key, anything else replaces. The patched
+    // documents must pass the same checks as the base ones, now and on every reload.
+    pub fn with_tenant_overlay(
+        mut self,
+        tenant: impl Into<String>,
+        overlay: Value,
+    ) -> Result<Self> {
+        let tenant = tenant.into();
+        if tenant.trim().is_empty() {
+            bail!("tenant overlay needs a tenant id");
+        }
+        let Some(documents) = overlay.as_object() else {
+            bail!("tenant overlay for {} is not an object", tenant);
+        };
+        if let Some(unknown) = documents
+            .keys()
+            .find(|key| !OVERLAY_DOCUMENTS.contains(&key.as_str()))
+        {
+            bail!(
+                "tenant overlay for {} patches unknown document {}",
+                tenant,
+                unknown
+            );
+        }
+        self.overlaid(&tenant, &overlay)
+            .check()
+            .with_context(|| format!("invalid policy overlay for tenant {}", tenant))?;
+        Arc::make_mut(&mut self.tenant_overlays).insert(tenant, overlay);
+        Ok(self)
+    }
+
+    // Tenants with an overlay, in id order.
+    pub fn tenants(&self) -> Vec<String> {
+        self.tenant_overlays.keys().cloned().collect()
+    }
+
+    // The policy a tenant's requests are checked and proved under: the base documents with the
+    // tenant's overlay applied. Tenants without one, and requests without a tenant, get the base.
+    pub fn for_tenant(&self, tenant: Option<&str>) -> PolicyEngine {
+        match tenant.and_then(|tenant| self.tenant_overlays.get_key_value(tenant)) {
+            Some((tenant, overlay)) => self.overlaid(tenant, overlay),
+            None => self.clone(),
+        }
+    }
+
+    fn overlaid(&self, tenant: &str, overlay: &Value) -> PolicyEngine {
+        let mut policy = self.clone();
+        let documents = [
+            &mut policy.claim_taxonomy,
+            &mut policy.source_precedence,
+            &mut policy.finality_rules,
+        ];
+        for (name, document) in OVERLAY_DOCUMENTS.iter().zip(documents) {
+            if let Some(patch) = overlay.get(*name) {
+                merge_patch(Arc::make_mut(document), patch);
+            }
+        }
+        policy.tenant = Some(tenant.to_string());
+        policy
+    }
+
+    fn check(&self) -> Result<()> {
+        check_documents(&self.claim_taxonomy, &self.source_precedence)?;
+        if !self.finality_rules.is_null() {
+            check_finality_rules(&self.finality_rules)?;
+        }
+        Ok(())
+    }
+
+    fn check_tenant_overlays(&self) -> Result<()> {
+        for (tenant, overlay) in self.tenant_overlays.iter() {
+            self.overlaid(tenant, overlay)
+                .check()
+                .with_context(|| format!("invalid policy overlay for tenant {}", tenant))?;
+        }
+        Ok(())
+    }
+
+    // Loads the documents and keeps polling them from a Tokio task; each change that parses becomes
+    // the next revision. A change that does not parse leaves the policy in force and is reported
+    // by `PolicyWatch::last_error` until the files are fixed.
+    pub fn watch(
+        claim_taxonomy: impl Into<PathBuf>,
+        source_precedence: impl Into<PathBuf>,
+    ) -> Result<PolicyWatch> {
+        PolicyWatch::start(
+            claim_taxonomy.into(),
+            source_precedence.into(),
+            DEFAULT_POLICY_POLL_INTERVAL,
+        )
+    }
+
+    // How many times the watched documents have been reloaded; 0 for a policy loaded once.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
     pub fn source_precedence_version(&self) -> String {
         self.source_precedence
             .get("version")
@@ -39,19 +220,76 @@ impl PolicyEngine {
     }
 
     pub fn policy_id(&self) -> String {
-        "default-v0.1.0".to_string()
+        let base = match self.revision {
+            0 => "default-v0.1.0".to_string(),
+            revision => format!("default-v0.1.0-r{}", revision),
+        };
+        match &self.tenant {
+            Some(tenant) => format!("{}+{}", base, tenant),
+            None => base,
+        }
+    }
+
+    // Commits to the documents in force, so two receipts with the same hash were held to the same
+    // standard whatever their policy ids say.
+    pub fn policy_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "claim_taxonomy": *self.claim_taxonomy,
+            "source_precedence": *self.source_precedence,
+            "finality_rules": *self.finality_rules
+        }))
     }
 
     pub fn finality_rule_id(&self) -> String {
-        "venue-default-finality-v0.1.0".to_string()
+        let field = |name: &str| self.finality_rules.get(name).and_then(Value::as_str);
+        match (field("rule_id"), field("version")) {
+            (Some(rule_id), Some(version)) => format!("{}-v{}", rule_id, version),
+            _ => "venue-default-finality-v0.1.0".to_string(),
+        }
+    }
+
+    pub fn finality_rule(&self, venue: Venue) -> Option<FinalityRule> {
+        self.finality_rules
+            .get("venues")
+            .and_then(|v| v.get(venue.slug()))
+            .and_then(|rule| serde_json::from_value(rule.clone()).ok())
+    }
+
+    // The venue's rule when `claim_type` has to wait for it; venues without a rule are not gated.
+    pub fn finality_gate(&self, venue: Venue, claim_type: ClaimType) -> Option<FinalityRule> {
+        let gated = self
+            .finality_rules
+            .get("gated_claims")
+            .and_then(Value::as_array)
+            .is_some_and(|claims| claims.iter().any(|c| c.as_str() == Some(claim_type.name())));
+        gated.then(|| self.finality_rule(venue)).flatten()
     }
 
-    pub fn evaluate(&self, venue: Venue, claim_type: ClaimType, bundle: &EvidenceBundle) -> PolicyDecision {
+    // What a receipt proved under this policy records.
+    pub fn context(&self) -> PolicyContext {
+        PolicyContext {
+            policy_id: self.policy_id(),
+            finality_rule_id: self.finality_rule_id(),
+            source_precedence_version: self.source_precedence_version(),
+            policy_hash: Some(self.policy_hash()),
+        }
+    }
+
+    pub fn evaluate(
+        &self,
+        venue: Venue,
+        claim_type: ClaimType,
+        bundle: &EvidenceBundle,
+    ) -> PolicyDecision {
         if !bundle.conflicts.is_empty() {
             return PolicyDecision {
                 ok: false,
                 reason: Some(NonProvableReason::EVIDENCE_CONFLICT),
-                details: format!("Conflicting evidence entries detected: {}", bundle.conflicts.join(", ")),
+                details: format!(
+                    "Conflicting evidence entries detected: {}",
+                    bundle.conflicts.join(", ")
+                ),
+                remediation: None,
             };
         }
 
@@ -60,6 +298,7 @@ impl PolicyEngine {
                 ok: false,
                 reason: Some(NonProvableReason::EVIDENCE_MISSING),
                 details: "No evidence artifacts were collected.".to_string(),
+                remediation: None,
             };
         }
 
@@ -72,12 +311,23 @@ impl PolicyEngine {
             return PolicyDecision {
                 ok: false,
                 reason: Some(NonProvableReason::EVIDENCE_MISSING),
-                details: format!("Missing required evidence tags: {}", missing_tags.join(", ")),
+                details: format!(
+                    "Missing required evidence tags: {}",
+                    missing_tags.join(", ")
+                ),
+                remediation: Some(Remediation {
+                    missing_tags,
+                    ..Remediation::for_reason(NonProvableReason::EVIDENCE_MISSING)
+                }),
             };
         }
 
         let preferred = self.preferred_sources(venue, claim_type);
-        let observed: Vec<&str> = bundle.items.iter().map(|i| i.source_kind.as_str()).collect();
+        let observed: Vec<&str> = bundle
+            .items
+            .iter()
+            .map(|i| i.source_kind.as_str())
+            .collect();
         if !preferred.is_empty() && !preferred.iter().any(|p| observed.contains(&p.as_str())) {
             return PolicyDecision {
                 ok: false,
@@ -86,24 +336,202 @@ impl PolicyEngine {
                     "No acceptable preferred source kinds observed. Expected one of: {}",
                     preferred.join(", ")
                 ),
+                remediation: Some(Remediation {
+                    failed_source: Some(preferred.join(", ")),
+                    ..Remediation::for_reason(NonProvableReason::SOURCE_UNAVAILABLE)
+                }),
+            };
+        }
+
+        if let Some(rule) = self.finality_gate(venue, claim_type) {
+            if let Some(shortfall) = rule.shortfall(bundle) {
+                return PolicyDecision {
+                    ok: false,
+                    reason: Some(NonProvableReason::FINALITY_TIMEOUT),
+                    details: format!(
+                        "{} on {:?} is not final under {}: {}.",
+                        claim_type.name(),
+                        venue,
+                        self.finality_rule_id(),
+                        shortfall
+                    ),
+                    remediation: Some(Remediation::for_reason(NonProvableReason::FINALITY_TIMEOUT)),
+                };
+            }
+        }
+
+        if let Some(book) = &bundle.order_book {
+            let snapshot_hash = book.snapshot_hash();
+            let recorded = bundle.items.iter().any(|item| {
+                item.artifact_hash == snapshot_hash
+                    && item.tags.iter().any(|t| t == ORDER_BOOK_SNAPSHOT_TAG)
+            });
+            if !recorded {
+                return PolicyDecision {
+                    ok: false,
+                    reason: Some(NonProvableReason::EVIDENCE_CONFLICT),
+                    details: format!(
+                        "Order book snapshot {} does not match any recorded order book artifact.",
+                        snapshot_hash
+                    ),
+                    remediation: None,
+                };
+            }
+        }
+
+        if claim_type == ClaimType::BEST_EXECUTION {
+            let Some(price) = &bundle.price_observation else {
+                return PolicyDecision {
+                    ok: false,
+                    reason: Some(NonProvableReason::EVIDENCE_MISSING),
+                    details: "Best execution claim requires a captured reference price quote."
+                        .to_string(),
+                    remediation: Some(Remediation {
+                        missing_tags: vec!["reference_price_quote".to_string()],
+                        ..Remediation::for_reason(NonProvableReason::EVIDENCE_MISSING)
+                    }),
+                };
             };
+            if price.reference_price <= 0.0
+                || price.execution_price <= 0.0
+                || price.tolerance_bps < 0.0
+            {
+                return PolicyDecision {
+                    ok: false,
+                    reason: Some(NonProvableReason::SCHEMA_INVALID),
+                    details: "Execution price and reference price must be positive and tolerance non-negative."
+                        .to_string(),
+                    remediation: None,
+                };
+            }
+            if let Some(max_bps) = self.max_slippage_bps() {
+                if price.tolerance_bps > max_bps {
+                    return PolicyDecision {
+                        ok: false,
+                        reason: Some(NonProvableReason::POLICY_VIOLATION),
+                        details: format!(
+                            "Requested slippage tolerance {} bps exceeds policy maximum {} bps.",
+                            price.tolerance_bps, max_bps
+                        ),
+                        remediation: None,
+                    };
+                }
+            }
+            if price.reference_source == ORDER_BOOK_MID_SOURCE {
+                let Some(mid) = bundle.order_book.as_ref().and_then(|b| b.mid_price()) else {
+                    return PolicyDecision {
+                        ok: false,
+                        reason: Some(NonProvableReason::EVIDENCE_MISSING),
+                        details: "Order book mid reference requires a captured two-sided order book snapshot."
+                            .to_string(),
+                        remediation: Some(Remediation {
+                            missing_tags: vec![ORDER_BOOK_SNAPSHOT_TAG.to_string()],
+                            ..Remediation::for_reason(NonProvableReason::EVIDENCE_MISSING)
+                        }),
+                    };
+                };
+                if (mid - price.reference_price).abs() > 1e-8 {
+                    return PolicyDecision {
+                        ok: false,
+                        reason: Some(NonProvableReason::EVIDENCE_CONFLICT),
+                        details: format!(
+                            "Reference price {} does not match the captured order book mid {}.",
+                            price.reference_price, mid
+                        ),
+                        remediation: None,
+                    };
+                }
+            }
+            let slippage_bps = price.slippage_bps();
+            if slippage_bps > price.tolerance_bps {
+                return PolicyDecision {
+                    ok: false,
+                    reason: Some(NonProvableReason::POLICY_VIOLATION),
+                    details: format!(
+                        "Observed slippage {:.2} bps against {} exceeds tolerance {} bps.",
+                        slippage_bps, price.reference_source, price.tolerance_bps
+                    ),
+                    remediation: None,
+                };
+            }
+        }
+
+        if matches!(
+            claim_type,
+            ClaimType::TRADE_BUSTED | ClaimType::TRADE_AMENDED
+        ) {
+            let reported = bundle.correction.as_ref().map(|c| c.claim_type());
+            if reported != Some(claim_type) {
+                return PolicyDecision {
+                    ok: false,
+                    reason: Some(NonProvableReason::EVIDENCE_CONFLICT),
+                    details: format!(
+                        "Venue correction feed reports {:?} for this execution, which does not support a {:?} claim.",
+                        reported, claim_type
+                    ),
+                    remediation: None,
+                };
+            }
         }
 
         PolicyDecision {
             ok: true,
             reason: None,
             details: String::new(),
+            remediation: None,
         }
     }
 
+    pub fn evidence_tag_vocabulary(&self) -> HashSet<String> {
+        string_set(self.claim_taxonomy.get("evidence_tag_vocabulary"))
+    }
+
+    pub fn known_source_kinds(&self) -> HashSet<String> {
+        string_set(self.source_precedence.get("source_kind_order"))
+    }
+
+    pub fn oracle_tolerance(&self) -> OracleTolerance {
+        let defaults = OracleTolerance::default();
+        let config = self.claim_taxonomy.get("oracle_cross_check");
+        let field = |name: &str| config.and_then(|v| v.get(name)).and_then(|v| v.as_f64());
+        OracleTolerance {
+            max_deviation_bps: field("max_deviation_bps").unwrap_or(defaults.max_deviation_bps),
+            max_staleness: field("max_staleness_secs")
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .unwrap_or(defaults.max_staleness),
+        }
+    }
+
+    fn max_slippage_bps(&self) -> Option<f64> {
+        self.claim_taxonomy
+            .get("claim_types")
+            .and_then(|v| v.get("BEST_EXECUTION"))
+            .and_then(|v| v.get("max_slippage_bps"))
+            .and_then(|v| v.as_f64())
+    }
+
+    pub fn payload_schema(&self, claim_type: ClaimType) -> Option<Value> {
+        self.claim_taxonomy
+            .get("claim_types")
+            .and_then(|v| v.get(claim_type.name()))
+            .and_then(|v| v.get("payload_schema"))
+            .cloned()
+    }
+
+    pub fn payload_schemas(&self) -> Value {
+        let schemas = ClaimType::all()
+            .filter_map(|claim_type| {
+                self.payload_schema(claim_type)
+                    .map(|schema| (claim_type.name().to_string(), schema))
+            })
+            .collect();
+        Value::Object(schemas)
+    }
+
     fn required_tags_for_claim(&self, claim_type: ClaimType) -> Vec<String> {
-        let key = match claim_type {
-            ClaimType::ORDER_PLACED => "ORDER_PLACED",
-            ClaimType::TRADE_EXECUTED => "TRADE_EXECUTED",
-        };
         self.claim_taxonomy
             .get("claim_types")
-            .and_then(|v| v.get(key))
+            .and_then(|v| v.get(claim_type.name()))
             .and_then(|v| v.get("required_evidence_tags_all"))
             .and_then(|v| v.as_array())
             .map(|arr| {
@@ -114,17 +542,55 @@ impl PolicyEngine {
             .unwrap_or_default()
     }
 
-    fn preferred_sources(&self, venue: Venue, claim_type: ClaimType) -> Vec<String> {
-        let venue_key = match venue {
-            Venue::Hyperliquid => "hyperliquid",
-            Venue::Base => "base",
-            Venue::Solana => "solana",
-            Venue::Polymarket => "polymarket",
-        };
-        let list_key = match claim_type {
-            ClaimType::ORDER_PLACED => "order_placed_sources_preferred",
-            ClaimType::TRADE_EXECUTED => "trade_executed_sources_preferred",
+    // Claims listed in a venue's `attestation_only_claims` are issued without a zk proof.
+    pub fn is_attestation_only(&self, venue: Venue, claim_type: ClaimType) -> bool {
+        self.source_precedence
+            .get("venues")
+            .and_then(|v| v.get(venue.slug()))
+            .and_then(|v| v.get("attestation_only_claims"))
+            .and_then(|v| v.as_array())
+            .is_some_and(|claims| claims.iter().any(|c| c.as_str() == Some(claim_type.name())))
+    }
+
+    pub fn with_attestation_only(mut self, venue: Venue, claim_type: ClaimType) -> Self {
+        if !self.is_attestation_only(venue, claim_type) {
+            let claims = &mut Arc::make_mut(&mut self.source_precedence)["venues"][venue.slug()]
+                ["attestation_only_claims"];
+            if !claims.is_array() {
+                *claims = Value::Array(vec![]);
+            }
+            if let Some(claims) = claims.as_array_mut() {
+                claims.push(Value::String(claim_type.name().to_string()));
+            }
+        }
+        self
+    }
+
+    // A venue's `max_timestamp_skew_ms` overrides the default policy's.
+    pub fn timestamp_tolerance(&self, venue: Venue) -> TimestampTolerance {
+        let skew = |policy: Option<&Value>| {
+            policy
+                .and_then(|v| v.get("max_timestamp_skew_ms"))
+                .and_then(|v| v.as_f64())
         };
+        let venue_policy = self
+            .source_precedence
+            .get("venues")
+            .and_then(|v| v.get(venue.slug()));
+        let default_policy = self.source_precedence.get("default_policy");
+        TimestampTolerance {
+            max_skew_ms: skew(venue_policy)
+                .or_else(|| skew(default_policy))
+                .unwrap_or(TimestampTolerance::default().max_skew_ms),
+        }
+    }
+
+    fn preferred_sources(&self, venue: Venue, claim_type: ClaimType) -> Vec<String> {
+        let venue_key = venue.slug();
+        let list_key = format!(
+            "{}_sources_preferred",
+            claim_type.name().to_ascii_lowercase()
+        );
         self.source_precedence
             .get("venues")
             .and_then(|v| v.get(venue_key))
@@ -139,9 +605,260 @@ impl PolicyEngine {
     }
 }
 
-fn read_json(path: &Path) -> Result<Value> {
-    let text = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
-    let parsed: Value =
-        serde_json::from_str(&text).with_context(|| format!("failed to parse json {}", path.display()))?;
+// A policy that follows its documents on disk. Engines subscribed to it (`ReceiptEngine::with_policy_watch`)
+// read the policy in force for each request, and a receipt keeps the revision it started under.
+#[derive(Debug, Clone)]
+pub struct PolicyWatch {
+    state: Arc<Mutex<WatchState>>,
+    updates: watch::Receiver<PolicyEngine>,
+}
+
+#[derive(Debug)]
+struct WatchState {
+    claim_taxonomy: PathBuf,
+    source_precedence: PathBuf,
+    contents: (String, String),
+    last_error: Option<String>,
+    sender: watch::Sender<PolicyEngine>,
+}
+
+impl PolicyWatch {
+    fn start(
+        claim_taxonomy: PathBuf,
+        source_precedence: PathBuf,
+        interval: Duration,
+    ) -> Result<Self> {
+        let contents = read_documents(&claim_taxonomy, &source_precedence)?;
+        let policy = parse_documents(&contents)?;
+        let (sender, updates) = watch::channel(policy);
+        let watcher = Self {
+            state: Arc::new(Mutex::new(WatchState {
+                claim_taxonomy,
+                source_precedence,
+                contents,
+                last_error: None,
+                sender,
+            })),
+            updates,
+        };
+        // Stops once every subscriber, this handle included, is gone.
+        let state = Arc::clone(&watcher.state);
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                if state
+                    .lock()
+                    .expect("policy watch lock poisoned")
+                    .sender
+                    .receiver_count()
+                    == 0
+                {
+                    return;
+                }
+                reload_documents(&state);
+            }
+        });
+        Ok(watcher)
+    }
+
+    // The policy in force now.
+    pub fn current(&self) -> PolicyEngine {
+        self.updates.borrow().clone()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<PolicyEngine> {
+        self.updates.clone()
+    }
+
+    // Applies finality rules to the policy in force and to every reload after it.
+    pub fn with_finality_rules(self, finality_rules: Value) -> Result<Self> {
+        {
+            let state = self.state.lock().expect("policy watch lock poisoned");
+            let policy = state
+                .sender
+                .borrow()
+                .clone()
+                .with_finality_rules(finality_rules)?;
+            state.sender.send_replace(policy);
+        }
+        Ok(self)
+    }
+
+    // Adds a tenant overlay to the policy in force and to every reload after it. A reload whose
+    // documents the overlay no longer fits is refused like one that does not parse.
+    pub fn with_tenant_overlay(self, tenant: impl Into<String>, overlay: Value) -> Result<Self> {
+        {
+            let state = self.state.lock().expect("policy watch lock poisoned");
+            let policy = state
+                .sender
+                .borrow()
+                .clone()
+                .with_tenant_overlay(tenant, overlay)?;
+            state.sender.send_replace(policy);
+        }
+        Ok(self)
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.state
+            .lock()
+            .expect("policy watch lock poisoned")
+            .last_error
+            .clone()
+    }
+
+    // Checks the documents now rather than at the next poll. Returns whether a new revision was
+    // swapped in.
+    pub fn reload(&self) -> bool {
+        reload_documents(&self.state)
+    }
+}
+
+fn reload_documents(state: &Mutex<WatchState>) -> bool {
+    let mut state = state.lock().expect("policy watch lock poisoned");
+    let loaded = read_documents(&state.claim_taxonomy, &state.source_precedence);
+    let contents = match loaded {
+        Ok(contents) if contents == state.contents => return false,
+        Ok(contents) => contents,
+        Err(err) => {
+            state.last_error = Some(format!("{:#}", err));
+            return false;
+        }
+    };
+    let current = state.sender.borrow().clone();
+    let parsed = parse_documents(&contents).and_then(|mut policy| {
+        policy.revision = current.revision + 1;
+        policy.finality_rules = current.finality_rules;
+        policy.tenant_overlays = current.tenant_overlays;
+        policy.check_tenant_overlays()?;
+        Ok(policy)
+    });
+    match parsed {
+        Ok(policy) => {
+            state.contents = contents;
+            state.last_error = None;
+            state.sender.send_replace(policy);
+            true
+        }
+        Err(err) => {
+            state.last_error = Some(format!("{:#}", err));
+            false
+        }
+    }
+}
+
+fn read_documents(claim_taxonomy: &Path, source_precedence: &Path) -> Result<(String, String)> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+    };
+    Ok((read(claim_taxonomy)?, read(source_precedence)?))
+}
+
+fn parse_documents((claim_taxonomy, source_precedence): &(String, String)) -> Result<PolicyEngine> {
+    PolicyEngine::from_values(
+        serde_json::from_str(claim_taxonomy).context("failed to parse claim taxonomy")?,
+        serde_json::from_str(source_precedence).context("failed to parse source precedence")?,
+    )
+}
+
+fn check_documents(claim_taxonomy: &Value, source_precedence: &Value) -> Result<()> {
+    if !claim_taxonomy
+        .get("claim_types")
+        .is_some_and(Value::is_object)
+    {
+        bail!("claim taxonomy has no claim_types object");
+    }
+    if !source_precedence
+        .get("venues")
+        .is_some_and(Value::is_object)
+    {
+        bail!("source precedence has no venues object");
+    }
+    Ok(())
+}
+
+fn check_finality_rules(finality_rules: &Value) -> Result<()> {
+    let Some(venues) = finality_rules.get("venues").and_then(Value::as_object) else {
+        bail!("finality rules have no venues object");
+    };
+    for (venue, rule) in venues {
+        serde_json::from_value::<FinalityRule>(rule.clone())
+            .with_context(|| format!("invalid finality rule for venue {}", venue))?;
+    }
+    for claim in finality_rules
+        .get("gated_claims")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if !claim
+            .as_str()
+            .is_some_and(|name| ClaimType::all().any(|c| c.name() == name))
+        {
+            bail!("finality rules gate unknown claim type {}", claim);
+        }
+    }
+    Ok(())
+}
+
+// RFC 7396 JSON merge patch.
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+fn string_set(value: Option<&Value>) -> HashSet<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// One of the default policy documents, by file name.
+pub(crate) fn default_spec_document(file: &str) -> Result<Value> {
+    #[cfg(feature = "embedded-spec")]
+    {
+        let text = match file {
+            CLAIM_TAXONOMY_FILE => EMBEDDED_CLAIM_TAXONOMY,
+            SOURCE_PRECEDENCE_FILE => EMBEDDED_SOURCE_PRECEDENCE,
+            FINALITY_RULES_FILE => EMBEDDED_FINALITY_RULES,
+            other => bail!("{} is not an embedded spec document", other),
+        };
+        serde_json::from_str(text)
+            .with_context(|| format!("failed to parse embedded json {}", file))
+    }
+    #[cfg(not(feature = "embedded-spec"))]
+    read_json(
+        &Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("spec")
+            .join(file),
+    )
+}
+
+pub(crate) fn read_json(path: &Path) -> Result<Value> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let parsed: Value = serde_json::from_str(&text)
+        .with_context(|| format!("failed to parse json {}", path.display()))?;
     Ok(parsed)
 }
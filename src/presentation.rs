@@ -0,0 +1,221 @@
+use crate::engine::recompute_receipt_hash;
+use crate::models::{now_iso, SignerIdentity, ZKReceipt};
+use crate::signing::{verify_ed25519, KeyRing, ReceiptSigner, ED25519_ALGORITHM};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const PRESENTATION_SIGNING_DOMAIN: &str = "zkputer-presentation-v1";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresentationChallenge {
+    pub nonce: String,
+    pub audience: String,
+    pub issued_at: String,
+    pub expires_at: String,
+}
+
+// A receipt handed to a counterparty together with a fresh Ed25519 signature over its hash and
+// the verifier's challenge. Copying someone else's receipt is not enough to present it: the holder
+// has to sign with the private key of a key in the ring that issued the receipt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptPresentation {
+    pub receipt: ZKReceipt,
+    pub challenge: PresentationChallenge,
+    pub holder: SignerIdentity,
+    pub presented_at: String,
+    pub signature: String,
+}
+
+// `signer` holds the private key of the holder's key in `keyring` that is valid now.
+pub fn present_receipt(
+    keyring: &KeyRing,
+    signer: &dyn ReceiptSigner,
+    receipt: &ZKReceipt,
+    challenge: &PresentationChallenge,
+) -> Result<ReceiptPresentation> {
+    if !keyring.verify_receipt_signature(receipt) {
+        bail!(
+            "receipt {} was not issued by this key ring",
+            receipt.receipt_id
+        );
+    }
+    let presented_at = now_iso();
+    let holder = signer.identity();
+    if holder.algorithm != ED25519_ALGORITHM {
+        bail!(
+            "presentations are signed with ed25519, not {}",
+            holder.algorithm
+        );
+    }
+    if !keyring
+        .keys()
+        .iter()
+        .any(|k| k.identity == *holder && k.is_valid_at(&presented_at))
+    {
+        bail!(
+            "holder key {} is not valid at {}",
+            holder.key_id,
+            presented_at
+        );
+    }
+    Ok(ReceiptPresentation {
+        receipt: receipt.clone(),
+        challenge: challenge.clone(),
+        holder: holder.clone(),
+        signature: signer.sign(&presentation_message(
+            &receipt.integrity.receipt_hash,
+            challenge,
+            &presented_at,
+        )),
+        presented_at,
+    })
+}
+
+fn presentation_message(
+    receipt_hash: &str,
+    challenge: &PresentationChallenge,
+    presented_at: &str,
+) -> Vec<u8> {
+    format!(
+        "{}:{}:{}:{}:{}:{}",
+        PRESENTATION_SIGNING_DOMAIN,
+        receipt_hash,
+        challenge.nonce,
+        challenge.audience,
+        challenge.expires_at,
+        presented_at
+    )
+    .into_bytes()
+}
+
+// Outstanding challenges are single-use: a presentation consumes its nonce whether or not it
+// verifies, so a captured presentation cannot be replayed against the same verifier.
+pub struct PresentationVerifier {
+    audience: String,
+    ttl: Duration,
+    outstanding: HashMap<String, PresentationChallenge>,
+}
+
+impl PresentationVerifier {
+    pub fn new(audience: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            audience: audience.into(),
+            ttl,
+            outstanding: HashMap::new(),
+        }
+    }
+
+    pub fn issue_challenge(&mut self) -> PresentationChallenge {
+        let issued_at = Utc::now();
+        let ttl = chrono::Duration::from_std(self.ttl).unwrap_or(chrono::Duration::MAX);
+        let challenge = PresentationChallenge {
+            nonce: Uuid::new_v4().simple().to_string(),
+            audience: self.audience.clone(),
+            issued_at: fmt_ts(issued_at),
+            expires_at: fmt_ts(
+                issued_at
+                    .checked_add_signed(ttl)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            ),
+        };
+        self.outstanding
+            .insert(challenge.nonce.clone(), challenge.clone());
+        challenge
+    }
+
+    // `holder_keys` is the presenting party's published key manifest; both the receipt and the
+    // presentation must verify against it.
+    pub fn verify(
+        &mut self,
+        presentation: &ReceiptPresentation,
+        holder_keys: &KeyRing,
+    ) -> Result<ZKReceipt> {
+        let challenge = &presentation.challenge;
+        let issued = self.outstanding.remove(&challenge.nonce).ok_or_else(|| {
+            anyhow!(
+                "challenge nonce {} is unknown or was already used",
+                challenge.nonce
+            )
+        })?;
+        if issued != *challenge {
+            bail!(
+                "presented challenge does not match the one issued for nonce {}",
+                challenge.nonce
+            );
+        }
+        if challenge.audience != self.audience {
+            bail!(
+                "challenge audience {} does not match verifier {}",
+                challenge.audience,
+                self.audience
+            );
+        }
+        let expires_at = parse_ts(&challenge.expires_at)?;
+        let presented_at = parse_ts(&presentation.presented_at)?;
+        if presented_at < parse_ts(&challenge.issued_at)?
+            || Utc::now() > expires_at
+            || presented_at > expires_at
+        {
+            bail!(
+                "challenge {} expired at {}",
+                challenge.nonce,
+                challenge.expires_at
+            );
+        }
+
+        let receipt = &presentation.receipt;
+        if recompute_receipt_hash(receipt) != receipt.integrity.receipt_hash {
+            bail!(
+                "receipt {} hash does not match its contents",
+                receipt.receipt_id
+            );
+        }
+        if !holder_keys.verify_receipt_signature(receipt) {
+            bail!(
+                "receipt {} was not issued by the presenting key ring",
+                receipt.receipt_id
+            );
+        }
+        let holder = &presentation.holder;
+        if holder.algorithm != ED25519_ALGORITHM {
+            bail!(
+                "presentation holder key {} is not an ed25519 key",
+                holder.key_id
+            );
+        }
+        let key_valid = holder_keys
+            .keys()
+            .iter()
+            .any(|k| k.identity == *holder && k.is_valid_at(&presentation.presented_at));
+        if !key_valid {
+            bail!(
+                "holder key {} is not valid at {}",
+                holder.key_id,
+                presentation.presented_at
+            );
+        }
+        let message = presentation_message(
+            &receipt.integrity.receipt_hash,
+            challenge,
+            &presentation.presented_at,
+        );
+        if !verify_ed25519(&holder.public_key, &message, &presentation.signature) {
+            bail!("presentation signature mismatch");
+        }
+        Ok(receipt.clone())
+    }
+}
+
+fn fmt_ts(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+fn parse_ts(raw: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| anyhow!("invalid timestamp {}", raw))
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofQueueStats {
+    pub max_concurrent: usize,
+    pub running: usize,
+    pub queued: usize,
+}
+
+// Caps how many proofs run at once across all receipt tasks. Tasks past the cap wait for a slot in
+// arrival order; adapter calls and finality waits are not counted against it.
+#[derive(Debug, Clone)]
+pub struct ProofQueue {
+    slots: Arc<Semaphore>,
+    max_concurrent: usize,
+    running: Arc<AtomicUsize>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl ProofQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            slots: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            running: Arc::new(AtomicUsize::new(0)),
+            queued: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    // ZKPUTER_MAX_CONCURRENT_PROOFS, or one proof per available core.
+    pub fn from_env() -> Self {
+        let max_concurrent = std::env::var("ZKPUTER_MAX_CONCURRENT_PROOFS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(default_max_concurrent);
+        Self::new(max_concurrent)
+    }
+
+    pub async fn run<F: Future>(&self, proof: F) -> F::Output {
+        let waiting = Counted::enter(&self.queued);
+        // The semaphore is never closed, so acquiring only fails if that invariant breaks.
+        let _slot = self
+            .slots
+            .acquire()
+            .await
+            .expect("proof queue semaphore closed");
+        drop(waiting);
+        let _running = Counted::enter(&self.running);
+        proof.await
+    }
+
+    pub fn stats(&self) -> ProofQueueStats {
+        ProofQueueStats {
+            max_concurrent: self.max_concurrent,
+            running: self.running.load(Ordering::SeqCst),
+            queued: self.queued.load(Ordering::SeqCst),
+        }
+    }
+}
+
+impl Default for ProofQueue {
+    fn default() -> Self {
+        Self::new(default_max_concurrent())
+    }
+}
+
+fn default_max_concurrent() -> usize {
+    std::thread::available_parallelism().map_or(4, usize::from)
+}
+
+// Decrements on drop, so a receipt task cancelled mid-wait or mid-proof leaves the counts right.
+struct Counted<'a>(&'a AtomicUsize);
+
+impl<'a> Counted<'a> {
+    fn enter(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
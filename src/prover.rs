@@ -1,13 +1,60 @@
-use crate::models::{hash_json, ProofBackend, ProofMetadata, VerificationMode};
-use anyhow::{anyhow, Result};
+use crate::circuits::CircuitRegistry;
+use crate::hashing::HashAlgorithm;
+use crate::models::{hash_json, ProofBackend, ProofMetadata, SignerIdentity, VerificationMode};
+use anyhow::{anyhow, bail, Result};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
+use std::time::Duration;
+
+pub const ATTESTATION_CIRCUIT_ID: &str = "attestation-only";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProverUsage {
+    pub cycles: u64,
+    pub fee_usd: f64,
+}
+
+// Until real zkVM integrations report execution stats, the MVP provers charge a fixed setup cost
+// plus a per-byte cost over the serialized public inputs.
+fn synthetic_usage(public_inputs: &Value, usd_per_million_cycles: f64) -> ProverUsage {
+    let cycles = 1_000_000 + 1_000 * public_inputs.to_string().len() as u64;
+    ProverUsage {
+        cycles,
+        fee_usd: cycles as f64 / 1_000_000.0 * usd_per_million_cycles,
+    }
+}
+
+// Preimages of the claim hash and evidence root in the public inputs. Backends that only hash the
+// public inputs ignore it; a zkVM backend proves the commitments open to these documents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProofWitness {
+    pub hash_algorithm: HashAlgorithm,
+    pub claim_preimage: Option<Value>,
+    pub evidence_preimage: Option<Value>,
+}
 
 #[async_trait]
 pub trait ProverBackend: Send + Sync {
     fn backend_name(&self) -> ProofBackend;
     async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata>;
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        Ok((self.prove(public_inputs).await?, ProverUsage::default()))
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        _witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        self.prove_metered(public_inputs).await
+    }
+
+    async fn available(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -20,21 +67,18 @@ impl ProverBackend for Sp1MvpProver {
     }
 
     async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
-        let public_inputs_hash = hash_json(public_inputs);
-        Ok(ProofMetadata {
-            backend: ProofBackend::SP1,
-            circuit_id: "trade-receipt-sp1".to_string(),
-            circuit_version: "v0.1.0".to_string(),
-            verifier_key_id: "sp1-vk-001".to_string(),
-            verifier_key_hash: hash_json(&serde_json::json!({
-                "backend": "SP1",
-                "verifier_key": "001"
-            })),
-            public_inputs_hash: public_inputs_hash.clone(),
-            verification_mode: VerificationMode::OFFCHAIN,
-            proof_artifact_ref: Some(format!("boundless://sp1/{}", public_inputs_hash)),
-            anchored_root_ref: None,
-        })
+        let circuit = CircuitRegistry::builtin().select_for(ProofBackend::SP1, public_inputs)?;
+        Ok(circuit.proof_metadata(
+            public_inputs,
+            format!("boundless://sp1/{}", hash_json(public_inputs)),
+        ))
+    }
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        Ok((
+            self.prove(public_inputs).await?,
+            synthetic_usage(public_inputs, 0.05),
+        ))
     }
 }
 
@@ -48,21 +92,43 @@ impl ProverBackend for PicoMvpProver {
     }
 
     async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
-        let public_inputs_hash = hash_json(public_inputs);
-        Ok(ProofMetadata {
-            backend: ProofBackend::PICO,
-            circuit_id: "trade-receipt-pico".to_string(),
-            circuit_version: "v0.1.0".to_string(),
-            verifier_key_id: "pico-vk-001".to_string(),
-            verifier_key_hash: hash_json(&serde_json::json!({
-                "backend": "PICO",
-                "verifier_key": "001"
-            })),
-            public_inputs_hash: public_inputs_hash.clone(),
-            verification_mode: VerificationMode::OFFCHAIN,
-            proof_artifact_ref: Some(format!("pico://receipt/{}", public_inputs_hash)),
-            anchored_root_ref: None,
-        })
+        let circuit = CircuitRegistry::builtin().select_for(ProofBackend::PICO, public_inputs)?;
+        Ok(circuit.proof_metadata(
+            public_inputs,
+            format!("pico://receipt/{}", hash_json(public_inputs)),
+        ))
+    }
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        Ok((
+            self.prove(public_inputs).await?,
+            synthetic_usage(public_inputs, 0.04),
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Risc0MvpProver;
+
+#[async_trait]
+impl ProverBackend for Risc0MvpProver {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::RISC0
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        let circuit = CircuitRegistry::builtin().select_for(ProofBackend::RISC0, public_inputs)?;
+        Ok(circuit.proof_metadata(
+            public_inputs,
+            format!("risc0://receipt/{}", hash_json(public_inputs)),
+        ))
+    }
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        Ok((
+            self.prove(public_inputs).await?,
+            synthetic_usage(public_inputs, 0.06),
+        ))
     }
 }
 
@@ -86,16 +152,201 @@ impl ProverBackend for FallbackProver {
     async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
         match self.primary.prove(public_inputs).await {
             Ok(proof) => Ok(proof),
-            Err(primary_err) => self.secondary.prove(public_inputs).await.map_err(|secondary_err| {
-                anyhow!(
-                    "primary backend {:?} failed: {}; fallback backend {:?} failed: {}",
-                    self.primary.backend_name(),
-                    primary_err,
-                    self.secondary.backend_name(),
-                    secondary_err
-                )
-            }),
+            Err(primary_err) => {
+                self.secondary
+                    .prove(public_inputs)
+                    .await
+                    .map_err(|secondary_err| {
+                        anyhow!(
+                            "primary backend {:?} failed: {}; fallback backend {:?} failed: {}",
+                            self.primary.backend_name(),
+                            primary_err,
+                            self.secondary.backend_name(),
+                            secondary_err
+                        )
+                    })
+            }
+        }
+    }
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        match self.primary.prove_metered(public_inputs).await {
+            Ok(metered) => Ok(metered),
+            Err(primary_err) => {
+                self.secondary
+                    .prove_metered(public_inputs)
+                    .await
+                    .map_err(|secondary_err| {
+                        anyhow!(
+                            "primary backend {:?} failed: {}; fallback backend {:?} failed: {}",
+                            self.primary.backend_name(),
+                            primary_err,
+                            self.secondary.backend_name(),
+                            secondary_err
+                        )
+                    })
+            }
+        }
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        match self
+            .primary
+            .prove_with_witness(public_inputs, witness)
+            .await
+        {
+            Ok(metered) => Ok(metered),
+            Err(primary_err) => self
+                .secondary
+                .prove_with_witness(public_inputs, witness)
+                .await
+                .map_err(|secondary_err| {
+                    anyhow!(
+                        "primary backend {:?} failed: {}; fallback backend {:?} failed: {}",
+                        self.primary.backend_name(),
+                        primary_err,
+                        self.secondary.backend_name(),
+                        secondary_err
+                    )
+                }),
+        }
+    }
+
+    async fn available(&self) -> bool {
+        self.primary.available().await || self.secondary.available().await
+    }
+}
+
+// Tries each backend in order until one produces a proof, giving each attempt at most
+// `attempt_timeout` when set. The metadata records the producing backend, and `fallback_from`
+// the backends that failed before it.
+pub struct ProverChain {
+    provers: Vec<Arc<dyn ProverBackend>>,
+    attempt_timeout: Option<Duration>,
+}
+
+impl ProverChain {
+    pub fn new(provers: Vec<Arc<dyn ProverBackend>>) -> Result<Self> {
+        if provers.is_empty() {
+            bail!("prover chain requires at least one backend");
+        }
+        Ok(Self {
+            provers,
+            attempt_timeout: None,
+        })
+    }
+
+    pub fn with_attempt_timeout(mut self, timeout: Duration) -> Self {
+        self.attempt_timeout = Some(timeout);
+        self
+    }
+
+    pub fn backends(&self) -> Vec<ProofBackend> {
+        self.provers.iter().map(|p| p.backend_name()).collect()
+    }
+}
+
+#[async_trait]
+impl ProverBackend for ProverChain {
+    fn backend_name(&self) -> ProofBackend {
+        self.provers[0].backend_name()
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        Ok(self.prove_metered(public_inputs).await?.0)
+    }
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        self.prove_with_witness(public_inputs, &ProofWitness::default())
+            .await
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        let mut failures = Vec::new();
+        for prover in &self.provers {
+            let attempt = prover.prove_with_witness(public_inputs, witness);
+            let outcome = match self.attempt_timeout {
+                Some(limit) => tokio::time::timeout(limit, attempt)
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow!("timed out after {} ms", limit.as_millis()))),
+                None => attempt.await,
+            };
+            match outcome {
+                Ok((mut proof, usage)) => {
+                    proof.fallback_from = failures.iter().map(|(backend, _)| *backend).collect();
+                    return Ok((proof, usage));
+                }
+                Err(err) => failures.push((prover.backend_name(), err.to_string())),
+            }
+        }
+        bail!(
+            "every prover in the chain failed: {}",
+            failures
+                .iter()
+                .map(|(backend, err)| format!("{:?}: {}", backend, err))
+                .collect::<Vec<_>>()
+                .join("; ")
+        )
+    }
+
+    async fn available(&self) -> bool {
+        for prover in &self.provers {
+            if prover.available().await {
+                return true;
+            }
         }
+        false
+    }
+}
+
+// Holds every proof for `delay` before handing it to the wrapped backend, to simulate proving
+// time in SLO runs.
+pub struct DelayedProver {
+    inner: Arc<dyn ProverBackend>,
+    delay: Duration,
+}
+
+impl DelayedProver {
+    pub fn new(inner: Arc<dyn ProverBackend>, delay: Duration) -> Self {
+        Self { inner, delay }
+    }
+}
+
+#[async_trait]
+impl ProverBackend for DelayedProver {
+    fn backend_name(&self) -> ProofBackend {
+        self.inner.backend_name()
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.prove(public_inputs).await
+    }
+
+    async fn prove_metered(&self, public_inputs: &Value) -> Result<(ProofMetadata, ProverUsage)> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.prove_metered(public_inputs).await
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.prove_with_witness(public_inputs, witness).await
+    }
+
+    async fn available(&self) -> bool {
+        self.inner.available().await
     }
 }
 
@@ -103,6 +354,7 @@ impl ProverBackend for FallbackProver {
 pub enum ProverStrategy {
     Sp1Only,
     PicoOnly,
+    Risc0Only,
     Sp1WithPicoFallback,
 }
 
@@ -110,6 +362,7 @@ impl ProverStrategy {
     pub fn from_env(raw: Option<&str>) -> Self {
         match raw.unwrap_or("sp1").trim().to_ascii_lowercase().as_str() {
             "pico" => Self::PicoOnly,
+            "risc0" => Self::Risc0Only,
             "sp1_with_pico_fallback" | "sp1+pico" => Self::Sp1WithPicoFallback,
             _ => Self::Sp1Only,
         }
@@ -120,9 +373,32 @@ pub fn build_mvp_prover(strategy: ProverStrategy) -> Arc<dyn ProverBackend> {
     match strategy {
         ProverStrategy::Sp1Only => Arc::new(Sp1MvpProver),
         ProverStrategy::PicoOnly => Arc::new(PicoMvpProver),
-        ProverStrategy::Sp1WithPicoFallback => {
-            Arc::new(FallbackProver::new(Arc::new(Sp1MvpProver), Arc::new(PicoMvpProver)))
-        }
+        ProverStrategy::Risc0Only => Arc::new(Risc0MvpProver),
+        ProverStrategy::Sp1WithPicoFallback => Arc::new(FallbackProver::new(
+            Arc::new(Sp1MvpProver),
+            Arc::new(PicoMvpProver),
+        )),
+    }
+}
+
+// Stands in for a proof on claims the policy marks attestation-only. The public inputs are bound
+// into the receipt hash as usual, so the issuer's receipt signature attests to the commitments.
+pub fn attestation_metadata(public_inputs: &Value, signer: &SignerIdentity) -> ProofMetadata {
+    ProofMetadata {
+        backend: ProofBackend::NONE,
+        circuit_id: ATTESTATION_CIRCUIT_ID.to_string(),
+        circuit_version: "v0.1.0".to_string(),
+        verifier_key_id: signer.key_id.clone(),
+        verifier_key_hash: hash_json(&serde_json::json!({
+            "algorithm": signer.algorithm,
+            "public_key": signer.public_key
+        })),
+        public_inputs_hash: hash_json(public_inputs),
+        verification_mode: VerificationMode::ATTESTATION_ONLY,
+        proof_artifact_ref: None,
+        anchored_root_ref: None,
+        anchor_inclusion: None,
+        fallback_from: vec![],
     }
 }
 
@@ -137,5 +413,7 @@ pub fn no_proof_metadata() -> ProofMetadata {
         verification_mode: VerificationMode::OFFCHAIN,
         proof_artifact_ref: None,
         anchored_root_ref: None,
+        anchor_inclusion: None,
+        fallback_from: vec![],
     }
 }
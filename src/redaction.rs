@@ -0,0 +1,289 @@
+use crate::models::{hash_str, CollectionRecord};
+use anyhow::{bail, Result};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde_json::Value;
+use std::fmt;
+
+const ACCOUNT_REF_FIELDS: &[&str] = &["account_ref"];
+const ORDER_REF_FIELDS: &[&str] = &["order_ref", "execution_ref", "venue_order_ref"];
+// Shorter refs are only masked where they appear as field values; substring replacement would
+// mangle unrelated text.
+const MIN_EMBEDDED_REF_LEN: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StructuredRedaction {
+    #[default]
+    Intact,
+    Hashed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RedactionConfig {
+    pub account_refs: bool,
+    pub order_refs: bool,
+    pub structured: StructuredRedaction,
+}
+
+impl RedactionConfig {
+    pub fn from_env(fields: Option<&str>, structured: Option<&str>) -> Self {
+        let mut config = Self::default();
+        for field in fields
+            .unwrap_or("")
+            .split(',')
+            .map(|f| f.trim().to_ascii_lowercase())
+        {
+            match field.as_str() {
+                "account_refs" | "accounts" => config.account_refs = true,
+                "order_refs" | "orders" => config.order_refs = true,
+                "all" => {
+                    config.account_refs = true;
+                    config.order_refs = true;
+                }
+                _ => {}
+            }
+        }
+        if structured.is_some_and(|s| s.trim().eq_ignore_ascii_case("hashed")) {
+            config.structured = StructuredRedaction::Hashed;
+        }
+        config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.account_refs || self.order_refs
+    }
+
+    pub fn render_text(&self, value: &Value) -> String {
+        let text = serde_json::to_string_pretty(value).unwrap_or_default();
+        if !self.is_enabled() {
+            return text;
+        }
+        self.replace_refs(value, text, mask_ref)
+    }
+
+    pub fn structured(&self, value: &Value) -> Value {
+        if !self.is_enabled() || self.structured == StructuredRedaction::Intact {
+            return value.clone();
+        }
+        let text = serde_json::to_string(value).unwrap_or_default();
+        let hashed = self.replace_refs(value, text, hash_str);
+        serde_json::from_str(&hashed).unwrap_or_else(|_| value.clone())
+    }
+
+    fn fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.account_refs {
+            fields.extend_from_slice(ACCOUNT_REF_FIELDS);
+        }
+        if self.order_refs {
+            fields.extend_from_slice(ORDER_REF_FIELDS);
+        }
+        fields
+    }
+
+    fn replace_refs(
+        &self,
+        value: &Value,
+        text: String,
+        replace: impl Fn(&str) -> String,
+    ) -> String {
+        let mut refs = Vec::new();
+        collect_refs(value, &self.fields(), &mut refs);
+        // Longest first so a ref that contains another is replaced whole.
+        refs.sort_by_key(|r| std::cmp::Reverse(r.len()));
+        refs.dedup();
+        let mut text = text;
+        for reference in refs {
+            let quoted = serde_json::to_string(&reference).unwrap_or_default();
+            let replacement = serde_json::to_string(&replace(&reference)).unwrap_or_default();
+            text = text.replace(&quoted, &replacement);
+            if reference.len() >= MIN_EMBEDDED_REF_LEN {
+                let embedded = replacement.trim_matches('"').to_string();
+                text = text.replace(quoted.trim_matches('"'), &embedded);
+            }
+        }
+        text
+    }
+}
+
+fn collect_refs(value: &Value, fields: &[&str], refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                match child {
+                    Value::String(s) if fields.contains(&key.as_str()) && !s.is_empty() => {
+                        refs.push(s.clone())
+                    }
+                    _ => collect_refs(child, fields, refs),
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_refs(item, fields, refs)),
+        _ => {}
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldTreatment {
+    #[default]
+    Keep,
+    Hash,
+    Omit,
+}
+
+// Applied to collection records before they are stored. Client IPs and URL query strings (which
+// can carry API keys or account ids) are hashed by default. A salt keeps hashed IPv4 addresses
+// from being reversed by enumerating the address space, so the default draws a random one; set an
+// operator salt with `with_salt` so auditors given it can still check a claimed value.
+#[derive(Clone)]
+pub struct CollectionRedaction {
+    pub client_ip: FieldTreatment,
+    pub client_region: FieldTreatment,
+    pub endpoint_query: FieldTreatment,
+    pub salt: String,
+}
+
+impl Default for CollectionRedaction {
+    fn default() -> Self {
+        Self {
+            client_ip: FieldTreatment::Hash,
+            client_region: FieldTreatment::Keep,
+            endpoint_query: FieldTreatment::Hash,
+            salt: random_salt(),
+        }
+    }
+}
+
+impl fmt::Debug for CollectionRedaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CollectionRedaction")
+            .field("client_ip", &self.client_ip)
+            .field("client_region", &self.client_region)
+            .field("endpoint_query", &self.endpoint_query)
+            .field("salt", &"<redacted>")
+            .finish()
+    }
+}
+
+fn random_salt() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+impl CollectionRedaction {
+    pub fn keep_all() -> Self {
+        Self {
+            client_ip: FieldTreatment::Keep,
+            client_region: FieldTreatment::Keep,
+            endpoint_query: FieldTreatment::Keep,
+            salt: String::new(),
+        }
+    }
+
+    // Hashing without a salt is what the salt is there to prevent, so an empty one is refused.
+    pub fn with_salt(mut self, salt: impl Into<String>) -> Result<Self> {
+        let salt = salt.into();
+        if salt.is_empty() {
+            bail!("collection redaction salt must not be empty");
+        }
+        self.salt = salt;
+        Ok(self)
+    }
+
+    pub fn salted_hash(&self, value: &str) -> String {
+        hash_str(&format!("{}{}", self.salt, value))
+    }
+
+    pub fn apply(&self, record: &mut CollectionRecord) {
+        if let Some((base, query)) = record.endpoint_url.split_once('?') {
+            match self.endpoint_query {
+                FieldTreatment::Keep => {}
+                FieldTreatment::Hash => {
+                    record.endpoint_url =
+                        format!("{}?query_hash={}", base, self.salted_hash(query));
+                    record.hashed_fields.push("endpoint_query".to_string());
+                }
+                FieldTreatment::Omit => record.endpoint_url = base.to_string(),
+            }
+        }
+        for (field, value, treatment) in [
+            ("client_ip", &mut record.client_ip, self.client_ip),
+            (
+                "client_region",
+                &mut record.client_region,
+                self.client_region,
+            ),
+        ] {
+            match (treatment, value.as_ref()) {
+                (FieldTreatment::Hash, Some(raw)) => {
+                    *value = Some(self.salted_hash(raw));
+                    record.hashed_fields.push(field.to_string());
+                }
+                (FieldTreatment::Omit, _) => *value = None,
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn mask_ref(reference: &str) -> String {
+    let chars: Vec<char> = reference.chars().collect();
+    if chars.len() <= MIN_EMBEDDED_REF_LEN {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn masks_refs_in_fields_and_embedded_text() {
+        let receipt = json!({
+            "subject": { "account_ref": "acct-secret-9f3a", "order_ref": "order-77", "execution_ref": null },
+            "claim": { "statement": "Order order-77 for acct-secret-9f3a was placed" }
+        });
+        let accounts_only = RedactionConfig::from_env(Some("account_refs"), None);
+        let text = accounts_only.render_text(&receipt);
+        assert!(!text.contains("acct-secret-9f3a"));
+        assert!(text.contains("****9f3a"));
+        assert!(text.contains("order-77"));
+        assert_eq!(accounts_only.structured(&receipt), receipt);
+
+        let hashed = RedactionConfig::from_env(Some("all"), Some("hashed")).structured(&receipt);
+        assert_eq!(hashed["subject"]["order_ref"], hash_str("order-77"));
+        assert!(!hashed["claim"]["statement"]
+            .as_str()
+            .unwrap()
+            .contains("acct-secret-9f3a"));
+    }
+
+    #[test]
+    fn collection_hashes_are_salted_unless_an_operator_salt_is_given() {
+        let first = CollectionRedaction::default();
+        let second = CollectionRedaction::default();
+        assert_eq!(first.salt.len(), 64);
+        assert_ne!(first.salt, second.salt);
+        assert_ne!(
+            first.salted_hash("198.51.100.24"),
+            hash_str("198.51.100.24")
+        );
+
+        assert!(!format!("{:?}", first).contains(&first.salt));
+
+        let operator = CollectionRedaction::default()
+            .with_salt("audit-salt")
+            .expect("salt");
+        assert_eq!(
+            operator.salted_hash("198.51.100.24"),
+            hash_str("audit-salt198.51.100.24")
+        );
+        assert!(operator.with_salt("").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::models::{ProofRequest, Venue};
+use serde::{Deserialize, Serialize};
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceFormat {
+    // 0x-prefixed 32-byte hex transaction hash.
+    EvmTxHash,
+    // base58 ed25519 signature, 64 bytes encoded.
+    SolanaSignature,
+    // Decimal order id, or a 0x-prefixed 16-byte client order id.
+    HyperliquidOrderId,
+    // Decimal trade id, or the 0x-prefixed hash of the transaction that filled.
+    HyperliquidFillId,
+    // 0x-prefixed 32-byte CLOB order hash.
+    PolymarketOrderHash,
+    // CLOB trade UUID.
+    PolymarketTradeId,
+}
+
+impl ReferenceFormat {
+    pub fn matches(self, value: &str) -> bool {
+        match self {
+            ReferenceFormat::EvmTxHash | ReferenceFormat::PolymarketOrderHash => {
+                is_hex_bytes(value, 32)
+            }
+            ReferenceFormat::SolanaSignature => {
+                (64..=88).contains(&value.len())
+                    && value.bytes().all(|b| BASE58_ALPHABET.contains(&b))
+            }
+            ReferenceFormat::HyperliquidOrderId => is_decimal_id(value) || is_hex_bytes(value, 16),
+            ReferenceFormat::HyperliquidFillId => is_decimal_id(value) || is_hex_bytes(value, 32),
+            ReferenceFormat::PolymarketTradeId => uuid::Uuid::try_parse(value).is_ok(),
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            ReferenceFormat::EvmTxHash => "a 0x-prefixed 32-byte transaction hash",
+            ReferenceFormat::SolanaSignature => "a base58 transaction signature",
+            ReferenceFormat::HyperliquidOrderId => {
+                "a numeric order id or 0x-prefixed 16-byte client order id"
+            }
+            ReferenceFormat::HyperliquidFillId => {
+                "a numeric trade id or 0x-prefixed 32-byte transaction hash"
+            }
+            ReferenceFormat::PolymarketOrderHash => "a 0x-prefixed 32-byte CLOB order hash",
+            ReferenceFormat::PolymarketTradeId => "a CLOB trade UUID",
+        }
+    }
+}
+
+// What a venue's order and execution refs look like. Adapters that talk to the venue opt in
+// through `VenueAdapter::reference_formats`, so the engine rejects a malformed ref at submit time
+// instead of after a round trip to the venue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceFormats {
+    pub order_ref: ReferenceFormat,
+    pub execution_ref: ReferenceFormat,
+}
+
+impl ReferenceFormats {
+    pub fn for_venue(venue: Venue) -> Self {
+        let (order_ref, execution_ref) = match venue {
+            Venue::Base => (ReferenceFormat::EvmTxHash, ReferenceFormat::EvmTxHash),
+            Venue::Solana => (
+                ReferenceFormat::SolanaSignature,
+                ReferenceFormat::SolanaSignature,
+            ),
+            Venue::Hyperliquid => (
+                ReferenceFormat::HyperliquidOrderId,
+                ReferenceFormat::HyperliquidFillId,
+            ),
+            Venue::Polymarket => (
+                ReferenceFormat::PolymarketOrderHash,
+                ReferenceFormat::PolymarketTradeId,
+            ),
+        };
+        Self {
+            order_ref,
+            execution_ref,
+        }
+    }
+
+    pub fn validate(&self, request: &ProofRequest) -> Vec<String> {
+        let mut issues = Vec::new();
+        if !self.order_ref.matches(&request.order_ref) {
+            issues.push(format!(
+                "order_ref {:?} is not {}",
+                request.order_ref,
+                self.order_ref.describe()
+            ));
+        }
+        if let Some(execution_ref) = &request.execution_ref {
+            if !self.execution_ref.matches(execution_ref) {
+                issues.push(format!(
+                    "execution_ref {:?} is not {}",
+                    execution_ref,
+                    self.execution_ref.describe()
+                ));
+            }
+        }
+        issues
+    }
+}
+
+fn is_hex_bytes(value: &str, len: usize) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == len * 2 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+fn is_decimal_id(value: &str) -> bool {
+    value.parse::<u64>().is_ok() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn venue_reference_formats() {
+        let tx = format!("0x{}", "ab".repeat(32));
+        assert!(ReferenceFormat::EvmTxHash.matches(&tx));
+        assert!(!ReferenceFormat::EvmTxHash.matches(&tx[2..]));
+        assert!(!ReferenceFormat::EvmTxHash.matches(&format!("{}00", tx)));
+        assert!(ReferenceFormat::HyperliquidOrderId.matches("77738308"));
+        assert!(ReferenceFormat::HyperliquidOrderId.matches(&format!("0x{}", "12".repeat(16))));
+        assert!(!ReferenceFormat::HyperliquidOrderId.matches("+77738308"));
+        assert!(ReferenceFormat::HyperliquidFillId.matches(&tx));
+        assert!(ReferenceFormat::SolanaSignature.matches(&"5".repeat(88)));
+        assert!(!ReferenceFormat::SolanaSignature.matches(&"0".repeat(88)));
+        assert!(ReferenceFormat::PolymarketTradeId.matches("7f0c4b6e-2a41-4b83-9c5d-3e1f2a7b8c90"));
+        assert!(!ReferenceFormat::PolymarketTradeId.matches("trade-1"));
+    }
+}
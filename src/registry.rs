@@ -0,0 +1,182 @@
+use crate::models::{ClaimType, Venue};
+
+// The one place venue slugs and claim names are spelled out. Receipts, public inputs, policy keys,
+// credential names and tool schemas all go through these tables, so the prover and the verifier
+// cannot disagree on a spelling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VenueEntry {
+    pub venue: Venue,
+    pub slug: &'static str,
+    pub display: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimEntry {
+    pub claim_type: ClaimType,
+    pub name: &'static str,
+    pub display: &'static str,
+}
+
+pub const VENUES: &[VenueEntry] = &[
+    VenueEntry {
+        venue: Venue::Hyperliquid,
+        slug: "hyperliquid",
+        display: "Hyperliquid",
+    },
+    VenueEntry {
+        venue: Venue::Base,
+        slug: "base",
+        display: "Base",
+    },
+    VenueEntry {
+        venue: Venue::Solana,
+        slug: "solana",
+        display: "Solana",
+    },
+    VenueEntry {
+        venue: Venue::Polymarket,
+        slug: "polymarket",
+        display: "Polymarket",
+    },
+];
+
+pub const CLAIMS: &[ClaimEntry] = &[
+    ClaimEntry {
+        claim_type: ClaimType::ORDER_PLACED,
+        name: "ORDER_PLACED",
+        display: "Order placed",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::TRADE_EXECUTED,
+        name: "TRADE_EXECUTED",
+        display: "Trade executed",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::BEST_EXECUTION,
+        name: "BEST_EXECUTION",
+        display: "Best execution",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::TRADE_BUSTED,
+        name: "TRADE_BUSTED",
+        display: "Trade busted",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::TRADE_AMENDED,
+        name: "TRADE_AMENDED",
+        display: "Trade amended",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::ORDER_REJECTED,
+        name: "ORDER_REJECTED",
+        display: "Order rejected",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::ORDER_CANCELLED,
+        name: "ORDER_CANCELLED",
+        display: "Order cancelled",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::POSITION_CLOSED,
+        name: "POSITION_CLOSED",
+        display: "Position closed",
+    },
+    ClaimEntry {
+        claim_type: ClaimType::BALANCE_AT_TIMESTAMP,
+        name: "BALANCE_AT_TIMESTAMP",
+        display: "Balance at timestamp",
+    },
+];
+
+impl Venue {
+    pub fn entry(self) -> &'static VenueEntry {
+        VENUES
+            .iter()
+            .find(|entry| entry.venue == self)
+            .expect("every venue has a registry entry")
+    }
+
+    pub fn slug(self) -> &'static str {
+        self.entry().slug
+    }
+
+    pub fn display_name(self) -> &'static str {
+        self.entry().display
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        VENUES
+            .iter()
+            .find(|entry| entry.slug == slug)
+            .map(|entry| entry.venue)
+    }
+
+    pub fn all() -> impl Iterator<Item = Venue> {
+        VENUES.iter().map(|entry| entry.venue)
+    }
+}
+
+impl ClaimType {
+    pub fn entry(self) -> &'static ClaimEntry {
+        CLAIMS
+            .iter()
+            .find(|entry| entry.claim_type == self)
+            .expect("every claim type has a registry entry")
+    }
+
+    pub fn name(self) -> &'static str {
+        self.entry().name
+    }
+
+    pub fn display_name(self) -> &'static str {
+        self.entry().display
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        CLAIMS
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.claim_type)
+    }
+
+    pub fn all() -> impl Iterator<Item = ClaimType> {
+        CLAIMS.iter().map(|entry| entry.claim_type)
+    }
+}
+
+pub fn venue_slugs() -> Vec<&'static str> {
+    VENUES.iter().map(|entry| entry.slug).collect()
+}
+
+pub fn claim_names() -> Vec<&'static str> {
+    CLAIMS.iter().map(|entry| entry.name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The tables must spell every variant the way serde does, or receipts and public inputs drift apart.
+    #[test]
+    fn registry_names_match_the_wire_format() {
+        for entry in VENUES {
+            assert_eq!(serde_json::to_value(entry.venue).unwrap(), entry.slug);
+            assert_eq!(Venue::from_slug(entry.slug), Some(entry.venue));
+        }
+        for entry in CLAIMS {
+            assert_eq!(serde_json::to_value(entry.claim_type).unwrap(), entry.name);
+            assert_eq!(ClaimType::from_name(entry.name), Some(entry.claim_type));
+        }
+        // The schema lists every variant, so a new one without a table entry fails here rather than at runtime.
+        let variants =
+            |schema: serde_json::Value| schema["enum"].as_array().cloned().unwrap_or_default();
+        assert_eq!(
+            variants(serde_json::to_value(schemars::schema_for!(Venue)).unwrap()),
+            venue_slugs()
+        );
+        assert_eq!(
+            variants(serde_json::to_value(schemars::schema_for!(ClaimType)).unwrap()),
+            claim_names()
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::anchoring::merkle_tree;
+use crate::models::{
+    hash_json, now_iso, ClaimType, ProofRequest, ReceiptStatus, SignerIdentity, Venue, ZKReceipt,
+};
+use crate::signing::{verify_signature, KeyRing, ReceiptSigner};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const PERIOD_REPORT_SIGNING_DOMAIN: &str = "zkputer-period-report-v1";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueSummary {
+    pub venue: Venue,
+    pub receipts: usize,
+    pub by_claim_type: BTreeMap<String, usize>,
+    pub notional: Option<f64>,
+}
+
+// A signed attestation over every PROVED receipt for one account in `[from, to)`. The Merkle
+// root uses the anchoring leaf encoding, so any listed receipt can later be shown to be part of
+// the report with an inclusion path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodReport {
+    pub account_ref: String,
+    pub from: String,
+    pub to: String,
+    pub generated_at: String,
+    pub receipts: usize,
+    pub notional: Option<f64>,
+    pub venues: Vec<VenueSummary>,
+    pub receipt_hashes: Vec<String>,
+    pub merkle_root: Option<String>,
+    pub report_hash: String,
+    pub signer: SignerIdentity,
+    pub signature: String,
+}
+
+impl PeriodReport {
+    pub fn compute_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "account_ref": self.account_ref,
+            "from": self.from,
+            "to": self.to,
+            "generated_at": self.generated_at,
+            "receipts": self.receipts,
+            "notional": self.notional,
+            "venues": self.venues,
+            "receipt_hashes": self.receipt_hashes,
+            "merkle_root": self.merkle_root,
+        }))
+    }
+
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!("{}:{}", PERIOD_REPORT_SIGNING_DOMAIN, self.report_hash).into_bytes()
+    }
+
+    pub fn verify(&self, keyring: &KeyRing) -> bool {
+        self.compute_hash() == self.report_hash
+            && keyring.keys().iter().any(|k| {
+                k.identity == self.signer
+                    && k.is_valid_at(&self.generated_at)
+                    && verify_signature(&k.identity, &self.signing_message(), &self.signature)
+            })
+    }
+}
+
+// A receipt falls in the window by its issuance time. `signer` is the engine's current signer.
+pub fn build_period_report(
+    account_ref: &str,
+    from: &str,
+    to: &str,
+    receipts: &[(ZKReceipt, Option<ProofRequest>)],
+    signer: &dyn ReceiptSigner,
+) -> Result<PeriodReport> {
+    let (start, end) = (parse_ts(from)?, parse_ts(to)?);
+    if start >= end {
+        bail!("report window start {} must precede its end {}", from, to);
+    }
+    let mut included: Vec<&(ZKReceipt, Option<ProofRequest>)> = receipts
+        .iter()
+        .filter(|(r, _)| r.status == ReceiptStatus::PROVED && r.subject.account_ref == account_ref)
+        .filter(|(r, _)| {
+            let at = r
+                .integrity
+                .issued_at
+                .as_deref()
+                .unwrap_or(&r.timing.created_at);
+            parse_ts(at).is_ok_and(|at| at >= start && at < end)
+        })
+        .collect();
+    included.sort_by(|(a, _), (b, _)| {
+        (
+            a.integrity.issued_at.as_deref(),
+            a.integrity.sequence,
+            &a.integrity.receipt_hash,
+        )
+            .cmp(&(
+                b.integrity.issued_at.as_deref(),
+                b.integrity.sequence,
+                &b.integrity.receipt_hash,
+            ))
+    });
+
+    let mut venues: BTreeMap<String, VenueSummary> = BTreeMap::new();
+    for (receipt, request) in &included {
+        let venue = receipt.subject.venue;
+        let summary = venues
+            .entry(format!("{:?}", venue))
+            .or_insert_with(|| VenueSummary {
+                venue,
+                receipts: 0,
+                by_claim_type: BTreeMap::new(),
+                notional: None,
+            });
+        summary.receipts += 1;
+        *summary
+            .by_claim_type
+            .entry(format!("{:?}", receipt.claim.r#type))
+            .or_insert(0) += 1;
+        if let Some(notional) = request
+            .as_ref()
+            .and_then(|r| request_notional(receipt.claim.r#type, r))
+        {
+            summary.notional = Some(summary.notional.unwrap_or(0.0) + notional);
+        }
+    }
+    let venues: Vec<VenueSummary> = venues.into_values().collect();
+    let notional = venues
+        .iter()
+        .filter_map(|v| v.notional)
+        .fold(None, |total: Option<f64>, n| Some(total.unwrap_or(0.0) + n));
+
+    let receipt_hashes: Vec<String> = included
+        .iter()
+        .map(|(r, _)| r.integrity.receipt_hash.clone())
+        .collect();
+    let merkle_root = if receipt_hashes.is_empty() {
+        None
+    } else {
+        Some(merkle_tree(&receipt_hashes)?.0)
+    };
+    let mut report = PeriodReport {
+        account_ref: account_ref.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        generated_at: now_iso(),
+        receipts: included.len(),
+        notional,
+        venues,
+        receipt_hashes,
+        merkle_root,
+        report_hash: String::new(),
+        signature: String::new(),
+        signer: signer.identity().clone(),
+    };
+    report.report_hash = report.compute_hash();
+    report.signature = signer.sign(&report.signing_message());
+    Ok(report)
+}
+
+// Only TRADE_EXECUTED payloads declare both a price and a size.
+fn request_notional(claim_type: ClaimType, request: &ProofRequest) -> Option<f64> {
+    if claim_type != ClaimType::TRADE_EXECUTED {
+        return None;
+    }
+    let field = |name: &str| request.payload.get(name).and_then(|v| v.as_f64());
+    Some(field("expected_price")? * field("expected_size")?)
+}
+
+fn parse_ts(raw: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| anyhow!("invalid timestamp {}", raw))
+}
@@ -0,0 +1,219 @@
+use crate::models::{hash_bytes, hash_json, now_iso, RawArtifact};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const GENESIS_RETENTION_HASH: &str = "genesis";
+pub const RETENTION_EXPIRED: &str = "retention expired";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum RetentionAction {
+    STORED,
+    DELETED,
+}
+
+// One entry in the retention audit log. Entries chain like receipt events, so a dropped or edited
+// deletion record breaks the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionEvent {
+    pub sequence: u64,
+    pub receipt_id: String,
+    pub artifact_hash: String,
+    pub action: RetentionAction,
+    pub reason: Option<String>,
+    pub expires_at: Option<String>,
+    pub recorded_at: String,
+    pub previous_hash: String,
+    pub entry_hash: String,
+}
+
+impl RetentionEvent {
+    pub fn compute_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "sequence": self.sequence,
+            "receipt_id": self.receipt_id,
+            "artifact_hash": self.artifact_hash,
+            "action": self.action,
+            "reason": self.reason,
+            "expires_at": self.expires_at,
+            "recorded_at": self.recorded_at,
+            "previous_hash": self.previous_hash,
+        }))
+    }
+}
+
+pub fn verify_retention_log(log: &[RetentionEvent]) -> Result<()> {
+    let mut previous_hash = GENESIS_RETENTION_HASH.to_string();
+    for (sequence, event) in log.iter().enumerate() {
+        if event.sequence != sequence as u64
+            || event.previous_hash != previous_hash
+            || event.entry_hash != event.compute_hash()
+        {
+            bail!("retention log is broken at entry {}", sequence);
+        }
+        previous_hash = event.entry_hash.clone();
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct HeldArtifact {
+    bytes: Vec<u8>,
+    expires: Instant,
+}
+
+#[derive(Debug, Default)]
+struct VaultState {
+    artifacts: BTreeMap<(String, String), HeldArtifact>,
+    log: Vec<RetentionEvent>,
+}
+
+impl VaultState {
+    fn record(
+        &mut self,
+        receipt_id: &str,
+        artifact_hash: &str,
+        action: RetentionAction,
+        reason: Option<&str>,
+        expires_at: Option<String>,
+    ) {
+        let mut event = RetentionEvent {
+            sequence: self.log.len() as u64,
+            receipt_id: receipt_id.to_string(),
+            artifact_hash: artifact_hash.to_string(),
+            action,
+            reason: reason.map(str::to_string),
+            expires_at,
+            recorded_at: now_iso(),
+            previous_hash: self.log.last().map_or_else(
+                || GENESIS_RETENTION_HASH.to_string(),
+                |e| e.entry_hash.clone(),
+            ),
+            entry_hash: String::new(),
+        };
+        event.entry_hash = event.compute_hash();
+        self.log.push(event);
+    }
+
+    fn delete_where(
+        &mut self,
+        reason: &str,
+        mut matches: impl FnMut(&(String, String), &HeldArtifact) -> bool,
+    ) -> usize {
+        let doomed: Vec<(String, String)> = self
+            .artifacts
+            .iter()
+            .filter(|(key, held)| matches(key, held))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &doomed {
+            self.artifacts.remove(key);
+            self.record(&key.0, &key.1, RetentionAction::DELETED, Some(reason), None);
+        }
+        doomed.len()
+    }
+}
+
+// Raw evidence bodies, which may carry payloads too sensitive to keep as long as receipts. Receipts
+// only commit to artifact hashes, so they stay verifiable once the bytes are gone; every store and
+// delete goes in a hash-chained log.
+#[derive(Debug, Clone)]
+pub struct EvidenceVault {
+    ttl: Duration,
+    state: Arc<Mutex<VaultState>>,
+}
+
+impl EvidenceVault {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Arc::new(Mutex::new(VaultState::default())),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    // Artifacts whose bytes don't hash to their declared hash are refused. A re-run that collects
+    // an artifact already held keeps the original expiry.
+    pub fn put(&self, receipt_id: &str, artifacts: &[RawArtifact]) -> Result<()> {
+        let mut state = self.state.lock().expect("evidence vault lock poisoned");
+        for artifact in artifacts {
+            if hash_bytes(&artifact.bytes) != artifact.artifact_hash {
+                bail!(
+                    "raw artifact bytes do not hash to {}",
+                    artifact.artifact_hash
+                );
+            }
+            let key = (receipt_id.to_string(), artifact.artifact_hash.clone());
+            if state.artifacts.contains_key(&key) {
+                continue;
+            }
+            let expires_at = chrono::Utc::now()
+                + chrono::Duration::from_std(self.ttl).unwrap_or(chrono::Duration::MAX);
+            state.artifacts.insert(
+                key,
+                HeldArtifact {
+                    bytes: artifact.bytes.clone(),
+                    expires: Instant::now() + self.ttl,
+                },
+            );
+            state.record(
+                receipt_id,
+                &artifact.artifact_hash,
+                RetentionAction::STORED,
+                None,
+                Some(expires_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)),
+            );
+        }
+        Ok(())
+    }
+
+    // None once the artifact has expired, even if no sweep has run yet.
+    pub fn get(&self, receipt_id: &str, artifact_hash: &str) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().expect("evidence vault lock poisoned");
+        let now = Instant::now();
+        state.delete_where(RETENTION_EXPIRED, |key, held| {
+            key.0 == receipt_id && key.1 == artifact_hash && held.expires <= now
+        });
+        state
+            .artifacts
+            .get(&(receipt_id.to_string(), artifact_hash.to_string()))
+            .map(|held| held.bytes.clone())
+    }
+
+    // Deletes every expired artifact; returns how many went.
+    pub fn sweep(&self) -> usize {
+        let mut state = self.state.lock().expect("evidence vault lock poisoned");
+        let now = Instant::now();
+        state.delete_where(RETENTION_EXPIRED, |_, held| held.expires <= now)
+    }
+
+    // Deletes a receipt's artifacts ahead of their expiry, e.g. on a data-subject request.
+    pub fn erase(&self, receipt_id: &str, reason: &str) -> usize {
+        let mut state = self.state.lock().expect("evidence vault lock poisoned");
+        state.delete_where(reason, |key, _| key.0 == receipt_id)
+    }
+
+    pub fn held(&self, receipt_id: &str) -> Vec<String> {
+        let state = self.state.lock().expect("evidence vault lock poisoned");
+        state
+            .artifacts
+            .keys()
+            .filter(|key| key.0 == receipt_id)
+            .map(|key| key.1.clone())
+            .collect()
+    }
+
+    pub fn log(&self) -> Vec<RetentionEvent> {
+        self.state
+            .lock()
+            .expect("evidence vault lock poisoned")
+            .log
+            .clone()
+    }
+}
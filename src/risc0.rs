@@ -0,0 +1,126 @@
+use crate::hashing::HashAlgorithm;
+use crate::models::{hash_json, ProofBackend, ProofMetadata, VerificationMode};
+use crate::prover::{ProofWitness, ProverBackend, ProverUsage};
+use crate::zkvm_host::run_host;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+
+pub const RISC0_CIRCUIT_ID: &str = "trade-receipt-risc0";
+pub const RISC0_CIRCUIT_VERSION: &str = "v0.2.0";
+
+#[derive(Debug, Serialize)]
+struct HostRequest {
+    public_inputs: Value,
+    witness: ProofWitness,
+    receipt_path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct HostResponse {
+    receipt_path: PathBuf,
+    image_id: String,
+    journal: Value,
+    cycles: u64,
+}
+
+// Proves receipts with the guest in risc0/methods/guest, which opens the same commitments as the
+// SP1 guest. Proving runs in the host binary built from risc0/host, one process per proof.
+#[derive(Debug, Clone)]
+pub struct Risc0Prover {
+    host: PathBuf,
+    receipt_dir: PathBuf,
+}
+
+impl Risc0Prover {
+    pub fn new(host: impl Into<PathBuf>, receipt_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            host: host.into(),
+            receipt_dir: receipt_dir.into(),
+        }
+    }
+
+    // ZKPUTER_RISC0_HOST names the host binary; receipts go to ZKPUTER_RISC0_RECEIPT_DIR, or a temp dir.
+    pub fn from_env() -> Result<Self> {
+        let host = std::env::var("ZKPUTER_RISC0_HOST").context("ZKPUTER_RISC0_HOST is not set")?;
+        let receipt_dir = std::env::var("ZKPUTER_RISC0_RECEIPT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir().join("zkputer-risc0"));
+        Ok(Self::new(host, receipt_dir))
+    }
+}
+
+#[async_trait]
+impl ProverBackend for Risc0Prover {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::RISC0
+    }
+
+    async fn prove(&self, _public_inputs: &Value) -> Result<ProofMetadata> {
+        bail!("RISC0 proving needs the claim and evidence preimages; use prove_with_witness")
+    }
+
+    async fn available(&self) -> bool {
+        self.host.is_file()
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        if witness.hash_algorithm != HashAlgorithm::Sha256 {
+            bail!(
+                "RISC0 guest opens sha256 commitments only, not {}",
+                witness.hash_algorithm.id()
+            );
+        }
+        if witness.claim_preimage.is_none() || witness.evidence_preimage.is_none() {
+            bail!("RISC0 guest opens JSON commitments only");
+        }
+        let public_inputs_hash = hash_json(public_inputs);
+        let request = HostRequest {
+            public_inputs: public_inputs.clone(),
+            witness: witness.clone(),
+            receipt_path: self.receipt_dir.join(format!(
+                "{}.bin",
+                public_inputs_hash.trim_start_matches("0x")
+            )),
+        };
+        std::fs::create_dir_all(&self.receipt_dir)
+            .with_context(|| format!("failed to create {}", self.receipt_dir.display()))?;
+        let host = self.host.clone();
+        let response: HostResponse =
+            tokio::task::spawn_blocking(move || run_host(&host, "RISC0", &request)).await??;
+        // The journal holds the public inputs the guest checked; they must be the ones we asked about.
+        if hash_json(&response.journal) != public_inputs_hash {
+            bail!("RISC0 receipt journal commits to different public inputs than requested");
+        }
+        let image_id = response.image_id.trim_start_matches("0x").to_string();
+        let metadata = ProofMetadata {
+            backend: ProofBackend::RISC0,
+            circuit_id: RISC0_CIRCUIT_ID.to_string(),
+            circuit_version: RISC0_CIRCUIT_VERSION.to_string(),
+            verifier_key_id: format!(
+                "risc0-image-{}",
+                image_id.chars().take(16).collect::<String>()
+            ),
+            verifier_key_hash: format!("0x{}", image_id),
+            public_inputs_hash,
+            verification_mode: VerificationMode::OFFCHAIN,
+            proof_artifact_ref: Some(format!("file://{}", response.receipt_path.display())),
+            anchored_root_ref: None,
+            anchor_inclusion: None,
+            fallback_from: vec![],
+        };
+        Ok((
+            metadata,
+            ProverUsage {
+                cycles: response.cycles,
+                fee_usd: 0.0,
+            },
+        ))
+    }
+}
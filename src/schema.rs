@@ -0,0 +1,48 @@
+use crate::models::{ProofRequest, ZKReceipt};
+use crate::validation::validate_against_schema;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+pub const GENERATED_RECEIPT_SCHEMA_PATH: &str = "spec/zkreceipt.generated.schema.json";
+
+pub fn receipt_json_schema() -> Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(ZKReceipt)).unwrap_or(Value::Null);
+    if let Some(obj) = schema.as_object_mut() {
+        obj.insert(
+            "$id".to_string(),
+            Value::String("https://zkputer.dev/spec/zkreceipt.generated.schema.json".to_string()),
+        );
+    }
+    schema
+}
+
+pub fn proof_request_json_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(ProofRequest)).unwrap_or(Value::Null)
+}
+
+// Checks a receipt document against the schema generated from the receipt types, so the shape a
+// verifier accepts is the one the structs serialize. An empty list means the document conforms.
+pub fn validate_receipt_json(document: &Value) -> Vec<String> {
+    static SCHEMA: OnceLock<Value> = OnceLock::new();
+    validate_against_schema("receipt", document, SCHEMA.get_or_init(receipt_json_schema))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_receipt_schema_is_up_to_date() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(GENERATED_RECEIPT_SCHEMA_PATH);
+        let committed: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("generated schema exists"))
+                .expect("generated schema parses");
+        assert_eq!(
+            committed,
+            receipt_json_schema(),
+            "run `cargo run --bin schema -- --write` to regenerate {}",
+            GENERATED_RECEIPT_SCHEMA_PATH
+        );
+    }
+}
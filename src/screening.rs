@@ -0,0 +1,209 @@
+use crate::models::{hash_json, now_iso, EvidenceBundle};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreeningHit {
+    pub subject: String,
+    pub list_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreeningResult {
+    pub list_version: String,
+    pub hits: Vec<ScreeningHit>,
+}
+
+#[async_trait]
+pub trait ScreeningProvider: Send + Sync {
+    fn provider_id(&self) -> String;
+    async fn screen(&self, subjects: &[String]) -> Result<ScreeningResult>;
+}
+
+// Screens against an in-memory list, matching case-insensitively so checksummed and lowercase
+// EVM addresses compare equal.
+#[derive(Debug, Clone)]
+pub struct StaticListScreeningProvider {
+    list_id: String,
+    list_version: String,
+    entries: HashSet<String>,
+}
+
+impl StaticListScreeningProvider {
+    pub fn new(
+        list_id: impl Into<String>,
+        list_version: impl Into<String>,
+        entries: Vec<String>,
+    ) -> Self {
+        Self {
+            list_id: list_id.into(),
+            list_version: list_version.into(),
+            entries: entries
+                .into_iter()
+                .map(|e| e.trim().to_ascii_lowercase())
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl ScreeningProvider for StaticListScreeningProvider {
+    fn provider_id(&self) -> String {
+        format!("static_list:{}", self.list_id)
+    }
+
+    async fn screen(&self, subjects: &[String]) -> Result<ScreeningResult> {
+        let hits = subjects
+            .iter()
+            .filter(|s| self.entries.contains(&s.to_ascii_lowercase()))
+            .map(|s| ScreeningHit {
+                subject: s.clone(),
+                list_id: self.list_id.clone(),
+                reason: "listed".to_string(),
+            })
+            .collect();
+        Ok(ScreeningResult {
+            list_version: self.list_version.clone(),
+            hits,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningStage {
+    PreSubmission,
+    Evidence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub enum ScreeningOutcome {
+    CLEAR,
+    BLOCKED,
+    UNAVAILABLE,
+}
+
+// One screening call as it was made, kept beside the receipt for compliance review. The hash
+// covers every other field so an exported record can be checked for later edits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreeningDecision {
+    pub receipt_id: String,
+    pub stage: ScreeningStage,
+    pub provider: String,
+    pub list_version: Option<String>,
+    pub subjects: Vec<String>,
+    pub hits: Vec<ScreeningHit>,
+    pub outcome: ScreeningOutcome,
+    pub detail: Option<String>,
+    pub decided_at: String,
+    pub decision_hash: String,
+}
+
+impl ScreeningDecision {
+    pub fn compute_hash(&self) -> String {
+        hash_json(&serde_json::json!({
+            "receipt_id": self.receipt_id,
+            "stage": self.stage,
+            "provider": self.provider,
+            "list_version": self.list_version,
+            "subjects": self.subjects,
+            "hits": self.hits,
+            "outcome": self.outcome,
+            "detail": self.detail,
+            "decided_at": self.decided_at,
+        }))
+    }
+}
+
+pub async fn screen_subjects(
+    provider: &dyn ScreeningProvider,
+    receipt_id: &str,
+    stage: ScreeningStage,
+    subjects: Vec<String>,
+) -> ScreeningDecision {
+    let (list_version, hits, outcome, detail) = match provider.screen(&subjects).await {
+        Ok(result) if result.hits.is_empty() => (
+            Some(result.list_version),
+            vec![],
+            ScreeningOutcome::CLEAR,
+            None,
+        ),
+        Ok(result) => (
+            Some(result.list_version),
+            result.hits,
+            ScreeningOutcome::BLOCKED,
+            None,
+        ),
+        Err(err) => (
+            None,
+            vec![],
+            ScreeningOutcome::UNAVAILABLE,
+            Some(err.to_string()),
+        ),
+    };
+    let mut decision = ScreeningDecision {
+        receipt_id: receipt_id.to_string(),
+        stage,
+        provider: provider.provider_id(),
+        list_version,
+        subjects,
+        hits,
+        outcome,
+        detail,
+        decided_at: now_iso(),
+        decision_hash: String::new(),
+    };
+    decision.decision_hash = decision.compute_hash();
+    decision
+}
+
+// EVM-style addresses referenced by evidence artifacts, e.g. a settlement transaction's
+// counterparty in `base://tx/0x...?from=0x...`.
+pub fn evidence_addresses(bundle: &EvidenceBundle) -> Vec<String> {
+    let mut addresses = BTreeSet::new();
+    for item in &bundle.items {
+        for text in [&item.artifact_ref, &item.source_id] {
+            addresses.extend(find_evm_addresses(text));
+        }
+    }
+    addresses.into_iter().collect()
+}
+
+fn find_evm_addresses(text: &str) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i + 42 <= bytes.len() {
+        let candidate = &bytes[i..i + 42];
+        let boundary_before = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+        let boundary_after = i + 42 == bytes.len() || !bytes[i + 42].is_ascii_alphanumeric();
+        if boundary_before
+            && boundary_after
+            && candidate.starts_with(b"0x")
+            && candidate[2..].iter().all(|b| b.is_ascii_hexdigit())
+        {
+            found.push(text[i..i + 42].to_ascii_lowercase());
+            i += 42;
+        } else {
+            i += 1;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_whole_evm_addresses_only() {
+        let addr = "0xAbCdEf0123456789abcdef0123456789ABCDEF01";
+        let text = format!("base://tx/0x{}?from={}", "11".repeat(32), addr);
+        assert_eq!(find_evm_addresses(&text), vec![addr.to_ascii_lowercase()]);
+        assert!(find_evm_addresses("order-0x1234").is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::models::ZKReceipt;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Default, Clone)]
+pub struct SearchIndex {
+    postings: HashMap<String, HashSet<String>>,
+    documents: HashMap<String, HashSet<String>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn index(&mut self, receipt: &ZKReceipt) {
+        self.remove(&receipt.receipt_id);
+        let tokens: HashSet<String> = tokenize(&searchable_text(receipt)).collect();
+        for token in &tokens {
+            self.postings
+                .entry(token.clone())
+                .or_default()
+                .insert(receipt.receipt_id.clone());
+        }
+        self.documents.insert(receipt.receipt_id.clone(), tokens);
+    }
+
+    pub fn remove(&mut self, receipt_id: &str) {
+        let Some(tokens) = self.documents.remove(receipt_id) else {
+            return;
+        };
+        for token in tokens {
+            if let Some(ids) = self.postings.get_mut(&token) {
+                ids.remove(receipt_id);
+                if ids.is_empty() {
+                    self.postings.remove(&token);
+                }
+            }
+        }
+    }
+
+    pub fn search(&self, query: &str) -> HashSet<String> {
+        let mut result: Option<HashSet<String>> = None;
+        for term in tokenize(query) {
+            let matches: HashSet<String> = self
+                .postings
+                .iter()
+                .filter(|(token, _)| token.starts_with(&term))
+                .flat_map(|(_, ids)| ids.iter().cloned())
+                .collect();
+            result = Some(match result {
+                Some(acc) => acc.intersection(&matches).cloned().collect(),
+                None => matches,
+            });
+        }
+        result.unwrap_or_default()
+    }
+}
+
+fn searchable_text(receipt: &ZKReceipt) -> String {
+    let mut parts = vec![
+        receipt.claim.statement.clone(),
+        receipt.subject.account_ref.clone(),
+        receipt.subject.order_ref.clone(),
+    ];
+    if let Some(execution_ref) = &receipt.subject.execution_ref {
+        parts.push(execution_ref.clone());
+    }
+    if let Some(non_provable) = &receipt.non_provable {
+        parts.push(non_provable.details.clone());
+    }
+    parts.join(" ")
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+}
@@ -0,0 +1,387 @@
+use crate::engine::{recompute_receipt_hash, recompute_schema_hash};
+use crate::models::{hash_bytes, hash_json, now_iso, Integrity, SignerIdentity, ZKReceipt};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const ED25519_ALGORITHM: &str = "ed25519";
+const RECEIPT_SIGNING_DOMAIN: &str = "zkputer-receipt-v1";
+
+// Holds a private key and produces detached, hex-encoded signatures over receipt signing
+// messages. The identity's `public_key` is what verifiers check the signature against.
+pub trait ReceiptSigner: Send + Sync {
+    fn identity(&self) -> &SignerIdentity;
+    fn sign(&self, message: &[u8]) -> String;
+}
+
+pub struct Ed25519ReceiptSigner {
+    identity: SignerIdentity,
+    key: SigningKey,
+}
+
+impl Ed25519ReceiptSigner {
+    // The key id defaults to a fingerprint of the public key.
+    pub fn from_seed(name: impl Into<String>, seed: [u8; 32]) -> Self {
+        let key = SigningKey::from_bytes(&seed);
+        let public_key = hex::encode(key.verifying_key().as_bytes());
+        let key_id = format!("ed25519-{}", &public_key[..16]);
+        Self {
+            identity: SignerIdentity::new(name, key_id, public_key, ED25519_ALGORITHM),
+            key,
+        }
+    }
+
+    pub fn from_hex_seed(name: impl Into<String>, seed: &str) -> Result<Self> {
+        let bytes = hex::decode(seed.trim()).context("ed25519 seed is not valid hex")?;
+        let seed: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            anyhow!("ed25519 seed must be 32 bytes, got {}", bytes.len())
+        })?;
+        Ok(Self::from_seed(name, seed))
+    }
+
+    // The file holds the 32-byte seed as hex.
+    pub fn from_file(name: impl Into<String>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let seed = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read signing key {}", path.display()))?;
+        Self::from_hex_seed(name, &seed)
+    }
+
+    pub fn with_key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.identity.key_id = key_id.into();
+        self
+    }
+}
+
+impl ReceiptSigner for Ed25519ReceiptSigner {
+    fn identity(&self) -> &SignerIdentity {
+        &self.identity
+    }
+
+    fn sign(&self, message: &[u8]) -> String {
+        hex::encode(self.key.sign(message).to_bytes())
+    }
+}
+
+// Stands in for a private key for identities that have none, such as the dev signer: the signature
+// binds the message's hash to the identity, and anyone can recompute it. It is never accepted where
+// a public key is checked.
+pub struct HashBindingSigner {
+    identity: SignerIdentity,
+}
+
+impl HashBindingSigner {
+    pub fn new(identity: SignerIdentity) -> Self {
+        Self { identity }
+    }
+}
+
+impl ReceiptSigner for HashBindingSigner {
+    fn identity(&self) -> &SignerIdentity {
+        &self.identity
+    }
+
+    fn sign(&self, message: &[u8]) -> String {
+        hash_json(&serde_json::json!({
+            "signer": self.identity.name,
+            "key_id": self.identity.key_id,
+            "algorithm": self.identity.algorithm,
+            "message_hash": hash_bytes(message)
+        }))
+    }
+}
+
+// ZKPUTER_SIGNING_KEY (hex seed) or ZKPUTER_SIGNING_KEY_FILE selects an Ed25519 key, named by
+// ZKPUTER_SIGNER_NAME (default "zkputer"). With neither set, receipts keep the dev signer.
+pub fn receipt_signer_from_env() -> Result<Option<Ed25519ReceiptSigner>> {
+    crate::config::SignerConfig::default()
+        .with_env_overrides(|name| std::env::var(name).ok())
+        .ed25519_signer()
+}
+
+// What an Ed25519 signer signs: the receipt hash, plus the issuance sequence and time once the
+// receipt is issued.
+pub fn receipt_signing_message(integrity: &Integrity) -> Vec<u8> {
+    match &integrity.issued_at {
+        Some(issued_at) => format!(
+            "{}:{}:{}:{}",
+            RECEIPT_SIGNING_DOMAIN, integrity.receipt_hash, integrity.sequence, issued_at
+        ),
+        None => format!("{}:{}", RECEIPT_SIGNING_DOMAIN, integrity.receipt_hash),
+    }
+    .into_bytes()
+}
+
+// Checks a signature `identity` made over `message`: by its public key for Ed25519 identities, by
+// recomputing the hash binding for the rest.
+pub fn verify_signature(identity: &SignerIdentity, message: &[u8], signature: &str) -> bool {
+    if identity.algorithm == ED25519_ALGORITHM {
+        return verify_ed25519(&identity.public_key, message, signature);
+    }
+    HashBindingSigner::new(identity.clone()).sign(message) == signature
+}
+
+pub fn verify_ed25519(public_key: &str, message: &[u8], signature: &str) -> bool {
+    let key = hex::decode(public_key)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+    let signature = hex::decode(signature)
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+        .map(|bytes| Signature::from_bytes(&bytes));
+    match (key, signature) {
+        (Some(key), Some(signature)) => key.verify(message, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerKey {
+    pub identity: SignerIdentity,
+    pub valid_from: String,
+    pub valid_until: Option<String>,
+}
+
+impl SignerKey {
+    pub fn new(
+        identity: SignerIdentity,
+        valid_from: impl Into<String>,
+        valid_until: Option<String>,
+    ) -> Self {
+        Self {
+            identity,
+            valid_from: valid_from.into(),
+            valid_until,
+        }
+    }
+
+    pub fn is_valid_at(&self, at: &str) -> bool {
+        let (Some(at), Some(from)) = (parse_ts(at), parse_ts(&self.valid_from)) else {
+            return false;
+        };
+        if at < from {
+            return false;
+        }
+        match &self.valid_until {
+            Some(until) => parse_ts(until).is_some_and(|until| at < until),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyManifest {
+    pub generated_at: String,
+    pub keys: Vec<SignerKey>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyRing {
+    keys: Vec<SignerKey>,
+}
+
+impl KeyRing {
+    pub fn new(keys: Vec<SignerKey>) -> Result<Self> {
+        if keys.is_empty() {
+            return Err(anyhow!("key ring requires at least one signer key"));
+        }
+        for key in &keys {
+            if parse_ts(&key.valid_from).is_none() {
+                return Err(anyhow!(
+                    "invalid valid_from for key {}",
+                    key.identity.key_id
+                ));
+            }
+            if key
+                .valid_until
+                .as_deref()
+                .is_some_and(|until| parse_ts(until).is_none())
+            {
+                return Err(anyhow!(
+                    "invalid valid_until for key {}",
+                    key.identity.key_id
+                ));
+            }
+        }
+        Ok(Self { keys })
+    }
+
+    pub fn single(identity: SignerIdentity) -> Self {
+        Self {
+            keys: vec![SignerKey::new(identity, "1970-01-01T00:00:00.000Z", None)],
+        }
+    }
+
+    pub fn keys(&self) -> &[SignerKey] {
+        &self.keys
+    }
+
+    pub fn signer_at(&self, at: &str) -> Option<&SignerIdentity> {
+        self.keys
+            .iter()
+            .filter(|k| k.is_valid_at(at))
+            .max_by_key(|k| parse_ts(&k.valid_from))
+            .map(|k| &k.identity)
+    }
+
+    pub fn current_signer(&self) -> Option<&SignerIdentity> {
+        self.signer_at(&now_iso())
+    }
+
+    // The key must be valid when it signed. An issued receipt signs its `issued_at`, and a receipt
+    // re-signed after a rotation is issued again, so that is the time checked; one not yet issued
+    // has no signed time and is checked at `created_at`.
+    pub fn verify_receipt_signature(&self, receipt: &ZKReceipt) -> bool {
+        let signer = &receipt.integrity.signer;
+        let signed_at = receipt
+            .integrity
+            .issued_at
+            .as_deref()
+            .unwrap_or(&receipt.timing.created_at);
+        self.keys.iter().any(|k| {
+            k.identity == *signer
+                && k.is_valid_at(signed_at)
+                && signature_matches(&k.identity, receipt)
+        })
+    }
+
+    pub fn manifest(&self) -> KeyManifest {
+        KeyManifest {
+            generated_at: now_iso(),
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl Default for KeyRing {
+    fn default() -> Self {
+        Self::single(SignerIdentity::dev())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityVerification {
+    pub schema_hash_valid: bool,
+    pub receipt_hash_valid: bool,
+    pub signature_valid: bool,
+}
+
+impl IntegrityVerification {
+    pub fn is_valid(&self) -> bool {
+        self.schema_hash_valid && self.receipt_hash_valid && self.signature_valid
+    }
+}
+
+impl ZKReceipt {
+    // Checks the receipt on its own: both hashes are recomputed from the receipt's fields. Given
+    // the signer's published public key, the signature must be an Ed25519 signature by that key,
+    // whatever algorithm the receipt names, so a receipt cannot downgrade itself to a hash binding.
+    // Without one it is checked against the identity it carries, which for hash-binding signers
+    // only shows the receipt is self-consistent.
+    pub fn verify_integrity(&self, public_key: Option<&str>) -> IntegrityVerification {
+        let signer = &self.integrity.signer;
+        let signature_valid = match public_key {
+            Some(public_key) => {
+                signer.algorithm == ED25519_ALGORITHM
+                    && signer.public_key == public_key
+                    && verify_ed25519(
+                        public_key,
+                        &receipt_signing_message(&self.integrity),
+                        &self.integrity.signature,
+                    )
+            }
+            None => signature_matches(signer, self),
+        };
+        IntegrityVerification {
+            schema_hash_valid: recompute_schema_hash(self) == self.integrity.schema_hash,
+            receipt_hash_valid: recompute_receipt_hash(self) == self.integrity.receipt_hash,
+            signature_valid,
+        }
+    }
+}
+
+fn signature_matches(signer: &SignerIdentity, receipt: &ZKReceipt) -> bool {
+    let integrity = &receipt.integrity;
+    verify_signature(
+        signer,
+        &receipt_signing_message(integrity),
+        &integrity.signature,
+    )
+}
+
+fn parse_ts(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(key_id: &str) -> SignerIdentity {
+        SignerIdentity::new(
+            "acme-verifier",
+            key_id,
+            format!("pk-{}", key_id),
+            "sha256-binding",
+        )
+    }
+
+    fn rotating_ring() -> KeyRing {
+        KeyRing::new(vec![
+            SignerKey::new(
+                identity("k1"),
+                "2026-01-01T00:00:00.000Z",
+                Some("2026-03-01T00:00:00.000Z".to_string()),
+            ),
+            SignerKey::new(identity("k2"), "2026-02-01T00:00:00.000Z", None),
+        ])
+        .expect("ring should build")
+    }
+
+    #[test]
+    fn newest_valid_key_signs_during_overlap() {
+        let ring = rotating_ring();
+        assert_eq!(
+            ring.signer_at("2026-01-15T00:00:00.000Z")
+                .map(|s| s.key_id.as_str()),
+            Some("k1")
+        );
+        assert_eq!(
+            ring.signer_at("2026-02-15T00:00:00.000Z")
+                .map(|s| s.key_id.as_str()),
+            Some("k2")
+        );
+        assert_eq!(ring.signer_at("2025-12-31T00:00:00.000Z"), None);
+    }
+
+    #[test]
+    fn ed25519_signer_matches_rfc8032_vector() {
+        let signer = Ed25519ReceiptSigner::from_hex_seed(
+            "rfc8032",
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        )
+        .expect("seed");
+        let public_key = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+        let signature = signer.sign(b"");
+        assert_eq!(signer.identity().public_key, public_key);
+        assert_eq!(
+            signature,
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+        assert!(verify_ed25519(public_key, b"", &signature));
+        assert!(!verify_ed25519(public_key, b"x", &signature));
+    }
+
+    #[test]
+    fn retired_key_still_valid_for_receipts_created_in_its_window() {
+        let ring = rotating_ring();
+        let k1 = &ring.keys()[0];
+        assert!(k1.is_valid_at("2026-02-28T23:59:59.000Z"));
+        assert!(!k1.is_valid_at("2026-03-01T00:00:00.000Z"));
+    }
+}
@@ -0,0 +1,274 @@
+use crate::adapters::StageLatency;
+use crate::models::{ClaimType, NonProvableReason, ReceiptStatus, StageBreakdown};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const SLOS_PATH: &str = "benchmarks/phase0_slos.json";
+pub const LATENCY_PROFILES_PATH: &str = "benchmarks/latency_profiles.json";
+
+// Scheduling noise allowed on top of a finality timeout before the timeout counts as unenforced.
+const TIMEOUT_SLACK_MS: f64 = 250.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletionObjective {
+    pub p95_seconds_max: f64,
+    #[serde(default)]
+    pub p99_seconds_max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonProvableObjective {
+    pub normal_conditions_max: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloObjectives {
+    pub execution_path_blocking_latency_ms: f64,
+    pub order_placed_proof_completion: CompletionObjective,
+    pub trade_executed_proof_completion: CompletionObjective,
+    pub non_provable_rate: NonProvableObjective,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloDocument {
+    pub version: String,
+    pub objectives: SloObjectives,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileExpectation {
+    // Every receipt meets the completion and non-provable objectives.
+    Normal,
+    // Receipts that wait for finality fail with FINALITY_TIMEOUT, on time; the rest are normal.
+    FinalityTimeout,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyProfile {
+    pub id: String,
+    pub latency_ms: StageLatency,
+    pub finality_timeout_ms: u64,
+    pub expect: ProfileExpectation,
+}
+
+#[derive(Debug, Deserialize)]
+struct LatencyProfileFile {
+    profiles: Vec<LatencyProfile>,
+}
+
+pub fn load_slo_document(path: &Path) -> Result<SloDocument> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("failed to parse SLOs {}", path.display()))
+}
+
+pub fn load_latency_profiles(path: &Path) -> Result<Vec<LatencyProfile>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let parsed: LatencyProfileFile = serde_json::from_str(&text)
+        .with_context(|| format!("failed to parse latency profiles {}", path.display()))?;
+    Ok(parsed.profiles)
+}
+
+// One receipt from a run: how long `submit` held the caller, how long the receipt took to reach its
+// terminal status, and the per-stage breakdown the receipt recorded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptSample {
+    pub claim_type: ClaimType,
+    pub awaits_finality: bool,
+    pub status: ReceiptStatus,
+    pub reason: Option<NonProvableReason>,
+    pub submit_ms: f64,
+    pub completion_ms: f64,
+    #[serde(default)]
+    pub breakdown: StageBreakdown,
+}
+
+// p95 of one pipeline stage across the samples that reached it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageAttribution {
+    pub stage: String,
+    pub samples: usize,
+    pub p95_ms: f64,
+}
+
+// Which stages a run's completion time went to, in pipeline order.
+pub fn attribute_stages(samples: &[ReceiptSample]) -> Vec<StageAttribution> {
+    let mut stages: Vec<(&'static str, Vec<f64>)> = vec![];
+    for sample in samples {
+        for (stage, ms) in sample.breakdown.stages() {
+            match stages.iter_mut().find(|(name, _)| *name == stage) {
+                Some((_, values)) => values.push(ms as f64),
+                None => stages.push((stage, vec![ms as f64])),
+            }
+        }
+    }
+    stages
+        .into_iter()
+        .filter_map(|(stage, values)| {
+            percentile(&values, 95.0).map(|p95_ms| StageAttribution {
+                stage: stage.to_string(),
+                samples: values.len(),
+                p95_ms,
+            })
+        })
+        .collect()
+}
+
+// Finality waiters seen by the health probe during a run, and how many were left at the end.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackpressureObservation {
+    pub peak_finality_waiters: usize,
+    pub residual_finality_waiters: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloCheck {
+    pub name: String,
+    pub measured: f64,
+    pub limit: f64,
+    pub passed: bool,
+}
+
+impl SloCheck {
+    fn at_most(name: impl Into<String>, measured: f64, limit: f64) -> Self {
+        Self {
+            name: name.into(),
+            measured,
+            limit,
+            passed: measured <= limit,
+        }
+    }
+}
+
+// Nearest-rank percentile.
+pub fn percentile(values: &[f64], p: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil().max(1.0) as usize;
+    Some(sorted[rank.min(sorted.len()) - 1])
+}
+
+// The submit path does no stage work, so with an acknowledge delay injected it must return before
+// the venue could have acknowledged.
+pub fn evaluate_slos(
+    slos: &SloDocument,
+    profile: &LatencyProfile,
+    samples: &[ReceiptSample],
+    backpressure: &BackpressureObservation,
+) -> Vec<SloCheck> {
+    let objectives = &slos.objectives;
+    let mut checks = Vec::new();
+    if profile.latency_ms.acknowledge > 0 {
+        let blocking = samples.iter().map(|s| s.submit_ms).fold(0.0, f64::max);
+        checks.push(SloCheck::at_most(
+            "execution_path_blocking_latency_ms",
+            blocking,
+            objectives.execution_path_blocking_latency_ms + profile.latency_ms.acknowledge as f64,
+        ));
+    }
+
+    let timing_out = |s: &&ReceiptSample| {
+        profile.expect == ProfileExpectation::FinalityTimeout && s.awaits_finality
+    };
+    let normal: Vec<&ReceiptSample> = samples.iter().filter(|s| !timing_out(s)).collect();
+    for (claim_type, objective, label) in [
+        (
+            ClaimType::ORDER_PLACED,
+            &objectives.order_placed_proof_completion,
+            "order_placed",
+        ),
+        (
+            ClaimType::TRADE_EXECUTED,
+            &objectives.trade_executed_proof_completion,
+            "trade_executed",
+        ),
+    ] {
+        let seconds: Vec<f64> = normal
+            .iter()
+            .filter(|s| s.claim_type == claim_type)
+            .map(|s| s.completion_ms / 1000.0)
+            .collect();
+        if let Some(p95) = percentile(&seconds, 95.0) {
+            checks.push(SloCheck::at_most(
+                format!("{}_p95_seconds", label),
+                p95,
+                objective.p95_seconds_max,
+            ));
+        }
+        if let (Some(p99), Some(max)) = (percentile(&seconds, 99.0), objective.p99_seconds_max) {
+            checks.push(SloCheck::at_most(
+                format!("{}_p99_seconds", label),
+                p99,
+                max,
+            ));
+        }
+    }
+    if !normal.is_empty() {
+        let non_provable = normal
+            .iter()
+            .filter(|s| s.status != ReceiptStatus::PROVED)
+            .count();
+        checks.push(SloCheck::at_most(
+            "non_provable_rate",
+            non_provable as f64 / normal.len() as f64,
+            objectives.non_provable_rate.normal_conditions_max,
+        ));
+    }
+
+    let timed_out: Vec<&ReceiptSample> = samples.iter().filter(timing_out).collect();
+    if !timed_out.is_empty() {
+        let missed = timed_out
+            .iter()
+            .filter(|s| s.reason != Some(NonProvableReason::FINALITY_TIMEOUT))
+            .count();
+        checks.push(SloCheck::at_most(
+            "finality_timeout_misses",
+            missed as f64,
+            0.0,
+        ));
+        let latency = &profile.latency_ms;
+        let latest = timed_out
+            .iter()
+            .map(|s| s.completion_ms)
+            .fold(0.0, f64::max);
+        checks.push(SloCheck::at_most(
+            "finality_timeout_enforced_ms",
+            latest,
+            (latency.acknowledge + latency.collect_evidence + profile.finality_timeout_ms) as f64
+                + TIMEOUT_SLACK_MS,
+        ));
+        checks.push(SloCheck {
+            name: "finality_waiters_peak".to_string(),
+            measured: backpressure.peak_finality_waiters as f64,
+            limit: timed_out.len() as f64,
+            passed: backpressure.peak_finality_waiters > 0
+                && backpressure.peak_finality_waiters <= timed_out.len(),
+        });
+    }
+    checks.push(SloCheck::at_most(
+        "finality_waiters_residual",
+        backpressure.residual_finality_waiters as f64,
+        0.0,
+    ));
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&values, 95.0), Some(19.0));
+        assert_eq!(percentile(&values, 99.0), Some(20.0));
+        assert_eq!(percentile(&values, 0.0), Some(1.0));
+        assert_eq!(percentile(&[], 95.0), None);
+    }
+}
@@ -0,0 +1,489 @@
+use crate::anchoring::{
+    effective_gas_price, AnchorPublication, AnchorPublisher, FeeBid, FeeEstimate, PublishAttempt,
+    SyntheticAnchorPublisher,
+};
+use crate::commitments::{evm_hash_words, hash_word};
+use crate::hashing::HashAlgorithm;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+pub const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+pub const DEFAULT_INCLUSION_TIMEOUT: Duration = Duration::from_secs(60);
+const FIRST_RECEIPT_POLL: Duration = Duration::from_millis(500);
+const MAX_RECEIPT_POLL: Duration = Duration::from_secs(8);
+
+// How anchor roots reach the chain: a funded EOA sends the transaction itself, or a smart account
+// submits an ERC-4337 user operation through a bundler, optionally sponsored by a paymaster.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum AnchorAccount {
+    Eoa {
+        address: String,
+    },
+    SmartAccount {
+        sender: String,
+        entry_point: String,
+        chain_id: u64,
+        paymaster: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserOperation {
+    pub sender: String,
+    pub nonce: u128,
+    pub init_code: String,
+    pub call_data: String,
+    pub call_gas_limit: u128,
+    pub verification_gas_limit: u128,
+    pub pre_verification_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub paymaster_and_data: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserOperationGas {
+    pub call_gas_limit: u128,
+    pub verification_gas_limit: u128,
+    pub pre_verification_gas: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserOperationReceipt {
+    pub user_op_hash: String,
+    pub transaction_hash: String,
+    pub success: bool,
+    pub actual_gas_used: u64,
+    pub actual_gas_cost: u128,
+}
+
+#[async_trait]
+pub trait Bundler: Send + Sync {
+    async fn fee_estimate(&self) -> Result<FeeEstimate>;
+    async fn nonce(&self, sender: &str, entry_point: &str) -> Result<u128>;
+    async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+        entry_point: &str,
+    ) -> Result<UserOperationGas>;
+    async fn send_user_operation(&self, op: &UserOperation, entry_point: &str) -> Result<String>;
+    async fn user_operation_receipt(
+        &self,
+        user_op_hash: &str,
+    ) -> Result<Option<UserOperationReceipt>>;
+}
+
+#[async_trait]
+pub trait Paymaster: Send + Sync {
+    async fn sponsor(&self, op: &UserOperation, entry_point: &str) -> Result<String>;
+}
+
+pub trait UserOperationSigner: Send + Sync {
+    fn sign(&self, user_op_hash: &str) -> String;
+}
+
+pub struct SmartAccountAnchorPublisher {
+    sender: String,
+    entry_point: String,
+    chain_id: u64,
+    anchor_contract: String,
+    bundler: Arc<dyn Bundler>,
+    paymaster: Option<Arc<dyn Paymaster>>,
+    signer: Arc<dyn UserOperationSigner>,
+    inclusion_timeout: Duration,
+    first_receipt_poll: Duration,
+}
+
+impl SmartAccountAnchorPublisher {
+    pub fn new(
+        sender: impl Into<String>,
+        entry_point: impl Into<String>,
+        chain_id: u64,
+        anchor_contract: impl Into<String>,
+        bundler: Arc<dyn Bundler>,
+        signer: Arc<dyn UserOperationSigner>,
+    ) -> Self {
+        Self {
+            sender: sender.into(),
+            entry_point: entry_point.into(),
+            chain_id,
+            anchor_contract: anchor_contract.into(),
+            bundler,
+            paymaster: None,
+            signer,
+            inclusion_timeout: DEFAULT_INCLUSION_TIMEOUT,
+            first_receipt_poll: FIRST_RECEIPT_POLL,
+        }
+    }
+
+    pub fn with_paymaster(mut self, paymaster: Arc<dyn Paymaster>) -> Self {
+        self.paymaster = Some(paymaster);
+        self
+    }
+
+    // How long a sent user operation may take to be bundled, and the first wait between receipt
+    // lookups, which doubles up to MAX_RECEIPT_POLL.
+    pub fn with_inclusion_timeout(mut self, timeout: Duration, first_poll: Duration) -> Self {
+        self.inclusion_timeout = timeout;
+        self.first_receipt_poll = first_poll.max(Duration::from_millis(1));
+        self
+    }
+
+    // The operation's receipt once bundled, or why none came before the inclusion timeout. A
+    // failed lookup is retried like a missing receipt, since the operation is already out.
+    async fn await_receipt(
+        &self,
+        user_op_hash: &str,
+    ) -> std::result::Result<UserOperationReceipt, String> {
+        let deadline = Instant::now() + self.inclusion_timeout;
+        let mut delay = self.first_receipt_poll;
+        let mut last_error = None;
+        loop {
+            match self.bundler.user_operation_receipt(user_op_hash).await {
+                Ok(Some(receipt)) => return Ok(receipt),
+                Ok(None) => {}
+                Err(err) => last_error = Some(err.to_string()),
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let mut reason = format!(
+                    "user operation {} not bundled within {} ms",
+                    user_op_hash,
+                    self.inclusion_timeout.as_millis()
+                );
+                if let Some(err) = last_error {
+                    reason.push_str(&format!(" (last receipt lookup failed: {})", err));
+                }
+                return Err(reason);
+            }
+            tokio::time::sleep(delay.min(remaining)).await;
+            delay = (delay * 2).min(MAX_RECEIPT_POLL);
+        }
+    }
+}
+
+#[async_trait]
+impl AnchorPublisher for SmartAccountAnchorPublisher {
+    async fn estimate_fees(&self) -> Result<FeeEstimate> {
+        self.bundler.fee_estimate().await
+    }
+
+    async fn publish_root(
+        &self,
+        root: &str,
+        leaf_count: usize,
+        bid: &FeeBid,
+    ) -> Result<PublishAttempt> {
+        let anchor_call = encode_anchor_root(root, leaf_count as u128);
+        let mut op = UserOperation {
+            sender: self.sender.clone(),
+            nonce: self.bundler.nonce(&self.sender, &self.entry_point).await?,
+            init_code: "0x".to_string(),
+            call_data: encode_execute(&self.anchor_contract, 0, &anchor_call)?,
+            max_fee_per_gas: bid.max_fee_per_gas_wei,
+            max_priority_fee_per_gas: bid.max_priority_fee_per_gas_wei,
+            paymaster_and_data: "0x".to_string(),
+            signature: "0x".to_string(),
+            ..UserOperation::default()
+        };
+        // Paymasters sign over the gas limits, so sponsorship happens after estimation.
+        let gas = self
+            .bundler
+            .estimate_user_operation_gas(&op, &self.entry_point)
+            .await
+            .context("bundler gas estimation failed")?;
+        op.call_gas_limit = gas.call_gas_limit;
+        op.verification_gas_limit = gas.verification_gas_limit;
+        op.pre_verification_gas = gas.pre_verification_gas;
+        if let Some(paymaster) = &self.paymaster {
+            op.paymaster_and_data = paymaster
+                .sponsor(&op, &self.entry_point)
+                .await
+                .context("paymaster declined to sponsor the anchor user operation")?;
+        }
+        op.signature =
+            self.signer
+                .sign(&user_operation_hash(&op, &self.entry_point, self.chain_id)?);
+
+        let user_op_hash = self
+            .bundler
+            .send_user_operation(&op, &self.entry_point)
+            .await?;
+        Ok(match self.await_receipt(&user_op_hash).await {
+            Ok(receipt) if receipt.success => PublishAttempt::Included(AnchorPublication {
+                tx_ref: receipt.transaction_hash,
+                gas_used: receipt.actual_gas_used,
+                fee_wei: receipt.actual_gas_cost,
+                user_op_hash: Some(user_op_hash),
+                sponsored: self.paymaster.is_some(),
+            }),
+            Ok(_) => {
+                PublishAttempt::NotIncluded(format!("user operation {} reverted", user_op_hash))
+            }
+            Err(reason) => PublishAttempt::NotIncluded(reason),
+        })
+    }
+}
+
+// ERC-4337 v0.6 userOpHash: keccak256(abi.encode(keccak256(pack(userOp)), entryPoint, chainId)).
+pub fn user_operation_hash(op: &UserOperation, entry_point: &str, chain_id: u64) -> Result<String> {
+    let packed = evm_hash_words(&[
+        address_word(&op.sender)?,
+        uint_word(op.nonce),
+        bytes_hash_word(&op.init_code)?,
+        bytes_hash_word(&op.call_data)?,
+        uint_word(op.call_gas_limit),
+        uint_word(op.verification_gas_limit),
+        uint_word(op.pre_verification_gas),
+        uint_word(op.max_fee_per_gas),
+        uint_word(op.max_priority_fee_per_gas),
+        bytes_hash_word(&op.paymaster_and_data)?,
+    ]);
+    Ok(evm_hash_words(&[
+        hash_word(&packed),
+        address_word(entry_point)?,
+        uint_word(u128::from(chain_id)),
+    ]))
+}
+
+pub fn encode_anchor_root(root: &str, leaf_count: u128) -> Vec<u8> {
+    let mut data = selector("anchorRoot(bytes32,uint256)").to_vec();
+    data.extend_from_slice(&hash_word(root));
+    data.extend_from_slice(&uint_word(leaf_count));
+    data
+}
+
+pub fn encode_execute(target: &str, value: u128, call: &[u8]) -> Result<String> {
+    let mut data = selector("execute(address,uint256,bytes)").to_vec();
+    data.extend_from_slice(&address_word(target)?);
+    data.extend_from_slice(&uint_word(value));
+    data.extend_from_slice(&uint_word(0x60));
+    data.extend_from_slice(&uint_word(call.len() as u128));
+    data.extend_from_slice(call);
+    data.resize(data.len() + (32 - call.len() % 32) % 32, 0);
+    Ok(format!("0x{}", hex::encode(data)))
+}
+
+fn selector(signature: &str) -> [u8; 4] {
+    let digest = HashAlgorithm::Keccak256.digest_bytes(signature.as_bytes());
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+fn uint_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn address_word(address: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))
+        .with_context(|| format!("invalid address {}", address))?;
+    if bytes.len() != 20 {
+        return Err(anyhow!("address {} must be 20 bytes", address));
+    }
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&bytes);
+    Ok(word)
+}
+
+fn bytes_hash_word(data: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(data.trim_start_matches("0x"))
+        .with_context(|| format!("invalid hex bytes {}", data))?;
+    Ok(HashAlgorithm::Keccak256.digest_bytes(&bytes))
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntheticUserOperationSigner {
+    pub key_id: String,
+}
+
+impl UserOperationSigner for SyntheticUserOperationSigner {
+    fn sign(&self, user_op_hash: &str) -> String {
+        HashAlgorithm::Keccak256.hash_json(&serde_json::json!({
+            "key_id": self.key_id,
+            "user_op_hash": user_op_hash
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyntheticPaymaster;
+
+#[async_trait]
+impl Paymaster for SyntheticPaymaster {
+    async fn sponsor(&self, op: &UserOperation, _entry_point: &str) -> Result<String> {
+        Ok(format!(
+            "0x{}{}",
+            "00".repeat(20),
+            op.sender.trim_start_matches("0x")
+        ))
+    }
+}
+
+// Accepts every user operation and bundles it immediately at a fixed base fee. Operations without
+// a paymaster are rejected unless the sender has a deposit, mirroring EntryPoint prefund checks.
+#[derive(Default)]
+pub struct SyntheticBundler {
+    pub chain_id: u64,
+    pub base_fee_wei: u128,
+    pub funded_senders: Vec<String>,
+    state: Mutex<SyntheticBundlerState>,
+}
+
+#[derive(Default)]
+struct SyntheticBundlerState {
+    nonces: HashMap<String, u128>,
+    receipts: HashMap<String, UserOperationReceipt>,
+}
+
+impl SyntheticBundler {
+    pub fn new(chain_id: u64, base_fee_wei: u128) -> Self {
+        Self {
+            chain_id,
+            base_fee_wei,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_funded_sender(mut self, sender: impl Into<String>) -> Self {
+        self.funded_senders.push(sender.into().to_ascii_lowercase());
+        self
+    }
+}
+
+#[async_trait]
+impl Bundler for SyntheticBundler {
+    async fn fee_estimate(&self) -> Result<FeeEstimate> {
+        Ok(FeeEstimate {
+            base_fee_wei: self.base_fee_wei,
+            priority_fee_wei: 100_000_000,
+        })
+    }
+
+    async fn nonce(&self, sender: &str, _entry_point: &str) -> Result<u128> {
+        Ok(self
+            .state
+            .lock()
+            .await
+            .nonces
+            .get(&sender.to_ascii_lowercase())
+            .copied()
+            .unwrap_or(0))
+    }
+
+    async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+        _entry_point: &str,
+    ) -> Result<UserOperationGas> {
+        Ok(UserOperationGas {
+            call_gas_limit: 40_000 + 16 * (op.call_data.len() as u128 / 2),
+            verification_gas_limit: 70_000,
+            pre_verification_gas: 45_000,
+        })
+    }
+
+    async fn send_user_operation(&self, op: &UserOperation, entry_point: &str) -> Result<String> {
+        let sender = op.sender.to_ascii_lowercase();
+        if op.paymaster_and_data == "0x" && !self.funded_senders.contains(&sender) {
+            return Err(anyhow!(
+                "AA21 didn't pay prefund: sender {} has no deposit and no paymaster",
+                op.sender
+            ));
+        }
+        let user_op_hash = user_operation_hash(op, entry_point, self.chain_id)?;
+        let mut state = self.state.lock().await;
+        *state.nonces.entry(sender).or_insert(0) += 1;
+        let gas_used =
+            (op.call_gas_limit + op.verification_gas_limit + op.pre_verification_gas) * 4 / 5;
+        let gas_price = effective_gas_price(
+            self.base_fee_wei,
+            &FeeBid {
+                max_fee_per_gas_wei: op.max_fee_per_gas,
+                max_priority_fee_per_gas_wei: op.max_priority_fee_per_gas,
+            },
+        );
+        state.receipts.insert(
+            user_op_hash.clone(),
+            UserOperationReceipt {
+                user_op_hash: user_op_hash.clone(),
+                transaction_hash: evm_hash_words(&[hash_word(&user_op_hash)]),
+                success: true,
+                actual_gas_used: gas_used as u64,
+                actual_gas_cost: gas_used * gas_price,
+            },
+        );
+        Ok(user_op_hash)
+    }
+
+    async fn user_operation_receipt(
+        &self,
+        user_op_hash: &str,
+    ) -> Result<Option<UserOperationReceipt>> {
+        Ok(self.state.lock().await.receipts.get(user_op_hash).cloned())
+    }
+}
+
+pub fn build_mvp_anchor_publisher(
+    account: &AnchorAccount,
+    anchor_contract: &str,
+) -> Arc<dyn AnchorPublisher> {
+    match account {
+        AnchorAccount::Eoa { .. } => Arc::new(SyntheticAnchorPublisher),
+        AnchorAccount::SmartAccount {
+            sender,
+            entry_point,
+            chain_id,
+            paymaster,
+        } => {
+            let mut bundler = SyntheticBundler::new(*chain_id, 1_000_000_000);
+            if paymaster.is_none() {
+                bundler = bundler.with_funded_sender(sender.clone());
+            }
+            let publisher = SmartAccountAnchorPublisher::new(
+                sender.clone(),
+                entry_point.clone(),
+                *chain_id,
+                anchor_contract,
+                Arc::new(bundler),
+                Arc::new(SyntheticUserOperationSigner {
+                    key_id: format!("{}-owner", sender),
+                }),
+            );
+            match paymaster {
+                Some(_) => Arc::new(publisher.with_paymaster(Arc::new(SyntheticPaymaster))),
+                None => Arc::new(publisher),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn execute_calldata_is_abi_encoded() {
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+        let call = encode_anchor_root(&format!("0x{}", "ab".repeat(32)), 3);
+        assert_eq!(call.len(), 4 + 64);
+        let encoded =
+            encode_execute("0x000000000000000000000000000000000000dEaD", 0, &call).expect("encode");
+        let bytes = hex::decode(encoded.trim_start_matches("0x")).unwrap();
+        // selector + 3 head words + length word + call padded to a word boundary
+        assert_eq!(bytes.len(), 4 + 32 * 4 + 96);
+        assert_eq!(&bytes[4..4 + 30], &[0u8; 30]);
+        assert_eq!(&bytes[4 + 30..4 + 32], &[0xde, 0xad]);
+        assert_eq!(bytes[4 + 32 * 3 + 31] as usize, call.len());
+    }
+}
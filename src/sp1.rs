@@ -0,0 +1,130 @@
+use crate::hashing::HashAlgorithm;
+use crate::models::{hash_json, ProofBackend, ProofMetadata, VerificationMode};
+use crate::prover::{ProofWitness, ProverBackend, ProverUsage};
+use crate::zkvm_host::run_host;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+
+pub const SP1_CIRCUIT_ID: &str = "trade-receipt-sp1";
+pub const SP1_CIRCUIT_VERSION: &str = "v0.2.0";
+
+#[derive(Debug, Serialize)]
+struct HostRequest {
+    public_inputs: Value,
+    witness: ProofWitness,
+    proof_path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct HostResponse {
+    proof_path: PathBuf,
+    vkey_hash: String,
+    public_values: Value,
+    cycles: u64,
+}
+
+// Proves receipts with the guest program in sp1/program. The SP1 SDK is not a dependency of this
+// crate; proving runs in the host binary built from sp1/script, one process per proof.
+#[derive(Debug, Clone)]
+pub struct Sp1Prover {
+    host: PathBuf,
+    proof_dir: PathBuf,
+}
+
+impl Sp1Prover {
+    pub fn new(host: impl Into<PathBuf>, proof_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            host: host.into(),
+            proof_dir: proof_dir.into(),
+        }
+    }
+
+    // ZKPUTER_SP1_HOST names the host binary; proofs go to ZKPUTER_SP1_PROOF_DIR, or a temp dir.
+    pub fn from_env() -> Result<Self> {
+        let host = std::env::var("ZKPUTER_SP1_HOST").context("ZKPUTER_SP1_HOST is not set")?;
+        let proof_dir = std::env::var("ZKPUTER_SP1_PROOF_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir().join("zkputer-sp1"));
+        Ok(Self::new(host, proof_dir))
+    }
+}
+
+#[async_trait]
+impl ProverBackend for Sp1Prover {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::SP1
+    }
+
+    async fn prove(&self, _public_inputs: &Value) -> Result<ProofMetadata> {
+        bail!("SP1 proving needs the claim and evidence preimages; use prove_with_witness")
+    }
+
+    async fn available(&self) -> bool {
+        self.host.is_file()
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        if witness.hash_algorithm != HashAlgorithm::Sha256 {
+            bail!(
+                "SP1 guest opens sha256 commitments only, not {}",
+                witness.hash_algorithm.id()
+            );
+        }
+        if witness.claim_preimage.is_none() || witness.evidence_preimage.is_none() {
+            bail!("SP1 guest opens JSON commitments only");
+        }
+        let public_inputs_hash = hash_json(public_inputs);
+        let request = HostRequest {
+            public_inputs: public_inputs.clone(),
+            witness: witness.clone(),
+            proof_path: self.proof_dir.join(format!(
+                "{}.bin",
+                public_inputs_hash.trim_start_matches("0x")
+            )),
+        };
+        std::fs::create_dir_all(&self.proof_dir)
+            .with_context(|| format!("failed to create {}", self.proof_dir.display()))?;
+        let host = self.host.clone();
+        let response: HostResponse =
+            tokio::task::spawn_blocking(move || run_host(&host, "SP1", &request)).await??;
+        // The guest commits the public inputs it checked; they must be the ones we asked about.
+        if hash_json(&response.public_values) != public_inputs_hash {
+            bail!("SP1 proof commits to different public inputs than requested");
+        }
+        let metadata = ProofMetadata {
+            backend: ProofBackend::SP1,
+            circuit_id: SP1_CIRCUIT_ID.to_string(),
+            circuit_version: SP1_CIRCUIT_VERSION.to_string(),
+            verifier_key_id: format!(
+                "sp1-vk-{}",
+                response
+                    .vkey_hash
+                    .trim_start_matches("0x")
+                    .chars()
+                    .take(16)
+                    .collect::<String>()
+            ),
+            verifier_key_hash: response.vkey_hash,
+            public_inputs_hash,
+            verification_mode: VerificationMode::OFFCHAIN,
+            proof_artifact_ref: Some(format!("file://{}", response.proof_path.display())),
+            anchored_root_ref: None,
+            anchor_inclusion: None,
+            fallback_from: vec![],
+        };
+        Ok((
+            metadata,
+            ProverUsage {
+                cycles: response.cycles,
+                fee_usd: 0.0,
+            },
+        ))
+    }
+}
@@ -0,0 +1,62 @@
+use crate::models::{now_iso, ClaimType, CostRecord, ReceiptStatus, Venue, ZKReceipt};
+use crate::proof_queue::ProofQueueStats;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsGroup {
+    pub venue: Venue,
+    pub claim_type: ClaimType,
+    pub receipts: usize,
+    pub by_status: HashMap<ReceiptStatus, usize>,
+    pub cost: CostRecord,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptStats {
+    pub generated_at: String,
+    pub receipts: usize,
+    pub cost: CostRecord,
+    pub groups: Vec<StatsGroup>,
+    #[serde(default)]
+    pub proof_queue: ProofQueueStats,
+}
+
+pub fn build_stats(receipts: &[ZKReceipt], costs: &HashMap<String, CostRecord>) -> ReceiptStats {
+    let mut groups: Vec<StatsGroup> = Vec::new();
+    let mut total = CostRecord::default();
+    for receipt in receipts {
+        let (venue, claim_type) = (receipt.subject.venue, receipt.claim.r#type);
+        let idx = match groups
+            .iter()
+            .position(|g| g.venue == venue && g.claim_type == claim_type)
+        {
+            Some(idx) => idx,
+            None => {
+                groups.push(StatsGroup {
+                    venue,
+                    claim_type,
+                    receipts: 0,
+                    by_status: HashMap::new(),
+                    cost: CostRecord::default(),
+                });
+                groups.len() - 1
+            }
+        };
+        let group = &mut groups[idx];
+        group.receipts += 1;
+        *group.by_status.entry(receipt.status).or_insert(0) += 1;
+        if let Some(cost) = costs.get(&receipt.receipt_id) {
+            group.cost.add(cost);
+            total.add(cost);
+        }
+    }
+    groups.sort_by_key(|g| (format!("{:?}", g.venue), format!("{:?}", g.claim_type)));
+    ReceiptStats {
+        generated_at: now_iso(),
+        receipts: receipts.len(),
+        cost: total,
+        groups,
+        proof_queue: ProofQueueStats::default(),
+    }
+}
@@ -0,0 +1,605 @@
+use crate::events::{fold_events, fold_events_at, ReceiptEvent};
+use crate::leader::{now_ms, LeaderElection, Lease, WriteFenced};
+use crate::models::{
+    ClaimType, CostRecord, ProofRequest, ReceiptLifecycle, ReceiptStatus, Venue, ZKReceipt,
+};
+use crate::persistence::{ChangeCursor, ReceiptBackend, SqliteReceiptBackend};
+use crate::screening::ScreeningDecision;
+use crate::search::SearchIndex;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReceiptQuery {
+    pub venue: Option<Venue>,
+    pub claim_type: Option<ClaimType>,
+    pub status: Option<ReceiptStatus>,
+    pub text: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl ReceiptQuery {
+    fn matches(&self, receipt: &ZKReceipt) -> bool {
+        self.venue.is_none_or(|v| v == receipt.subject.venue)
+            && self.claim_type.is_none_or(|c| c == receipt.claim.r#type)
+            && self.status.is_none_or(|s| s == receipt.status)
+    }
+}
+
+pub struct ReceiptWatch {
+    updates: watch::Receiver<ZKReceipt>,
+    started: bool,
+    finished: bool,
+}
+
+impl ReceiptWatch {
+    pub async fn next(&mut self) -> Option<ZKReceipt> {
+        if self.finished {
+            return None;
+        }
+        if self.started && self.updates.changed().await.is_err() {
+            self.finished = true;
+            return None;
+        }
+        self.started = true;
+        let snapshot = self.updates.borrow_and_update().clone();
+        self.finished = snapshot.status.is_terminal();
+        Some(snapshot)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ReceiptStore {
+    receipts: Arc<Mutex<HashMap<String, watch::Sender<ZKReceipt>>>>,
+    requests: Arc<Mutex<HashMap<String, ProofRequest>>>,
+    costs: Arc<Mutex<HashMap<String, CostRecord>>>,
+    screenings: Arc<Mutex<HashMap<String, Vec<ScreeningDecision>>>>,
+    search: Option<Arc<Mutex<SearchIndex>>>,
+    backend: Option<Arc<dyn ReceiptBackend>>,
+    persist_error: Arc<std::sync::Mutex<Option<String>>>,
+    // Leases for stores without a backend, shared by the engines holding clones of the store.
+    leases: Arc<std::sync::Mutex<HashMap<String, Lease>>>,
+    // Receipt writes through this clone need its election's current tenure of the lease.
+    fence: Option<LeaderElection>,
+    // How far `refresh` has read the backend's changes.
+    refresh_cursor: Arc<std::sync::Mutex<ChangeCursor>>,
+    dropped_write: Arc<std::sync::Mutex<Option<String>>>,
+}
+
+impl ReceiptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Reloads everything the backend holds, then writes every later change through to it. Reads
+    // are still served from memory.
+    pub fn open(backend: Arc<dyn ReceiptBackend>) -> Result<Self> {
+        let (records, cursor) = backend.load_changes(ChangeCursor::default())?;
+        let mut receipts = HashMap::new();
+        for receipt in records.receipts {
+            receipts.insert(receipt.receipt_id.clone(), watch::channel(receipt).0);
+        }
+        let mut screenings: HashMap<String, Vec<ScreeningDecision>> = HashMap::new();
+        for decision in records.screenings {
+            screenings
+                .entry(decision.receipt_id.clone())
+                .or_default()
+                .push(decision);
+        }
+        Ok(Self {
+            receipts: Arc::new(Mutex::new(receipts)),
+            requests: Arc::new(Mutex::new(records.requests.into_iter().collect())),
+            costs: Arc::new(Mutex::new(records.costs.into_iter().collect())),
+            screenings: Arc::new(Mutex::new(screenings)),
+            search: None,
+            backend: Some(backend),
+            persist_error: Arc::default(),
+            leases: Arc::default(),
+            fence: None,
+            refresh_cursor: Arc::new(std::sync::Mutex::new(cursor)),
+            dropped_write: Arc::default(),
+        })
+    }
+
+    // Receipt writes through this clone succeed only while `election` leads, and only while the
+    // store still holds the tenure it won, so a leader that was replaced without noticing cannot
+    // overwrite its successor.
+    pub fn with_fence(mut self, election: LeaderElection) -> Self {
+        self.fence = Some(election);
+        self
+    }
+
+    // Picks up what other engines wrote to the backend since the last refresh: receipts at a
+    // later revision than the copy in memory, and their requests, costs and screenings. Only what
+    // changed is read back. Watchers see refreshed receipts like any other write. Returns how
+    // many receipts changed.
+    pub async fn refresh(&self) -> Result<usize> {
+        let Some(backend) = &self.backend else {
+            return Ok(0);
+        };
+        let records = {
+            let mut cursor = self
+                .refresh_cursor
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let (records, next) = backend.load_changes(*cursor)?;
+            *cursor = next;
+            records
+        };
+        let mut changed = vec![];
+        {
+            let mut receipts = self.receipts.lock().await;
+            for receipt in records.receipts {
+                match receipts.get(&receipt.receipt_id) {
+                    Some(sender) if sender.borrow().revision >= receipt.revision => {}
+                    Some(sender) => {
+                        sender.send_replace(receipt.clone());
+                        changed.push(receipt);
+                    }
+                    None => {
+                        receipts.insert(
+                            receipt.receipt_id.clone(),
+                            watch::channel(receipt.clone()).0,
+                        );
+                        changed.push(receipt);
+                    }
+                }
+            }
+        }
+        self.requests.lock().await.extend(records.requests);
+        self.costs.lock().await.extend(records.costs);
+        {
+            let mut screenings = self.screenings.lock().await;
+            for decision in records.screenings {
+                let decisions = screenings.entry(decision.receipt_id.clone()).or_default();
+                if !decisions
+                    .iter()
+                    .any(|known| known.decision_hash == decision.decision_hash)
+                {
+                    decisions.push(decision);
+                }
+            }
+        }
+        if let Some(search) = &self.search {
+            let mut index = search.lock().await;
+            for receipt in &changed {
+                index.index(receipt);
+            }
+        }
+        Ok(changed.len())
+    }
+
+    // Claims the named lease for `holder` for `ttl`, in the backend when there is one so engines
+    // sharing it agree, and returns whoever holds it afterwards.
+    pub fn acquire_lease(&self, name: &str, holder: &str, ttl: Duration) -> Result<Lease> {
+        let now = now_ms();
+        if let Some(backend) = &self.backend {
+            return backend.acquire_lease(name, holder, ttl, now);
+        }
+        let mut leases = self
+            .leases
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let lease = Lease::claim(leases.get(name), name, holder, ttl, now);
+        leases.insert(name.to_string(), lease.clone());
+        Ok(lease)
+    }
+
+    pub fn release_lease(&self, name: &str, holder: &str) -> Result<()> {
+        if let Some(backend) = &self.backend {
+            return backend.release_lease(name, holder);
+        }
+        let mut leases = self
+            .leases
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(lease) = leases.get_mut(name).filter(|lease| lease.holder == holder) {
+            lease.expires_at_ms = 0;
+        }
+        Ok(())
+    }
+
+    pub fn open_sqlite(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open(Arc::new(SqliteReceiptBackend::open(path)?))
+    }
+
+    // Keeps every receipt write as an immutable, hash-chained event instead of replacing a row.
+    pub fn open_event_sourced(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open(Arc::new(SqliteReceiptBackend::open_event_sourced(path)?))
+    }
+
+    pub fn with_search_index(mut self) -> Self {
+        let mut index = SearchIndex::new();
+        if let Ok(receipts) = self.receipts.try_lock() {
+            for sender in receipts.values() {
+                index.index(&sender.borrow());
+            }
+        }
+        self.search = Some(Arc::new(Mutex::new(index)));
+        self
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    // The most recent failed write-through, cleared by the next successful one.
+    pub fn persistence_error(&self) -> Option<String> {
+        self.persist_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    // The most recent pipeline write given up on because the stored copy had moved on.
+    pub fn dropped_write(&self) -> Option<String> {
+        self.dropped_write
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn record_dropped_write(&self, receipt_id: &str, err: &anyhow::Error) {
+        *self
+            .dropped_write
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            Some(format!("receipt {}: {:#}", receipt_id, err));
+    }
+
+    // Receipts held when the store was built; only meaningful before it is shared.
+    pub(crate) fn snapshot_now(&self) -> Vec<ZKReceipt> {
+        self.receipts
+            .try_lock()
+            .map(|receipts| receipts.values().map(|s| s.borrow().clone()).collect())
+            .unwrap_or_default()
+    }
+
+    // The tenure receipt writes are fenced on; an error when the fencing election does not lead.
+    fn fence(&self) -> Result<Option<Lease>> {
+        let Some(election) = &self.fence else {
+            return Ok(None);
+        };
+        match election
+            .lease()
+            .filter(|lease| lease.is_held_by(election.holder(), now_ms()))
+        {
+            Some(lease) => Ok(Some(lease)),
+            None => Err(WriteFenced(format!(
+                "instance {} does not hold the {} lease",
+                election.holder(),
+                election.lease_name()
+            ))
+            .into()),
+        }
+    }
+
+    // Writes one receipt revision, checking the fence against the backend's lease in the same
+    // transaction, or against the shared leases without a backend.
+    fn persist_receipt(&self, receipt: &ZKReceipt, expected_revision: u64) -> Result<()> {
+        let fence = self.fence()?;
+        if self.backend.is_none() {
+            if let Some(fence) = &fence {
+                let leases = self
+                    .leases
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                fence.check_fence(leases.get(&fence.name))?;
+            }
+        }
+        self.persist(|b| b.compare_and_swap_receipt(receipt, expected_revision, fence.as_ref()))
+    }
+
+    fn persist(&self, write: impl FnOnce(&dyn ReceiptBackend) -> Result<()>) -> Result<()> {
+        let Some(backend) = &self.backend else {
+            return Ok(());
+        };
+        let result = write(backend.as_ref());
+        *self
+            .persist_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            result.as_ref().err().map(|err| format!("{:#}", err));
+        result
+    }
+
+    pub fn has_search_index(&self) -> bool {
+        self.search.is_some()
+    }
+
+    // A fenced write is dropped, memory included, and reported like a failed write-through.
+    pub async fn insert(&self, mut receipt: ZKReceipt) {
+        let mut receipts = self.receipts.lock().await;
+        let current = receipts
+            .get(&receipt.receipt_id)
+            .map(|sender| sender.borrow().revision);
+        receipt.revision = current.unwrap_or(0) + 1;
+        if let Err(err) = self.persist_receipt(&receipt, current.unwrap_or(0)) {
+            if err.downcast_ref::<WriteFenced>().is_some() {
+                *self
+                    .persist_error
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(format!("{:#}", err));
+                return;
+            }
+        }
+        if let Some(search) = &self.search {
+            search.lock().await.index(&receipt);
+        }
+        match receipts.get(&receipt.receipt_id) {
+            Some(sender) => {
+                sender.send_replace(receipt);
+            }
+            None => {
+                let receipt_id = receipt.receipt_id.clone();
+                receipts.insert(receipt_id, watch::channel(receipt).0);
+            }
+        }
+    }
+
+    // Adds a new receipt, which must start in one of the lifecycle's initial statuses.
+    pub async fn create(&self, receipt: ZKReceipt) -> Result<()> {
+        ReceiptLifecycle::standard().check_initial(receipt.status)?;
+        self.fence()?;
+        if self.receipts.lock().await.contains_key(&receipt.receipt_id) {
+            bail!("receipt {} already exists", receipt.receipt_id);
+        }
+        self.insert(receipt).await;
+        Ok(())
+    }
+
+    // Writes `receipt` only if the stored copy is still at `receipt.revision`, i.e. nobody else
+    // wrote it since the caller read it, and only if the lifecycle allows the status change.
+    // Returns the stored receipt with its new revision.
+    pub async fn compare_and_swap(&self, mut receipt: ZKReceipt) -> Result<ZKReceipt> {
+        {
+            let receipts = self.receipts.lock().await;
+            let sender = receipts
+                .get(&receipt.receipt_id)
+                .ok_or_else(|| anyhow!("unknown receipt id: {}", receipt.receipt_id))?;
+            let (current, status) = {
+                let stored = sender.borrow();
+                (stored.revision, stored.status)
+            };
+            if current != receipt.revision {
+                bail!(
+                    "receipt {} was modified concurrently: expected revision {}, found {}",
+                    receipt.receipt_id,
+                    receipt.revision,
+                    current
+                );
+            }
+            ReceiptLifecycle::standard()
+                .check(status, receipt.status)
+                .map_err(|err| anyhow!("receipt {}: {}", receipt.receipt_id, err))?;
+            receipt.revision = current + 1;
+            // The backend checks the revision again, against what other engines sharing it wrote.
+            self.persist_receipt(&receipt, current)?;
+            sender.send_replace(receipt.clone());
+        }
+        if let Some(search) = &self.search {
+            search.lock().await.index(&receipt);
+        }
+        Ok(receipt)
+    }
+
+    // The receipt's events, oldest first, after checking that the hash chain is intact.
+    pub async fn history(&self, receipt_id: &str) -> Result<Vec<ReceiptEvent>> {
+        let backend = self
+            .backend
+            .as_ref()
+            .ok_or_else(|| anyhow!("receipt history needs an event-sourced store"))?;
+        let events = backend.history(receipt_id)?;
+        if events.is_empty() {
+            bail!("unknown receipt id: {}", receipt_id);
+        }
+        fold_events(&events)?;
+        Ok(events)
+    }
+
+    // The receipt as it stood at the RFC 3339 time `at`; None if it did not exist yet.
+    pub async fn receipt_at(&self, receipt_id: &str, at: &str) -> Result<Option<ZKReceipt>> {
+        fold_events_at(&self.history(receipt_id).await?, at)
+    }
+
+    pub async fn get(&self, receipt_id: &str) -> Option<ZKReceipt> {
+        self.receipts
+            .lock()
+            .await
+            .get(receipt_id)
+            .map(|s| s.borrow().clone())
+    }
+
+    pub async fn find_by_receipt_hash(&self, receipt_hash: &str) -> Option<ZKReceipt> {
+        self.receipts.lock().await.values().find_map(|s| {
+            let receipt = s.borrow();
+            (receipt.integrity.receipt_hash == receipt_hash).then(|| receipt.clone())
+        })
+    }
+
+    pub async fn record_request(&self, receipt_id: &str, request: ProofRequest) {
+        self.persist(|b| b.put_request(receipt_id, &request)).ok();
+        self.requests
+            .lock()
+            .await
+            .insert(receipt_id.to_string(), request);
+    }
+
+    pub async fn request(&self, receipt_id: &str) -> Option<ProofRequest> {
+        self.requests.lock().await.get(receipt_id).cloned()
+    }
+
+    pub async fn record_cost(&self, receipt_id: &str, cost: &CostRecord) {
+        let mut costs = self.costs.lock().await;
+        let total = costs.entry(receipt_id.to_string()).or_default();
+        total.add(cost);
+        self.persist(|b| b.put_cost(receipt_id, total)).ok();
+    }
+
+    pub async fn cost(&self, receipt_id: &str) -> Option<CostRecord> {
+        self.costs.lock().await.get(receipt_id).cloned()
+    }
+
+    pub async fn costs(&self) -> HashMap<String, CostRecord> {
+        self.costs.lock().await.clone()
+    }
+
+    pub async fn record_screening(&self, decision: ScreeningDecision) {
+        self.persist(|b| b.put_screening(&decision)).ok();
+        self.screenings
+            .lock()
+            .await
+            .entry(decision.receipt_id.clone())
+            .or_default()
+            .push(decision);
+    }
+
+    pub async fn screenings(&self, receipt_id: &str) -> Vec<ScreeningDecision> {
+        self.screenings
+            .lock()
+            .await
+            .get(receipt_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn subscribe(&self, receipt_id: &str) -> Option<watch::Receiver<ZKReceipt>> {
+        self.receipts
+            .lock()
+            .await
+            .get(receipt_id)
+            .map(|s| s.subscribe())
+    }
+
+    pub async fn watch(&self, receipt_id: &str) -> Option<ReceiptWatch> {
+        self.subscribe(receipt_id)
+            .await
+            .map(|updates| ReceiptWatch {
+                updates,
+                started: false,
+                finished: false,
+            })
+    }
+
+    pub async fn status_counts(&self) -> HashMap<ReceiptStatus, usize> {
+        let receipts = self.receipts.lock().await;
+        let mut counts = HashMap::new();
+        for sender in receipts.values() {
+            *counts.entry(sender.borrow().status).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub async fn list(&self, query: &ReceiptQuery) -> Result<Vec<ZKReceipt>> {
+        let text_matches = self.text_matches(query).await?;
+        let receipts = self.receipts.lock().await;
+        let mut matched: Vec<ZKReceipt> = receipts
+            .values()
+            .map(|s| s.borrow().clone())
+            .filter(|r| query.matches(r))
+            .filter(|r| {
+                text_matches
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&r.receipt_id))
+            })
+            .collect();
+        matched.sort_by(|a, b| {
+            b.timing
+                .created_at
+                .cmp(&a.timing.created_at)
+                .then_with(|| a.receipt_id.cmp(&b.receipt_id))
+        });
+        if let Some(limit) = query.limit {
+            matched.truncate(limit);
+        }
+        Ok(matched)
+    }
+
+    // Up to `limit` matching receipts in export order, oldest first and strictly after `after`.
+    // Only the page itself is cloned, so walking a large store page by page stays flat in memory.
+    // `query.limit` is ignored; callers paging through an export count for themselves.
+    pub async fn page(
+        &self,
+        query: &ReceiptQuery,
+        after: Option<&ExportCursor>,
+        limit: usize,
+    ) -> Result<Vec<ZKReceipt>> {
+        let text_matches = self.text_matches(query).await?;
+        let receipts = self.receipts.lock().await;
+        let mut keys: Vec<ExportCursor> = receipts
+            .values()
+            .filter_map(|s| {
+                let receipt = s.borrow();
+                let matched = query.matches(&receipt)
+                    && text_matches
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(&receipt.receipt_id));
+                matched.then(|| ExportCursor::of(&receipt))
+            })
+            .filter(|key| after.is_none_or(|after| key > after))
+            .collect();
+        keys.sort();
+        keys.truncate(limit);
+        Ok(keys
+            .iter()
+            .filter_map(|key| receipts.get(&key.receipt_id).map(|s| s.borrow().clone()))
+            .collect())
+    }
+
+    async fn text_matches(&self, query: &ReceiptQuery) -> Result<Option<HashSet<String>>> {
+        match query.text.as_deref().filter(|t| !t.trim().is_empty()) {
+            Some(text) => {
+                let search = self.search.as_ref().ok_or_else(|| {
+                    anyhow!("full-text search requires the receipt search index to be enabled")
+                })?;
+                Ok(Some(search.lock().await.search(text)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+// Where an export stands: receipts are exported in (created_at, receipt_id) order, so the last
+// receipt sent is enough to resume after it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ExportCursor {
+    pub created_at: String,
+    pub receipt_id: String,
+}
+
+impl ExportCursor {
+    pub fn of(receipt: &ZKReceipt) -> Self {
+        Self {
+            created_at: receipt.timing.created_at.clone(),
+            receipt_id: receipt.receipt_id.clone(),
+        }
+    }
+
+    // Opaque to clients; receipt ids never contain '|'.
+    pub fn encode(&self) -> String {
+        format!("{}|{}", self.created_at, self.receipt_id)
+    }
+
+    pub fn decode(raw: &str) -> Result<Self> {
+        let (created_at, receipt_id) = raw
+            .split_once('|')
+            .ok_or_else(|| anyhow!("malformed export cursor: {}", raw))?;
+        Ok(Self {
+            created_at: created_at.to_string(),
+            receipt_id: receipt_id.to_string(),
+        })
+    }
+}
+
+// ZKPUTER_STORE_PATH selects a SQLite database; without it receipts live in memory only.
+// ZKPUTER_STORE_MODE=event_sourced keeps every receipt write as an event in that database.
+pub fn receipt_store_from_env() -> Result<ReceiptStore> {
+    crate::config::StoreConfig::default()
+        .with_env_overrides(|name| std::env::var(name).ok())?
+        .open()
+}
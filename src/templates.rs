@@ -1,10 +1,14 @@
-use crate::models::{ClaimType, ProofRequest, Venue};
+use crate::models::{
+    BalanceRange, BalanceSnapshot, ClaimType, CorrectionKind, EvidenceBundle, ExecutionAck,
+    LocalizedStatement, PositionClosure, ProofRequest, StatementLocale, Venue, PNL_BLINDING_FIELD,
+};
 use anyhow::{anyhow, Result};
 use serde::Serialize;
 use serde_json::{Map, Value};
 
 pub const TEMPLATE_ORDER_PLACEMENT_VERIFICATION: &str = "order_placement_verification";
 pub const TEMPLATE_TRADE_EXECUTION_VERIFICATION: &str = "trade_execution_verification";
+pub const TEMPLATE_BEST_EXECUTION_VERIFICATION: &str = "best_execution_verification";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct VerificationTemplate {
@@ -36,6 +40,23 @@ pub fn list_verification_templates() -> Vec<VerificationTemplate> {
             required_fields: &["venue", "account_ref", "order_ref", "execution_ref"],
             optional_fields: &["wait_for_result", "wait_timeout_ms", "fill_qty", "fill_price", "notes"],
         },
+        VerificationTemplate {
+            template_id: TEMPLATE_BEST_EXECUTION_VERIFICATION,
+            name: "Best execution verification",
+            claim_type: ClaimType::BEST_EXECUTION,
+            description:
+                "Verifies that an execution price was within a basis-point tolerance of a reference price at execution time.",
+            required_fields: &[
+                "venue",
+                "account_ref",
+                "order_ref",
+                "execution_ref",
+                "execution_price",
+                "reference_price",
+                "max_slippage_bps",
+            ],
+            optional_fields: &["wait_for_result", "wait_timeout_ms", "reference_source", "notes"],
+        },
     ]
 }
 
@@ -46,7 +67,10 @@ pub fn template_ids() -> Vec<&'static str> {
         .collect()
 }
 
-pub fn build_request_from_template(template_id: &str, template_args: &Value) -> Result<ProofRequest> {
+pub fn build_request_from_template(
+    template_id: &str,
+    template_args: &Value,
+) -> Result<ProofRequest> {
     let args = template_args
         .as_object()
         .ok_or_else(|| anyhow!("template_args must be an object"))?;
@@ -59,14 +83,18 @@ pub fn build_request_from_template(template_id: &str, template_args: &Value) ->
     let (claim_type, required_execution_ref) = match template_id {
         TEMPLATE_ORDER_PLACEMENT_VERIFICATION => (ClaimType::ORDER_PLACED, false),
         TEMPLATE_TRADE_EXECUTION_VERIFICATION => (ClaimType::TRADE_EXECUTED, true),
+        TEMPLATE_BEST_EXECUTION_VERIFICATION => (ClaimType::BEST_EXECUTION, true),
         _ => return Err(anyhow!("unsupported template_id: {}", template_id)),
     };
 
     if required_execution_ref && execution_ref.is_none() {
-        return Err(anyhow!(
-            "template {} requires execution_ref",
-            TEMPLATE_TRADE_EXECUTION_VERIFICATION
-        ));
+        return Err(anyhow!("template {} requires execution_ref", template_id));
+    }
+
+    if claim_type == ClaimType::BEST_EXECUTION {
+        for field in ["execution_price", "reference_price", "max_slippage_bps"] {
+            required_number(args, field)?;
+        }
     }
 
     let mut payload_map = args.clone();
@@ -89,25 +117,290 @@ pub fn build_request_from_template(template_id: &str, template_args: &Value) ->
     );
 
     Ok(ProofRequest {
-        venue,
-        claim_type,
-        account_ref,
-        order_ref,
         execution_ref,
         payload: Value::Object(payload_map),
+        ..ProofRequest::new(venue, claim_type, account_ref, order_ref)
     })
 }
 
-fn parse_venue(raw: &str) -> Result<Venue> {
-    match raw {
-        "hyperliquid" => Ok(Venue::Hyperliquid),
-        "base" => Ok(Venue::Base),
-        "solana" => Ok(Venue::Solana),
-        "polymarket" => Ok(Venue::Polymarket),
-        _ => Err(anyhow!("unsupported venue: {}", raw)),
+// Statement prose per claim type and locale. The English templates mirror the canonical statement
+// the adapters build; the others are translations filled from the same structured fields.
+pub fn statement_template(claim_type: ClaimType, locale: StatementLocale) -> &'static str {
+    match (claim_type, locale) {
+        (ClaimType::ORDER_PLACED, StatementLocale::En) => {
+            "Order {order_ref} for account {account_ref} was accepted on venue {venue} at {accepted_at}."
+        }
+        (ClaimType::ORDER_PLACED, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} は {accepted_at} に取引所 {venue} で受理されました。"
+        }
+        (ClaimType::ORDER_PLACED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 已于 {accepted_at} 在交易场所 {venue} 被接受。"
+        }
+        (ClaimType::TRADE_EXECUTED, StatementLocale::En) => {
+            "Order {order_ref} for account {account_ref} was executed on venue {venue} with execution ref {execution_ref}."
+        }
+        (ClaimType::TRADE_EXECUTED, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} は取引所 {venue} で約定しました（約定参照 {execution_ref}）。"
+        }
+        (ClaimType::TRADE_EXECUTED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 已在交易场所 {venue} 成交，成交编号 {execution_ref}。"
+        }
+        (ClaimType::BEST_EXECUTION, StatementLocale::En) => {
+            "Order {order_ref} for account {account_ref} was executed on venue {venue} with execution ref {execution_ref} at price {execution_price}, within {tolerance_bps} bps of reference price {reference_price} from {reference_source} observed at {reference_observed_at}."
+        }
+        (ClaimType::BEST_EXECUTION, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} は取引所 {venue} で価格 {execution_price} にて約定し（約定参照 {execution_ref}）、{reference_observed_at} に {reference_source} で観測された参照価格 {reference_price} から {tolerance_bps} bps 以内でした。"
+        }
+        (ClaimType::BEST_EXECUTION, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 已在交易场所 {venue} 以价格 {execution_price} 成交，成交编号 {execution_ref}，与 {reference_source} 于 {reference_observed_at} 观察到的参考价格 {reference_price} 相差不超过 {tolerance_bps} bps。"
+        }
+        (ClaimType::TRADE_BUSTED, StatementLocale::En) => {
+            "Execution {execution_ref} of order {order_ref} for account {account_ref} on venue {venue} was busted by venue correction {correction_ref} observed at {correction_observed_at}."
+        }
+        (ClaimType::TRADE_BUSTED, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} の約定 {execution_ref} は、{correction_observed_at} に観測された取引所 {venue} の訂正 {correction_ref} により取り消されました。"
+        }
+        (ClaimType::TRADE_BUSTED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 在交易场所 {venue} 的成交 {execution_ref} 已被 {correction_observed_at} 观察到的交易场所更正 {correction_ref} 撤销。"
+        }
+        (ClaimType::TRADE_AMENDED, StatementLocale::En) => {
+            "Execution {execution_ref} of order {order_ref} for account {account_ref} on venue {venue} was amended to price {amended_price} and size {amended_size} by venue correction {correction_ref} observed at {correction_observed_at}."
+        }
+        (ClaimType::TRADE_AMENDED, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} の約定 {execution_ref} は、{correction_observed_at} に観測された取引所 {venue} の訂正 {correction_ref} により価格 {amended_price}、数量 {amended_size} に修正されました。"
+        }
+        (ClaimType::TRADE_AMENDED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 在交易场所 {venue} 的成交 {execution_ref} 已根据 {correction_observed_at} 观察到的交易场所更正 {correction_ref} 修改为价格 {amended_price}、数量 {amended_size}。"
+        }
+        (ClaimType::ORDER_REJECTED, StatementLocale::En) => {
+            "Order {order_ref} for account {account_ref} was rejected on venue {venue} at {rejected_at} with reason \"{rejection_reason}\" (order hash {order_hash})."
+        }
+        (ClaimType::ORDER_REJECTED, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} は {rejected_at} に取引所 {venue} で拒否されました（理由「{rejection_reason}」、注文ハッシュ {order_hash}）。"
+        }
+        (ClaimType::ORDER_REJECTED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 已于 {rejected_at} 被交易场所 {venue} 拒绝，原因为“{rejection_reason}”，订单哈希 {order_hash}。"
+        }
+        (ClaimType::ORDER_CANCELLED, StatementLocale::En) => {
+            "Order {order_ref} for account {account_ref} was cancelled on venue {venue} at {cancelled_at} by venue cancellation {cancellation_ref}."
+        }
+        (ClaimType::ORDER_CANCELLED, StatementLocale::Ja) => {
+            "アカウント {account_ref} の注文 {order_ref} は {cancelled_at} に取引所 {venue} の取消 {cancellation_ref} によりキャンセルされました。"
+        }
+        (ClaimType::ORDER_CANCELLED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的订单 {order_ref} 已于 {cancelled_at} 被交易场所 {venue} 的撤单记录 {cancellation_ref} 取消。"
+        }
+        (ClaimType::POSITION_CLOSED, StatementLocale::En) => {
+            "Position {position_ref} for account {account_ref} was closed on venue {venue} at {closed_at} by order {order_ref} (venue closure {closure_ref}), realizing PnL under commitment {pnl_commitment}."
+        }
+        (ClaimType::POSITION_CLOSED, StatementLocale::Ja) => {
+            "アカウント {account_ref} のポジション {position_ref} は {closed_at} に取引所 {venue} で注文 {order_ref} によりクローズされました（取引所のクローズ記録 {closure_ref}）。実現損益のコミットメントは {pnl_commitment} です。"
+        }
+        (ClaimType::POSITION_CLOSED, StatementLocale::Zh) => {
+            "账户 {account_ref} 的仓位 {position_ref} 已于 {closed_at} 在交易场所 {venue} 通过订单 {order_ref} 平仓（平仓记录 {closure_ref}），已实现盈亏承诺为 {pnl_commitment}。"
+        }
+        (ClaimType::BALANCE_AT_TIMESTAMP, StatementLocale::En) => {
+            "Account {account_ref} held at least {min_balance} {asset} on venue {venue} as of {as_of} (venue balance snapshot {snapshot_ref})."
+        }
+        (ClaimType::BALANCE_AT_TIMESTAMP, StatementLocale::Ja) => {
+            "アカウント {account_ref} は {as_of} 時点で取引所 {venue} に {asset} を {min_balance} 以上保有していました（取引所の残高スナップショット {snapshot_ref}）。"
+        }
+        (ClaimType::BALANCE_AT_TIMESTAMP, StatementLocale::Zh) => {
+            "截至 {as_of}，账户 {account_ref} 在交易场所 {venue} 持有至少 {min_balance} {asset}（余额快照 {snapshot_ref}）。"
+        }
+    }
+}
+
+fn unchanged(locale: StatementLocale) -> &'static str {
+    match locale {
+        StatementLocale::En => "unchanged",
+        StatementLocale::Ja => "変更なし",
+        StatementLocale::Zh => "未变更",
     }
 }
 
+pub(crate) fn rejection_reason(ack: &ExecutionAck) -> Result<&str> {
+    ack.rejection_reason
+        .as_deref()
+        .filter(|_| !ack.accepted)
+        .ok_or_else(|| anyhow!("rejection statement requires the venue's rejection reason"))
+}
+
+// The closure a POSITION_CLOSED statement describes, and the commitment to its realized PnL under
+// the blinding the request's payload carries.
+pub(crate) fn position_pnl<'a>(
+    request: &ProofRequest,
+    bundle: &'a EvidenceBundle,
+) -> Result<(&'a PositionClosure, String)> {
+    let closure = bundle.position_closure.as_ref().ok_or_else(|| {
+        anyhow!("position closed statement requires a venue position closure artifact")
+    })?;
+    let blinding = request
+        .payload
+        .get(PNL_BLINDING_FIELD)
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            anyhow!(
+                "position closed statement requires the payload's {}",
+                PNL_BLINDING_FIELD
+            )
+        })?;
+    Ok((closure, closure.pnl_commitment(blinding)))
+}
+
+// The snapshot a BALANCE_AT_TIMESTAMP statement rests on and the range the request claims. Fails
+// unless the snapshot is the requested account's, asset's and point's, and its balance is in range.
+pub(crate) fn balance_range<'a>(
+    request: &ProofRequest,
+    bundle: &'a EvidenceBundle,
+) -> Result<(&'a BalanceSnapshot, BalanceRange)> {
+    let snapshot = bundle
+        .balance_snapshot
+        .as_ref()
+        .ok_or_else(|| anyhow!("balance statement requires a venue balance snapshot"))?;
+    let range = BalanceRange::from_payload(&request.payload)?;
+    if snapshot.account_ref != request.account_ref
+        || snapshot.asset != range.asset
+        || !range.is_taken_at(snapshot)
+    {
+        return Err(anyhow!(
+            "balance snapshot {} is not of {} for account {} at the requested point",
+            snapshot.snapshot_ref,
+            range.asset,
+            request.account_ref
+        ));
+    }
+    match range.contains(&snapshot.balance) {
+        Some(true) => Ok((snapshot, range)),
+        Some(false) => Err(anyhow!(
+            "balance snapshot {} shows less than {} {}",
+            snapshot.snapshot_ref,
+            range.min_balance,
+            range.asset
+        )),
+        None => Err(anyhow!(
+            "balance snapshot {} has a malformed balance",
+            snapshot.snapshot_ref
+        )),
+    }
+}
+
+pub fn render_statement(
+    locale: StatementLocale,
+    request: &ProofRequest,
+    ack: &ExecutionAck,
+    bundle: &EvidenceBundle,
+) -> Result<String> {
+    let mut fields: Vec<(&str, String)> = vec![
+        ("venue", request.venue.slug().to_string()),
+        ("account_ref", request.account_ref.clone()),
+        ("order_ref", request.order_ref.clone()),
+        ("accepted_at", ack.accepted_at.clone()),
+    ];
+    let execution_ref = request
+        .execution_ref
+        .clone()
+        .unwrap_or_else(|| "UNKNOWN".to_string());
+    match request.claim_type {
+        ClaimType::ORDER_PLACED => {}
+        ClaimType::TRADE_EXECUTED => fields.push(("execution_ref", execution_ref)),
+        ClaimType::BEST_EXECUTION => {
+            let price = bundle.price_observation.as_ref().ok_or_else(|| {
+                anyhow!("best execution statement requires a reference price quote")
+            })?;
+            fields.extend([
+                ("execution_ref", execution_ref),
+                ("execution_price", price.execution_price.to_string()),
+                ("tolerance_bps", price.tolerance_bps.to_string()),
+                ("reference_price", price.reference_price.to_string()),
+                ("reference_source", price.reference_source.clone()),
+                ("reference_observed_at", price.reference_observed_at.clone()),
+            ]);
+        }
+        ClaimType::TRADE_BUSTED | ClaimType::TRADE_AMENDED => {
+            let correction = bundle.correction.as_ref().ok_or_else(|| {
+                anyhow!("correction statement requires a venue correction artifact")
+            })?;
+            fields.extend([
+                ("execution_ref", correction.execution_ref.clone()),
+                ("correction_ref", correction.correction_ref.clone()),
+                ("correction_observed_at", correction.observed_at.clone()),
+            ]);
+            if let CorrectionKind::Amended {
+                amended_price,
+                amended_size,
+            } = &correction.kind
+            {
+                let or_unchanged = |v: &Option<f64>| {
+                    v.map_or_else(|| unchanged(locale).to_string(), |v| v.to_string())
+                };
+                fields.push(("amended_price", or_unchanged(amended_price)));
+                fields.push(("amended_size", or_unchanged(amended_size)));
+            }
+        }
+        ClaimType::ORDER_REJECTED => fields.extend([
+            ("rejected_at", ack.accepted_at.clone()),
+            ("rejection_reason", rejection_reason(ack)?.to_string()),
+            ("order_hash", request.order_hash()),
+        ]),
+        ClaimType::ORDER_CANCELLED => {
+            let cancellation = bundle.cancellation.as_ref().ok_or_else(|| {
+                anyhow!("cancellation statement requires a venue cancellation artifact")
+            })?;
+            fields.extend([
+                ("cancellation_ref", cancellation.cancellation_ref.clone()),
+                ("cancelled_at", cancellation.cancelled_at.clone()),
+            ]);
+        }
+        ClaimType::POSITION_CLOSED => {
+            let (closure, commitment) = position_pnl(request, bundle)?;
+            fields.extend([
+                ("position_ref", closure.position_ref.clone()),
+                ("closure_ref", closure.closure_ref.clone()),
+                ("closed_at", closure.closed_at.clone()),
+                ("pnl_commitment", commitment),
+            ]);
+        }
+        ClaimType::BALANCE_AT_TIMESTAMP => {
+            let (snapshot, range) = balance_range(request, bundle)?;
+            fields.extend([
+                ("asset", range.asset),
+                ("min_balance", range.min_balance),
+                ("as_of", snapshot.as_of.clone()),
+                ("snapshot_ref", snapshot.snapshot_ref.clone()),
+            ]);
+        }
+    }
+    let mut text = statement_template(request.claim_type, locale).to_string();
+    for (key, value) in fields {
+        text = text.replace(&format!("{{{}}}", key), &value);
+    }
+    Ok(text)
+}
+
+pub fn render_localized_statements(
+    locales: &[StatementLocale],
+    claim_hash: &str,
+    request: &ProofRequest,
+    ack: &ExecutionAck,
+    bundle: &EvidenceBundle,
+) -> Result<Vec<LocalizedStatement>> {
+    locales
+        .iter()
+        .map(|&locale| {
+            Ok(LocalizedStatement {
+                locale,
+                text: render_statement(locale, request, ack, bundle)?,
+                derived_from: claim_hash.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_venue(raw: &str) -> Result<Venue> {
+    Venue::from_slug(raw).ok_or_else(|| anyhow!("unsupported venue: {}", raw))
+}
+
 fn required_string(args: &Map<String, Value>, field: &str) -> Result<String> {
     args.get(field)
         .and_then(|v| v.as_str())
@@ -115,6 +408,12 @@ fn required_string(args: &Map<String, Value>, field: &str) -> Result<String> {
         .ok_or_else(|| anyhow!("{} is required", field))
 }
 
+fn required_number(args: &Map<String, Value>, field: &str) -> Result<f64> {
+    args.get(field)
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| anyhow!("{} is required and must be a number", field))
+}
+
 fn optional_string(args: &Map<String, Value>, field: &str) -> Option<String> {
     args.get(field)
         .and_then(|v| v.as_str())
@@ -140,7 +439,10 @@ mod tests {
         assert_eq!(req.claim_type, ClaimType::ORDER_PLACED);
         assert_eq!(req.execution_ref, None);
         assert_eq!(req.payload["notes"], "smoke");
-        assert_eq!(req.payload["template"]["template_id"], TEMPLATE_ORDER_PLACEMENT_VERIFICATION);
+        assert_eq!(
+            req.payload["template"]["template_id"],
+            TEMPLATE_ORDER_PLACEMENT_VERIFICATION
+        );
     }
 
     #[test]
@@ -155,4 +457,19 @@ mod tests {
         );
         assert!(result.is_err());
     }
+
+    #[test]
+    fn best_execution_template_requires_prices() {
+        let result = build_request_from_template(
+            TEMPLATE_BEST_EXECUTION_VERIFICATION,
+            &serde_json::json!({
+                "venue": "hyperliquid",
+                "account_ref": "acct-3",
+                "order_ref": "order-3",
+                "execution_ref": "exec-3",
+                "execution_price": 100.1
+            }),
+        );
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,275 @@
+use crate::adapters::{
+    BaseEvmAdapter, ClobApi, HttpClobApi, HttpJsonRpc, JsonRpc, PolymarketAdapter, SolanaAdapter,
+    SyntheticClobApi, SyntheticEvmChain, SyntheticSolanaCluster, VenueAdapter,
+};
+use crate::engine::ReceiptEngine;
+use crate::http::{read_request, write_response, HttpRequest, HttpResponse};
+use crate::policy::PolicyEngine;
+use crate::prover::Sp1MvpProver;
+use crate::verifier::OffchainVerifier;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+// Paths the fake venue serves its built-in endpoints under.
+pub const FAKE_BASE_RPC_PATH: &str = "/base";
+pub const FAKE_POLYGON_RPC_PATH: &str = "/polygon";
+pub const FAKE_SOLANA_RPC_PATH: &str = "/solana";
+pub const FAKE_CLOB_PATH: &str = "/clob";
+
+// An answer the fake venue gives instead of its built-in endpoints. It matches the request's
+// method and path, or the path and query when `path` has a '?', for `times` requests (every
+// request when unset).
+#[derive(Debug, Clone)]
+pub struct CannedResponse {
+    method: String,
+    path: String,
+    status: u16,
+    body: Value,
+    times: Option<usize>,
+}
+
+impl CannedResponse {
+    pub fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: method.to_ascii_uppercase(),
+            path: path.to_string(),
+            status: 200,
+            body: Value::Null,
+            times: None,
+        }
+    }
+
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_body(mut self, body: Value) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn with_times(mut self, times: usize) -> Self {
+        self.times = Some(times.max(1));
+        self
+    }
+
+    fn matches(&self, request: &HttpRequest) -> bool {
+        let target = match &request.query {
+            Some(query) if self.path.contains('?') => format!("{}?{}", request.path, query),
+            _ => request.path.clone(),
+        };
+        self.method == request.method && self.path == target
+    }
+}
+
+// A request the fake venue received, in arrival order.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    // Null unless the body was JSON.
+    pub body: Value,
+}
+
+struct FakeVenueState {
+    base: Arc<SyntheticEvmChain>,
+    polygon: Arc<SyntheticEvmChain>,
+    solana: Arc<SyntheticSolanaCluster>,
+    clob: Arc<SyntheticClobApi>,
+    canned: Mutex<Vec<CannedResponse>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+// An in-process HTTP venue on a loopback port, so adapters run over their real HTTP clients
+// without network access. It serves JSON-RPC for a Base chain, a Polygon chain and a Solana
+// cluster, and the Polymarket CLOB's order and trade lookups, all backed by the synthetic venues:
+// script orders and fills through `base()`, `polygon()`, `solana()` and `clob()`. Canned responses
+// take precedence, for outages and malformed answers. The server stops when dropped.
+pub struct FakeVenueServer {
+    addr: SocketAddr,
+    state: Arc<FakeVenueState>,
+    task: JoinHandle<()>,
+}
+
+impl FakeVenueServer {
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("failed to bind the fake venue")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(FakeVenueState {
+            base: Arc::new(SyntheticEvmChain::new()),
+            polygon: Arc::new(SyntheticEvmChain::new()),
+            solana: Arc::new(SyntheticSolanaCluster::new()),
+            clob: Arc::new(SyntheticClobApi::new()),
+            canned: Mutex::new(vec![]),
+            requests: Mutex::new(vec![]),
+        });
+        let serving = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&serving);
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, &state).await;
+                });
+            }
+        });
+        Ok(Self { addr, state, task })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn base(&self) -> &Arc<SyntheticEvmChain> {
+        &self.state.base
+    }
+
+    pub fn polygon(&self) -> &Arc<SyntheticEvmChain> {
+        &self.state.polygon
+    }
+
+    pub fn solana(&self) -> &Arc<SyntheticSolanaCluster> {
+        &self.state.solana
+    }
+
+    pub fn clob(&self) -> &Arc<SyntheticClobApi> {
+        &self.state.clob
+    }
+
+    pub fn respond(&self, response: CannedResponse) {
+        lock(&self.state.canned).push(response);
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        lock(&self.state.requests).clone()
+    }
+
+    pub fn base_adapter(&self) -> Result<BaseEvmAdapter> {
+        BaseEvmAdapter::connect(&format!("{}{}", self.url(), FAKE_BASE_RPC_PATH))
+    }
+
+    pub fn solana_adapter(&self) -> Result<SolanaAdapter> {
+        SolanaAdapter::connect(&format!("{}{}", self.url(), FAKE_SOLANA_RPC_PATH))
+    }
+
+    pub fn polymarket_adapter(&self) -> Result<PolymarketAdapter> {
+        Ok(PolymarketAdapter::new(
+            Arc::new(HttpClobApi::new(&format!(
+                "{}{}",
+                self.url(),
+                FAKE_CLOB_PATH
+            ))?),
+            Arc::new(HttpJsonRpc::new(&format!(
+                "{}{}",
+                self.url(),
+                FAKE_POLYGON_RPC_PATH
+            ))?),
+        ))
+    }
+
+    // An engine with the default policy and the MVP prover, whose Base, Solana and Polymarket
+    // adapters all go through this server.
+    pub fn engine(&self) -> Result<ReceiptEngine> {
+        let adapters: Vec<Arc<dyn VenueAdapter>> = vec![
+            Arc::new(self.base_adapter()?),
+            Arc::new(self.solana_adapter()?),
+            Arc::new(self.polymarket_adapter()?),
+        ];
+        Ok(ReceiptEngine::new(
+            adapters,
+            PolicyEngine::new(None)?,
+            Arc::new(Sp1MvpProver),
+            OffchainVerifier::default(),
+        ))
+    }
+}
+
+impl Drop for FakeVenueServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: &FakeVenueState) -> Result<()> {
+    let response = match read_request(&mut stream).await {
+        Ok(request) => route(state, request).await,
+        Err(err) => HttpResponse::json(400, json!({ "error": err.to_string() })),
+    };
+    write_response(&mut stream, &response).await
+}
+
+async fn route(state: &FakeVenueState, request: HttpRequest) -> HttpResponse {
+    let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+    lock(&state.requests).push(RecordedRequest {
+        method: request.method.clone(),
+        path: request.path.clone(),
+        query: request.query.clone(),
+        body: body.clone(),
+    });
+    if let Some(canned) = take_canned(state, &request) {
+        return HttpResponse::json(canned.status, canned.body);
+    }
+    let rpc: Arc<dyn JsonRpc> = match (request.method.as_str(), request.path.as_str()) {
+        ("POST", FAKE_BASE_RPC_PATH) => state.base.clone(),
+        ("POST", FAKE_POLYGON_RPC_PATH) => state.polygon.clone(),
+        ("POST", FAKE_SOLANA_RPC_PATH) => state.solana.clone(),
+        ("GET", path) if path.starts_with(FAKE_CLOB_PATH) => {
+            let path = &path[FAKE_CLOB_PATH.len()..];
+            let target = match &request.query {
+                Some(query) => format!("{}?{}", path, query),
+                None => path.to_string(),
+            };
+            return match state.clob.get(&target).await {
+                Ok(answer) => HttpResponse::json(200, answer),
+                Err(err) => HttpResponse::json(404, json!({ "error": err.to_string() })),
+            };
+        }
+        _ => {
+            return HttpResponse::json(
+                404,
+                json!({ "error": format!("no route for {}", request.path) }),
+            )
+        }
+    };
+    let method = body
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let params = body.get("params").cloned().unwrap_or(Value::Null);
+    let answer = match rpc.call(method, params).await {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }),
+        Err(err) => {
+            json!({ "jsonrpc": "2.0", "id": body["id"], "error": { "code": -32000, "message": err.to_string() } })
+        }
+    };
+    HttpResponse::json(200, answer)
+}
+
+fn take_canned(state: &FakeVenueState, request: &HttpRequest) -> Option<CannedResponse> {
+    let mut canned = lock(&state.canned);
+    let index = canned
+        .iter()
+        .position(|response| response.matches(request))?;
+    let response = canned[index].clone();
+    match &mut canned[index].times {
+        Some(1) => {
+            canned.remove(index);
+        }
+        Some(times) => *times -= 1,
+        None => {}
+    }
+    Some(response)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,370 @@
+use crate::models::{BalanceRange, ClaimType, EvidenceBundle, ProofRequest};
+use chrono::DateTime;
+use serde_json::Value;
+use std::collections::HashSet;
+
+const MAX_REF_LEN: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvidenceLimits {
+    pub max_payload_bytes: usize,
+    pub max_artifact_bytes: u64,
+    pub max_bundle_bytes: u64,
+}
+
+impl Default for EvidenceLimits {
+    fn default() -> Self {
+        Self {
+            max_payload_bytes: 64 * 1024,
+            max_artifact_bytes: 8 * 1024 * 1024,
+            max_bundle_bytes: 32 * 1024 * 1024,
+        }
+    }
+}
+
+pub fn check_payload_size(payload: &Value, limits: &EvidenceLimits) -> Option<String> {
+    let size = serde_json::to_vec(payload).map_or(0, |body| body.len());
+    (size > limits.max_payload_bytes).then(|| {
+        format!(
+            "payload is {} bytes, over the {} byte limit",
+            size, limits.max_payload_bytes
+        )
+    })
+}
+
+// The bundle total counts the artifacts' reported sizes plus the evidence metadata that would be
+// persisted with the receipt.
+pub fn check_evidence_size(bundle: &EvidenceBundle, limits: &EvidenceLimits) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (idx, item) in bundle.items.iter().enumerate() {
+        if item.size_bytes > limits.max_artifact_bytes {
+            issues.push(format!(
+                "evidence[{}] artifact is {} bytes, over the {} byte limit",
+                idx, item.size_bytes, limits.max_artifact_bytes
+            ));
+        }
+    }
+    let persisted = (
+        &bundle.items,
+        &bundle.conflicts,
+        &bundle.price_observation,
+        &bundle.correction,
+        &bundle.cancellation,
+        &bundle.position_closure,
+        &bundle.balance_snapshot,
+        &bundle.collection,
+    );
+    let metadata = serde_json::to_vec(&persisted).map_or(0, |body| body.len() as u64);
+    let total = bundle
+        .items
+        .iter()
+        .fold(metadata, |sum, item| sum.saturating_add(item.size_bytes));
+    if total > limits.max_bundle_bytes {
+        issues.push(format!(
+            "evidence bundle is {} bytes, over the {} byte limit",
+            total, limits.max_bundle_bytes
+        ));
+    }
+    issues
+}
+
+pub fn validate_request(request: &ProofRequest) -> Vec<String> {
+    let mut issues = Vec::new();
+    check_ref("account_ref", &request.account_ref, &mut issues);
+    check_ref("order_ref", &request.order_ref, &mut issues);
+    if let Some(execution_ref) = &request.execution_ref {
+        check_ref("execution_ref", execution_ref, &mut issues);
+    }
+    if let Some(tenant_id) = &request.tenant_id {
+        check_ref("tenant_id", tenant_id, &mut issues);
+    }
+
+    match &request.payload {
+        Value::Null | Value::Object(_) => {}
+        _ => issues.push("payload must be a JSON object".to_string()),
+    }
+    // The range is what the proof commits to, so a malformed one is refused before evidence is collected.
+    if request.claim_type == ClaimType::BALANCE_AT_TIMESTAMP {
+        if let Err(err) = BalanceRange::from_payload(&request.payload) {
+            issues.push(err.to_string());
+        }
+    }
+    if request.deadline_ms == Some(0) {
+        issues.push("deadline_ms must be positive".to_string());
+    }
+    if request.escrow_hold_ms == Some(0) {
+        issues.push("escrow_hold_ms must be positive".to_string());
+    }
+    if request.finality_timeout_ms == Some(0) {
+        issues.push("finality_timeout_ms must be positive".to_string());
+    }
+    issues
+}
+
+pub fn validate_payload(payload: &Value, schema: &Value) -> Vec<String> {
+    let mut issues = Vec::new();
+    match payload {
+        Value::Null => check_schema(
+            "payload",
+            &Value::Object(Default::default()),
+            schema,
+            schema,
+            &mut issues,
+        ),
+        Value::Object(_) => check_schema("payload", payload, schema, schema, &mut issues),
+        _ => {}
+    }
+    issues
+}
+
+pub fn validate_against_schema(root: &str, value: &Value, schema: &Value) -> Vec<String> {
+    let mut issues = Vec::new();
+    check_schema(root, value, schema, schema, &mut issues);
+    issues
+}
+
+// Supports the subset of JSON Schema used by the taxonomy payload schemas and the schema generated
+// from the receipt types: type (one or a list), required, properties, additionalProperties (false
+// only), items, enum, anyOf, $ref into the root's $defs, minimum, exclusiveMinimum, maximum, minLength.
+fn check_schema(path: &str, value: &Value, schema: &Value, root: &Value, issues: &mut Vec<String>) {
+    if let Some(reference) = schema.get("$ref").and_then(|v| v.as_str()) {
+        match reference
+            .strip_prefix("#/$defs/")
+            .and_then(|name| root.get("$defs")?.get(name))
+        {
+            Some(target) => check_schema(path, value, target, root, issues),
+            None => issues.push(format!("{} refers to unknown schema {}", path, reference)),
+        }
+        return;
+    }
+    if let Some(branches) = schema.get("anyOf").and_then(|v| v.as_array()) {
+        let mut first_issues = None;
+        for branch in branches {
+            let mut branch_issues = Vec::new();
+            check_schema(path, value, branch, root, &mut branch_issues);
+            if branch_issues.is_empty() {
+                first_issues = None;
+                break;
+            }
+            first_issues.get_or_insert(branch_issues);
+        }
+        if let Some(branch_issues) = first_issues {
+            issues.extend(branch_issues);
+            return;
+        }
+    }
+    let expected: Vec<&str> = match schema.get("type") {
+        Some(Value::String(expected)) => vec![expected.as_str()],
+        Some(Value::Array(expected)) => expected.iter().filter_map(|v| v.as_str()).collect(),
+        _ => vec![],
+    };
+    if !expected.is_empty()
+        && !expected
+            .iter()
+            .any(|expected| type_matches(expected, value))
+    {
+        issues.push(format!(
+            "{} must be of type {}",
+            path,
+            expected.join(" or ")
+        ));
+        return;
+    }
+    if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array()) {
+        if !allowed.contains(value) {
+            issues.push(format!(
+                "{} must be one of {}",
+                path,
+                Value::Array(allowed.clone())
+            ));
+        }
+    }
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|v| v.as_f64()) {
+            if number < min {
+                issues.push(format!("{} must be >= {}", path, min));
+            }
+        }
+        if let Some(min) = schema.get("exclusiveMinimum").and_then(|v| v.as_f64()) {
+            if number <= min {
+                issues.push(format!("{} must be > {}", path, min));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(|v| v.as_f64()) {
+            if number > max {
+                issues.push(format!("{} must be <= {}", path, max));
+            }
+        }
+    }
+    if let (Some(text), Some(min_len)) = (
+        value.as_str(),
+        schema.get("minLength").and_then(|v| v.as_u64()),
+    ) {
+        if (text.trim().chars().count() as u64) < min_len {
+            issues.push(format!("{} must be at least {} characters", path, min_len));
+        }
+    }
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(|v| v.as_object());
+        for field in schema
+            .get("required")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let Some(field) = field.as_str() {
+                if !object.contains_key(field) {
+                    issues.push(format!("{}.{} is required", path, field));
+                }
+            }
+        }
+        for (key, child) in object {
+            match properties.and_then(|p| p.get(key)) {
+                Some(child_schema) => check_schema(
+                    &format!("{}.{}", path, key),
+                    child,
+                    child_schema,
+                    root,
+                    issues,
+                ),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    issues.push(format!("{}.{} is not an allowed field", path, key));
+                }
+                None => {}
+            }
+        }
+    }
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            check_schema(
+                &format!("{}[{}]", path, index),
+                item,
+                item_schema,
+                root,
+                issues,
+            );
+        }
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.as_f64().is_some_and(f64::is_finite),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn check_ref(field: &str, value: &str, issues: &mut Vec<String>) {
+    if value.trim().is_empty() {
+        issues.push(format!("{} must be non-empty", field));
+        return;
+    }
+    if value.len() > MAX_REF_LEN {
+        issues.push(format!("{} exceeds {} bytes", field, MAX_REF_LEN));
+    }
+    if value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        issues.push(format!(
+            "{} must not contain whitespace or control characters",
+            field
+        ));
+    }
+}
+
+pub fn validate_evidence(
+    bundle: &EvidenceBundle,
+    tag_vocabulary: &HashSet<String>,
+    source_kinds: &HashSet<String>,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (idx, item) in bundle.items.iter().enumerate() {
+        if item.source_id.trim().is_empty() {
+            issues.push(format!("evidence[{}].source_id must be non-empty", idx));
+        }
+        if !source_kinds.is_empty() && !source_kinds.contains(&item.source_kind) {
+            issues.push(format!(
+                "evidence[{}].source_kind {} is not a known source kind",
+                idx, item.source_kind
+            ));
+        }
+        if item.artifact_ref.trim().is_empty() {
+            issues.push(format!("evidence[{}].artifact_ref must be non-empty", idx));
+        }
+        if !is_hash_hex(&item.artifact_hash) {
+            issues.push(format!(
+                "evidence[{}].artifact_hash is not a 0x-prefixed 32-byte hex digest",
+                idx
+            ));
+        }
+        if DateTime::parse_from_rfc3339(&item.observed_at).is_err() {
+            issues.push(format!(
+                "evidence[{}].observed_at is not an RFC 3339 timestamp",
+                idx
+            ));
+        }
+        for tag in &item.tags {
+            if !tag_vocabulary.contains(tag) {
+                issues.push(format!("evidence[{}] carries unknown tag {}", idx, tag));
+            }
+        }
+    }
+    let mut unknown_observed: Vec<&String> = bundle
+        .observed_tags
+        .iter()
+        .filter(|tag| !tag_vocabulary.contains(*tag))
+        .collect();
+    unknown_observed.sort();
+    for tag in unknown_observed {
+        issues.push(format!(
+            "observed tag {} is not in the taxonomy vocabulary",
+            tag
+        ));
+    }
+    if let Some(finality) = &bundle.finality_observed_at {
+        if DateTime::parse_from_rfc3339(finality).is_err() {
+            issues.push("finality_observed_at is not an RFC 3339 timestamp".to_string());
+        }
+    }
+    issues
+}
+
+pub fn is_hash_hex(value: &str) -> bool {
+    value.strip_prefix("0x").is_some_and(|hex| {
+        hex.len() == 64 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_schema_subset_checks_types_bounds_and_required() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["price"],
+            "additionalProperties": false,
+            "properties": {
+                "price": { "type": "number", "exclusiveMinimum": 0 },
+                "side": { "type": "string", "enum": ["buy", "sell"] }
+            }
+        });
+        assert!(
+            validate_payload(&serde_json::json!({"price": 1.5, "side": "buy"}), &schema).is_empty()
+        );
+        let issues = validate_payload(
+            &serde_json::json!({"price": 0, "side": "hold", "extra": 1}),
+            &schema,
+        );
+        assert_eq!(issues.len(), 3);
+        assert_eq!(
+            validate_payload(&Value::Null, &schema),
+            vec!["payload.price is required".to_string()]
+        );
+    }
+}
@@ -1,32 +1,158 @@
-use crate::models::{hash_json, ClaimType, ProofBackend, ReceiptStatus, Venue, ZKReceipt};
+use crate::circuits::CircuitRegistry;
+use crate::claims::ClaimHandlers;
+use crate::models::{
+    hash_json, ClaimType, ProofBackend, ProofMetadata, ReceiptStatus, VerificationMode, ZKReceipt,
+};
+use crate::prover::ATTESTATION_CIRCUIT_ID;
+use serde::{Deserialize, Serialize};
 
+// Where the key a receipt's signature was checked against came from. Only a key from this
+// deployment's key ring, a trusted instance, or the caller says who issued the receipt; the
+// receipt's own embedded key only shows it is internally consistent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum SignerKeySource {
+    LocalKeyRing,
+    TrustedInstance { instance_id: String },
+    Supplied,
+    Embedded,
+}
+
+impl SignerKeySource {
+    pub fn is_trusted(&self) -> bool {
+        !matches!(self, SignerKeySource::Embedded)
+    }
+}
+
+// A standalone check of a receipt document, which may have been issued elsewhere. `proof_valid`
+// is None for receipts that were never proved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiptVerification {
+    pub receipt_id: String,
+    pub status: ReceiptStatus,
+    pub schema_hash_valid: bool,
+    pub receipt_hash_valid: bool,
+    pub signature_valid: bool,
+    pub proof_valid: Option<bool>,
+    pub signer_key_id: String,
+    pub key_source: SignerKeySource,
+    pub signer_trusted: bool,
+    pub valid: bool,
+    pub checked_at: String,
+}
+
+// Proofs from the MVP circuits are checked against the circuit registry. Proofs from the zkVM
+// hosts' own guests verify only under a verifying key hash (a RISC0 image id) pinned here, since
+// the host reports whatever key its guest was built with.
 #[derive(Debug, Default, Clone)]
-pub struct OffchainVerifier;
+pub struct OffchainVerifier {
+    pinned_keys: Vec<(ProofBackend, String)>,
+}
 
 impl OffchainVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Accepts host proofs from `backend` whose verifying key hash, or image id, is `key_hash`.
+    pub fn with_pinned_key(mut self, backend: ProofBackend, key_hash: &str) -> Self {
+        self.pinned_keys
+            .push((backend, normalize_key_hash(key_hash)));
+        self
+    }
+
+    pub fn pinned_keys(&self) -> &[(ProofBackend, String)] {
+        &self.pinned_keys
+    }
+
     pub async fn verify(&self, receipt: &ZKReceipt) -> bool {
+        self.verify_with_handlers(receipt, &ClaimHandlers::default())
+            .await
+    }
+
+    // Receipts for claims with a registered handler commit to that handler's public input layout.
+    pub async fn verify_with_handlers(
+        &self,
+        receipt: &ZKReceipt,
+        handlers: &ClaimHandlers,
+    ) -> bool {
         if receipt.status != ReceiptStatus::PROVED {
             return false;
         }
-        if receipt.proof.backend == ProofBackend::NONE {
+        let claim_type = receipt.claim.r#type;
+        let backend_ok = match receipt.proof.backend {
+            ProofBackend::SP1 => {
+                self.verify_host(&receipt.proof, claim_type, "trade-receipt-sp1", "sp1-vk-")
+            }
+            ProofBackend::PICO => {
+                self.verify_host(&receipt.proof, claim_type, "trade-receipt-pico", "pico-vk-")
+            }
+            // RISC0 receipts are checked against the guest's image id rather than a separate key.
+            ProofBackend::RISC0 => self.verify_host(
+                &receipt.proof,
+                claim_type,
+                "trade-receipt-risc0",
+                "risc0-image-",
+            ),
+            ProofBackend::NONE => verify_attestation(receipt),
+        };
+        if !backend_ok {
             return false;
         }
-        let venue = match receipt.subject.venue {
-            Venue::Hyperliquid => "hyperliquid",
-            Venue::Base => "base",
-            Venue::Solana => "solana",
-            Venue::Polymarket => "polymarket",
-        };
-        let claim_type = match receipt.claim.r#type {
-            ClaimType::ORDER_PLACED => "ORDER_PLACED",
-            ClaimType::TRADE_EXECUTED => "TRADE_EXECUTED",
-        };
-        let expected = hash_json(&serde_json::json!({
-            "claim_hash": receipt.claim.claim_hash,
-            "evidence_root": receipt.provenance.evidence_root,
-            "venue": venue,
-            "claim_type": claim_type
-        }));
+        let expected = hash_json(&handlers.public_inputs(
+            &receipt.claim,
+            &receipt.provenance.evidence_root,
+            receipt.subject.venue,
+        ));
         expected == receipt.proof.public_inputs_hash
     }
+
+    // A registered circuit must be the one registered for the receipt's claim, with its own key.
+    // Other circuit ids are the zkVM hosts' guests (and receipts from before the registry): their
+    // key hash must be pinned for the backend, and the key id, which each backend names its own
+    // way, derived from it.
+    fn verify_host(
+        &self,
+        proof: &ProofMetadata,
+        claim_type: ClaimType,
+        host_circuit: &str,
+        key_prefix: &str,
+    ) -> bool {
+        if let Some(circuit) =
+            CircuitRegistry::builtin().get(&proof.circuit_id, &proof.circuit_version)
+        {
+            return circuit.matches(proof, claim_type);
+        }
+        let key_hash = normalize_key_hash(&proof.verifier_key_hash);
+        proof.circuit_id.starts_with(host_circuit)
+            && self
+                .pinned_keys
+                .iter()
+                .any(|(backend, pinned)| *backend == proof.backend && *pinned == key_hash)
+            && proof.verifier_key_id.to_ascii_lowercase()
+                == format!(
+                    "{}{}",
+                    key_prefix,
+                    key_hash[2..].chars().take(16).collect::<String>()
+                )
+    }
+}
+
+fn normalize_key_hash(key_hash: &str) -> String {
+    format!(
+        "0x{}",
+        key_hash
+            .trim()
+            .trim_start_matches("0x")
+            .to_ascii_lowercase()
+    )
+}
+
+// Attestation-only receipts carry no proof; they stand on the receipt signature, so the
+// "verifying key" must be the signer's own.
+fn verify_attestation(receipt: &ZKReceipt) -> bool {
+    let proof = &receipt.proof;
+    proof.verification_mode == VerificationMode::ATTESTATION_ONLY
+        && proof.circuit_id == ATTESTATION_CIRCUIT_ID
+        && proof.verifier_key_id == receipt.integrity.signer.key_id
 }
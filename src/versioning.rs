@@ -0,0 +1,136 @@
+use crate::models::ZKReceipt;
+use crate::validation::validate_against_schema;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// v0.2.0 receipts carry structured signer identities, issuance sequences, commitment settings and
+// the lineage fields; v0.1.0 is the original format in spec/zkreceipt.v0.1.0.schema.json.
+pub const RECEIPT_FORMAT_VERSION: &str = "v0.2.0";
+pub const LEGACY_RECEIPT_FORMAT_VERSION: &str = "v0.1.0";
+const LEGACY_RECEIPT_SCHEMA: &str = include_str!("../spec/zkreceipt.v0.1.0.schema.json");
+
+// What an engine emits, what it accepts from elsewhere, and what it can down-convert to for
+// clients pinned to an older format. Receipts stored before the bump are still stamped v0.1.0
+// but carry the current fields, so they read as-is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiptVersionSupport {
+    pub produces: String,
+    pub reads: Vec<String>,
+    pub converts_to: Vec<String>,
+}
+
+impl Default for ReceiptVersionSupport {
+    fn default() -> Self {
+        Self {
+            produces: RECEIPT_FORMAT_VERSION.to_string(),
+            reads: vec![
+                LEGACY_RECEIPT_FORMAT_VERSION.to_string(),
+                RECEIPT_FORMAT_VERSION.to_string(),
+            ],
+            converts_to: vec![
+                LEGACY_RECEIPT_FORMAT_VERSION.to_string(),
+                RECEIPT_FORMAT_VERSION.to_string(),
+            ],
+        }
+    }
+}
+
+impl ReceiptVersionSupport {
+    pub fn check_produces(&self, version: &str) -> Result<()> {
+        if version != self.produces {
+            bail!(
+                "receipt format {} cannot be produced; this engine produces {} (older formats are available by down-conversion)",
+                version,
+                self.produces
+            );
+        }
+        Ok(())
+    }
+
+    pub fn check_readable(&self, receipt: &ZKReceipt) -> Result<()> {
+        if !self.reads.contains(&receipt.version) {
+            bail!(
+                "receipt {} uses unsupported format {}; this engine reads {}",
+                receipt.receipt_id,
+                receipt.version,
+                self.reads.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    // The receipt as a client pinned to `version` expects it. The hashes and signature are those
+    // of the receipt as issued, so they verify only against the issued format.
+    pub fn convert(&self, receipt: &ZKReceipt, version: &str) -> Result<Value> {
+        self.check_readable(receipt)?;
+        if !self.converts_to.iter().any(|v| v == version) {
+            bail!(
+                "receipt format {} is not supported; available formats are {}",
+                version,
+                self.converts_to.join(", ")
+            );
+        }
+        let mut value = serde_json::to_value(receipt)?;
+        if version == RECEIPT_FORMAT_VERSION {
+            return Ok(value);
+        }
+        let schema: Value = serde_json::from_str(LEGACY_RECEIPT_SCHEMA)
+            .context("legacy receipt schema is invalid JSON")?;
+        value["version"] = Value::String(LEGACY_RECEIPT_FORMAT_VERSION.to_string());
+        if value["status"] == "AWAITING_FINALITY" {
+            value["status"] = Value::String("PENDING".to_string());
+        }
+        let signer_name = value["integrity"]["signer"]["name"].clone();
+        value["integrity"]["signer"] = signer_name;
+        let projected = project(&value, &schema);
+        let issues = validate_against_schema("receipt", &projected, &schema);
+        if !issues.is_empty() {
+            return Err(anyhow!(
+                "receipt {} cannot be expressed in format {}: {}",
+                receipt.receipt_id,
+                version,
+                issues.join("; ")
+            ));
+        }
+        Ok(projected)
+    }
+}
+
+// Drops every field the schema does not declare, where it disallows additional properties, and
+// unset optional fields, which the legacy format omits rather than nulls.
+fn project(value: &Value, schema: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            let required = |key: &str| {
+                schema
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .is_some_and(|r| r.iter().any(|f| f == key))
+            };
+            Value::Object(
+                object
+                    .iter()
+                    .filter_map(|(key, child)| match properties.and_then(|p| p.get(key)) {
+                        Some(_) if child.is_null() && !required(key) => None,
+                        Some(child_schema) => Some((key.clone(), project(child, child_schema))),
+                        None if closed => None,
+                        None => Some((key.clone(), child.clone())),
+                    })
+                    .collect(),
+            )
+        }
+        Value::Array(items) => match schema.get("items") {
+            Some(item_schema) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| project(item, item_schema))
+                    .collect(),
+            ),
+            None => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
@@ -0,0 +1,173 @@
+use crate::models::{hash_bytes, now_iso, ZKReceipt};
+use crate::signing::{verify_signature, KeyRing, ReceiptSigner};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const HEADER_SIGNATURE: &str = "X-Zkputer-Signature";
+pub const HEADER_DELIVERY_ID: &str = "X-Zkputer-Delivery-Id";
+pub const HEADER_NONCE: &str = "X-Zkputer-Delivery-Nonce";
+pub const HEADER_TIMESTAMP: &str = "X-Zkputer-Timestamp";
+const WEBHOOK_SIGNING_DOMAIN: &str = "zkputer-webhook-v1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub event: String,
+    pub receipt: ZKReceipt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedWebhookDelivery {
+    pub delivery_id: String,
+    pub nonce: String,
+    pub timestamp: String,
+    pub key_id: String,
+    pub algorithm: String,
+    pub signature: String,
+    pub body: Vec<u8>,
+}
+
+impl SignedWebhookDelivery {
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                HEADER_SIGNATURE,
+                format!(
+                    "keyid={},alg={},sig={}",
+                    self.key_id, self.algorithm, self.signature
+                ),
+            ),
+            (HEADER_DELIVERY_ID, self.delivery_id.clone()),
+            (HEADER_NONCE, self.nonce.clone()),
+            (HEADER_TIMESTAMP, self.timestamp.clone()),
+        ]
+    }
+}
+
+// `signer` is the current key's signer, an Ed25519 private key wherever one is loaded.
+pub fn sign_webhook_delivery(
+    signer: &dyn ReceiptSigner,
+    event: &WebhookEvent,
+) -> Result<SignedWebhookDelivery> {
+    let timestamp = now_iso();
+    let body = serde_json::to_vec(event)?;
+    let nonce = Uuid::new_v4().simple().to_string();
+    let signature = signer.sign(&delivery_message(&nonce, &timestamp, &body));
+    let identity = signer.identity();
+    Ok(SignedWebhookDelivery {
+        delivery_id: Uuid::new_v4().to_string(),
+        nonce,
+        timestamp,
+        key_id: identity.key_id.clone(),
+        algorithm: identity.algorithm.clone(),
+        signature,
+        body,
+    })
+}
+
+fn delivery_message(nonce: &str, timestamp: &str, body: &[u8]) -> Vec<u8> {
+    format!(
+        "{}:{}:{}:{}",
+        WEBHOOK_SIGNING_DOMAIN,
+        nonce,
+        timestamp,
+        hash_bytes(body)
+    )
+    .into_bytes()
+}
+
+pub struct WebhookReceiver {
+    keyring: KeyRing,
+    max_age: Duration,
+    // Nonce to the delivery's timestamp. A delivery older than max_age is refused anyway, so its
+    // nonce is forgotten then.
+    seen_nonces: HashMap<String, DateTime<Utc>>,
+}
+
+impl WebhookReceiver {
+    pub fn new(keyring: KeyRing, max_age: Duration) -> Self {
+        Self {
+            keyring,
+            max_age,
+            seen_nonces: HashMap::new(),
+        }
+    }
+
+    // Nonces still held for replay checks.
+    pub fn tracked_nonces(&self) -> usize {
+        self.seen_nonces.len()
+    }
+
+    pub fn verify(&mut self, headers: &[(&str, String)], body: &[u8]) -> Result<WebhookEvent> {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+                .ok_or_else(|| anyhow!("missing webhook header {}", name))
+        };
+        let nonce = header(HEADER_NONCE)?;
+        let timestamp = header(HEADER_TIMESTAMP)?;
+        let (key_id, signature) = parse_signature_header(header(HEADER_SIGNATURE)?)?;
+
+        let sent_at = DateTime::parse_from_rfc3339(timestamp)
+            .map_err(|_| anyhow!("invalid webhook timestamp {}", timestamp))?
+            .with_timezone(&Utc);
+        let now = Utc::now();
+        let within_window = |sent_at: &DateTime<Utc>| {
+            now.signed_duration_since(*sent_at)
+                .num_milliseconds()
+                .unsigned_abs()
+                <= self.max_age.as_millis() as u64
+        };
+        if !within_window(&sent_at) {
+            bail!(
+                "webhook delivery timestamp {} outside the accepted window",
+                timestamp
+            );
+        }
+
+        let key = self
+            .keyring
+            .keys()
+            .iter()
+            .find(|k| k.identity.key_id == key_id && k.is_valid_at(timestamp))
+            .ok_or_else(|| anyhow!("no signer key {} valid at {}", key_id, timestamp))?;
+        // The key's own algorithm decides how the signature is checked, not the header's `alg`.
+        if !verify_signature(
+            &key.identity,
+            &delivery_message(nonce, timestamp, body),
+            &signature,
+        ) {
+            bail!("webhook signature mismatch");
+        }
+        self.seen_nonces.retain(|_, seen_at| within_window(seen_at));
+        if self
+            .seen_nonces
+            .insert(nonce.to_string(), sent_at)
+            .is_some()
+        {
+            bail!("webhook delivery nonce {} was already used", nonce);
+        }
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+fn parse_signature_header(raw: &str) -> Result<(String, String)> {
+    let mut key_id = None;
+    let mut signature = None;
+    for part in raw.split(',') {
+        match part.trim().split_once('=') {
+            Some(("keyid", v)) => key_id = Some(v.to_string()),
+            Some(("sig", v)) => signature = Some(v.to_string()),
+            _ => {}
+        }
+    }
+    match (key_id, signature) {
+        (Some(key_id), Some(signature)) => Ok((key_id, signature)),
+        _ => Err(anyhow!("malformed {} header", HEADER_SIGNATURE)),
+    }
+}
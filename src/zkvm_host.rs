@@ -0,0 +1,38 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+// zkVM SDKs are not dependencies of this crate. Each real backend proves in a host binary built
+// with its own toolchain, which reads one request as JSON on stdin and answers on stdout.
+pub(crate) fn run_host<Req: Serialize, Resp: DeserializeOwned>(
+    host: &Path,
+    zkvm: &str,
+    request: &Req,
+) -> Result<Resp> {
+    let mut child = Command::new(host)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start {} host {}", zkvm, host.display()))?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("{} host stdin unavailable", zkvm))?;
+    stdin.write_all(&serde_json::to_vec(request)?)?;
+    drop(stdin);
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "{} host exited with {}: {}",
+            zkvm,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("{} host returned an unreadable response", zkvm))
+}
@@ -1,18 +1,103 @@
-use std::sync::Arc;
-use std::time::Duration;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde_json::Value;
-use zkputer::adapters::{SyntheticVenueAdapter, VenueAdapter};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use zkputer::adapters::{
+    load_workload_scenarios, order_filled_topic, AdapterCapabilities, BaseEvmAdapter, ClobApi,
+    FinalitySemantics, HttpClobApi, HttpJsonRpc, JsonRpc, PolymarketAdapter, SolanaAdapter,
+    SyntheticClobApi, SyntheticEvmChain, SyntheticLog, SyntheticSolanaCluster,
+    SyntheticVenueAdapter, VenueAdapter, CTF_EXCHANGE, WORKLOADS_PATH,
+};
+use zkputer::anchoring::{
+    merkle_tree, root_anchored_topic, verify_inclusion, AnchorFeePolicy, AnchorPublication,
+    AnchorPublisher, AnchorStatus, AnchorTxChecker, FeeAction, FeeBid, FeeEstimate, PublishAttempt,
+    SyntheticAnchorPublisher,
+};
+use zkputer::audit::{load_receipt_documents, ReceiptAuditor, ReceiptDocument};
+use zkputer::backfill::{
+    parse_backfill_input, run_backfill, BackfillCheckpoint, BackfillFormat, BackfillOptions,
+};
+use zkputer::circuits::{CircuitRegistry, CircuitSpec, GuestProgram, MVP_CIRCUIT_VERSION};
+use zkputer::claims::{
+    balance_range_public_inputs, position_closed_public_inputs, standard_public_inputs,
+    ClaimHandler, PositionClosedHandler,
+};
+use zkputer::clock::{normalize_timestamp, ClockOffset};
+use zkputer::commitments::{evm_hash_words, hash_word, string_word, CommitmentMode};
+use zkputer::config::{Config, ReceiptEngineBuilder, StoreBackend};
+use zkputer::conformance::{
+    certify_adapter, load_adapter_fixtures, FixtureVerdict, ADAPTER_FIXTURES_PATH,
+};
+use zkputer::credentials::{
+    adapter_credential_name, seal_credentials, CredentialStore, SealedFileCredentialSource,
+};
+use zkputer::dispute::{
+    export_dispute_bundle, DisputeBundle, DisputeExport, DEFAULT_DISPUTE_INLINE_LIMIT,
+};
+use zkputer::escrow::WITHHELD;
+use zkputer::events::fold_events;
+use zkputer::export::{ArtifactChunk, ExportItem, ReceiptExport, ARTIFACT_CHUNK_BYTES};
+use zkputer::federation::TrustStore;
+use zkputer::finality::{
+    ChainHeadSource, FinalityObserver, FinalityRule, SyntheticChainHead, TimedChainHead,
+};
+use zkputer::hashing::HashAlgorithm;
+use zkputer::health::{CircuitBreakers, CircuitState, HealthStatus};
+use zkputer::http::{serve, HttpApi, HttpRequest, RateLimit};
+use zkputer::issuance::missing_sequences;
+use zkputer::journal::{read_journal, verify_journal, ObservationJournal};
+use zkputer::leader::{EngineRole, LeaderElection, WriteFenced};
 use zkputer::models::{
-    ClaimType, NonProvableReason, ProofBackend, ProofMetadata, ProofRequest, ReceiptStatus, Venue,
+    hash_json, pnl_commitment, AnchorInclusion, BalanceRange, BalanceSnapshot, ChainCommitment,
+    ClaimType, CorrectionKind, EvidenceBundle, EvidenceItem, ExecutionAck, NonProvableReason,
+    OrderCancellation, PositionClosure, ProofBackend, ProofMetadata, ProofRequest,
+    PublicVerificationQuery, ReceiptLifecycle, ReceiptStatus, RefreshAction, SignerIdentity,
+    StatementLocale, TradeCorrection, TruthClaim, Venue, VerificationMode, EVIDENCE_HASH_DOMAIN,
+    HASH_DOMAIN_VERSION, RECEIPT_HASH_DOMAIN,
 };
+use zkputer::oracles::{PriceOracle, SyntheticPriceOracle, ORACLE_PRICE_TAG, ORACLE_SOURCE_KIND};
+use zkputer::orderbook::{OrderBookSnapshot, ORDER_BOOK_SNAPSHOT_TAG};
+use zkputer::payload_encryption::{
+    open_payload, seal_payload_field, PayloadDecryptionKey, PayloadEncryptionKey,
+    ENCRYPTED_FIELDS_KEY,
+};
+use zkputer::persistence::{ReceiptBackend, SqliteReceiptBackend};
 use zkputer::policy::PolicyEngine;
-use zkputer::prover::{FallbackProver, PicoMvpProver, ProverBackend, Sp1MvpProver};
-use zkputer::templates::{
-    build_request_from_template, TEMPLATE_ORDER_PLACEMENT_VERIFICATION,
+use zkputer::presentation::{present_receipt, PresentationVerifier};
+use zkputer::proof_queue::ProofQueueStats;
+use zkputer::prover::{
+    build_mvp_prover, DelayedProver, FallbackProver, PicoMvpProver, ProofWitness, ProverBackend,
+    ProverStrategy, ProverUsage, Risc0MvpProver, Sp1MvpProver,
+};
+use zkputer::redaction::{CollectionRedaction, FieldTreatment};
+use zkputer::references::{ReferenceFormat, ReferenceFormats};
+use zkputer::screening::{
+    ScreeningOutcome, ScreeningProvider, ScreeningResult, ScreeningStage,
+    StaticListScreeningProvider,
+};
+use zkputer::signing::{
+    receipt_signing_message, verify_ed25519, Ed25519ReceiptSigner, HashBindingSigner, KeyRing,
+    ReceiptSigner, SignerKey, ED25519_ALGORITHM,
+};
+use zkputer::slo::{
+    attribute_stages, evaluate_slos, load_latency_profiles, load_slo_document,
+    BackpressureObservation, ProfileExpectation, ReceiptSample, LATENCY_PROFILES_PATH, SLOS_PATH,
 };
+use zkputer::smart_account::{
+    build_mvp_anchor_publisher, AnchorAccount, Bundler, SmartAccountAnchorPublisher,
+    SyntheticBundler, SyntheticPaymaster, SyntheticUserOperationSigner, UserOperation,
+    UserOperationGas, UserOperationReceipt, ENTRY_POINT_V06,
+};
+use zkputer::store::{ExportCursor, ReceiptQuery, ReceiptStore};
+use zkputer::templates::{build_request_from_template, TEMPLATE_ORDER_PLACEMENT_VERIFICATION};
+use zkputer::test_support::{CannedResponse, FakeVenueServer, FAKE_BASE_RPC_PATH, FAKE_CLOB_PATH};
+use zkputer::validation::EvidenceLimits;
 use zkputer::verifier::OffchainVerifier;
+use zkputer::versioning::{LEGACY_RECEIPT_FORMAT_VERSION, RECEIPT_FORMAT_VERSION};
+use zkputer::webhooks::WebhookReceiver;
 use zkputer::ReceiptEngine;
 
 fn engine_with_prover(prover: Arc<dyn ProverBackend>) -> ReceiptEngine {
@@ -48,18 +133,475 @@ impl ProverBackend for AlwaysFailProver {
     }
 }
 
+#[derive(Debug)]
+struct OfflineProver;
+
+#[async_trait]
+impl ProverBackend for OfflineProver {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::SP1
+    }
+
+    async fn prove(&self, _public_inputs: &Value) -> Result<ProofMetadata> {
+        Err(anyhow!("prover offline"))
+    }
+
+    async fn available(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug)]
+struct SlowProver(Duration);
+
+#[async_trait]
+impl ProverBackend for SlowProver {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::SP1
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        tokio::time::sleep(self.0).await;
+        Sp1MvpProver.prove(public_inputs).await
+    }
+}
+
+#[derive(Debug, Default)]
+struct WitnessRecordingProver(std::sync::Mutex<Vec<ProofWitness>>);
+
+#[async_trait]
+impl ProverBackend for WitnessRecordingProver {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::SP1
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        Sp1MvpProver.prove(public_inputs).await
+    }
+
+    async fn prove_with_witness(
+        &self,
+        public_inputs: &Value,
+        witness: &ProofWitness,
+    ) -> Result<(ProofMetadata, ProverUsage)> {
+        self.0.lock().expect("lock").push(witness.clone());
+        Sp1MvpProver.prove_metered(public_inputs).await
+    }
+}
+
+// Writes a proof artifact to disk per proof, the way the zkVM host provers do.
+#[derive(Debug)]
+struct FileArtifactProver {
+    dir: std::path::PathBuf,
+    artifact_bytes: usize,
+}
+
+#[async_trait]
+impl ProverBackend for FileArtifactProver {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::SP1
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        let mut proof = Sp1MvpProver.prove(public_inputs).await?;
+        let path = self.dir.join(format!(
+            "{}.bin",
+            proof.public_inputs_hash.trim_start_matches("0x")
+        ));
+        std::fs::write(&path, vec![7u8; self.artifact_bytes])?;
+        proof.proof_artifact_ref = Some(format!("file://{}", path.display()));
+        Ok(proof)
+    }
+}
+
+#[derive(Debug)]
+struct CorrectableAdapter {
+    inner: SyntheticVenueAdapter,
+    correction: std::sync::Mutex<Option<&'static str>>,
+}
+
+impl CorrectableAdapter {
+    fn new(venue: Venue) -> Self {
+        Self {
+            inner: SyntheticVenueAdapter::new(venue),
+            correction: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn apply(&self, correction: &'static str) {
+        *self.correction.lock().expect("lock") = Some(correction);
+    }
+}
+
+#[async_trait]
+impl VenueAdapter for CorrectableAdapter {
+    fn venue(&self) -> Venue {
+        self.inner.venue()
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        self.inner.acknowledge(request).await
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        let mut bundle = self.inner.collect_evidence(request, ack).await?;
+        let correction = *self.correction.lock().expect("lock");
+        match correction {
+            Some("amended") => {
+                for item in bundle
+                    .items
+                    .iter_mut()
+                    .filter(|i| i.artifact_ref.contains("/execution/"))
+                {
+                    item.artifact_hash = HashAlgorithm::Sha256.hash_str("amended fill");
+                }
+            }
+            Some("busted") => {
+                bundle
+                    .items
+                    .retain(|i| !i.artifact_ref.contains("/execution/"));
+                bundle.observed_tags.remove("execution_identity");
+            }
+            _ => {}
+        }
+        Ok(bundle)
+    }
+}
+
+// Counts venue round trips so tests can tell cached responses from fresh ones.
+#[derive(Debug)]
+struct CountingAdapter {
+    inner: CorrectableAdapter,
+    acknowledgements: std::sync::atomic::AtomicUsize,
+    collections: std::sync::atomic::AtomicUsize,
+}
+
+impl CountingAdapter {
+    fn new(venue: Venue) -> Self {
+        Self {
+            inner: CorrectableAdapter::new(venue),
+            acknowledgements: std::sync::atomic::AtomicUsize::new(0),
+            collections: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    fn calls(&self) -> (usize, usize) {
+        (
+            self.acknowledgements
+                .load(std::sync::atomic::Ordering::SeqCst),
+            self.collections.load(std::sync::atomic::Ordering::SeqCst),
+        )
+    }
+}
+
+#[async_trait]
+impl VenueAdapter for CountingAdapter {
+    fn venue(&self) -> Venue {
+        self.inner.venue()
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        self.acknowledgements
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.inner.acknowledge(request).await
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        self.collections
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.inner.collect_evidence(request, ack).await
+    }
+}
+
+// A downstream claim plugin: its own statement wording, an extra required tag, and a public input
+// layout that also commits to a layout version.
+struct DeskOrderHandler {
+    required_tags: Vec<String>,
+}
+
+#[async_trait]
+impl ClaimHandler for DeskOrderHandler {
+    fn claim_type(&self) -> ClaimType {
+        ClaimType::ORDER_PLACED
+    }
+
+    fn required_evidence_tags(&self) -> Vec<String> {
+        self.required_tags.clone()
+    }
+
+    async fn build_statement(
+        &self,
+        _adapter: &dyn VenueAdapter,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+        _bundle: &EvidenceBundle,
+    ) -> Result<String> {
+        Ok(format!(
+            "Desk order {} was live at {}.",
+            request.order_ref, ack.accepted_at
+        ))
+    }
+
+    fn public_inputs(&self, claim: &TruthClaim, evidence_root: &str, venue: Venue) -> Value {
+        let mut inputs =
+            standard_public_inputs(&claim.claim_hash, evidence_root, venue, self.claim_type());
+        inputs["layout"] = serde_json::json!("desk-order-v1");
+        inputs
+    }
+}
+
+const GWEI: u128 = 1_000_000_000;
+
+struct CongestedPublisher {
+    base_fee_wei: u128,
+    inclusion_priority_wei: u128,
+}
+
+#[async_trait]
+impl AnchorPublisher for CongestedPublisher {
+    async fn estimate_fees(&self) -> Result<FeeEstimate> {
+        Ok(FeeEstimate {
+            base_fee_wei: self.base_fee_wei,
+            priority_fee_wei: GWEI,
+        })
+    }
+
+    async fn publish_root(
+        &self,
+        root: &str,
+        _leaf_count: usize,
+        bid: &FeeBid,
+    ) -> Result<PublishAttempt> {
+        if bid.max_priority_fee_per_gas_wei < self.inclusion_priority_wei {
+            return Ok(PublishAttempt::NotIncluded(
+                "replacement underpriced".to_string(),
+            ));
+        }
+        Ok(PublishAttempt::Included(AnchorPublication {
+            tx_ref: format!("congested://{}", root),
+            gas_used: 50_000,
+            fee_wei: 50_000 * (self.base_fee_wei + bid.max_priority_fee_per_gas_wei),
+            ..AnchorPublication::default()
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct UnreachableAdapter;
+
+#[async_trait]
+impl VenueAdapter for UnreachableAdapter {
+    fn venue(&self) -> Venue {
+        Venue::Hyperliquid
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities {
+            adapter_id: "test-fixture".to_string(),
+            adapter_version: "0.0.1".to_string(),
+            claim_types: vec![ClaimType::ORDER_PLACED],
+            evidence_tags: vec![],
+            finality: FinalitySemantics::Instant,
+            supports_batch: false,
+            supports_execution: false,
+            supports_corrections: true,
+        }
+    }
+
+    async fn acknowledge(&self, _request: &ProofRequest) -> Result<ExecutionAck> {
+        Err(anyhow!("venue api unreachable"))
+    }
+
+    async fn collect_evidence(
+        &self,
+        _request: &ProofRequest,
+        _ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        Err(anyhow!("venue api unreachable"))
+    }
+
+    async fn poll_corrections(&self) -> Result<Vec<TradeCorrection>> {
+        Err(anyhow!("venue api unreachable"))
+    }
+}
+
+// Echoes its API key into evidence or errors, as a careless real adapter might.
+struct LeakyAdapter {
+    inner: SyntheticVenueAdapter,
+    credentials: CredentialStore,
+}
+
+#[async_trait]
+impl VenueAdapter for LeakyAdapter {
+    fn venue(&self) -> Venue {
+        self.inner.venue()
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        self.inner.acknowledge(request).await
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        let key = self
+            .credentials
+            .get(&adapter_credential_name(Venue::Solana))
+            .await?;
+        if request.payload.get("fail").is_some() {
+            return Err(anyhow!("401 unauthorized for api key {}", key.expose()));
+        }
+        let mut bundle = self.inner.collect_evidence(request, ack).await?;
+        bundle.items[0].artifact_ref =
+            format!("{}?api_key={}", bundle.items[0].artifact_ref, key.expose());
+        Ok(bundle)
+    }
+}
+
+// Settles against the counterparty named in the payload, which then shows up in the evidence.
+struct CounterpartyAdapter(SyntheticVenueAdapter);
+
+#[async_trait]
+impl VenueAdapter for CounterpartyAdapter {
+    fn venue(&self) -> Venue {
+        self.0.venue()
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        self.0.capabilities()
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        self.0.acknowledge(request).await
+    }
+
+    async fn collect_evidence(
+        &self,
+        request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        let mut bundle = self.0.collect_evidence(request, ack).await?;
+        if let Some(counterparty) = request.payload.get("counterparty").and_then(|v| v.as_str()) {
+            bundle.items[0].artifact_ref = format!(
+                "{}?counterparty={}",
+                bundle.items[0].artifact_ref, counterparty
+            );
+        }
+        Ok(bundle)
+    }
+}
+
+struct OfflineScreening;
+
+#[async_trait]
+impl ScreeningProvider for OfflineScreening {
+    fn provider_id(&self) -> String {
+        "offline".to_string()
+    }
+
+    async fn screen(&self, _subjects: &[String]) -> Result<ScreeningResult> {
+        Err(anyhow!("screening api timed out"))
+    }
+}
+
+struct GarbageEvidenceAdapter;
+
+#[async_trait]
+impl VenueAdapter for GarbageEvidenceAdapter {
+    fn venue(&self) -> Venue {
+        Venue::Base
+    }
+
+    fn capabilities(&self) -> AdapterCapabilities {
+        AdapterCapabilities {
+            adapter_id: "test-fixture".to_string(),
+            adapter_version: "0.0.1".to_string(),
+            claim_types: vec![ClaimType::ORDER_PLACED],
+            evidence_tags: vec![],
+            finality: FinalitySemantics::Instant,
+            supports_batch: false,
+            supports_execution: false,
+            supports_corrections: false,
+        }
+    }
+
+    async fn acknowledge(&self, request: &ProofRequest) -> Result<ExecutionAck> {
+        Ok(ExecutionAck {
+            accepted: true,
+            venue_order_ref: request.order_ref.clone(),
+            acceptance_artifact_ref: "base://ack".to_string(),
+            acceptance_artifact_hash: "not-a-hash".to_string(),
+            accepted_at: "yesterday".to_string(),
+            rejection_reason: None,
+        })
+    }
+
+    async fn collect_evidence(
+        &self,
+        _request: &ProofRequest,
+        ack: &ExecutionAck,
+    ) -> Result<EvidenceBundle> {
+        Ok(EvidenceBundle {
+            items: vec![EvidenceItem {
+                source_id: "base-primary".to_string(),
+                source_kind: "canonical_chain_state".to_string(),
+                artifact_ref: ack.acceptance_artifact_ref.clone(),
+                artifact_hash: ack.acceptance_artifact_hash.clone(),
+                observed_at: ack.accepted_at.clone(),
+                tags: vec!["vibes".to_string()],
+                hash_algorithm: HashAlgorithm::Sha256,
+                size_bytes: 0,
+            }],
+            observed_tags: ["vibes".to_string()].into_iter().collect(),
+            conflicts: vec![],
+            finality_observed_at: None,
+            inclusion_height: None,
+            confirmations: None,
+            commitment: None,
+            price_observation: None,
+            correction: None,
+            cancellation: None,
+            position_closure: None,
+            balance_snapshot: None,
+            collection: vec![],
+            order_book: None,
+            raw_artifacts: vec![],
+        })
+    }
+}
+
 #[tokio::test]
 async fn order_placed_proves() {
     let engine = engine();
     let receipt_id = engine
-        .submit(ProofRequest {
-            venue: Venue::Base,
-            claim_type: ClaimType::ORDER_PLACED,
-            account_ref: "acct-1".to_string(),
-            order_ref: "order-1".to_string(),
-            execution_ref: None,
-            payload: serde_json::json!({}),
-        })
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-1",
+            "order-1",
+        ))
         .await
         .expect("submit");
     let receipt = engine
@@ -73,14 +615,12 @@ async fn order_placed_proves() {
 async fn trade_executed_missing_execution_ref_non_provable() {
     let engine = engine();
     let receipt_id = engine
-        .submit(ProofRequest {
-            venue: Venue::Solana,
-            claim_type: ClaimType::TRADE_EXECUTED,
-            account_ref: "acct-2".to_string(),
-            order_ref: "order-2".to_string(),
-            execution_ref: None,
-            payload: serde_json::json!({}),
-        })
+        .submit(ProofRequest::new(
+            Venue::Solana,
+            ClaimType::TRADE_EXECUTED,
+            "acct-2",
+            "order-2",
+        ))
         .await
         .expect("submit");
     let receipt = engine
@@ -88,14 +628,16 @@ async fn trade_executed_missing_execution_ref_non_provable() {
         .await
         .expect("wait");
     assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    let non_provable = receipt.non_provable.as_ref().expect("non provable present");
     assert_eq!(
-        receipt
-            .non_provable
-            .as_ref()
-            .expect("non provable present")
-            .reason_code,
+        non_provable.reason_code,
         NonProvableReason::EVIDENCE_MISSING
     );
+    assert!(non_provable
+        .remediation
+        .missing_tags
+        .contains(&"execution_identity".to_string()));
+    assert!(!non_provable.remediation.reprove_may_help);
 }
 
 #[tokio::test]
@@ -103,12 +645,13 @@ async fn conflicting_evidence_non_provable() {
     let engine = engine();
     let receipt_id = engine
         .submit(ProofRequest {
-            venue: Venue::Hyperliquid,
-            claim_type: ClaimType::ORDER_PLACED,
-            account_ref: "acct-3".to_string(),
-            order_ref: "order-3".to_string(),
-            execution_ref: None,
             payload: serde_json::json!({"simulate_conflict": true}),
+            ..ProofRequest::new(
+                Venue::Hyperliquid,
+                ClaimType::ORDER_PLACED,
+                "acct-3",
+                "order-3",
+            )
         })
         .await
         .expect("submit");
@@ -134,14 +677,12 @@ async fn fallback_prover_uses_pico_when_primary_fails() {
         Arc::new(PicoMvpProver),
     )));
     let receipt_id = engine
-        .submit(ProofRequest {
-            venue: Venue::Polymarket,
-            claim_type: ClaimType::ORDER_PLACED,
-            account_ref: "acct-4".to_string(),
-            order_ref: "order-4".to_string(),
-            execution_ref: None,
-            payload: serde_json::json!({}),
-        })
+        .submit(ProofRequest::new(
+            Venue::Polymarket,
+            ClaimType::ORDER_PLACED,
+            "acct-4",
+            "order-4",
+        ))
         .await
         .expect("submit");
     let receipt = engine
@@ -153,6 +694,7 @@ async fn fallback_prover_uses_pico_when_primary_fails() {
 }
 
 #[tokio::test]
+#[allow(clippy::bool_assert_comparison)]
 async fn template_order_placement_flow_proves() {
     let engine = engine();
     let request = build_request_from_template(
@@ -177,3 +719,8303 @@ async fn template_order_placement_flow_proves() {
         "evidence should be present"
     );
 }
+
+#[tokio::test]
+async fn best_execution_within_tolerance_proves() {
+    let engine = engine();
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some("exec-5".to_string()),
+            payload: serde_json::json!({
+                "execution_price": 100.05,
+                "reference_price": 100.0,
+                "reference_source": "oracle_mid",
+                "max_slippage_bps": 10
+            }),
+            ..ProofRequest::new(
+                Venue::Hyperliquid,
+                ClaimType::BEST_EXECUTION,
+                "acct-5",
+                "order-5",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert!(receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .any(|item| item.tags.iter().any(|t| t == "reference_price_quote")));
+}
+
+#[tokio::test]
+async fn best_execution_against_order_book_mid_records_the_snapshot() {
+    let request = ProofRequest {
+        execution_ref: Some("exec-book".to_string()),
+        payload: serde_json::json!({
+            "execution_price": 100.05,
+            "reference_price": 100.0,
+            "reference_source": "order_book_mid",
+            "max_slippage_bps": 10,
+            "market": "BTC-PERP",
+            "order_book_depth": 5
+        }),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::BEST_EXECUTION,
+            "acct-book",
+            "order-book",
+        )
+    };
+    let engine = engine();
+    let receipt_id = engine.submit(request.clone()).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    let book_item = receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .find(|item| item.tags.iter().any(|t| t == ORDER_BOOK_SNAPSHOT_TAG))
+        .expect("order book evidence");
+    assert!(book_item
+        .artifact_ref
+        .starts_with("hyperliquid://book/BTC-PERP/order-book"));
+
+    let adapter = SyntheticVenueAdapter::new(Venue::Hyperliquid);
+    let ack = adapter.acknowledge(&request).await.expect("ack");
+    let bundle = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("collect");
+    let book = bundle.order_book.clone().expect("snapshot");
+    assert_eq!(book.bids.len(), 5);
+    assert_eq!(book.mid_price(), Some(100.0));
+    assert_eq!(
+        OrderBookSnapshot::decompress(&book.compress()).expect("decompress"),
+        book
+    );
+    let policy = PolicyEngine::new(None).expect("policy");
+    assert!(
+        policy
+            .evaluate(Venue::Hyperliquid, ClaimType::BEST_EXECUTION, &bundle)
+            .ok
+    );
+
+    let mut swapped = bundle.clone();
+    swapped.order_book.as_mut().expect("snapshot").bids[0].size = 99.0;
+    let decision = policy.evaluate(Venue::Hyperliquid, ClaimType::BEST_EXECUTION, &swapped);
+    assert_eq!(decision.reason, Some(NonProvableReason::EVIDENCE_CONFLICT));
+
+    let mut bookless = bundle;
+    bookless.order_book = None;
+    let decision = policy.evaluate(Venue::Hyperliquid, ClaimType::BEST_EXECUTION, &bookless);
+    assert_eq!(decision.reason, Some(NonProvableReason::EVIDENCE_MISSING));
+}
+
+#[tokio::test]
+async fn oracle_quotes_cross_check_venue_prices() {
+    let chainlink = SyntheticPriceOracle::chainlink(Ed25519ReceiptSigner::from_seed(
+        "chainlink-don-1",
+        [31u8; 32],
+    ))
+    .with_price("BTC-USD", 100.0);
+    let pyth = SyntheticPriceOracle::pyth(Ed25519ReceiptSigner::from_seed(
+        "pyth-publisher-1",
+        [32u8; 32],
+    ))
+    .with_price("BTC-USD", 100.02);
+    let oracle_engine =
+        engine().with_price_oracles(vec![Arc::new(chainlink.clone()), Arc::new(pyth)]);
+    let claim = |order: &str, execution_price: f64, feed: &str| ProofRequest {
+        execution_ref: Some(format!("exec-{}", order)),
+        payload: serde_json::json!({
+            "execution_price": execution_price,
+            "reference_price": execution_price,
+            "max_slippage_bps": 10,
+            "oracle_feed": feed
+        }),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::BEST_EXECUTION,
+            "acct-oracle",
+            order.to_string(),
+        )
+    };
+
+    let receipt_id = oracle_engine
+        .submit(claim("oracle-ok", 100.05, "BTC-USD"))
+        .await
+        .expect("submit");
+    let receipt = oracle_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    let quotes: Vec<_> = receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .filter(|item| item.source_kind == ORACLE_SOURCE_KIND)
+        .collect();
+    assert_eq!(quotes.len(), 2);
+    assert!(quotes
+        .iter()
+        .all(|item| item.tags.iter().any(|t| t == ORACLE_PRICE_TAG)));
+    assert!(quotes
+        .iter()
+        .any(|item| item.artifact_ref.starts_with("chainlink://BTC-USD/round/")));
+    assert!(quotes
+        .iter()
+        .any(|item| item.artifact_ref.starts_with("pyth://BTC-USD/round/")));
+
+    let receipt_id = oracle_engine
+        .submit(claim("oracle-off", 101.0, "BTC-USD"))
+        .await
+        .expect("submit");
+    let receipt = oracle_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.expect("non provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::POLICY_VIOLATION
+    );
+    assert!(non_provable.details.contains("chainlink BTC-USD"));
+
+    let receipt_id = oracle_engine
+        .submit(claim("oracle-missing", 100.0, "DOGE-USD"))
+        .await
+        .expect("submit");
+    let receipt = oracle_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.expect("non provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::SOURCE_UNAVAILABLE
+    );
+
+    let mut forged = chainlink
+        .quote_at("BTC-USD", "2026-03-02T14:00:05.000Z")
+        .await
+        .expect("quote");
+    assert_eq!(forged.published_at, "2026-03-02T14:00:00.000Z");
+    assert!(forged.verify(&chainlink.signer()));
+    assert!(!forged.verify(
+        &Ed25519ReceiptSigner::from_seed("other", [33u8; 32])
+            .identity()
+            .public_key
+    ));
+    forged.price = 101.0;
+    assert!(!forged.verify(&chainlink.signer()));
+}
+
+async fn prove_with_adapter(
+    adapter: SyntheticVenueAdapter,
+    order: &str,
+) -> zkputer::models::ZKReceipt {
+    let skewed_engine = ReceiptEngine::new(
+        vec![Arc::new(adapter)],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let request = ProofRequest {
+        execution_ref: Some(format!("exec-{}", order)),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::TRADE_EXECUTED,
+            "acct-clock",
+            order.to_string(),
+        )
+    };
+    let receipt_id = skewed_engine.submit(request).await.expect("submit");
+    skewed_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait")
+}
+
+#[tokio::test]
+async fn clock_offsets_normalize_skewed_timestamps_before_conflict_checks() {
+    let small =
+        SyntheticVenueAdapter::new(Venue::Hyperliquid).with_clock_skew("hyperliquid-api", 3);
+    let receipt = prove_with_adapter(small, "clock-small").await;
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+
+    let skewed =
+        SyntheticVenueAdapter::new(Venue::Hyperliquid).with_clock_skew("hyperliquid-api", 5_000);
+    let receipt = prove_with_adapter(skewed, "clock-skewed").await;
+    let non_provable = receipt.non_provable.expect("non provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::EVIDENCE_CONFLICT
+    );
+    assert!(
+        non_provable.details.contains("submission_timestamp_skew"),
+        "{}",
+        non_provable.details
+    );
+
+    let offset = ClockOffset {
+        source_id: "hyperliquid-api".to_string(),
+        offset_ms: 5_000.0,
+        uncertainty_ms: 15.0,
+        measured_at: "2026-03-02T14:00:00.000Z".to_string(),
+    };
+    let corrected = SyntheticVenueAdapter::new(Venue::Hyperliquid)
+        .with_clock_skew("hyperliquid-api", 5_000)
+        .with_clock_offset(offset.clone());
+    let receipt = prove_with_adapter(corrected, "clock-corrected").await;
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+
+    let execution_early = SyntheticVenueAdapter::new(Venue::Hyperliquid)
+        .with_clock_skew("hyperliquid-execution", -60_000);
+    let receipt = prove_with_adapter(execution_early, "clock-early").await;
+    let details = receipt.non_provable.expect("non provable").details;
+    assert!(
+        details.contains("execution_before_submission"),
+        "{}",
+        details
+    );
+
+    assert_eq!(
+        normalize_timestamp("2026-03-02T16:00:05.000123+02:00", Some(&offset)),
+        normalize_timestamp("2026-03-02T14:00:00.000123Z", None)
+    );
+}
+
+fn evm_engine(chain: Arc<SyntheticEvmChain>) -> ReceiptEngine {
+    ReceiptEngine::new(
+        vec![Arc::new(BaseEvmAdapter::new(chain))],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+}
+
+fn evm_trade(tx_hash: &str, contract: &str) -> ProofRequest {
+    ProofRequest {
+        execution_ref: Some(tx_hash.to_string()),
+        payload: serde_json::json!({ "contract": contract }),
+        ..ProofRequest::new(
+            Venue::Base,
+            ClaimType::TRADE_EXECUTED,
+            "acct-evm",
+            tx_hash.to_string(),
+        )
+    }
+}
+
+#[tokio::test]
+async fn base_evm_adapter_proves_trades_from_verified_chain_state() {
+    let router = "0x2626664c2603336e57b271c5c0b26f421741e481";
+    let trader = "0x1111111111111111111111111111111111111111";
+    let chain = Arc::new(SyntheticEvmChain::new());
+    let fill = chain.submit_transaction(
+        trader,
+        vec![SyntheticLog {
+            address: router.to_string(),
+            topics: vec![format!("0x{}", "ab".repeat(32))],
+            data: "0x01".to_string(),
+        }],
+        true,
+    );
+    let reverted = chain.submit_transaction(trader, vec![], false);
+    let tampered = chain.submit_transaction(trader, vec![], true);
+    chain.mine_empty(12);
+
+    let engine = evm_engine(chain.clone());
+    let receipt_id = engine
+        .submit(evm_trade(&fill, router))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    assert!(receipt.timing.finality_observed_at.is_some());
+    assert!(receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .any(|item| item.artifact_ref == format!("base://tx/{}?from={}", fill, trader)));
+
+    let other_contract = engine
+        .submit(evm_trade(
+            &fill,
+            "0x0000000000000000000000000000000000000bad",
+        ))
+        .await
+        .expect("submit");
+    let other_contract = engine
+        .wait_for_receipt(&other_contract, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        other_contract.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::EVIDENCE_MISSING)
+    );
+
+    let reverted = engine
+        .submit(evm_trade(&reverted, router))
+        .await
+        .expect("submit");
+    let reverted = engine
+        .wait_for_receipt(&reverted, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        reverted.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::EVIDENCE_CONFLICT)
+    );
+
+    chain.tamper_receipt(&tampered, "cumulativeGasUsed", serde_json::json!("0x1"));
+    let adapter = BaseEvmAdapter::new(chain.clone());
+    let request = evm_trade(&tampered, router);
+    let ack = adapter.acknowledge(&request).await.expect("ack");
+    let bundle = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("collect");
+    assert!(
+        bundle
+            .conflicts
+            .contains(&"receipts_root_mismatch".to_string()),
+        "{:?}",
+        bundle.conflicts
+    );
+}
+
+fn solana_trade(signature: &str, program: &str) -> ProofRequest {
+    ProofRequest {
+        execution_ref: Some(signature.to_string()),
+        payload: serde_json::json!({ "program": program }),
+        ..ProofRequest::new(
+            Venue::Solana,
+            ClaimType::TRADE_EXECUTED,
+            "acct-solana",
+            signature.to_string(),
+        )
+    }
+}
+
+#[tokio::test]
+async fn solana_adapter_records_commitment_and_waits_for_finalized_slots() {
+    let program = "PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY";
+    let trader = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    let cluster = Arc::new(SyntheticSolanaCluster::new());
+    let fill = cluster.submit_transaction(trader, Some(program), true);
+    let failed = cluster.submit_transaction(trader, Some(program), false);
+    let dropped = cluster.submit_transaction(trader, Some(program), true);
+
+    let adapter = SolanaAdapter::new(cluster.clone());
+    let request = solana_trade(&fill, program);
+    let ack = adapter.acknowledge(&request).await.expect("ack");
+    let bundle = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("collect");
+    assert!(bundle.conflicts.is_empty(), "{:?}", bundle.conflicts);
+    assert_eq!(bundle.commitment, Some(ChainCommitment::Confirmed));
+    assert_eq!(bundle.inclusion_height, Some(1));
+    assert!(bundle.finality_observed_at.is_none());
+
+    let head = Arc::new(adapter.finalized_slot_head());
+    let solana_engine = ReceiptEngine::new(
+        vec![Arc::new(SolanaAdapter::new(cluster.clone()))],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_finality_observer(
+        FinalityObserver::new(Duration::from_millis(10)).with_head_source(Venue::Solana, head),
+        Duration::from_secs(5),
+    );
+    let receipt_id = solana_engine.submit(request.clone()).await.expect("submit");
+    let mut status = None;
+    for _ in 0..200 {
+        status = solana_engine
+            .get_receipt(&receipt_id)
+            .await
+            .map(|r| r.status);
+        if status != Some(ReceiptStatus::PENDING) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(status, Some(ReceiptStatus::AWAITING_FINALITY));
+    cluster.advance_slots(32);
+    let receipt = solana_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    assert!(receipt.timing.finality_observed_at.is_some());
+
+    let bundle = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("collect");
+    assert_eq!(bundle.commitment, Some(ChainCommitment::Finalized));
+    let first_seen = bundle.finality_observed_at.clone().expect("finalized");
+    let again = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("collect");
+    assert_eq!(again.finality_observed_at, Some(first_seen));
+
+    let failed_id = solana_engine
+        .submit(solana_trade(&failed, program))
+        .await
+        .expect("submit");
+    let failed_receipt = solana_engine
+        .wait_for_receipt(&failed_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        failed_receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::EVIDENCE_CONFLICT)
+    );
+
+    cluster.drop_from_block(&dropped);
+    let request = solana_trade(&dropped, program);
+    let ack = adapter.acknowledge(&request).await.expect("ack");
+    let bundle = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("collect");
+    assert!(bundle
+        .conflicts
+        .contains(&"transaction_not_in_block".to_string()));
+    assert!(adapter
+        .acknowledge(&solana_trade("0xnot-base58", program))
+        .await
+        .is_err());
+}
+
+fn polymarket_claim(claim_type: ClaimType, order: &str, trade: Option<&str>) -> ProofRequest {
+    ProofRequest {
+        execution_ref: trade.map(str::to_string),
+        ..ProofRequest::new(
+            Venue::Polymarket,
+            claim_type,
+            "acct-polymarket",
+            order.to_string(),
+        )
+    }
+}
+
+#[tokio::test]
+async fn polymarket_adapter_pairs_clob_records_with_ctf_settlement() {
+    let maker = "0x2222222222222222222222222222222222222222";
+    let market = format!("0x{}", "cd".repeat(32));
+    let clob = Arc::new(SyntheticClobApi::new());
+    let polygon = Arc::new(SyntheticEvmChain::new());
+    let polymarket_engine = ReceiptEngine::new(
+        vec![Arc::new(PolymarketAdapter::new(
+            clob.clone(),
+            polygon.clone(),
+        ))],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let settle = |claim: ProofRequest| {
+        let polymarket_engine = &polymarket_engine;
+        async move {
+            let receipt_id = polymarket_engine.submit(claim).await.expect("submit");
+            polymarket_engine
+                .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+                .await
+                .expect("wait")
+        }
+    };
+
+    let order = clob.place_order(maker, &market, "7131", "BUY", 0.55, 100.0);
+    let placed = settle(polymarket_claim(ClaimType::ORDER_PLACED, &order, None)).await;
+    assert_eq!(
+        placed.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        placed.non_provable
+    );
+
+    let word = |address: &str| format!("0x{:0>64}", address.trim_start_matches("0x"));
+    let settlement = polygon.submit_transaction(
+        maker,
+        vec![SyntheticLog {
+            address: CTF_EXCHANGE.to_string(),
+            topics: vec![
+                order_filled_topic(),
+                order.clone(),
+                word(maker),
+                word(CTF_EXCHANGE),
+            ],
+            data: "0x".to_string(),
+        }],
+        true,
+    );
+    polygon.mine_empty(32);
+    let trade = clob.match_order(&order, "CONFIRMED", Some(&settlement));
+    let executed = settle(polymarket_claim(
+        ClaimType::TRADE_EXECUTED,
+        &order,
+        Some(&trade),
+    ))
+    .await;
+    assert_eq!(
+        executed.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        executed.non_provable
+    );
+    assert!(executed.timing.finality_observed_at.is_some());
+    let kinds: Vec<&str> = executed
+        .provenance
+        .evidence_items
+        .iter()
+        .map(|item| item.source_kind.as_str())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "venue_api_unsigned",
+            "venue_api_unsigned",
+            "canonical_chain_state"
+        ]
+    );
+    assert!(executed
+        .provenance
+        .evidence_items
+        .iter()
+        .any(|item| item.artifact_ref == format!("polymarket://polygon/tx/{}", settlement)));
+
+    let unsettled_order = clob.place_order(maker, &market, "7131", "SELL", 0.6, 10.0);
+    let unsettled = clob.match_order(&unsettled_order, "MATCHED", None);
+    let receipt = settle(polymarket_claim(
+        ClaimType::TRADE_EXECUTED,
+        &unsettled_order,
+        Some(&unsettled),
+    ))
+    .await;
+    assert_eq!(
+        receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::EVIDENCE_MISSING)
+    );
+
+    let unfilled = clob.match_order(&unsettled_order, "CONFIRMED", Some(&settlement));
+    let receipt = settle(polymarket_claim(
+        ClaimType::TRADE_EXECUTED,
+        &unsettled_order,
+        Some(&unfilled),
+    ))
+    .await;
+    let non_provable = receipt.non_provable.expect("non provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::EVIDENCE_CONFLICT
+    );
+    assert!(non_provable.details.contains("settlement_missing_fill"));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("accept");
+        let mut buf = vec![0u8; 4096];
+        let read = socket.read(&mut buf).await.unwrap_or(0);
+        let request_line = String::from_utf8_lossy(&buf[..read])
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let body = serde_json::json!({ "request_line": request_line }).to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.expect("write");
+    });
+    let api = HttpClobApi::new(&format!("http://{}/clob/", addr)).expect("client");
+    let answer = api.get("/data/order/0xabc").await.expect("get");
+    assert_eq!(
+        answer["request_line"],
+        "GET /clob/data/order/0xabc HTTP/1.1"
+    );
+    assert!(HttpClobApi::new("https://clob.polymarket.com").is_err());
+}
+
+#[tokio::test]
+async fn http_json_rpc_client_reads_chunked_responses() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("accept");
+        let mut buf = vec![0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":"0x1b4"}"#;
+        let (head, tail) = body.split_at(10);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            head.len(),
+            head,
+            tail.len(),
+            tail
+        );
+        socket.write_all(response.as_bytes()).await.expect("write");
+    });
+    let rpc = HttpJsonRpc::new(&format!("http://{}/rpc/secret-key", addr)).expect("client");
+    assert_eq!(rpc.endpoint(), format!("http://{}", addr));
+    let head = rpc
+        .call("eth_blockNumber", serde_json::json!([]))
+        .await
+        .expect("call");
+    assert_eq!(head, serde_json::json!("0x1b4"));
+    assert!(HttpJsonRpc::new("https://mainnet.base.org").is_err());
+}
+
+#[tokio::test]
+async fn localized_statements_are_derived_from_the_canonical_claim() {
+    let localized = engine().with_statement_locales(vec![
+        StatementLocale::Ja,
+        StatementLocale::Zh,
+        StatementLocale::En,
+    ]);
+    let receipt_id = localized
+        .submit(ProofRequest {
+            execution_ref: Some("exec-i18n".to_string()),
+            payload: serde_json::json!({
+                "execution_price": 100.05,
+                "reference_price": 100.0,
+                "reference_source": "oracle_mid",
+                "max_slippage_bps": 10
+            }),
+            ..ProofRequest::new(
+                Venue::Hyperliquid,
+                ClaimType::BEST_EXECUTION,
+                "acct-i18n",
+                "order-i18n",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = localized
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.claim.statement_locale, StatementLocale::En);
+
+    let locales: Vec<_> = receipt
+        .claim
+        .localized_statements
+        .iter()
+        .map(|s| s.locale)
+        .collect();
+    assert_eq!(
+        locales,
+        vec![
+            StatementLocale::Ja,
+            StatementLocale::Zh,
+            StatementLocale::En
+        ]
+    );
+    for localized in &receipt.claim.localized_statements {
+        assert_eq!(localized.derived_from, receipt.claim.claim_hash);
+        assert!(localized.text.contains("order-i18n"));
+        assert!(!localized.text.contains('{'));
+    }
+    assert!(receipt.claim.localized_statements[0].text.contains("約定"));
+    assert!(receipt.claim.localized_statements[1].text.contains("成交"));
+    // The English rendering reproduces the canonical prose, which alone is hashed into the claim.
+    assert_eq!(
+        receipt.claim.localized_statements[2].text,
+        receipt.claim.statement
+    );
+
+    let plain = engine();
+    let plain_id = plain.submit(base_order(9001)).await.expect("submit");
+    let plain_receipt = plain
+        .wait_for_receipt(&plain_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(plain_receipt.status, ReceiptStatus::PROVED);
+    assert!(plain_receipt.claim.localized_statements.is_empty());
+}
+
+#[tokio::test]
+async fn best_execution_outside_tolerance_non_provable() {
+    let engine = engine();
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some("exec-6".to_string()),
+            payload: serde_json::json!({
+                "execution_price": 101.0,
+                "reference_price": 100.0,
+                "max_slippage_bps": 25
+            }),
+            ..ProofRequest::new(Venue::Base, ClaimType::BEST_EXECUTION, "acct-6", "order-6")
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        receipt
+            .non_provable
+            .as_ref()
+            .expect("non provable present")
+            .reason_code,
+        NonProvableReason::POLICY_VIOLATION
+    );
+}
+
+#[tokio::test]
+async fn configured_signer_identity_is_embedded_in_integrity() {
+    let signer = SignerIdentity::new(
+        "acme-verifier",
+        "acme-key-2026-01",
+        "0xabc123",
+        "sha256-binding",
+    );
+    let engine = engine().with_signer(signer.clone());
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-7",
+            "order-7",
+        ))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.integrity.signer, signer);
+}
+
+#[tokio::test]
+async fn rotated_keyring_signs_with_current_key_and_verifies() {
+    let old = SignerIdentity::new("acme-verifier", "acme-key-old", "0x01", "sha256-binding");
+    let new = SignerIdentity::new("acme-verifier", "acme-key-new", "0x02", "sha256-binding");
+    let keyring = KeyRing::new(vec![
+        SignerKey::new(
+            old,
+            "2020-01-01T00:00:00.000Z",
+            Some("2999-01-01T00:00:00.000Z".to_string()),
+        ),
+        SignerKey::new(new.clone(), "2021-01-01T00:00:00.000Z", None),
+    ])
+    .expect("keyring");
+    let engine = engine().with_keyring(keyring.clone());
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Solana,
+            ClaimType::ORDER_PLACED,
+            "acct-8",
+            "order-8",
+        ))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.integrity.signer, new);
+    assert!(keyring.verify_receipt_signature(&receipt));
+    assert_eq!(engine.key_manifest().keys.len(), 2);
+}
+
+#[tokio::test]
+async fn receipts_rotated_while_in_flight_verify_after_revocation() {
+    let old = SignerIdentity::new("acme-verifier", "acme-key-old", "0x01", "sha256-binding");
+    let new = SignerIdentity::new("acme-verifier", "acme-key-new", "0x02", "sha256-binding");
+    let rotation = chrono::Utc::now() + chrono::Duration::milliseconds(300);
+    let keyring = KeyRing::new(vec![
+        SignerKey::new(old.clone(), "2020-01-01T00:00:00.000Z", None),
+        SignerKey::new(
+            new.clone(),
+            rotation.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            None,
+        ),
+    ])
+    .expect("keyring");
+    let engine = engine().with_keyring(keyring.clone());
+    let mut request = base_order(1606);
+    request.payload = serde_json::json!({ "simulate_latency_ms": { "collect_evidence": 500 } });
+    let receipt_id = engine.submit(request).await.expect("submit");
+    let pending = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(pending.integrity.signer, old);
+
+    // Settles after the new key takes over, then is revoked under it.
+    let proved = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(proved.status, ReceiptStatus::PROVED);
+    assert!(keyring.verify_receipt_signature(&proved));
+    let revoked = engine
+        .invalidate(&receipt_id, "upheld dispute", "ops")
+        .await
+        .expect("invalidate");
+    assert_eq!(revoked.integrity.signer, new);
+    assert!(
+        revoked.timing.created_at < rotation.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+    );
+    assert!(keyring.verify_receipt_signature(&revoked));
+
+    // The validity check rests on the signed issuance time, not on `created_at`.
+    let mut backdated = revoked.clone();
+    backdated.integrity.issued_at = Some("2020-06-01T00:00:00.000Z".to_string());
+    assert!(!keyring.verify_receipt_signature(&backdated));
+}
+
+#[tokio::test]
+async fn ed25519_signer_produces_detached_signatures_third_parties_verify() {
+    let path = std::env::temp_dir().join(format!("zkputer-signing-key-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&path, format!("{}\n", hex::encode([7u8; 32]))).expect("write key");
+    let signer = Ed25519ReceiptSigner::from_file("acme-verifier", &path).expect("load key");
+    std::fs::remove_file(&path).ok();
+    let identity = signer.identity().clone();
+    assert_eq!(identity.algorithm, ED25519_ALGORITHM);
+    assert_eq!(
+        identity,
+        Ed25519ReceiptSigner::from_hex_seed("acme-verifier", &hex::encode([7u8; 32]))
+            .expect("seed")
+            .identity()
+            .clone()
+    );
+    assert!(Ed25519ReceiptSigner::from_hex_seed("acme-verifier", "abcd").is_err());
+
+    let signing = engine().with_receipt_signer(Arc::new(signer));
+    let id = signing.submit(base_order(7300)).await.expect("submit");
+    let receipt = signing
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.integrity.signer, identity);
+    assert_eq!(receipt.integrity.signature.len(), 128);
+    assert!(signing.keyring().verify_receipt_signature(&receipt));
+    // Only the public key is needed to check the signature.
+    assert!(verify_ed25519(
+        &identity.public_key,
+        &receipt_signing_message(&receipt.integrity),
+        &receipt.integrity.signature
+    ));
+
+    let mut renumbered = receipt.clone();
+    renumbered.integrity.sequence += 1;
+    assert!(!signing.keyring().verify_receipt_signature(&renumbered));
+    let mut forged = receipt.clone();
+    forged.integrity.signature = hex::encode([0u8; 64]);
+    assert!(!signing.keyring().verify_receipt_signature(&forged));
+
+    // An ed25519 key ring entry without its private key cannot sign.
+    let keyless = engine().with_signer(identity);
+    let err = keyless
+        .submit(base_order(7301))
+        .await
+        .expect_err("no private key");
+    assert!(err.to_string().contains("no private key"));
+}
+
+#[tokio::test]
+async fn receipt_versions_are_declared_and_down_converted_for_pinned_clients() {
+    let versioned = engine()
+        .with_receipt_version(RECEIPT_FORMAT_VERSION)
+        .expect("current version");
+    assert!(engine()
+        .with_receipt_version(LEGACY_RECEIPT_FORMAT_VERSION)
+        .is_err());
+    let support = versioned.receipt_versions();
+    assert_eq!(support.produces, RECEIPT_FORMAT_VERSION);
+    assert!(support
+        .reads
+        .iter()
+        .any(|v| v == LEGACY_RECEIPT_FORMAT_VERSION));
+
+    let id = versioned.submit(base_order(7320)).await.expect("submit");
+    let receipt = versioned
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.version, RECEIPT_FORMAT_VERSION);
+
+    let current = versioned
+        .get_receipt_as(&id, RECEIPT_FORMAT_VERSION)
+        .await
+        .expect("current");
+    assert_eq!(current, serde_json::to_value(&receipt).expect("json"));
+    let legacy = versioned
+        .get_receipt_as(&id, LEGACY_RECEIPT_FORMAT_VERSION)
+        .await
+        .expect("legacy");
+    assert_eq!(legacy["version"], LEGACY_RECEIPT_FORMAT_VERSION);
+    assert_eq!(
+        legacy["integrity"]["signer"],
+        receipt.integrity.signer.name.as_str()
+    );
+    assert!(legacy.get("revision").is_none());
+    assert!(legacy["integrity"].get("sequence").is_none());
+    assert_eq!(
+        legacy["integrity"]["receipt_hash"],
+        receipt.integrity.receipt_hash.as_str()
+    );
+
+    let err = versioned
+        .get_receipt_as(&id, "v9.0.0")
+        .await
+        .expect_err("unknown format");
+    assert!(err.to_string().contains("v0.1.0, v0.2.0"));
+
+    // Claim types added after v0.1.0 have no legacy representation.
+    let mut request = base_order(7321);
+    request.claim_type = ClaimType::BEST_EXECUTION;
+    request.execution_ref = Some("exec-7321".to_string());
+    request.payload = serde_json::json!({});
+    let id = versioned.submit(request).await.expect("submit");
+    versioned
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let err = versioned
+        .get_receipt_as(&id, LEGACY_RECEIPT_FORMAT_VERSION)
+        .await
+        .expect_err("incompatible");
+    assert!(
+        err.to_string()
+            .contains("cannot be expressed in format v0.1.0"),
+        "{}",
+        err
+    );
+
+    let mut foreign = receipt.clone();
+    foreign.version = "v3.0.0".to_string();
+    let err = versioned
+        .verify_foreign_receipt(&foreign)
+        .await
+        .expect_err("unreadable format");
+    assert!(err.to_string().contains("unsupported format v3.0.0"));
+}
+
+#[tokio::test]
+async fn receipts_verify_integrity_against_a_public_key_alone() {
+    let signer = Ed25519ReceiptSigner::from_seed("acme-verifier", [9u8; 32]);
+    let public_key = signer.identity().public_key.clone();
+    let signing = engine().with_receipt_signer(Arc::new(signer));
+    let id = signing.submit(base_order(7310)).await.expect("submit");
+    let receipt = signing
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+
+    let check = receipt.verify_integrity(Some(&public_key));
+    assert!(check.is_valid(), "{:?}", check);
+    let other_key = Ed25519ReceiptSigner::from_seed("acme-verifier", [10u8; 32])
+        .identity()
+        .public_key
+        .clone();
+    assert!(!receipt.verify_integrity(Some(&other_key)).signature_valid);
+
+    let mut restated = receipt.clone();
+    restated.status = ReceiptStatus::NON_PROVABLE;
+    let check = restated.verify_integrity(Some(&public_key));
+    assert!(check.schema_hash_valid);
+    assert!(!check.receipt_hash_valid);
+
+    let mut reversioned = receipt.clone();
+    reversioned.version = "v9.9.9".to_string();
+    assert!(
+        !reversioned
+            .verify_integrity(Some(&public_key))
+            .schema_hash_valid
+    );
+
+    // A receipt cannot pass itself off as a hash binding to dodge the key it was checked against.
+    let mut downgraded = receipt.clone();
+    downgraded.integrity.signer.algorithm = "sha256-binding".to_string();
+    let binding = HashBindingSigner::new(downgraded.integrity.signer.clone());
+    downgraded.integrity.signature = binding.sign(&receipt_signing_message(&downgraded.integrity));
+    let check = downgraded.verify_integrity(Some(&public_key));
+    assert!(
+        check.receipt_hash_valid && !check.signature_valid,
+        "{:?}",
+        check
+    );
+    let verified = signing
+        .verify_receipt(&downgraded, Some(&public_key))
+        .await
+        .expect("verify");
+    assert!(!verified.signature_valid && !verified.valid);
+
+    // The signer's issuance watermark is signed with the same key.
+    let watermarks = signing.watermarks();
+    assert_eq!(watermarks.len(), 1);
+    assert!(verify_ed25519(
+        &public_key,
+        &watermarks[0].signing_message(),
+        &watermarks[0].signature
+    ));
+
+    // Hash-binding receipts only check out against the identity they carry, never a supplied key.
+    let dev = engine();
+    let id = dev.submit(base_order(7311)).await.expect("submit");
+    let receipt = dev
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(receipt.verify_integrity(None).is_valid());
+    assert!(
+        !receipt
+            .verify_integrity(Some(&SignerIdentity::dev().public_key))
+            .signature_valid
+    );
+}
+
+#[tokio::test]
+async fn full_text_search_finds_receipts_by_statement_terms() {
+    let engine = engine().with_search_index();
+    for (order_ref, payload) in [
+        ("ord-abc-001", serde_json::json!({})),
+        (
+            "ord-xyz-002",
+            serde_json::json!({"simulate_conflict": true}),
+        ),
+    ] {
+        let receipt_id = engine
+            .submit(ProofRequest {
+                payload,
+                ..ProofRequest::new(
+                    Venue::Hyperliquid,
+                    ClaimType::ORDER_PLACED,
+                    "acct-9",
+                    order_ref.to_string(),
+                )
+            })
+            .await
+            .expect("submit");
+        engine
+            .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+    }
+
+    let by_order = engine
+        .list_receipts(&ReceiptQuery {
+            text: Some("order ord-abc".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("search");
+    assert_eq!(by_order.len(), 1);
+    assert_eq!(by_order[0].subject.order_ref, "ord-abc-001");
+
+    let by_details = engine
+        .list_receipts(&ReceiptQuery {
+            text: Some("conflicting".to_string()),
+            status: Some(ReceiptStatus::NON_PROVABLE),
+            ..Default::default()
+        })
+        .await
+        .expect("search");
+    assert_eq!(by_details.len(), 1);
+    assert_eq!(by_details[0].subject.order_ref, "ord-xyz-002");
+}
+
+#[tokio::test]
+async fn unsupported_venue_claim_rejected_up_front() {
+    let adapters: Vec<Arc<dyn VenueAdapter>> = vec![Arc::new(
+        SyntheticVenueAdapter::with_claim_types(Venue::Polymarket, vec![ClaimType::ORDER_PLACED]),
+    )];
+    let engine = ReceiptEngine::new(
+        adapters,
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some("exec-10".to_string()),
+            ..ProofRequest::new(
+                Venue::Polymarket,
+                ClaimType::TRADE_EXECUTED,
+                "acct-10",
+                "order-10",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .get_receipt(&receipt_id)
+        .await
+        .expect("receipt stored");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        receipt
+            .non_provable
+            .as_ref()
+            .expect("non provable present")
+            .reason_code,
+        NonProvableReason::UNSUPPORTED_VENUE_CLAIM
+    );
+}
+
+#[tokio::test]
+async fn webhook_deliveries_are_signed_and_replays_rejected() {
+    let engine = engine();
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-11",
+            "order-11",
+        ))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let delivery = engine
+        .sign_webhook_delivery("receipt.proved", receipt)
+        .expect("sign delivery");
+    let headers = delivery.headers();
+
+    let mut receiver = WebhookReceiver::new(engine.keyring().clone(), Duration::from_secs(300));
+    let event = receiver
+        .verify(&headers, &delivery.body)
+        .expect("valid delivery");
+    assert_eq!(event.receipt.receipt_id, receipt_id);
+    assert!(
+        receiver.verify(&headers, &delivery.body).is_err(),
+        "replayed nonce must be rejected"
+    );
+
+    // Nonces are only kept while their delivery could still be accepted.
+    let mut short_receiver =
+        WebhookReceiver::new(engine.keyring().clone(), Duration::from_millis(200));
+    short_receiver
+        .verify(&headers, &delivery.body)
+        .expect("fresh delivery");
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let later = engine
+        .sign_webhook_delivery("receipt.proved", event.receipt.clone())
+        .expect("sign delivery");
+    short_receiver
+        .verify(&later.headers(), &later.body)
+        .expect("fresh delivery");
+    assert_eq!(short_receiver.tracked_nonces(), 1);
+
+    let mut tampered = delivery.body.clone();
+    tampered.push(b' ');
+    let mut fresh_receiver =
+        WebhookReceiver::new(engine.keyring().clone(), Duration::from_secs(300));
+    assert!(fresh_receiver.verify(&headers, &tampered).is_err());
+
+    // With a private key loaded, deliveries carry an Ed25519 signature by it.
+    let signing = self::engine().with_receipt_signer(Arc::new(Ed25519ReceiptSigner::from_seed(
+        "hooks", [23u8; 32],
+    )));
+    let delivery = signing
+        .sign_webhook_delivery("receipt.proved", event.receipt)
+        .expect("sign delivery");
+    assert_eq!(delivery.algorithm, ED25519_ALGORITHM);
+    let mut receiver = WebhookReceiver::new(signing.keyring().clone(), Duration::from_secs(300));
+    receiver
+        .verify(&delivery.headers(), &delivery.body)
+        .expect("valid delivery");
+    let mut forged = delivery.clone();
+    forged.algorithm = "sha256-binding".to_string();
+    forged.nonce = "forged".to_string();
+    forged.signature =
+        HashBindingSigner::new(signing.keyring().keys()[0].identity.clone()).sign(b"anything");
+    assert!(receiver.verify(&forged.headers(), &forged.body).is_err());
+}
+
+#[tokio::test]
+async fn counterparty_presentations_bind_holder_nonce_and_audience() {
+    let holder = engine().with_receipt_signer(Arc::new(Ed25519ReceiptSigner::from_seed(
+        "desk-holder",
+        [21u8; 32],
+    )));
+    let receipt_id = holder
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-present",
+            "order-present",
+        ))
+        .await
+        .expect("submit");
+    let receipt = holder
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let holder_keys = holder.keyring().clone();
+
+    let mut verifier = PresentationVerifier::new("desk.example", Duration::from_secs(60));
+    let challenge = verifier.issue_challenge();
+    let presentation = holder
+        .present_receipt(&receipt_id, &challenge)
+        .await
+        .expect("present");
+    let verified = verifier
+        .verify(&presentation, &holder_keys)
+        .expect("valid presentation");
+    assert_eq!(verified.receipt_id, receipt_id);
+    assert!(
+        verifier.verify(&presentation, &holder_keys).is_err(),
+        "replayed nonce must be rejected"
+    );
+
+    let mut other_desk = PresentationVerifier::new("other.example", Duration::from_secs(60));
+    let foreign = other_desk.issue_challenge();
+    let mut redirected = holder
+        .present_receipt(&receipt_id, &challenge)
+        .await
+        .expect("present");
+    redirected.challenge = foreign;
+    assert!(
+        other_desk.verify(&redirected, &holder_keys).is_err(),
+        "signature is bound to the issued challenge"
+    );
+
+    let impostor = Ed25519ReceiptSigner::from_seed("impostor", [22u8; 32]);
+    let impostor_keys = KeyRing::single(impostor.identity().clone());
+    let challenge = verifier.issue_challenge();
+    assert!(present_receipt(&impostor_keys, &impostor, &receipt, &challenge).is_err());
+    let mut stolen = holder
+        .present_receipt(&receipt_id, &challenge)
+        .await
+        .expect("present");
+    stolen.holder = impostor.identity().clone();
+    assert!(
+        verifier.verify(&stolen, &impostor_keys).is_err(),
+        "copied receipt cannot be passed off"
+    );
+
+    // The signature is by the holder's private key; a forged hash binding does not pass.
+    let challenge = verifier.issue_challenge();
+    let mut forged = holder
+        .present_receipt(&receipt_id, &challenge)
+        .await
+        .expect("present");
+    forged.signature = "00".repeat(64);
+    assert!(verifier.verify(&forged, &holder_keys).is_err());
+    // Without a private key for its current key an engine cannot present at all.
+    let dev = engine();
+    let dev_id = dev.submit(base_order(7320)).await.expect("submit");
+    dev.wait_for_receipt(&dev_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let err = dev
+        .present_receipt(&dev_id, &verifier.issue_challenge())
+        .await
+        .expect_err("no private key");
+    assert!(err.to_string().contains("no private key loaded"), "{}", err);
+
+    let mut expired_verifier = PresentationVerifier::new("desk.example", Duration::ZERO);
+    let expired = expired_verifier.issue_challenge();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let late = holder
+        .present_receipt(&receipt_id, &expired)
+        .await
+        .expect("present");
+    assert!(
+        expired_verifier.verify(&late, &holder_keys).is_err(),
+        "expired challenge must be rejected"
+    );
+}
+
+#[tokio::test]
+async fn period_report_summarizes_proved_receipts_under_a_signed_merkle_root() {
+    let engine = engine().with_receipt_signer(Arc::new(Ed25519ReceiptSigner::from_seed(
+        "reports", [24u8; 32],
+    )));
+    let window_start = zkputer::models::now_iso();
+    let requests = [
+        (
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-report",
+            None,
+            serde_json::json!({}),
+        ),
+        (
+            Venue::Solana,
+            ClaimType::TRADE_EXECUTED,
+            "acct-report",
+            Some("exec-report-1"),
+            serde_json::json!({"expected_price": 100.0, "expected_size": 2.5}),
+        ),
+        (
+            Venue::Solana,
+            ClaimType::TRADE_EXECUTED,
+            "acct-report",
+            Some("exec-report-2"),
+            serde_json::json!({"expected_price": 50.0, "expected_size": 1.0}),
+        ),
+        (
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-elsewhere",
+            None,
+            serde_json::json!({}),
+        ),
+    ];
+    let mut hashes = Vec::new();
+    for (i, (venue, claim_type, account, execution_ref, payload)) in
+        requests.into_iter().enumerate()
+    {
+        let receipt_id = engine
+            .submit(ProofRequest {
+                execution_ref: execution_ref.map(str::to_string),
+                payload,
+                ..ProofRequest::new(
+                    venue,
+                    claim_type,
+                    account.to_string(),
+                    format!("order-report-{}", i),
+                )
+            })
+            .await
+            .expect("submit");
+        let receipt = engine
+            .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(receipt.status, ReceiptStatus::PROVED);
+        if account == "acct-report" {
+            hashes.push(receipt.integrity.receipt_hash);
+        }
+    }
+
+    let report = engine
+        .build_period_report("acct-report", &window_start, "2999-01-01T00:00:00.000Z")
+        .await
+        .expect("report");
+    assert_eq!(report.receipts, 3);
+    assert_eq!(report.notional, Some(300.0));
+    assert_eq!(report.venues.len(), 2);
+    let solana = report
+        .venues
+        .iter()
+        .find(|v| v.venue == Venue::Solana)
+        .expect("solana");
+    assert_eq!(solana.by_claim_type.get("TRADE_EXECUTED"), Some(&2));
+    let base = report
+        .venues
+        .iter()
+        .find(|v| v.venue == Venue::Base)
+        .expect("base");
+    assert_eq!(base.notional, None);
+    let mut reported = report.receipt_hashes.clone();
+    reported.sort();
+    hashes.sort();
+    assert_eq!(reported, hashes);
+    assert_eq!(
+        report.merkle_root,
+        Some(merkle_tree(&report.receipt_hashes).expect("tree").0)
+    );
+    assert!(report.verify(engine.keyring()));
+    assert!(verify_ed25519(
+        &report.signer.public_key,
+        &report.signing_message(),
+        &report.signature
+    ));
+
+    let mut tampered = report.clone();
+    tampered.receipts = 2;
+    assert!(!tampered.verify(engine.keyring()));
+
+    let empty = engine
+        .build_period_report(
+            "acct-report",
+            "2000-01-01T00:00:00.000Z",
+            "2000-02-01T00:00:00.000Z",
+        )
+        .await
+        .expect("empty report");
+    assert_eq!(empty.receipts, 0);
+    assert_eq!(empty.merkle_root, None);
+    assert!(engine
+        .build_period_report(
+            "acct-report",
+            "2000-02-01T00:00:00.000Z",
+            "2000-01-01T00:00:00.000Z"
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn configured_hash_algorithm_is_recorded_in_integrity() {
+    let engine = engine().with_hash_algorithm(HashAlgorithm::Blake3);
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some("exec-12".to_string()),
+            ..ProofRequest::new(
+                Venue::Solana,
+                ClaimType::TRADE_EXECUTED,
+                "acct-12",
+                "order-12",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.integrity.hash_algorithm, HashAlgorithm::Blake3);
+    assert!(receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .all(|item| item.hash_algorithm == HashAlgorithm::Sha256));
+}
+
+#[tokio::test]
+async fn evm_commitment_mode_uses_keccak_abi_words() {
+    let engine = engine().with_commitment_mode(CommitmentMode::EVM_ABI);
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some("exec-13".to_string()),
+            ..ProofRequest::new(
+                Venue::Base,
+                ClaimType::TRADE_EXECUTED,
+                "acct-13",
+                "order-13",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.integrity.commitment_mode, CommitmentMode::EVM_ABI);
+    assert_eq!(receipt.integrity.hash_algorithm, HashAlgorithm::Keccak256);
+
+    let mut leaves: Vec<[u8; 32]> = receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .map(|i| hash_word(&i.artifact_hash))
+        .collect();
+    leaves.sort();
+    leaves.insert(0, string_word(EVIDENCE_HASH_DOMAIN));
+    assert_eq!(receipt.provenance.evidence_root, evm_hash_words(&leaves));
+
+    assert_eq!(receipt.integrity.hash_domain_version, HASH_DOMAIN_VERSION);
+    let expected_receipt_hash = evm_hash_words(&[
+        string_word(RECEIPT_HASH_DOMAIN),
+        string_word("PROVED"),
+        hash_word(&receipt.claim.claim_hash),
+        hash_word(&receipt.provenance.evidence_root),
+        hash_word(&receipt.proof.public_inputs_hash),
+    ]);
+    assert_eq!(receipt.integrity.receipt_hash, expected_receipt_hash);
+}
+
+#[tokio::test]
+async fn requested_verification_mode_routes_through_anchoring() {
+    let request = |n: usize, verification_mode| ProofRequest {
+        verification_mode,
+        ..base_order(n + 700)
+    };
+
+    let anchoring = engine().with_anchor_publisher(Arc::new(SyntheticAnchorPublisher));
+    let offchain_id = anchoring
+        .submit(request(0, VerificationMode::OFFCHAIN))
+        .await
+        .expect("submit");
+    let offchain = anchoring
+        .wait_for_receipt(&offchain_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(offchain.status, ReceiptStatus::PROVED);
+    assert_eq!(offchain.proof.verification_mode, VerificationMode::OFFCHAIN);
+    assert!(offchain.proof.anchor_inclusion.is_none());
+
+    for (n, mode) in [
+        (1, VerificationMode::ONCHAIN_ANCHORED),
+        (2, VerificationMode::OFFCHAIN_AND_ANCHORED),
+    ] {
+        let receipt_id = anchoring.submit(request(n, mode)).await.expect("submit");
+        let receipt = anchoring
+            .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(receipt.status, ReceiptStatus::PROVED);
+        assert_eq!(receipt.proof.verification_mode, mode);
+        let inclusion = receipt
+            .proof
+            .anchor_inclusion
+            .as_ref()
+            .expect("anchored before issuance");
+        assert!(verify_inclusion(&receipt.integrity.receipt_hash, inclusion));
+        assert!(receipt.integrity.issued_at.is_some());
+        assert!(anchoring.keyring().verify_receipt_signature(&receipt));
+        let cost = anchoring.receipt_cost(&receipt_id).await.expect("cost");
+        assert!(cost.anchoring_gas > 0);
+    }
+
+    let unconfigured = engine();
+    let receipt_id = unconfigured
+        .submit(request(3, VerificationMode::ONCHAIN_ANCHORED))
+        .await
+        .expect("submit");
+    let receipt = unconfigured
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        receipt.non_provable.as_ref().map(|n| n.reason_code),
+        Some(NonProvableReason::UNSUPPORTED_VENUE_CLAIM)
+    );
+
+    let capped = engine()
+        .with_anchor_publisher(Arc::new(SyntheticAnchorPublisher))
+        .with_anchor_fee_policy(AnchorFeePolicy {
+            max_fee_per_gas_wei: 1,
+            ..AnchorFeePolicy::default()
+        });
+    let receipt_id = capped
+        .submit(request(4, VerificationMode::OFFCHAIN_AND_ANCHORED))
+        .await
+        .expect("submit");
+    let receipt = capped
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        receipt.non_provable.as_ref().map(|n| n.reason_code),
+        Some(NonProvableReason::SOURCE_UNAVAILABLE)
+    );
+    assert!(receipt.proof.anchor_inclusion.is_none());
+}
+
+#[tokio::test]
+async fn store_rejects_writes_based_on_a_stale_revision() {
+    let engine = engine();
+    let receipt_id = engine.submit(base_order(800)).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(receipt.revision >= 2, "pipeline writes bump the revision");
+
+    let store = ReceiptStore::new();
+    store.insert(receipt.clone()).await;
+    let first = store.get(&receipt_id).await.expect("stored");
+    let second = first.clone();
+    assert_eq!(first.revision, 1);
+
+    let mut invalidated = first;
+    invalidated.status = ReceiptStatus::INVALIDATED;
+    let written = store
+        .compare_and_swap(invalidated)
+        .await
+        .expect("first writer wins");
+    assert_eq!(written.revision, 2);
+
+    let mut clobber = second;
+    clobber.status = ReceiptStatus::PROVED;
+    assert!(
+        store.compare_and_swap(clobber).await.is_err(),
+        "stale writer must not clobber"
+    );
+    let stored = store.get(&receipt_id).await.expect("stored");
+    assert_eq!(stored.status, ReceiptStatus::INVALIDATED);
+    assert_eq!(stored.revision, 2);
+
+    // Two stores sharing a database: the backend rejects a write based on a revision the other
+    // store has already replaced, even though the writer's own copy is still at it.
+    for event_sourced in [false, true] {
+        let path =
+            std::env::temp_dir().join(format!("zkputer-cas-{}.sqlite", uuid::Uuid::new_v4()));
+        let open = |path: &std::path::Path| match event_sourced {
+            true => ReceiptStore::open_event_sourced(path),
+            false => ReceiptStore::open_sqlite(path),
+        };
+        let first = open(&path).expect("open store");
+        first.insert(receipt.clone()).await;
+        let second = open(&path).expect("open store");
+        let mut invalidated = first.get(&receipt_id).await.expect("stored");
+        invalidated.status = ReceiptStatus::INVALIDATED;
+        first
+            .compare_and_swap(invalidated)
+            .await
+            .expect("first writer wins");
+
+        let mut clobber = second.get(&receipt_id).await.expect("stored");
+        assert_eq!(clobber.revision, 1);
+        clobber.status = ReceiptStatus::INVALIDATED;
+        clobber.claim.statement = "clobbered".to_string();
+        let err = second
+            .compare_and_swap(clobber)
+            .await
+            .expect_err("stale writer must not clobber");
+        assert!(err.to_string().contains("modified concurrently"), "{}", err);
+        assert_eq!(second.get(&receipt_id).await.expect("stored").revision, 1);
+        let reopened = open(&path)
+            .expect("reopen store")
+            .get(&receipt_id)
+            .await
+            .expect("stored");
+        assert_eq!(
+            (reopened.revision, reopened.claim.statement.as_str()),
+            (2, receipt.claim.statement.as_str())
+        );
+        std::fs::remove_file(&path).ok();
+    }
+}
+
+#[tokio::test]
+async fn pipelines_retry_settling_writes_that_conflict_with_a_receipt_still_in_flight() {
+    let path = std::env::temp_dir().join(format!("zkputer-commit-{}.sqlite", uuid::Uuid::new_v4()));
+    let engine = engine_with_prover(Arc::new(SlowProver(Duration::from_millis(400))))
+        .with_store(ReceiptStore::open_sqlite(&path).expect("open store"));
+    let other = ReceiptStore::open_sqlite(&path).expect("open store");
+    let proving = |receipt_id: String| {
+        let other = other.clone();
+        async move {
+            for _ in 0..100 {
+                other.refresh().await.expect("refresh");
+                match other.get(&receipt_id).await {
+                    Some(receipt) if receipt.status == ReceiptStatus::AWAITING_FINALITY => {
+                        return receipt
+                    }
+                    _ => tokio::time::sleep(Duration::from_millis(10)).await,
+                }
+            }
+            panic!("receipt {} never reached proving", receipt_id);
+        }
+    };
+
+    // Another engine rewrites the receipt while it is proved; the settling write lands on top.
+    let receipt_id = engine.submit(base_order(856)).await.expect("submit");
+    let mut rewritten = proving(receipt_id.clone()).await;
+    rewritten.timing.updated_at = zkputer::models::now_iso();
+    let rewritten = other.compare_and_swap(rewritten).await.expect("write");
+    let proved = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        proved.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        proved.non_provable
+    );
+    assert_eq!(proved.revision, rewritten.revision + 1);
+    assert!(engine
+        .health()
+        .await
+        .issues
+        .iter()
+        .all(|issue| !issue.contains("write dropped")));
+
+    // A copy settled elsewhere stands, and health says the pipeline's result was dropped.
+    let receipt_id = engine.submit(base_order(857)).await.expect("submit");
+    let mut settled = proving(receipt_id.clone()).await;
+    settled.status = ReceiptStatus::NON_PROVABLE;
+    other.compare_and_swap(settled).await.expect("write");
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(
+        engine.get_receipt(&receipt_id).await.map(|r| r.status),
+        Some(ReceiptStatus::NON_PROVABLE)
+    );
+    let issues = engine.health().await.issues;
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.contains("write dropped") && issue.contains(&receipt_id)),
+        "{:?}",
+        issues
+    );
+    drop((engine, other));
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
+
+#[tokio::test]
+async fn store_rejects_status_changes_the_lifecycle_does_not_allow() {
+    let lifecycle = ReceiptLifecycle::standard();
+    assert!(lifecycle.allows(ReceiptStatus::PENDING, ReceiptStatus::AWAITING_FINALITY));
+    assert!(lifecycle.allows(ReceiptStatus::PROVED, ReceiptStatus::INVALIDATED));
+    assert!(!lifecycle.allows(ReceiptStatus::NON_PROVABLE, ReceiptStatus::PROVED));
+    assert!(!lifecycle.allows(ReceiptStatus::INVALIDATED, ReceiptStatus::PROVED));
+    assert!(!lifecycle.allows(ReceiptStatus::AWAITING_FINALITY, ReceiptStatus::PENDING));
+
+    let mut request = base_order(801);
+    request.payload = serde_json::json!({ "simulate_conflict": true });
+    let rejecting = engine();
+    let receipt_id = rejecting.submit(request).await.expect("submit");
+    let receipt = rejecting
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+
+    let store = ReceiptStore::new();
+    store.insert(receipt.clone()).await;
+    let mut revived = store.get(&receipt_id).await.expect("stored");
+    revived.status = ReceiptStatus::PROVED;
+    let err = store
+        .compare_and_swap(revived)
+        .await
+        .expect_err("NON_PROVABLE is terminal");
+    assert!(err
+        .to_string()
+        .contains("illegal receipt transition NON_PROVABLE -> PROVED"));
+    assert_eq!(
+        store.get(&receipt_id).await.expect("stored").status,
+        ReceiptStatus::NON_PROVABLE
+    );
+
+    let mut fresh = receipt;
+    fresh.receipt_id = "rcpt-created-proved".to_string();
+    fresh.status = ReceiptStatus::PROVED;
+    assert!(
+        store.create(fresh).await.is_err(),
+        "receipts start PENDING or NON_PROVABLE"
+    );
+}
+
+#[tokio::test]
+async fn sqlite_store_keeps_receipts_across_restarts() {
+    let path =
+        std::env::temp_dir().join(format!("zkputer-receipts-{}.sqlite", uuid::Uuid::new_v4()));
+    let (receipt_id, before) = {
+        let first = engine().with_store(ReceiptStore::open_sqlite(&path).expect("open store"));
+        let receipt_id = first.submit(base_order(820)).await.expect("submit");
+        let receipt = first
+            .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(receipt.status, ReceiptStatus::PROVED);
+        assert!(first.health().await.store.persistent);
+        (receipt_id, receipt)
+    };
+
+    let restarted = engine()
+        .with_store(ReceiptStore::open_sqlite(&path).expect("reopen store"))
+        .with_search_index();
+    let after = restarted
+        .get_receipt(&receipt_id)
+        .await
+        .expect("receipt survives restart");
+    assert_eq!(after.integrity.receipt_hash, before.integrity.receipt_hash);
+    assert_eq!(after.revision, before.revision);
+    assert!(restarted.receipt_cost(&receipt_id).await.is_some());
+    let proved = restarted
+        .list_receipts(&ReceiptQuery {
+            status: Some(ReceiptStatus::PROVED),
+            ..ReceiptQuery::default()
+        })
+        .await
+        .expect("list");
+    assert!(proved.iter().any(|r| r.receipt_id == receipt_id));
+
+    let next_id = restarted.submit(base_order(821)).await.expect("submit");
+    let next = restarted
+        .wait_for_receipt(&next_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(
+        next.integrity.sequence > before.integrity.sequence,
+        "issuance sequences continue after a restart"
+    );
+    drop(restarted);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
+
+#[tokio::test]
+async fn foreign_receipts_verify_only_against_trusted_instances() {
+    let foreign = engine().with_signer(SignerIdentity::new(
+        "venue-b",
+        "vb-1",
+        "pk-vb-1",
+        "sha256-binding",
+    ));
+    let receipt_id = foreign.submit(base_order(830)).await.expect("submit");
+    let receipt = foreign
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let descriptor = foreign.instance_descriptor();
+    assert_eq!(descriptor.instance_id, "venue-b");
+
+    let untrusting = engine();
+    let err = untrusting
+        .verify_foreign_receipt(&receipt)
+        .await
+        .expect_err("no trust configured");
+    assert!(err.to_string().contains("no trusted instance"), "{}", err);
+
+    let federated = engine().with_trust_store(
+        TrustStore::new()
+            .with_instance(descriptor.clone())
+            .expect("trust"),
+    );
+    let verified = federated
+        .verify_foreign_receipt(&receipt)
+        .await
+        .expect("foreign receipt verifies");
+    assert_eq!(verified.instance_id, "venue-b");
+    assert_eq!(verified.status, ReceiptStatus::PROVED);
+    assert!(verified.proof_verified);
+
+    let presented = PublicVerificationQuery::Receipt(Box::new(receipt.clone()));
+    let public = federated.verify_public(&presented).await;
+    assert!(!public.known);
+    assert_eq!(public.integrity_valid, Some(true));
+    assert_eq!(public.proof_valid, Some(true));
+    let public = untrusting.verify_public(&presented).await;
+    assert_eq!(public.integrity_valid, Some(false));
+
+    let mut forged_proof = receipt.clone();
+    forged_proof.proof.verifier_key_id = "forged-vk".to_string();
+    let public = foreign
+        .verify_public(&PublicVerificationQuery::Receipt(Box::new(forged_proof)))
+        .await;
+    assert!(public.known);
+    assert_eq!(public.proof_valid, Some(false));
+
+    let mut relabeled = receipt.clone();
+    relabeled.policy.policy_id = "permissive-v9".to_string();
+    let err = federated
+        .verify_foreign_receipt(&relabeled)
+        .await
+        .expect_err("unpublished policy");
+    assert!(err.to_string().contains("is not published"), "{}", err);
+
+    let mut tampered = receipt.clone();
+    tampered.claim.claim_hash = "00".repeat(32);
+    assert!(federated.verify_foreign_receipt(&tampered).await.is_err());
+
+    let mut stale = descriptor;
+    stale.policies[0].source_precedence_version = "0.0.1".to_string();
+    let narrowed =
+        engine().with_trust_store(TrustStore::new().with_instance(stale).expect("trust"));
+    assert!(narrowed.verify_foreign_receipt(&receipt).await.is_err());
+}
+
+#[tokio::test]
+async fn synthetic_adapter_follows_workload_scenarios() {
+    let scenarios =
+        load_workload_scenarios(std::path::Path::new(WORKLOADS_PATH)).expect("workloads");
+    let scenario = scenarios
+        .iter()
+        .find(|s| s.venue == Venue::Hyperliquid && s.claim_type == ClaimType::TRADE_EXECUTED)
+        .expect("hyperliquid fill scenario")
+        .clone();
+    let adapter = SyntheticVenueAdapter::new(Venue::Hyperliquid).with_workload_scenarios(scenarios);
+    let request = ProofRequest {
+        execution_ref: Some("exec-workload".to_string()),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::TRADE_EXECUTED,
+            "acct-workload",
+            "order-workload",
+        )
+    };
+    let ack = adapter.acknowledge(&request).await.expect("ack");
+    assert!(ack
+        .acceptance_artifact_ref
+        .contains(&scenario.action_template));
+    let bundle = adapter
+        .collect_evidence(&request, &ack)
+        .await
+        .expect("evidence");
+    let mut observed: Vec<String> = bundle.observed_tags.iter().cloned().collect();
+    observed.sort();
+    let mut profile = scenario.evidence_profile.clone();
+    profile.sort();
+    assert_eq!(observed, profile);
+    assert!(bundle.items.iter().any(|i| i.artifact_ref
+        == format!(
+            "hyperliquid://{}/execution/exec-workload",
+            scenario.action_template
+        )));
+
+    let plain = SyntheticVenueAdapter::new(Venue::Hyperliquid);
+    let plain_ack = plain.acknowledge(&request).await.expect("ack");
+    assert_ne!(
+        plain_ack.acceptance_artifact_hash,
+        ack.acceptance_artifact_hash
+    );
+
+    let engine = ReceiptEngine::new(
+        vec![Arc::new(adapter)],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let receipt_id = engine.submit(request).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+}
+
+#[tokio::test]
+async fn oversized_payloads_and_evidence_are_rejected() {
+    let limits = EvidenceLimits {
+        max_payload_bytes: 64,
+        ..EvidenceLimits::default()
+    };
+    let payload_capped = engine().with_evidence_limits(limits);
+    let receipt_id = payload_capped
+        .submit(ProofRequest {
+            payload: serde_json::json!({ "notes": "x".repeat(100) }),
+            ..base_order(900)
+        })
+        .await
+        .expect("submit");
+    let receipt = payload_capped
+        .get_receipt(&receipt_id)
+        .await
+        .expect("receipt");
+    let non_provable = receipt.non_provable.as_ref().expect("non provable");
+    assert_eq!(non_provable.reason_code, NonProvableReason::SCHEMA_INVALID);
+    assert!(non_provable.details.contains("byte limit"));
+
+    let artifact_capped = engine().with_evidence_limits(EvidenceLimits {
+        max_artifact_bytes: 16,
+        ..EvidenceLimits::default()
+    });
+    let receipt_id = artifact_capped
+        .submit(base_order(901))
+        .await
+        .expect("submit");
+    let receipt = artifact_capped
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.as_ref().expect("non provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::POLICY_VIOLATION
+    );
+    assert!(non_provable.details.contains("evidence[0] artifact"));
+    assert!(
+        receipt.provenance.evidence_items.is_empty(),
+        "oversized evidence is not persisted"
+    );
+
+    let bundle_capped = engine().with_evidence_limits(EvidenceLimits {
+        max_bundle_bytes: 256,
+        ..EvidenceLimits::default()
+    });
+    let receipt_id = bundle_capped.submit(base_order(902)).await.expect("submit");
+    let receipt = bundle_capped
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.as_ref().expect("non provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::POLICY_VIOLATION
+    );
+    assert!(non_provable.details.contains("evidence bundle is"));
+}
+
+#[tokio::test]
+async fn legacy_untagged_commitments_still_verify() {
+    let engine = engine().with_domain_separation(false);
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-legacy",
+            "order-legacy",
+        ))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.integrity.hash_domain_version, 0);
+    let expected_receipt_hash = HashAlgorithm::Sha256.hash_json(&serde_json::json!({
+        "status": receipt.status,
+        "claim_hash": receipt.claim.claim_hash,
+        "evidence_root": receipt.provenance.evidence_root,
+        "proof_hash": receipt.proof.public_inputs_hash
+    }));
+    assert_eq!(receipt.integrity.receipt_hash, expected_receipt_hash);
+
+    let check = engine
+        .verify_public(&PublicVerificationQuery::Receipt(Box::new(receipt)))
+        .await;
+    assert_eq!(check.integrity_valid, Some(true));
+}
+
+#[tokio::test]
+async fn malformed_request_rejected_synchronously_as_schema_invalid() {
+    let engine = engine();
+    let receipt_id = engine
+        .submit(ProofRequest {
+            payload: serde_json::json!(["not", "an", "object"]),
+            ..ProofRequest::new(
+                Venue::Hyperliquid,
+                ClaimType::ORDER_PLACED,
+                "   ",
+                "order 14",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .get_receipt(&receipt_id)
+        .await
+        .expect("receipt stored");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    let non_provable = receipt.non_provable.as_ref().expect("non provable present");
+    assert_eq!(non_provable.reason_code, NonProvableReason::SCHEMA_INVALID);
+    assert!(non_provable
+        .details
+        .contains("account_ref must be non-empty"));
+    assert!(non_provable
+        .details
+        .contains("payload must be a JSON object"));
+}
+
+#[tokio::test]
+async fn malformed_adapter_evidence_is_schema_invalid() {
+    let engine = ReceiptEngine::new(
+        vec![Arc::new(GarbageEvidenceAdapter)],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-15",
+            "order-15",
+        ))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.as_ref().expect("non provable present");
+    assert_eq!(non_provable.reason_code, NonProvableReason::SCHEMA_INVALID);
+    assert!(non_provable.details.contains("artifact_hash"));
+    assert!(non_provable.details.contains("observed_at"));
+    assert!(non_provable.details.contains("unknown tag vibes"));
+}
+
+#[tokio::test]
+async fn wait_after_timeout_still_observes_completion() {
+    let engine = engine_with_prover(Arc::new(SlowProver(Duration::from_millis(200))));
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-16",
+            "order-16",
+        ))
+        .await
+        .expect("submit");
+    assert!(engine
+        .wait_for_receipt(&receipt_id, Duration::from_millis(10))
+        .await
+        .is_err());
+    let (first, second) = tokio::join!(
+        engine.wait_for_receipt(&receipt_id, Duration::from_secs(5)),
+        engine.wait_for_receipt(&receipt_id, Duration::from_secs(5)),
+    );
+    assert_eq!(first.expect("first wait").status, ReceiptStatus::PROVED);
+    assert_eq!(second.expect("second wait").status, ReceiptStatus::PROVED);
+    assert!(engine
+        .wait_for_receipt("missing-receipt", Duration::from_millis(10))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn watch_yields_intermediate_states_until_terminal() {
+    let engine = engine_with_prover(Arc::new(SlowProver(Duration::from_millis(100))));
+    let receipt_id = engine
+        .submit(ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            "acct-17",
+            "order-17",
+        ))
+        .await
+        .expect("submit");
+    let mut watch = engine.watch(&receipt_id).await.expect("watch");
+    let mut statuses = Vec::new();
+    while let Some(snapshot) = tokio::time::timeout(Duration::from_secs(5), watch.next())
+        .await
+        .expect("watch should not stall")
+    {
+        statuses.push(snapshot.status);
+    }
+    assert!(statuses.contains(&ReceiptStatus::AWAITING_FINALITY));
+    assert_eq!(statuses.last(), Some(&ReceiptStatus::PROVED));
+    assert!(engine.watch("missing-receipt").await.is_err());
+}
+
+#[tokio::test]
+async fn subscribe_reports_status_transitions_and_closes_at_terminal() {
+    let engine = engine_with_prover(Arc::new(SlowProver(Duration::from_millis(100))));
+    let receipt_id = engine.submit(base_order(1)).await.expect("submit");
+    let mut statuses = engine.subscribe(&receipt_id).await.expect("subscribe");
+    let mut seen = vec![*statuses.borrow_and_update()];
+    while tokio::time::timeout(Duration::from_secs(5), statuses.changed())
+        .await
+        .expect("subscription should not stall")
+        .is_ok()
+    {
+        seen.push(*statuses.borrow_and_update());
+    }
+    assert_eq!(seen.last(), Some(&ReceiptStatus::PROVED));
+    assert!(seen.windows(2).all(|pair| pair[0] != pair[1]));
+
+    // A receipt that is already terminal yields its status and a closed channel.
+    let mut settled = engine.subscribe(&receipt_id).await.expect("subscribe");
+    assert_eq!(*settled.borrow_and_update(), ReceiptStatus::PROVED);
+    assert!(settled.changed().await.is_err());
+    assert!(engine.subscribe("missing-receipt").await.is_err());
+}
+
+#[tokio::test]
+async fn cancel_stops_the_pipeline_task_and_settles_the_receipt_as_cancelled() {
+    let engine = engine_with_prover(Arc::new(SlowProver(Duration::from_secs(30))));
+    let receipt_id = engine.submit(base_order(1)).await.expect("submit");
+    for _ in 0..100 {
+        if engine.proof_queue_stats().running == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(engine.proof_queue_stats().running, 1);
+    assert_eq!(engine.in_flight_count(), 1);
+
+    let cancelled = tokio::time::timeout(Duration::from_secs(1), engine.cancel(&receipt_id))
+        .await
+        .expect("cancel should not wait for the prover")
+        .expect("cancel");
+    assert_eq!(cancelled.status, ReceiptStatus::CANCELLED);
+    assert!(cancelled.non_provable.is_none());
+    assert!(cancelled.integrity.issued_at.is_some());
+    assert!(engine.keyring().verify_receipt_signature(&cancelled));
+    assert_eq!(engine.in_flight_count(), 0);
+    assert_eq!(engine.proof_queue_stats().running, 0);
+    assert_eq!(
+        engine
+            .get_receipt(&receipt_id)
+            .await
+            .expect("receipt")
+            .status,
+        ReceiptStatus::CANCELLED
+    );
+    assert!(engine.cancel(&receipt_id).await.is_err());
+
+    let settled = engine_with_prover(Arc::new(Sp1MvpProver));
+    let proved = settled.submit(base_order(2)).await.expect("submit");
+    settled
+        .wait_for_receipt(&proved, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(settled
+        .cancel(&proved)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("only in-flight receipts"));
+    assert!(settled.cancel("missing-receipt").await.is_err());
+}
+
+#[tokio::test]
+async fn payload_violating_claim_schema_is_schema_invalid() {
+    let engine = engine();
+    assert!(engine.payload_schemas().get("BEST_EXECUTION").is_some());
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some("exec-18".to_string()),
+            payload: serde_json::json!({
+                "execution_price": "100.0",
+                "reference_price": 100.0
+            }),
+            ..ProofRequest::new(
+                Venue::Hyperliquid,
+                ClaimType::BEST_EXECUTION,
+                "acct-18",
+                "order-18",
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine.get_receipt(&receipt_id).await.expect("receipt");
+    let non_provable = receipt.non_provable.as_ref().expect("non provable present");
+    assert_eq!(non_provable.reason_code, NonProvableReason::SCHEMA_INVALID);
+    assert!(non_provable
+        .details
+        .contains("payload.execution_price must be of type number"));
+    assert!(non_provable
+        .details
+        .contains("payload.max_slippage_bps is required"));
+}
+
+#[tokio::test]
+async fn adapter_certification_flags_malformed_evidence() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let fixtures = load_adapter_fixtures(&root.join(ADAPTER_FIXTURES_PATH)).expect("fixtures");
+    let policy = PolicyEngine::new(None).expect("policy should load");
+
+    let synthetic = certify_adapter(
+        &SyntheticVenueAdapter::new(Venue::Solana),
+        &policy,
+        &fixtures,
+    )
+    .await;
+    assert!(synthetic
+        .iter()
+        .all(|o| o.verdict == FixtureVerdict::Passed));
+
+    let garbage = certify_adapter(&GarbageEvidenceAdapter, &policy, &fixtures).await;
+    assert!(garbage
+        .iter()
+        .any(|o| matches!(&o.verdict, FixtureVerdict::Failed(detail) if detail.contains("malformed evidence"))));
+    assert!(garbage
+        .iter()
+        .any(|o| matches!(o.verdict, FixtureVerdict::Skipped(_))));
+}
+
+fn base_order(n: usize) -> ProofRequest {
+    ProofRequest::new(
+        Venue::Base,
+        ClaimType::ORDER_PLACED,
+        format!("acct-finality-{}", n),
+        format!("order-finality-{}", n),
+    )
+}
+
+#[tokio::test]
+async fn finality_observer_releases_many_pending_receipts() {
+    let observer = FinalityObserver::new(Duration::from_millis(2))
+        .with_head_source(Venue::Base, Arc::new(SyntheticChainHead::new(1_000, 1)));
+    let engine = engine().with_finality_observer(observer.clone(), Duration::from_secs(5));
+    let mut receipt_ids = Vec::new();
+    for n in 0..50 {
+        receipt_ids.push(engine.submit(base_order(n)).await.expect("submit"));
+    }
+    for receipt_id in &receipt_ids {
+        let receipt = engine
+            .wait_for_receipt(receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(receipt.status, ReceiptStatus::PROVED);
+        assert!(receipt.timing.finality_observed_at.is_some());
+    }
+    assert_eq!(observer.pending(Venue::Base).await, 0);
+}
+
+#[tokio::test]
+async fn stalled_chain_head_yields_finality_timeout() {
+    let observer = FinalityObserver::new(Duration::from_millis(2))
+        .with_head_source(Venue::Base, Arc::new(SyntheticChainHead::new(1_000, 0)));
+    let engine = engine().with_finality_observer(observer.clone(), Duration::from_millis(50));
+    let receipt_id = engine.submit(base_order(99)).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.as_ref().expect("non provable present");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::FINALITY_TIMEOUT
+    );
+    assert!(non_provable.remediation.reprove_may_help);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(observer.pending(Venue::Base).await, 0);
+}
+
+fn scripted_base_engine(finality_timeout: Duration) -> (ReceiptEngine, Arc<SyntheticVenueAdapter>) {
+    let base = Arc::new(SyntheticVenueAdapter::new(Venue::Base));
+    let observer =
+        FinalityObserver::new(Duration::from_millis(2)).with_head_source(Venue::Base, base.clone());
+    let engine = ReceiptEngine::new(
+        vec![base.clone()],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_finality_observer(observer, finality_timeout);
+    (engine, base)
+}
+
+#[tokio::test]
+async fn scripted_synthetic_scenarios_drive_the_engine_state_machine() {
+    let (engine, base) = scripted_base_engine(Duration::from_secs(5));
+    let mut request = base_order(400);
+    request.payload = serde_json::json!({
+        "script": {
+            "ack_delay_ms": 25,
+            "fail_acknowledge": [1],
+            "fail_collect_evidence": [1],
+            "finality_after_polls": 5
+        }
+    });
+    let started = std::time::Instant::now();
+    let receipt_id = engine.submit(request).await.expect("submit");
+    let first = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(started.elapsed() >= Duration::from_millis(25));
+    let failure = first.non_provable.as_ref().expect("non provable");
+    assert_eq!(failure.reason_code, NonProvableReason::SOURCE_UNAVAILABLE);
+    assert_eq!(
+        failure.remediation.failed_source.as_deref(),
+        Some("Base adapter acknowledge")
+    );
+
+    engine
+        .retry(&receipt_id)
+        .await
+        .expect("retry after acknowledge failure");
+    let second = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let failure = second.non_provable.as_ref().expect("non provable");
+    assert_eq!(
+        failure.remediation.failed_source.as_deref(),
+        Some("Base adapter collect_evidence")
+    );
+
+    let head_before = base.head().await.expect("head");
+    engine
+        .retry(&receipt_id)
+        .await
+        .expect("retry after collection failure");
+    let proved = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(proved.status, ReceiptStatus::PROVED);
+    assert!(proved.timing.finality_observed_at.is_some());
+    assert_eq!(proved.provenance.prior_attempts.len(), 2);
+    assert!(base.head().await.expect("head") > head_before + 5);
+
+    let mut partial = base_order(401);
+    partial.payload =
+        serde_json::json!({ "script": { "evidence_phases": [{ "tags": ["order_identity"] }] } });
+    let receipt_id = engine.submit(partial).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.non_provable.expect("non provable").reason_code,
+        NonProvableReason::EVIDENCE_MISSING
+    );
+
+    let mut conflicted = base_order(402);
+    conflicted.payload =
+        serde_json::json!({ "script": { "evidence_phases": [{ "conflict": true }] } });
+    let receipt_id = engine.submit(conflicted).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.non_provable.expect("non provable").reason_code,
+        NonProvableReason::EVIDENCE_CONFLICT
+    );
+
+    let mut malformed = base_order(403);
+    malformed.payload = serde_json::json!({ "script": { "fail_everything": true } });
+    let receipt_id = engine.submit(malformed).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(receipt
+        .non_provable
+        .expect("non provable")
+        .details
+        .contains("invalid synthetic scenario script"));
+
+    let (slow_engine, _) = scripted_base_engine(Duration::from_millis(50));
+    let mut slow = base_order(404);
+    slow.payload = serde_json::json!({ "script": { "finality_after_polls": 100_000 } });
+    let receipt_id = slow_engine.submit(slow).await.expect("submit");
+    let receipt = slow_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.non_provable.expect("non provable").reason_code,
+        NonProvableReason::FINALITY_TIMEOUT
+    );
+
+    // Finality timeouts are advertised as retryable, so retry accepts them.
+    slow_engine
+        .retry(&receipt_id)
+        .await
+        .expect("retry after finality timeout");
+    let receipt = slow_engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let attempts = &receipt.provenance.prior_attempts;
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].reason_code, NonProvableReason::FINALITY_TIMEOUT);
+}
+
+#[tokio::test]
+async fn every_receipt_write_is_rehashed_and_resigned_from_its_contents() {
+    let engine = engine();
+    let receipt_id = proved_trade(&engine, 980).await;
+    let proved = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert!(proved.verify_integrity(None).is_valid());
+    assert!(proved.integrity.sequence > 0);
+
+    // Anchoring changes no hashed field, so the receipt keeps its issuance.
+    engine
+        .anchor_receipts(std::slice::from_ref(&receipt_id), &SyntheticAnchorPublisher)
+        .await
+        .expect("anchor");
+    let anchored = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(
+        anchored.proof.verification_mode,
+        VerificationMode::OFFCHAIN_AND_ANCHORED
+    );
+    assert_eq!(anchored.integrity.sequence, proved.integrity.sequence);
+    assert_eq!(anchored.integrity.issued_at, proved.integrity.issued_at);
+    assert!(anchored.verify_integrity(None).is_valid());
+
+    let revoked = engine
+        .invalidate(&receipt_id, "dispute upheld", "ops")
+        .await
+        .expect("invalidate");
+    assert!(revoked.integrity.sequence > proved.integrity.sequence);
+    assert!(revoked.verify_integrity(None).is_valid());
+    let issued = engine.issuance_log(&revoked.integrity.signer.key_id);
+    assert_eq!(
+        issued.iter().filter(|e| e.receipt_id == receipt_id).count(),
+        2
+    );
+
+    // A retried receipt is unissued while pending and issued afresh when it settles.
+    let mut request = base_order(980);
+    request.payload = serde_json::json!({ "script": { "fail_acknowledge": [1] } });
+    let receipt_id = engine.submit(request).await.expect("submit");
+    let failed = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(failed.integrity.sequence > 0);
+    let pending = engine.retry(&receipt_id).await.expect("retry");
+    assert_eq!(
+        (
+            pending.integrity.sequence,
+            pending.integrity.issued_at.as_deref()
+        ),
+        (0, None)
+    );
+    assert!(pending.verify_integrity(None).is_valid());
+    let settled = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(settled.status, ReceiptStatus::PROVED);
+    assert!(settled.integrity.sequence > failed.integrity.sequence);
+    assert!(settled.verify_integrity(None).is_valid());
+}
+
+#[tokio::test]
+async fn anchored_receipts_carry_verifiable_inclusion_proofs() {
+    let engine = engine();
+    let mut receipt_ids = Vec::new();
+    for n in 0..5 {
+        let receipt_id = engine.submit(base_order(100 + n)).await.expect("submit");
+        engine
+            .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        receipt_ids.push(receipt_id);
+    }
+    let batch = engine
+        .anchor_receipts(&receipt_ids, &SyntheticAnchorPublisher)
+        .await
+        .expect("anchor");
+    for receipt_id in &receipt_ids {
+        let exported: zkputer::ZKReceipt = serde_json::from_str(
+            &serde_json::to_string(&engine.get_receipt(receipt_id).await.expect("receipt"))
+                .expect("export"),
+        )
+        .expect("import");
+        let inclusion = exported
+            .proof
+            .anchor_inclusion
+            .as_ref()
+            .expect("inclusion proof");
+        assert_eq!(inclusion.anchored_root, batch.root);
+        assert_eq!(Some(&inclusion.anchor_tx_ref), batch.anchor_tx_ref.as_ref());
+        assert!(verify_inclusion(
+            &exported.integrity.receipt_hash,
+            inclusion
+        ));
+        assert!(engine.keyring().verify_receipt_signature(&exported));
+    }
+
+    let mut unproved = base_order(200);
+    unproved.claim_type = ClaimType::TRADE_EXECUTED;
+    let unproved_id = engine.submit(unproved).await.expect("submit");
+    engine
+        .wait_for_receipt(&unproved_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(engine
+        .anchor_receipts(&[unproved_id], &SyntheticAnchorPublisher)
+        .await
+        .is_err());
+}
+
+async fn proved_trade(engine: &ReceiptEngine, n: usize) -> String {
+    let receipt_id = engine
+        .submit(ProofRequest {
+            execution_ref: Some(format!("exec-refresh-{}", n)),
+            ..ProofRequest::new(
+                Venue::Solana,
+                ClaimType::TRADE_EXECUTED,
+                format!("acct-refresh-{}", n),
+                format!("order-refresh-{}", n),
+            )
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    receipt_id
+}
+
+#[tokio::test]
+async fn refresh_evidence_reconfirms_reproves_or_invalidates() {
+    let adapter = Arc::new(CorrectableAdapter::new(Venue::Solana));
+    let engine = ReceiptEngine::new(
+        vec![adapter.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+
+    let unchanged = proved_trade(&engine, 1).await;
+    let refresh = engine.refresh_evidence(&unchanged).await.expect("refresh");
+    assert_eq!(refresh.action, RefreshAction::RECONFIRMED);
+    assert!(refresh.divergences.is_empty());
+
+    let amended = proved_trade(&engine, 2).await;
+    let original_root = engine
+        .get_receipt(&amended)
+        .await
+        .expect("receipt")
+        .provenance
+        .evidence_root;
+    adapter.apply("amended");
+    let refresh = engine.refresh_evidence(&amended).await.expect("refresh");
+    assert_eq!(refresh.action, RefreshAction::REPROVING);
+    assert!(refresh.divergences[0].contains("changed hash"));
+    let reproved = engine
+        .wait_for_receipt(&amended, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(reproved.status, ReceiptStatus::PROVED);
+    assert_ne!(reproved.provenance.evidence_root, original_root);
+
+    adapter.apply("busted");
+    let refresh = engine.refresh_evidence(&amended).await.expect("refresh");
+    assert_eq!(refresh.action, RefreshAction::INVALIDATED);
+    let invalidated = engine.get_receipt(&amended).await.expect("receipt");
+    assert_eq!(invalidated.status, ReceiptStatus::INVALIDATED);
+    assert!(engine.keyring().verify_receipt_signature(&invalidated));
+    assert!(engine.refresh_evidence(&amended).await.is_err());
+}
+
+#[tokio::test]
+async fn refresh_evidence_ignores_observation_times_in_hashed_artifacts() {
+    let request = ProofRequest {
+        execution_ref: Some("exec-restamp".to_string()),
+        payload: serde_json::json!({
+            "execution_price": 100.05,
+            "reference_price": 100.0,
+            "max_slippage_bps": 10
+        }),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::BEST_EXECUTION,
+            "acct-restamp",
+            "order-restamp",
+        )
+    };
+
+    // Acceptance and quote times stay part of what the artifact hashes commit to.
+    let adapter = SyntheticVenueAdapter::new(Venue::Hyperliquid);
+    let first = adapter.acknowledge(&request).await.expect("ack");
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let second = adapter.acknowledge(&request).await.expect("ack");
+    assert_ne!(
+        first.acceptance_artifact_hash,
+        second.acceptance_artifact_hash
+    );
+
+    let engine = engine();
+    let receipt_id = engine.submit(request).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert!(receipt
+        .provenance
+        .evidence_items
+        .iter()
+        .any(|item| item.tags.iter().any(|t| t == "reference_price_quote")));
+
+    // A re-fetch that differs only in those times is not a divergence.
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let refresh = engine.refresh_evidence(&receipt_id).await.expect("refresh");
+    assert_eq!(
+        refresh.action,
+        RefreshAction::RECONFIRMED,
+        "{:?}",
+        refresh.divergences
+    );
+    assert_eq!(
+        engine
+            .get_receipt(&receipt_id)
+            .await
+            .expect("receipt")
+            .status,
+        ReceiptStatus::PROVED
+    );
+}
+
+#[tokio::test]
+async fn adapter_responses_are_cached_per_order_and_refresh_bypasses_the_cache() {
+    let adapter = Arc::new(CountingAdapter::new(Venue::Solana));
+    let engine = ReceiptEngine::new(
+        vec![adapter.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_adapter_cache(Duration::from_secs(60));
+
+    // A retry of the same order is answered from the cache and bills no adapter calls.
+    let first = proved_trade(&engine, 1).await;
+    let retry = proved_trade(&engine, 1).await;
+    assert_eq!(adapter.calls(), (1, 1));
+    assert_eq!(
+        engine
+            .receipt_cost(&retry)
+            .await
+            .map_or(0, |c| c.adapter_calls),
+        0
+    );
+    let original_root = engine
+        .get_receipt(&first)
+        .await
+        .expect("receipt")
+        .provenance
+        .evidence_root;
+    assert_eq!(
+        engine
+            .get_receipt(&retry)
+            .await
+            .expect("receipt")
+            .provenance
+            .evidence_root,
+        original_root
+    );
+
+    // A different claim against the same order is not someone else's evidence.
+    let placed = engine
+        .submit(ProofRequest::new(
+            Venue::Solana,
+            ClaimType::ORDER_PLACED,
+            "acct-refresh-1",
+            "order-refresh-1",
+        ))
+        .await
+        .expect("submit");
+    engine
+        .wait_for_receipt(&placed, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(adapter.calls(), (2, 2));
+
+    // Refresh goes to the venue even with a warm cache, and later retries see what it fetched.
+    adapter.inner.apply("amended");
+    let refresh = engine.refresh_evidence(&first).await.expect("refresh");
+    assert_eq!(refresh.action, RefreshAction::REPROVING);
+    assert_eq!(adapter.calls(), (3, 3));
+    engine
+        .wait_for_receipt(&first, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let after_refresh = proved_trade(&engine, 1).await;
+    assert_eq!(adapter.calls(), (3, 3));
+    assert_ne!(
+        engine
+            .get_receipt(&after_refresh)
+            .await
+            .expect("receipt")
+            .provenance
+            .evidence_root,
+        original_root
+    );
+
+    // The re-prove the refresh kicked off is itself a hit on the evidence the refresh just stored.
+    let stats = engine.adapter_cache_stats().expect("cache configured");
+    assert_eq!((stats.hits, stats.misses, stats.entries), (3, 2, 1));
+    assert!(engine.invalidate_adapter_cache(Venue::Solana, "order-refresh-1"));
+    proved_trade(&engine, 1).await;
+    assert_eq!(adapter.calls(), (4, 4));
+
+    let short_lived = ReceiptEngine::new(
+        vec![adapter.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_adapter_cache(Duration::from_millis(20));
+    proved_trade(&short_lived, 2).await;
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    proved_trade(&short_lived, 2).await;
+    assert_eq!(adapter.calls(), (6, 6));
+}
+
+#[tokio::test]
+async fn registered_claim_handlers_shape_statements_evidence_and_public_inputs() {
+    let plugged = engine().with_claim_handler(Arc::new(DeskOrderHandler {
+        required_tags: vec![],
+    }));
+    let receipt_id = plugged.submit(base_order(1)).await.expect("submit");
+    let receipt = plugged
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert!(receipt
+        .claim
+        .statement
+        .starts_with("Desk order order-finality-1 was live at"));
+    // The handler's layout is what the proof commits to, so only a verifier holding it accepts the receipt.
+    assert!(!OffchainVerifier::default().verify(&receipt).await);
+    assert!(
+        OffchainVerifier::default()
+            .verify_with_handlers(&receipt, plugged.claim_handlers())
+            .await
+    );
+    assert!(
+        plugged
+            .dispute_bundle(&receipt_id)
+            .await
+            .expect("bundle")
+            .proof_valid
+    );
+
+    // Claims without a handler keep the built-in behaviour.
+    let trade = proved_trade(&plugged, 1).await;
+    assert!(
+        OffchainVerifier::default()
+            .verify(&plugged.get_receipt(&trade).await.expect("receipt"))
+            .await
+    );
+
+    let strict = engine().with_claim_handler(Arc::new(DeskOrderHandler {
+        required_tags: vec!["desk_risk_check".to_string()],
+    }));
+    let receipt_id = strict.submit(base_order(2)).await.expect("submit");
+    let receipt = strict
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    let non_provable = receipt.non_provable.expect("reason");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::EVIDENCE_MISSING
+    );
+    assert!(non_provable
+        .details
+        .contains("ORDER_PLACED claim handler: desk_risk_check"));
+    assert_eq!(
+        non_provable.remediation.missing_tags,
+        vec!["desk_risk_check".to_string()]
+    );
+}
+
+async fn exported_archive() -> (ReceiptEngine, Vec<String>) {
+    let dir = std::env::temp_dir().join(format!("zkputer-export-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("artifact dir");
+    let engine = engine_with_prover(Arc::new(FileArtifactProver {
+        dir,
+        artifact_bytes: 150 * 1024,
+    }));
+    let mut ids = Vec::new();
+    for n in 0..5 {
+        ids.push(proved_trade(&engine, n).await);
+    }
+    // Anchoring without a publisher is rejected up front, leaving one NON_PROVABLE receipt.
+    let mut rejected = base_order(9);
+    rejected.verification_mode = VerificationMode::ONCHAIN_ANCHORED;
+    engine.submit(rejected).await.expect("submit");
+    (engine, ids)
+}
+
+async fn drain(mut items: tokio::sync::mpsc::Receiver<Result<ExportItem>>) -> Vec<ExportItem> {
+    let mut drained = Vec::new();
+    while let Some(item) = items.recv().await {
+        drained.push(item.expect("export item"));
+    }
+    drained
+}
+
+#[tokio::test]
+async fn receipt_exports_page_through_the_store_resume_from_a_cursor_and_chunk_artifacts() {
+    let (engine, proved) = exported_archive().await;
+    let only_proved = ReceiptQuery {
+        status: Some(ReceiptStatus::PROVED),
+        ..ReceiptQuery::default()
+    };
+
+    let items = drain(engine.export_receipts(ReceiptExport {
+        query: only_proved.clone(),
+        page_size: 2,
+        ..ReceiptExport::default()
+    }))
+    .await;
+    let (exported, cursors): (Vec<String>, Vec<ExportCursor>) = items
+        .iter()
+        .map(|item| match item {
+            ExportItem::Receipt { receipt, cursor } => (receipt.receipt_id.clone(), cursor.clone()),
+            ExportItem::ArtifactChunk(_) => panic!("artifacts were not requested"),
+        })
+        .unzip();
+    assert_eq!(exported.len(), 5);
+    assert!(cursors.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        exported.iter().collect::<std::collections::HashSet<_>>(),
+        proved.iter().collect()
+    );
+
+    // A second export resumes after the cursor of the last receipt the first one delivered.
+    let first_two = drain(engine.export_receipts(ReceiptExport {
+        query: ReceiptQuery {
+            limit: Some(2),
+            ..only_proved.clone()
+        },
+        page_size: 1,
+        ..ReceiptExport::default()
+    }))
+    .await;
+    assert_eq!(first_two.len(), 2);
+    let ExportItem::Receipt { cursor, .. } = &first_two[1] else {
+        panic!("expected a receipt");
+    };
+    let cursor = ExportCursor::decode(&cursor.encode()).expect("cursor round trips");
+    let rest = drain(engine.export_receipts(ReceiptExport {
+        query: only_proved.clone(),
+        after: Some(cursor),
+        ..ReceiptExport::default()
+    }))
+    .await;
+    let resumed: Vec<String> = first_two
+        .iter()
+        .chain(rest.iter())
+        .filter_map(|item| match item {
+            ExportItem::Receipt { receipt, .. } => Some(receipt.receipt_id.clone()),
+            ExportItem::ArtifactChunk(_) => None,
+        })
+        .collect();
+    assert_eq!(resumed, exported);
+
+    // Each receipt is followed by its artifact in bounded chunks that reassemble to the file.
+    let with_artifacts = drain(engine.export_receipts(ReceiptExport {
+        query: ReceiptQuery {
+            limit: Some(1),
+            ..only_proved
+        },
+        include_artifacts: true,
+        ..ReceiptExport::default()
+    }))
+    .await;
+    let ExportItem::Receipt { receipt, .. } = &with_artifacts[0] else {
+        panic!("the receipt comes before its artifact");
+    };
+    let chunks: Vec<&ArtifactChunk> = with_artifacts[1..]
+        .iter()
+        .map(|item| match item {
+            ExportItem::ArtifactChunk(chunk) => chunk,
+            ExportItem::Receipt { .. } => panic!("limit was one receipt"),
+        })
+        .collect();
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks
+        .iter()
+        .all(|c| c.receipt_id == receipt.receipt_id && c.data.len() <= ARTIFACT_CHUNK_BYTES));
+    assert_eq!(chunks.iter().filter(|c| c.last).count(), 1);
+    assert!(chunks[2].last);
+    assert_eq!(chunks[2].offset, 2 * ARTIFACT_CHUNK_BYTES as u64);
+    let artifact_path = receipt
+        .proof
+        .proof_artifact_ref
+        .as_deref()
+        .expect("artifact")
+        .trim_start_matches("file://");
+    let reassembled: Vec<u8> = chunks.iter().flat_map(|c| c.data.iter().copied()).collect();
+    assert_eq!(
+        reassembled,
+        std::fs::read(artifact_path).expect("artifact file")
+    );
+
+    // Everything, in created order; the NON_PROVABLE receipt is included without a status filter.
+    let everything = drain(engine.export_receipts(ReceiptExport::default())).await;
+    assert_eq!(everything.len(), 6);
+}
+
+#[cfg(feature = "grpc")]
+#[tokio::test]
+async fn grpc_export_streams_receipts_and_artifact_chunks() {
+    use zkputer::grpc::{
+        export_receipts_response::Item, serve, ExportReceiptsRequest, ReceiptArchiveClient,
+    };
+
+    let (engine, proved) = exported_archive().await;
+    let engine = Arc::new(engine);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(serve(engine.clone(), listener, async {
+        let _ = stopped.await;
+    }));
+    let mut client = ReceiptArchiveClient::connect(addr).await.expect("connect");
+
+    let mut stream = client
+        .export_receipts(ExportReceiptsRequest {
+            status: Some("PROVED".to_string()),
+            page_size: 2,
+            include_artifacts: true,
+            ..ExportReceiptsRequest::default()
+        })
+        .await
+        .expect("export");
+    let mut receipts = Vec::new();
+    let mut artifact_bytes = 0;
+    while let Some(message) = stream.message().await.expect("message") {
+        match message.item.expect("item") {
+            Item::Receipt(exported) => {
+                let receipt: zkputer::ZKReceipt =
+                    serde_json::from_str(&exported.receipt_json).expect("receipt json");
+                assert!(engine.keyring().verify_receipt_signature(&receipt));
+                assert_eq!(exported.receipt_id, receipt.receipt_id);
+                receipts.push(receipt.receipt_id);
+            }
+            Item::ArtifactChunk(chunk) => {
+                assert_eq!(Some(&chunk.receipt_id), receipts.last());
+                artifact_bytes += chunk.data.len();
+            }
+        }
+    }
+    assert_eq!(
+        receipts.iter().collect::<std::collections::HashSet<_>>(),
+        proved.iter().collect()
+    );
+    assert_eq!(artifact_bytes, 5 * 150 * 1024);
+
+    let rejected = client
+        .export_receipts(ExportReceiptsRequest {
+            venue: Some("nasdaq".to_string()),
+            ..ExportReceiptsRequest::default()
+        })
+        .await;
+    assert_eq!(
+        rejected.err().map(|status| status.code()),
+        Some(tonic::Code::InvalidArgument)
+    );
+
+    let _ = stop.send(());
+    server.await.expect("join").expect("serve");
+}
+
+#[tokio::test]
+async fn venue_correction_invalidates_and_supersedes_trade_receipt() {
+    let adapter = Arc::new(SyntheticVenueAdapter::new(Venue::Solana));
+    let engine = ReceiptEngine::new(
+        vec![adapter.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let busted = proved_trade(&engine, 10).await;
+    let amended = proved_trade(&engine, 11).await;
+    let untouched = proved_trade(&engine, 12).await;
+
+    adapter.publish_correction(TradeCorrection {
+        venue: Venue::Solana,
+        order_ref: "order-refresh-10".to_string(),
+        execution_ref: "exec-refresh-10".to_string(),
+        correction_ref: "bust-10".to_string(),
+        kind: CorrectionKind::Busted,
+        observed_at: "2026-01-01T00:00:00Z".to_string(),
+    });
+    adapter.publish_correction(TradeCorrection {
+        venue: Venue::Solana,
+        order_ref: "order-refresh-11".to_string(),
+        execution_ref: "exec-refresh-11".to_string(),
+        correction_ref: "amend-11".to_string(),
+        kind: CorrectionKind::Amended {
+            amended_price: Some(99.5),
+            amended_size: None,
+        },
+        observed_at: "2026-01-01T00:00:00Z".to_string(),
+    });
+
+    // Originals stand until their superseding receipts are proved.
+    let issued = engine.process_corrections().await.expect("corrections");
+    assert_eq!(issued.len(), 2);
+    for outcome in &issued {
+        let original = engine
+            .get_receipt(&outcome.original_receipt_id)
+            .await
+            .expect("receipt");
+        if outcome.superseding_status != ReceiptStatus::PROVED {
+            assert_eq!(original.status, ReceiptStatus::PROVED);
+        }
+        engine
+            .wait_for_receipt(&outcome.superseding_receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+    }
+    let outcomes = engine.process_corrections().await.expect("corrections");
+    assert_eq!(outcomes.len(), 2);
+    for (original_id, claim_type) in [
+        (&busted, ClaimType::TRADE_BUSTED),
+        (&amended, ClaimType::TRADE_AMENDED),
+    ] {
+        let outcome = outcomes
+            .iter()
+            .find(|o| &o.original_receipt_id == original_id)
+            .expect("outcome");
+        assert_eq!(outcome.superseding_status, ReceiptStatus::PROVED);
+        assert!(
+            issued
+                .iter()
+                .any(|o| o.superseding_receipt_id == outcome.superseding_receipt_id),
+            "reused, not reissued"
+        );
+        let original = engine.get_receipt(original_id).await.expect("receipt");
+        assert_eq!(original.status, ReceiptStatus::INVALIDATED);
+        assert_eq!(
+            original.superseded_by.as_deref(),
+            Some(outcome.superseding_receipt_id.as_str())
+        );
+        let superseding = engine
+            .get_receipt(&outcome.superseding_receipt_id)
+            .await
+            .expect("receipt");
+        assert_eq!(superseding.status, ReceiptStatus::PROVED);
+        assert_eq!(superseding.claim.r#type, claim_type);
+        assert_eq!(
+            superseding.supersedes.as_deref(),
+            Some(original_id.as_str())
+        );
+        if claim_type == ClaimType::TRADE_AMENDED {
+            assert!(superseding
+                .claim
+                .statement
+                .contains("amended to price 99.5"));
+        }
+    }
+    assert_eq!(
+        engine
+            .get_receipt(&untouched)
+            .await
+            .expect("receipt")
+            .status,
+        ReceiptStatus::PROVED
+    );
+    assert!(engine
+        .process_corrections()
+        .await
+        .expect("corrections")
+        .is_empty());
+
+    // A feed that fails is reported, and does not keep the other feeds from being worked through.
+    let adapter = Arc::new(SyntheticVenueAdapter::new(Venue::Solana));
+    let engine = ReceiptEngine::new(
+        vec![
+            adapter.clone() as Arc<dyn VenueAdapter>,
+            Arc::new(UnreachableAdapter),
+        ],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let original = proved_trade(&engine, 13).await;
+    adapter.publish_correction(TradeCorrection {
+        venue: Venue::Solana,
+        order_ref: "order-refresh-13".to_string(),
+        execution_ref: "exec-refresh-13".to_string(),
+        correction_ref: "bust-13".to_string(),
+        kind: CorrectionKind::Busted,
+        observed_at: "2026-01-01T00:00:00Z".to_string(),
+    });
+    let err = engine
+        .process_corrections()
+        .await
+        .expect_err("one feed is down");
+    assert!(err.to_string().contains("Hyperliquid"), "{}", err);
+    let issues = engine.health().await.issues;
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.starts_with("venue corrections failed")),
+        "{:?}",
+        issues
+    );
+    let superseding = engine
+        .list_receipts(&ReceiptQuery {
+            claim_type: Some(ClaimType::TRADE_BUSTED),
+            ..ReceiptQuery::default()
+        })
+        .await
+        .expect("list");
+    assert_eq!(superseding.len(), 1);
+    assert_eq!(
+        superseding[0].supersedes.as_deref(),
+        Some(original.as_str())
+    );
+}
+
+#[tokio::test]
+async fn dispute_bundle_packages_receipt_lineage_and_keys_for_export() {
+    let adapter = Arc::new(SyntheticVenueAdapter::new(Venue::Solana));
+    let disputing = ReceiptEngine::new(
+        vec![adapter.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let original_id = proved_trade(&disputing, 20).await;
+    adapter.publish_correction(TradeCorrection {
+        venue: Venue::Solana,
+        order_ref: "order-refresh-20".to_string(),
+        execution_ref: "exec-refresh-20".to_string(),
+        correction_ref: "bust-20".to_string(),
+        kind: CorrectionKind::Busted,
+        observed_at: "2026-01-01T00:00:00Z".to_string(),
+    });
+    let outcome = disputing
+        .process_corrections()
+        .await
+        .expect("corrections")
+        .remove(0);
+    disputing
+        .wait_for_receipt(&outcome.superseding_receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    disputing.process_corrections().await.expect("corrections");
+
+    let bundle = disputing
+        .dispute_bundle(&original_id)
+        .await
+        .expect("bundle");
+    assert_eq!(bundle.receipt.status, ReceiptStatus::INVALIDATED);
+    assert_eq!(
+        bundle.request.as_ref().map(|r| r.order_ref.as_str()),
+        Some("order-refresh-20")
+    );
+    assert_eq!(
+        bundle
+            .lineage
+            .iter()
+            .map(|r| r.receipt_id.as_str())
+            .collect::<Vec<_>>(),
+        vec![outcome.superseding_receipt_id.as_str()]
+    );
+    assert!(bundle.integrity_valid);
+    assert!(
+        !bundle.proof_valid,
+        "an invalidated receipt no longer stands as proved"
+    );
+    assert_eq!(bundle.key_manifest.keys.len(), 1);
+    assert_eq!(bundle.compute_hash(), bundle.bundle_hash);
+
+    let superseding = disputing
+        .dispute_bundle(&outcome.superseding_receipt_id)
+        .await
+        .expect("bundle");
+    assert!(superseding.proof_valid);
+    assert_eq!(superseding.lineage[0].receipt_id, original_id);
+    assert!(disputing.dispute_bundle("no-such-receipt").await.is_err());
+
+    let dir = std::env::temp_dir().join(format!("zkputer-disputes-{}", uuid::Uuid::new_v4()));
+    match export_dispute_bundle(&bundle, DEFAULT_DISPUTE_INLINE_LIMIT, &dir).expect("export") {
+        DisputeExport::Inline {
+            size_bytes, base64, ..
+        } => {
+            assert_eq!(base64.len(), size_bytes.div_ceil(3) * 4);
+        }
+        other => panic!("expected an inline export, got {:?}", other),
+    }
+    match export_dispute_bundle(&bundle, 64, &dir).expect("export") {
+        DisputeExport::Reference { uri, sha256, .. } => {
+            let path = uri.strip_prefix("file://").expect("file uri");
+            let written = std::fs::read(path).expect("bundle file");
+            assert_eq!(zkputer::models::hash_bytes(&written), sha256);
+            let parsed: DisputeBundle = serde_json::from_slice(&written).expect("bundle json");
+            assert_eq!(parsed.bundle_hash, bundle.bundle_hash);
+        }
+        other => panic!("expected a file reference, got {:?}", other),
+    }
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn backfill_resumes_from_checkpoint() {
+    let input = (1..=6)
+        .map(|n| {
+            if n == 4 {
+                "{\"venue\":\"base\",\"claim_type\":\"ORDER_PLACED\"}".to_string()
+            } else {
+                format!(
+                    "{{\"venue\":\"base\",\"claim_type\":\"ORDER_PLACED\",\"account_ref\":\"acct-bf\",\"order_ref\":\"order-bf-{}\"}}",
+                    n
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let records = parse_backfill_input(&input, BackfillFormat::Jsonl).expect("parse");
+    let checkpoint_path =
+        std::env::temp_dir().join(format!("zkputer-backfill-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&checkpoint_path);
+    let options = BackfillOptions {
+        batch_size: 2,
+        ..BackfillOptions::default()
+    };
+
+    let first = run_backfill(&engine(), &records[..3], Some(&checkpoint_path), &options)
+        .await
+        .expect("first run");
+    assert_eq!(first.next_line, 4);
+    assert_eq!(first.proved, 3);
+
+    let resumed = run_backfill(&engine(), &records, Some(&checkpoint_path), &options)
+        .await
+        .expect("resumed run");
+    assert_eq!(resumed.next_line, 7);
+    assert_eq!(resumed.submitted, 5);
+    assert_eq!(resumed.proved, 5);
+    assert_eq!(resumed.errors.len(), 1);
+    assert_eq!(resumed.errors[0].line, 4);
+    assert_eq!(
+        BackfillCheckpoint::load(&checkpoint_path).expect("load"),
+        resumed
+    );
+    let _ = std::fs::remove_file(&checkpoint_path);
+
+    // Rows the engine refuses are recorded against their line, and the run carries on past them.
+    let standby =
+        engine().with_leader_election(LeaderElection::new("standby", Duration::from_secs(30)));
+    let refused = run_backfill(&standby, &records[..2], Some(&checkpoint_path), &options)
+        .await
+        .expect("refused run");
+    assert_eq!((refused.next_line, refused.submitted), (3, 0));
+    assert_eq!(
+        refused.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert!(
+        refused.errors[0].error.contains("standby"),
+        "{}",
+        refused.errors[0].error
+    );
+    assert_eq!(
+        BackfillCheckpoint::load(&checkpoint_path).expect("load"),
+        refused
+    );
+    let _ = std::fs::remove_file(&checkpoint_path);
+}
+
+#[tokio::test]
+async fn repeated_adapter_failures_open_the_circuit_breaker() {
+    let engine = ReceiptEngine::new(
+        vec![
+            Arc::new(UnreachableAdapter),
+            Arc::new(SyntheticVenueAdapter::new(Venue::Base)),
+        ],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_circuit_breaker(2, Duration::from_secs(60));
+    let request = |n: usize| ProofRequest {
+        venue: Venue::Hyperliquid,
+        ..base_order(n)
+    };
+    for n in 0..2 {
+        let id = engine.submit(request(n)).await.expect("submit");
+        let receipt = engine
+            .wait_for_receipt(&id, Duration::from_secs(2))
+            .await
+            .expect("terminal");
+        assert!(receipt
+            .non_provable
+            .unwrap()
+            .details
+            .contains("unreachable"));
+    }
+
+    let id = engine.submit(request(2)).await.expect("submit");
+    let rejected = engine.get_receipt(&id).await.expect("stored");
+    let non_provable = rejected.non_provable.expect("fail fast");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::SOURCE_UNAVAILABLE
+    );
+    assert_eq!(
+        non_provable.remediation.failed_source.as_deref(),
+        Some("Hyperliquid circuit breaker")
+    );
+    assert!(non_provable.remediation.retry_after_ms.is_some());
+
+    let report = engine.health().await;
+    assert!(report.ready);
+    assert_eq!(report.status, HealthStatus::DEGRADED);
+    let hyperliquid = report
+        .adapters
+        .iter()
+        .find(|a| a.venue == Venue::Hyperliquid)
+        .unwrap();
+    assert_eq!(hyperliquid.circuit, CircuitState::Open);
+    assert_eq!(hyperliquid.consecutive_failures, 2);
+    assert_eq!(report.store.receipts, 3);
+
+    // Once the cooldown passes, the half-open breaker lets exactly one trial request through.
+    let breakers = CircuitBreakers::new(1, Duration::from_millis(100));
+    breakers.record_failure(Venue::Hyperliquid).await;
+    assert!(!breakers.admit(Venue::Hyperliquid).await);
+    tokio::time::sleep(Duration::from_millis(120)).await;
+    assert_eq!(
+        breakers.state(Venue::Hyperliquid).await,
+        CircuitState::HalfOpen
+    );
+    assert!(
+        breakers.admit(Venue::Hyperliquid).await,
+        "the trial request"
+    );
+    assert!(
+        !breakers.admit(Venue::Hyperliquid).await,
+        "turned away while the trial runs"
+    );
+    breakers.record_failure(Venue::Hyperliquid).await;
+    assert_eq!(breakers.state(Venue::Hyperliquid).await, CircuitState::Open);
+    tokio::time::sleep(Duration::from_millis(120)).await;
+    assert!(breakers.admit(Venue::Hyperliquid).await);
+    breakers.record_success(Venue::Hyperliquid).await;
+    assert!(breakers.admit(Venue::Hyperliquid).await && breakers.admit(Venue::Hyperliquid).await);
+}
+
+async fn http_get(addr: std::net::SocketAddr, path: &str) -> (u16, Value) {
+    let (status, _, body) = http_call(addr, "GET", path, None, &[]).await;
+    (status, body)
+}
+
+// Returns the status, the response head and the JSON body.
+async fn http_call(
+    addr: std::net::SocketAddr,
+    method: &str,
+    target: &str,
+    token: Option<&str>,
+    body: &[u8],
+) -> (u16, String, Value) {
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    let auth = token
+        .map(|t| format!("Authorization: Bearer {}\r\n", t))
+        .unwrap_or_default();
+    let head = format!(
+        "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\n\r\n",
+        method,
+        target,
+        auth,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await.expect("write");
+    stream.write_all(body).await.expect("write");
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).await.expect("read");
+    let status = raw.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    (
+        status,
+        head.to_string(),
+        serde_json::from_str(body).expect("json body"),
+    )
+}
+
+#[tokio::test]
+async fn health_endpoints_report_liveness_and_readiness() {
+    let serve_engine = |engine: ReceiptEngine| async move {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, Arc::new(HttpApi::new(Arc::new(engine)))));
+        addr
+    };
+
+    let healthy = serve_engine(engine()).await;
+    let (status, body) = http_get(healthy, "/readyz").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "HEALTHY");
+    assert_eq!(body["adapters"].as_array().unwrap().len(), 4);
+    assert_eq!(body["queues"]["pending"], 0);
+
+    let degraded = serve_engine(engine_with_prover(Arc::new(OfflineProver))).await;
+    let (status, body) = http_get(degraded, "/healthz").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["live"], true);
+    let (status, body) = http_get(degraded, "/readyz").await;
+    assert_eq!(status, 503);
+    assert_eq!(body["prover"]["available"], false);
+    assert_eq!(http_get(degraded, "/nope").await.0, 404);
+}
+
+#[tokio::test]
+async fn public_verification_endpoint_checks_hashes_and_receipts() {
+    let engine = Arc::new(engine());
+    let receipt_id = proved_trade(&engine, 1).await;
+    engine
+        .anchor_receipts(std::slice::from_ref(&receipt_id), &SyntheticAnchorPublisher)
+        .await
+        .expect("anchor");
+    let receipt = engine.get_receipt(&receipt_id).await.unwrap();
+    let limit = RateLimit {
+        per_minute: 1,
+        burst: 3,
+    };
+    let api = HttpApi::new(Arc::clone(&engine)).with_public_verify_limit(
+        limit,
+        RateLimit {
+            per_minute: 100,
+            burst: 100,
+        },
+    );
+    let get = |path: String| HttpRequest {
+        method: "GET".to_string(),
+        path,
+        query: None,
+        authorization: None,
+        peer: Some("203.0.113.7".parse().unwrap()),
+        body: vec![],
+        headers: vec![],
+    };
+
+    let by_hash = api
+        .route(&get(format!(
+            "/v1/verify/{}",
+            receipt.integrity.receipt_hash
+        )))
+        .await;
+    assert_eq!(by_hash.status, 200);
+    assert_eq!(by_hash.body["known"], true);
+    assert_eq!(by_hash.body["status"], "PROVED");
+    assert_eq!(by_hash.body["proof_valid"], true);
+    assert_eq!(by_hash.body["anchor_inclusion_valid"], true);
+    assert!(by_hash.body["anchor_tx_ref"].as_str().is_some());
+    assert!(by_hash.body.get("subject").is_none());
+
+    let mut tampered = receipt.clone();
+    tampered.status = ReceiptStatus::INVALIDATED;
+    let presented = api
+        .route(&HttpRequest {
+            method: "POST".to_string(),
+            body: serde_json::to_vec(&serde_json::json!({ "receipt": tampered })).unwrap(),
+            ..get("/v1/verify".to_string())
+        })
+        .await;
+    assert_eq!(presented.status, 200);
+    assert_eq!(presented.body["integrity_valid"], false);
+
+    let malformed = api.route(&get("/v1/verify/not-a-hash".to_string())).await;
+    assert_eq!(malformed.status, 400);
+    let limited = api
+        .route(&get(format!(
+            "/v1/verify/{}",
+            receipt.integrity.receipt_hash
+        )))
+        .await;
+    assert_eq!(limited.status, 429);
+    assert!(limited
+        .headers
+        .iter()
+        .any(|(name, _)| name == "Retry-After"));
+    let other_client = api
+        .route(&HttpRequest {
+            peer: Some("198.51.100.1".parse().unwrap()),
+            ..get(format!("/v1/verify/{}", receipt.integrity.receipt_hash))
+        })
+        .await;
+    assert_eq!(other_client.status, 200);
+}
+
+#[tokio::test]
+async fn cost_records_track_adapter_prover_and_anchoring_usage() {
+    let engine = engine();
+    let first = proved_trade(&engine, 1).await;
+    let second = proved_trade(&engine, 2).await;
+    let batch = engine
+        .anchor_receipts(&[first.clone(), second.clone()], &SyntheticAnchorPublisher)
+        .await
+        .expect("anchor");
+    let order = engine.submit(base_order(3)).await.expect("submit");
+    engine
+        .wait_for_receipt(&order, Duration::from_secs(2))
+        .await
+        .expect("terminal");
+
+    let cost = engine.receipt_cost(&first).await.expect("cost recorded");
+    assert_eq!(cost.adapter_calls, 2);
+    assert!(cost.prover_cycles > 0);
+    assert!(cost.prover_fee_usd > 0.0);
+    let other = engine.receipt_cost(&second).await.unwrap();
+    assert_eq!(cost.anchoring_gas + other.anchoring_gas, batch.gas_used);
+    assert_eq!(
+        cost.anchoring_fee_wei + other.anchoring_fee_wei,
+        batch.fee_wei
+    );
+
+    let stats = engine.stats(&ReceiptQuery::default()).await.expect("stats");
+    assert_eq!(stats.receipts, 3);
+    assert_eq!(stats.groups.len(), 2);
+    let trades = stats
+        .groups
+        .iter()
+        .find(|g| g.venue == Venue::Solana && g.claim_type == ClaimType::TRADE_EXECUTED)
+        .expect("solana trades");
+    assert_eq!(trades.receipts, 2);
+    assert_eq!(trades.by_status[&ReceiptStatus::PROVED], 2);
+    assert_eq!(trades.cost.anchoring_gas, batch.gas_used);
+    assert_eq!(stats.cost.adapter_calls, 6);
+}
+
+#[tokio::test]
+async fn anchoring_bumps_fees_until_included_and_defers_under_congestion() {
+    let policy = AnchorFeePolicy {
+        max_fee_per_gas_wei: 50 * GWEI,
+        max_priority_fee_per_gas_wei: 2 * GWEI,
+        ..AnchorFeePolicy::default()
+    };
+    let anchoring = engine().with_anchor_fee_policy(policy);
+    let receipt_id = proved_trade(&anchoring, 1).await;
+    let ids = std::slice::from_ref(&receipt_id);
+
+    let congested = CongestedPublisher {
+        base_fee_wei: 80 * GWEI,
+        inclusion_priority_wei: GWEI,
+    };
+    let deferred = anchoring
+        .anchor_receipts(ids, &congested)
+        .await
+        .expect("deferral is not an error");
+    assert_eq!(deferred.status, AnchorStatus::DEFERRED);
+    assert!(deferred.inclusions.is_empty());
+    assert_eq!(deferred.fee_decisions.len(), 1);
+    assert!(deferred.fee_decisions[0].capped);
+    let untouched = anchoring.get_receipt(&receipt_id).await.unwrap();
+    assert!(untouched.proof.anchor_inclusion.is_none());
+
+    let slow = CongestedPublisher {
+        base_fee_wei: 10 * GWEI,
+        inclusion_priority_wei: 3 * GWEI / 2,
+    };
+    let batch = anchoring.anchor_receipts(ids, &slow).await.expect("anchor");
+    assert_eq!(batch.status, AnchorStatus::ANCHORED);
+    let actions: Vec<FeeAction> = batch.fee_decisions.iter().map(|d| d.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            FeeAction::SUBMITTED,
+            FeeAction::NOT_INCLUDED,
+            FeeAction::BUMPED,
+            FeeAction::NOT_INCLUDED,
+            FeeAction::BUMPED,
+            FeeAction::INCLUDED
+        ]
+    );
+    let final_bid = batch.fee_decisions.last().unwrap().bid.unwrap();
+    assert!(final_bid.max_priority_fee_per_gas_wei >= 3 * GWEI / 2);
+    assert!(final_bid.max_fee_per_gas_wei <= policy.max_fee_per_gas_wei);
+    assert_eq!(batch.inclusions.len(), 1);
+
+    let capped_engine = engine().with_anchor_fee_policy(AnchorFeePolicy {
+        max_priority_fee_per_gas_wei: 6 * GWEI / 5,
+        ..policy
+    });
+    let capped_id = proved_trade(&capped_engine, 2).await;
+    let capped = capped_engine
+        .anchor_receipts(std::slice::from_ref(&capped_id), &slow)
+        .await
+        .expect("capped");
+    assert_eq!(capped.status, AnchorStatus::DEFERRED);
+    assert!(capped.anchor_tx_ref.is_none());
+    assert_eq!(
+        capped.fee_decisions.last().unwrap().action,
+        FeeAction::DEFERRED
+    );
+}
+
+#[tokio::test]
+async fn smart_account_anchoring_uses_paymaster_sponsored_user_operations() {
+    let engine = engine();
+    let receipt_id = proved_trade(&engine, 1).await;
+    let ids = std::slice::from_ref(&receipt_id);
+    let sender = "0x1111111111111111111111111111111111111111";
+    let anchor_contract = "0x2222222222222222222222222222222222222222";
+
+    let unfunded = SmartAccountAnchorPublisher::new(
+        sender,
+        ENTRY_POINT_V06,
+        8453,
+        anchor_contract,
+        Arc::new(SyntheticBundler::new(8453, GWEI)),
+        Arc::new(SyntheticUserOperationSigner {
+            key_id: "owner".to_string(),
+        }),
+    );
+    let err = engine.anchor_receipts(ids, &unfunded).await.unwrap_err();
+    assert!(err.to_string().contains("AA21"));
+
+    let sponsored = build_mvp_anchor_publisher(
+        &AnchorAccount::SmartAccount {
+            sender: sender.to_string(),
+            entry_point: ENTRY_POINT_V06.to_string(),
+            chain_id: 8453,
+            paymaster: Some("https://paymaster.example".to_string()),
+        },
+        anchor_contract,
+    );
+    let batch = engine
+        .anchor_receipts(ids, sponsored.as_ref())
+        .await
+        .expect("anchor via user operation");
+    assert_eq!(batch.status, AnchorStatus::ANCHORED);
+    assert!(batch.sponsored);
+    assert!(batch.user_op_hash.is_some());
+    assert!(batch.gas_used > 0);
+    let receipt = engine.get_receipt(&receipt_id).await.unwrap();
+    let inclusion = receipt.proof.anchor_inclusion.expect("inclusion");
+    assert_eq!(Some(&inclusion.anchor_tx_ref), batch.anchor_tx_ref.as_ref());
+    assert!(verify_inclusion(
+        &receipt.integrity.receipt_hash,
+        &inclusion
+    ));
+
+    let eoa = build_mvp_anchor_publisher(
+        &AnchorAccount::Eoa {
+            address: sender.to_string(),
+        },
+        anchor_contract,
+    );
+    let second = proved_trade(&engine, 2).await;
+    let plain = engine
+        .anchor_receipts(&[second], eoa.as_ref())
+        .await
+        .expect("eoa anchor");
+    assert!(!plain.sponsored);
+    assert!(plain.user_op_hash.is_none());
+
+    // The receipt is polled with backoff until the operation is bundled or the timeout passes.
+    let publisher = |bundler: Arc<SlowBundler>| {
+        SmartAccountAnchorPublisher::new(
+            sender,
+            ENTRY_POINT_V06,
+            8453,
+            anchor_contract,
+            bundler,
+            Arc::new(SyntheticUserOperationSigner {
+                key_id: "owner".to_string(),
+            }),
+        )
+        .with_paymaster(Arc::new(SyntheticPaymaster))
+        .with_inclusion_timeout(Duration::from_millis(500), Duration::from_millis(10))
+    };
+    let bid = FeeBid {
+        max_fee_per_gas_wei: 2 * GWEI,
+        max_priority_fee_per_gas_wei: GWEI / 10,
+    };
+    let slow = Arc::new(SlowBundler::new(3));
+    let root = format!("0x{}", "ab".repeat(32));
+    let attempt = publisher(slow.clone())
+        .publish_root(&root, 1, &bid)
+        .await
+        .expect("publish");
+    assert!(
+        matches!(attempt, PublishAttempt::Included(ref publication) if publication.user_op_hash.is_some())
+    );
+    assert_eq!(slow.lookups.load(std::sync::atomic::Ordering::SeqCst), 4);
+    let stuck = Arc::new(SlowBundler::new(usize::MAX));
+    let started = std::time::Instant::now();
+    let attempt = publisher(stuck.clone())
+        .publish_root(&root, 1, &bid)
+        .await
+        .expect("publish");
+    assert!(
+        matches!(attempt, PublishAttempt::NotIncluded(ref reason) if reason.contains("not bundled within 500 ms"))
+    );
+    assert!(started.elapsed() >= Duration::from_millis(500));
+    // 10, 20, 40, 80, 160 ms and the rest of the timeout, not a lookup every 10 ms.
+    assert!(stuck.lookups.load(std::sync::atomic::Ordering::SeqCst) <= 8);
+}
+
+// Bundles like SyntheticBundler, but reports no receipt (or a failed lookup) for the first
+// `pending` lookups.
+struct SlowBundler {
+    inner: SyntheticBundler,
+    pending: usize,
+    lookups: std::sync::atomic::AtomicUsize,
+}
+
+impl SlowBundler {
+    fn new(pending: usize) -> Self {
+        Self {
+            inner: SyntheticBundler::new(8453, GWEI),
+            pending,
+            lookups: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Bundler for SlowBundler {
+    async fn fee_estimate(&self) -> Result<FeeEstimate> {
+        self.inner.fee_estimate().await
+    }
+
+    async fn nonce(&self, sender: &str, entry_point: &str) -> Result<u128> {
+        self.inner.nonce(sender, entry_point).await
+    }
+
+    async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+        entry_point: &str,
+    ) -> Result<UserOperationGas> {
+        self.inner
+            .estimate_user_operation_gas(op, entry_point)
+            .await
+    }
+
+    async fn send_user_operation(&self, op: &UserOperation, entry_point: &str) -> Result<String> {
+        self.inner.send_user_operation(op, entry_point).await
+    }
+
+    async fn user_operation_receipt(
+        &self,
+        user_op_hash: &str,
+    ) -> Result<Option<UserOperationReceipt>> {
+        match self
+            .lookups
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+        {
+            1 => Err(anyhow::anyhow!("bundler unavailable")),
+            n if n < self.pending => Ok(None),
+            _ => self.inner.user_operation_receipt(user_op_hash).await,
+        }
+    }
+}
+
+fn write_sealed(path: &std::path::Path, key: &[u8; 32], entries: &[(&str, &str)]) {
+    let credentials = entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    std::fs::write(path, seal_credentials(key, &credentials).expect("seal"))
+        .expect("write sealed credentials");
+}
+
+#[tokio::test]
+async fn adapter_credentials_rotate_without_restart_and_never_leak() {
+    let key = [42u8; 32];
+    let path =
+        std::env::temp_dir().join(format!("zkputer-credentials-{}.json", uuid::Uuid::new_v4()));
+    let hl = adapter_credential_name(Venue::Hyperliquid);
+    let sol = adapter_credential_name(Venue::Solana);
+    write_sealed(
+        &path,
+        &key,
+        &[
+            (hl.as_str(), "hl-key-v1-0001"),
+            (sol.as_str(), "sol-key-v1-0001"),
+        ],
+    );
+    let credentials = CredentialStore::new(Arc::new(SealedFileCredentialSource::new(&path, key)))
+        .expect("credentials should load");
+
+    let adapters: Vec<Arc<dyn VenueAdapter>> = vec![
+        Arc::new(
+            SyntheticVenueAdapter::new(Venue::Hyperliquid)
+                .with_credentials(credentials.clone(), hl.clone()),
+        ),
+        Arc::new(LeakyAdapter {
+            inner: SyntheticVenueAdapter::new(Venue::Solana),
+            credentials: credentials.clone(),
+        }),
+    ];
+    let engine = ReceiptEngine::new(
+        adapters,
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_credentials(credentials.clone());
+    let order = |venue: Venue, n: usize, payload: Value| ProofRequest {
+        payload,
+        ..ProofRequest::new(
+            venue,
+            ClaimType::ORDER_PLACED,
+            format!("acct-cred-{}", n),
+            format!("order-cred-{}", n),
+        )
+    };
+    let submit_and_wait = |request: ProofRequest| {
+        let engine = &engine;
+        async move {
+            let id = engine.submit(request).await.expect("submit");
+            engine
+                .wait_for_receipt(&id, Duration::from_secs(5))
+                .await
+                .expect("wait")
+        }
+    };
+
+    let first = submit_and_wait(order(Venue::Hyperliquid, 1, serde_json::json!({}))).await;
+    assert_eq!(first.status, ReceiptStatus::PROVED);
+
+    write_sealed(
+        &path,
+        &key,
+        &[
+            (hl.as_str(), "hl-key-v2-0002"),
+            (sol.as_str(), "sol-key-v1-0001"),
+        ],
+    );
+    assert_eq!(
+        credentials.refresh().await.expect("refresh"),
+        vec![hl.clone()]
+    );
+    let info = credentials.describe().await;
+    let hl_info = info.iter().find(|i| i.name == hl).expect("hl credential");
+    assert_eq!(hl_info.version, 2);
+    assert!(!serde_json::to_string(&info)
+        .unwrap()
+        .contains("hl-key-v2-0002"));
+    let rotated = submit_and_wait(order(Venue::Hyperliquid, 2, serde_json::json!({}))).await;
+    assert_eq!(rotated.status, ReceiptStatus::PROVED);
+
+    let leaked = submit_and_wait(order(Venue::Solana, 3, serde_json::json!({}))).await;
+    assert_eq!(leaked.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        leaked.non_provable.as_ref().unwrap().reason_code,
+        NonProvableReason::SCHEMA_INVALID
+    );
+    let failed =
+        submit_and_wait(order(Venue::Solana, 4, serde_json::json!({ "fail": true }))).await;
+    assert_eq!(
+        failed.non_provable.as_ref().unwrap().reason_code,
+        NonProvableReason::SOURCE_UNAVAILABLE
+    );
+    for receipt in [&leaked, &failed] {
+        assert!(!serde_json::to_string(receipt)
+            .unwrap()
+            .contains("sol-key-v1-0001"));
+    }
+    assert!(failed
+        .non_provable
+        .as_ref()
+        .unwrap()
+        .details
+        .contains("[REDACTED]"));
+
+    write_sealed(&path, &key, &[(sol.as_str(), "sol-key-v1-0001")]);
+    assert_eq!(
+        credentials.refresh().await.expect("refresh"),
+        vec![hl.clone()]
+    );
+    let revoked = submit_and_wait(order(Venue::Hyperliquid, 5, serde_json::json!({}))).await;
+    assert_eq!(
+        revoked.non_provable.as_ref().unwrap().reason_code,
+        NonProvableReason::SOURCE_UNAVAILABLE
+    );
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn screening_blocks_flagged_accounts_and_evidence_addresses() {
+    let flagged_address = "0x9f8e7d6c5b4a39281706f5e4d3c2b1a098765432";
+    let provider = StaticListScreeningProvider::new(
+        "ofac-sdn",
+        "2026-10-01",
+        vec!["acct-sanctioned".to_string(), flagged_address.to_string()],
+    );
+    let screened_engine = |provider: Arc<dyn ScreeningProvider>| {
+        ReceiptEngine::new(
+            vec![Arc::new(CounterpartyAdapter(SyntheticVenueAdapter::new(
+                Venue::Base,
+            )))],
+            PolicyEngine::new(None).expect("policy should load"),
+            Arc::new(Sp1MvpProver),
+            OffchainVerifier::default(),
+        )
+        .with_screening_provider(provider)
+    };
+    let screened = screened_engine(Arc::new(provider));
+    let order = |account: &str, n: usize, counterparty: &str| ProofRequest {
+        payload: serde_json::json!({ "counterparty": counterparty }),
+        ..ProofRequest::new(
+            Venue::Base,
+            ClaimType::ORDER_PLACED,
+            account.to_string(),
+            format!("order-screen-{}", n),
+        )
+    };
+    let clean_counterparty = "0x1111111111111111111111111111111111111111";
+
+    let id = screened
+        .submit(order("acct-sanctioned", 1, clean_counterparty))
+        .await
+        .expect("submit");
+    let blocked = screened
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(blocked.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        blocked.non_provable.as_ref().unwrap().reason_code,
+        NonProvableReason::COMPLIANCE_BLOCKED
+    );
+    assert!(
+        !blocked
+            .non_provable
+            .as_ref()
+            .unwrap()
+            .remediation
+            .reprove_may_help
+    );
+    let decisions = screened.screening_decisions(&id).await;
+    assert_eq!(decisions.len(), 1);
+    assert_eq!(decisions[0].stage, ScreeningStage::PreSubmission);
+    assert_eq!(decisions[0].outcome, ScreeningOutcome::BLOCKED);
+    assert_eq!(decisions[0].list_version.as_deref(), Some("2026-10-01"));
+    assert_eq!(decisions[0].decision_hash, decisions[0].compute_hash());
+    assert!(blocked
+        .non_provable
+        .as_ref()
+        .unwrap()
+        .details
+        .contains(&decisions[0].decision_hash));
+
+    let checksummed = "0x9F8E7D6C5B4A39281706F5E4D3C2B1A098765432";
+    let id = screened
+        .submit(order("acct-clean", 2, checksummed))
+        .await
+        .expect("submit");
+    let blocked = screened
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        blocked.non_provable.as_ref().unwrap().reason_code,
+        NonProvableReason::COMPLIANCE_BLOCKED
+    );
+    let decisions = screened.screening_decisions(&id).await;
+    let stages: Vec<_> = decisions.iter().map(|d| (d.stage, d.outcome)).collect();
+    assert_eq!(
+        stages,
+        vec![
+            (ScreeningStage::PreSubmission, ScreeningOutcome::CLEAR),
+            (ScreeningStage::Evidence, ScreeningOutcome::BLOCKED)
+        ]
+    );
+    assert_eq!(decisions[1].hits[0].subject, flagged_address);
+
+    let id = screened
+        .submit(order("acct-clean", 3, clean_counterparty))
+        .await
+        .expect("submit");
+    let proved = screened
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(proved.status, ReceiptStatus::PROVED);
+    assert_eq!(screened.screening_decisions(&id).await.len(), 2);
+
+    let offline = screened_engine(Arc::new(OfflineScreening));
+    let id = offline
+        .submit(order("acct-clean", 4, clean_counterparty))
+        .await
+        .expect("submit");
+    let unavailable = offline
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        unavailable.non_provable.as_ref().unwrap().reason_code,
+        NonProvableReason::SOURCE_UNAVAILABLE
+    );
+    assert_eq!(
+        offline.screening_decisions(&id).await[0].outcome,
+        ScreeningOutcome::UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn terminal_receipts_carry_signed_issuance_sequences_and_watermark() {
+    let engine = Arc::new(engine());
+    let mut receipts = Vec::new();
+    for (n, conflict) in [(1, false), (2, true), (3, false)] {
+        let mut request = base_order(7100 + n);
+        request.payload = serde_json::json!({ "simulate_conflict": conflict });
+        let id = engine.submit(request).await.expect("submit");
+        receipts.push(
+            engine
+                .wait_for_receipt(&id, Duration::from_secs(5))
+                .await
+                .expect("wait"),
+        );
+    }
+    let mut sequences: Vec<u64> = receipts.iter().map(|r| r.integrity.sequence).collect();
+    sequences.sort();
+    assert_eq!(sequences, vec![1, 2, 3]);
+    assert_eq!(receipts[1].status, ReceiptStatus::NON_PROVABLE);
+    for receipt in &receipts {
+        assert!(receipt.integrity.issued_at.is_some());
+        assert!(engine.keyring().verify_receipt_signature(receipt));
+    }
+    let mut renumbered = receipts[0].clone();
+    renumbered.integrity.sequence += 10;
+    assert!(!engine.keyring().verify_receipt_signature(&renumbered));
+
+    let watermarks = engine.watermarks();
+    assert_eq!(watermarks.len(), 1);
+    let watermark = &watermarks[0];
+    assert_eq!(watermark.sequence, 3);
+    assert!(watermark.verify());
+    let mut raised = watermark.clone();
+    raised.sequence += 1;
+    assert!(!raised.verify());
+    let log = engine.issuance_log(&watermark.signer.key_id);
+    assert_eq!(
+        log.last().map(|e| e.receipt_hash.as_str()),
+        Some(watermark.receipt_hash.as_str())
+    );
+    // A verifier shown only the first receipt can tell two were withheld.
+    assert_eq!(
+        missing_sequences(&[receipts[0].integrity.sequence], watermark.sequence).len(),
+        2
+    );
+
+    let api = HttpApi::new(Arc::clone(&engine));
+    let response = api
+        .route(&HttpRequest {
+            method: "GET".to_string(),
+            path: "/v1/watermark".to_string(),
+            query: None,
+            authorization: None,
+            peer: None,
+            body: vec![],
+            headers: vec![],
+        })
+        .await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body["watermarks"][0]["sequence"], 3);
+}
+
+#[tokio::test]
+async fn evidence_collection_provenance_is_recorded_with_sensitive_parts_hashed() {
+    let raw = engine().with_collection_redaction(CollectionRedaction::keep_all());
+    let id = raw.submit(base_order(7201)).await.expect("submit");
+    let receipt = raw
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    let collection = &receipt.provenance.collection;
+    assert_eq!(collection.len(), receipt.provenance.evidence_items.len());
+    for (record, item) in collection.iter().zip(&receipt.provenance.evidence_items) {
+        assert_eq!(record.artifact_hash, item.artifact_hash);
+        assert!(record.hashed_fields.is_empty());
+        // An auditor can re-issue the exact request and match its hash.
+        let reissued = serde_json::json!({ "method": "GET", "url": record.endpoint_url });
+        assert_eq!(record.request_hash, zkputer::models::hash_json(&reissued));
+        assert!(!record.adapter_version.is_empty());
+    }
+
+    let redaction = CollectionRedaction::default()
+        .with_salt("audit-salt")
+        .expect("salt");
+    let private = engine().with_collection_redaction(CollectionRedaction {
+        client_region: FieldTreatment::Omit,
+        ..redaction.clone()
+    });
+    let id = private.submit(base_order(7202)).await.expect("submit");
+    let receipt = private
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let record = &receipt.provenance.collection[0];
+    assert_eq!(
+        record.hashed_fields,
+        vec!["endpoint_query".to_string(), "client_ip".to_string()]
+    );
+    assert_eq!(
+        record.client_ip.as_deref(),
+        Some(redaction.salted_hash("198.51.100.24").as_str())
+    );
+    assert_eq!(record.client_region, None);
+    assert!(record.endpoint_url.contains("?query_hash=0x"));
+    assert!(!serde_json::to_string(&receipt)
+        .unwrap()
+        .contains("198.51.100.24"));
+}
+
+#[tokio::test]
+async fn receipts_pin_the_adapter_id_and_version_that_collected_evidence() {
+    let engine = engine();
+    let capabilities = SyntheticVenueAdapter::new(Venue::Base).capabilities();
+    assert_eq!(capabilities.adapter_id, "synthetic-base");
+
+    let id = engine.submit(base_order(7301)).await.expect("submit");
+    let proved = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(proved.status, ReceiptStatus::PROVED);
+    assert_eq!(proved.provenance.adapter_id, capabilities.adapter_id);
+    assert_eq!(
+        proved.provenance.adapter_version,
+        capabilities.adapter_version
+    );
+    assert!(proved
+        .provenance
+        .collection
+        .iter()
+        .all(|record| record.adapter_version == capabilities.adapter_version));
+
+    let mut conflicting = base_order(7302);
+    conflicting.payload = serde_json::json!({ "simulate_conflict": true });
+    let id = engine.submit(conflicting).await.expect("submit");
+    let rejected = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(rejected.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        rejected.provenance.adapter_version,
+        capabilities.adapter_version
+    );
+}
+
+#[tokio::test]
+async fn injected_stage_latency_is_checked_against_the_slo_document() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let slos = load_slo_document(&root.join(SLOS_PATH)).expect("slos");
+    let profiles = load_latency_profiles(&root.join(LATENCY_PROFILES_PATH)).expect("profiles");
+    let stall = profiles
+        .iter()
+        .find(|p| p.expect == ProfileExpectation::FinalityTimeout)
+        .expect("a finality timeout profile");
+
+    // Payload overrides slow collection down without holding up the submit call.
+    let delayed = engine_with_prover(Arc::new(DelayedProver::new(
+        Arc::new(Sp1MvpProver),
+        Duration::from_millis(20),
+    )));
+    let mut request = ProofRequest {
+        payload: serde_json::json!({ "simulate_latency_ms": { "acknowledge": 30, "collect_evidence": 120 } }),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::ORDER_PLACED,
+            "acct-latency",
+            "order-latency-1",
+        )
+    };
+    let started = std::time::Instant::now();
+    let receipt_id = delayed.submit(request.clone()).await.expect("submit");
+    let submit_ms = started.elapsed().as_secs_f64() * 1000.0;
+    assert!(submit_ms < 30.0, "submit blocked for {} ms", submit_ms);
+    let receipt = delayed
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let completion_ms = started.elapsed().as_secs_f64() * 1000.0;
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert!(
+        completion_ms >= 170.0,
+        "completed after {} ms",
+        completion_ms
+    );
+
+    // A chain that takes far longer than the finality timeout yields FINALITY_TIMEOUT on time.
+    let observer = FinalityObserver::new(Duration::from_millis(5)).with_head_source(
+        Venue::Base,
+        Arc::new(TimedChainHead::new(Duration::from_secs(60))),
+    );
+    let stalled = engine().with_finality_observer(
+        observer.clone(),
+        Duration::from_millis(stall.finality_timeout_ms),
+    );
+    request.venue = Venue::Base;
+    request.order_ref = "order-latency-2".to_string();
+    request.payload = serde_json::json!({ "simulate_latency_ms": { "acknowledge": 20 } });
+    let started = std::time::Instant::now();
+    let receipt_id = stalled.submit(request).await.expect("submit");
+    let submit_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let receipt = stalled
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let timed_out = ReceiptSample {
+        claim_type: ClaimType::ORDER_PLACED,
+        awaits_finality: true,
+        status: receipt.status,
+        reason: receipt.non_provable.as_ref().map(|n| n.reason_code),
+        submit_ms,
+        completion_ms: started.elapsed().as_secs_f64() * 1000.0,
+        breakdown: receipt.timing.breakdown.clone(),
+    };
+    assert_eq!(timed_out.reason, Some(NonProvableReason::FINALITY_TIMEOUT));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(observer.pending(Venue::Base).await, 0);
+
+    let normal = ReceiptSample {
+        claim_type: ClaimType::ORDER_PLACED,
+        awaits_finality: false,
+        status: ReceiptStatus::PROVED,
+        reason: None,
+        submit_ms: 1.0,
+        completion_ms,
+        breakdown: Default::default(),
+    };
+    let backpressure = BackpressureObservation {
+        peak_finality_waiters: 1,
+        residual_finality_waiters: 0,
+    };
+    let checks = evaluate_slos(
+        &slos,
+        stall,
+        &[normal.clone(), timed_out.clone()],
+        &backpressure,
+    );
+    assert!(checks.iter().all(|c| c.passed), "{:?}", checks);
+    assert!(checks
+        .iter()
+        .any(|c| c.name == "finality_timeout_enforced_ms"));
+
+    // The same timeout under a profile that expects normal completion is a non-provable breach.
+    let mut nominal = stall.clone();
+    nominal.expect = ProfileExpectation::Normal;
+    let checks = evaluate_slos(&slos, &nominal, &[normal, timed_out], &backpressure);
+    let breach = checks
+        .iter()
+        .find(|c| c.name == "non_provable_rate")
+        .expect("rate check");
+    assert!(!breach.passed);
+}
+
+#[tokio::test]
+async fn event_sourced_store_keeps_a_tamper_evident_receipt_history() {
+    let path = std::env::temp_dir().join(format!("zkputer-events-{}.sqlite", uuid::Uuid::new_v4()));
+    let (receipt_id, current) = {
+        let first =
+            engine().with_store(ReceiptStore::open_event_sourced(&path).expect("open store"));
+        let receipt_id = first.submit(base_order(830)).await.expect("submit");
+        let receipt = first
+            .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(receipt.status, ReceiptStatus::PROVED);
+
+        let history = first.receipt_history(&receipt_id).await.expect("history");
+        assert_eq!(history.len() as u64, receipt.revision);
+        assert_eq!(
+            history[0].changes.len(),
+            1,
+            "the first event carries the whole receipt"
+        );
+        assert_eq!(history[0].status, ReceiptStatus::PENDING);
+        assert!(history[1..]
+            .iter()
+            .all(|e| e.changes.iter().all(|c| !c.path.is_empty())));
+        assert_eq!(
+            fold_events(&history[..1]).expect("fold").status,
+            ReceiptStatus::PENDING
+        );
+        let as_json = |r: &zkputer::ZKReceipt| serde_json::to_value(r).expect("json");
+        assert_eq!(
+            as_json(&fold_events(&history).expect("fold")),
+            as_json(&receipt)
+        );
+
+        assert!(first
+            .receipt_at(&receipt_id, "2000-01-01T00:00:00Z")
+            .await
+            .expect("at")
+            .is_none());
+        let now = zkputer::models::now_iso();
+        let at_now = first
+            .receipt_at(&receipt_id, &now)
+            .await
+            .expect("at")
+            .expect("receipt exists");
+        assert_eq!(as_json(&at_now), as_json(&receipt));
+        (receipt_id, receipt)
+    };
+
+    let restarted =
+        engine().with_store(ReceiptStore::open_event_sourced(&path).expect("reopen store"));
+    let reloaded = restarted
+        .get_receipt(&receipt_id)
+        .await
+        .expect("receipt survives restart");
+    assert_eq!(
+        serde_json::to_value(&reloaded).expect("json"),
+        serde_json::to_value(&current).expect("json")
+    );
+
+    let plain = engine().with_store(
+        ReceiptStore::open_sqlite(
+            std::env::temp_dir().join(format!("zkputer-receipts-{}.sqlite", uuid::Uuid::new_v4())),
+        )
+        .expect("plain store"),
+    );
+    let plain_id = plain.submit(base_order(831)).await.expect("submit");
+    assert!(plain.receipt_history(&plain_id).await.is_err());
+
+    let conn = rusqlite::Connection::open(&path).expect("open database");
+    assert!(
+        conn.execute("DELETE FROM receipt_events", []).is_err(),
+        "events are immutable"
+    );
+    conn.execute_batch("DROP TRIGGER receipt_events_no_update")
+        .expect("drop trigger");
+    conn.execute(
+        "UPDATE receipt_events SET body = replace(body, '\"PENDING\"', '\"PROVED\"') WHERE revision = 1",
+        [],
+    )
+    .expect("tamper");
+    drop(conn);
+    let err = ReceiptStore::open_event_sourced(&path)
+        .err()
+        .expect("tampered history is rejected");
+    assert!(err.to_string().contains("broken"), "{}", err);
+}
+
+#[tokio::test]
+async fn provers_receive_preimages_that_open_the_public_commitments() {
+    let prover = Arc::new(WitnessRecordingProver::default());
+    let fallback = Arc::new(FallbackProver::new(prover.clone(), Arc::new(PicoMvpProver)));
+    let recording = engine_with_prover(fallback);
+    let receipt_id = recording.submit(base_order(840)).await.expect("submit");
+    let receipt = recording
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+
+    let witness = prover
+        .0
+        .lock()
+        .expect("lock")
+        .pop()
+        .expect("prover saw a witness");
+    assert_eq!(witness.hash_algorithm, HashAlgorithm::Sha256);
+    let claim = witness.claim_preimage.expect("claim preimage");
+    let evidence = witness.evidence_preimage.expect("evidence preimage");
+    assert_eq!(
+        HashAlgorithm::Sha256.hash_json(&claim),
+        receipt.claim.claim_hash
+    );
+    assert_eq!(
+        HashAlgorithm::Sha256.hash_json(&evidence),
+        receipt.provenance.evidence_root
+    );
+    assert_eq!(
+        evidence["leaves"].as_array().map(Vec::len),
+        Some(receipt.provenance.evidence_items.len())
+    );
+
+    let evm = engine_with_prover(prover.clone()).with_commitment_mode(CommitmentMode::EVM_ABI);
+    let receipt_id = evm.submit(base_order(841)).await.expect("submit");
+    evm.wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let witness = prover
+        .0
+        .lock()
+        .expect("lock")
+        .pop()
+        .expect("prover saw a witness");
+    assert!(witness.claim_preimage.is_none() && witness.evidence_preimage.is_none());
+}
+
+#[tokio::test]
+async fn malformed_venue_refs_are_rejected_at_submit_time() {
+    let formats = ReferenceFormats::for_venue(Venue::Base);
+    assert_eq!(formats.execution_ref, ReferenceFormat::EvmTxHash);
+
+    let chain = Arc::new(SyntheticEvmChain::new());
+    let evm = evm_engine(chain);
+    let router = "0x2626664c2603336e57b271c5c0b26f421741e481";
+    let receipt_id = evm
+        .submit(evm_trade("0xdeadbeef", router))
+        .await
+        .expect("submit");
+    let receipt = evm
+        .get_receipt(&receipt_id)
+        .await
+        .expect("receipt is stored at submit time");
+    assert_eq!(receipt.status, ReceiptStatus::NON_PROVABLE);
+    let non_provable = receipt.non_provable.expect("non-provable detail");
+    assert_eq!(non_provable.reason_code, NonProvableReason::SCHEMA_INVALID);
+    assert!(
+        non_provable.details.contains("order_ref \"0xdeadbeef\""),
+        "{}",
+        non_provable.details
+    );
+    assert!(
+        non_provable.details.contains("execution_ref"),
+        "{}",
+        non_provable.details
+    );
+
+    let strict = ReceiptEngine::new(
+        vec![Arc::new(
+            SyntheticVenueAdapter::new(Venue::Hyperliquid).with_reference_formats(),
+        )],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let mut request = ProofRequest {
+        execution_ref: Some("exec-1".to_string()),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::TRADE_EXECUTED,
+            "acct-refs",
+            "hl-oid-1",
+        )
+    };
+    let receipt_id = strict.submit(request.clone()).await.expect("submit");
+    let receipt = strict.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(
+        receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::SCHEMA_INVALID)
+    );
+
+    request.order_ref = "77738308".to_string();
+    request.execution_ref = Some("1048576".to_string());
+    let receipt_id = strict.submit(request).await.expect("submit");
+    let receipt = strict
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+}
+
+#[tokio::test]
+async fn risc0_receipts_are_verified_by_their_own_backend() {
+    assert_eq!(
+        ProverStrategy::from_env(Some("risc0")),
+        ProverStrategy::Risc0Only
+    );
+    assert_eq!(
+        build_mvp_prover(ProverStrategy::Risc0Only).backend_name(),
+        ProofBackend::RISC0
+    );
+
+    let risc0 = engine_with_prover(Arc::new(Risc0MvpProver));
+    let receipt_id = risc0.submit(base_order(850)).await.expect("submit");
+    let receipt = risc0
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.proof.backend, ProofBackend::RISC0);
+    assert!(receipt.proof.verifier_key_id.starts_with("risc0-image-"));
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+
+    // A RISC0 receipt carrying another zkVM's verifying key fails, even with matching public inputs.
+    let mut relabelled = receipt.clone();
+    relabelled.proof.backend = ProofBackend::SP1;
+    assert!(!OffchainVerifier::default().verify(&relabelled).await);
+    let mut foreign_key = receipt;
+    foreign_key.proof.verifier_key_id = "sp1-vk-001".to_string();
+    assert!(!OffchainVerifier::default().verify(&foreign_key).await);
+}
+
+#[tokio::test]
+async fn proofs_use_the_circuit_registered_for_their_claim_type() {
+    let engine = engine();
+    let order = engine.submit(base_order(855)).await.expect("submit");
+    let order = engine
+        .wait_for_receipt(&order, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let trade = engine
+        .get_receipt(&proved_trade(&engine, 855).await)
+        .await
+        .expect("receipt");
+    assert_eq!(order.proof.circuit_id, "inclusion-order-placed-sp1");
+    assert_eq!(trade.proof.circuit_id, "inclusion-trade-executed-sp1");
+    assert_eq!(trade.proof.circuit_version, MVP_CIRCUIT_VERSION);
+    let circuit = CircuitRegistry::builtin()
+        .select(ProofBackend::SP1, ClaimType::TRADE_EXECUTED)
+        .expect("registered");
+    assert_eq!(trade.proof.verifier_key_id, circuit.verifier_key_id);
+    assert_ne!(order.proof.verifier_key_hash, trade.proof.verifier_key_hash);
+    assert!(OffchainVerifier::default().verify(&trade).await);
+
+    // A trade receipt carrying the order circuit's proof fails, though that circuit and key are real.
+    let mut borrowed = trade.clone();
+    borrowed.proof.circuit_id = order.proof.circuit_id.clone();
+    borrowed.proof.verifier_key_id = order.proof.verifier_key_id.clone();
+    borrowed.proof.verifier_key_hash = order.proof.verifier_key_hash.clone();
+    assert!(!OffchainVerifier::default().verify(&borrowed).await);
+    // Receipts naming the shared or host circuit verify only under a pinned key, with the key id
+    // derived from it.
+    let mut legacy = trade;
+    legacy.proof.circuit_id = "trade-receipt-sp1".to_string();
+    legacy.proof.circuit_version = "v0.1.0".to_string();
+    legacy.proof.verifier_key_id = format!("sp1-vk-{}", &legacy.proof.verifier_key_hash[2..18]);
+    assert!(!OffchainVerifier::default().verify(&legacy).await);
+    let pinned =
+        OffchainVerifier::new().with_pinned_key(ProofBackend::SP1, &legacy.proof.verifier_key_hash);
+    assert!(pinned.verify(&legacy).await);
+    let mut prefixed_only = legacy.clone();
+    prefixed_only.proof.verifier_key_id = "sp1-vk-001".to_string();
+    assert!(!pinned.verify(&prefixed_only).await);
+    let mut unpinned = legacy.clone();
+    unpinned.proof.verifier_key_hash = format!("0x{}", "ab".repeat(32));
+    unpinned.proof.verifier_key_id = format!("sp1-vk-{}", "ab".repeat(8));
+    assert!(!pinned.verify(&unpinned).await);
+    assert!(
+        !OffchainVerifier::new()
+            .with_pinned_key(ProofBackend::RISC0, &legacy.proof.verifier_key_hash)
+            .verify(&legacy)
+            .await
+    );
+
+    let best = CircuitRegistry::builtin()
+        .select(ProofBackend::RISC0, ClaimType::BEST_EXECUTION)
+        .expect("registered");
+    assert_eq!(
+        (best.guest, best.circuit_id.as_str()),
+        (GuestProgram::Aggregate, "aggregate-best-execution-risc0")
+    );
+    assert!(best.verifier_key_id.starts_with("risc0-image-"));
+
+    // The version registered last is selected; the others stay for verification.
+    let registry = CircuitRegistry::new()
+        .with_circuit(CircuitSpec::mvp(
+            ProofBackend::PICO,
+            ClaimType::ORDER_PLACED,
+            "v0.2.0",
+        ))
+        .with_circuit(CircuitSpec::mvp(
+            ProofBackend::PICO,
+            ClaimType::ORDER_PLACED,
+            "v0.3.0",
+        ))
+        .with_circuit(CircuitSpec::mvp(
+            ProofBackend::PICO,
+            ClaimType::ORDER_PLACED,
+            "v0.2.0",
+        ));
+    assert_eq!(registry.circuits().len(), 2);
+    assert_eq!(
+        registry
+            .select(ProofBackend::PICO, ClaimType::ORDER_PLACED)
+            .unwrap()
+            .circuit_version,
+        "v0.2.0"
+    );
+    assert!(registry
+        .get("inclusion-order-placed-pico", "v0.3.0")
+        .is_some());
+    assert!(registry
+        .select(ProofBackend::SP1, ClaimType::ORDER_PLACED)
+        .is_none());
+    let inputs = standard_public_inputs("0x01", "0x02", Venue::Base, ClaimType::TRADE_EXECUTED);
+    assert!(registry.select_for(ProofBackend::PICO, &inputs).is_err());
+    assert!(Sp1MvpProver
+        .prove(&serde_json::json!({ "claim_hash": "0x01" }))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn receipt_directories_are_audited_in_parallel() {
+    let issuer = engine();
+    let mut receipts = Vec::new();
+    for n in 0..4 {
+        let receipt_id = issuer.submit(base_order(860 + n)).await.expect("submit");
+        receipts.push(
+            issuer
+                .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+                .await
+                .expect("wait"),
+        );
+    }
+    let rejected = issuer
+        .submit(ProofRequest {
+            account_ref: String::new(),
+            ..base_order(864)
+        })
+        .await
+        .expect("submit");
+    receipts.push(
+        issuer
+            .get_receipt(&rejected)
+            .await
+            .expect("rejected receipt"),
+    );
+
+    let dir = std::env::temp_dir().join(format!("zkputer-audit-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("nested")).expect("dir");
+    for receipt in &receipts[..2] {
+        std::fs::write(
+            dir.join(format!("{}.json", receipt.receipt_id)),
+            serde_json::to_string(receipt).expect("json"),
+        )
+        .expect("write");
+    }
+    let archive: Vec<String> = receipts[2..]
+        .iter()
+        .map(|r| serde_json::to_string(r).expect("json"))
+        .collect();
+    std::fs::write(dir.join("nested").join("batch.jsonl"), archive.join("\n")).expect("write");
+    let mut tampered = receipts[0].clone();
+    tampered.claim.claim_hash = receipts[1].claim.claim_hash.clone();
+    std::fs::write(
+        dir.join("tampered.json"),
+        serde_json::to_string(&tampered).expect("json"),
+    )
+    .expect("write");
+    std::fs::write(dir.join("garbage.json"), "{\"not\": \"a receipt\"}").expect("write");
+    std::fs::write(dir.join("notes.txt"), "ignored").expect("write");
+
+    let documents = load_receipt_documents(&dir).expect("load");
+    assert_eq!(documents.len(), 7);
+    assert!(documents
+        .iter()
+        .any(|d| d.location.ends_with("batch.jsonl:3")));
+
+    let auditor = ReceiptAuditor::new()
+        .with_keys(issuer.keyring().clone())
+        .with_concurrency(3);
+    let report = auditor.audit(&dir.display().to_string(), documents).await;
+    assert_eq!((report.total, report.passed, report.failed), (7, 5, 2));
+    assert_eq!(report.signer_keys, "manifest");
+    assert_eq!(report.by_status.get("PROVED"), Some(&5));
+    assert_eq!(report.by_status.get("NON_PROVABLE"), Some(&1));
+    assert_eq!(report.failure_reasons.get("unreadable"), Some(&1));
+    assert_eq!(
+        report.failure_reasons.get("receipt_hash_mismatch"),
+        Some(&1)
+    );
+    assert!(report
+        .failures
+        .iter()
+        .any(|f| f.location.ends_with("tampered.json")));
+    assert!(!report.slowest_checks.is_empty() && report.slowest_checks.len() <= 10);
+    assert!(report
+        .slowest_checks
+        .windows(2)
+        .all(|w| w[0].elapsed_ms >= w[1].elapsed_ms));
+    let json = serde_json::to_value(&report).expect("report serializes");
+    assert_eq!(json["failed"], 2);
+
+    // Receipts signed by a key the counterparty does not hold fail on the signature.
+    let stranger = KeyRing::single(SignerIdentity::new(
+        "other-issuer",
+        "other-key-0",
+        "0xother",
+        "sha256-binding",
+    ));
+    let report = ReceiptAuditor::new()
+        .with_keys(stranger)
+        .audit("dir", load_receipt_documents(&dir).expect("load"))
+        .await;
+    assert_eq!(report.failure_reasons.get("signature_invalid"), Some(&6));
+    let report = ReceiptAuditor::new()
+        .audit("dir", load_receipt_documents(&dir).expect("load"))
+        .await;
+    assert_eq!(
+        (report.passed, report.signer_keys.as_str()),
+        (5, "embedded")
+    );
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn prover_chain_falls_back_in_order_and_records_the_producing_backend() {
+    let slow_pico: Arc<dyn ProverBackend> = Arc::new(DelayedProver::new(
+        Arc::new(PicoMvpProver),
+        Duration::from_millis(500),
+    ));
+    let chained = engine()
+        .with_provers(
+            vec![
+                Arc::new(AlwaysFailProver),
+                slow_pico.clone(),
+                Arc::new(Risc0MvpProver),
+            ],
+            Some(Duration::from_millis(50)),
+        )
+        .expect("chain");
+    let receipt_id = chained.submit(base_order(870)).await.expect("submit");
+    let receipt = chained
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.proof.backend, ProofBackend::RISC0);
+    assert_eq!(
+        receipt.proof.fallback_from,
+        vec![ProofBackend::SP1, ProofBackend::PICO]
+    );
+
+    let exhausted = engine()
+        .with_provers(
+            vec![Arc::new(AlwaysFailProver), slow_pico],
+            Some(Duration::from_millis(50)),
+        )
+        .expect("chain");
+    let receipt_id = exhausted.submit(base_order(871)).await.expect("submit");
+    let receipt = exhausted
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.expect("non-provable");
+    assert_eq!(non_provable.reason_code, NonProvableReason::PROOF_FAILURE);
+    assert!(
+        non_provable
+            .details
+            .contains("forced primary prover failure"),
+        "{}",
+        non_provable.details
+    );
+    assert!(
+        non_provable.details.contains("PICO: timed out after 50 ms"),
+        "{}",
+        non_provable.details
+    );
+
+    assert!(engine().with_provers(vec![], None).is_err());
+}
+
+#[tokio::test]
+async fn attestation_only_claims_skip_the_prover_and_stand_on_the_signature() {
+    let policy = PolicyEngine::new(None)
+        .expect("policy should load")
+        .with_attestation_only(Venue::Base, ClaimType::ORDER_PLACED);
+    assert!(policy.is_attestation_only(Venue::Base, ClaimType::ORDER_PLACED));
+    assert!(!policy.is_attestation_only(Venue::Hyperliquid, ClaimType::ORDER_PLACED));
+    let attesting = ReceiptEngine::new(
+        vec![
+            Arc::new(SyntheticVenueAdapter::new(Venue::Base)),
+            Arc::new(SyntheticVenueAdapter::new(Venue::Hyperliquid)),
+        ],
+        policy,
+        Arc::new(AlwaysFailProver),
+        OffchainVerifier::default(),
+    );
+
+    let receipt_id = attesting.submit(base_order(880)).await.expect("submit");
+    let receipt = attesting
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(
+        receipt.proof.verification_mode,
+        VerificationMode::ATTESTATION_ONLY
+    );
+    assert_eq!(receipt.proof.backend, ProofBackend::NONE);
+    assert_eq!(
+        receipt.proof.verifier_key_id,
+        receipt.integrity.signer.key_id
+    );
+    assert!(receipt.proof.proof_artifact_ref.is_none());
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+    assert!(receipt.verify_integrity(None).is_valid());
+    let mut relabelled = receipt.clone();
+    relabelled.proof.verifier_key_id = "someone-else".to_string();
+    assert!(!OffchainVerifier::default().verify(&relabelled).await);
+
+    // Other claims still go to the prover, and requesters cannot opt themselves out of proving.
+    let hyperliquid = ProofRequest {
+        venue: Venue::Hyperliquid,
+        ..base_order(881)
+    };
+    let receipt_id = attesting.submit(hyperliquid.clone()).await.expect("submit");
+    let receipt = attesting
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::PROOF_FAILURE)
+    );
+    let receipt_id = attesting
+        .submit(ProofRequest {
+            verification_mode: VerificationMode::ATTESTATION_ONLY,
+            ..hyperliquid
+        })
+        .await
+        .expect("submit");
+    let receipt = attesting.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(
+        receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::POLICY_VIOLATION)
+    );
+}
+
+#[tokio::test]
+async fn proofs_run_through_a_bounded_queue() {
+    let bounded = Arc::new(
+        engine_with_prover(Arc::new(DelayedProver::new(
+            Arc::new(Sp1MvpProver),
+            Duration::from_millis(300),
+        )))
+        .with_max_concurrent_proofs(2),
+    );
+    let mut ids = Vec::new();
+    for n in 0..6 {
+        ids.push(bounded.submit(base_order(890 + n)).await.expect("submit"));
+    }
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+    let mut peak = ProofQueueStats::default();
+    while tokio::time::Instant::now() < deadline {
+        let stats = bounded.proof_queue_stats();
+        assert!(stats.running <= 2, "{:?}", stats);
+        if stats.running + stats.queued > peak.running + peak.queued {
+            peak = stats;
+        }
+        if stats.running == 2 && stats.queued == 4 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!((peak.running, peak.queued), (2, 4));
+    let health = bounded.health().await;
+    assert!(
+        health.queues.proofs_running <= 2
+            && health.queues.proofs_running + health.queues.proofs_queued > 0
+    );
+
+    for id in &ids {
+        let receipt = bounded
+            .wait_for_receipt(id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    }
+    let stats = bounded
+        .stats(&ReceiptQuery::default())
+        .await
+        .expect("stats");
+    assert_eq!(
+        stats.proof_queue,
+        ProofQueueStats {
+            max_concurrent: 2,
+            running: 0,
+            queued: 0
+        }
+    );
+}
+
+#[tokio::test]
+async fn request_deadlines_are_shared_across_stages_and_record_where_time_went() {
+    let slow = engine_with_prover(Arc::new(DelayedProver::new(
+        Arc::new(Sp1MvpProver),
+        Duration::from_millis(400),
+    )));
+    let receipt_id = slow
+        .submit(ProofRequest {
+            payload: serde_json::json!({ "simulate_latency_ms": { "collect_evidence": 60 } }),
+            deadline_ms: Some(150),
+            ..base_order(900)
+        })
+        .await
+        .expect("submit");
+    let receipt = slow
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::DEADLINE_EXCEEDED
+    );
+    assert!(
+        non_provable.details.contains("during prove"),
+        "{}",
+        non_provable.details
+    );
+    let miss = receipt
+        .timing
+        .deadline_miss
+        .expect("deadline miss is recorded");
+    assert_eq!((miss.budget_ms, miss.stage.as_str()), (150, "prove"));
+    let stages: Vec<&str> = miss.spent.iter().map(|s| s.stage.as_str()).collect();
+    assert_eq!(stages, vec!["acknowledge", "collect_evidence", "prove"]);
+    assert!(miss.spent[1].elapsed_ms >= 60);
+    assert!(miss.spent.iter().map(|s| s.elapsed_ms).sum::<u64>() <= 150 + 50);
+
+    // A stage that alone outlasts the budget is cut off there.
+    let receipt_id = slow
+        .submit(ProofRequest {
+            payload: serde_json::json!({ "simulate_latency_ms": { "acknowledge": 300 } }),
+            deadline_ms: Some(50),
+            ..base_order(901)
+        })
+        .await
+        .expect("submit");
+    let receipt = slow
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.timing.deadline_miss.map(|m| m.stage),
+        Some("acknowledge".to_string())
+    );
+
+    let receipt_id = slow
+        .submit(ProofRequest {
+            deadline_ms: Some(5_000),
+            ..base_order(902)
+        })
+        .await
+        .expect("submit");
+    let receipt = slow
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert!(receipt.timing.deadline_miss.is_none());
+
+    let receipt_id = slow
+        .submit(ProofRequest {
+            deadline_ms: Some(0),
+            ..base_order(903)
+        })
+        .await
+        .expect("submit");
+    let receipt = slow.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(
+        receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::SCHEMA_INVALID)
+    );
+}
+
+#[tokio::test]
+async fn receipts_break_their_latency_down_by_pipeline_stage() {
+    let slow = engine_with_prover(Arc::new(DelayedProver::new(
+        Arc::new(Sp1MvpProver),
+        Duration::from_millis(80),
+    )));
+    let receipt_id = slow
+        .submit(ProofRequest {
+            payload: serde_json::json!({ "simulate_latency_ms": { "acknowledge": 30, "collect_evidence": 50 } }),
+            ..base_order(910)
+        })
+        .await
+        .expect("submit");
+    let receipt = slow
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    let breakdown = receipt.timing.breakdown.clone();
+    assert!(breakdown.ack_ms >= Some(30), "{:?}", breakdown);
+    assert!(breakdown.collect_ms >= Some(50), "{:?}", breakdown);
+    assert!(breakdown.prove_ms >= Some(80), "{:?}", breakdown);
+    let stages: Vec<&str> = breakdown
+        .stages()
+        .into_iter()
+        .map(|(stage, _)| stage)
+        .collect();
+    assert_eq!(stages, vec!["ack", "collect", "policy", "prove", "verify"]);
+
+    // The stage that failed is timed; the stages after it never ran.
+    let receipt_id = slow
+        .submit(ProofRequest {
+            payload: serde_json::json!({ "script": { "fail_collect_evidence": [1] } }),
+            ..base_order(911)
+        })
+        .await
+        .expect("submit");
+    let failed = slow
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(failed.status, ReceiptStatus::NON_PROVABLE);
+    let stages: Vec<&str> = failed
+        .timing
+        .breakdown
+        .stages()
+        .into_iter()
+        .map(|(stage, _)| stage)
+        .collect();
+    assert_eq!(stages, vec!["ack", "collect"]);
+
+    let sample = |receipt: &zkputer::models::ZKReceipt| ReceiptSample {
+        claim_type: receipt.claim.r#type,
+        awaits_finality: false,
+        status: receipt.status,
+        reason: receipt.non_provable.as_ref().map(|n| n.reason_code),
+        submit_ms: 0.0,
+        completion_ms: 0.0,
+        breakdown: receipt.timing.breakdown.clone(),
+    };
+    let attribution = attribute_stages(&[sample(&receipt), sample(&failed)]);
+    let counts: Vec<(&str, usize)> = attribution
+        .iter()
+        .map(|a| (a.stage.as_str(), a.samples))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("ack", 2),
+            ("collect", 2),
+            ("policy", 1),
+            ("prove", 1),
+            ("verify", 1)
+        ]
+    );
+    assert!(attribution[3].p95_ms >= 80.0);
+}
+
+// Fails its first `failures` proofs, then proves like the SP1 MVP prover.
+#[derive(Debug)]
+struct FlakyProver {
+    failures: usize,
+    calls: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl ProverBackend for FlakyProver {
+    fn backend_name(&self) -> ProofBackend {
+        ProofBackend::SP1
+    }
+
+    async fn prove(&self, public_inputs: &Value) -> Result<ProofMetadata> {
+        if self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < self.failures {
+            return Err(anyhow!("prover worker restarted"));
+        }
+        Sp1MvpProver.prove(public_inputs).await
+    }
+}
+
+#[tokio::test]
+async fn retry_reruns_transient_failures_and_keeps_prior_attempts_in_provenance() {
+    let flaky = engine_with_prover(Arc::new(FlakyProver {
+        failures: 2,
+        calls: std::sync::atomic::AtomicUsize::new(0),
+    }));
+    let request = ProofRequest {
+        venue: Venue::Polymarket,
+        ..base_order(950)
+    };
+    let receipt_id = flaky.submit(request).await.expect("submit");
+    let failed = flaky
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        failed.non_provable.as_ref().map(|n| n.reason_code),
+        Some(NonProvableReason::PROOF_FAILURE)
+    );
+
+    let retrying = flaky.retry(&receipt_id).await.expect("retry");
+    assert_eq!(retrying.receipt_id, receipt_id);
+    assert_eq!(retrying.provenance.prior_attempts.len(), 1);
+    let again = flaky
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(again.status, ReceiptStatus::NON_PROVABLE);
+
+    flaky.retry(&receipt_id).await.expect("second retry");
+    let proved = flaky
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(proved.status, ReceiptStatus::PROVED);
+    assert!(proved.non_provable.is_none());
+    assert!(OffchainVerifier::default().verify(&proved).await);
+    let attempts = &proved.provenance.prior_attempts;
+    assert_eq!(
+        attempts.iter().map(|a| a.attempt).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert!(attempts
+        .iter()
+        .all(|a| a.reason_code == NonProvableReason::PROOF_FAILURE));
+    assert!(attempts[0].details.contains("prover worker restarted"));
+    // Each re-run fetches again, so only the artifacts without an acceptance time hash the same.
+    assert_eq!(
+        attempts[1].artifact_hashes.len(),
+        proved.provenance.evidence_items.len()
+    );
+    assert!(proved
+        .provenance
+        .evidence_items
+        .iter()
+        .filter(|item| item.source_kind == "venue_api_unsigned")
+        .all(|item| attempts[1].artifact_hashes.contains(&item.artifact_hash)));
+
+    // Settled receipts and failures a re-run cannot fix are refused.
+    let err = flaky
+        .retry(&receipt_id)
+        .await
+        .expect_err("proved receipts are not retried");
+    assert!(err
+        .to_string()
+        .contains("only SOURCE_UNAVAILABLE, PROOF_FAILURE and FINALITY_TIMEOUT"));
+    let engine = engine();
+    let unsupported = engine
+        .submit(ProofRequest {
+            venue: Venue::Polymarket,
+            claim_type: ClaimType::TRADE_BUSTED,
+            ..base_order(951)
+        })
+        .await
+        .expect("submit");
+    let rejected = engine
+        .wait_for_receipt(&unsupported, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(rejected.status, ReceiptStatus::NON_PROVABLE);
+    assert!(engine.retry(&unsupported).await.is_err());
+    assert!(engine.retry("zkr_missing").await.is_err());
+}
+
+const ANCHOR_CONTRACT: &str = "0x00000000000000000000000000000000000a4c40";
+
+// Anchors roots as transactions on a synthetic Base chain, emitting RootAnchored like the real contract.
+struct ChainAnchorPublisher(Arc<SyntheticEvmChain>);
+
+#[async_trait]
+impl AnchorPublisher for ChainAnchorPublisher {
+    async fn estimate_fees(&self) -> Result<FeeEstimate> {
+        SyntheticAnchorPublisher.estimate_fees().await
+    }
+
+    async fn publish_root(
+        &self,
+        root: &str,
+        leaf_count: usize,
+        _bid: &FeeBid,
+    ) -> Result<PublishAttempt> {
+        let log = SyntheticLog {
+            address: ANCHOR_CONTRACT.to_string(),
+            topics: vec![root_anchored_topic(), root.to_string()],
+            data: format!("0x{:064x}", leaf_count),
+        };
+        let tx_ref = self.0.submit_transaction(
+            "0x00000000000000000000000000000000000a0c01",
+            vec![log],
+            true,
+        );
+        Ok(PublishAttempt::Included(AnchorPublication {
+            tx_ref,
+            gas_used: 46_000,
+            ..AnchorPublication::default()
+        }))
+    }
+}
+
+#[tokio::test]
+async fn auditor_checks_anchor_transactions_against_the_chain() {
+    let chain = Arc::new(SyntheticEvmChain::new());
+    let anchoring = engine().with_anchor_publisher(Arc::new(ChainAnchorPublisher(chain.clone())));
+    let receipt_id = anchoring
+        .submit(ProofRequest {
+            verification_mode: VerificationMode::ONCHAIN_ANCHORED,
+            ..base_order(960)
+        })
+        .await
+        .expect("submit");
+    let receipt = anchoring
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    let document = |receipt: &zkputer::ZKReceipt, location: &str| ReceiptDocument {
+        location: location.to_string(),
+        contents: serde_json::to_string(receipt).expect("json"),
+    };
+    let checker = || {
+        AnchorTxChecker::new(chain.clone())
+            .with_min_confirmations(3)
+            .with_anchor_contract(ANCHOR_CONTRACT)
+    };
+    let auditor = ReceiptAuditor::new()
+        .with_keys(anchoring.keyring().clone())
+        .with_anchor_checker(checker());
+
+    // Included, but only one block deep.
+    let report = auditor
+        .audit("chain", vec![document(&receipt, "anchored.json")])
+        .await;
+    assert_eq!(report.anchor_rpc.as_deref(), Some("synthetic://base"));
+    assert_eq!(report.failure_reasons.get("anchor_unconfirmed"), Some(&1));
+    // Offline verification alone cannot tell.
+    assert_eq!(
+        ReceiptAuditor::new()
+            .audit("offline", vec![document(&receipt, "anchored.json")])
+            .await
+            .failed,
+        0
+    );
+
+    chain.mine_empty(2);
+    let report = auditor
+        .audit("chain", vec![document(&receipt, "anchored.json")])
+        .await;
+    assert_eq!((report.passed, report.failed), (1, 0));
+    assert!(report.slowest_checks.iter().any(|c| c.check == "anchor_tx"));
+
+    // The anchor reference is outside the signed hashes, so a forged one only shows up on chain.
+    let inclusion = receipt.proof.anchor_inclusion.clone().expect("inclusion");
+    let mut forged = receipt.clone();
+    let unrelated =
+        chain.submit_transaction("0x00000000000000000000000000000000000a0c02", vec![], true);
+    forged.proof.anchor_inclusion = Some(AnchorInclusion {
+        anchor_tx_ref: unrelated,
+        ..inclusion.clone()
+    });
+    let mut reverted = receipt.clone();
+    let failed_tx = chain.submit_transaction(
+        "0x00000000000000000000000000000000000a0c01",
+        vec![SyntheticLog {
+            address: ANCHOR_CONTRACT.to_string(),
+            topics: vec![root_anchored_topic(), inclusion.anchored_root.clone()],
+            data: "0x".to_string(),
+        }],
+        false,
+    );
+    reverted.proof.anchor_inclusion = Some(AnchorInclusion {
+        anchor_tx_ref: failed_tx,
+        ..inclusion.clone()
+    });
+    let mut missing = receipt.clone();
+    missing.proof.anchor_inclusion = Some(AnchorInclusion {
+        anchor_tx_ref: format!("0x{}", "ee".repeat(32)),
+        ..inclusion.clone()
+    });
+    let mut stripped = receipt.clone();
+    stripped.proof.anchor_inclusion = None;
+    chain.mine_empty(3);
+    let documents = vec![
+        document(&forged, "forged.json"),
+        document(&reverted, "reverted.json"),
+        document(&missing, "missing.json"),
+        document(&stripped, "stripped.json"),
+    ];
+    let report = auditor.audit("chain", documents).await;
+    assert_eq!(report.failed, 4);
+    for reason in [
+        "anchor_root_not_in_tx",
+        "anchor_tx_reverted",
+        "anchor_tx_not_found",
+        "anchor_missing",
+    ] {
+        assert_eq!(report.failure_reasons.get(reason), Some(&1), "{}", reason);
+    }
+
+    // Another contract emitting the same event does not count.
+    let elsewhere = ReceiptAuditor::new().with_anchor_checker(
+        AnchorTxChecker::new(chain.clone())
+            .with_anchor_contract("0x00000000000000000000000000000000000b0b0b"),
+    );
+    let report = elsewhere
+        .audit("chain", vec![document(&receipt, "anchored.json")])
+        .await;
+    assert_eq!(
+        report.failure_reasons.get("anchor_root_not_in_tx"),
+        Some(&1)
+    );
+}
+
+#[tokio::test]
+async fn invalidate_withdraws_a_proved_receipt_and_records_who_and_why() {
+    let engine = engine();
+    let receipt_id = proved_trade(&engine, 970).await;
+    let proved = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert!(proved.invalidation.is_none());
+
+    assert!(engine
+        .invalidate(&receipt_id, " ", "ops@desk")
+        .await
+        .is_err());
+    let invalidated = engine
+        .invalidate(&receipt_id, "counterparty dispute upheld", "ops@desk")
+        .await
+        .expect("invalidate");
+    assert_eq!(invalidated.status, ReceiptStatus::INVALIDATED);
+    let invalidation = invalidated
+        .invalidation
+        .as_ref()
+        .expect("invalidation recorded");
+    assert_eq!(
+        (invalidation.reason.as_str(), invalidation.actor.as_str()),
+        ("counterparty dispute upheld", "ops@desk")
+    );
+    assert_eq!(invalidation.invalidated_at, invalidated.timing.updated_at);
+    assert!(invalidated.non_provable.is_none());
+
+    // Re-signed and re-sequenced as a new issuance over the INVALIDATED status.
+    assert!(invalidated.integrity.sequence > proved.integrity.sequence);
+    assert_ne!(
+        invalidated.integrity.receipt_hash,
+        proved.integrity.receipt_hash
+    );
+    assert!(invalidated.verify_integrity(None).is_valid());
+    assert!(engine.keyring().verify_receipt_signature(&invalidated));
+    assert!(!OffchainVerifier::default().verify(&invalidated).await);
+
+    let stored = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(stored.invalidation, invalidated.invalidation);
+    let err = engine
+        .invalidate(&receipt_id, "again", "ops@desk")
+        .await
+        .expect_err("already invalidated");
+    assert!(err
+        .to_string()
+        .contains("only PROVED receipts can be invalidated"));
+    assert!(engine
+        .invalidate("zkr_missing", "gone", "ops@desk")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn http_receipt_routes_submit_fetch_and_filter_behind_a_bearer_token() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().unwrap();
+    let engine = Arc::new(engine());
+    tokio::spawn(serve(
+        listener,
+        Arc::new(HttpApi::new(Arc::clone(&engine)).with_api_token("s3cret")),
+    ));
+
+    let request = serde_json::to_vec(&base_order(980)).unwrap();
+    let (status, head, _) = http_call(addr, "POST", "/v1/receipts", None, &request).await;
+    assert_eq!(status, 401);
+    assert!(head.contains("WWW-Authenticate: Bearer"));
+    assert_eq!(
+        http_call(addr, "GET", "/v1/receipts", Some("wrong"), &[])
+            .await
+            .0,
+        401
+    );
+    // Probes stay open.
+    let (status, body) = http_get(addr, "/health").await;
+    assert_eq!((status, body["live"].as_bool()), (200, Some(true)));
+
+    let (status, head, submitted) =
+        http_call(addr, "POST", "/v1/receipts", Some("s3cret"), &request).await;
+    assert_eq!(status, 202);
+    let receipt_id = submitted["receipt_id"]
+        .as_str()
+        .expect("receipt id")
+        .to_string();
+    assert!(head.contains(&format!("Location: /v1/receipts/{}", receipt_id)));
+    engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let solana_id = proved_trade(&engine, 980).await;
+
+    let (status, _, fetched) = http_call(
+        addr,
+        "GET",
+        &format!("/v1/receipts/{}", receipt_id),
+        Some("s3cret"),
+        &[],
+    )
+    .await;
+    assert_eq!((status, fetched["status"].as_str()), (200, Some("PROVED")));
+    let target = format!("/v1/receipts/{}?version=v0.1.0", receipt_id);
+    let (status, _, legacy) = http_call(addr, "GET", &target, Some("s3cret"), &[]).await;
+    assert_eq!((status, legacy["version"].as_str()), (200, Some("v0.1.0")));
+    assert_eq!(
+        http_call(addr, "GET", "/v1/receipts/zkr_missing", Some("s3cret"), &[])
+            .await
+            .0,
+        404
+    );
+
+    let (status, _, listed) = http_call(
+        addr,
+        "GET",
+        "/v1/receipts?venue=solana&status=PROVED",
+        Some("s3cret"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(listed["count"], 1);
+    assert_eq!(
+        listed["receipts"][0]["receipt_id"].as_str(),
+        Some(solana_id.as_str())
+    );
+    let (_, _, listed) = http_call(
+        addr,
+        "GET",
+        "/v1/receipts?claim_type=ORDER_PLACED&limit=10",
+        Some("s3cret"),
+        &[],
+    )
+    .await;
+    assert_eq!(
+        listed["receipts"][0]["receipt_id"].as_str(),
+        Some(receipt_id.as_str())
+    );
+    for bad in [
+        "/v1/receipts?venue=nasdaq",
+        "/v1/receipts?status=DONE",
+        "/v1/receipts?limit=ten",
+        "/v1/receipts?colour=red",
+    ] {
+        assert_eq!(
+            http_call(addr, "GET", bad, Some("s3cret"), &[]).await.0,
+            400,
+            "{}",
+            bad
+        );
+    }
+    assert_eq!(
+        http_call(
+            addr,
+            "POST",
+            "/v1/receipts",
+            Some("s3cret"),
+            b"{\"venue\": \"base\"}"
+        )
+        .await
+        .0,
+        400
+    );
+    assert_eq!(
+        http_call(addr, "DELETE", "/v1/receipts", Some("s3cret"), &[])
+            .await
+            .0,
+        405
+    );
+
+    // Oversized header lines and too many headers are refused before the head is finished.
+    let raw_status = |head: String| async move {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+        stream.write_all(head.as_bytes()).await.expect("write");
+        stream.shutdown().await.expect("shutdown");
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).await.expect("read");
+        (
+            raw.split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string(),
+            raw,
+        )
+    };
+    let (status, raw) = raw_status(format!(
+        "GET /health HTTP/1.1\r\nX-Long: {}",
+        "a".repeat(8 << 10)
+    ))
+    .await;
+    assert_eq!(status, "400");
+    assert!(raw.contains("exceeds 8192 bytes"), "{}", raw);
+    let (status, raw) = raw_status(format!(
+        "GET /health HTTP/1.1\r\n{}",
+        "X-Many: 1\r\n".repeat(101)
+    ))
+    .await;
+    assert_eq!(status, "400");
+    assert!(raw.contains("more than 100 headers"), "{}", raw);
+}
+
+#[tokio::test]
+async fn evidence_bodies_expire_before_receipts_and_deletions_are_logged() {
+    let engine = engine().with_evidence_retention(Duration::from_millis(300));
+    let receipt_id = proved_trade(&engine, 990).await;
+    let erased_id = proved_trade(&engine, 991).await;
+    let receipt = engine.get_receipt(&receipt_id).await.expect("receipt");
+    let items = &receipt.provenance.evidence_items;
+    assert!(items.len() >= 3);
+    for item in items {
+        let body = engine
+            .evidence_artifact(&receipt_id, &item.artifact_hash)
+            .expect("body retained");
+        assert_eq!(zkputer::models::hash_bytes(&body), item.artifact_hash);
+        assert_eq!(body.len() as u64, item.size_bytes);
+    }
+
+    assert!(engine.erase_evidence(&erased_id, " ").is_err());
+    assert_eq!(
+        engine
+            .erase_evidence(&erased_id, "data subject request")
+            .unwrap(),
+        3
+    );
+    let erased = engine.get_receipt(&erased_id).await.expect("receipt");
+    assert!(engine
+        .evidence_artifact(
+            &erased_id,
+            &erased.provenance.evidence_items[0].artifact_hash
+        )
+        .is_none());
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    // Reads past expiry miss even before a sweep, and delete what they find.
+    assert!(engine
+        .evidence_artifact(&receipt_id, &items[0].artifact_hash)
+        .is_none());
+    assert_eq!(engine.sweep_evidence(), items.len() - 1);
+    assert_eq!(engine.sweep_evidence(), 0);
+
+    // The receipt only commits to hashes, so it verifies as before without the bodies.
+    let after = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(after.integrity.receipt_hash, receipt.integrity.receipt_hash);
+    assert!(OffchainVerifier::default().verify(&after).await);
+    assert!(after.verify_integrity(None).is_valid());
+
+    let log = engine.evidence_retention_log();
+    zkputer::retention::verify_retention_log(&log).expect("log chains");
+    let deleted: Vec<_> = log
+        .iter()
+        .filter(|e| e.action == zkputer::retention::RetentionAction::DELETED)
+        .collect();
+    assert_eq!(deleted.len(), items.len() + 3);
+    assert!(deleted
+        .iter()
+        .filter(|e| e.receipt_id == erased_id)
+        .all(|e| e.reason.as_deref() == Some("data subject request")));
+    assert!(deleted
+        .iter()
+        .filter(|e| e.receipt_id == receipt_id)
+        .all(|e| e.reason.as_deref() == Some(zkputer::retention::RETENTION_EXPIRED)));
+    let stored = log
+        .iter()
+        .find(|e| e.receipt_id == receipt_id)
+        .expect("stored entry");
+    assert!(stored.expires_at.is_some());
+    let mut tampered = log.clone();
+    tampered.retain(|e| {
+        !(e.receipt_id == erased_id && e.action == zkputer::retention::RetentionAction::DELETED)
+    });
+    assert!(zkputer::retention::verify_retention_log(&tampered).is_err());
+}
+
+#[test]
+fn mcp_sessions_negotiate_the_protocol_version_and_advertise_only_enabled_features() {
+    use zkputer::mcp::{
+        negotiate_protocol_version, McpFeatures, McpSession, LATEST_PROTOCOL_VERSION,
+    };
+
+    assert_eq!(negotiate_protocol_version(Some("2024-11-05")), "2024-11-05");
+    assert_eq!(negotiate_protocol_version(Some("2025-03-26")), "2025-03-26");
+    assert_eq!(
+        negotiate_protocol_version(Some("2099-01-01")),
+        LATEST_PROTOCOL_VERSION
+    );
+    assert_eq!(negotiate_protocol_version(None), LATEST_PROTOCOL_VERSION);
+
+    let mut session = McpSession::new(McpFeatures::default());
+    let init = session.initialize(
+        &serde_json::json!({ "protocolVersion": LATEST_PROTOCOL_VERSION, "clientInfo": { "name": "probe" } }),
+        "0.1.0",
+    );
+    assert_eq!(init["protocolVersion"], LATEST_PROTOCOL_VERSION);
+    assert_eq!(init["serverInfo"]["name"], "zkputer-mcp");
+    assert_eq!(session.client_info().unwrap()["name"], "probe");
+    let capabilities = &init["capabilities"];
+    assert!(capabilities.get("tools").is_some());
+    assert!(capabilities.get("resources").is_none());
+    assert_eq!(
+        capabilities["experimental"]["zkputer"]["progressNotifications"],
+        false
+    );
+    assert_eq!(capabilities["experimental"]["zkputer"]["streaming"], false);
+    let result = session.tool_result("{}".to_string(), serde_json::json!({ "ok": true }));
+    assert_eq!(result["structuredContent"]["ok"], true);
+    let token = serde_json::json!("tok-1");
+    assert!(session
+        .progress_notification(&token, 1, "PENDING")
+        .is_none());
+
+    let features = McpFeatures::from_env(Some("notifications, Resources"));
+    assert_eq!(
+        features,
+        McpFeatures {
+            resources: true,
+            notifications: true,
+            streaming: false
+        }
+    );
+    let mut session = McpSession::new(features);
+    let init = session.initialize(
+        &serde_json::json!({ "protocolVersion": "2025-03-26" }),
+        "0.1.0",
+    );
+    assert_eq!(init["protocolVersion"], "2025-03-26");
+    assert_eq!(init["capabilities"]["resources"]["subscribe"], true);
+    assert_eq!(
+        init["capabilities"]["experimental"]["zkputer"]["structuredContent"],
+        false
+    );
+    // Too old for structured content: the text carries everything instead.
+    let result = session.tool_result(
+        "{\"ok\":true}".to_string(),
+        serde_json::json!({ "ok": true }),
+    );
+    assert!(result.get("structuredContent").is_none());
+    assert_eq!(result["content"][0]["text"], "{\"ok\":true}");
+    let progress = session
+        .progress_notification(&token, 2, "PROVED")
+        .expect("notifications on");
+    assert_eq!(progress["method"], "notifications/progress");
+    assert_eq!(progress["params"]["message"], "PROVED");
+
+    session.initialize(
+        &serde_json::json!({ "protocolVersion": "2024-11-05" }),
+        "0.1.0",
+    );
+    assert_eq!(session.protocol_version(), "2024-11-05");
+    let progress = session
+        .progress_notification(&token, 3, "PROVED")
+        .expect("notifications on");
+    assert_eq!(progress["params"]["progress"], 3);
+    assert!(progress["params"].get("message").is_none());
+}
+
+// Returns the status, the response head and the raw body.
+async fn raw_http(
+    addr: std::net::SocketAddr,
+    method: &str,
+    target: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> (u16, String, String) {
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    let extra: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    let head = format!(
+        "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\n\r\n",
+        method,
+        target,
+        extra,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await.expect("write");
+    stream.write_all(body).await.expect("write");
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).await.expect("read");
+    let status = raw.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    (status, head.to_string(), body.to_string())
+}
+
+fn sse_messages(body: &str) -> Vec<Value> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter_map(|data| serde_json::from_str(data).ok())
+        .collect()
+}
+
+async fn next_sse_data(stream: &mut tokio::io::BufReader<tokio::net::TcpStream>) -> String {
+    use tokio::io::AsyncBufReadExt;
+    loop {
+        let mut line = String::new();
+        assert!(
+            stream.read_line(&mut line).await.unwrap() > 0,
+            "stream closed"
+        );
+        if let Some(data) = line.trim_end().strip_prefix("data: ") {
+            return data.to_string();
+        }
+    }
+}
+
+#[tokio::test]
+async fn mcp_is_served_over_streamable_http_and_the_legacy_sse_transport() {
+    use zkputer::mcp::{McpFeatures, McpServer};
+    use zkputer::mcp_http::{serve_mcp_http, McpHttpTransport};
+
+    let features = McpFeatures {
+        resources: false,
+        notifications: true,
+        streaming: true,
+    };
+    let server = Arc::new(McpServer::new(Arc::new(engine()), features));
+    let transport = Arc::new(McpHttpTransport::new(server).with_api_token("s3cret"));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(serve_mcp_http(listener, Arc::clone(&transport)));
+    let auth = ("Authorization", "Bearer s3cret");
+    let rpc = |id: u64, method: &str, params: Value| {
+        serde_json::to_vec(
+            &serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+        )
+        .unwrap()
+    };
+
+    let init = rpc(
+        1,
+        "initialize",
+        serde_json::json!({ "protocolVersion": "2025-06-18", "clientInfo": { "name": "probe" } }),
+    );
+    assert_eq!(raw_http(addr, "POST", "/mcp", &[], &init).await.0, 401);
+    assert_eq!(
+        raw_http(
+            addr,
+            "POST",
+            "/mcp",
+            &[auth, ("Origin", "https://evil.example")],
+            &init
+        )
+        .await
+        .0,
+        403
+    );
+    let (status, head, body) = raw_http(
+        addr,
+        "POST",
+        "/mcp",
+        &[auth, ("Origin", "http://localhost:3000")],
+        &init,
+    )
+    .await;
+    assert_eq!(status, 200);
+    let initialized: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(initialized["result"]["protocolVersion"], "2025-06-18");
+    assert_eq!(
+        initialized["result"]["capabilities"]["experimental"]["zkputer"]["streaming"],
+        true
+    );
+    let session_id = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Mcp-Session-Id: "))
+        .expect("session header")
+        .to_string();
+    let session = ("Mcp-Session-Id", session_id.as_str());
+
+    let list = rpc(2, "tools/list", serde_json::json!({}));
+    assert_eq!(raw_http(addr, "POST", "/mcp", &[auth], &list).await.0, 400);
+    assert_eq!(
+        raw_http(
+            addr,
+            "POST",
+            "/mcp",
+            &[auth, ("Mcp-Session-Id", "nope")],
+            &list
+        )
+        .await
+        .0,
+        404
+    );
+    assert_eq!(
+        raw_http(
+            addr,
+            "POST",
+            "/mcp",
+            &[auth, session, ("MCP-Protocol-Version", "1999-01-01")],
+            &list
+        )
+        .await
+        .0,
+        400
+    );
+    let notification = serde_json::to_vec(
+        &serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )
+    .unwrap();
+    let (status, _, body) = raw_http(addr, "POST", "/mcp", &[auth, session], &notification).await;
+    assert_eq!((status, body.as_str()), (202, ""));
+    let (status, _, body) = raw_http(
+        addr,
+        "POST",
+        "/mcp",
+        &[auth, session, ("MCP-Protocol-Version", "2025-06-18")],
+        &list,
+    )
+    .await;
+    assert_eq!(status, 200);
+    let tools: Value = serde_json::from_str(&body).unwrap();
+    assert!(tools["result"]["tools"].as_array().unwrap().len() >= 5);
+
+    // With SSE accepted, progress notifications stream ahead of the result.
+    let call = rpc(
+        3,
+        "tools/call",
+        serde_json::json!({
+            "name": "zkputer_verify_claim",
+            "_meta": { "progressToken": "p-1" },
+            "arguments": { "venue": "base", "claim_type": "ORDER_PLACED", "account_ref": "acct-mcp-http", "order_ref": "order-mcp-http" }
+        }),
+    );
+    let accept = ("Accept", "application/json, text/event-stream");
+    let (status, head, body) =
+        raw_http(addr, "POST", "/mcp", &[auth, session, accept], &call).await;
+    assert_eq!(status, 200);
+    assert!(head.contains("Content-Type: text/event-stream"));
+    let messages = sse_messages(&body);
+    assert!(messages.len() >= 2);
+    assert!(messages[..messages.len() - 1]
+        .iter()
+        .all(|m| m["method"] == "notifications/progress" && m["params"]["progressToken"] == "p-1"));
+    let last = messages.last().unwrap();
+    assert_eq!(last["id"], 3);
+    assert_eq!(last["result"]["structuredContent"]["status"], "PROVED");
+    let (status, head, body) = raw_http(addr, "POST", "/mcp", &[auth, session], &call).await;
+    assert_eq!(status, 200);
+    assert!(head.contains("Content-Type: application/json"));
+    assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["id"], 3);
+
+    assert_eq!(
+        raw_http(addr, "GET", "/mcp", &[auth, session], &[]).await.0,
+        406
+    );
+    assert_eq!(
+        raw_http(addr, "PUT", "/mcp", &[auth, session], &[]).await.0,
+        405
+    );
+    assert_eq!(
+        raw_http(addr, "DELETE", "/mcp", &[auth, session], &[])
+            .await
+            .0,
+        200
+    );
+    assert_eq!(
+        raw_http(addr, "POST", "/mcp", &[auth, session], &list)
+            .await
+            .0,
+        404
+    );
+
+    // The 2024-11-05 transport: results arrive on the SSE stream, not on the POST.
+    let mut sse = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    sse.write_all(b"GET /sse HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer s3cret\r\n\r\n")
+        .await
+        .unwrap();
+    let mut sse = tokio::io::BufReader::new(sse);
+    let endpoint = next_sse_data(&mut sse).await;
+    assert!(endpoint.starts_with("/messages?session_id="));
+    assert_eq!(
+        raw_http(addr, "POST", "/messages?session_id=nope", &[auth], &list)
+            .await
+            .0,
+        404
+    );
+    let legacy_init = rpc(
+        7,
+        "initialize",
+        serde_json::json!({ "protocolVersion": "2024-11-05" }),
+    );
+    let (status, _, body) = raw_http(addr, "POST", &endpoint, &[auth], &legacy_init).await;
+    assert_eq!((status, body.as_str()), (202, ""));
+    let response: Value = serde_json::from_str(&next_sse_data(&mut sse).await).unwrap();
+    assert_eq!(
+        (
+            response["id"].as_u64(),
+            response["result"]["protocolVersion"].as_str()
+        ),
+        (Some(7), Some("2024-11-05"))
+    );
+    raw_http(addr, "POST", &endpoint, &[auth], &call).await;
+    let mut response: Value = serde_json::from_str(&next_sse_data(&mut sse).await).unwrap();
+    while response["method"] == "notifications/progress" {
+        // Too old for progress messages.
+        assert!(response["params"].get("message").is_none());
+        response = serde_json::from_str(&next_sse_data(&mut sse).await).unwrap();
+    }
+    assert_eq!(response["id"], 3);
+    assert!(response["result"].get("structuredContent").is_none());
+    assert_eq!(transport.session_count(), 1);
+
+    // Idle sessions expire instead of holding a slot until the server restarts.
+    let server = Arc::new(McpServer::new(Arc::new(engine()), McpFeatures::default()));
+    let transport = Arc::new(
+        McpHttpTransport::new(server).with_session_idle_timeout(Duration::from_millis(100)),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(serve_mcp_http(listener, Arc::clone(&transport)));
+    let (_, head, _) = raw_http(addr, "POST", "/mcp", &[], &init).await;
+    let session_id = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Mcp-Session-Id: "))
+        .expect("session header")
+        .to_string();
+    assert_eq!(
+        raw_http(
+            addr,
+            "POST",
+            "/mcp",
+            &[("Mcp-Session-Id", session_id.as_str())],
+            &list
+        )
+        .await
+        .0,
+        200
+    );
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(
+        raw_http(
+            addr,
+            "POST",
+            "/mcp",
+            &[("Mcp-Session-Id", session_id.as_str())],
+            &list
+        )
+        .await
+        .0,
+        404
+    );
+    assert_eq!(transport.session_count(), 0);
+    raw_http(addr, "POST", "/mcp", &[], &init).await;
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    raw_http(addr, "POST", "/mcp", &[], &init).await;
+    assert_eq!(transport.session_count(), 1);
+}
+
+#[tokio::test]
+async fn mcp_list_receipts_pages_through_summaries_by_filter() {
+    use zkputer::mcp::{McpFeatures, McpServer};
+
+    let engine = Arc::new(engine());
+    let trades = [
+        proved_trade(&engine, 995).await,
+        proved_trade(&engine, 996).await,
+        proved_trade(&engine, 997).await,
+    ];
+    let order = engine.submit(base_order(995)).await.expect("submit");
+    engine
+        .wait_for_receipt(&order, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let server = McpServer::new(Arc::clone(&engine), McpFeatures::default());
+    let mut session = server.session();
+    let call = |arguments: Value| {
+        serde_json::to_vec(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "zkputer_list_receipts", "arguments": arguments }
+        }))
+        .unwrap()
+    };
+
+    let listed = server
+        .handle(
+            &mut session,
+            &call(serde_json::json!({ "venue": "solana", "page_size": 2 })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .expect("response");
+    let page = &listed["result"]["structuredContent"];
+    assert_eq!(page["count"], 2);
+    let ids: Vec<&str> = page["receipts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["receipt_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![trades[0].as_str(), trades[1].as_str()]);
+    let summary = &page["receipts"][0];
+    assert_eq!(
+        (summary["status"].as_str(), summary["claim_type"].as_str()),
+        (Some("PROVED"), Some("TRADE_EXECUTED"))
+    );
+    assert_eq!(summary["order_ref"], "order-refresh-995");
+    assert!(summary.get("provenance").is_none());
+    let cursor = page["next_cursor"]
+        .as_str()
+        .expect("more pages")
+        .to_string();
+
+    let listed = server
+        .handle(
+            &mut session,
+            &call(serde_json::json!({ "venue": "solana", "page_size": 2, "cursor": cursor })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .expect("response");
+    let page = &listed["result"]["structuredContent"];
+    assert_eq!(
+        page["receipts"][0]["receipt_id"].as_str(),
+        Some(trades[2].as_str())
+    );
+    assert!(page["next_cursor"].is_null());
+
+    let listed = server
+        .handle(
+            &mut session,
+            &call(serde_json::json!({ "status": "PROVED", "claim_type": "ORDER_PLACED" })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .expect("response");
+    assert_eq!(listed["result"]["structuredContent"]["count"], 1);
+    assert_eq!(
+        listed["result"]["structuredContent"]["receipts"][0]["receipt_id"].as_str(),
+        Some(order.as_str())
+    );
+    let rejected = server
+        .handle(
+            &mut session,
+            &call(serde_json::json!({ "status": "DONE" })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .expect("response");
+    assert_eq!(rejected["error"]["code"], -32000);
+
+    let tools = zkputer::mcp::tool_definitions();
+    let tool = tools["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "zkputer_list_receipts")
+        .expect("listed");
+    assert!(tool["inputSchema"]["properties"]["status"]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("NON_PROVABLE")));
+}
+
+#[tokio::test]
+async fn mcp_resources_address_receipts_by_uri_and_report_updates() {
+    use zkputer::mcp::{McpFeatures, McpServer};
+    use zkputer::mcp_http::{serve_mcp_http, McpHttpTransport};
+
+    let engine = Arc::new(engine());
+    let trade = proved_trade(&engine, 985).await;
+    let order = engine.submit(base_order(985)).await.expect("submit");
+    engine
+        .wait_for_receipt(&order, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let features = McpFeatures {
+        resources: true,
+        notifications: true,
+        streaming: false,
+    };
+    let server = Arc::new(McpServer::new(Arc::clone(&engine), features));
+    let mut session = server.session();
+    let rpc = |method: &str, params: Value| {
+        serde_json::to_vec(
+            &serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }),
+        )
+        .unwrap()
+    };
+
+    let listed = server
+        .handle(
+            &mut session,
+            &rpc("resources/list", serde_json::json!({})),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    let resources = listed["result"]["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 2);
+    let uri = format!("zkreceipt://{}", trade);
+    let entry = resources
+        .iter()
+        .find(|r| r["uri"] == uri.as_str())
+        .expect("trade listed");
+    assert_eq!(entry["mimeType"], "application/json");
+    assert_eq!(
+        entry["description"],
+        "TRADE_EXECUTED receipt on solana, PROVED"
+    );
+    assert!(listed["result"].get("nextCursor").is_none());
+
+    let read = server
+        .handle(
+            &mut session,
+            &rpc("resources/read", serde_json::json!({ "uri": uri })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    let content = &read["result"]["contents"][0];
+    assert_eq!(content["uri"], uri.as_str());
+    let receipt: Value = serde_json::from_str(content["text"].as_str().unwrap()).unwrap();
+    assert_eq!(receipt["receipt_id"], trade.as_str());
+    assert_eq!(receipt["status"], "PROVED");
+
+    let templates = server
+        .handle(
+            &mut session,
+            &rpc("resources/templates/list", serde_json::json!({})),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        templates["result"]["resourceTemplates"][0]["uriTemplate"],
+        "zkreceipt://{receipt_id}"
+    );
+    let missing = server
+        .handle(
+            &mut session,
+            &rpc(
+                "resources/read",
+                serde_json::json!({ "uri": "zkreceipt://nope" }),
+            ),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(missing["error"]["code"], -32002);
+    let foreign = server
+        .handle(
+            &mut session,
+            &rpc(
+                "resources/read",
+                serde_json::json!({ "uri": format!("file:///{}", trade) }),
+            ),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(foreign["error"]["code"], -32002);
+    let malformed = server
+        .handle(
+            &mut session,
+            &rpc("resources/read", serde_json::json!({})),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(malformed["error"]["code"], -32602);
+
+    // Subscribers hear about every later write, including ones after the receipt settled.
+    let (outbound, mut updates) = tokio::sync::mpsc::unbounded_channel();
+    session.attach_outbound(outbound);
+    let subscribed = server
+        .handle(
+            &mut session,
+            &rpc("resources/subscribe", serde_json::json!({ "uri": uri })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(subscribed.get("error").is_none());
+    assert_eq!(session.subscriptions(), vec![uri.clone()]);
+    engine
+        .invalidate(&trade, "venue restated the fill", "ops")
+        .await
+        .expect("invalidate");
+    let update = tokio::time::timeout(Duration::from_secs(5), updates.recv())
+        .await
+        .expect("update")
+        .unwrap();
+    assert_eq!(update["method"], "notifications/resources/updated");
+    assert_eq!(update["params"]["uri"], uri.as_str());
+    server
+        .handle(
+            &mut session,
+            &rpc("resources/unsubscribe", serde_json::json!({ "uri": uri })),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(session.subscriptions().is_empty());
+
+    let plain = McpServer::new(Arc::clone(&engine), McpFeatures::default());
+    let mut plain_session = plain.session();
+    let disabled = plain
+        .handle(
+            &mut plain_session,
+            &rpc("resources/list", serde_json::json!({})),
+            &mut |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(disabled["error"]["code"], -32601);
+
+    // Over streamable HTTP the updates travel on the session's GET stream.
+    let transport = Arc::new(McpHttpTransport::new(Arc::clone(&server)));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(serve_mcp_http(listener, transport));
+    let init = rpc(
+        "initialize",
+        serde_json::json!({ "protocolVersion": "2025-06-18" }),
+    );
+    let (_, head, _) = raw_http(addr, "POST", "/mcp", &[], &init).await;
+    let session_id = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Mcp-Session-Id: "))
+        .expect("session header")
+        .to_string();
+    let order_uri = format!("zkreceipt://{}", order);
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    let get = format!(
+        "GET /mcp HTTP/1.1\r\nHost: localhost\r\nAccept: text/event-stream\r\nMcp-Session-Id: {}\r\n\r\n",
+        session_id
+    );
+    stream.write_all(get.as_bytes()).await.unwrap();
+    let mut stream = tokio::io::BufReader::new(stream);
+    let mut status_line = String::new();
+    tokio::io::AsyncBufReadExt::read_line(&mut stream, &mut status_line)
+        .await
+        .unwrap();
+    assert!(status_line.contains(" 200 "));
+    let subscribe = rpc(
+        "resources/subscribe",
+        serde_json::json!({ "uri": order_uri }),
+    );
+    assert_eq!(
+        raw_http(
+            addr,
+            "POST",
+            "/mcp",
+            &[("Mcp-Session-Id", session_id.as_str())],
+            &subscribe
+        )
+        .await
+        .0,
+        200
+    );
+    engine
+        .invalidate(&order, "venue restated the order", "ops")
+        .await
+        .expect("invalidate");
+    let update: Value = serde_json::from_str(&next_sse_data(&mut stream).await).unwrap();
+    assert_eq!(update["params"]["uri"], order_uri.as_str());
+}
+
+#[tokio::test]
+async fn mcp_verify_receipt_checks_documents_issued_elsewhere() {
+    use zkputer::mcp::{McpFeatures, McpServer};
+
+    let foreign_signer = Ed25519ReceiptSigner::from_seed("venue-c", [12u8; 32]);
+    let foreign_key = foreign_signer.identity().public_key.clone();
+    let foreign = engine().with_receipt_signer(Arc::new(foreign_signer));
+    let engine = Arc::new(engine());
+    let own = engine
+        .get_receipt(&proved_trade(&engine, 990).await)
+        .await
+        .expect("receipt");
+    let theirs = foreign
+        .get_receipt(&proved_trade(&foreign, 991).await)
+        .await
+        .expect("receipt");
+    let server = McpServer::new(Arc::clone(&engine), McpFeatures::default());
+    let mut session = server.session();
+    let mut verify = async |arguments: Value| {
+        let message = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "zkputer_verify_receipt", "arguments": arguments }
+        });
+        let response = server
+            .handle(
+                &mut session,
+                &serde_json::to_vec(&message).unwrap(),
+                &mut |_| Ok(()),
+            )
+            .await
+            .expect("response");
+        response["result"].clone()
+    };
+
+    let checked = verify(serde_json::json!({ "receipt": own })).await["structuredContent"].clone();
+    assert_eq!(checked["valid"], true);
+    assert_eq!(checked["proof_valid"], true);
+    assert_eq!(checked["key_source"]["kind"], "local_key_ring");
+
+    // Without a key for the foreign signer the receipt is only shown to be self-consistent.
+    let checked = verify(serde_json::json!({ "receipt": serde_json::to_string(&theirs).unwrap() }))
+        .await["structuredContent"]
+        .clone();
+    assert_eq!(
+        (
+            checked["valid"].as_bool(),
+            checked["signer_trusted"].as_bool()
+        ),
+        (Some(true), Some(false))
+    );
+    assert_eq!(checked["key_source"]["kind"], "embedded");
+    let checked = verify(serde_json::json!({ "receipt": theirs, "public_key": foreign_key })).await
+        ["structuredContent"]
+        .clone();
+    assert_eq!(
+        (
+            checked["valid"].as_bool(),
+            checked["signer_trusted"].as_bool()
+        ),
+        (Some(true), Some(true))
+    );
+    let checked = verify(serde_json::json!({ "receipt": theirs, "public_key": "pk-someone-else" }))
+        .await["structuredContent"]
+        .clone();
+    assert_eq!(
+        (
+            checked["valid"].as_bool(),
+            checked["signature_valid"].as_bool()
+        ),
+        (Some(false), Some(false))
+    );
+
+    let mut tampered = theirs.clone();
+    tampered.provenance.evidence_root = "f".repeat(64);
+    let checked = verify(serde_json::json!({ "receipt": tampered, "public_key": foreign_key }))
+        .await["structuredContent"]
+        .clone();
+    assert_eq!(
+        (
+            checked["valid"].as_bool(),
+            checked["receipt_hash_valid"].as_bool()
+        ),
+        (Some(false), Some(false))
+    );
+
+    let mut forged = theirs.clone();
+    forged.proof.public_inputs_hash = "0".repeat(64);
+    let checked = verify(serde_json::json!({ "receipt": forged, "public_key": foreign_key })).await
+        ["structuredContent"]
+        .clone();
+    assert_eq!(
+        (checked["valid"].as_bool(), checked["proof_valid"].as_bool()),
+        (Some(false), Some(false))
+    );
+
+    let rejected = verify(serde_json::json!({ "receipt": { "receipt_id": "r-1" } })).await;
+    assert_eq!(rejected["isError"], true);
+    assert!(rejected["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .contains("zkreceipt schema"));
+
+    // The schema generated from the receipt types accepts issued receipts and names what is off.
+    let mut document = serde_json::to_value(&theirs).unwrap();
+    assert!(zkputer::schema::validate_receipt_json(&document).is_empty());
+    document["status"] = serde_json::json!("DONE");
+    document["proof"]["fallback_from"] = serde_json::json!(["SP1", 7]);
+    document["provenance"]["evidence_items"][0]["tags"] = serde_json::json!("fill");
+    let issues = zkputer::schema::validate_receipt_json(&document);
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.starts_with("receipt.status must be one of")),
+        "{:?}",
+        issues
+    );
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.starts_with("receipt.proof.fallback_from[1]")),
+        "{:?}",
+        issues
+    );
+    assert!(
+        issues.contains(
+            &"receipt.provenance.evidence_items[0].tags must be of type array".to_string()
+        ),
+        "{:?}",
+        issues
+    );
+}
+
+#[tokio::test]
+async fn execute_and_prove_places_the_order_and_proves_the_venue_ack() {
+    let gateway = Arc::new(SyntheticVenueAdapter::new(Venue::Base).with_order_execution());
+    let build = |enabled: bool| {
+        ReceiptEngine::new(
+            vec![
+                gateway.clone() as Arc<dyn VenueAdapter>,
+                Arc::new(SyntheticVenueAdapter::new(Venue::Solana)),
+            ],
+            PolicyEngine::new(None).expect("policy should load"),
+            Arc::new(Sp1MvpProver),
+            OffchainVerifier::default(),
+        )
+        .with_order_execution(enabled)
+    };
+    let engine = build(true);
+    assert!(engine.adapter_capabilities()[&Venue::Base].supports_execution);
+    assert!(!engine.adapter_capabilities()[&Venue::Solana].supports_execution);
+
+    let receipt_id = engine
+        .execute_and_prove(base_order(960))
+        .await
+        .expect("execute");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert!(receipt.provenance.submitted_by_engine);
+    assert_eq!(
+        gateway.submitted_orders(),
+        vec!["order-finality-960".to_string()]
+    );
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+    // Plain submissions still only observe.
+    let observed = engine.submit(base_order(961)).await.expect("submit");
+    let observed = engine
+        .wait_for_receipt(&observed, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert!(!observed.provenance.submitted_by_engine);
+    assert_eq!(gateway.submitted_orders().len(), 1);
+
+    // Nothing is sent without both gates, or for claims other than ORDER_PLACED.
+    let err = build(false)
+        .execute_and_prove(base_order(962))
+        .await
+        .expect_err("gated");
+    assert!(err.to_string().contains("not enabled"));
+    let solana = ProofRequest {
+        venue: Venue::Solana,
+        ..base_order(963)
+    };
+    assert!(engine.execute_and_prove(solana).await.is_err());
+    let trade = ProofRequest {
+        claim_type: ClaimType::TRADE_EXECUTED,
+        execution_ref: Some("exec-964".to_string()),
+        ..base_order(964)
+    };
+    assert!(engine.execute_and_prove(trade).await.is_err());
+    assert_eq!(gateway.submitted_orders().len(), 1);
+
+    // A failed submission may still have reached the venue, so it is not retried.
+    let mut flaky = base_order(965);
+    flaky.payload = serde_json::json!({ "script": { "fail_acknowledge": [1] } });
+    let failed = engine.execute_and_prove(flaky).await.expect("execute");
+    let failed_receipt = engine
+        .wait_for_receipt(&failed, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = failed_receipt.non_provable.expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::SOURCE_UNAVAILABLE
+    );
+    assert_eq!(
+        non_provable.remediation.failed_source.as_deref(),
+        Some("Base adapter submit_order")
+    );
+    let err = engine.retry(&failed).await.expect_err("not resubmitted");
+    assert!(err.to_string().contains("may still have reached the venue"));
+    assert_eq!(gateway.submitted_orders().len(), 1);
+}
+
+#[tokio::test]
+async fn refused_orders_are_proved_as_order_rejected_receipts() {
+    let gateway = Arc::new(SyntheticVenueAdapter::new(Venue::Base).with_order_execution());
+    let engine = ReceiptEngine::new(
+        vec![gateway.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_order_execution(true);
+
+    let mut refused = base_order(970);
+    refused.payload = serde_json::json!({ "reject_reason": "insufficient margin" });
+    let order_hash = refused.order_hash();
+    let receipt_id = engine.execute_and_prove(refused).await.expect("execute");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.claim.r#type, ClaimType::ORDER_REJECTED);
+    assert!(receipt
+        .claim
+        .statement
+        .contains("was rejected on venue base"));
+    assert!(receipt.claim.statement.contains("insufficient margin"));
+    assert!(receipt.claim.statement.contains(&order_hash));
+    assert!(receipt.provenance.submitted_by_engine);
+    assert_eq!(
+        gateway.submitted_orders(),
+        vec!["order-finality-970".to_string()]
+    );
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+
+    // Observed orders must match the claim: a rejection the venue never gave, or a placement it
+    // refused, is a conflict.
+    let unrefused = ProofRequest {
+        claim_type: ClaimType::ORDER_REJECTED,
+        ..base_order(971)
+    };
+    let mut refused_placement = base_order(972);
+    refused_placement.payload = serde_json::json!({ "reject_reason": "market closed" });
+    for request in [unrefused, refused_placement] {
+        let id = engine.submit(request).await.expect("submit");
+        let receipt = engine
+            .wait_for_receipt(&id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        let non_provable = receipt.non_provable.expect("non-provable");
+        assert_eq!(
+            non_provable.reason_code,
+            NonProvableReason::EVIDENCE_CONFLICT
+        );
+    }
+}
+
+#[tokio::test]
+async fn cancelled_orders_are_proved_from_the_venue_cancellation_artifact() {
+    let venue = Arc::new(SyntheticVenueAdapter::new(Venue::Base));
+    let engine = ReceiptEngine::new(
+        vec![venue.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_statement_locales(vec![StatementLocale::Ja]);
+    venue.publish_cancellation(OrderCancellation {
+        venue: Venue::Base,
+        order_ref: "order-finality-980".to_string(),
+        cancellation_ref: "cxl-980".to_string(),
+        cancelled_at: "2026-01-01T00:00:00Z".to_string(),
+    });
+
+    let cancelled = ProofRequest {
+        claim_type: ClaimType::ORDER_CANCELLED,
+        ..base_order(980)
+    };
+    let receipt_id = engine.submit(cancelled).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.claim.r#type, ClaimType::ORDER_CANCELLED);
+    assert_eq!(
+        receipt.claim.statement,
+        "Order order-finality-980 for account acct-finality-980 was cancelled on venue base at 2026-01-01T00:00:00Z by venue cancellation cxl-980."
+    );
+    assert!(receipt.claim.localized_statements[0]
+        .text
+        .contains("cxl-980"));
+    assert!(receipt.provenance.evidence_items.iter().any(|item| item
+        .tags
+        .contains(&"venue_cancellation_artifact".to_string())));
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+
+    // An order the venue never cancelled lacks the cancellation artifact the claim requires.
+    let open = ProofRequest {
+        claim_type: ClaimType::ORDER_CANCELLED,
+        ..base_order(981)
+    };
+    let id = engine.submit(open).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.non_provable.expect("non-provable").reason_code,
+        NonProvableReason::EVIDENCE_MISSING
+    );
+}
+
+#[tokio::test]
+async fn closed_positions_commit_to_realized_pnl_without_disclosing_it() {
+    let venue = Arc::new(SyntheticVenueAdapter::new(Venue::Base));
+    let engine = ReceiptEngine::new(
+        vec![venue.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_statement_locales(vec![StatementLocale::Zh]);
+    let closure = PositionClosure {
+        venue: Venue::Base,
+        position_ref: "pos-990".to_string(),
+        order_ref: "order-finality-990".to_string(),
+        closure_ref: "close-990".to_string(),
+        closed_at: "2026-01-01T00:00:00Z".to_string(),
+        realized_pnl: "-1532.75".to_string(),
+        currency: "USDC".to_string(),
+    };
+    venue.publish_position_closure(closure.clone());
+    let blinding = "0x5f2c8e1a9b3d4f60718293a4b5c6d7e8f90112233445566778899aabbccddeef";
+    let closed = ProofRequest {
+        claim_type: ClaimType::POSITION_CLOSED,
+        payload: serde_json::json!({ "pnl_blinding": blinding }),
+        ..base_order(990)
+    };
+
+    let receipt_id = engine.submit(closed).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    let commitment = receipt.claim.public_values["pnl_commitment"]
+        .as_str()
+        .expect("pnl commitment")
+        .to_string();
+    assert_eq!(
+        commitment,
+        pnl_commitment("pos-990", "-1532.75", "USDC", blinding)
+    );
+    assert_eq!(commitment, closure.pnl_commitment(blinding));
+    assert_eq!(
+        receipt.claim.statement,
+        format!(
+            "Position pos-990 for account acct-finality-990 was closed on venue base at 2026-01-01T00:00:00Z by order order-finality-990 (venue closure close-990), realizing PnL under commitment {}.",
+            commitment
+        )
+    );
+    let published = serde_json::to_string(&receipt).expect("json");
+    assert!(
+        !published.contains("1532"),
+        "the receipt never carries the PnL"
+    );
+    assert!(receipt.claim.localized_statements[0]
+        .text
+        .contains(&commitment));
+    assert_eq!(
+        receipt.proof.public_inputs_hash,
+        hash_json(&position_closed_public_inputs(
+            &receipt.claim.claim_hash,
+            &receipt.provenance.evidence_root,
+            Venue::Base,
+            &commitment,
+        ))
+    );
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+    let mut reopened = receipt.clone();
+    reopened.claim.public_values.insert(
+        "pnl_commitment".to_string(),
+        Value::String(pnl_commitment("pos-990", "250.00", "USDC", blinding)),
+    );
+    assert!(
+        !OffchainVerifier::default().verify(&reopened).await,
+        "another PnL does not match the proof"
+    );
+
+    // Without a blinding the commitment could be brute-forced, so the request is refused.
+    let unblinded = ProofRequest {
+        claim_type: ClaimType::POSITION_CLOSED,
+        ..base_order(990)
+    };
+    let id = engine.submit(unblinded).await.expect("submit");
+    let receipt = engine.get_receipt(&id).await.expect("receipt");
+    assert_eq!(
+        receipt.non_provable.expect("non-provable").reason_code,
+        NonProvableReason::SCHEMA_INVALID
+    );
+}
+
+// A desk's own POSITION_CLOSED layout on top of the built-in PnL commitment.
+struct DeskClosureHandler;
+
+#[async_trait]
+impl ClaimHandler for DeskClosureHandler {
+    fn claim_type(&self) -> ClaimType {
+        ClaimType::POSITION_CLOSED
+    }
+
+    fn public_values(
+        &self,
+        request: &ProofRequest,
+        bundle: &EvidenceBundle,
+    ) -> Result<serde_json::Map<String, Value>> {
+        PositionClosedHandler.public_values(request, bundle)
+    }
+
+    fn public_inputs(&self, claim: &TruthClaim, evidence_root: &str, venue: Venue) -> Value {
+        let mut inputs = PositionClosedHandler.public_inputs(claim, evidence_root, venue);
+        inputs["layout"] = serde_json::json!("desk-closure-v1");
+        inputs
+    }
+}
+
+#[tokio::test]
+async fn plugged_position_closed_handlers_commit_to_the_claims_pnl() {
+    let venue = Arc::new(SyntheticVenueAdapter::new(Venue::Base));
+    let engine = ReceiptEngine::new(
+        vec![venue.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_claim_handler(Arc::new(DeskClosureHandler));
+    venue.publish_position_closure(PositionClosure {
+        venue: Venue::Base,
+        position_ref: "pos-991".to_string(),
+        order_ref: "order-finality-991".to_string(),
+        closure_ref: "close-991".to_string(),
+        closed_at: "2026-01-01T00:00:00Z".to_string(),
+        realized_pnl: "88.10".to_string(),
+        currency: "USDC".to_string(),
+    });
+    let blinding = "0x0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+    let receipt_id = engine
+        .submit(ProofRequest {
+            claim_type: ClaimType::POSITION_CLOSED,
+            payload: serde_json::json!({ "pnl_blinding": blinding }),
+            ..base_order(991)
+        })
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+
+    let commitment = pnl_commitment("pos-991", "88.10", "USDC", blinding);
+    let mut expected = position_closed_public_inputs(
+        &receipt.claim.claim_hash,
+        &receipt.provenance.evidence_root,
+        Venue::Base,
+        &commitment,
+    );
+    expected["layout"] = serde_json::json!("desk-closure-v1");
+    assert_eq!(receipt.proof.public_inputs_hash, hash_json(&expected));
+    assert!(
+        OffchainVerifier::default()
+            .verify_with_handlers(&receipt, engine.claim_handlers())
+            .await
+    );
+}
+
+#[tokio::test]
+async fn engines_are_built_from_toml_config_with_env_overrides() {
+    let dir = std::env::temp_dir().join(format!("zkputer-config-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let key_path = dir.join("signer.key");
+    std::fs::write(&key_path, "07".repeat(32)).expect("key");
+    let text = format!(
+        r#"
+venues = ["base", "solana"]
+statement_locales = ["ja"]
+max_concurrent_proofs = 2
+
+[signer]
+name = "desk-a"
+key_path = "{}"
+
+[prover]
+strategy = "pico"
+sp1_vkey_hash = "0xABCD"
+
+[store]
+backend = "sqlite"
+path = "{}"
+"#,
+        key_path.display(),
+        dir.join("receipts.db").display()
+    );
+    let config = Config::from_toml_str(&text).expect("config");
+    assert_eq!(config.venues, vec![Venue::Base, Venue::Solana]);
+    assert_eq!(config.store.backend, StoreBackend::Sqlite);
+    assert!(Config::from_toml_str("signer_name = \"typo\"").is_err());
+
+    let env = HashMap::from([
+        ("ZKPUTER_VENUES", "base"),
+        ("ZKPUTER_SIGNER_NAME", "desk-b"),
+        ("ZKPUTER_PROVER_STRATEGY", ""),
+        ("ZKPUTER_RISC0_IMAGE_ID", "ef01"),
+    ]);
+    let config = config
+        .with_env_overrides(|name| env.get(name).map(|v| v.to_string()))
+        .expect("overrides");
+    assert_eq!(config.venues, vec![Venue::Base]);
+    assert_eq!(config.prover.strategy, "pico");
+    assert_eq!(
+        config.prover.verifier().pinned_keys(),
+        &[
+            (ProofBackend::SP1, "0xabcd".to_string()),
+            (ProofBackend::RISC0, "0xef01".to_string())
+        ]
+    );
+    let engine = ReceiptEngineBuilder::new(config)
+        .build()
+        .await
+        .expect("build");
+    assert_eq!(
+        engine.adapter_capabilities().keys().collect::<Vec<_>>(),
+        vec![&Venue::Base]
+    );
+    assert_eq!(engine.proof_queue_stats().max_concurrent, 2);
+    let receipt_id = engine.submit(base_order(970)).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(receipt.proof.backend, ProofBackend::PICO);
+    assert_eq!(receipt.integrity.signer.name, "desk-b");
+    assert_eq!(receipt.integrity.signer.algorithm, "ed25519");
+    assert_eq!(receipt.claim.localized_statements.len(), 1);
+    assert!(dir.join("receipts.db").exists());
+
+    // Without a key the named signer binds receipts by hash; explicit parts replace configured ones.
+    let config = Config::default()
+        .with_env_overrides(|name| (name == "ZKPUTER_SIGNER_NAME").then(|| "desk-c".to_string()))
+        .expect("overrides");
+    let engine = ReceiptEngineBuilder::new(config)
+        .with_adapter(Arc::new(
+            SyntheticVenueAdapter::new(Venue::Base).with_order_execution(),
+        ))
+        .with_store(ReceiptStore::new())
+        .build()
+        .await
+        .expect("build");
+    assert_eq!(engine.adapter_capabilities().len(), 4);
+    assert!(engine.adapter_capabilities()[&Venue::Base].supports_execution);
+    assert_eq!(engine.key_manifest().keys[0].identity.name, "desk-c");
+
+    let unknown = Config::default()
+        .with_env_overrides(|name| (name == "ZKPUTER_VENUES").then(|| "nasdaq".to_string()));
+    assert!(unknown.is_err());
+    // A host binary selects the real zkVM prover, which needs its feature and a pinned key.
+    let hosted =
+        Config::from_toml_str("[prover]\nsp1_host = \"/opt/zkputer/sp1-host\"").expect("config");
+    let err = hosted.prover.prover().err().expect("refused");
+    assert!(err.to_string().contains(if cfg!(feature = "sp1") {
+        "sp1_vkey_hash"
+    } else {
+        "sp1 feature"
+    }));
+    let hosted = Config::default()
+        .with_env_overrides(|name| match name {
+            "ZKPUTER_PROVER_STRATEGY" => Some("risc0".to_string()),
+            "ZKPUTER_RISC0_HOST" => Some("/opt/zkputer/risc0-host".to_string()),
+            "ZKPUTER_RISC0_IMAGE_ID" => Some("ef01".to_string()),
+            _ => None,
+        })
+        .expect("overrides");
+    match hosted.prover.prover() {
+        Ok(prover) => {
+            assert!(cfg!(feature = "risc0") && prover.backend_name() == ProofBackend::RISC0)
+        }
+        Err(err) => assert!(!cfg!(feature = "risc0") && err.to_string().contains("risc0 feature")),
+    }
+    assert_eq!(
+        Config::default()
+            .prover
+            .prover()
+            .expect("mvp prover")
+            .backend_name(),
+        ProofBackend::SP1
+    );
+    let pathless = Config::from_toml_str("[store]\nbackend = \"event_sourced\"").expect("config");
+    assert!(ReceiptEngineBuilder::new(pathless).build().await.is_err());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn policy_engines_can_be_built_from_in_memory_documents() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("spec");
+    let read = |file: &str| -> Value {
+        serde_json::from_str(&std::fs::read_to_string(root.join(file)).expect("read"))
+            .expect("parse")
+    };
+    let mut taxonomy = read("claim-taxonomy.json");
+    let precedence = read("source-precedence.json");
+    let schema = serde_json::json!({ "type": "object", "required": ["desk"] });
+    taxonomy["claim_types"]["ORDER_PLACED"]["payload_schema"] = schema.clone();
+    let policy = PolicyEngine::from_values(taxonomy.clone(), precedence.clone()).expect("policy");
+    assert_eq!(policy.payload_schema(ClaimType::ORDER_PLACED), Some(schema));
+    assert_eq!(
+        policy.source_precedence_version(),
+        PolicyEngine::new(None)
+            .expect("default policy")
+            .source_precedence_version()
+    );
+
+    let err =
+        PolicyEngine::from_values(serde_json::json!({}), precedence).expect_err("no claim types");
+    assert!(err.to_string().contains("claim_types"));
+    assert!(PolicyEngine::from_values(taxonomy, serde_json::json!([])).is_err());
+}
+
+#[tokio::test]
+async fn encrypted_payload_fields_are_opened_only_inside_the_pipeline() {
+    let key = PayloadDecryptionKey::generate();
+    let store = ReceiptStore::new();
+    let engine = engine()
+        .with_store(store.clone())
+        .with_payload_decryption_key(key.clone());
+    let public_key = engine.payload_encryption_key().expect("public key");
+    assert_eq!(
+        engine.instance_descriptor().payload_encryption_key,
+        Some(public_key.to_hex())
+    );
+
+    let (notes, notes_commitment) = seal_payload_field(
+        &public_key,
+        "notes",
+        &serde_json::json!("desk strategy alpha"),
+    )
+    .expect("seal");
+    let (price, price_commitment) =
+        seal_payload_field(&public_key, "expected_price", &serde_json::json!(101.5)).expect("seal");
+    let request = ProofRequest {
+        execution_ref: Some("exec-sealed".to_string()),
+        payload: serde_json::json!({ ENCRYPTED_FIELDS_KEY: { "notes": notes, "expected_price": price } }),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::TRADE_EXECUTED,
+            "acct-sealed",
+            "order-sealed",
+        )
+    };
+    let receipt_id = engine.submit(request.clone()).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(receipt.status, ReceiptStatus::PROVED);
+    assert_eq!(
+        receipt.claim.encrypted_fields,
+        vec![price_commitment, notes_commitment]
+    );
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+    let stored = store.request(&receipt_id).await.expect("stored request");
+    assert!(stored.payload.get("notes").is_none());
+    assert!(!serde_json::to_string(&stored)
+        .expect("json")
+        .contains("desk strategy alpha"));
+
+    // Fields sealed to another key, or sent to an engine without one, are rejected.
+    let other = PayloadDecryptionKey::generate().public_key();
+    let (foreign, _) = seal_payload_field(&other, "notes", &serde_json::json!("x")).expect("seal");
+    let misaddressed = ProofRequest {
+        payload: serde_json::json!({ ENCRYPTED_FIELDS_KEY: { "notes": foreign } }),
+        ..request.clone()
+    };
+    for (engine, request) in [(&engine, misaddressed), (&self::engine(), request.clone())] {
+        let id = engine.submit(request).await.expect("submit");
+        let receipt = engine
+            .wait_for_receipt(&id, Duration::from_secs(5))
+            .await
+            .expect("wait");
+        assert_eq!(
+            receipt.non_provable.expect("non-provable").reason_code,
+            NonProvableReason::SCHEMA_INVALID
+        );
+    }
+
+    // Tampered ciphertexts fail authentication, and low-order keys are refused outright.
+    let (mut tampered, _) =
+        seal_payload_field(&public_key, "notes", &serde_json::json!("x")).expect("seal");
+    tampered.ciphertext.replace_range(
+        0..2,
+        if tampered.ciphertext.starts_with("00") {
+            "01"
+        } else {
+            "00"
+        },
+    );
+    let tampered = ProofRequest {
+        payload: serde_json::json!({ ENCRYPTED_FIELDS_KEY: { "notes": tampered } }),
+        ..request.clone()
+    };
+    let err = open_payload(&tampered, Some(&key)).expect_err("tampered");
+    assert!(
+        format!("{:#}", err).contains("failed authentication"),
+        "{:#}",
+        err
+    );
+    let low_order = PayloadEncryptionKey::from_hex(&"00".repeat(32)).expect("key");
+    assert!(seal_payload_field(&low_order, "notes", &serde_json::json!("x")).is_err());
+}
+
+#[tokio::test]
+async fn watched_policy_swaps_in_tightened_requirements_without_a_restart() {
+    let dir = std::env::temp_dir().join(format!("zkputer-policy-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let spec = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("spec");
+    let (taxonomy_path, precedence_path) = (
+        dir.join("claim-taxonomy.json"),
+        dir.join("source-precedence.json"),
+    );
+    std::fs::copy(spec.join("claim-taxonomy.json"), &taxonomy_path).expect("copy");
+    std::fs::copy(spec.join("source-precedence.json"), &precedence_path).expect("copy");
+
+    let watch = PolicyEngine::watch(&taxonomy_path, &precedence_path).expect("watch");
+    let engine = engine().with_policy_watch(&watch);
+    let order = |n: usize| {
+        ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::ORDER_PLACED,
+            format!("acct-policy-{}", n),
+            format!("order-policy-{}", n),
+        )
+    };
+    let id = engine.submit(order(1)).await.expect("submit");
+    let before = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(before.status, ReceiptStatus::PROVED);
+    assert_eq!(before.policy.policy_id, "default-v0.1.0");
+    assert!(!watch.reload(), "unchanged files are not a new revision");
+
+    let mut taxonomy: Value =
+        serde_json::from_str(&std::fs::read_to_string(&taxonomy_path).expect("read"))
+            .expect("parse");
+    taxonomy["claim_types"]["ORDER_PLACED"]["required_evidence_tags_all"]
+        .as_array_mut()
+        .expect("tags")
+        .push(serde_json::json!("reference_price_quote"));
+    std::fs::write(&taxonomy_path, taxonomy.to_string()).expect("write");
+    assert!(watch.reload());
+    assert_eq!(watch.current().revision(), 1);
+    let id = engine.submit(order(2)).await.expect("submit");
+    let after = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        after.non_provable.expect("non-provable").reason_code,
+        NonProvableReason::EVIDENCE_MISSING
+    );
+    assert_eq!(after.policy.policy_id, "default-v0.1.0-r1");
+    let published: Vec<String> = engine
+        .instance_descriptor()
+        .policies
+        .into_iter()
+        .map(|p| p.policy_id)
+        .collect();
+    assert_eq!(
+        published,
+        vec![
+            "default-v0.1.0".to_string(),
+            "default-v0.1.0-r1".to_string()
+        ]
+    );
+
+    // A broken edit keeps the policy in force until it is fixed.
+    std::fs::write(&taxonomy_path, "{ not json").expect("write");
+    assert!(!watch.reload());
+    assert!(watch
+        .last_error()
+        .is_some_and(|err| err.contains("claim taxonomy")));
+    assert_eq!(watch.current().revision(), 1);
+}
+
+#[tokio::test]
+async fn tenant_overlays_patch_the_base_policy_per_tenant() {
+    let base = PolicyEngine::new(None).expect("policy should load");
+    let strict = serde_json::json!({
+        "claim_taxonomy": {
+            "claim_types": {
+                "ORDER_PLACED": {
+                    "required_evidence_tags_all": [
+                        "order_identity",
+                        "submission_timestamp",
+                        "venue_acceptance_artifact",
+                        "reference_price_quote"
+                    ]
+                }
+            }
+        }
+    });
+    let typo = serde_json::json!({ "claim_taxonomies": {} });
+    assert!(base.clone().with_tenant_overlay("strict", typo).is_err());
+    let broken = serde_json::json!({ "source_precedence": { "venues": null } });
+    assert!(base.clone().with_tenant_overlay("strict", broken).is_err());
+    let policy = base.with_tenant_overlay("strict", strict).expect("overlay");
+    assert_eq!(policy.tenants(), vec!["strict".to_string()]);
+
+    let engine = ReceiptEngine::new(
+        vec![Arc::new(SyntheticVenueAdapter::new(Venue::Hyperliquid)) as Arc<dyn VenueAdapter>],
+        policy,
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+    let order = |n: usize, tenant_id: Option<&str>| ProofRequest {
+        tenant_id: tenant_id.map(str::to_string),
+        ..ProofRequest::new(
+            Venue::Hyperliquid,
+            ClaimType::ORDER_PLACED,
+            format!("acct-tenant-{}", n),
+            format!("order-tenant-{}", n),
+        )
+    };
+    let mut receipts = Vec::new();
+    for (n, tenant_id) in [(1, None), (2, Some("strict")), (3, Some("lenient"))] {
+        let id = engine.submit(order(n, tenant_id)).await.expect("submit");
+        receipts.push(
+            engine
+                .wait_for_receipt(&id, Duration::from_secs(5))
+                .await
+                .expect("wait"),
+        );
+    }
+    let [untenanted, strict, lenient] = receipts.try_into().expect("three receipts");
+
+    assert_eq!(untenanted.status, ReceiptStatus::PROVED);
+    assert_eq!(untenanted.policy.policy_id, "default-v0.1.0");
+    assert!(untenanted.policy.policy_hash.is_some());
+    let non_provable = strict.non_provable.expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::EVIDENCE_MISSING
+    );
+    assert_eq!(
+        non_provable.remediation.missing_tags,
+        vec!["reference_price_quote".to_string()]
+    );
+    assert_eq!(strict.policy.policy_id, "default-v0.1.0+strict");
+    assert_ne!(strict.policy.policy_hash, untenanted.policy.policy_hash);
+    // A tenant without an overlay is held to the base policy.
+    assert_eq!(lenient.status, ReceiptStatus::PROVED);
+    assert_eq!(lenient.policy, untenanted.policy);
+
+    let published: Vec<String> = engine
+        .instance_descriptor()
+        .policies
+        .into_iter()
+        .map(|p| p.policy_id)
+        .collect();
+    assert!(published.contains(&"default-v0.1.0+strict".to_string()));
+}
+
+#[tokio::test]
+async fn finality_rules_gate_trades_on_each_venues_confirmation_depth() {
+    let policy = PolicyEngine::new(None).expect("policy should load");
+    assert_eq!(policy.finality_rule_id(), "venue-default-finality-v0.1.0");
+    assert!(matches!(
+        policy.finality_gate(Venue::Base, ClaimType::TRADE_EXECUTED),
+        Some(FinalityRule::Confirmations { depth: 12, .. })
+    ));
+    assert_eq!(
+        policy.finality_gate(Venue::Base, ClaimType::ORDER_PLACED),
+        None
+    );
+
+    let router = "0x2626664c2603336e57b271c5c0b26f421741e481";
+    let chain = Arc::new(SyntheticEvmChain::new());
+    let fill = chain.submit_transaction(
+        "0x1111111111111111111111111111111111111111",
+        vec![SyntheticLog {
+            address: router.to_string(),
+            topics: vec![format!("0x{}", "ab".repeat(32))],
+            data: "0x01".to_string(),
+        }],
+        true,
+    );
+    chain.mine_empty(3);
+    // The adapter is satisfied at two confirmations; the policy is not.
+    let shallow = |policy: PolicyEngine| {
+        ReceiptEngine::new(
+            vec![Arc::new(
+                BaseEvmAdapter::new(chain.clone()).with_confirmations(2),
+            )],
+            policy,
+            Arc::new(Sp1MvpProver),
+            OffchainVerifier::default(),
+        )
+    };
+    let engine = shallow(policy.clone());
+    let id = engine
+        .submit(evm_trade(&fill, router))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::FINALITY_TIMEOUT
+    );
+    assert!(
+        non_provable.details.contains("of 12 confirmations"),
+        "{}",
+        non_provable.details
+    );
+    assert!(non_provable.remediation.retry_after_ms.is_some());
+    assert_eq!(
+        receipt.policy.finality_rule_id,
+        "venue-default-finality-v0.1.0"
+    );
+
+    let spec = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("spec/finality-rules.json");
+    let mut rules: Value =
+        serde_json::from_str(&std::fs::read_to_string(spec).expect("read")).expect("parse");
+    rules["version"] = serde_json::json!("0.2.0");
+    rules["venues"]["base"] =
+        serde_json::json!({ "kind": "confirmations", "depth": 2, "timeout_ms": 50 });
+    let relaxed = policy
+        .clone()
+        .with_finality_rules(rules.clone())
+        .expect("rules");
+    let engine = shallow(relaxed.clone());
+    let id = engine
+        .submit(evm_trade(&fill, router))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    assert_eq!(
+        receipt.policy.finality_rule_id,
+        "venue-default-finality-v0.2.0"
+    );
+
+    // Waiting on the observer, the rule's timeout applies when it is tighter than the engine's.
+    let stalled = shallow(relaxed).with_finality_observer(
+        FinalityObserver::new(Duration::from_millis(5)).with_head_source(
+            Venue::Base,
+            Arc::new(TimedChainHead::new(Duration::from_secs(60))),
+        ),
+        Duration::from_secs(5),
+    );
+    let started = std::time::Instant::now();
+    let id = stalled
+        .submit(evm_trade(&fill, router))
+        .await
+        .expect("submit");
+    let receipt = stalled
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::FINALITY_TIMEOUT
+    );
+    assert!(
+        non_provable.details.contains("within 50 ms"),
+        "{}",
+        non_provable.details
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    rules["venues"]["solana"] = serde_json::json!({ "kind": "commitment", "level": "rooted" });
+    assert!(policy.with_finality_rules(rules).is_err());
+}
+
+#[tokio::test]
+async fn escrowed_receipts_publish_only_their_commitment_until_released() {
+    let engine = Arc::new(engine());
+    let api = HttpApi::new(Arc::clone(&engine));
+    let call = |method: &str, path: String, body: Vec<u8>| HttpRequest {
+        method: method.to_string(),
+        path,
+        query: None,
+        authorization: None,
+        peer: None,
+        body,
+        headers: vec![],
+    };
+    let request = ProofRequest {
+        escrow_hold_ms: Some(1_000),
+        ..base_order(990)
+    };
+    let submitted = api
+        .route(&call(
+            "POST",
+            "/v1/receipts".to_string(),
+            serde_json::to_vec(&request).unwrap(),
+        ))
+        .await;
+    assert_eq!(submitted.status, 202);
+    assert_eq!(submitted.body["subject"]["order_ref"], WITHHELD);
+    let receipt_id = submitted.body["receipt_id"]
+        .as_str()
+        .expect("receipt id")
+        .to_string();
+    let full = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(full.status, ReceiptStatus::PROVED);
+    assert_eq!(full.subject.order_ref, "order-finality-990");
+    assert!(!full.provenance.evidence_items.is_empty());
+
+    // Everyone else sees the commitment, which still verifies against the signer.
+    let fetched = api
+        .route(&call("GET", format!("/v1/receipts/{}", receipt_id), vec![]))
+        .await;
+    assert_eq!(fetched.status, 200);
+    let public: zkputer::models::ZKReceipt =
+        serde_json::from_value(fetched.body.clone()).expect("receipt");
+    assert_eq!(
+        (
+            public.subject.account_ref.as_str(),
+            public.subject.order_ref.as_str()
+        ),
+        (WITHHELD, WITHHELD)
+    );
+    assert!(public.claim.statement.starts_with(WITHHELD));
+    assert!(
+        public.provenance.evidence_items.is_empty()
+            && public.timing.execution_observed_at.is_none()
+    );
+    assert_eq!(public.integrity.receipt_hash, full.integrity.receipt_hash);
+    assert_eq!(
+        public.provenance.evidence_root,
+        full.provenance.evidence_root
+    );
+    assert!(public.verify_integrity(None).is_valid());
+    let listed = api
+        .route(&call("GET", "/v1/receipts".to_string(), vec![]))
+        .await;
+    assert_eq!(listed.body["receipts"][0]["subject"]["order_ref"], WITHHELD);
+    let delivery = engine
+        .sign_webhook_delivery("receipt.proved", full.clone())
+        .expect("delivery");
+    assert!(!String::from_utf8(delivery.body)
+        .unwrap()
+        .contains("order-finality-990"));
+
+    let release = |actor: &str| {
+        call(
+            "POST",
+            format!("/v1/receipts/{}/escrow/release", receipt_id),
+            serde_json::to_vec(&serde_json::json!({ "actor": actor })).unwrap(),
+        )
+    };
+    assert_eq!(api.route(&release("ops@desk")).await.status, 409);
+    assert_eq!(api.route(&release(" ")).await.status, 400);
+    let release_after = full.escrow.as_ref().expect("escrow").release_after.clone();
+    let due = chrono::DateTime::parse_from_rfc3339(&release_after)
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    tokio::time::sleep(
+        (due - chrono::Utc::now()).to_std().unwrap_or_default() + Duration::from_millis(20),
+    )
+    .await;
+
+    let released = api.route(&release("ops@desk")).await;
+    assert_eq!(released.status, 200);
+    assert_eq!(released.body["escrow"]["released_by"], "ops@desk");
+    // Releasing is not a new issuance: nothing the receipt hash covers changed.
+    let stored = engine.get_receipt(&receipt_id).await.expect("receipt");
+    assert_eq!(stored.integrity.sequence, full.integrity.sequence);
+    assert!(stored.verify_integrity(None).is_valid());
+    let fetched = api
+        .route(&call("GET", format!("/v1/receipts/{}", receipt_id), vec![]))
+        .await;
+    assert_eq!(fetched.body["subject"]["order_ref"], "order-finality-990");
+    assert_eq!(api.route(&release("ops@desk")).await.status, 409);
+    assert!(engine
+        .release_escrow(&proved_trade(&engine, 991).await, "ops@desk")
+        .await
+        .is_err());
+
+    // Receipts that settle without a proof are held and released the same way.
+    let failing = ProofRequest {
+        claim_type: ClaimType::TRADE_EXECUTED,
+        escrow_hold_ms: Some(1),
+        ..base_order(992)
+    };
+    let id = engine.submit(failing).await.expect("submit");
+    let failed = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(failed.status, ReceiptStatus::NON_PROVABLE);
+    assert!(engine
+        .published_receipt(&id)
+        .await
+        .unwrap()
+        .non_provable
+        .unwrap()
+        .details
+        .is_empty());
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let released = engine
+        .release_escrow(&id, "ops@desk")
+        .await
+        .expect("release");
+    assert_eq!(released.status, ReceiptStatus::NON_PROVABLE);
+    assert_eq!(
+        engine
+            .published_receipt(&id)
+            .await
+            .unwrap()
+            .non_provable
+            .unwrap()
+            .details,
+        failed.non_provable.unwrap().details
+    );
+}
+
+#[tokio::test]
+async fn finality_rechecks_poll_the_adapter_until_the_finality_deadline() {
+    let router = "0x2626664c2603336e57b271c5c0b26f421741e481";
+    let chain = Arc::new(SyntheticEvmChain::new());
+    let fill = |chain: &SyntheticEvmChain| {
+        chain.submit_transaction(
+            "0x1111111111111111111111111111111111111111",
+            vec![SyntheticLog {
+                address: router.to_string(),
+                topics: vec![format!("0x{}", "ab".repeat(32))],
+                data: "0x01".to_string(),
+            }],
+            true,
+        )
+    };
+    let engine = ReceiptEngine::new(
+        vec![Arc::new(
+            BaseEvmAdapter::new(chain.clone()).with_confirmations(2),
+        )],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_finality_rechecks(Duration::from_millis(5));
+
+    // Short of the rule's 12 confirmations when collected; the chain catches up while it waits.
+    let deepening = fill(&chain);
+    chain.mine_empty(3);
+    let request = ProofRequest {
+        finality_timeout_ms: Some(5_000),
+        ..evm_trade(&deepening, router)
+    };
+    let id = engine.submit(request).await.expect("submit");
+    let miner = {
+        let chain = chain.clone();
+        tokio::spawn(async move {
+            for _ in 0..10 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                chain.mine_empty(1);
+            }
+        })
+    };
+    let receipt = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    miner.await.expect("miner");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    assert!(receipt.timing.finality_deadline.is_some());
+    assert!(receipt.timing.breakdown.finality_ms.is_some());
+    assert!(engine.receipt_cost(&id).await.expect("cost").adapter_calls > 2);
+
+    // Nothing is mined this time, so the request's tighter deadline runs out.
+    let stuck = fill(&chain);
+    let request = ProofRequest {
+        finality_timeout_ms: Some(60),
+        ..evm_trade(&stuck, router)
+    };
+    let id = engine.submit(request).await.expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    let non_provable = receipt.non_provable.as_ref().expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::FINALITY_TIMEOUT
+    );
+    assert!(
+        non_provable.details.contains("finality deadline"),
+        "{}",
+        non_provable.details
+    );
+    assert!(
+        non_provable.details.contains("of 12 confirmations"),
+        "{}",
+        non_provable.details
+    );
+    assert!(non_provable.details.contains(
+        receipt
+            .timing
+            .finality_deadline
+            .as_deref()
+            .expect("deadline")
+    ));
+    let invalid = ProofRequest {
+        finality_timeout_ms: Some(0),
+        ..evm_trade(&stuck, router)
+    };
+    let id = engine.submit(invalid).await.expect("submit");
+    let receipt = engine.get_receipt(&id).await.expect("receipt");
+    assert_eq!(
+        receipt.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::SCHEMA_INVALID)
+    );
+}
+
+#[tokio::test]
+async fn fake_venue_server_serves_adapters_over_real_http() {
+    let venue = FakeVenueServer::start().await.expect("fake venue");
+    let engine = venue.engine().expect("engine");
+    let settle = |request: ProofRequest| {
+        let engine = &engine;
+        async move {
+            let receipt_id = engine.submit(request).await.expect("submit");
+            engine
+                .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+                .await
+                .expect("wait")
+        }
+    };
+
+    let router = "0x2626664c2603336e57b271c5c0b26f421741e481";
+    let fill = venue.base().submit_transaction(
+        "0x1111111111111111111111111111111111111111",
+        vec![SyntheticLog {
+            address: router.to_string(),
+            topics: vec![format!("0x{}", "ab".repeat(32))],
+            data: "0x01".to_string(),
+        }],
+        true,
+    );
+    venue.base().mine_empty(12);
+    let receipt = settle(evm_trade(&fill, router)).await;
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    assert!(receipt
+        .provenance
+        .collection
+        .iter()
+        .all(|record| record.endpoint_url.starts_with(&venue.url())));
+    let calls = venue.requests();
+    assert!(calls.iter().any(|call| call.path == FAKE_BASE_RPC_PATH
+        && call.body["method"] == "eth_getTransactionReceipt"));
+
+    let maker = "0x2222222222222222222222222222222222222222";
+    let order = venue.clob().place_order(
+        maker,
+        &format!("0x{}", "cd".repeat(32)),
+        "7131",
+        "BUY",
+        0.55,
+        100.0,
+    );
+    let placed = settle(polymarket_claim(ClaimType::ORDER_PLACED, &order, None)).await;
+    assert_eq!(
+        placed.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        placed.non_provable
+    );
+    assert!(venue
+        .requests()
+        .iter()
+        .any(|call| call.path == format!("{}/data/order/{}", FAKE_CLOB_PATH, order)));
+
+    // A canned outage stands in for the venue until it is used up.
+    let down = venue.clob().place_order(
+        maker,
+        &format!("0x{}", "cd".repeat(32)),
+        "7131",
+        "SELL",
+        0.6,
+        10.0,
+    );
+    venue.respond(
+        CannedResponse::new("GET", &format!("{}/data/order/{}", FAKE_CLOB_PATH, down))
+            .with_status(503)
+            .with_body(serde_json::json!({ "error": "maintenance" }))
+            .with_times(1),
+    );
+    let unavailable = settle(polymarket_claim(ClaimType::ORDER_PLACED, &down, None)).await;
+    assert_eq!(
+        unavailable.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::SOURCE_UNAVAILABLE)
+    );
+    let recovered = settle(polymarket_claim(ClaimType::ORDER_PLACED, &down, None)).await;
+    assert_eq!(
+        recovered.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        recovered.non_provable
+    );
+}
+
+#[tokio::test]
+async fn standbys_serve_reads_and_take_over_pending_receipts_from_a_lapsed_leader() {
+    let path =
+        std::env::temp_dir().join(format!("zkputer-receipts-{}.sqlite", uuid::Uuid::new_v4()));
+    let ttl = Duration::from_secs(30);
+    let first = engine_with_prover(Arc::new(SlowProver(Duration::from_secs(30))))
+        .with_store(ReceiptStore::open_sqlite(&path).expect("open store"))
+        .with_leader_election(LeaderElection::new("instance-a", ttl));
+    let second = engine()
+        .with_store(ReceiptStore::open_sqlite(&path).expect("open store"))
+        .with_leader_election(LeaderElection::new("instance-b", ttl));
+    assert_eq!(
+        first.role(),
+        EngineRole::Standby,
+        "nobody leads before campaigning"
+    );
+    assert_eq!(
+        first.campaign().await.expect("campaign"),
+        EngineRole::Leader
+    );
+    assert_eq!(
+        second.campaign().await.expect("campaign"),
+        EngineRole::Standby
+    );
+    assert_eq!(second.current_leader().as_deref(), Some("instance-a"));
+    assert_eq!(second.health().await.role, Some(EngineRole::Standby));
+    let err = second
+        .submit(base_order(850))
+        .await
+        .expect_err("standbys turn writes away");
+    assert!(err.to_string().contains("instance-a"), "{}", err);
+
+    // The leader's prover hangs, so the receipt is still pending when it steps down.
+    let receipt_id = first.submit(base_order(851)).await.expect("submit");
+    assert_eq!(
+        second.campaign().await.expect("campaign"),
+        EngineRole::Standby
+    );
+    let read = second
+        .get_receipt(&receipt_id)
+        .await
+        .expect("standby reads the leader's receipts");
+    assert_eq!(read.status, ReceiptStatus::PENDING);
+    first.step_down().await.expect("step down");
+    assert_eq!(first.in_flight_count(), 0);
+
+    assert_eq!(
+        second.campaign().await.expect("campaign"),
+        EngineRole::Leader
+    );
+    assert_eq!(
+        second
+            .leader_election()
+            .and_then(|e| e.lease())
+            .map(|l| l.epoch),
+        Some(2)
+    );
+    let proved = second
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        proved.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        proved.non_provable
+    );
+    assert_eq!(
+        first.campaign().await.expect("campaign"),
+        EngineRole::Standby
+    );
+    assert_eq!(
+        first.get_receipt(&receipt_id).await.map(|r| r.status),
+        Some(ReceiptStatus::PROVED)
+    );
+    assert!(first.submit(base_order(852)).await.is_err());
+
+    // HTTP writes to a standby answer 503 and name the leader.
+    let api = HttpApi::new(Arc::new(first));
+    let response = api
+        .route(&HttpRequest {
+            method: "POST".to_string(),
+            path: "/v1/receipts".to_string(),
+            query: None,
+            authorization: None,
+            peer: None,
+            body: serde_json::to_vec(&base_order(853)).expect("json"),
+            headers: vec![],
+        })
+        .await;
+    assert_eq!(response.status, 503);
+    assert_eq!(response.body["leader"], "instance-b");
+    drop((api, second));
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
+
+#[tokio::test]
+async fn leader_writes_are_fenced_on_the_lease_epoch_and_refreshes_read_only_changes() {
+    let path = std::env::temp_dir().join(format!("zkputer-fence-{}.sqlite", uuid::Uuid::new_v4()));
+    let source = engine();
+    let receipt_id = source.submit(base_order(854)).await.expect("submit");
+    let mut receipt = source
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    receipt.revision = 1;
+
+    // A leader whose lease was taken over cannot write, even before it notices it lost it.
+    let stale = SqliteReceiptBackend::open(&path).expect("open backend");
+    let current = SqliteReceiptBackend::open(&path).expect("open backend");
+    let ttl = Duration::from_millis(200);
+    let old = stale
+        .acquire_lease("receipt-engine", "instance-a", ttl, 1_000)
+        .expect("lease");
+    let new = current
+        .acquire_lease("receipt-engine", "instance-b", ttl, 2_000)
+        .expect("lease");
+    assert_eq!((old.epoch, new.epoch), (1, 2));
+    let err = stale
+        .compare_and_swap_receipt(&receipt, 0, Some(&old))
+        .expect_err("old tenure is fenced");
+    assert!(err.downcast_ref::<WriteFenced>().is_some(), "{}", err);
+    current
+        .compare_and_swap_receipt(&receipt, 0, Some(&new))
+        .expect("current tenure writes");
+
+    // Refreshes read back only what other stores wrote since the last one.
+    let reader = ReceiptStore::open_sqlite(&path).expect("open store");
+    assert_eq!(
+        reader.refresh().await.expect("refresh"),
+        0,
+        "nothing changed since it loaded"
+    );
+    let writer = ReceiptStore::open_sqlite(&path).expect("open store");
+    let mut invalidated = writer.get(&receipt_id).await.expect("stored");
+    invalidated.status = ReceiptStatus::INVALIDATED;
+    writer.compare_and_swap(invalidated).await.expect("write");
+    assert_eq!(reader.refresh().await.expect("refresh"), 1);
+    assert_eq!(
+        reader.get(&receipt_id).await.map(|r| r.status),
+        Some(ReceiptStatus::INVALIDATED)
+    );
+    assert_eq!(reader.refresh().await.expect("refresh"), 0);
+
+    // A campaign that fails steps down and says why in health until one succeeds.
+    let leader = engine()
+        .with_store(ReceiptStore::open_sqlite(&path).expect("open store"))
+        .with_leader_election(LeaderElection::new("instance-c", Duration::from_secs(30)));
+    assert_eq!(
+        leader.campaign().await.expect("campaign"),
+        EngineRole::Leader
+    );
+    let conn = rusqlite::Connection::open(&path).expect("open database");
+    conn.execute_batch("DROP TABLE leases")
+        .expect("drop leases");
+    leader
+        .campaign()
+        .await
+        .expect_err("the lease table is gone");
+    assert_eq!(leader.role(), EngineRole::Standby);
+    assert!(leader.submit(base_order(855)).await.is_err());
+    let issues = leader.health().await.issues;
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.starts_with("leader election failed")),
+        "{:?}",
+        issues
+    );
+    conn.execute_batch("CREATE TABLE leases (name TEXT PRIMARY KEY, holder TEXT NOT NULL, epoch INTEGER NOT NULL, expires_at_ms INTEGER NOT NULL)")
+        .expect("restore leases");
+    assert_eq!(
+        leader.campaign().await.expect("campaign"),
+        EngineRole::Leader
+    );
+    assert!(leader
+        .health()
+        .await
+        .issues
+        .iter()
+        .all(|issue| !issue.starts_with("leader election failed")));
+    drop((conn, leader, reader, writer, stale, current));
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
+
+#[tokio::test]
+async fn observation_journal_records_every_venue_response_in_a_signed_chain() {
+    let venue = FakeVenueServer::start().await.expect("fake venue");
+    let dir = std::env::temp_dir().join(format!("zkputer-journal-{}", uuid::Uuid::new_v4()));
+    let signer: Arc<dyn ReceiptSigner> =
+        Arc::new(Ed25519ReceiptSigner::from_seed("journal", [9u8; 32]));
+    let public_key = signer.identity().public_key.clone();
+    // One entry per file, so every response also rotates the journal.
+    let journal = ObservationJournal::open(&dir)
+        .expect("journal")
+        .with_signer(signer.clone())
+        .with_max_file_bytes(1);
+    let rpc = HttpJsonRpc::new(&format!("{}{}", venue.url(), FAKE_BASE_RPC_PATH))
+        .expect("rpc")
+        .with_journal(journal.clone());
+    let engine = ReceiptEngine::new(
+        vec![Arc::new(BaseEvmAdapter::new(Arc::new(rpc)))],
+        PolicyEngine::new(None).expect("policy"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    );
+
+    let router = "0x2626664c2603336e57b271c5c0b26f421741e481";
+    let fill = venue.base().submit_transaction(
+        "0x1111111111111111111111111111111111111111",
+        vec![SyntheticLog {
+            address: router.to_string(),
+            topics: vec![format!("0x{}", "ab".repeat(32))],
+            data: "0x01".to_string(),
+        }],
+        true,
+    );
+    venue.base().mine_empty(12);
+    let receipt_id = engine
+        .submit(evm_trade(&fill, router))
+        .await
+        .expect("submit");
+    let receipt = engine
+        .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+        .await
+        .expect("wait");
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    let entries = read_journal(&dir).expect("read");
+    assert_eq!(
+        entries.len(),
+        venue.requests().len(),
+        "every response is journaled, selected as evidence or not"
+    );
+    assert!(entries.len() > receipt.provenance.evidence_items.len());
+    assert!(entries
+        .iter()
+        .all(|entry| entry.source == "JSON-RPC" && entry.status.as_deref() == Some("200")));
+    assert!(entries
+        .iter()
+        .all(|entry| entry.request_hash.is_some() && entry.path.is_empty()));
+
+    // Failed answers are journaled too, and a reopened journal carries the chain on.
+    venue.respond(
+        CannedResponse::new("GET", &format!("{}/data/order/0xdead", FAKE_CLOB_PATH))
+            .with_status(503)
+            .with_body(serde_json::json!({ "error": "maintenance" })),
+    );
+    let reopened = ObservationJournal::open(&dir)
+        .expect("reopen")
+        .with_signer(signer)
+        .with_max_file_bytes(1);
+    let clob = HttpClobApi::new(&format!("{}{}", venue.url(), FAKE_CLOB_PATH))
+        .expect("clob")
+        .with_journal(reopened);
+    clob.get("/data/order/0xdead")
+        .await
+        .expect_err("venue is down");
+    let entries = read_journal(&dir).expect("read");
+    let outage = entries.last().expect("outage entry");
+    assert_eq!(
+        (outage.source.as_str(), outage.status.as_deref()),
+        ("CLOB API", Some("503"))
+    );
+    assert_eq!(outage.path, "/data/order/0xdead");
+    assert_eq!(outage.request_hash, None);
+    assert_eq!(
+        verify_journal(&dir, Some(&public_key)).expect("verify"),
+        entries.len()
+    );
+    let files: Vec<_> = std::fs::read_dir(&dir)
+        .expect("dir")
+        .map(|entry| entry.expect("entry").path())
+        .collect();
+    assert_eq!(files.len(), entries.len());
+    assert!(
+        verify_journal(&dir, Some(&"00".repeat(32))).is_err(),
+        "entries are signed by the journal's key"
+    );
+
+    // Dropping a file, or editing what an entry recorded, breaks the journal.
+    let mut sorted = files.clone();
+    sorted.sort();
+    let middle = std::fs::read_to_string(&sorted[1]).expect("read file");
+    std::fs::remove_file(&sorted[1]).expect("remove");
+    assert!(verify_journal(&dir, None).is_err());
+    let edited = middle.replacen(
+        &entries[1].response_hash,
+        &format!("0x{}", "00".repeat(32)),
+        1,
+    );
+    std::fs::write(&sorted[1], edited).expect("write");
+    assert!(verify_journal(&dir, None)
+        .unwrap_err()
+        .to_string()
+        .contains("altered"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn balance_claims_prove_a_floor_at_a_point_without_disclosing_the_balance() {
+    let venue = Arc::new(SyntheticVenueAdapter::new(Venue::Base));
+    let engine = ReceiptEngine::new(
+        vec![venue.clone() as Arc<dyn VenueAdapter>],
+        PolicyEngine::new(None).expect("policy should load"),
+        Arc::new(Sp1MvpProver),
+        OffchainVerifier::default(),
+    )
+    .with_statement_locales(vec![StatementLocale::Ja]);
+    venue.publish_balance_snapshot(BalanceSnapshot {
+        venue: Venue::Base,
+        account_ref: "acct-finality-995".to_string(),
+        asset: "USDC".to_string(),
+        balance: "1250000.123456".to_string(),
+        as_of: "2026-01-01T00:00:00Z".to_string(),
+        block: Some(24_000_000),
+        snapshot_ref: "balances-24000000".to_string(),
+    });
+    let settle = |min_balance: &str, point: serde_json::Value| {
+        let mut payload = serde_json::json!({ "asset": "USDC", "min_balance": min_balance });
+        payload
+            .as_object_mut()
+            .expect("object")
+            .extend(point.as_object().expect("object").clone());
+        let request = ProofRequest {
+            claim_type: ClaimType::BALANCE_AT_TIMESTAMP,
+            order_ref: "solvency-995".to_string(),
+            payload,
+            ..base_order(995)
+        };
+        let engine = &engine;
+        async move {
+            let receipt_id = engine.submit(request).await.expect("submit");
+            engine
+                .wait_for_receipt(&receipt_id, Duration::from_secs(5))
+                .await
+                .expect("wait")
+        }
+    };
+
+    let receipt = settle(
+        "1000000",
+        serde_json::json!({ "as_of": "2026-01-01T00:00:00.000Z" }),
+    )
+    .await;
+    assert_eq!(
+        receipt.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        receipt.non_provable
+    );
+    assert_eq!(
+        receipt.claim.statement,
+        "Account acct-finality-995 held at least 1000000 USDC on venue base as of 2026-01-01T00:00:00Z (venue balance snapshot balances-24000000)."
+    );
+    assert!(receipt.claim.localized_statements[0]
+        .text
+        .contains("1000000"));
+    let range: BalanceRange =
+        serde_json::from_value(receipt.claim.public_values["balance_range"].clone())
+            .expect("balance range");
+    assert_eq!(
+        (
+            range.asset.as_str(),
+            range.min_balance.as_str(),
+            range.block
+        ),
+        ("USDC", "1000000", None)
+    );
+    assert!(
+        !serde_json::to_string(&receipt)
+            .expect("json")
+            .contains("1250000"),
+        "the receipt never carries the balance"
+    );
+    assert_eq!(
+        receipt.proof.public_inputs_hash,
+        hash_json(&balance_range_public_inputs(
+            &receipt.claim.claim_hash,
+            &receipt.provenance.evidence_root,
+            Venue::Base,
+            &range,
+        ))
+    );
+    assert!(OffchainVerifier::default().verify(&receipt).await);
+    let mut widened = receipt.clone();
+    widened.claim.public_values["balance_range"]["min_balance"] = serde_json::json!("5000000");
+    assert!(
+        !OffchainVerifier::default().verify(&widened).await,
+        "a higher floor does not match the proof"
+    );
+
+    // Points can be named by block; the comparison is exact at any precision.
+    let by_block = settle(
+        "1250000.123456",
+        serde_json::json!({ "as_of": "2026-01-01T00:00:00Z", "block": 24_000_000 }),
+    )
+    .await;
+    assert_eq!(
+        by_block.status,
+        ReceiptStatus::PROVED,
+        "{:?}",
+        by_block.non_provable
+    );
+    assert_eq!(
+        by_block.claim.public_values["balance_range"]["block"].as_u64(),
+        Some(24_000_000)
+    );
+
+    let short = settle(
+        "1250000.1234561",
+        serde_json::json!({ "as_of": "2026-01-01T00:00:00Z" }),
+    )
+    .await;
+    let non_provable = short.non_provable.expect("non-provable");
+    assert_eq!(
+        non_provable.reason_code,
+        NonProvableReason::EVIDENCE_CONFLICT
+    );
+    assert!(
+        non_provable
+            .details
+            .contains("shows less than 1250000.1234561 USDC"),
+        "{}",
+        non_provable.details
+    );
+    let elsewhen = settle("1", serde_json::json!({ "as_of": "2026-01-02T00:00:00Z" })).await;
+    assert_eq!(
+        elsewhen.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::EVIDENCE_MISSING)
+    );
+    let malformed = settle(
+        "1e6",
+        serde_json::json!({ "as_of": "2026-01-01T00:00:00Z" }),
+    )
+    .await;
+    assert_eq!(
+        malformed.non_provable.map(|n| n.reason_code),
+        Some(NonProvableReason::SCHEMA_INVALID)
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_carry_configurable_safety_annotations() {
+    use zkputer::mcp::{McpFeatures, McpServer, ToolAnnotations};
+
+    async fn list_tools(server: &McpServer, version: &str) -> Vec<Value> {
+        let rpc = |method: &str, params: Value| {
+            serde_json::to_vec(&serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })).unwrap()
+        };
+        let mut session = server.session();
+        server
+            .handle(
+                &mut session,
+                &rpc(
+                    "initialize",
+                    serde_json::json!({ "protocolVersion": version }),
+                ),
+                &mut |_| Ok(()),
+            )
+            .await
+            .expect("initialized");
+        let listed = server
+            .handle(
+                &mut session,
+                &rpc("tools/list", serde_json::json!({})),
+                &mut |_| Ok(()),
+            )
+            .await
+            .expect("listed");
+        listed["result"]["tools"].as_array().expect("tools").clone()
+    }
+    let annotations = |tools: &[Value], name: &str| {
+        tools
+            .iter()
+            .find(|tool| tool["name"] == name)
+            .expect("tool")["annotations"]
+            .clone()
+    };
+
+    let server = McpServer::new(Arc::new(engine()), McpFeatures::default());
+    let tools = list_tools(&server, "2025-06-18").await;
+    assert!(
+        tools.iter().all(|tool| tool["annotations"].is_object()),
+        "every tool is annotated"
+    );
+    for name in [
+        "zkputer_get_receipt",
+        "zkputer_list_receipts",
+        "zkputer_verify_receipt",
+        "zkputer_get_stats",
+    ] {
+        assert_eq!(annotations(&tools, name)["readOnlyHint"], true, "{}", name);
+    }
+    let submit = annotations(&tools, "zkputer_verify_claim");
+    assert_eq!(
+        (
+            &submit["readOnlyHint"],
+            &submit["destructiveHint"],
+            &submit["idempotentHint"],
+            &submit["openWorldHint"]
+        ),
+        (
+            &Value::Bool(false),
+            &Value::Bool(false),
+            &Value::Bool(false),
+            &Value::Bool(true)
+        )
+    );
+    assert_eq!(annotations(&tools, "zkputer_verify_template"), submit);
+    assert_eq!(
+        zkputer::mcp::tool_definitions()["tools"]
+            .as_array()
+            .unwrap()
+            .len(),
+        tools.len()
+    );
+    let legacy = list_tools(&server, "2024-11-05").await;
+    assert!(
+        legacy.iter().all(|tool| tool.get("annotations").is_none()),
+        "annotations arrived in 2025-03-26"
+    );
+
+    // Operators can tighten or relax the hints per tool.
+    let configured = McpServer::new(Arc::new(engine()), McpFeatures::default())
+        .with_tool_annotation_overrides(&serde_json::json!({
+            "zkputer_verify_claim": { "destructiveHint": true },
+            "zkputer_export_dispute_bundle": { "readOnlyHint": true }
+        }))
+        .expect("overrides");
+    let tools = list_tools(&configured, "2025-03-26").await;
+    assert_eq!(
+        annotations(&tools, "zkputer_verify_claim")["destructiveHint"],
+        true
+    );
+    assert_eq!(
+        annotations(&tools, "zkputer_verify_claim")["openWorldHint"],
+        true,
+        "unnamed hints keep their defaults"
+    );
+    assert_eq!(
+        configured.tool_annotations("zkputer_export_dispute_bundle"),
+        Some(ToolAnnotations::READ_ONLY)
+    );
+    assert!(McpServer::new(Arc::new(engine()), McpFeatures::default())
+        .with_tool_annotation_overrides(
+            &serde_json::json!({ "zkputer_cancel": { "destructiveHint": true } })
+        )
+        .is_err());
+    assert!(
+        McpServer::new(Arc::new(engine()), McpFeatures::default())
+            .with_tool_annotation_overrides(
+                &serde_json::json!({ "zkputer_get_receipt": { "readonlyHint": false } })
+            )
+            .is_err(),
+        "misspelled hints are refused"
+    );
+}